@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use std::ffi::OsString;
+
+use crate::CliError;
+
+/// Convert iproute2 style global options like `-br` or `-4` to clap long
+/// options as clap only supports single character for single dash options.
+/// Only the options before the object name (e.g. `link`) are converted.
+///
+/// The iproute2 accepts single dash long options with any prefix of the
+/// option name, hence the `long_options` should be ordered like iproute2
+/// `main()` so that the first match wins on ambiguous prefix. The value
+/// given by `=`, e.g. `-color=auto`, is kept. The `short_options` are only
+/// converted on exact match, e.g. `-4` to `--family=inet`. The options in
+/// `options_with_value` take the next argument as value.
+pub fn normalize_args<I>(
+    args: I,
+    long_options: &[(&str, &str)],
+    short_options: &[(&str, &str)],
+    options_with_value: &[&str],
+) -> Vec<OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args = args.into_iter();
+    let mut ret: Vec<OsString> = args.next().into_iter().collect();
+
+    while let Some(arg) = args.next() {
+        let Some(opt) = arg.to_str() else {
+            ret.push(arg);
+            break;
+        };
+        let Some(name) = opt.strip_prefix('-') else {
+            ret.push(arg);
+            break;
+        };
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (name, None),
+        };
+        let opt = if let Some((_, short_opt)) = short_options
+            .iter()
+            .find(|(short_name, _)| *short_name == opt)
+        {
+            short_opt.to_string()
+        } else if name.len() > 1
+            && !name.starts_with('-')
+            && let Some((_, long_opt)) = long_options
+                .iter()
+                .find(|(long_name, _)| long_name.starts_with(name))
+        {
+            match value {
+                Some(value) => format!("{long_opt}={value}"),
+                None => long_opt.to_string(),
+            }
+        } else {
+            opt.to_string()
+        };
+        let takes_value = options_with_value.contains(&opt.as_str());
+        ret.push(opt.into());
+        if takes_value {
+            ret.extend(args.next());
+        }
+    }
+    ret.extend(args);
+    ret
+}
+
+/// Take the value of current option, equal to iproute2 `NEXT_ARG()`
+pub fn next_opt<'a>(
+    opts: &mut std::slice::Iter<'_, &'a str>,
+) -> Result<&'a str, CliError> {
+    opts.next().copied().ok_or_else(|| {
+        CliError::from("Command line is not complete. Try option \"help\"")
+    })
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+
+use std::ffi::OsString;
+
+use iproute_rs::CliError;
+
+// Ordered like iproute2 bridge `main()`, see `iproute_rs::normalize_args()`
+const IPROUTE2_LONG_OPTIONS: [(&str, &str); 11] = [
+    ("help", "--help"),
+    ("Version", "--Version"),
+    ("stats", "--stats"),
+    ("statistics", "--stats"),
+    ("oneline", "--oneline"),
+    ("timestamp", "--timestamp"),
+    ("compressvlans", "--compressvlans"),
+    ("json", "--json"),
+    ("pretty", "--pretty"),
+    ("tshort", "--tshort"),
+    ("color", "--color"),
+];
+
+/// Convert iproute2 style global options of `bridge` to clap long options
+pub(crate) fn normalize_args<I>(args: I) -> Vec<OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    iproute_rs::normalize_args(args, &IPROUTE2_LONG_OPTIONS, &[], &[])
+}
+
+/// Equal to iproute2 `invarg()`
+pub(crate) fn invarg(error_msg: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+/// Equal to iproute2 `duparg()`
+pub(crate) fn duparg(key: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("duplicate \"{key}\": \"{value}\" is the second value.")
+            .as_str(),
+    )
+}
+
+/// Equal to iproute2 `duparg2()`
+pub(crate) fn duparg2(key: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("either \"{key}\" is duplicate, or \"{value}\" is a garbage.")
+            .as_str(),
+    )
+}
+
+// Like iproute2 `get_unsigned()`, hexadecimal with `0x` prefix is supported
+pub(crate) fn parse_num<T: TryFrom<u64>>(value: &str) -> Option<T> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse::<u64>().ok()
+    }
+    .and_then(|v| T::try_from(v).ok())
+}
+
+/// Equal to `getservbyname(name, "udp")` on the `/etc/services` database
+pub(crate) fn udp_service_port(name: &str) -> Option<u16> {
+    let services = std::fs::read_to_string("/etc/services").ok()?;
+    services.lines().find_map(|line| {
+        let mut fields = line.split('#').next()?.split_whitespace();
+        let service = fields.next()?;
+        let (port, protocol) = fields.next()?.split_once('/')?;
+        (protocol == "udp"
+            && (service == name || fields.any(|alias| alias == name)))
+        .then(|| port.parse().ok())
+        .flatten()
+    })
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::CliError;
+
+use super::{
+    flush::handle_flush,
+    modify::{FdbModifyCmd, handle_modify},
+    show::{CliFdbEntry, handle_show},
+};
+
+pub(crate) const FDB_USAGE: &str = "\
+Usage: bridge fdb { add | append | del | replace } ADDR dev DEV
+              [ self ] [ master ] [ use ] [ router ] [ extern_learn ]
+              [ sticky ] [ local | static | dynamic ] [ vlan VID ]
+              { [ dst IPADDR ] [ port PORT] [ vni VNI ] | [ nhid NHID ] }
+\t       [ via DEV ] [ src_vni VNI ]
+       bridge fdb [ show [ br BRDEV ] [ brport DEV ] [ vlan VID ]
+              [ state STATE ] [ dynamic ] ]
+       bridge fdb flush dev DEV [ brport DEV ] [ vlan VID ]
+              [ self ] [ master ] [ [no]permanent | [no]static | [no]dynamic ]
+              [ [no]added_by_user ] [ [no]extern_learn ] [ [no]sticky ]
+              [ [no]offloaded ]";
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+pub(crate) struct FdbCommand;
+
+impl FdbCommand {
+    pub(crate) const CMD: &'static str = "fdb";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("forwarding database management")
+            .alias("fd")
+            .alias("f")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show forwarding database entries")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add new forwarding database entry")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("append")
+                    .about("append remote destination to existing entry")
+                    .alias("appen")
+                    .alias("appe")
+                    .alias("app")
+                    .alias("ap")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("replace")
+                    .about("add or change forwarding database entry")
+                    .alias("replac")
+                    .alias("repla")
+                    .alias("repl")
+                    .alias("rep")
+                    .alias("re")
+                    .alias("r")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete forwarding database entry")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("flush")
+                    .about("flush forwarding database entries")
+                    .alias("flus")
+                    .alias("flu")
+                    .alias("fl")
+                    .alias("f")
+                    .arg(gen_opts_arg()),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        handle: &rtnetlink::Handle,
+    ) -> Result<Option<Vec<CliFdbEntry>>, CliError> {
+        for (subcommand, cmd) in [
+            ("add", FdbModifyCmd::Add),
+            ("append", FdbModifyCmd::Append),
+            ("replace", FdbModifyCmd::Replace),
+            ("delete", FdbModifyCmd::Delete),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(subcommand) {
+                handle_modify(handle, &get_opts(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("flush") {
+            handle_flush(handle, &get_opts(matches)).await?;
+            return Ok(None);
+        }
+
+        let opts = match matches.subcommand_matches("show") {
+            Some(matches) => get_opts(matches),
+            None => Vec::new(),
+        };
+        handle_show(handle, &opts, matches.get_count("STATS") > 0)
+            .await
+            .map(Into::into)
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::DefaultNla,
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour::{
+            NeighbourAttribute, NeighbourFlags, NeighbourMessage,
+            NeighbourState,
+        },
+    },
+};
+
+use super::{
+    modify::fdb_request,
+    names::{
+        NDA_NDM_FLAGS_MASK, NDA_NDM_STATE_MASK, NTF_EXT_LEARNED, NTF_MASTER,
+        NTF_OFFLOADED, NTF_SELF, NTF_STICKY, NTF_USE, NUD_NOARP, NUD_PERMANENT,
+    },
+};
+use crate::{args::duparg2, iface::IfaceMap};
+
+// The `NLM_F_BULK` is not supported by netlink-packet-core yet
+const NLM_F_BULK: u16 = 0x200;
+
+const VLAN_N_VID: u16 = 4096;
+
+// The flags could be matched by `[no]<name>` selectors of `bridge fdb flush`
+const FLUSH_FLAG_NAMES: [(&str, u8); 4] = [
+    ("added_by_user", NTF_USE),
+    ("extern_learn", NTF_EXT_LEARNED),
+    ("sticky", NTF_STICKY),
+    ("offloaded", NTF_OFFLOADED),
+];
+
+/// Options of `bridge fdb flush` following the argument grammar of
+/// iproute2 `fdb_flush()`.
+#[derive(Debug, Default)]
+struct FdbFlushOptions<'a> {
+    dev: Option<&'a str>,
+    brport: Option<&'a str>,
+    vlan: Option<u16>,
+    state: u16,
+    state_mask: u16,
+    flags: u8,
+    flags_mask: u8,
+}
+
+impl<'a> FdbFlushOptions<'a> {
+    fn parse(opts: &[&'a str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => ret.dev = Some(next_opt(&mut opts)?),
+                "master" => ret.flags |= NTF_MASTER,
+                "self" => ret.flags |= NTF_SELF,
+                "permanent" => {
+                    ret.state |= NUD_PERMANENT;
+                    ret.state_mask |= NUD_PERMANENT;
+                }
+                "nopermanent" => {
+                    ret.state &= !NUD_PERMANENT;
+                    ret.state_mask |= NUD_PERMANENT;
+                }
+                "static" => {
+                    ret.state |= NUD_NOARP;
+                    ret.state_mask |= NUD_NOARP | NUD_PERMANENT;
+                }
+                "nostatic" => {
+                    ret.state &= !NUD_NOARP;
+                    ret.state_mask |= NUD_NOARP;
+                }
+                "dynamic" => {
+                    ret.state &= !NUD_NOARP | NUD_PERMANENT;
+                    ret.state_mask |= NUD_NOARP | NUD_PERMANENT;
+                }
+                "nodynamic" => {
+                    ret.state |= NUD_NOARP;
+                    ret.state_mask |= NUD_NOARP;
+                }
+                "brport" => {
+                    if ret.brport.is_some() {
+                        return Err(duparg2("brport", opt));
+                    }
+                    ret.brport = Some(next_opt(&mut opts)?);
+                }
+                "vlan" => {
+                    if ret.vlan.is_some() {
+                        return Err(duparg2("vlan", opt));
+                    }
+                    // Like iproute2 `atoi()`, invalid number means VLAN 0
+                    ret.vlan =
+                        Some(next_opt(&mut opts)?.parse().unwrap_or_default());
+                }
+                "help" => {
+                    next_opt(&mut opts)?;
+                }
+                _ => {
+                    let (name, set) = match opt.strip_prefix("no") {
+                        Some(name) => (name, false),
+                        None => (*opt, true),
+                    };
+                    // Like iproute2, unknown options are ignored
+                    if let Some((_, flag)) = FLUSH_FLAG_NAMES
+                        .iter()
+                        .find(|(flag_name, _)| *flag_name == name)
+                    {
+                        if set {
+                            ret.flags |= flag;
+                        } else {
+                            ret.flags &= !flag;
+                        }
+                        ret.flags_mask |= flag;
+                    }
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    fn to_nl_msg(
+        &self,
+        ifaces: &IfaceMap,
+    ) -> Result<NeighbourMessage, CliError> {
+        let Some(dev) = self.dev else {
+            return Err(CliError::from("Device is a required argument."));
+        };
+
+        let mut nl_msg = NeighbourMessage::default();
+        nl_msg.header.family = AddressFamily::Bridge;
+        nl_msg.header.ifindex = ifaces.name_to_index(dev).ok_or_else(|| {
+            CliError::from(
+                format!("Cannot find bridge device \"{dev}\"").as_str(),
+            )
+        })?;
+
+        if let Some(brport) = self.brport {
+            let index = ifaces.name_to_index(brport).ok_or_else(|| {
+                CliError::from(
+                    format!("Cannot find bridge port device \"{brport}\"")
+                        .as_str(),
+                )
+            })?;
+            nl_msg.attributes.push(NeighbourAttribute::IfIndex(index));
+        }
+
+        if let Some(vlan) = self.vlan {
+            if vlan >= VLAN_N_VID {
+                return Err(CliError::from(
+                    format!("Invalid VLAN ID \"{vlan}\"").as_str(),
+                ));
+            }
+            nl_msg.attributes.push(NeighbourAttribute::Vlan(vlan));
+        }
+
+        // Like iproute2, assume `self` when not defined
+        let mut flags = self.flags;
+        if flags & (NTF_SELF | NTF_MASTER) == 0 {
+            flags |= NTF_SELF;
+        }
+        nl_msg.header.flags = NeighbourFlags::from_bits_retain(flags);
+        nl_msg.header.state = NeighbourState::from(self.state);
+
+        if self.flags_mask != 0 {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Other(DefaultNla::new(
+                    NDA_NDM_FLAGS_MASK,
+                    vec![self.flags_mask],
+                )));
+        }
+        if self.state_mask != 0 {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Other(DefaultNla::new(
+                    NDA_NDM_STATE_MASK,
+                    self.state_mask.to_ne_bytes().to_vec(),
+                )));
+        }
+        Ok(nl_msg)
+    }
+}
+
+pub(crate) async fn handle_flush(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let options = FdbFlushOptions::parse(opts)?;
+    let ifaces = IfaceMap::new(handle).await?;
+    let nl_msg = options.to_nl_msg(&ifaces)?;
+
+    fdb_request(
+        handle,
+        RouteNetlinkMessage::DelNeighbour(nl_msg),
+        NLM_F_BULK,
+    )
+    .await
+}
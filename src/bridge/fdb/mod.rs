@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod flush;
+mod modify;
+mod names;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::FdbCommand,
+    show::{CliFdbEntry, FdbShowFilter},
+};
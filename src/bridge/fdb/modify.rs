@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NLM_F_ACK, NLM_F_APPEND, NLM_F_CREATE, NLM_F_EXCL,
+        NLM_F_REPLACE, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour::{
+            NeighbourAttribute, NeighbourFlags, NeighbourMessage,
+            NeighbourState,
+        },
+    },
+};
+
+use super::names::{
+    NDA_NH_ID, NTF_EXT_LEARNED, NTF_MASTER, NTF_ROUTER, NTF_SELF, NTF_STICKY,
+    NTF_USE, NUD_NOARP, NUD_PERMANENT, NUD_REACHABLE,
+};
+use crate::{
+    args::{duparg2, invarg, parse_num, udp_service_port},
+    iface::IfaceMap,
+};
+
+const ETH_ALEN: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FdbModifyCmd {
+    Add,
+    /// Add another remote destination to existing entry
+    Append,
+    /// Update existing entry or create new one
+    Replace,
+    Delete,
+}
+
+/// Options of `bridge fdb add`, `append`, `replace` and `del` following
+/// the argument grammar of iproute2 `fdb_modify()`.
+#[derive(Debug)]
+struct FdbModifyOptions<'a> {
+    addr: Option<&'a str>,
+    dev: Option<&'a str>,
+    dst: Option<IpAddr>,
+    nhid: u32,
+    port: u16,
+    vni: Option<u32>,
+    src_vni: Option<u32>,
+    via: Option<u32>,
+    vlan: Option<u16>,
+    state: u16,
+    flags: u8,
+}
+
+// Like iproute2 `strtoul()` check of `fdb_modify()`, the VNI is 24 bits
+fn parse_vni(value: &str, error_msg: &str) -> Result<u32, CliError> {
+    parse_num::<u32>(value)
+        .filter(|vni| vni >> 24 == 0)
+        .ok_or_else(|| invarg(error_msg, value))
+}
+
+impl<'a> FdbModifyOptions<'a> {
+    fn parse(opts: &[&'a str], ifaces: &IfaceMap) -> Result<Self, CliError> {
+        let mut ret = Self {
+            addr: None,
+            dev: None,
+            dst: None,
+            nhid: 0,
+            port: 0,
+            vni: None,
+            src_vni: None,
+            via: None,
+            vlan: None,
+            state: NUD_NOARP,
+            flags: 0,
+        };
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => ret.dev = Some(next_opt(&mut opts)?),
+                "dst" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.dst.is_some() {
+                        return Err(duparg2("dst", value));
+                    }
+                    ret.dst = Some(value.parse().map_err(|_| {
+                        CliError::from(
+                            format!(
+                                "any valid address is expected rather than \
+                                 \"{value}\"."
+                            )
+                            .as_str(),
+                        )
+                    })?);
+                }
+                "nhid" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.nhid = parse_num(value).ok_or_else(|| {
+                        invarg("\"id\" value is invalid", value)
+                    })?;
+                }
+                "port" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.port =
+                        if value.starts_with(|c: char| c.is_ascii_digit()) {
+                            parse_num(value)
+                        } else {
+                            udp_service_port(value)
+                        }
+                        .ok_or_else(|| invarg("invalid port", value))?;
+                }
+                "vni" => {
+                    ret.vni =
+                        Some(parse_vni(next_opt(&mut opts)?, "invalid VNI")?);
+                }
+                "src_vni" => {
+                    ret.src_vni = Some(parse_vni(
+                        next_opt(&mut opts)?,
+                        "invalid src VNI",
+                    )?);
+                }
+                "via" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.via =
+                        Some(ifaces.name_to_index(value).ok_or_else(|| {
+                            CliError::from(
+                                format!("Cannot find device \"{value}\"")
+                                    .as_str(),
+                            )
+                        })?);
+                }
+                "self" => ret.flags |= NTF_SELF,
+                "master" => ret.flags |= NTF_MASTER,
+                "router" => ret.flags |= NTF_ROUTER,
+                "local" | "permanent" => ret.state |= NUD_PERMANENT,
+                "temp" | "static" => ret.state |= NUD_REACHABLE,
+                "dynamic" => {
+                    ret.state |= NUD_REACHABLE;
+                    ret.state &= !NUD_NOARP;
+                }
+                "vlan" => {
+                    if ret.vlan.is_some() {
+                        return Err(duparg2("vlan", opt));
+                    }
+                    // Like iproute2 `atoi()`, invalid number means VLAN 0
+                    ret.vlan =
+                        Some(next_opt(&mut opts)?.parse().unwrap_or_default());
+                }
+                "use" => ret.flags |= NTF_USE,
+                "extern_learn" => ret.flags |= NTF_EXT_LEARNED,
+                "sticky" => ret.flags |= NTF_STICKY,
+                _ => {
+                    let addr = if *opt == "to" || *opt == "help" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if ret.addr.is_some() {
+                        return Err(duparg2("to", addr));
+                    }
+                    ret.addr = Some(addr);
+                }
+            }
+        }
+
+        // Like iproute2, assume `self` and `permanent` when not defined
+        if ret.flags & (NTF_SELF | NTF_MASTER) == 0 {
+            ret.flags |= NTF_SELF;
+        }
+        if ret.state & (NUD_PERMANENT | NUD_REACHABLE) == 0 {
+            ret.state |= NUD_PERMANENT;
+        }
+        Ok(ret)
+    }
+
+    fn to_nl_msg(
+        &self,
+        ifaces: &IfaceMap,
+    ) -> Result<NeighbourMessage, CliError> {
+        let (Some(dev), Some(addr)) = (self.dev, self.addr) else {
+            return Err(CliError::from(
+                "Device and address are required arguments.",
+            ));
+        };
+        if self.nhid != 0
+            && (self.dst.is_some() || self.port != 0 || self.vni.is_some())
+        {
+            return Err(CliError::from(
+                "dst, port, vni are mutually exclusive with nhid",
+            ));
+        }
+        let mac = parse_mac(addr).ok_or_else(|| {
+            CliError::from(format!("Invalid mac address {addr}").as_str())
+        })?;
+
+        let mut nl_msg = NeighbourMessage::default();
+        nl_msg.header.family = AddressFamily::Bridge;
+        nl_msg.header.state = NeighbourState::from(self.state);
+        nl_msg.header.flags = NeighbourFlags::from_bits_retain(self.flags);
+
+        nl_msg
+            .attributes
+            .push(NeighbourAttribute::LinkLocalAddress(mac));
+        if let Some(dst) = self.dst {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Destination(dst.into()));
+        }
+        if let Some(vlan) = self.vlan {
+            nl_msg.attributes.push(NeighbourAttribute::Vlan(vlan));
+        }
+        if self.nhid != 0 {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Other(DefaultNla::new(
+                    NDA_NH_ID,
+                    self.nhid.to_ne_bytes().to_vec(),
+                )));
+        }
+        if self.port != 0 {
+            nl_msg.attributes.push(NeighbourAttribute::Port(self.port));
+        }
+        if let Some(vni) = self.vni {
+            nl_msg.attributes.push(NeighbourAttribute::Vni(vni));
+        }
+        if let Some(src_vni) = self.src_vni {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::SourceVni(src_vni));
+        }
+        if let Some(via) = self.via {
+            nl_msg.attributes.push(NeighbourAttribute::IfIndex(via));
+        }
+
+        nl_msg.header.ifindex = ifaces.name_to_index(dev).ok_or_else(|| {
+            CliError::from(format!("Cannot find device \"{dev}\"").as_str())
+        })?;
+        Ok(nl_msg)
+    }
+}
+
+// Like iproute2 using `sscanf("%hhx:%hhx:%hhx:%hhx:%hhx:%hhx")`
+fn parse_mac(value: &str) -> Option<Vec<u8>> {
+    let ret: Vec<u8> = value
+        .split(':')
+        .take(ETH_ALEN)
+        .map_while(|byte| u8::from_str_radix(byte, 16).ok())
+        .collect();
+    (ret.len() == ETH_ALEN).then_some(ret)
+}
+
+/// Send the FDB request and wait for the kernel acknowledgement
+pub(super) async fn fdb_request(
+    handle: &rtnetlink::Handle,
+    msg: RouteNetlinkMessage,
+    flags: u16,
+) -> Result<(), CliError> {
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(msg),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK | flags;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn handle_modify(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    cmd: FdbModifyCmd,
+) -> Result<(), CliError> {
+    let ifaces = IfaceMap::new(handle).await?;
+    let nl_msg = FdbModifyOptions::parse(opts, &ifaces)?.to_nl_msg(&ifaces)?;
+
+    let (msg, flags) = match cmd {
+        FdbModifyCmd::Add => (
+            RouteNetlinkMessage::NewNeighbour(nl_msg),
+            NLM_F_CREATE | NLM_F_EXCL,
+        ),
+        FdbModifyCmd::Append => (
+            RouteNetlinkMessage::NewNeighbour(nl_msg),
+            NLM_F_CREATE | NLM_F_APPEND,
+        ),
+        FdbModifyCmd::Replace => (
+            RouteNetlinkMessage::NewNeighbour(nl_msg),
+            NLM_F_CREATE | NLM_F_REPLACE,
+        ),
+        FdbModifyCmd::Delete => (RouteNetlinkMessage::DelNeighbour(nl_msg), 0),
+    };
+    fdb_request(handle, msg, flags).await
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::CliError;
+
+use crate::args::{invarg, parse_num};
+
+pub(super) const NUD_REACHABLE: u16 = 0x02;
+pub(super) const NUD_STALE: u16 = 0x04;
+pub(super) const NUD_NOARP: u16 = 0x40;
+pub(super) const NUD_PERMANENT: u16 = 0x80;
+
+pub(super) const NTF_USE: u8 = 0x01;
+pub(super) const NTF_SELF: u8 = 0x02;
+pub(super) const NTF_MASTER: u8 = 0x04;
+pub(super) const NTF_EXT_LEARNED: u8 = 0x10;
+pub(super) const NTF_OFFLOADED: u8 = 0x20;
+pub(super) const NTF_STICKY: u8 = 0x40;
+pub(super) const NTF_ROUTER: u8 = 0x80;
+
+pub(super) const NTF_EXT_LOCKED: u32 = 0x2;
+
+// The `NDA_FLAGS_EXT`, `NDA_NH_ID` and the flush masks are not supported
+// by netlink-packet-route yet
+pub(super) const NDA_NH_ID: u16 = 13;
+pub(super) const NDA_FLAGS_EXT: u16 = 15;
+pub(super) const NDA_NDM_STATE_MASK: u16 = 16;
+pub(super) const NDA_NDM_FLAGS_MASK: u16 = 17;
+
+// Equal to the order of iproute2 `fdb_print_flags()`
+pub(super) const NTF_NAMES: [(u8, &str); 6] = [
+    (NTF_SELF, "self"),
+    (NTF_ROUTER, "router"),
+    (NTF_EXT_LEARNED, "extern_learn"),
+    (NTF_OFFLOADED, "offload"),
+    (NTF_MASTER, "master"),
+    (NTF_STICKY, "sticky"),
+];
+
+/// Equal to iproute2 `state_n2a()` of bridge fdb
+pub(super) fn fdb_state_to_name(state: u16) -> String {
+    if state & NUD_PERMANENT != 0 {
+        "permanent".to_string()
+    } else if state & NUD_NOARP != 0 {
+        "static".to_string()
+    } else if state & NUD_STALE != 0 {
+        "stale".to_string()
+    } else if state & NUD_REACHABLE != 0 {
+        String::new()
+    } else if state == 0 {
+        // C `printf("%#x")` has no `0x` prefix for zero
+        "state=0".to_string()
+    } else {
+        format!("state={state:#x}")
+    }
+}
+
+/// Equal to iproute2 `state_a2n()` of bridge fdb
+pub(super) fn parse_fdb_state(value: &str) -> Result<u16, CliError> {
+    Ok(match value {
+        "permanent" => NUD_PERMANENT,
+        "static" | "temp" => NUD_NOARP,
+        "stale" => NUD_STALE,
+        "reachable" | "dynamic" => NUD_REACHABLE,
+        "all" => u16::MAX,
+        _ => parse_num::<u32>(value)
+            .ok_or_else(|| invarg("invalid state", value))? as u16,
+    })
+}
@@ -0,0 +1,374 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, mac_to_string, next_opt,
+    write_with_color,
+};
+use rtnetlink::{
+    packet_core::Nla,
+    packet_route::{
+        AddressFamily,
+        neighbour::{NeighbourAddress, NeighbourAttribute, NeighbourMessage},
+    },
+};
+use serde::Serialize;
+
+use super::names::{
+    NDA_FLAGS_EXT, NDA_NH_ID, NTF_EXT_LOCKED, NTF_NAMES, NUD_PERMANENT,
+    fdb_state_to_name, parse_fdb_state,
+};
+use crate::{args::duparg, iface::IfaceMap};
+
+// Like iproute2 `get_user_hz()`, the cache info is in `USER_HZ` which is
+// always 100 on Linux
+const USER_HZ: u32 = 100;
+
+/// Equal to iproute2 `print_fdb()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliFdbEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ifname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dst: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vlan: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vni: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    src_vni: Option<u32>,
+    #[serde(rename = "viaIfIndex", skip_serializing_if = "Option::is_none")]
+    via_ifindex: Option<u32>,
+    #[serde(rename = "viaIf", skip_serializing_if = "Option::is_none")]
+    via_if: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nhid: Option<u32>,
+    #[serde(rename = "linkNetNsId", skip_serializing_if = "Option::is_none")]
+    link_netnsid: Option<i32>,
+    /// Seconds since last used, only shown with `-statistics`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    used: Option<u32>,
+    /// Seconds since last updated, only shown with `-statistics`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated: Option<u32>,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    master: Option<String>,
+    state: String,
+}
+
+impl std::fmt::Display for CliFdbEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(mac) = &self.mac {
+            write_with_color!(f, CliColor::Mac, "{mac}")?;
+            write!(f, " ")?;
+        }
+        if let Some(ifname) = &self.ifname {
+            write!(f, "dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{ifname}")?;
+            write!(f, " ")?;
+        }
+        if let Some(dst) = &self.dst {
+            let color = if dst.is_ipv4() {
+                CliColor::Ipv4Addr
+            } else {
+                CliColor::Ipv6Addr
+            };
+            write!(f, "dst ")?;
+            write_with_color!(f, color, "{dst}")?;
+            write!(f, " ")?;
+        }
+        if let Some(vlan) = self.vlan {
+            write!(f, "vlan {vlan} ")?;
+        }
+        if let Some(port) = self.port {
+            write!(f, "port {port} ")?;
+        }
+        if let Some(vni) = self.vni {
+            write!(f, "vni {vni} ")?;
+        }
+        if let Some(src_vni) = self.src_vni {
+            write!(f, "src_vni {src_vni} ")?;
+        }
+        if let Some(via_ifindex) = self.via_ifindex {
+            write!(f, "via ifindex {via_ifindex} ")?;
+        }
+        if let Some(via_if) = &self.via_if {
+            write!(f, "via {via_if} ")?;
+        }
+        if let Some(nhid) = self.nhid {
+            write!(f, "nhid {nhid} ")?;
+        }
+        if let Some(link_netnsid) = self.link_netnsid {
+            write!(f, "link-netnsid {link_netnsid} ")?;
+        }
+        if let (Some(used), Some(updated)) = (self.used, self.updated) {
+            write!(f, "used {used}/{updated} ")?;
+        }
+        for flag in &self.flags {
+            write!(f, "{flag} ")?;
+        }
+        if let Some(master) = &self.master {
+            write!(f, "master {master} ")?;
+        }
+        write!(f, "{}", self.state)
+    }
+}
+
+impl CanDisplay for CliFdbEntry {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliFdbEntry {}
+
+// Like iproute2, the `NDA_DST` of bridge family is IPv6 address when holding
+// 16 bytes, otherwise IPv4 address.
+fn fdb_dst_to_ip(addr: &NeighbourAddress) -> Option<IpAddr> {
+    match addr {
+        NeighbourAddress::Inet(v) => Some(IpAddr::V4(*v)),
+        NeighbourAddress::Inet6(v) => Some(IpAddr::V6(*v)),
+        NeighbourAddress::Other(v) => {
+            if let Ok(octets) = <[u8; 16]>::try_from(v.as_slice()) {
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            } else if let Ok(octets) = <[u8; 4]>::try_from(v.as_slice()) {
+                Some(IpAddr::V4(Ipv4Addr::from(octets)))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn nla_to_u32(nla: &impl Nla) -> Option<u32> {
+    let mut buf = [0u8; 4];
+    if nla.value_len() == buf.len() {
+        nla.emit_value(&mut buf);
+        Some(u32::from_ne_bytes(buf))
+    } else {
+        None
+    }
+}
+
+impl CliFdbEntry {
+    pub(crate) fn new(
+        nl_msg: &NeighbourMessage,
+        filter: &FdbShowFilter,
+        ifaces: &IfaceMap,
+        show_stats: bool,
+    ) -> Self {
+        let header = &nl_msg.header;
+        let mut ret = Self {
+            state: fdb_state_to_name(u16::from(header.state)),
+            ..Default::default()
+        };
+
+        if filter.index.is_none() && header.ifindex != 0 {
+            ret.ifname = Some(ifaces.index_to_name(header.ifindex));
+        }
+
+        let mut ext_flags = 0u32;
+        let mut via_ifindex = None;
+        for nla in nl_msg.attributes.iter() {
+            match nla {
+                NeighbourAttribute::LinkLocalAddress(v) => {
+                    ret.mac = Some(mac_to_string(v));
+                }
+                NeighbourAttribute::Destination(addr) => {
+                    ret.dst = fdb_dst_to_ip(addr);
+                }
+                NeighbourAttribute::Vlan(v) if *v != 0 => {
+                    ret.vlan = Some(*v);
+                }
+                NeighbourAttribute::Port(v) => ret.port = Some(*v),
+                NeighbourAttribute::Vni(v) => ret.vni = Some(*v),
+                NeighbourAttribute::SourceVni(v) => ret.src_vni = Some(*v),
+                NeighbourAttribute::IfIndex(v) => via_ifindex = Some(*v),
+                NeighbourAttribute::LinkNetNsId(v) => {
+                    ret.link_netnsid = Some(*v);
+                }
+                NeighbourAttribute::Controller(v) => {
+                    ret.master = Some(ifaces.index_to_name(*v));
+                }
+                NeighbourAttribute::CacheInfo(info) if show_stats => {
+                    ret.used = Some(info.used / USER_HZ);
+                    ret.updated = Some(info.updated / USER_HZ);
+                }
+                NeighbourAttribute::Other(nla) if nla.kind() == NDA_NH_ID => {
+                    ret.nhid = nla_to_u32(nla);
+                }
+                NeighbourAttribute::Other(nla)
+                    if nla.kind() == NDA_FLAGS_EXT =>
+                {
+                    ext_flags = nla_to_u32(nla).unwrap_or_default();
+                }
+                _ => (),
+            }
+        }
+
+        // Like iproute2, the ifindex is not resolved to name when it
+        // belongs to other network namespace
+        if let Some(index) = via_ifindex {
+            if ret.link_netnsid.is_some() {
+                ret.via_ifindex = Some(index);
+            } else {
+                ret.via_if = Some(ifaces.index_to_name(index));
+            }
+        }
+
+        let flags = header.flags.bits();
+        for (mask, name) in NTF_NAMES {
+            if flags & mask != 0 {
+                ret.flags.push(name.to_string());
+            }
+        }
+        if ext_flags & NTF_EXT_LOCKED != 0 {
+            ret.flags.push("locked".to_string());
+        }
+        ret
+    }
+}
+
+/// Filter of `bridge fdb show` following the argument grammar of iproute2
+/// `fdb_show()`
+#[derive(Debug, Default)]
+pub(crate) struct FdbShowFilter<'a> {
+    dev: Option<&'a str>,
+    br: Option<&'a str>,
+    index: Option<u32>,
+    br_index: Option<u32>,
+    vlan: u16,
+    state: u16,
+    dynamic: bool,
+}
+
+impl<'a> FdbShowFilter<'a> {
+    pub(crate) fn parse(opts: &[&'a str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" | "brport" => ret.dev = Some(next_opt(&mut opts)?),
+                "br" => ret.br = Some(next_opt(&mut opts)?),
+                "vlan" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.vlan != 0 {
+                        return Err(duparg("vlan", value));
+                    }
+                    // Like iproute2 `atoi()`, invalid number means no
+                    // filter on VLAN
+                    ret.vlan = value.parse().unwrap_or_default();
+                }
+                "state" => {
+                    ret.state |= parse_fdb_state(next_opt(&mut opts)?)?;
+                }
+                "dynamic" => ret.dynamic = true,
+                "help" => return Err(CliError::from(super::cli::FDB_USAGE)),
+                // Like iproute2, unknown options are ignored
+                _ => (),
+            }
+        }
+        Ok(ret)
+    }
+
+    pub(crate) fn resolve_dev(
+        &mut self,
+        ifaces: &IfaceMap,
+    ) -> Result<(), CliError> {
+        if let Some(br) = self.br {
+            self.br_index =
+                Some(ifaces.name_to_index(br).ok_or_else(|| {
+                    CliError::from(
+                        format!("Cannot find bridge device \"{br}\"").as_str(),
+                    )
+                })?);
+        }
+        if let Some(dev) = self.dev {
+            self.index = Some(ifaces.name_to_index(dev).ok_or_else(|| {
+                CliError::from(format!("Cannot find device \"{dev}\"").as_str())
+            })?);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn matches(
+        &self,
+        nl_msg: &NeighbourMessage,
+        ifaces: &IfaceMap,
+    ) -> bool {
+        let header = &nl_msg.header;
+        if header.family != AddressFamily::Bridge {
+            return false;
+        }
+        if self.index.is_some_and(|index| index != header.ifindex) {
+            return false;
+        }
+        // Like kernel `rtnl_fdb_dump()`, the bridge filter matches the
+        // bridge itself and its ports
+        if let Some(br_index) = self.br_index
+            && header.ifindex != br_index
+            && ifaces.controller(header.ifindex) != Some(br_index)
+        {
+            return false;
+        }
+        let state = u16::from(header.state);
+        if self.state != 0 && state & self.state == 0 {
+            return false;
+        }
+        if self.vlan != 0 {
+            let vlan = nl_msg
+                .attributes
+                .iter()
+                .find_map(|nla| match nla {
+                    NeighbourAttribute::Vlan(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            if vlan != self.vlan {
+                return false;
+            }
+        }
+        !(self.dynamic && state & NUD_PERMANENT != 0)
+    }
+}
+
+// The kernel only filters the FDB dump on device and bridge when netlink
+// strict checking is enabled, hence we filter the dump by ourselves.
+pub(crate) async fn dump_fdb(
+    handle: &rtnetlink::Handle,
+) -> Result<Vec<NeighbourMessage>, CliError> {
+    let mut request = handle.neighbours().get();
+    request.message_mut().header.family = AddressFamily::Bridge;
+
+    let mut ret = Vec::new();
+    let mut entries = request.execute();
+    while let Some(nl_msg) = entries.try_next().await? {
+        ret.push(nl_msg);
+    }
+    Ok(ret)
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    show_stats: bool,
+) -> Result<Vec<CliFdbEntry>, CliError> {
+    let mut filter = FdbShowFilter::parse(opts)?;
+    let ifaces = IfaceMap::new(handle).await?;
+    filter.resolve_dev(&ifaces)?;
+
+    Ok(dump_fdb(handle)
+        .await?
+        .iter()
+        .filter(|nl_msg| filter.matches(nl_msg, &ifaces))
+        .map(|nl_msg| CliFdbEntry::new(nl_msg, &filter, &ifaces, show_stats))
+        .collect())
+}
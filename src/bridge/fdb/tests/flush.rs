@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, PORT_NAME, VXLAN_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+const FLUSH_TEST_ENTRIES: [&[&str]; 5] = [
+    &["00:23:45:67:89:1a", "dev", PORT_NAME, "master", "static"],
+    &["00:23:45:67:89:1b", "dev", PORT_NAME, "master"],
+    &["00:23:45:67:89:1c", "dev", PORT_NAME, "master", "dynamic"],
+    &[
+        "00:23:45:67:89:1d",
+        "dev",
+        PORT_NAME,
+        "master",
+        "sticky",
+        "dynamic",
+    ],
+    &["00:23:45:67:89:1e", "dev", VXLAN_NAME, "dst", "192.0.2.2"],
+];
+
+// Like iproute2, `bridge fdb add` fails on existing entries, hence use
+// `replace` to restore the flushed entries.
+fn add_fdb_entries() {
+    for args in FLUSH_TEST_ENTRIES {
+        exec_cmd(&[&["bridge", "fdb", "replace"], args].concat());
+    }
+}
+
+fn get_fdb_entries() -> String {
+    [
+        exec_cmd(&["bridge", "fdb", "show", "dev", PORT_NAME]),
+        exec_cmd(&["bridge", "fdb", "show", "dev", VXLAN_NAME]),
+    ]
+    .concat()
+}
+
+// Flush the FDB by iproute2 and by us, the remaining entries should be
+// identical.
+fn assert_fdb_flush(args: &[&str]) {
+    add_fdb_entries();
+    exec_cmd(&[&["bridge", "fdb", "flush"], args].concat());
+    let expected_output = get_fdb_entries();
+
+    add_fdb_entries();
+    let output = bridge_rs_exec_cmd(&[&["fdb", "flush"], args].concat());
+    assert!(output.is_empty());
+    let our_output = get_fdb_entries();
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_fdb_flush() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["dev", BR_NAME][..],
+            &["dev", BR_NAME, "brport", PORT_NAME, "nosticky"][..],
+            &["dev", PORT_NAME, "master", "static"][..],
+            &["dev", PORT_NAME, "master", "nopermanent"][..],
+            &["dev", BR_NAME, "dynamic"][..],
+            &["dev", BR_NAME, "nodynamic", "nostatic"][..],
+            &["dev", BR_NAME, "added_by_user", "noextern_learn"][..],
+            &["dev", BR_NAME, "vlan", "1", "sticky"][..],
+            &["dev", VXLAN_NAME, "self"][..],
+        ] {
+            assert_fdb_flush(args);
+        }
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_fdb_flush_invalid_args() {
+    for (args, error) in [
+        (&["fdb", "flush"][..], "Device is a required argument."),
+        (
+            &["fdb", "flush", "dev", "fdbtest-absent0"][..],
+            "Cannot find bridge device \"fdbtest-absent0\"",
+        ),
+        (
+            &["fdb", "flush", "dev", "lo", "brport", "fdbtest-absent0"][..],
+            "Cannot find bridge port device \"fdbtest-absent0\"",
+        ),
+        (
+            &["fdb", "flush", "dev", "lo", "vlan", "5000"][..],
+            "Invalid VLAN ID \"5000\"",
+        ),
+        (
+            &["fdb", "flush", "dev", "lo", "brport", "lo", "brport", "lo"][..],
+            "either \"brport\" is duplicate, or \"brport\" is a garbage.",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success(), "{args:?} should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod flush;
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use crate::tests::exec_cmd;
+
+const BR_NAME: &str = "fdbtest-br0";
+const PORT_NAME: &str = "fdbtest-veth0";
+const VXLAN_NAME: &str = "fdbtest-vx0";
+
+// Bridge with a veth port and a standalone VXLAN interface for the remote
+// destination entries
+fn setup_bridge() {
+    let peer_name = format!("{PORT_NAME}p");
+    exec_cmd(&["ip", "link", "add", BR_NAME, "type", "bridge"]);
+    exec_cmd(&[
+        "ip", "link", "add", PORT_NAME, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", PORT_NAME, "master", BR_NAME]);
+    // Prevent the learned entries from changing the dump between commands
+    exec_cmd(&[
+        "ip",
+        "link",
+        "set",
+        PORT_NAME,
+        "type",
+        "bridge_slave",
+        "learning",
+        "off",
+    ]);
+    for iface in [BR_NAME, PORT_NAME, &peer_name] {
+        exec_cmd(&["ip", "link", "set", iface, "up"]);
+    }
+    exec_cmd(&[
+        "ip",
+        "link",
+        "add",
+        VXLAN_NAME,
+        "type",
+        "vxlan",
+        "id",
+        "42",
+        "dstport",
+        "4789",
+        "local",
+        "192.0.2.1",
+        "nolearning",
+    ]);
+}
+
+fn cleanup_bridge() {
+    for iface in [BR_NAME, PORT_NAME, VXLAN_NAME] {
+        exec_cmd(&["ip", "link", "del", iface]);
+    }
+}
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, PORT_NAME, VXLAN_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+// Apply the changes by iproute2 and by us, the resulting FDB should be
+// identical.
+fn assert_fdb_modify(
+    changes: &[&[&str]],
+    show_args: &[&str],
+    cleanups: &[&[&str]],
+) {
+    let show_args = [&["bridge", "fdb", "show"][..], show_args].concat();
+
+    for args in changes {
+        exec_cmd(&[&["bridge", "fdb"][..], args].concat());
+    }
+    let expected_output = exec_cmd(&show_args);
+    for args in cleanups {
+        exec_cmd(&[&["bridge", "fdb", "del"][..], args].concat());
+    }
+
+    for args in changes {
+        let output = bridge_rs_exec_cmd(&[&["fdb"][..], args].concat());
+        assert!(output.is_empty());
+    }
+    let our_output = exec_cmd(&show_args);
+    for args in cleanups {
+        exec_cmd(&[&["bridge", "fdb", "del"][..], args].concat());
+    }
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_fdb_modify() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        assert_fdb_modify(
+            &[
+                &["add", "00:23:45:67:89:1a", "dev", PORT_NAME, "master"],
+                &[
+                    "add",
+                    "to",
+                    "00:23:45:67:89:1b",
+                    "dev",
+                    PORT_NAME,
+                    "master",
+                    "static",
+                ],
+                &[
+                    "add",
+                    "00:23:45:67:89:1c",
+                    "dev",
+                    PORT_NAME,
+                    "master",
+                    "sticky",
+                    "dynamic",
+                ],
+                &["add", "00:23:45:67:89:1d", "dev", PORT_NAME, "self"],
+                &[
+                    "replace",
+                    "00:23:45:67:89:1a",
+                    "dev",
+                    PORT_NAME,
+                    "master",
+                    "temp",
+                ],
+            ],
+            &["dev", PORT_NAME],
+            &[
+                &["00:23:45:67:89:1a", "dev", PORT_NAME, "master"],
+                &["00:23:45:67:89:1b", "dev", PORT_NAME, "master"],
+                &["00:23:45:67:89:1c", "dev", PORT_NAME, "master"],
+                &["00:23:45:67:89:1d", "dev", PORT_NAME],
+            ],
+        );
+
+        assert_fdb_modify(
+            &[
+                &[
+                    "add",
+                    "00:00:00:00:00:00",
+                    "dev",
+                    VXLAN_NAME,
+                    "dst",
+                    "192.0.2.2",
+                ],
+                &[
+                    "append",
+                    "00:00:00:00:00:00",
+                    "dev",
+                    VXLAN_NAME,
+                    "dst",
+                    "192.0.2.3",
+                    "port",
+                    "4790",
+                    "vni",
+                    "0x64",
+                    "via",
+                    BR_NAME,
+                ],
+                &[
+                    "append",
+                    "00:00:00:00:00:00",
+                    "dev",
+                    VXLAN_NAME,
+                    "dst",
+                    "192.0.2.4",
+                ],
+                &[
+                    "del",
+                    "00:00:00:00:00:00",
+                    "dev",
+                    VXLAN_NAME,
+                    "dst",
+                    "192.0.2.4",
+                ],
+                &[
+                    "add",
+                    "00:23:45:67:89:1e",
+                    "dev",
+                    VXLAN_NAME,
+                    "dst",
+                    "192.0.2.5",
+                    "port",
+                    "domain",
+                    "self",
+                    "permanent",
+                ],
+            ],
+            &["dev", VXLAN_NAME],
+            &[
+                &["00:00:00:00:00:00", "dev", VXLAN_NAME],
+                &["00:23:45:67:89:1e", "dev", VXLAN_NAME],
+            ],
+        );
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_fdb_modify_invalid_args() {
+    for (args, error) in [
+        (
+            &["fdb", "add", "00:23:45:67:89:1a"][..],
+            "Device and address are required arguments.",
+        ),
+        (
+            &["fdb", "add", "zz", "dev", "lo"][..],
+            "Invalid mac address zz",
+        ),
+        (
+            &["fdb", "add", "00:23:45:67:89:1a", "dev", "fdbtest-absent0"][..],
+            "Cannot find device \"fdbtest-absent0\"",
+        ),
+        (
+            &["fdb", "add", "00:23:45:67:89:1a", "dev", "lo", "dst", "foo"][..],
+            "any valid address is expected rather than \"foo\".",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "dst",
+                "192.0.2.1",
+                "dst",
+                "192.0.2.2",
+            ][..],
+            "either \"dst\" is duplicate, or \"192.0.2.2\" is a garbage.",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "port",
+                "foo",
+            ][..],
+            "argument \"foo\" is wrong: invalid port",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "port",
+                "70000",
+            ][..],
+            "argument \"70000\" is wrong: invalid port",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "vni",
+                "0x1000000",
+            ][..],
+            "argument \"0x1000000\" is wrong: invalid VNI",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "src_vni",
+                "x",
+            ][..],
+            "argument \"x\" is wrong: invalid src VNI",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "nhid",
+                "1",
+                "dst",
+                "192.0.2.1",
+            ][..],
+            "dst, port, vni are mutually exclusive with nhid",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "00:23:45:67:89:1b",
+                "dev",
+                "lo",
+            ][..],
+            "either \"to\" is duplicate, or \"00:23:45:67:89:1b\" is a \
+             garbage.",
+        ),
+        (
+            &[
+                "fdb",
+                "add",
+                "00:23:45:67:89:1a",
+                "dev",
+                "lo",
+                "vlan",
+                "1",
+                "vlan",
+                "2",
+            ][..],
+            "either \"vlan\" is duplicate, or \"vlan\" is a garbage.",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success(), "{args:?} should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, PORT_NAME, VXLAN_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+fn add_fdb_entries() {
+    for args in [
+        &["00:23:45:67:89:1a", "dev", PORT_NAME, "master", "static"][..],
+        &["00:23:45:67:89:1b", "dev", PORT_NAME, "master"][..],
+        &["00:23:45:67:89:1c", "dev", PORT_NAME, "master", "dynamic"][..],
+        &[
+            "00:23:45:67:89:1d",
+            "dev",
+            PORT_NAME,
+            "master",
+            "sticky",
+            "dynamic",
+        ][..],
+        &["00:23:45:67:89:1e", "dev", PORT_NAME, "self"][..],
+        &["00:00:00:00:00:00", "dev", VXLAN_NAME, "dst", "192.0.2.2"][..],
+        &[
+            "00:23:45:67:89:1f",
+            "dev",
+            VXLAN_NAME,
+            "dst",
+            "192.0.2.3",
+            "port",
+            "4790",
+            "vni",
+            "100",
+            "via",
+            BR_NAME,
+        ][..],
+    ] {
+        exec_cmd(&[&["bridge", "fdb", "add"], args].concat());
+    }
+}
+
+#[test]
+fn test_fdb_show() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        add_fdb_entries();
+        for args in [
+            &[][..],
+            &["-j"][..],
+            &["-s"][..],
+            &["-statistics", "-j"][..],
+            &["-o"][..],
+        ] {
+            for show_args in [
+                &["fdb", "show", "br", BR_NAME][..],
+                &["fdb", "show", "dev", PORT_NAME][..],
+                &["fdb", "show", "brport", PORT_NAME, "state", "static"][..],
+                &["fdb", "show", "dev", PORT_NAME, "state", "permanent"][..],
+                &[
+                    "fdb", "show", "dev", PORT_NAME, "state", "stale", "state",
+                    "0x2",
+                ][..],
+                &["fdb", "show", "dev", PORT_NAME, "dynamic"][..],
+                &["fdb", "show", "dev", PORT_NAME, "vlan", "1"][..],
+                &["fdb", "show", "dev", VXLAN_NAME][..],
+                &["fdb", "list", "br", BR_NAME, "state", "all"][..],
+                &["f", "s", "br", BR_NAME, "foo"][..],
+            ] {
+                let expected_output =
+                    exec_cmd(&[&["bridge"], args, show_args].concat());
+                let our_output =
+                    bridge_rs_exec_cmd(&[args, show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_fdb_show_invalid_args() {
+    for (args, error) in [
+        (
+            &["fdb", "show", "br", "fdbtest-absent0"][..],
+            "Cannot find bridge device \"fdbtest-absent0\"",
+        ),
+        (
+            &["fdb", "show", "dev", "fdbtest-absent0"][..],
+            "Cannot find device \"fdbtest-absent0\"",
+        ),
+        (
+            &["fdb", "show", "state", "foo"][..],
+            "argument \"foo\" is wrong: invalid state",
+        ),
+        (
+            &["fdb", "show", "vlan", "1", "vlan", "2"][..],
+            "duplicate \"vlan\": \"2\" is the second value.",
+        ),
+        (
+            &["fdb", "show", "dev"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success(), "{args:?} should fail");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use futures_util::TryStreamExt;
+use iproute_rs::CliError;
+use rtnetlink::packet_route::link::{LinkAttribute, LinkFlags, LinkMessage};
+
+/// Interface names, controllers and flags indexed by interface index, like
+/// the iproute2 `ll_init_map()` cache.
+#[derive(Debug, Default)]
+pub(crate) struct IfaceMap {
+    names: HashMap<u32, String>,
+    controllers: HashMap<u32, u32>,
+    flags: HashMap<u32, LinkFlags>,
+}
+
+impl IfaceMap {
+    pub(crate) async fn new(
+        handle: &rtnetlink::Handle,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut links = handle.link().get().execute();
+        while let Some(nl_msg) = links.try_next().await? {
+            ret.remember(nl_msg);
+        }
+        Ok(ret)
+    }
+
+    fn remember(&mut self, nl_msg: LinkMessage) {
+        let index = nl_msg.header.index;
+        self.flags.insert(index, nl_msg.header.flags);
+        for nla in nl_msg.attributes {
+            match nla {
+                LinkAttribute::IfName(name) => {
+                    self.names.insert(index, name);
+                }
+                LinkAttribute::Controller(controller) => {
+                    self.controllers.insert(index, controller);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Like iproute2 `ll_link_get()`, query the interface missing from
+    /// cache. The failure is ignored as the interface might be deleted
+    /// already.
+    pub(crate) async fn fetch(
+        &mut self,
+        handle: &rtnetlink::Handle,
+        index: u32,
+    ) {
+        if index == 0 || self.names.contains_key(&index) {
+            return;
+        }
+        let mut links = handle.link().get().match_index(index).execute();
+        if let Ok(Some(nl_msg)) = links.try_next().await {
+            self.remember(nl_msg);
+        }
+    }
+
+    /// Equal to iproute2 `ll_index_to_name()`
+    pub(crate) fn index_to_name(&self, index: u32) -> String {
+        self.names
+            .get(&index)
+            .cloned()
+            .unwrap_or_else(|| format!("if{index}"))
+    }
+
+    /// Equal to iproute2 `ll_name_to_index()`, the `if<index>` format is
+    /// also supported.
+    pub(crate) fn name_to_index(&self, name: &str) -> Option<u32> {
+        self.names
+            .iter()
+            .find_map(|(index, ifname)| (ifname == name).then_some(*index))
+            .or_else(|| {
+                name.strip_prefix("if")?
+                    .parse()
+                    .ok()
+                    .filter(|i| self.names.contains_key(i))
+            })
+    }
+
+    pub(crate) fn controller(&self, index: u32) -> Option<u32> {
+        self.controllers.get(&index).copied()
+    }
+
+    /// Equal to iproute2 `ll_index_to_flags()`, the flags are not updated
+    /// once cached.
+    pub(crate) fn flags(&self, index: u32) -> LinkFlags {
+        self.flags.get(&index).copied().unwrap_or_default()
+    }
+}
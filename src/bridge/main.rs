@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MIT
+
+mod args;
+mod fdb;
+mod iface;
+mod monitor;
+mod stp;
+mod vlan;
+
+#[cfg(test)]
+mod tests;
+
+use std::io::IsTerminal;
+
+use iproute_rs::{
+    CliColor, CliError, CliTimestamp, OutputFormat, gen_output_string,
+    print_output, print_result_and_exit,
+};
+
+use self::{
+    args::normalize_args, fdb::FdbCommand, monitor::MonitorCommand,
+    vlan::VlanCommand,
+};
+
+fn gen_command() -> clap::Command {
+    clap::Command::new("bridge")
+        .version(clap::crate_version!())
+        .author(clap::crate_authors!())
+        .about("Bridge command line of rust-netlink")
+        .disable_help_flag(true)
+        // The `-V` is used by `-Version` like iproute2
+        .disable_version_flag(true)
+        .arg(
+            clap::Arg::new("HELP")
+                .long("help")
+                .help("Print help")
+                .action(clap::ArgAction::Help)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("VERSION")
+                .short('V')
+                .long("Version")
+                .help("Print Version")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("JSON")
+                .short('j')
+                .long("json")
+                .help("JSON output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("PRETTY")
+                .short('p')
+                .long("pretty")
+                .help("Pretty JSON output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("YAML")
+                .short('y')
+                .help("YAML output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("TIMESTAMP")
+                .short('t')
+                .long("timestamp")
+                .help("Print timestamp before output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("TSHORT")
+                .long("tshort")
+                .help("Print timestamp before output in short format")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("STATS")
+                .short('s')
+                .long("stats")
+                .visible_alias("statistics")
+                .help("Show statistics")
+                .action(clap::ArgAction::Count)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("ONELINE")
+                .short('o')
+                .long("oneline")
+                .help("Output each record on a single line")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("COMPRESSVLANS")
+                .short('c')
+                .long("compressvlans")
+                .help("Show VLAN ranges instead of individual VLANs")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        // Like iproute2, the `-c` is reserved for `-compressvlans`
+        .arg(
+            clap::Arg::new("COLOR")
+                .long("color")
+                .help("Colorful output, `-color` alone means always")
+                .action(clap::ArgAction::Set)
+                .value_parser(["always", "auto", "never"])
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("always")
+                .default_value("auto")
+                .global(true),
+        )
+        .subcommand(FdbCommand::gen_command())
+        .subcommand(VlanCommand::gen_command())
+        .subcommand(MonitorCommand::gen_command())
+}
+
+fn get_output_format(matches: &clap::ArgMatches) -> OutputFormat {
+    if matches.get_flag("JSON") && matches.get_flag("PRETTY") {
+        OutputFormat::PrettyJson
+    } else if matches.get_flag("JSON") {
+        OutputFormat::Json
+    } else if matches.get_flag("YAML") {
+        OutputFormat::Yaml
+    } else if matches.get_flag("ONELINE") {
+        OutputFormat::Oneline
+    } else {
+        OutputFormat::default()
+    }
+}
+
+async fn dispatch(
+    matches: &clap::ArgMatches,
+    handle: &rtnetlink::Handle,
+) -> Result<String, CliError> {
+    let fmt = get_output_format(matches);
+    if let Some(matches) = matches.subcommand_matches(FdbCommand::CMD) {
+        Ok(gen_output_string(
+            &FdbCommand::handle(matches, handle).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(VlanCommand::CMD) {
+        Ok(gen_output_string(
+            &VlanCommand::handle(matches, handle).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(MonitorCommand::CMD)
+    {
+        MonitorCommand::handle(matches, handle, fmt)
+            .await
+            .map(|()| String::new())
+    } else {
+        Err(CliError::from(
+            "Object is not specified. Try \"bridge help\"",
+        ))
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), CliError> {
+    let mut app = gen_command();
+    let args = normalize_args(std::env::args_os());
+    let matches = app
+        .try_get_matches_from_mut(&args)
+        .unwrap_or_else(|e| e.exit());
+
+    if let Some(color_str) = matches.get_one::<String>("COLOR")
+        && (color_str == "always"
+            || (color_str == "auto" && std::io::stdout().is_terminal()))
+    {
+        CliColor::enable();
+    }
+
+    if matches.get_flag("TSHORT") {
+        CliTimestamp::enable(true);
+    } else if matches.get_flag("TIMESTAMP") {
+        CliTimestamp::enable(false);
+    }
+
+    if matches.get_flag("VERSION") {
+        print_result_and_exit(
+            Ok(app.render_version().to_string()),
+            get_output_format(&matches),
+        );
+    } else if matches.subcommand().is_some() {
+        let (connection, handle, _) = rtnetlink::new_connection()?;
+        tokio::spawn(connection);
+
+        let result = dispatch(&matches, &handle).await;
+        print_output(&result);
+        if let Err(e) = result {
+            std::process::exit(e.code);
+        }
+    } else {
+        app.print_help()?;
+        println!();
+    }
+
+    Ok(())
+}
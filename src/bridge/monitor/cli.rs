@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, OutputFormat};
+
+use super::listen::handle_monitor;
+
+pub(crate) const MONITOR_USAGE: &str =
+    "Usage: bridge monitor [link | fdb | mdb | vlan | all | label]";
+
+pub(crate) struct MonitorCommand;
+
+impl MonitorCommand {
+    pub(crate) const CMD: &'static str = "monitor";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("monitor bridge objects")
+            .alias("monito")
+            .alias("monit")
+            .alias("moni")
+            .alias("mon")
+            .alias("mo")
+            .arg(
+                clap::Arg::new("options")
+                    .action(clap::ArgAction::Append)
+                    .trailing_var_arg(true),
+            )
+    }
+
+    /// The monitor prints events by itself as they arrive, hence nothing is
+    /// returned.
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        handle: &rtnetlink::Handle,
+        fmt: OutputFormat,
+    ) -> Result<(), CliError> {
+        let opts: Vec<&str> = matches
+            .get_many::<String>("options")
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_monitor(handle, &opts, fmt, matches.get_count("STATS") > 0).await
+    }
+}
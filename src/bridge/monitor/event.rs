@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use rtnetlink::packet_route::{
+    AddressFamily, RouteNetlinkMessage, neighbour::NeighbourAttribute,
+};
+use serde::Serialize;
+
+use super::{
+    link::CliBridgeLink,
+    mdb::{BridgeMdbMessage, CliMonitorMdb, RTM_DELMDB, RTM_NEWMDB},
+    message::BridgeMonitorMessage,
+};
+use crate::{
+    fdb::{CliFdbEntry, FdbShowFilter},
+    iface::IfaceMap,
+    vlan::{BridgeVlanMessage, CliVlanPort, RTM_DELVLAN, RTM_NEWVLAN},
+};
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliMonitorObject {
+    Link(CliBridgeLink),
+    Fdb(CliFdbEntry),
+    Mdb(CliMonitorMdb),
+    Vlan(CliVlanPort),
+}
+
+impl std::fmt::Display for CliMonitorObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Link(v) => write!(f, "{v}"),
+            Self::Fdb(v) => write!(f, "{v}"),
+            Self::Mdb(v) => write!(f, "{v}"),
+            Self::Vlan(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl CliMonitorObject {
+    // Equal to the labels of iproute2 bridge `accept_msg()`
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Link(_) => "[LINK]",
+            Self::Fdb(_) => "[NEIGH]",
+            Self::Mdb(_) => "[MDB]",
+            Self::Vlan(_) => "[VLAN]",
+        }
+    }
+}
+
+/// The netlink notification received by `bridge monitor`
+#[derive(Serialize)]
+pub(crate) struct CliMonitorEvent {
+    /// Prefix the event with the object label like `[LINK]`
+    #[serde(skip)]
+    show_label: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deleted: bool,
+    #[serde(flatten)]
+    object: CliMonitorObject,
+}
+
+impl std::fmt::Display for CliMonitorEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.show_label {
+            write!(f, "{}", self.object.label())?;
+        }
+        if self.deleted {
+            write!(f, "Deleted ")?;
+        }
+        write!(f, "{}", self.object)
+    }
+}
+
+impl CanDisplay for CliMonitorEvent {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliMonitorEvent {}
+
+async fn fetch_ifaces(
+    handle: &rtnetlink::Handle,
+    ifaces: &mut IfaceMap,
+    indexes: &[u32],
+) {
+    for index in indexes {
+        ifaces.fetch(handle, *index).await;
+    }
+}
+
+fn decode_error(e: impl std::fmt::Display) -> CliError {
+    CliError::from(format!("{e}").as_str())
+}
+
+/// Convert the netlink notification to event, `None` for message not
+/// interested. Like iproute2 `ll_index_to_name()`, the interfaces missing
+/// from cache are queried from kernel.
+pub(crate) async fn parse_nl_msg_to_event(
+    nl_msg: BridgeMonitorMessage,
+    handle: &rtnetlink::Handle,
+    ifaces: &mut IfaceMap,
+    show_label: bool,
+    show_stats: bool,
+) -> Result<Option<CliMonitorEvent>, CliError> {
+    let deleted = match &nl_msg {
+        BridgeMonitorMessage::Route(nl_msg) => matches!(
+            nl_msg,
+            RouteNetlinkMessage::DelLink(_)
+                | RouteNetlinkMessage::DelNeighbour(_)
+        ),
+        BridgeMonitorMessage::Raw { message_type, .. } => {
+            matches!(*message_type, RTM_DELMDB | RTM_DELVLAN)
+        }
+    };
+    let object = match nl_msg {
+        BridgeMonitorMessage::Route(RouteNetlinkMessage::NewLink(m))
+        | BridgeMonitorMessage::Route(RouteNetlinkMessage::DelLink(m)) => {
+            fetch_ifaces(handle, ifaces, &CliBridgeLink::ifindexes(&m)).await;
+            CliMonitorObject::Link(CliBridgeLink::new(&m, ifaces))
+        }
+        BridgeMonitorMessage::Route(RouteNetlinkMessage::NewNeighbour(m))
+        | BridgeMonitorMessage::Route(RouteNetlinkMessage::DelNeighbour(m)) => {
+            // Like iproute2 `print_fdb()`, only bridge family is shown
+            if m.header.family != AddressFamily::Bridge {
+                return Ok(None);
+            }
+            let mut indexes = vec![m.header.ifindex];
+            indexes.extend(m.attributes.iter().filter_map(|nla| match nla {
+                NeighbourAttribute::Controller(i)
+                | NeighbourAttribute::IfIndex(i) => Some(*i),
+                _ => None,
+            }));
+            fetch_ifaces(handle, ifaces, &indexes).await;
+            CliMonitorObject::Fdb(CliFdbEntry::new(
+                &m,
+                &FdbShowFilter::default(),
+                ifaces,
+                show_stats,
+            ))
+        }
+        BridgeMonitorMessage::Raw {
+            message_type: RTM_NEWMDB | RTM_DELMDB,
+            payload,
+        } => {
+            let m = BridgeMdbMessage::parse(&payload).map_err(decode_error)?;
+            fetch_ifaces(handle, ifaces, &m.ifindexes()).await;
+            let Some(mdb) = CliMonitorMdb::new(&m, ifaces) else {
+                return Ok(None);
+            };
+            CliMonitorObject::Mdb(mdb)
+        }
+        BridgeMonitorMessage::Raw {
+            message_type: RTM_NEWVLAN | RTM_DELVLAN,
+            payload,
+        } => {
+            let m = BridgeVlanMessage::parse(&payload).map_err(decode_error)?;
+            fetch_ifaces(handle, ifaces, &[m.header.ifindex]).await;
+            let Some(port) = CliVlanPort::new(&m, ifaces) else {
+                return Ok(None);
+            };
+            CliMonitorObject::Vlan(port)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(CliMonitorEvent {
+        show_label,
+        deleted,
+        object,
+    }))
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliColor, write_with_color};
+use rtnetlink::{
+    packet_core::Nla,
+    packet_route::link::{LinkAttribute, LinkFlags, LinkMessage},
+};
+use serde::Serialize;
+
+use crate::{iface::IfaceMap, stp::stp_state_to_name};
+
+// Equal to `linux/if_link.h`
+const IFLA_BRPORT_STATE: u16 = 1;
+const IFLA_BRPORT_PRIORITY: u16 = 2;
+const IFLA_BRPORT_COST: u16 = 3;
+
+// Equal to the order of iproute2 bridge `print_link_flags()`
+const LINK_FLAGS_NAMES: [(LinkFlags, &str); 18] = [
+    (LinkFlags::Loopback, "LOOPBACK"),
+    (LinkFlags::Broadcast, "BROADCAST"),
+    (LinkFlags::Pointopoint, "POINTOPOINT"),
+    (LinkFlags::Multicast, "MULTICAST"),
+    (LinkFlags::Noarp, "NOARP"),
+    (LinkFlags::Allmulti, "ALLMULTI"),
+    (LinkFlags::Promisc, "PROMISC"),
+    (LinkFlags::Notrailers, "NOTRAILERS"),
+    (LinkFlags::Debug, "DEBUG"),
+    (LinkFlags::Dynamic, "DYNAMIC"),
+    (LinkFlags::Automedia, "AUTOMEDIA"),
+    (LinkFlags::Portsel, "PORTSEL"),
+    (LinkFlags::Controller, "MASTER"),
+    (LinkFlags::Port, "SLAVE"),
+    (LinkFlags::Up, "UP"),
+    (LinkFlags::LowerUp, "LOWER_UP"),
+    (LinkFlags::Dormant, "DORMANT"),
+    (LinkFlags::Echo, "ECHO"),
+];
+
+/// Equal to iproute2 bridge `print_link_flags()`, the `M-DOWN` means the
+/// lower interface is down.
+fn link_flags_to_names(mut flags: LinkFlags, lower_down: bool) -> Vec<String> {
+    let mut ret = Vec::new();
+    if flags.contains(LinkFlags::Up) && !flags.contains(LinkFlags::Running) {
+        ret.push("NO-CARRIER".to_string());
+    }
+    flags.remove(LinkFlags::Running);
+    for (flag, name) in LINK_FLAGS_NAMES {
+        if flags.contains(flag) {
+            flags.remove(flag);
+            ret.push(name.to_string());
+        }
+    }
+    if !flags.is_empty() {
+        ret.push(format!("{:x}", flags.bits()));
+    }
+    if lower_down {
+        ret.push("M-DOWN".to_string());
+    }
+    ret
+}
+
+fn nla_to_u32(nla: &impl Nla) -> Option<u32> {
+    let mut buf = vec![0u8; nla.value_len()];
+    nla.emit_value(&mut buf);
+    match buf.len() {
+        1 => Some(u32::from(buf[0])),
+        2 => Some(u32::from(u16::from_ne_bytes([buf[0], buf[1]]))),
+        4 => Some(u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]])),
+        _ => None,
+    }
+}
+
+/// Equal to iproute2 bridge `print_linkinfo()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliBridgeLink {
+    ifindex: u32,
+    ifname: String,
+    /// The lower interface shown as `ifname@link`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mtu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    master: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<u32>,
+}
+
+impl CliBridgeLink {
+    /// The interface indexes to resolve for showing this message
+    pub(crate) fn ifindexes(nl_msg: &LinkMessage) -> Vec<u32> {
+        let has_netnsid = nl_msg
+            .attributes
+            .iter()
+            .any(|attr| matches!(attr, LinkAttribute::LinkNetNsId(_)));
+        nl_msg
+            .attributes
+            .iter()
+            .filter_map(|attr| match attr {
+                LinkAttribute::Link(i) if !has_netnsid => Some(*i),
+                LinkAttribute::Controller(i) => Some(*i),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn new(nl_msg: &LinkMessage, ifaces: &IfaceMap) -> Self {
+        let index = nl_msg.header.index;
+        let mut ret = Self {
+            ifindex: index,
+            ifname: ifaces.index_to_name(index),
+            ..Default::default()
+        };
+        let mut iflink = None;
+        let mut has_netnsid = false;
+        for attr in nl_msg.attributes.iter() {
+            match attr {
+                LinkAttribute::IfName(name) => ret.ifname = name.to_string(),
+                LinkAttribute::Link(i) => iflink = Some(*i),
+                LinkAttribute::LinkNetNsId(_) => has_netnsid = true,
+                LinkAttribute::Mtu(v) => ret.mtu = Some(*v),
+                LinkAttribute::Controller(i) => {
+                    ret.master = Some(ifaces.index_to_name(*i));
+                }
+                LinkAttribute::ProtoInfoBridge(infos) => {
+                    for info in infos {
+                        let value = nla_to_u32(info);
+                        match info.kind() {
+                            IFLA_BRPORT_STATE => {
+                                ret.state =
+                                    value.map(|v| stp_state_to_name(v as u8));
+                            }
+                            IFLA_BRPORT_PRIORITY => ret.priority = value,
+                            IFLA_BRPORT_COST => ret.cost = value,
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Equal to iproute2 `print_name_and_link()`
+        let mut lower_down = false;
+        ret.link = match iflink {
+            Some(0) => Some("NONE".to_string()),
+            Some(i) if has_netnsid => Some(format!("if{i}")),
+            Some(i) => {
+                lower_down = !ifaces.flags(i).contains(LinkFlags::Up);
+                Some(ifaces.index_to_name(i))
+            }
+            None => None,
+        };
+        ret.flags = link_flags_to_names(nl_msg.header.flags, lower_down);
+        ret
+    }
+}
+
+impl std::fmt::Display for CliBridgeLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.ifindex)?;
+        match &self.link {
+            Some(link) => write_with_color!(
+                f,
+                CliColor::IfaceName,
+                "{}@{link}",
+                self.ifname
+            )?,
+            None => {
+                write_with_color!(f, CliColor::IfaceName, "{}", self.ifname)?
+            }
+        }
+        write!(f, ": <{}> ", self.flags.join(","))?;
+        if let Some(mtu) = self.mtu {
+            write!(f, "mtu {mtu} ")?;
+        }
+        if let Some(master) = &self.master {
+            write!(f, "master {master} ")?;
+        }
+        if let Some(state) = &self.state {
+            write!(f, "state {state} ")?;
+        }
+        if let Some(priority) = self.priority {
+            write!(f, "priority {priority} ")?;
+        }
+        if let Some(cost) = self.cost {
+            write!(f, "cost {cost} ")?;
+        }
+        Ok(())
+    }
+}
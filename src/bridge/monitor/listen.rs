@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, OutputFormat, gen_output_string, print_output};
+use rtnetlink::{
+    packet_core::NetlinkPayload,
+    sys::{AsyncSocket, protocols::NETLINK_ROUTE},
+};
+
+use super::{
+    cli::MONITOR_USAGE, event::parse_nl_msg_to_event,
+    message::BridgeMonitorMessage,
+};
+use crate::iface::IfaceMap;
+
+const RTNLGRP_LINK: u32 = 1;
+const RTNLGRP_NEIGH: u32 = 3;
+const RTNLGRP_MDB: u32 = 26;
+const RTNLGRP_BRVLAN: u32 = 33;
+
+/// The objects to monitor following the argument grammar of iproute2 bridge
+/// `do_monitor()`
+#[derive(Debug, Default)]
+struct MonitorOptions {
+    link: bool,
+    fdb: bool,
+    mdb: bool,
+    vlan: bool,
+    /// Prefix the events with object label
+    label: bool,
+}
+
+impl MonitorOptions {
+    fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        // Like iproute2, the `all` discards the objects specified before it
+        // while the objects specified after `all` discard it.
+        let mut all = true;
+
+        for opt in opts {
+            match *opt {
+                "link" => {
+                    ret.link = true;
+                    all = false;
+                }
+                "fdb" => {
+                    ret.fdb = true;
+                    all = false;
+                }
+                "mdb" => {
+                    ret.mdb = true;
+                    all = false;
+                }
+                "vlan" => {
+                    ret.vlan = true;
+                    all = false;
+                }
+                "all" => {
+                    all = true;
+                    ret.label = true;
+                }
+                "label" => ret.label = true,
+                "help" => return Err(CliError::from(MONITOR_USAGE)),
+                _ => {
+                    return Err(CliError::from(
+                        format!(
+                            "Argument \"{opt}\" is unknown, try \"bridge \
+                             monitor help\"."
+                        )
+                        .as_str(),
+                    ));
+                }
+            }
+        }
+        // Like iproute2, the VLAN is not included by default as
+        // `RTNLGRP_BRVLAN` is beyond the legacy multicast groups bitmask.
+        if all {
+            ret.link = true;
+            ret.fdb = true;
+            ret.mdb = true;
+        }
+        Ok(ret)
+    }
+
+    fn groups(&self) -> Vec<u32> {
+        let mut ret = Vec::new();
+        if self.link {
+            ret.push(RTNLGRP_LINK);
+        }
+        if self.fdb {
+            ret.push(RTNLGRP_NEIGH);
+        }
+        if self.mdb {
+            ret.push(RTNLGRP_MDB);
+        }
+        if self.vlan {
+            ret.push(RTNLGRP_BRVLAN);
+        }
+        ret
+    }
+}
+
+/// Print the netlink notifications of specified objects until interrupted
+pub(crate) async fn handle_monitor(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    fmt: OutputFormat,
+    show_stats: bool,
+) -> Result<(), CliError> {
+    let monitor_opts = MonitorOptions::parse(opts)?;
+
+    // The `RTM_NEWMDB` and `RTM_NEWVLAN` are not supported by
+    // `rtnetlink::new_connection()`, hence use our own message type.
+    let (mut connection, _, mut messages) = rtnetlink::proto::new_connection::<
+        BridgeMonitorMessage,
+    >(NETLINK_ROUTE)?;
+    for group in monitor_opts.groups() {
+        connection.socket_mut().socket_mut().add_membership(group)?;
+    }
+    tokio::spawn(connection);
+
+    let mut ifaces = IfaceMap::new(handle).await?;
+
+    while let Some((nl_msg, _)) = messages.next().await {
+        let NetlinkPayload::InnerMessage(nl_msg) = nl_msg.payload else {
+            continue;
+        };
+        if let Some(event) = parse_nl_msg_to_event(
+            nl_msg,
+            handle,
+            &mut ifaces,
+            monitor_opts.label,
+            show_stats,
+        )
+        .await?
+        {
+            print_output(&Ok(gen_output_string(&event, fmt)));
+        }
+    }
+    Ok(())
+}
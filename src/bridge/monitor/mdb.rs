@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use iproute_rs::{CliColor, mac_to_string, write_with_color};
+use rtnetlink::packet_core::{
+    DecodeError, ErrorContext, NlasIterator, parse_u16, parse_u32,
+};
+use serde::Serialize;
+
+use crate::iface::IfaceMap;
+
+// Equal to `linux/rtnetlink.h`
+pub(crate) const RTM_NEWMDB: u16 = 84;
+pub(crate) const RTM_DELMDB: u16 = 85;
+
+// Equal to `linux/if_bridge.h`
+const MDBA_MDB: u16 = 1;
+const MDBA_ROUTER: u16 = 2;
+const MDBA_MDB_ENTRY: u16 = 1;
+const MDBA_MDB_ENTRY_INFO: u16 = 1;
+const MDBA_MDB_EATTR_SOURCE: u16 = 2;
+const MDBA_ROUTER_PORT: u16 = 1;
+
+const MDB_PERMANENT: u8 = 1;
+
+const MDB_FLAGS_NAMES: [(u8, &str); 4] = [
+    (1 << 0, "offload"),
+    (1 << 1, "fast_leave"),
+    (1 << 2, "added_by_star_ex"),
+    (1 << 3, "blocked"),
+];
+
+const ETH_P_IP: u16 = 0x0800;
+const ETH_ALEN: usize = 6;
+
+// The size of `struct br_port_msg`
+const BR_PORT_MSG_LEN: usize = 8;
+// The aligned size of `struct br_mdb_entry`
+const BR_MDB_ENTRY_LEN: usize = 28;
+// The offset of `addr.proto` in `struct br_mdb_entry`
+const BR_MDB_ENTRY_PROTO_OFFSET: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MdbGroup {
+    Ip(IpAddr),
+    Mac([u8; ETH_ALEN]),
+}
+
+impl MdbGroup {
+    fn color(&self) -> CliColor {
+        match self {
+            Self::Ip(IpAddr::V4(_)) => CliColor::Ipv4Addr,
+            Self::Ip(IpAddr::V6(_)) => CliColor::Ipv6Addr,
+            Self::Mac(_) => CliColor::Clear,
+        }
+    }
+}
+
+impl std::fmt::Display for MdbGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "{ip}"),
+            Self::Mac(mac) => write!(f, "{}", mac_to_string(mac)),
+        }
+    }
+}
+
+/// Equal to kernel `struct br_mdb_entry` with `MDBA_MDB_EATTR_SOURCE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BridgeMdbEntry {
+    ifindex: u32,
+    state: u8,
+    flags: u8,
+    vid: u16,
+    group: MdbGroup,
+    source: Option<IpAddr>,
+}
+
+impl BridgeMdbEntry {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < BR_MDB_ENTRY_PROTO_OFFSET + 2 {
+            return Err(DecodeError::from(format!(
+                "Invalid br_mdb_entry length {}",
+                buf.len()
+            )));
+        }
+        let proto = u16::from_be_bytes([
+            buf[BR_MDB_ENTRY_PROTO_OFFSET],
+            buf[BR_MDB_ENTRY_PROTO_OFFSET + 1],
+        ]);
+        let addr = &buf[8..BR_MDB_ENTRY_PROTO_OFFSET];
+        let group = if proto == 0 {
+            let mut mac = [0u8; ETH_ALEN];
+            mac.copy_from_slice(&addr[..ETH_ALEN]);
+            MdbGroup::Mac(mac)
+        } else if proto == ETH_P_IP {
+            let mut octets = [0u8; 4];
+            octets.copy_from_slice(&addr[..4]);
+            MdbGroup::Ip(IpAddr::V4(Ipv4Addr::from(octets)))
+        } else {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(addr);
+            MdbGroup::Ip(IpAddr::V6(Ipv6Addr::from(octets)))
+        };
+
+        let mut source = None;
+        if buf.len() > BR_MDB_ENTRY_LEN {
+            for nla in NlasIterator::new(&buf[BR_MDB_ENTRY_LEN..]) {
+                let nla = nla.context("invalid MDBA_MDB_ENTRY_INFO")?;
+                if nla.kind() == MDBA_MDB_EATTR_SOURCE {
+                    source = bytes_to_ip(nla.value());
+                }
+            }
+        }
+
+        Ok(Self {
+            ifindex: parse_u32(&buf[..4])?,
+            state: buf[4],
+            flags: buf[5],
+            vid: parse_u16(&buf[6..8])?,
+            group,
+            source,
+        })
+    }
+}
+
+fn bytes_to_ip(data: &[u8]) -> Option<IpAddr> {
+    if let Ok(octets) = <[u8; 4]>::try_from(data) {
+        Some(IpAddr::V4(Ipv4Addr::from(octets)))
+    } else if let Ok(octets) = <[u8; 16]>::try_from(data) {
+        Some(IpAddr::V6(Ipv6Addr::from(octets)))
+    } else {
+        None
+    }
+}
+
+/// The `RTM_NEWMDB` and `RTM_DELMDB` notifications
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BridgeMdbMessage {
+    /// Interface index of bridge
+    pub(crate) ifindex: u32,
+    entries: Vec<BridgeMdbEntry>,
+    router_ports: Vec<u32>,
+}
+
+impl BridgeMdbMessage {
+    pub(crate) fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < BR_PORT_MSG_LEN {
+            return Err(DecodeError::from(format!(
+                "Invalid br_port_msg length {}",
+                buf.len()
+            )));
+        }
+        let mut ret = Self {
+            ifindex: parse_u32(&buf[4..BR_PORT_MSG_LEN])?,
+            ..Default::default()
+        };
+        for nla in NlasIterator::new(&buf[BR_PORT_MSG_LEN..]) {
+            let nla = nla.context("invalid bridge mdb attribute")?;
+            match nla.kind() {
+                MDBA_MDB => {
+                    for entry in NlasIterator::new(nla.value()) {
+                        let entry = entry.context("invalid MDBA_MDB")?;
+                        if entry.kind() != MDBA_MDB_ENTRY {
+                            continue;
+                        }
+                        for info in NlasIterator::new(entry.value()) {
+                            let info =
+                                info.context("invalid MDBA_MDB_ENTRY")?;
+                            if info.kind() == MDBA_MDB_ENTRY_INFO {
+                                ret.entries
+                                    .push(BridgeMdbEntry::parse(info.value())?);
+                            }
+                        }
+                    }
+                }
+                // Like iproute2, only the first router port is shown
+                MDBA_ROUTER => {
+                    if let Some(port) = NlasIterator::new(nla.value()).next() {
+                        let port = port.context("invalid MDBA_ROUTER")?;
+                        if port.kind() == MDBA_ROUTER_PORT {
+                            ret.router_ports.push(
+                                parse_u32(
+                                    port.value().get(..4).unwrap_or_default(),
+                                )
+                                .context("invalid MDBA_ROUTER_PORT")?,
+                            );
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(ret)
+    }
+
+    /// The interface indexes to resolve for showing this message
+    pub(crate) fn ifindexes(&self) -> Vec<u32> {
+        let mut ret = vec![self.ifindex];
+        ret.extend(self.entries.iter().map(|e| e.ifindex));
+        ret.extend(self.router_ports.iter().copied());
+        ret
+    }
+}
+
+/// Equal to iproute2 `print_mdb_entry()`
+#[derive(Serialize)]
+pub(crate) struct CliMdbEntry {
+    index: u32,
+    dev: String,
+    port: String,
+    grp: String,
+    #[serde(skip)]
+    grp_color: CliColor,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    src: Option<IpAddr>,
+    state: String,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vid: Option<u16>,
+}
+
+impl std::fmt::Display for CliMdbEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "dev ")?;
+        write_with_color!(f, CliColor::IfaceName, "{}", self.dev)?;
+        write!(f, " port {} grp ", self.port)?;
+        write_with_color!(f, self.grp_color, "{}", self.grp)?;
+        if let Some(src) = &self.src {
+            let color = if src.is_ipv4() {
+                CliColor::Ipv4Addr
+            } else {
+                CliColor::Ipv6Addr
+            };
+            write!(f, " src ")?;
+            write_with_color!(f, color, "{src}")?;
+        }
+        write!(f, " {}", self.state)?;
+        for flag in &self.flags {
+            write!(f, " {flag}")?;
+        }
+        if let Some(vid) = self.vid {
+            write!(f, " vid {vid}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The router port notification, equal to iproute2 `print_router_entries()`
+/// for monitor.
+#[derive(Serialize)]
+pub(crate) struct CliMdbRouter {
+    dev: String,
+    port: String,
+}
+
+impl std::fmt::Display for CliMdbRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "router port dev {} master {}", self.port, self.dev)
+    }
+}
+
+/// Equal to iproute2 `print_mdb_mon()`
+#[derive(Serialize)]
+pub(crate) struct CliMonitorMdb {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    mdb: Vec<CliMdbEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    router: Vec<CliMdbRouter>,
+}
+
+impl CliMonitorMdb {
+    /// Return `None` when holding neither group nor router port
+    pub(crate) fn new(
+        nl_msg: &BridgeMdbMessage,
+        ifaces: &IfaceMap,
+    ) -> Option<Self> {
+        let dev = ifaces.index_to_name(nl_msg.ifindex);
+        let mdb: Vec<CliMdbEntry> = nl_msg
+            .entries
+            .iter()
+            .map(|entry| CliMdbEntry {
+                index: nl_msg.ifindex,
+                dev: dev.clone(),
+                port: ifaces.index_to_name(entry.ifindex),
+                grp: entry.group.to_string(),
+                grp_color: entry.group.color(),
+                src: entry.source,
+                state: if entry.state & MDB_PERMANENT != 0 {
+                    "permanent"
+                } else {
+                    "temp"
+                }
+                .to_string(),
+                flags: MDB_FLAGS_NAMES
+                    .iter()
+                    .filter(|(mask, _)| entry.flags & mask != 0)
+                    .map(|(_, name)| name.to_string())
+                    .collect(),
+                vid: (entry.vid != 0).then_some(entry.vid),
+            })
+            .collect();
+        let router: Vec<CliMdbRouter> = nl_msg
+            .router_ports
+            .iter()
+            .map(|port| CliMdbRouter {
+                dev: dev.clone(),
+                port: ifaces.index_to_name(*port),
+            })
+            .collect();
+        if mdb.is_empty() && router.is_empty() {
+            None
+        } else {
+            Some(Self { mdb, router })
+        }
+    }
+}
+
+impl std::fmt::Display for CliMonitorMdb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .mdb
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.router.iter().map(ToString::to_string))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
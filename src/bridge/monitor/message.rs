@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::{
+    packet_core::{
+        DecodeError, NetlinkDeserializable, NetlinkHeader, NetlinkSerializable,
+    },
+    packet_route::RouteNetlinkMessage,
+};
+
+use super::mdb::{RTM_DELMDB, RTM_NEWMDB};
+use crate::vlan::{RTM_DELVLAN, RTM_NEWVLAN};
+
+/// The rtnetlink notification received by `bridge monitor`. The message
+/// types unknown to [RouteNetlinkMessage] like `RTM_NEWMDB` are kept raw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BridgeMonitorMessage {
+    Route(RouteNetlinkMessage),
+    Raw { message_type: u16, payload: Vec<u8> },
+}
+
+impl NetlinkSerializable for BridgeMonitorMessage {
+    fn message_type(&self) -> u16 {
+        match self {
+            Self::Route(msg) => msg.message_type(),
+            Self::Raw { message_type, .. } => *message_type,
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        match self {
+            Self::Route(msg) => msg.buffer_len(),
+            Self::Raw { payload, .. } => payload.len(),
+        }
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Route(msg) => msg.serialize(buffer),
+            Self::Raw { payload, .. } => buffer.copy_from_slice(payload),
+        }
+    }
+}
+
+impl NetlinkDeserializable for BridgeMonitorMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        let raw = || Self::Raw {
+            message_type: header.message_type,
+            payload: payload.to_vec(),
+        };
+        Ok(match header.message_type {
+            RTM_NEWMDB | RTM_DELMDB | RTM_NEWVLAN | RTM_DELVLAN => raw(),
+            // The message not decodable is ignored by monitor
+            _ => RouteNetlinkMessage::deserialize(header, payload)
+                .map(Self::Route)
+                .unwrap_or_else(|_| raw()),
+        })
+    }
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod event;
+mod link;
+mod listen;
+mod mdb;
+mod message;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::MonitorCommand;
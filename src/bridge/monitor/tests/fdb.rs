@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_bridge, filter_events, monitor_both, setup_bridge};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_fdb() {
+    let br_name = "mntest-br2";
+    let port_name = "mntest-veth2";
+    let _lock = lock_net_test();
+    setup_bridge(br_name, port_name);
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["monitor", "fdb"], || {
+                for entry in [
+                    &["00:23:45:67:89:3a", "master", "static"][..],
+                    &["00:23:45:67:89:3b", "master", "static", "vlan", "1"][..],
+                    &["00:23:45:67:89:3c", "self", "permanent"][..],
+                ] {
+                    exec_cmd(
+                        &[
+                            &["bridge", "fdb", "add"],
+                            entry,
+                            &["dev", port_name],
+                        ]
+                        .concat(),
+                    );
+                    exec_cmd(
+                        &[
+                            &["bridge", "fdb", "del"],
+                            entry,
+                            &["dev", port_name],
+                        ]
+                        .concat(),
+                    );
+                }
+            });
+        let expected_events = filter_events(&expected_output, "00:23:45:67:89");
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, "00:23:45:67:89")
+        );
+    });
+
+    cleanup_bridge(br_name, port_name);
+    assert!(result.is_ok());
+}
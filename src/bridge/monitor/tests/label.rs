@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_bridge, filter_events, monitor_both, setup_bridge};
+use crate::tests::{exec_cmd, lock_net_test};
+
+fn change_port(port_name: &str) {
+    exec_cmd(&[
+        "bridge",
+        "fdb",
+        "add",
+        "00:23:45:67:89:4a",
+        "dev",
+        port_name,
+        "master",
+        "static",
+    ]);
+    exec_cmd(&[
+        "bridge",
+        "fdb",
+        "del",
+        "00:23:45:67:89:4a",
+        "dev",
+        port_name,
+        "master",
+        "static",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "set",
+        port_name,
+        "type",
+        "bridge_slave",
+        "cost",
+        "30",
+    ]);
+}
+
+#[test]
+fn test_monitor_label() {
+    let br_name = "mntest-br4";
+    let port_name = "mntest-veth4";
+    let _lock = lock_net_test();
+    setup_bridge(br_name, port_name);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["monitor", "all"][..],
+            &["monitor", "all", "link", "fdb"][..],
+        ] {
+            let (expected_output, our_output) =
+                monitor_both(args, || change_port(port_name));
+            let expected_events = filter_events(&expected_output, port_name);
+
+            assert!(expected_events.iter().any(|e| e.starts_with("[LINK]")));
+            assert!(expected_events.iter().any(|e| e.starts_with("[NEIGH]")));
+            pretty_assertions::assert_eq!(
+                expected_events,
+                filter_events(&our_output, port_name)
+            );
+        }
+    });
+
+    cleanup_bridge(br_name, port_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_monitor_tshort() {
+    let br_name = "mntest-br5";
+    let port_name = "mntest-veth5";
+    let _lock = lock_net_test();
+    setup_bridge(br_name, port_name);
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["--tshort", "monitor", "link"], || {
+                change_port(port_name)
+            });
+        let strip_timestamp = |output: &str| -> Vec<String> {
+            filter_events(output, port_name)
+                .iter()
+                .map(|e| {
+                    let (timestamp, event) =
+                        e.split_once("] ").expect("No timestamp prefix found");
+                    assert!(timestamp.starts_with('['));
+                    event.to_string()
+                })
+                .collect()
+        };
+        let expected_events = strip_timestamp(&expected_output);
+
+        assert!(!expected_events.is_empty());
+        pretty_assertions::assert_eq!(
+            expected_events,
+            strip_timestamp(&our_output)
+        );
+    });
+
+    cleanup_bridge(br_name, port_name);
+    assert!(result.is_ok());
+}
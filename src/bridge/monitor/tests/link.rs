@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT
+
+use super::{filter_events, monitor_both};
+use crate::tests::{bridge_rs_exec_cmd_output, exec_cmd, lock_net_test};
+
+fn change_links(br_name: &str, port_name: &str) {
+    let peer_name = format!("{port_name}p");
+    exec_cmd(&["ip", "link", "add", br_name, "type", "bridge"]);
+    exec_cmd(&[
+        "ip", "link", "add", port_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", port_name, "master", br_name]);
+    exec_cmd(&["ip", "link", "set", port_name, "up"]);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "set",
+        port_name,
+        "type",
+        "bridge_slave",
+        "priority",
+        "10",
+        "cost",
+        "20",
+    ]);
+    exec_cmd(&["ip", "link", "set", port_name, "nomaster"]);
+    exec_cmd(&["ip", "link", "del", port_name]);
+    exec_cmd(&["ip", "link", "del", br_name]);
+}
+
+#[test]
+fn test_monitor_link() {
+    let br_name = "mntest-br0";
+    let port_name = "mntest-veth0";
+    let _lock = lock_net_test();
+
+    let (expected_output, our_output) =
+        monitor_both(&["monitor", "link"], || change_links(br_name, port_name));
+    let expected_events = filter_events(&expected_output, "mntest-");
+
+    assert!(expected_events.iter().any(|e| e.contains(" priority 10 ")));
+    assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+    pretty_assertions::assert_eq!(
+        expected_events,
+        filter_events(&our_output, "mntest-")
+    );
+}
+
+#[test]
+fn test_monitor_link_json() {
+    let br_name = "mntest-br1";
+    let port_name = "mntest-veth1";
+    let _lock = lock_net_test();
+
+    let (_, our_output) = monitor_both(&["-j", "monitor", "link"], || {
+        change_links(br_name, port_name)
+    });
+    let events: Vec<serde_json::Value> = our_output
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("Invalid JSON line"))
+        .filter(|e: &serde_json::Value| e["ifname"] == port_name)
+        .collect();
+
+    assert!(events.len() >= 2);
+    assert!(events[0].get("deleted").is_none());
+    assert!(events.iter().any(|e| e["master"] == br_name));
+    assert_eq!(events.last().unwrap()["deleted"], true);
+}
+
+#[test]
+fn test_monitor_invalid_args() {
+    for (args, error) in [
+        (
+            &["monitor", "foo"][..],
+            "Argument \"foo\" is unknown, try \"bridge monitor help\".",
+        ),
+        (&["monitor", "help"][..], "Usage: bridge monitor"),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_bridge, filter_events, monitor_both, setup_bridge};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_mdb() {
+    let br_name = "mntest-br3";
+    let port_name = "mntest-veth3";
+    let _lock = lock_net_test();
+    setup_bridge(br_name, port_name);
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["monitor", "mdb"], || {
+                for grp in [
+                    &["239.1.1.1", "permanent"][..],
+                    &["ff0e::1", "temp"][..],
+                    &["01:00:5e:01:01:01", "permanent"][..],
+                ] {
+                    let args =
+                        [&["dev", br_name, "port", port_name, "grp"][..], grp]
+                            .concat();
+                    exec_cmd(&[&["bridge", "mdb", "add"][..], &args].concat());
+                    exec_cmd(&[&["bridge", "mdb", "del"][..], &args].concat());
+                }
+                for mcast_router in ["2", "1"] {
+                    exec_cmd(&[
+                        "ip",
+                        "link",
+                        "set",
+                        port_name,
+                        "type",
+                        "bridge_slave",
+                        "mcast_router",
+                        mcast_router,
+                    ]);
+                }
+            });
+        let expected_events =
+            filter_events(&expected_output, &format!("port {port_name} "))
+                .into_iter()
+                .chain(filter_events(
+                    &expected_output,
+                    &format!("router port dev {port_name} "),
+                ))
+                .collect::<Vec<String>>();
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        assert!(expected_events.iter().any(|e| e.contains("router port")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, &format!("port {port_name} "))
+                .into_iter()
+                .chain(filter_events(
+                    &our_output,
+                    &format!("router port dev {port_name} "),
+                ))
+                .collect::<Vec<String>>()
+        );
+    });
+
+    cleanup_bridge(br_name, port_name);
+    assert!(result.is_ok());
+}
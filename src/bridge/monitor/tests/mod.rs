@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod fdb;
+#[cfg(test)]
+mod label;
+#[cfg(test)]
+mod link;
+#[cfg(test)]
+mod mdb;
+#[cfg(test)]
+mod vlan;
+
+use std::{io::Read, process::Child, time::Duration};
+
+use crate::tests::{bridge_rs_spawn, exec_cmd};
+
+// Wait for the monitors subscribing the netlink groups
+const MONITOR_WAIT: Duration = Duration::from_millis(500);
+
+/// Bridge with a veth port which is up
+fn setup_bridge(br_name: &str, port_name: &str) {
+    let peer_name = format!("{port_name}p");
+    exec_cmd(&["ip", "link", "add", br_name, "type", "bridge"]);
+    exec_cmd(&[
+        "ip", "link", "add", port_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", port_name, "master", br_name]);
+    for iface in [br_name, port_name, &peer_name] {
+        exec_cmd(&["ip", "link", "set", iface, "up"]);
+    }
+}
+
+fn cleanup_bridge(br_name: &str, port_name: &str) {
+    exec_cmd(&["ip", "link", "del", port_name]);
+    exec_cmd(&["ip", "link", "del", br_name]);
+}
+
+/// Start both iproute2 and bridge-rs monitor, then run the changes and
+/// return their outputs
+fn monitor_both(args: &[&str], changes: impl FnOnce()) -> (String, String) {
+    // The iproute2 does not flush stdout for MDB and VLAN events
+    let expected = std::process::Command::new("stdbuf")
+        .args([&["-oL", "bridge"], args].concat())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn iproute2 monitor");
+    let ours = bridge_rs_spawn(args);
+    std::thread::sleep(MONITOR_WAIT);
+
+    changes();
+
+    std::thread::sleep(MONITOR_WAIT);
+    (stop_monitor(expected), stop_monitor(ours))
+}
+
+fn stop_monitor(mut child: Child) -> String {
+    child.kill().ok();
+    child.wait().ok();
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output).ok();
+    }
+    output
+}
+
+/// Only keep the events containing the keyword as other tests might change
+/// the system in parallel. The event starts with line without indent.
+fn filter_events(output: &str, keyword: &str) -> Vec<String> {
+    let mut events: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with(' ') {
+            if let Some(event) = events.last_mut() {
+                event.push('\n');
+                event.push_str(line);
+            }
+        } else {
+            events.push(line.to_string());
+        }
+    }
+    events.retain(|e| e.contains(keyword));
+    events
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_bridge, filter_events, monitor_both, setup_bridge};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_vlan() {
+    let br_name = "mntest-br6";
+    let port_name = "mntest-veth6";
+    let _lock = lock_net_test();
+    setup_bridge(br_name, port_name);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "set",
+        br_name,
+        "type",
+        "bridge",
+        "vlan_filtering",
+        "1",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["monitor", "vlan"], || {
+                exec_cmd(&[
+                    "bridge", "vlan", "add", "vid", "10-12", "dev", port_name,
+                ]);
+                exec_cmd(&[
+                    "bridge", "vlan", "add", "vid", "20", "dev", port_name,
+                    "pvid", "untagged",
+                ]);
+                exec_cmd(&[
+                    "bridge", "vlan", "del", "vid", "10-12", "dev", port_name,
+                ]);
+            });
+        let expected_events = filter_events(&expected_output, port_name);
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, port_name)
+        );
+    });
+
+    cleanup_bridge(br_name, port_name);
+    assert!(result.is_ok());
+}
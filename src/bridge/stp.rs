@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+
+// Equal to iproute2 `port_states` and `stp_states`
+const STP_STATE_NAMES: [&str; 5] = [
+    "disabled",
+    "listening",
+    "learning",
+    "forwarding",
+    "blocking",
+];
+
+/// Equal to iproute2 `print_portstate()` and `print_stp_state()`, the
+/// unknown state is shown as number in parentheses.
+pub(crate) fn stp_state_to_name(state: u8) -> String {
+    STP_STATE_NAMES
+        .get(usize::from(state))
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("({state})"))
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MIT
+
+pub(crate) fn exec_cmd(args: &[&str]) -> String {
+    let output = std::process::Command::new(args[0])
+        .args(&args[1..])
+        .output()
+        .unwrap_or_else(|e| panic!("failed to execute command {args:?}: {e}"));
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        panic!("Command failed: {args:?}\nstderr: {stderr}");
+    }
+
+    String::from_utf8(output.stdout)
+        .expect("Failed to convert command output to String")
+}
+
+pub(crate) fn bridge_rs_exec_cmd(args: &[&str]) -> String {
+    let output = bridge_rs_exec_cmd_output(args);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        panic!("Command failed: {args:?}\nstderr: {stderr}");
+    }
+
+    String::from_utf8(output.stdout)
+        .expect("Failed to convert command output to String")
+}
+
+/// Execute without checking the exit status
+pub(crate) fn bridge_rs_exec_cmd_output(args: &[&str]) -> std::process::Output {
+    std::process::Command::new(bridge_rs_path())
+        .args(args)
+        .output()
+        .unwrap_or_else(|e| {
+            panic!("failed to execute bridge-rs command {args:?}: {e}")
+        })
+}
+
+/// Spawn bridge-rs with stdout captured, used by long running command like
+/// `bridge monitor`
+pub(crate) fn bridge_rs_spawn(args: &[&str]) -> std::process::Child {
+    std::process::Command::new(bridge_rs_path())
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| {
+            panic!("failed to spawn bridge-rs command {args:?}: {e}")
+        })
+}
+
+fn bridge_rs_path() -> String {
+    let mut cur_exec_path =
+        std::env::current_exe().expect("No current exec path");
+
+    cur_exec_path.pop();
+    cur_exec_path.pop();
+
+    cur_exec_path
+        .join("bridge")
+        .to_str()
+        .expect("Not UTF-8 string")
+        .to_string()
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+
+mod cmd;
+
+use std::sync::{Mutex, MutexGuard};
+
+pub(crate) use self::cmd::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, bridge_rs_spawn, exec_cmd,
+};
+
+// The tests changing the global state of the network namespace, e.g.
+// the bridges and ports of fixed names, should hold this lock to not run in
+// parallel.
+static NET_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_net_test() -> MutexGuard<'static, ()> {
+    // The lock is poisoned by failed test which is harmless here
+    NET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
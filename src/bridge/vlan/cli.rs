@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use rtnetlink::sys::protocols::NETLINK_ROUTE;
+use serde::Serialize;
+
+use super::{
+    VLAN_ID_LEN,
+    global::{CliVlanGlobalPort, handle_global_set, handle_global_show},
+    message::BridgeVlanNetlinkMessage,
+    show::{CliVlanShowPort, handle_show},
+    tunnel::{CliVlanTunnelPort, handle_tunnel_show},
+};
+
+pub(crate) const VLAN_USAGE: &str = "\
+Usage: bridge vlan { add | del } vid VLAN_ID dev DEV [ tunnel_info id \
+                                     TUNNEL_ID ]
+                                                     [ pvid ] [ untagged ]
+                                                     [ self ] [ master ]
+       bridge vlan { set } vid VLAN_ID dev DEV [ state STP_STATE ]
+                                               [ mcast_router MULTICAST_ROUTER \
+                                     ]
+       bridge vlan { show } [ dev DEV ] [ vid VLAN_ID ]
+       bridge vlan { tunnelshow } [ dev DEV ] [ vid VLAN_ID ]
+       bridge vlan global { set } vid VLAN_ID dev DEV
+                      [ mcast_snooping MULTICAST_SNOOPING ]
+                      [ mcast_querier MULTICAST_QUERIER ]
+                      [ mcast_igmp_version IGMP_VERSION ]
+                      [ mcast_mld_version MLD_VERSION ]
+                      [ mcast_last_member_count LAST_MEMBER_COUNT ]
+                      [ mcast_last_member_interval LAST_MEMBER_INTERVAL ]
+                      [ mcast_startup_query_count STARTUP_QUERY_COUNT ]
+                      [ mcast_startup_query_interval STARTUP_QUERY_INTERVAL ]
+                      [ mcast_membership_interval MEMBERSHIP_INTERVAL ]
+                      [ mcast_querier_interval QUERIER_INTERVAL ]
+                      [ mcast_query_interval QUERY_INTERVAL ]
+                      [ mcast_query_response_interval QUERY_RESPONSE_INTERVAL ]
+                      [ msti MSTI ]
+       bridge vlan global { show } [ dev DEV ] [ vid VLAN_ID ]";
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliVlanOutput {
+    Show(Vec<CliVlanShowPort>),
+    Global(Vec<CliVlanGlobalPort>),
+    Tunnel(Vec<CliVlanTunnelPort>),
+}
+
+impl CliVlanOutput {
+    // Like iproute2, the header is printed even when no VLAN found
+    fn gen_text(&self, oneline: bool) -> String {
+        let (header, body) = match self {
+            Self::Show(ports) => (String::new(), gen_body(ports, oneline)),
+            Self::Global(ports) => (String::new(), gen_body(ports, oneline)),
+            Self::Tunnel(ports) => {
+                ("  tunnel-id".to_string(), gen_body(ports, oneline))
+            }
+        };
+        let mut ret =
+            format!("{:16}  {:VLAN_ID_LEN$}{header}", "port", "vlan-id");
+        if !body.is_empty() {
+            ret.push('\n');
+            ret.push_str(&body);
+        }
+        ret
+    }
+}
+
+// Like iproute2 `print_nl()` in oneline mode, every VLAN is ended by `\`
+// without newline.
+fn gen_body<T: CanDisplay>(ports: &[T], oneline: bool) -> String {
+    if oneline {
+        ports
+            .iter()
+            .map(|port| format!("{}\\", port.gen_oneline_string()))
+            .collect()
+    } else {
+        ports.gen_string()
+    }
+}
+
+impl CanDisplay for CliVlanOutput {
+    fn gen_string(&self) -> String {
+        self.gen_text(false)
+    }
+
+    // Like iproute2, the header is still ended by newline
+    fn gen_oneline_string(&self) -> String {
+        self.gen_text(true)
+    }
+}
+
+impl CanOutput for CliVlanOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+pub(crate) struct VlanCommand;
+
+impl VlanCommand {
+    pub(crate) const CMD: &'static str = "vlan";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("VLAN filter list management")
+            .alias("vla")
+            .alias("vl")
+            .alias("v")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show VLAN filter list")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("tunnelshow")
+                    .about("show VLAN to tunnel ID mappings")
+                    .alias("tunnelsho")
+                    .alias("tunnelsh")
+                    .alias("tunnels")
+                    .alias("tunnel")
+                    .alias("tunne")
+                    .alias("tunn")
+                    .alias("tun")
+                    .alias("tu")
+                    .alias("t")
+                    .arg(gen_opts_arg()),
+            )
+            // Like iproute2, the `global` and its subcommands are not
+            // matched by prefix
+            .subcommand(
+                clap::Command::new("global")
+                    .about("bridge global VLAN options")
+                    .subcommand_required(false)
+                    .subcommand(
+                        clap::Command::new("show")
+                            .about("show global VLAN options")
+                            .alias("lst")
+                            .alias("list")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("set")
+                            .about("change global VLAN options")
+                            .arg(gen_opts_arg()),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        handle: &rtnetlink::Handle,
+    ) -> Result<Option<CliVlanOutput>, CliError> {
+        // Like iproute2 `vlan_show()`, the statistics are shown instead of
+        // VLAN list and tunnel mappings
+        if matches.get_count("STATS") > 0
+            && !matches!(matches.subcommand(), Some(("global", _)))
+        {
+            return Err(CliError::from(
+                "VLAN statistics are not supported yet",
+            ));
+        }
+        let compress_vlans = matches.get_flag("COMPRESSVLANS");
+        match matches.subcommand() {
+            Some(("show", matches)) => {
+                handle_show(handle, &get_opts(matches), compress_vlans)
+                    .await
+                    .map(CliVlanOutput::Show)
+                    .map(Some)
+            }
+            // Like iproute2, `bridge vlan` without command means `show`
+            None => handle_show(handle, &[], compress_vlans)
+                .await
+                .map(CliVlanOutput::Show)
+                .map(Some),
+            Some(("tunnelshow", matches)) => {
+                handle_tunnel_show(handle, &get_opts(matches))
+                    .await
+                    .map(CliVlanOutput::Tunnel)
+                    .map(Some)
+            }
+            Some(("global", matches)) => {
+                Self::handle_global(matches, handle).await
+            }
+            _ => Err(CliError::from(VLAN_USAGE)),
+        }
+    }
+
+    async fn handle_global(
+        matches: &clap::ArgMatches,
+        handle: &rtnetlink::Handle,
+    ) -> Result<Option<CliVlanOutput>, CliError> {
+        // The `RTM_GETVLAN` is not supported by `rtnetlink::Handle`, hence
+        // a connection of our own message type shared by the whole command.
+        let (connection, vlan_handle, _) = rtnetlink::proto::new_connection::<
+            BridgeVlanNetlinkMessage,
+        >(NETLINK_ROUTE)?;
+        tokio::spawn(connection);
+
+        if let Some(matches) = matches.subcommand_matches("set") {
+            handle_global_set(handle, &vlan_handle, &get_opts(matches)).await?;
+            return Ok(None);
+        }
+        let opts = match matches.subcommand_matches("show") {
+            Some(matches) => get_opts(matches),
+            None => Vec::new(),
+        };
+        handle_global_show(handle, &vlan_handle, &opts)
+            .await
+            .map(CliVlanOutput::Global)
+            .map(Some)
+    }
+}
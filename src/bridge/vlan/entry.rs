@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliColor, write_with_color};
+use rtnetlink::packet_route::{AddressFamily, link::BridgeVlanInfoFlags};
+use serde::Serialize;
+
+use super::{
+    global::CliVlanGlobalOpts,
+    message::{BridgeVlanAttr, BridgeVlanMessage, VlanEntryOption},
+};
+use crate::{iface::IfaceMap, stp::stp_state_to_name};
+
+/// Options of VLAN range on port, equal to iproute2 `print_vlan_opts()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliVlanEntry {
+    vlan: u16,
+    #[serde(rename = "vlanEnd", skip_serializing_if = "Option::is_none")]
+    vlan_end: Option<u16>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    flags: Vec<String>,
+    state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_router: Option<u8>,
+}
+
+impl CliVlanEntry {
+    fn new(opts: &[VlanEntryOption]) -> Self {
+        let mut ret = Self::default();
+        let mut state = 0;
+        for opt in opts {
+            match opt {
+                VlanEntryOption::Info { flags, vid } => {
+                    ret.vlan = *vid;
+                    // Equal to iproute2 `print_vlan_flags()`
+                    if flags.contains(BridgeVlanInfoFlags::Pvid) {
+                        ret.flags.push("PVID".to_string());
+                    }
+                    if flags.contains(BridgeVlanInfoFlags::Untagged) {
+                        ret.flags.push("Egress Untagged".to_string());
+                    }
+                }
+                VlanEntryOption::Range(v) => ret.vlan_end = Some(*v),
+                VlanEntryOption::State(v) => state = *v,
+                VlanEntryOption::McastRouter(v) => ret.mcast_router = Some(*v),
+                _ => (),
+            }
+        }
+        if ret.vlan_end == Some(ret.vlan) {
+            ret.vlan_end = None;
+        }
+        ret.state = stp_state_to_name(state);
+        ret
+    }
+}
+
+impl std::fmt::Display for CliVlanEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.vlan)?;
+        if let Some(vlan_end) = self.vlan_end {
+            write!(f, "-{vlan_end}")?;
+        }
+        for flag in &self.flags {
+            write!(f, " {flag}")?;
+        }
+        writeln!(f)?;
+        write!(f, "{:20}state {} ", "", self.state)?;
+        if let Some(mcast_router) = self.mcast_router {
+            write!(f, "mcast_router {mcast_router} ")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliVlanRtmEntry {
+    Entry(CliVlanEntry),
+    Global(CliVlanGlobalOpts),
+}
+
+impl std::fmt::Display for CliVlanRtmEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Entry(v) => write!(f, "{v}"),
+            Self::Global(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// The VLAN notification of port, equal to iproute2 `print_vlan_rtm()` with
+/// `monitor` enabled
+#[derive(Serialize)]
+pub(crate) struct CliVlanPort {
+    ifname: String,
+    vlans: Vec<CliVlanRtmEntry>,
+}
+
+impl CliVlanPort {
+    /// Return `None` for message not in bridge family
+    pub(crate) fn new(
+        nl_msg: &BridgeVlanMessage,
+        ifaces: &IfaceMap,
+    ) -> Option<Self> {
+        if nl_msg.header.family != u8::from(AddressFamily::Bridge) {
+            return None;
+        }
+        let vlans = nl_msg
+            .attributes
+            .iter()
+            .filter_map(|attr| match attr {
+                BridgeVlanAttr::Entry(opts) => {
+                    Some(CliVlanRtmEntry::Entry(CliVlanEntry::new(opts)))
+                }
+                BridgeVlanAttr::GlobalOptions(opts) => {
+                    Some(CliVlanRtmEntry::Global(CliVlanGlobalOpts::new(opts)))
+                }
+                _ => None,
+            })
+            .collect();
+        Some(Self {
+            ifname: ifaces.index_to_name(nl_msg.header.ifindex),
+            vlans,
+        })
+    }
+}
+
+impl std::fmt::Display for CliVlanPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{:16}  ", self.ifname)?;
+        for (i, vlan) in self.vlans.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n{:18}", "")?;
+            }
+            write!(f, "{vlan}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliVlanPort {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVlanPort {}
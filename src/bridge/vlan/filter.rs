@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::link::BridgeVlanInfoFlags;
+
+use crate::{args::duparg, iface::IfaceMap};
+
+/// Filters of `bridge vlan show`, `bridge vlan tunnelshow` and
+/// `bridge vlan global show` following the argument grammar of iproute2
+/// `vlan_show()`.
+#[derive(Debug, Default)]
+pub(crate) struct VlanShowFilter<'a> {
+    dev: Option<&'a str>,
+    pub(crate) index: Option<u32>,
+    /// Like iproute2 `filter_vlan`, 0 means no filter
+    pub(crate) vid: u16,
+}
+
+impl<'a> VlanShowFilter<'a> {
+    pub(crate) fn parse(opts: &[&'a str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.dev.is_some() {
+                        return Err(duparg("dev", value));
+                    }
+                    ret.dev = Some(value);
+                }
+                "vid" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.vid != 0 {
+                        return Err(duparg("vid", value));
+                    }
+                    // Like iproute2 `atoi()`, invalid number means no filter
+                    ret.vid = value.parse().unwrap_or_default();
+                }
+                // Like iproute2, unknown options are ignored
+                _ => (),
+            }
+        }
+        Ok(ret)
+    }
+
+    pub(crate) fn resolve_dev(
+        &mut self,
+        ifaces: &IfaceMap,
+    ) -> Result<(), CliError> {
+        if let Some(dev) = self.dev {
+            self.index = Some(ifaces.name_to_index(dev).ok_or_else(|| {
+                CliError::from(format!("Cannot find device \"{dev}\"").as_str())
+            })?);
+        }
+        Ok(())
+    }
+}
+
+/// Equal to iproute2 `filter_vlan_check()`: `None` means stop checking the
+/// remaining VLANs of this port.
+pub(crate) fn filter_vlan_check(
+    filter_vid: u16,
+    vid: u16,
+    flags: BridgeVlanInfoFlags,
+) -> Option<bool> {
+    if filter_vid != 0
+        && vid > filter_vid
+        && !flags.contains(BridgeVlanInfoFlags::RangeEnd)
+    {
+        None
+    } else {
+        Some(
+            !flags.contains(BridgeVlanInfoFlags::RangeBegin)
+                && vid >= filter_vid,
+        )
+    }
+}
@@ -0,0 +1,414 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, nl_request,
+    write_with_color,
+};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+    proto::ConnectionHandle,
+};
+use serde::Serialize;
+
+use super::{
+    VLAN_N_VID,
+    filter::VlanShowFilter,
+    message::{
+        BRIDGE_VLANDB_DUMPF_GLOBAL, BridgeVlanAttr, BridgeVlanHeader,
+        BridgeVlanMessage, BridgeVlanNetlinkMessage, VlanGlobalOption,
+    },
+};
+use crate::{
+    args::{invarg, parse_num},
+    iface::IfaceMap,
+};
+
+/// Global options of VLAN range on bridge, equal to iproute2
+/// `print_vlan_global_opts()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliVlanGlobalOpts {
+    vlan: u16,
+    #[serde(rename = "vlanEnd", skip_serializing_if = "Option::is_none")]
+    vlan_end: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_snooping: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_querier: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_igmp_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_mld_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_last_member_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_last_member_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_startup_query_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_startup_query_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_membership_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_querier_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_query_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_query_response_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msti: Option<u16>,
+}
+
+impl CliVlanGlobalOpts {
+    pub(super) fn new(opts: &[VlanGlobalOption]) -> Self {
+        let mut ret = Self::default();
+        for opt in opts {
+            match opt {
+                VlanGlobalOption::Id(v) => ret.vlan = *v,
+                VlanGlobalOption::Range(v) => ret.vlan_end = Some(*v),
+                VlanGlobalOption::McastSnooping(v) => {
+                    ret.mcast_snooping = Some(*v)
+                }
+                VlanGlobalOption::McastQuerier(v) => {
+                    ret.mcast_querier = Some(*v)
+                }
+                VlanGlobalOption::McastIgmpVersion(v) => {
+                    ret.mcast_igmp_version = Some(*v)
+                }
+                VlanGlobalOption::McastMldVersion(v) => {
+                    ret.mcast_mld_version = Some(*v)
+                }
+                VlanGlobalOption::McastLastMemberCount(v) => {
+                    ret.mcast_last_member_count = Some(*v)
+                }
+                VlanGlobalOption::McastLastMemberInterval(v) => {
+                    ret.mcast_last_member_interval = Some(*v)
+                }
+                VlanGlobalOption::McastStartupQueryCount(v) => {
+                    ret.mcast_startup_query_count = Some(*v)
+                }
+                VlanGlobalOption::McastStartupQueryInterval(v) => {
+                    ret.mcast_startup_query_interval = Some(*v)
+                }
+                VlanGlobalOption::McastMembershipInterval(v) => {
+                    ret.mcast_membership_interval = Some(*v)
+                }
+                VlanGlobalOption::McastQuerierInterval(v) => {
+                    ret.mcast_querier_interval = Some(*v)
+                }
+                VlanGlobalOption::McastQueryInterval(v) => {
+                    ret.mcast_query_interval = Some(*v)
+                }
+                VlanGlobalOption::McastQueryResponseInterval(v) => {
+                    ret.mcast_query_response_interval = Some(*v)
+                }
+                VlanGlobalOption::Msti(v) => ret.msti = Some(*v),
+                _ => (),
+            }
+        }
+        // Like iproute2, the range is omitted when holding single VLAN
+        if ret.vlan_end == Some(ret.vlan) {
+            ret.vlan_end = None;
+        }
+        ret
+    }
+
+    fn vlan_end(&self) -> u16 {
+        self.vlan_end.unwrap_or(self.vlan)
+    }
+}
+
+impl std::fmt::Display for CliVlanGlobalOpts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.vlan)?;
+        if let Some(vlan_end) = self.vlan_end {
+            write!(f, "-{vlan_end}")?;
+        }
+        writeln!(f)?;
+        write!(f, "{:20}", "")?;
+        // Equal to the order of iproute2 `print_vlan_global_opts()`
+        let opts = [
+            ("mcast_snooping", self.mcast_snooping.map(u64::from)),
+            ("mcast_querier", self.mcast_querier.map(u64::from)),
+            ("mcast_igmp_version", self.mcast_igmp_version.map(u64::from)),
+            ("mcast_mld_version", self.mcast_mld_version.map(u64::from)),
+            (
+                "mcast_last_member_count",
+                self.mcast_last_member_count.map(u64::from),
+            ),
+            (
+                "mcast_last_member_interval",
+                self.mcast_last_member_interval,
+            ),
+            (
+                "mcast_startup_query_count",
+                self.mcast_startup_query_count.map(u64::from),
+            ),
+            (
+                "mcast_startup_query_interval",
+                self.mcast_startup_query_interval,
+            ),
+            ("mcast_membership_interval", self.mcast_membership_interval),
+            ("mcast_querier_interval", self.mcast_querier_interval),
+            ("mcast_query_interval", self.mcast_query_interval),
+            (
+                "mcast_query_response_interval",
+                self.mcast_query_response_interval,
+            ),
+            ("msti", self.msti.map(u64::from)),
+        ];
+        for (name, value) in opts {
+            if let Some(value) = value {
+                write!(f, "{name} {value} ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct CliVlanGlobalPort {
+    ifname: String,
+    vlans: Vec<CliVlanGlobalOpts>,
+}
+
+impl std::fmt::Display for CliVlanGlobalPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{:16}  ", self.ifname)?;
+        for (i, vlan) in self.vlans.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n{:18}", "")?;
+            }
+            write!(f, "{vlan}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliVlanGlobalPort {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVlanGlobalPort {}
+
+/// Equal to iproute2 `vlan_global_show()`
+pub(crate) async fn handle_global_show(
+    handle: &rtnetlink::Handle,
+    vlan_handle: &ConnectionHandle<BridgeVlanNetlinkMessage>,
+    opts: &[&str],
+) -> Result<Vec<CliVlanGlobalPort>, CliError> {
+    let mut filter = VlanShowFilter::parse(opts)?;
+    let ifaces = IfaceMap::new(handle).await?;
+    filter.resolve_dev(&ifaces)?;
+
+    let replies = nl_request(
+        vlan_handle,
+        BridgeVlanNetlinkMessage::Get(BridgeVlanMessage {
+            header: BridgeVlanHeader {
+                family: u8::from(AddressFamily::Bridge),
+                ..Default::default()
+            },
+            attributes: vec![BridgeVlanAttr::DumpFlags(
+                BRIDGE_VLANDB_DUMPF_GLOBAL,
+            )],
+        }),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?;
+
+    let mut ret: Vec<CliVlanGlobalPort> = Vec::new();
+    // Like iproute2 `vlan_rtm_cur_ifidx`, the VLANs of the same bridge
+    // might be split into multiple messages.
+    let mut cur_index = None;
+    for reply in replies {
+        let nl_msg = reply.into_message();
+        if nl_msg.header.family != u8::from(AddressFamily::Bridge)
+            || filter.index.is_some_and(|i| i != nl_msg.header.ifindex)
+        {
+            continue;
+        }
+        for attr in nl_msg.attributes.iter() {
+            let BridgeVlanAttr::GlobalOptions(opts) = attr else {
+                continue;
+            };
+            let vlan = CliVlanGlobalOpts::new(opts);
+            if filter.vid != 0
+                && (filter.vid < vlan.vlan || filter.vid > vlan.vlan_end())
+            {
+                continue;
+            }
+            match ret.last_mut() {
+                Some(port) if cur_index == Some(nl_msg.header.ifindex) => {
+                    port.vlans.push(vlan)
+                }
+                _ => {
+                    cur_index = Some(nl_msg.header.ifindex);
+                    ret.push(CliVlanGlobalPort {
+                        ifname: ifaces.index_to_name(nl_msg.header.ifindex),
+                        vlans: vec![vlan],
+                    });
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
+
+// Like iproute2, the VLAN ID is parsed by `atoi()` into `short`
+fn parse_vid(value: &str) -> i16 {
+    value.parse::<i32>().unwrap_or_default() as i16
+}
+
+fn parse_gopt<T: TryFrom<u64>>(name: &str, value: &str) -> Result<T, CliError> {
+    parse_num(value).ok_or_else(|| invarg(&format!("invalid {name}"), value))
+}
+
+/// Equal to iproute2 `vlan_global_option_set()`
+pub(crate) async fn handle_global_set(
+    handle: &rtnetlink::Handle,
+    vlan_handle: &ConnectionHandle<BridgeVlanNetlinkMessage>,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let ifaces = IfaceMap::new(handle).await?;
+    let mut ifindex = None;
+    let mut vid = None;
+    let mut gopts = Vec::new();
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "dev" => {
+                let value = next_opt(&mut opts)?;
+                ifindex =
+                    Some(ifaces.name_to_index(value).ok_or_else(|| {
+                        CliError::from(
+                            format!("Cannot find network device \"{value}\"")
+                                .as_str(),
+                        )
+                    })?);
+            }
+            "vid" => {
+                let value = next_opt(&mut opts)?;
+                let (start, end) = match value.split_once('-') {
+                    Some((start, end)) => {
+                        let (start, end) = (parse_vid(start), parse_vid(end));
+                        if start >= end || end >= VLAN_N_VID as i16 {
+                            return Err(CliError::from(
+                                format!(
+                                    "Invalid VLAN range \"{}-{}\"",
+                                    start as u16, end as u16
+                                )
+                                .as_str(),
+                            ));
+                        }
+                        (start, Some(end))
+                    }
+                    None => (parse_vid(value), None),
+                };
+                if start >= VLAN_N_VID as i16 {
+                    return Err(CliError::from(
+                        format!("Invalid VLAN ID \"{}\"", start as u16)
+                            .as_str(),
+                    ));
+                }
+                gopts.push(VlanGlobalOption::Id(start as u16));
+                if let Some(end) = end {
+                    gopts.push(VlanGlobalOption::Range(end as u16));
+                }
+                vid = Some(start);
+            }
+            "mcast_snooping" => gopts.push(VlanGlobalOption::McastSnooping(
+                parse_gopt(opt, next_opt(&mut opts)?)?,
+            )),
+            "mcast_querier" => gopts.push(VlanGlobalOption::McastQuerier(
+                parse_gopt(opt, next_opt(&mut opts)?)?,
+            )),
+            "mcast_igmp_version" => {
+                gopts.push(VlanGlobalOption::McastIgmpVersion(parse_gopt(
+                    opt,
+                    next_opt(&mut opts)?,
+                )?))
+            }
+            "mcast_mld_version" => {
+                gopts.push(VlanGlobalOption::McastMldVersion(parse_gopt(
+                    opt,
+                    next_opt(&mut opts)?,
+                )?))
+            }
+            "mcast_last_member_count" => {
+                gopts.push(VlanGlobalOption::McastLastMemberCount(parse_gopt(
+                    opt,
+                    next_opt(&mut opts)?,
+                )?))
+            }
+            "mcast_startup_query_count" => {
+                gopts.push(VlanGlobalOption::McastStartupQueryCount(
+                    parse_gopt(opt, next_opt(&mut opts)?)?,
+                ))
+            }
+            "mcast_last_member_interval" => {
+                gopts.push(VlanGlobalOption::McastLastMemberInterval(
+                    parse_gopt(opt, next_opt(&mut opts)?)?,
+                ))
+            }
+            "mcast_startup_query_interval" => {
+                gopts.push(VlanGlobalOption::McastStartupQueryInterval(
+                    parse_gopt(opt, next_opt(&mut opts)?)?,
+                ))
+            }
+            "mcast_membership_interval" => {
+                gopts.push(VlanGlobalOption::McastMembershipInterval(
+                    parse_gopt(opt, next_opt(&mut opts)?)?,
+                ))
+            }
+            "mcast_querier_interval" => {
+                gopts.push(VlanGlobalOption::McastQuerierInterval(parse_gopt(
+                    opt,
+                    next_opt(&mut opts)?,
+                )?))
+            }
+            "mcast_query_interval" => {
+                gopts.push(VlanGlobalOption::McastQueryInterval(parse_gopt(
+                    opt,
+                    next_opt(&mut opts)?,
+                )?))
+            }
+            "mcast_query_response_interval" => {
+                gopts.push(VlanGlobalOption::McastQueryResponseInterval(
+                    parse_gopt(opt, next_opt(&mut opts)?)?,
+                ))
+            }
+            "msti" => gopts.push(VlanGlobalOption::Msti(parse_gopt(
+                opt,
+                next_opt(&mut opts)?,
+            )?)),
+            "help" => {
+                next_opt(&mut opts)?;
+            }
+            // Like iproute2, unknown options are ignored
+            _ => (),
+        }
+    }
+
+    let (Some(ifindex), Some(_)) = (ifindex, vid) else {
+        return Err(CliError::from(
+            "Device and VLAN ID are required arguments.",
+        ));
+    };
+
+    nl_request(
+        vlan_handle,
+        BridgeVlanNetlinkMessage::New(BridgeVlanMessage {
+            header: BridgeVlanHeader {
+                family: u8::from(AddressFamily::Bridge),
+                ifindex,
+            },
+            attributes: vec![BridgeVlanAttr::GlobalOptions(gopts)],
+        }),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
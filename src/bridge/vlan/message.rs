@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::{
+    packet_core::{
+        DecodeError, DefaultNla, Emitable, ErrorContext, NetlinkDeserializable,
+        NetlinkHeader, NetlinkSerializable, Nla, NlaBuffer, NlasIterator,
+        Parseable, parse_u8, parse_u16, parse_u32, parse_u64,
+    },
+    packet_route::link::BridgeVlanInfoFlags,
+};
+
+// Equal to `linux/rtnetlink.h`
+pub(crate) const RTM_NEWVLAN: u16 = 112;
+pub(crate) const RTM_DELVLAN: u16 = 113;
+const RTM_GETVLAN: u16 = 114;
+
+// Equal to `linux/if_bridge.h`
+const BRIDGE_VLANDB_ENTRY: u16 = 1;
+const BRIDGE_VLANDB_DUMP_FLAGS: u16 = 2;
+const BRIDGE_VLANDB_GLOBAL_OPTIONS: u16 = 3;
+
+pub(crate) const BRIDGE_VLANDB_DUMPF_GLOBAL: u32 = 1 << 1;
+
+const BRIDGE_VLANDB_ENTRY_INFO: u16 = 1;
+const BRIDGE_VLANDB_ENTRY_RANGE: u16 = 2;
+const BRIDGE_VLANDB_ENTRY_STATE: u16 = 3;
+const BRIDGE_VLANDB_ENTRY_MCAST_ROUTER: u16 = 6;
+
+// The size of `struct bridge_vlan_info`
+const BRIDGE_VLAN_INFO_LEN: usize = 4;
+
+const BRIDGE_VLANDB_GOPTS_ID: u16 = 1;
+const BRIDGE_VLANDB_GOPTS_RANGE: u16 = 2;
+const BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING: u16 = 3;
+const BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION: u16 = 4;
+const BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION: u16 = 5;
+const BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT: u16 = 6;
+const BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT: u16 = 7;
+const BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL: u16 = 8;
+const BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL: u16 = 10;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL: u16 = 11;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL: u16 = 12;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL: u16 = 13;
+const BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL: u16 = 14;
+const BRIDGE_VLANDB_GOPTS_MCAST_QUERIER: u16 = 15;
+const BRIDGE_VLANDB_GOPTS_MSTI: u16 = 18;
+
+// The size of `struct br_vlan_msg`
+const BR_VLAN_MSG_LEN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BridgeVlanNetlinkMessage {
+    New(BridgeVlanMessage),
+    Del(BridgeVlanMessage),
+    Get(BridgeVlanMessage),
+}
+
+impl BridgeVlanNetlinkMessage {
+    fn message(&self) -> &BridgeVlanMessage {
+        match self {
+            Self::New(msg) | Self::Del(msg) | Self::Get(msg) => msg,
+        }
+    }
+
+    pub(crate) fn into_message(self) -> BridgeVlanMessage {
+        match self {
+            Self::New(msg) | Self::Del(msg) | Self::Get(msg) => msg,
+        }
+    }
+}
+
+impl NetlinkSerializable for BridgeVlanNetlinkMessage {
+    fn message_type(&self) -> u16 {
+        match self {
+            Self::New(_) => RTM_NEWVLAN,
+            Self::Del(_) => RTM_DELVLAN,
+            Self::Get(_) => RTM_GETVLAN,
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.message().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        self.message().emit(buffer)
+    }
+}
+
+impl NetlinkDeserializable for BridgeVlanNetlinkMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        let msg = BridgeVlanMessage::parse(payload)?;
+        Ok(match header.message_type {
+            RTM_NEWVLAN => Self::New(msg),
+            RTM_DELVLAN => Self::Del(msg),
+            RTM_GETVLAN => Self::Get(msg),
+            kind => {
+                return Err(DecodeError::from(format!(
+                    "Unknown vlan message type {kind}"
+                )));
+            }
+        })
+    }
+}
+
+/// Equal to kernel `struct br_vlan_msg`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BridgeVlanHeader {
+    pub(crate) family: u8,
+    pub(crate) ifindex: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BridgeVlanMessage {
+    pub(crate) header: BridgeVlanHeader,
+    pub(crate) attributes: Vec<BridgeVlanAttr>,
+}
+
+impl BridgeVlanMessage {
+    pub(crate) fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < BR_VLAN_MSG_LEN {
+            return Err(DecodeError::from(format!(
+                "Invalid br_vlan_msg length {}",
+                buf.len()
+            )));
+        }
+        let header = BridgeVlanHeader {
+            family: buf[0],
+            ifindex: parse_u32(&buf[4..BR_VLAN_MSG_LEN])?,
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(&buf[BR_VLAN_MSG_LEN..]) {
+            let nla = nla.context("invalid bridge vlan attribute")?;
+            attributes.push(BridgeVlanAttr::parse(&nla)?);
+        }
+        Ok(Self { header, attributes })
+    }
+}
+
+impl Emitable for BridgeVlanMessage {
+    fn buffer_len(&self) -> usize {
+        BR_VLAN_MSG_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.header.family;
+        buffer[1..4].fill(0);
+        buffer[4..BR_VLAN_MSG_LEN]
+            .copy_from_slice(&self.header.ifindex.to_ne_bytes());
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[BR_VLAN_MSG_LEN..]);
+    }
+}
+
+/// The `BRIDGE_VLANDB_*` attributes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BridgeVlanAttr {
+    Entry(Vec<VlanEntryOption>),
+    DumpFlags(u32),
+    GlobalOptions(Vec<VlanGlobalOption>),
+    Other(DefaultNla),
+}
+
+impl Nla for BridgeVlanAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Entry(v) => v.as_slice().buffer_len(),
+            Self::DumpFlags(_) => 4,
+            Self::GlobalOptions(v) => v.as_slice().buffer_len(),
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Entry(_) => BRIDGE_VLANDB_ENTRY,
+            Self::DumpFlags(_) => BRIDGE_VLANDB_DUMP_FLAGS,
+            Self::GlobalOptions(_) => BRIDGE_VLANDB_GLOBAL_OPTIONS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, Self::Entry(_) | Self::GlobalOptions(_))
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Entry(v) => v.as_slice().emit(buffer),
+            Self::DumpFlags(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::GlobalOptions(v) => v.as_slice().emit(buffer),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for BridgeVlanAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            BRIDGE_VLANDB_ENTRY => {
+                let mut opts = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid BRIDGE_VLANDB_ENTRY")?;
+                    opts.push(VlanEntryOption::parse(&nla)?);
+                }
+                Self::Entry(opts)
+            }
+            BRIDGE_VLANDB_DUMP_FLAGS => Self::DumpFlags(
+                parse_u32(payload)
+                    .context("invalid BRIDGE_VLANDB_DUMP_FLAGS")?,
+            ),
+            BRIDGE_VLANDB_GLOBAL_OPTIONS => {
+                let mut opts = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid BRIDGE_VLANDB_GLOBAL_OPTIONS")?;
+                    opts.push(VlanGlobalOption::parse(&nla)?);
+                }
+                Self::GlobalOptions(opts)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The `BRIDGE_VLANDB_ENTRY_*` attributes of port VLAN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum VlanEntryOption {
+    /// Equal to kernel `struct bridge_vlan_info`
+    Info {
+        flags: BridgeVlanInfoFlags,
+        vid: u16,
+    },
+    Range(u16),
+    State(u8),
+    McastRouter(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for VlanEntryOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Info { .. } => BRIDGE_VLAN_INFO_LEN,
+            Self::Range(_) => 2,
+            Self::State(_) | Self::McastRouter(_) => 1,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Info { .. } => BRIDGE_VLANDB_ENTRY_INFO,
+            Self::Range(_) => BRIDGE_VLANDB_ENTRY_RANGE,
+            Self::State(_) => BRIDGE_VLANDB_ENTRY_STATE,
+            Self::McastRouter(_) => BRIDGE_VLANDB_ENTRY_MCAST_ROUTER,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Info { flags, vid } => {
+                buffer[..2].copy_from_slice(&flags.bits().to_ne_bytes());
+                buffer[2..BRIDGE_VLAN_INFO_LEN]
+                    .copy_from_slice(&vid.to_ne_bytes());
+            }
+            Self::Range(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::State(v) | Self::McastRouter(v) => buffer[0] = *v,
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for VlanEntryOption {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            BRIDGE_VLANDB_ENTRY_INFO => {
+                if payload.len() < BRIDGE_VLAN_INFO_LEN {
+                    return Err(DecodeError::from(format!(
+                        "Invalid BRIDGE_VLANDB_ENTRY_INFO length {}",
+                        payload.len()
+                    )));
+                }
+                Self::Info {
+                    flags: BridgeVlanInfoFlags::from_bits_retain(parse_u16(
+                        &payload[..2],
+                    )?),
+                    vid: parse_u16(&payload[2..BRIDGE_VLAN_INFO_LEN])?,
+                }
+            }
+            BRIDGE_VLANDB_ENTRY_RANGE => Self::Range(parse_u16(payload)?),
+            BRIDGE_VLANDB_ENTRY_STATE => Self::State(parse_u8(payload)?),
+            BRIDGE_VLANDB_ENTRY_MCAST_ROUTER => {
+                Self::McastRouter(parse_u8(payload)?)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The `BRIDGE_VLANDB_GOPTS_*` attributes, the intervals are in centiseconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum VlanGlobalOption {
+    Id(u16),
+    Range(u16),
+    McastSnooping(u8),
+    McastIgmpVersion(u8),
+    McastMldVersion(u8),
+    McastLastMemberCount(u32),
+    McastStartupQueryCount(u32),
+    McastLastMemberInterval(u64),
+    McastMembershipInterval(u64),
+    McastQuerierInterval(u64),
+    McastQueryInterval(u64),
+    McastQueryResponseInterval(u64),
+    McastStartupQueryInterval(u64),
+    McastQuerier(u8),
+    Msti(u16),
+    Other(DefaultNla),
+}
+
+impl Nla for VlanGlobalOption {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::McastSnooping(_)
+            | Self::McastIgmpVersion(_)
+            | Self::McastMldVersion(_)
+            | Self::McastQuerier(_) => 1,
+            Self::Id(_) | Self::Range(_) | Self::Msti(_) => 2,
+            Self::McastLastMemberCount(_) | Self::McastStartupQueryCount(_) => {
+                4
+            }
+            Self::McastLastMemberInterval(_)
+            | Self::McastMembershipInterval(_)
+            | Self::McastQuerierInterval(_)
+            | Self::McastQueryInterval(_)
+            | Self::McastQueryResponseInterval(_)
+            | Self::McastStartupQueryInterval(_) => 8,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Id(_) => BRIDGE_VLANDB_GOPTS_ID,
+            Self::Range(_) => BRIDGE_VLANDB_GOPTS_RANGE,
+            Self::McastSnooping(_) => BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING,
+            Self::McastIgmpVersion(_) => BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION,
+            Self::McastMldVersion(_) => BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION,
+            Self::McastLastMemberCount(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT
+            }
+            Self::McastStartupQueryCount(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT
+            }
+            Self::McastLastMemberInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL
+            }
+            Self::McastMembershipInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL
+            }
+            Self::McastQuerierInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL
+            }
+            Self::McastQueryInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL
+            }
+            Self::McastQueryResponseInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL
+            }
+            Self::McastStartupQueryInterval(_) => {
+                BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL
+            }
+            Self::McastQuerier(_) => BRIDGE_VLANDB_GOPTS_MCAST_QUERIER,
+            Self::Msti(_) => BRIDGE_VLANDB_GOPTS_MSTI,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::McastSnooping(v)
+            | Self::McastIgmpVersion(v)
+            | Self::McastMldVersion(v)
+            | Self::McastQuerier(v) => buffer[0] = *v,
+            Self::Id(v) | Self::Range(v) | Self::Msti(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::McastLastMemberCount(v) | Self::McastStartupQueryCount(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::McastLastMemberInterval(v)
+            | Self::McastMembershipInterval(v)
+            | Self::McastQuerierInterval(v)
+            | Self::McastQueryInterval(v)
+            | Self::McastQueryResponseInterval(v)
+            | Self::McastStartupQueryInterval(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for VlanGlobalOption {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            BRIDGE_VLANDB_GOPTS_ID => Self::Id(parse_u16(payload)?),
+            BRIDGE_VLANDB_GOPTS_RANGE => Self::Range(parse_u16(payload)?),
+            BRIDGE_VLANDB_GOPTS_MCAST_SNOOPING => {
+                Self::McastSnooping(parse_u8(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_IGMP_VERSION => {
+                Self::McastIgmpVersion(parse_u8(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_MLD_VERSION => {
+                Self::McastMldVersion(parse_u8(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_CNT => {
+                Self::McastLastMemberCount(parse_u32(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_CNT => {
+                Self::McastStartupQueryCount(parse_u32(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_LAST_MEMBER_INTVL => {
+                Self::McastLastMemberInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_MEMBERSHIP_INTVL => {
+                Self::McastMembershipInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERIER_INTVL => {
+                Self::McastQuerierInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERY_INTVL => {
+                Self::McastQueryInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERY_RESPONSE_INTVL => {
+                Self::McastQueryResponseInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_STARTUP_QUERY_INTVL => {
+                Self::McastStartupQueryInterval(parse_u64(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MCAST_QUERIER => {
+                Self::McastQuerier(parse_u8(payload)?)
+            }
+            BRIDGE_VLANDB_GOPTS_MSTI => Self::Msti(parse_u16(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod entry;
+mod filter;
+mod global;
+mod message;
+mod show;
+mod tunnel;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::VlanCommand,
+    entry::CliVlanPort,
+    message::{BridgeVlanMessage, RTM_DELVLAN, RTM_NEWVLAN},
+};
+
+const VLAN_N_VID: u16 = 4096;
+// Equal to iproute2 `VLAN_ID_LEN` which is the width of `4094-4094`
+const VLAN_ID_LEN: usize = 9;
@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::TryStreamExt;
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    link::{AfSpecBridge, BridgeVlanInfoFlags, LinkAttribute, LinkExtentMask},
+};
+use serde::Serialize;
+
+use super::filter::{VlanShowFilter, filter_vlan_check};
+use crate::iface::IfaceMap;
+
+/// VLAN or VLAN range of port, equal to iproute2 `print_vlan_info()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliVlanInfo {
+    vlan: u16,
+    #[serde(rename = "vlanEnd", skip_serializing_if = "Option::is_none")]
+    vlan_end: Option<u16>,
+    /// Like iproute2 `print_vlan_flags()`, the array is omitted only when
+    /// kernel reports no flag at all, including `BRIDGE_VLAN_INFO_RANGE_END`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<Vec<String>>,
+}
+
+impl std::fmt::Display for CliVlanInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.vlan)?;
+        if let Some(vlan_end) = self.vlan_end {
+            write!(f, "-{vlan_end}")?;
+        }
+        for flag in self.flags.iter().flatten() {
+            write!(f, " {flag}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct CliVlanShowPort {
+    ifname: String,
+    vlans: Vec<CliVlanInfo>,
+}
+
+impl std::fmt::Display for CliVlanShowPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{:16}  ", self.ifname)?;
+        for (i, vlan) in self.vlans.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n{:18}", "")?;
+            }
+            write!(f, "{vlan}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliVlanShowPort {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVlanShowPort {}
+
+fn parse_vlan_infos(
+    af_spec: &[AfSpecBridge],
+    filter_vid: u16,
+) -> Vec<CliVlanInfo> {
+    let mut ret = Vec::new();
+    let mut last_vid_start = 0;
+    for nla in af_spec {
+        let AfSpecBridge::VlanInfo(info) = nla else {
+            continue;
+        };
+        if !info.flags.contains(BridgeVlanInfoFlags::RangeEnd) {
+            last_vid_start = info.vid;
+        }
+        match filter_vlan_check(filter_vid, info.vid, info.flags) {
+            None => break,
+            Some(false) => continue,
+            Some(true) => (),
+        }
+
+        let flags = (!info.flags.is_empty()).then(|| {
+            let mut names = Vec::new();
+            if info.flags.contains(BridgeVlanInfoFlags::Pvid) {
+                names.push("PVID".to_string());
+            }
+            if info.flags.contains(BridgeVlanInfoFlags::Untagged) {
+                names.push("Egress Untagged".to_string());
+            }
+            names
+        });
+        ret.push(CliVlanInfo {
+            vlan: last_vid_start,
+            vlan_end: (last_vid_start != info.vid).then_some(info.vid),
+            flags,
+        });
+    }
+    ret
+}
+
+/// Equal to iproute2 `vlan_show()` with `VLAN_SHOW_VLAN`, the VLAN ranges are
+/// compressed by kernel when `compress_vlans` is set.
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    compress_vlans: bool,
+) -> Result<Vec<CliVlanShowPort>, CliError> {
+    let mut filter = VlanShowFilter::parse(opts)?;
+    let ifaces = IfaceMap::new(handle).await?;
+    filter.resolve_dev(&ifaces)?;
+
+    let ext_mask = if compress_vlans {
+        LinkExtentMask::BrvlanCompressed
+    } else {
+        LinkExtentMask::Brvlan
+    };
+    let mut ret = Vec::new();
+    let mut links = handle
+        .link()
+        .get()
+        .set_filter_mask(AddressFamily::Bridge, vec![ext_mask])
+        .execute();
+    while let Some(nl_msg) = links.try_next().await? {
+        if nl_msg.header.interface_family != AddressFamily::Bridge
+            || filter.index.is_some_and(|i| i != nl_msg.header.index)
+        {
+            continue;
+        }
+        for attr in nl_msg.attributes.iter() {
+            if let LinkAttribute::AfSpecBridge(af_spec) = attr {
+                let vlans = parse_vlan_infos(af_spec, filter.vid);
+                if !vlans.is_empty() {
+                    ret.push(CliVlanShowPort {
+                        ifname: ifaces.index_to_name(nl_msg.header.index),
+                        vlans,
+                    });
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+#[test]
+fn test_vlan_global_show() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&[][..], &["-j"][..]] {
+            for show_args in [
+                &["vlan", "global"][..],
+                &["vlan", "global", "show", "dev", BR_NAME][..],
+                &["vlan", "global", "show", "vid", "105"][..],
+                &["vlan", "global", "list", "dev", BR_NAME, "vid", "200"][..],
+                &["vlan", "global", "lst", "vid", "300"][..],
+            ] {
+                let expected_output =
+                    exec_cmd(&[&["bridge"], args, show_args].concat());
+                let our_output =
+                    bridge_rs_exec_cmd(&[args, show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+// Apply the change by iproute2 and by us, the resulting global options
+// should be identical.
+fn assert_vlan_global_set(args: &[&str], cleanup: &[&str]) {
+    let show_args = ["bridge", "-j", "vlan", "global", "show", "dev", BR_NAME];
+
+    exec_cmd(&[&["bridge", "vlan", "global", "set"][..], args].concat());
+    let expected_output = exec_cmd(&show_args);
+    exec_cmd(&[&["bridge", "vlan", "global", "set"][..], cleanup].concat());
+
+    let output =
+        bridge_rs_exec_cmd(&[&["vlan", "global", "set"][..], args].concat());
+    assert!(output.is_empty());
+    let our_output = exec_cmd(&show_args);
+    exec_cmd(&[&["bridge", "vlan", "global", "set"][..], cleanup].concat());
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_vlan_global_set() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        assert_vlan_global_set(
+            &["dev", BR_NAME, "vid", "200", "mcast_snooping", "0"],
+            &["dev", BR_NAME, "vid", "200", "mcast_snooping", "1"],
+        );
+        assert_vlan_global_set(
+            &[
+                "vid",
+                "100-105",
+                "dev",
+                BR_NAME,
+                "mcast_querier",
+                "1",
+                "mcast_igmp_version",
+                "3",
+                "mcast_mld_version",
+                "2",
+            ],
+            &[
+                "vid",
+                "100-105",
+                "dev",
+                BR_NAME,
+                "mcast_querier",
+                "0",
+                "mcast_igmp_version",
+                "2",
+                "mcast_mld_version",
+                "1",
+            ],
+        );
+        assert_vlan_global_set(
+            &[
+                "dev",
+                BR_NAME,
+                "vid",
+                "110",
+                "mcast_last_member_count",
+                "3",
+                "mcast_startup_query_count",
+                "0x3",
+                "mcast_last_member_interval",
+                "200",
+                "mcast_query_interval",
+                "6000",
+            ],
+            &[
+                "dev",
+                BR_NAME,
+                "vid",
+                "110",
+                "mcast_last_member_count",
+                "2",
+                "mcast_startup_query_count",
+                "2",
+                "mcast_last_member_interval",
+                "100",
+                "mcast_query_interval",
+                "12500",
+            ],
+        );
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_vlan_global_invalid_args() {
+    for (args, error) in [
+        (
+            &["vlan", "global", "set"][..],
+            "Device and VLAN ID are required arguments.",
+        ),
+        (
+            &["vlan", "global", "set", "vid", "5000"][..],
+            "Invalid VLAN ID \"5000\"",
+        ),
+        (
+            &["vlan", "global", "set", "vid", "5-3"][..],
+            "Invalid VLAN range \"5-3\"",
+        ),
+        (
+            &["vlan", "global", "set", "vid", "3-5000"][..],
+            "Invalid VLAN range \"3-5000\"",
+        ),
+        (
+            &["vlan", "global", "set", "dev", "vlantest-absent0"][..],
+            "Cannot find network device \"vlantest-absent0\"",
+        ),
+        (
+            &["vlan", "global", "set", "mcast_snooping", "300"][..],
+            "argument \"300\" is wrong: invalid mcast_snooping",
+        ),
+        (
+            &["vlan", "global", "set", "mcast_query_interval", "x"][..],
+            "argument \"x\" is wrong: invalid mcast_query_interval",
+        ),
+        (
+            &["vlan", "global", "set", "mcast_querier"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["vlan", "global", "show", "dev", "lo", "dev", "lo"][..],
+            "duplicate \"dev\": \"lo\" is the second value.",
+        ),
+        (
+            &["vlan", "global", "show", "dev", "vlantest-absent0"][..],
+            "Cannot find device \"vlantest-absent0\"",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod global;
+#[cfg(test)]
+mod show;
+#[cfg(test)]
+mod tunnel;
+
+use crate::tests::exec_cmd;
+
+const BR_NAME: &str = "vlantest-br0";
+const VXLAN_NAME: &str = "vlantest-vx0";
+
+// VLAN filtering bridge with per-VLAN multicast snooping and a VXLAN port
+// holding VLAN to tunnel ID mappings
+fn setup_bridge() {
+    exec_cmd(&[
+        "ip",
+        "link",
+        "add",
+        BR_NAME,
+        "type",
+        "bridge",
+        "vlan_filtering",
+        "1",
+        "mcast_snooping",
+        "1",
+        "mcast_vlan_snooping",
+        "1",
+    ]);
+    exec_cmd(&[
+        "ip", "link", "add", VXLAN_NAME, "type", "vxlan", "dstport", "4789",
+        "external",
+    ]);
+    exec_cmd(&["ip", "link", "set", VXLAN_NAME, "master", BR_NAME]);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "set",
+        VXLAN_NAME,
+        "type",
+        "bridge_slave",
+        "vlan_tunnel",
+        "on",
+    ]);
+    for args in [
+        &["add", "dev", BR_NAME, "vid", "100-110", "self"][..],
+        &["add", "dev", BR_NAME, "vid", "200", "self"][..],
+        &["add", "dev", VXLAN_NAME, "vid", "100-110"][..],
+        &["add", "dev", VXLAN_NAME, "vid", "200"][..],
+        &[
+            "add",
+            "dev",
+            VXLAN_NAME,
+            "vid",
+            "100-110",
+            "tunnel_info",
+            "id",
+            "1000-1010",
+        ][..],
+        &[
+            "add",
+            "dev",
+            VXLAN_NAME,
+            "vid",
+            "200",
+            "tunnel_info",
+            "id",
+            "2000",
+        ][..],
+    ] {
+        exec_cmd(&[&["bridge", "vlan"], args].concat());
+    }
+    for iface in [BR_NAME, VXLAN_NAME] {
+        exec_cmd(&["ip", "link", "set", iface, "up"]);
+    }
+}
+
+fn cleanup_bridge() {
+    for iface in [VXLAN_NAME, BR_NAME] {
+        exec_cmd(&["ip", "link", "del", iface]);
+    }
+}
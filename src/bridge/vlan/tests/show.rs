@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, VXLAN_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+#[test]
+fn test_vlan_show() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &[][..],
+            &["-j"][..],
+            &["-c"][..],
+            &["-compressvlans", "-j"][..],
+            &["-com", "-o"][..],
+            &["-o"][..],
+        ] {
+            // Other tests might create bridge in parallel, hence always
+            // filter on device or VLAN
+            for show_args in [
+                &["vlan", "show", "dev", VXLAN_NAME][..],
+                &["vlan", "show", "dev", BR_NAME][..],
+                &["vlan", "show", "vid", "105"][..],
+                &["vlan", "list", "dev", BR_NAME, "vid", "200"][..],
+                &["v", "s", "dev", VXLAN_NAME, "vid", "1"][..],
+            ] {
+                let expected_output =
+                    exec_cmd(&[&["bridge"], args, show_args].concat());
+                let our_output =
+                    bridge_rs_exec_cmd(&[args, show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_vlan_show_invalid_args() {
+    for (args, error) in [
+        (
+            &["vlan", "show", "dev", "vlantest-absent0"][..],
+            "Cannot find device \"vlantest-absent0\"",
+        ),
+        (
+            &["vlan", "show", "vid", "1", "vid", "2"][..],
+            "duplicate \"vid\": \"2\" is the second value.",
+        ),
+        (
+            &["-s", "vlan", "show"][..],
+            "VLAN statistics are not supported yet",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT
+
+use super::{BR_NAME, VXLAN_NAME, cleanup_bridge, setup_bridge};
+use crate::tests::{
+    bridge_rs_exec_cmd, bridge_rs_exec_cmd_output, exec_cmd, lock_net_test,
+};
+
+#[test]
+fn test_vlan_tunnel_show() {
+    let _lock = lock_net_test();
+    setup_bridge();
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&[][..], &["-j"][..]] {
+            for show_args in [
+                &["vlan", "tunnelshow"][..],
+                &["vlan", "tunnelshow", "dev", VXLAN_NAME][..],
+                &["vlan", "tunnelshow", "dev", BR_NAME][..],
+                &["vlan", "tunnelshow", "vid", "200"][..],
+                &["vlan", "tunnelshow", "vid", "105"][..],
+                &["v", "tu", "dev", VXLAN_NAME, "vid", "100"][..],
+            ] {
+                let expected_output =
+                    exec_cmd(&[&["bridge"], args, show_args].concat());
+                let our_output =
+                    bridge_rs_exec_cmd(&[args, show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+
+    cleanup_bridge();
+    if let Err(e) = result {
+        std::panic::resume_unwind(e);
+    }
+}
+
+#[test]
+fn test_vlan_tunnel_show_invalid_args() {
+    for (args, error) in [
+        (
+            &["vlan", "tunnelshow", "dev", "vlantest-absent0"][..],
+            "Cannot find device \"vlantest-absent0\"",
+        ),
+        (
+            &["vlan", "tunnelshow", "vid", "1", "vid", "2"][..],
+            "duplicate \"vid\": \"2\" is the second value.",
+        ),
+        (
+            &["vlan", "tunnelshow", "dev"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+    ] {
+        let output = bridge_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
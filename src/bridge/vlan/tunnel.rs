@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::TryStreamExt;
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    link::{
+        AfSpecBridge, BridgeVlanInfoFlags, BridgeVlanTunnelInfo, LinkAttribute,
+        LinkExtentMask,
+    },
+};
+use serde::Serialize;
+
+use super::{
+    VLAN_ID_LEN,
+    filter::{VlanShowFilter, filter_vlan_check},
+};
+use crate::iface::IfaceMap;
+
+/// VLAN to tunnel ID mapping, equal to iproute2 `print_vlan_tunnel_info()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliVlanTunnel {
+    vlan: u16,
+    #[serde(rename = "vlanEnd", skip_serializing_if = "Option::is_none")]
+    vlan_end: Option<u16>,
+    tunid: u32,
+    #[serde(rename = "tunidEnd", skip_serializing_if = "Option::is_none")]
+    tunid_end: Option<u32>,
+}
+
+impl std::fmt::Display for CliVlanTunnel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let vlan = match self.vlan_end {
+            Some(vlan_end) => format!("{}-{vlan_end}", self.vlan),
+            None => self.vlan.to_string(),
+        };
+        write!(f, "{vlan:VLAN_ID_LEN$}  {}", self.tunid)?;
+        if let Some(tunid_end) = self.tunid_end {
+            write!(f, "-{tunid_end}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct CliVlanTunnelPort {
+    ifname: String,
+    tunnels: Vec<CliVlanTunnel>,
+}
+
+impl std::fmt::Display for CliVlanTunnelPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{:16}  ", self.ifname)?;
+        for (i, tunnel) in self.tunnels.iter().enumerate() {
+            if i != 0 {
+                write!(f, "\n{:18}", "")?;
+            }
+            write!(f, "{tunnel}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliVlanTunnelPort {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVlanTunnelPort {}
+
+fn parse_vlan_tunnels(
+    af_spec: &[AfSpecBridge],
+    filter_vid: u16,
+) -> Vec<CliVlanTunnel> {
+    let mut ret = Vec::new();
+    let mut range_begin: Option<(u16, u32)> = None;
+    for nla in af_spec {
+        let AfSpecBridge::VlanTunnelInfo(infos) = nla else {
+            continue;
+        };
+        let mut vid = None;
+        let mut tunid = 0;
+        let mut flags = BridgeVlanInfoFlags::empty();
+        for info in infos {
+            match info {
+                BridgeVlanTunnelInfo::Id(v) => tunid = *v,
+                BridgeVlanTunnelInfo::Vid(v) => vid = Some(*v),
+                BridgeVlanTunnelInfo::Flags(v) => flags = *v,
+                _ => (),
+            }
+        }
+        let Some(vid) = vid else {
+            continue;
+        };
+        // Kernel compresses consecutive mappings into ranges
+        if flags.contains(BridgeVlanInfoFlags::RangeBegin) {
+            range_begin = Some((vid, tunid));
+            continue;
+        } else if !flags.contains(BridgeVlanInfoFlags::RangeEnd) {
+            range_begin = Some((vid, tunid));
+        }
+
+        match filter_vlan_check(filter_vid, vid, flags) {
+            None => break,
+            Some(false) => continue,
+            Some(true) => (),
+        }
+
+        let (vid_start, tunid_start) = range_begin.unwrap_or((vid, tunid));
+        ret.push(CliVlanTunnel {
+            vlan: vid_start,
+            vlan_end: (vid_start != vid).then_some(vid),
+            tunid: tunid_start,
+            tunid_end: (tunid_start != tunid).then_some(tunid),
+        });
+    }
+    ret
+}
+
+/// Equal to iproute2 `vlan_show()` with `VLAN_SHOW_TUNNELINFO`
+pub(crate) async fn handle_tunnel_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<Vec<CliVlanTunnelPort>, CliError> {
+    let mut filter = VlanShowFilter::parse(opts)?;
+    let ifaces = IfaceMap::new(handle).await?;
+    filter.resolve_dev(&ifaces)?;
+
+    let mut ret = Vec::new();
+    // Kernel only provides the VLAN tunnel information for `AF_BRIDGE`
+    // dump with `RTEXT_FILTER_BRVLAN`
+    let mut links = handle
+        .link()
+        .get()
+        .set_filter_mask(AddressFamily::Bridge, vec![LinkExtentMask::Brvlan])
+        .execute();
+    while let Some(nl_msg) = links.try_next().await? {
+        if nl_msg.header.interface_family != AddressFamily::Bridge
+            || filter.index.is_some_and(|i| i != nl_msg.header.index)
+        {
+            continue;
+        }
+        for attr in nl_msg.attributes.iter() {
+            if let LinkAttribute::AfSpecBridge(af_spec) = attr {
+                let tunnels = parse_vlan_tunnels(af_spec, filter.vid);
+                if !tunnels.is_empty() {
+                    ret.push(CliVlanTunnelPort {
+                        ifname: ifaces.index_to_name(nl_msg.header.index),
+                        tunnels,
+                    });
+                }
+            }
+        }
+    }
+    Ok(ret)
+}
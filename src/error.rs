@@ -36,9 +36,43 @@ impl From<std::io::Error> for CliError {
 
 impl From<rtnetlink::Error> for CliError {
     fn from(e: rtnetlink::Error) -> Self {
+        let msg = match e {
+            // Like iproute2, show the kernel errno as `strerror()`
+            rtnetlink::Error::NetlinkError(ref err_msg) => match err_msg.code {
+                Some(code) => format!(
+                    "RTNETLINK answers: {}",
+                    nix::errno::Errno::from_raw(code.get().abs()).desc()
+                ),
+                None => format!("rtnetlink::Error: {e}"),
+            },
+            _ => format!("rtnetlink::Error: {e}"),
+        };
         CliError {
             code: DEFAULT_ERROR_CODE,
-            msg: format!("rtnetlink::Error: {e}"),
+            msg,
+        }
+    }
+}
+
+impl From<genetlink::GenetlinkError> for CliError {
+    fn from(e: genetlink::GenetlinkError) -> Self {
+        let msg = match e {
+            // Like iproute2, show the kernel errno as `strerror()`, e.g.
+            // the generic netlink family is not loaded
+            genetlink::GenetlinkError::NetlinkError(ref io_err) => {
+                match io_err.raw_os_error() {
+                    Some(code) => format!(
+                        "RTNETLINK answers: {}",
+                        nix::errno::Errno::from_raw(code.abs()).desc()
+                    ),
+                    None => format!("genetlink::GenetlinkError: {e}"),
+                }
+            }
+            _ => format!("genetlink::GenetlinkError: {e}"),
+        };
+        CliError {
+            code: DEFAULT_ERROR_CODE,
+            msg,
         }
     }
 }
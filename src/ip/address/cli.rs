@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: MIT
 
-use super::show::handle_show;
-use crate::{CliError, link::CliLinkInfo};
+use super::{
+    flush::handle_flush,
+    modify::{AddressModifyCmd, handle_modify},
+    show::{CliAddressLinkInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
 
 pub(crate) struct AddressCommand;
 
@@ -43,29 +47,119 @@ impl AddressCommand {
                 ),
             )
             .subcommand(
-                clap::Command::new("delete").about("delete address from link"),
+                clap::Command::new("delete")
+                    .about("delete address from link")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
             )
             .subcommand(
                 clap::Command::new("change")
                     .alias("set")
-                    .about("change device attributes"),
+                    .alias("chg")
+                    .about("change address attributes")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("flush")
+                    .about("flush addresses")
+                    .alias("flus")
+                    .alias("flu")
+                    .alias("fl")
+                    .alias("f")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("replace")
+                    .about("change or add address")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
             )
     }
 
     pub(crate) async fn handle(
         matches: &clap::ArgMatches,
-    ) -> Result<Vec<CliLinkInfo>, CliError> {
-        if let Some(_matches) = matches.subcommand_matches("add") {
-            todo!()
-        } else if let Some(matches) = matches.subcommand_matches("show") {
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliAddressLinkInfo>>, CliError> {
+        let handle = ctx.handle();
+        for (subcommand, cmd) in [
+            ("add", AddressModifyCmd::Add),
+            ("change", AddressModifyCmd::Change),
+            ("replace", AddressModifyCmd::Replace),
+            ("delete", AddressModifyCmd::Delete),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(subcommand) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(handle, &opts, get_family(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("flush") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_flush(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_count("STATS") > 0,
+                matches.get_one::<u32>("LOOPS").copied().unwrap_or_default(),
+                matches.get_flag("FORCE"),
+            )
+            .await?;
+            return Ok(None);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("show") {
             let opts: Vec<&str> = matches
                 .get_many::<String>("options")
                 .unwrap_or_default()
                 .map(String::as_str)
                 .collect();
-            handle_show(&opts, matches.get_flag("DETAILS")).await
+            handle_show(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_flag("DETAILS"),
+                matches.get_flag("NUMERIC"),
+                matches.get_flag("RESOLVE"),
+            )
+            .await
+            .map(Into::into)
         } else {
-            handle_show(&[], matches.get_flag("DETAILS")).await
+            handle_show(
+                handle,
+                &[],
+                get_family(matches),
+                matches.get_flag("DETAILS"),
+                matches.get_flag("NUMERIC"),
+                matches.get_flag("RESOLVE"),
+            )
+            .await
+            .map(Into::into)
         }
     }
 }
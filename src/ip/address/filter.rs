@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::{AddressFamily, address::AddressFlags};
+
+use super::show::{ADDRESS_FLAG_DATA, CliAddressInfo};
+use crate::{link::parse_num, prefix::CliIpPrefix};
+
+// Equal to iproute2 `rtnl_rtscope_tab`
+pub(crate) const SCOPE_NAMES: [(&str, u8); 5] = [
+    ("global", 0),
+    ("site", 200),
+    ("link", 253),
+    ("host", 254),
+    ("nowhere", 255),
+];
+
+/// Filters of `ip address show` following the argument grammar of iproute2
+/// `ipaddr_list_flush_or_save()`. The options not related to address are
+/// kept for filtering links.
+#[derive(Debug, Default)]
+pub(crate) struct AddressShowFilter<'a> {
+    pub(crate) link_opts: Vec<&'a str>,
+    /// `None` for `scope all`
+    scope: Option<u8>,
+    prefix: Option<CliIpPrefix>,
+    label: Option<String>,
+    flags: AddressFlags,
+    flag_mask: AddressFlags,
+}
+
+impl<'a> AddressShowFilter<'a> {
+    pub(crate) fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "to" => {
+                    ret.prefix =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "scope" => {
+                    let scope = next_opt(&mut opts)?;
+                    ret.scope = if scope == "all" {
+                        None
+                    } else {
+                        Some(parse_scope(scope, "invalid \"scope\"")?)
+                    };
+                }
+                "label" => {
+                    ret.label = Some(next_opt(&mut opts)?.to_string());
+                }
+                _ if ret.parse_flag(opt) => (),
+                _ => {
+                    ret.link_opts.push(opt);
+                    // Keep the argument of link filter together with its
+                    // keyword
+                    if matches!(*opt, "dev" | "type" | "master") {
+                        ret.link_opts.push(next_opt(&mut opts)?);
+                    }
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    // Like iproute2 `get_filter()`, the flag could be inverted by `-`
+    // prefix, `dynamic` and `primary` are the inverted `permanent` and
+    // `secondary`.
+    fn parse_flag(&mut self, opt: &str) -> bool {
+        let (mut invert, mut name) = match opt.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, opt),
+        };
+        match name {
+            "dynamic" => {
+                invert = !invert;
+                name = "permanent";
+            }
+            "primary" => {
+                invert = !invert;
+                name = "secondary";
+            }
+            _ => (),
+        }
+        let Some(flag_data) = ADDRESS_FLAG_DATA.iter().find(|f| f.name == name)
+        else {
+            return false;
+        };
+        self.flags.set(flag_data.mask, !invert);
+        self.flag_mask.insert(flag_data.mask);
+        true
+    }
+
+    /// Whether `primary` keyword is used, i.e. `secondary` flag is required
+    /// to be unset
+    pub(crate) fn is_primary_only(&self) -> bool {
+        self.flag_mask.contains(AddressFlags::Secondary)
+            && !self.flags.contains(AddressFlags::Secondary)
+    }
+
+    /// Like iproute2 `ipaddr_filter()`, the label is matched against the
+    /// interface name when address has no label.
+    pub(crate) fn matches(&self, addr: &CliAddressInfo, ifname: &str) -> bool {
+        if let Some(scope) = self.scope
+            && addr.raw_scope != scope
+        {
+            return false;
+        }
+        if !((self.flags ^ addr.raw_flags) & self.flag_mask).is_empty() {
+            return false;
+        }
+        if let Some(label) = self.label.as_deref() {
+            let addr_label = if addr.label.is_empty() {
+                ifname
+            } else {
+                addr.label.as_str()
+            };
+            if !glob_match(
+                &label.chars().collect::<Vec<char>>(),
+                &addr_label.chars().collect::<Vec<char>>(),
+            ) {
+                return false;
+            }
+        }
+        if let Some(prefix) = self.prefix.as_ref()
+            && !addr.local_addr.is_some_and(|ip| prefix.contains(&ip))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+// Like iproute2 `rtnl_rtscope_a2n()`, both name and number are supported
+pub(crate) fn parse_scope(
+    value: &str,
+    error_msg: &str,
+) -> Result<u8, CliError> {
+    if let Some((_, scope)) = SCOPE_NAMES.iter().find(|(n, _)| *n == value) {
+        Ok(*scope)
+    } else {
+        parse_num(value, error_msg)
+    }
+}
+
+// Equal to fnmatch(3) without flags used by iproute2 for `label` filter
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => {
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        Some(('?', rest)) => !text.is_empty() && glob_match(rest, &text[1..]),
+        Some(('[', rest)) => {
+            let Some((&c, text_rest)) = text.split_first() else {
+                return false;
+            };
+            match match_bracket(rest, c) {
+                Some((true, pattern_rest)) => {
+                    glob_match(pattern_rest, text_rest)
+                }
+                Some((false, _)) => false,
+                // Unterminated bracket is matched literally
+                None => c == '[' && glob_match(rest, text_rest),
+            }
+        }
+        Some(('\\', rest)) if !rest.is_empty() => {
+            text.first() == Some(&rest[0]) && glob_match(&rest[1..], &text[1..])
+        }
+        Some((p, rest)) => {
+            text.first() == Some(p) && glob_match(rest, &text[1..])
+        }
+    }
+}
+
+// Match a bracket expression like `[a-z]` or `[!0-9]`, return the match
+// result and the remaining pattern after `]`.
+fn match_bracket(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+    let (negate, mut pattern) = match pattern.split_first() {
+        Some(('!' | '^', rest)) => (true, rest),
+        _ => (false, pattern),
+    };
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let (&start, rest) = pattern.split_first()?;
+        // `]` as first character is a literal
+        if start == ']' && !first {
+            return Some((matched != negate, rest));
+        }
+        first = false;
+        if let ['-', end, rest @ ..] = rest
+            && *end != ']'
+        {
+            matched |= (start..=*end).contains(&c);
+            pattern = rest;
+        } else {
+            matched |= start == c;
+            pattern = rest;
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, io::Write};
+
+use futures_util::stream::TryStreamExt;
+use iproute_rs::{CliError, CliNumberFormat};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    address::{AddressFlags, AddressMessage},
+};
+
+use super::{filter::AddressShowFilter, show::parse_nl_msg_to_address};
+
+/// Like iproute2 `ipaddr_flush()`, dump and delete the matching addresses
+/// round by round until nothing left or `max_loops` reached. When
+/// `show_stats` is true, the summary of each round is printed immediately so
+/// it is still visible when flush fails. The failure of deleting an address
+/// stops the flush unless `force` is true, in which case the failure is
+/// reported and the flush continues.
+pub(crate) async fn handle_flush(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    show_stats: bool,
+    max_loops: u32,
+    force: bool,
+) -> Result<(), CliError> {
+    if opts.is_empty() {
+        return Err(CliError::from("Flush requires arguments."));
+    }
+    if family == AddressFamily::Packet {
+        return Err(CliError::from("Cannot flush link addresses."));
+    }
+    let filter = AddressShowFilter::parse(opts, family)?;
+
+    let ifnames: HashMap<u32, String> = crate::link::handle_show(
+        handle,
+        &filter.link_opts,
+        false,
+        0,
+        CliNumberFormat::default(),
+        false,
+    )
+    .await?
+    .into_iter()
+    .map(|link| (link.get_ifindex(), link.get_ifname().to_string()))
+    .collect();
+
+    let mut round = 0;
+    loop {
+        if max_loops != 0 && round >= max_loops {
+            return Err(CliError::from(
+                format!(
+                    "*** Flush remains incomplete after {max_loops} rounds. \
+                     ***"
+                )
+                .as_str(),
+            ));
+        }
+        let flushed =
+            get_matching_addresses(handle, &filter, family, &ifnames).await?;
+        if flushed.is_empty() {
+            break;
+        }
+        round += 1;
+        if show_stats {
+            writeln!(
+                std::io::stdout(),
+                "\n*** Round {round}, deleting {} addresses ***",
+                flushed.len()
+            )
+            .ok();
+        }
+        for nl_msg in flushed {
+            if let Err(e) = delete_address(handle, nl_msg).await {
+                if !force {
+                    return Err(e);
+                }
+                writeln!(std::io::stderr(), "{e}").ok();
+            }
+        }
+        // Flushing primary addresses takes one round only, otherwise the
+        // secondary addresses promoted to primary will be flushed also.
+        if filter.is_primary_only() {
+            break;
+        }
+    }
+
+    if show_stats {
+        if round == 0 {
+            writeln!(std::io::stdout(), "Nothing to flush.").ok();
+        } else {
+            writeln!(
+                std::io::stdout(),
+                "*** Flush is complete after {round} round{} ***",
+                if round > 1 { "s" } else { "" }
+            )
+            .ok();
+        }
+    }
+    Ok(())
+}
+
+async fn get_matching_addresses(
+    handle: &rtnetlink::Handle,
+    filter: &AddressShowFilter<'_>,
+    family: AddressFamily,
+    ifnames: &HashMap<u32, String>,
+) -> Result<Vec<AddressMessage>, CliError> {
+    let mut address_get_handle = handle.address().get();
+    address_get_handle.message_mut().header.family = family;
+
+    let mut ret = Vec::new();
+    let mut addresses = address_get_handle.execute();
+    while let Some(nl_msg) = addresses.try_next().await? {
+        if family != AddressFamily::Unspec && nl_msg.header.family != family {
+            continue;
+        }
+        let Some(ifname) = ifnames.get(&nl_msg.header.index) else {
+            continue;
+        };
+        let addr_info = parse_nl_msg_to_address(nl_msg.clone(), true)?;
+        if filter.matches(&addr_info, ifname) {
+            ret.push(nl_msg);
+        }
+    }
+    Ok(ret)
+}
+
+// Deleting primary address also removes its secondary addresses unless
+// `promote_secondaries` enabled, hence ignore the already removed secondary
+// ones.
+async fn delete_address(
+    handle: &rtnetlink::Handle,
+    nl_msg: AddressMessage,
+) -> Result<(), CliError> {
+    let is_secondary =
+        AddressFlags::from_bits_retain(nl_msg.header.flags.bits().into())
+            .contains(AddressFlags::Secondary);
+    match handle.address().del(nl_msg).execute().await {
+        Err(rtnetlink::Error::NetlinkError(e))
+            if is_secondary
+                && e.code.map(|c| c.get().abs())
+                    == Some(nix::errno::Errno::EADDRNOTAVAIL as i32) => {}
+        Err(e) => {
+            return Err(CliError::from(
+                format!(
+                    "Failed to send flush request: {}",
+                    CliError::from(e).msg
+                )
+                .as_str(),
+            ));
+        }
+        Ok(()) => (),
+    }
+    Ok(())
+}
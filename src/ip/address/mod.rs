@@ -1,9 +1,17 @@
 // SPDX-License-Identifier: MIT
 
 mod cli;
+mod filter;
+mod flush;
+mod modify;
 mod show;
 
 #[cfg(test)]
 mod tests;
 
-pub(crate) use self::{cli::AddressCommand, show::CliAddressInfo};
+pub(crate) use self::{
+    cli::AddressCommand,
+    filter::{SCOPE_NAMES, parse_scope},
+    modify::duplicate_arg,
+    show::{CliAddressInfo, parse_nl_msg_to_address},
+};
@@ -0,0 +1,463 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    io::Write,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use futures_util::stream::{StreamExt, TryStreamExt};
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NLM_F_ACK, NLM_F_REPLACE, NLM_F_REQUEST, NetlinkHeader,
+        NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        address::{
+            AddressAttribute, AddressFlags, AddressHeaderFlags, AddressMessage,
+            AddressProtocol, AddressScope, CacheInfo,
+        },
+    },
+};
+
+use super::{filter::parse_scope, show::IFA_RT_PRIORITY};
+use crate::{
+    link::parse_num,
+    prefix::{CliIpPrefix, parse_ip_addr},
+};
+
+// Equal to iproute2 `rtnl_addrprot_tab`
+const PROTOCOL_NAMES: [(&str, u8); 4] = [
+    ("unspec", 0),
+    ("kernel_lo", 1),
+    ("kernel_ra", 2),
+    ("kernel_ll", 3),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AddressModifyCmd {
+    Add,
+    /// Update existing address only
+    Change,
+    /// Update existing address or create new one
+    Replace,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BroadcastOpt {
+    Addr(Ipv4Addr),
+    /// `brd +`: host bits of the prefix set
+    Set,
+    /// `brd -`: host bits of the prefix cleared
+    Clear,
+}
+
+/// Options of `ip address add`, `change`, `replace` and `delete` following the
+/// argument grammar of iproute2 `ipaddr_modify()`.
+#[derive(Debug, Default)]
+pub(super) struct AddressModifyOptions {
+    iface_name: Option<String>,
+    family: AddressFamily,
+    local: Option<CliIpPrefix>,
+    local_prefix_len_specified: bool,
+    peer: Option<CliIpPrefix>,
+    broadcast: Option<BroadcastOpt>,
+    anycast: Option<IpAddr>,
+    scope: Option<u8>,
+    label: Option<String>,
+    metric: Option<u32>,
+    valid_lft: Option<u32>,
+    preferred_lft: Option<u32>,
+    protocol: Option<u8>,
+    flags: AddressFlags,
+}
+
+impl AddressModifyOptions {
+    pub(super) fn parse(
+        opts: &[&str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            family: match family {
+                AddressFamily::Inet | AddressFamily::Inet6 => family,
+                _ => AddressFamily::Unspec,
+            },
+            ..Default::default()
+        };
+        let mut opts_iter = opts.iter();
+
+        while let Some(opt) = opts_iter.next() {
+            match *opt {
+                "peer" | "remote" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    if ret.peer.is_some() {
+                        return Err(duplicate_arg("peer", value));
+                    }
+                    let peer = CliIpPrefix::parse(value, ret.family)?;
+                    ret.set_family(&peer.addr);
+                    ret.peer = Some(peer);
+                }
+                "broadcast" | "brd" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    if ret.broadcast.is_some() {
+                        return Err(duplicate_arg("broadcast", value));
+                    }
+                    ret.broadcast = Some(match value {
+                        "+" => BroadcastOpt::Set,
+                        "-" => BroadcastOpt::Clear,
+                        _ => match parse_ip_addr(value, ret.family)? {
+                            IpAddr::V4(addr) => {
+                                ret.family = AddressFamily::Inet;
+                                BroadcastOpt::Addr(addr)
+                            }
+                            IpAddr::V6(_) => {
+                                return Err(CliError::from(
+                                    "Broadcast can be set only for IPv4 \
+                                     addresses",
+                                ));
+                            }
+                        },
+                    });
+                }
+                "anycast" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    if ret.anycast.is_some() {
+                        return Err(duplicate_arg("anycast", value));
+                    }
+                    let addr = parse_ip_addr(value, ret.family)?;
+                    ret.set_family(&addr);
+                    ret.anycast = Some(addr);
+                }
+                "scope" => {
+                    ret.scope = Some(parse_scope(
+                        next_opt(&mut opts_iter)?,
+                        "invalid scope value.",
+                    )?);
+                }
+                "dev" => {
+                    ret.iface_name =
+                        Some(next_opt(&mut opts_iter)?.to_string());
+                }
+                "label" => {
+                    ret.label = Some(next_opt(&mut opts_iter)?.to_string());
+                }
+                "metric" | "priority" | "preference" => {
+                    ret.metric = Some(parse_num(
+                        next_opt(&mut opts_iter)?,
+                        "\"metric\" value is invalid",
+                    )?);
+                }
+                "valid_lft" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    if ret.valid_lft.is_some() {
+                        return Err(duplicate_arg("valid_lft", value));
+                    }
+                    ret.valid_lft = Some(parse_lifetime(value, "valid_lft")?);
+                }
+                "preferred_lft" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    if ret.preferred_lft.is_some() {
+                        return Err(duplicate_arg("preferred_lft", value));
+                    }
+                    ret.preferred_lft =
+                        Some(parse_lifetime(value, "preferred_lft")?);
+                }
+                "proto" => {
+                    let value = next_opt(&mut opts_iter)?;
+                    ret.protocol = Some(
+                        match PROTOCOL_NAMES.iter().find(|(n, _)| *n == value) {
+                            Some((_, proto)) => *proto,
+                            None => {
+                                parse_num(value, "\"proto\" value is invalid")?
+                            }
+                        },
+                    );
+                }
+                "mngtmpaddr" => ret.flags |= AddressFlags::Managetempaddr,
+                "noprefixroute" => ret.flags |= AddressFlags::Noprefixroute,
+                "autojoin" => ret.flags |= AddressFlags::Mcautojoin,
+                "home" => ret.flags |= AddressFlags::Homeaddress,
+                "nodad" => ret.flags |= AddressFlags::Nodad,
+                "optimistic" => ret.flags |= AddressFlags::Optimistic,
+                _ => {
+                    let value = if *opt == "local" {
+                        next_opt(&mut opts_iter)?
+                    } else {
+                        opt
+                    };
+                    if ret.local.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "Either \"local\" is duplicate, or \
+                                 \"{value}\" is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    let local = CliIpPrefix::parse(value, ret.family)?;
+                    ret.set_family(&local.addr);
+                    ret.local = Some(local);
+                    ret.local_prefix_len_specified = value.contains('/');
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    // Like iproute2, the first address decides the family of follow up
+    // addresses
+    fn set_family(&mut self, addr: &IpAddr) {
+        if self.family == AddressFamily::Unspec {
+            self.family = match addr {
+                IpAddr::V4(_) => AddressFamily::Inet,
+                IpAddr::V6(_) => AddressFamily::Inet6,
+            };
+        }
+    }
+
+    // Like iproute2, deleting IPv4 address without prefix length and peer
+    // removes the address regardless of its prefix length
+    fn is_wildcard_delete(&self, cmd: AddressModifyCmd) -> bool {
+        cmd == AddressModifyCmd::Delete
+            && self.peer.is_none()
+            && !self.local_prefix_len_specified
+            && matches!(self.local.map(|l| l.addr), Some(IpAddr::V4(_)))
+    }
+
+    async fn to_nl_msg(
+        &self,
+        handle: &rtnetlink::Handle,
+        cmd: AddressModifyCmd,
+    ) -> Result<AddressMessage, CliError> {
+        let Some(iface_name) = self.iface_name.as_deref() else {
+            return Err(CliError::from(
+                "Not enough information: \"dev\" argument is required.",
+            ));
+        };
+        if let Some(label) = self.label.as_deref()
+            && !label.starts_with(iface_name)
+        {
+            return Err(CliError::from(
+                format!(
+                    "\"dev\" ({iface_name}) must match \"label\" ({label})."
+                )
+                .as_str(),
+            ));
+        }
+
+        let mut nl_msg = AddressMessage::default();
+        nl_msg.header.family = self.family;
+
+        // Like iproute2, the prefix length of peer takes precedence and the
+        // local address is also the peer address if peer not defined.
+        let peer = self.peer.or(self.local);
+        nl_msg.header.prefix_len =
+            peer.map(|p| p.prefix_len).unwrap_or_default();
+        if let Some(local) = self.local {
+            nl_msg.attributes.push(AddressAttribute::Local(local.addr));
+        }
+        if let Some(peer) = peer {
+            if self.is_wildcard_delete(cmd) {
+                // Without `IFA_ADDRESS`, kernel ignores the prefix length
+                writeln!(
+                    std::io::stderr(),
+                    "Warning: Executing wildcard deletion to stay compatible \
+                     with old scripts.\n         Explicitly specify the \
+                     prefix length ({}/{}) to avoid this warning.\n         \
+                     This special behaviour is likely to disappear in further \
+                     releases,\n         fix your scripts!",
+                    peer.addr,
+                    peer.prefix_len
+                )
+                .ok();
+            } else {
+                nl_msg.attributes.push(AddressAttribute::Address(peer.addr));
+            }
+        }
+
+        match (self.broadcast, peer) {
+            (Some(BroadcastOpt::Addr(addr)), _) => {
+                nl_msg.attributes.push(AddressAttribute::Broadcast(addr));
+            }
+            (Some(opt), Some(peer)) if cmd != AddressModifyCmd::Delete => {
+                let IpAddr::V4(addr) = peer.addr else {
+                    return Err(CliError::from(
+                        "Broadcast can be set only for IPv4 addresses",
+                    ));
+                };
+                // Like iproute2, no broadcast for /31 and /32
+                if peer.prefix_len <= 30 {
+                    let host_mask = u32::MAX >> peer.prefix_len;
+                    let brd = if matches!(opt, BroadcastOpt::Set) {
+                        u32::from(addr) | host_mask
+                    } else {
+                        u32::from(addr) & !host_mask
+                    };
+                    nl_msg
+                        .attributes
+                        .push(AddressAttribute::Broadcast(Ipv4Addr::from(brd)));
+                }
+            }
+            _ => (),
+        }
+        if let Some(IpAddr::V6(addr)) = self.anycast {
+            nl_msg.attributes.push(AddressAttribute::Anycast(addr));
+        }
+        if let Some(label) = self.label.as_ref() {
+            nl_msg
+                .attributes
+                .push(AddressAttribute::Label(label.clone()));
+        }
+        if let Some(metric) = self.metric {
+            nl_msg
+                .attributes
+                .push(AddressAttribute::Other(DefaultNla::new(
+                    IFA_RT_PRIORITY,
+                    metric.to_ne_bytes().to_vec(),
+                )));
+        }
+        if let Some(protocol) = self.protocol {
+            nl_msg.attributes.push(AddressAttribute::Protocol(
+                AddressProtocol::from(protocol),
+            ));
+        }
+
+        // Like iproute2 `default_scope()`, loopback IPv4 address is host
+        // scope by default
+        nl_msg.header.scope = AddressScope::from(match self.scope {
+            Some(scope) => scope,
+            None if cmd == AddressModifyCmd::Delete => 0,
+            None => match self.local.map(|l| l.addr) {
+                Some(IpAddr::V4(addr)) if addr.octets()[0] == 127 => 254,
+                _ => 0,
+            },
+        });
+
+        // The flags not fit into header are stored in `IFA_FLAGS`
+        if let Ok(flags) = u8::try_from(self.flags.bits()) {
+            nl_msg.header.flags = AddressHeaderFlags::from_bits_retain(flags);
+        } else {
+            nl_msg.attributes.push(AddressAttribute::Flags(self.flags));
+        }
+
+        if self.valid_lft.is_some() || self.preferred_lft.is_some() {
+            let valid_lft = self.valid_lft.unwrap_or(u32::MAX);
+            let preferred_lft = self.preferred_lft.unwrap_or(u32::MAX);
+            if valid_lft == 0 {
+                return Err(CliError::from("valid_lft is zero"));
+            }
+            if valid_lft < preferred_lft {
+                return Err(CliError::from(
+                    "preferred_lft is greater than valid_lft",
+                ));
+            }
+            let mut cache_info = CacheInfo::default();
+            cache_info.ifa_valid = valid_lft;
+            cache_info.ifa_preferred = preferred_lft;
+            nl_msg
+                .attributes
+                .push(AddressAttribute::CacheInfo(cache_info));
+        }
+
+        nl_msg.header.index = handle
+            .link()
+            .get()
+            .match_name(iface_name.to_string())
+            .execute()
+            .try_next()
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                CliError::from(
+                    format!("Cannot find device \"{iface_name}\"").as_str(),
+                )
+            })?
+            .header
+            .index;
+
+        Ok(nl_msg)
+    }
+}
+
+pub(crate) fn duplicate_arg(key: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("duplicate \"{key}\": \"{value}\" is the second value.")
+            .as_str(),
+    )
+}
+
+// Like iproute2 `set_lifetime()`, `forever` means infinity lifetime
+fn parse_lifetime(value: &str, name: &str) -> Result<u32, CliError> {
+    if value == "forever" {
+        Ok(u32::MAX)
+    } else {
+        parse_num(value, &format!("{name} value"))
+    }
+}
+
+pub(crate) async fn handle_modify(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    cmd: AddressModifyCmd,
+) -> Result<(), CliError> {
+    let nl_msg = AddressModifyOptions::parse(opts, family)?
+        .to_nl_msg(handle, cmd)
+        .await?;
+
+    match cmd {
+        AddressModifyCmd::Add | AddressModifyCmd::Replace => {
+            let Some(addr) = nl_msg.attributes.iter().find_map(|a| match a {
+                AddressAttribute::Local(addr)
+                | AddressAttribute::Address(addr) => Some(*addr),
+                _ => None,
+            }) else {
+                return Err(CliError::from(
+                    "Not enough information: \"local\" argument is required.",
+                ));
+            };
+
+            let mut request = handle.address().add(
+                nl_msg.header.index,
+                addr,
+                nl_msg.header.prefix_len,
+            );
+            *request.message_mut() = nl_msg;
+            if cmd == AddressModifyCmd::Replace {
+                request = request.replace();
+            }
+            request.execute().await?;
+        }
+        AddressModifyCmd::Change => change_address(handle, nl_msg).await?,
+        AddressModifyCmd::Delete => {
+            handle.address().del(nl_msg).execute().await?;
+        }
+    }
+    Ok(())
+}
+
+// The rtnetlink crate has no request for `NLM_F_REPLACE` without
+// `NLM_F_CREATE`, which updates existing address only.
+async fn change_address(
+    handle: &rtnetlink::Handle,
+    nl_msg: AddressMessage,
+) -> Result<(), CliError> {
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewAddress(nl_msg)),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_REPLACE;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
@@ -1,17 +1,32 @@
 // SPDX-License-Identifier: MIT
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+};
 
 use futures_util::TryStreamExt;
 use indexmap::IndexMap;
-use iproute_rs::{CanDisplay, CanOutput, CliColor, write_with_color};
-use rtnetlink::packet_route::{
-    AddressFamily,
-    address::{AddressAttribute, AddressFlags, AddressMessage, AddressScope},
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliNumberFormat, link::CliLinkInfo,
+    resolve_hostnames, write_with_color,
+};
+use rtnetlink::{
+    packet_core::Nla,
+    packet_route::{
+        AddressFamily,
+        address::{
+            AddressAttribute, AddressFlags, AddressMessage, AddressScope,
+        },
+    },
 };
 use serde::Serialize;
 
-use crate::{CliError, link::CliLinkInfo};
+use super::filter::AddressShowFilter;
+use crate::{
+    CliError,
+    link::{LinkShowFilter, query_iface_index},
+};
 
 #[derive(Serialize, Default)]
 pub(crate) struct CliAddressInfo {
@@ -19,28 +34,45 @@ pub(crate) struct CliAddressInfo {
     index: u32,
     family: String,
     local: String,
+    /// Peer address of point-to-point link, shown when `IFA_ADDRESS`
+    /// differs from `IFA_LOCAL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
     prefixlen: u8,
     #[serde(skip_serializing_if = "Option::is_none")]
+    metric: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     broadcast: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anycast: Option<String>,
     scope: String,
     #[serde(flatten, skip_serializing_if = "IndexMap::is_empty")]
     flags: IndexMap<String, bool>,
     #[serde(skip_serializing_if = "String::is_empty")]
     protocol: String,
     #[serde(skip_serializing_if = "String::is_empty")]
-    label: String,
+    pub(super) label: String,
     valid_life_time: u32,
     preferred_life_time: u32,
+    #[serde(skip)]
+    pub(super) raw_scope: u8,
+    #[serde(skip)]
+    pub(super) raw_flags: AddressFlags,
+    #[serde(skip)]
+    pub(super) local_addr: Option<IpAddr>,
 }
 
+// The `IFA_RT_PRIORITY` is not supported by netlink-packet-route yet
+pub(super) const IFA_RT_PRIORITY: u16 = 9;
+
 #[derive(Clone, Copy)]
-struct AddressFlagData {
-    name: &'static str,
-    mask: AddressFlags,
+pub(super) struct AddressFlagData {
+    pub(super) name: &'static str,
+    pub(super) mask: AddressFlags,
 }
 
 // equal to iproute2 `struct ifa_flag_data_t` in `ipaddress.c`
-const ADDRESS_FLAG_DATA: &[AddressFlagData] = &[
+pub(super) const ADDRESS_FLAG_DATA: &[AddressFlagData] = &[
     AddressFlagData {
         name: "secondary",
         mask: AddressFlags::Secondary,
@@ -104,7 +136,19 @@ impl std::fmt::Display for CliAddressInfo {
             "{}",
             self.local
         )?;
+        if let Some(peer) = &self.address {
+            write!(f, " peer ")?;
+            write_with_color!(
+                f,
+                CliColor::address_color(&self.family),
+                "{}",
+                peer
+            )?;
+        }
         write!(f, "/{}", self.prefixlen)?;
+        if let Some(metric) = self.metric {
+            write!(f, " metric {metric}")?;
+        }
         if let Some(broadcast) = &self.broadcast {
             write!(f, " brd ")?;
             write_with_color!(
@@ -114,6 +158,15 @@ impl std::fmt::Display for CliAddressInfo {
                 broadcast
             )?;
         }
+        if let Some(anycast) = &self.anycast {
+            write!(f, " any ")?;
+            write_with_color!(
+                f,
+                CliColor::address_color(&self.family),
+                "{}",
+                anycast
+            )?;
+        }
         write!(f, " scope {} ", self.scope)?;
         self.write_flags(f)?;
 
@@ -160,8 +213,86 @@ impl CanDisplay for CliAddressInfo {
 
 impl CanOutput for CliAddressInfo {}
 
-fn addr_scope_to_cli_string(addr_scope: &AddressScope) -> String {
+/// Interface of `ip address show` with its addresses appended
+#[derive(Serialize)]
+pub(crate) struct CliAddressLinkInfo {
+    #[serde(flatten)]
+    link: CliLinkInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    addr_info: Option<Vec<CliAddressInfo>>,
+}
+
+impl CliAddressLinkInfo {
+    // Like iproute2, `-family link` shows no address.
+    fn new(
+        mut link: CliLinkInfo,
+        family: AddressFamily,
+        include_details: bool,
+    ) -> Self {
+        link.show_only_addr_details(family, include_details);
+        Self {
+            link,
+            addr_info: (family != AddressFamily::Packet).then(Vec::new),
+        }
+    }
+
+    fn add_address(&mut self, addr_info: CliAddressInfo) {
+        self.addr_info.get_or_insert_default().push(addr_info);
+    }
+
+    fn has_address(&self) -> bool {
+        self.addr_info.as_ref().is_some_and(|a| !a.is_empty())
+    }
+}
+
+impl std::fmt::Display for CliAddressLinkInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.link)?;
+        if let Some(addr_info) = &self.addr_info {
+            for addr in addr_info {
+                write!(f, "\n    {}", addr)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliAddressLinkInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, `ip -oneline address` prints a line for each address
+    // without the link information.
+    fn gen_oneline_string(&self) -> String {
+        let Some(addr_info) = self.addr_info.as_ref() else {
+            return self.to_string().replace('\n', "\\");
+        };
+        let lines: Vec<String> = addr_info
+            .iter()
+            .map(|addr| {
+                format!(
+                    "{}: {}{:<5} {}{addr}",
+                    self.link.get_ifindex(),
+                    CliColor::IfaceName,
+                    self.link.get_ifname(),
+                    CliColor::Clear,
+                )
+                .replace('\n', "\\")
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+impl CanOutput for CliAddressLinkInfo {}
+
+fn addr_scope_to_cli_string(
+    addr_scope: &AddressScope,
+    numeric: bool,
+) -> String {
     match addr_scope {
+        _ if numeric => u8::from(*addr_scope).to_string(),
         AddressScope::Universe => "global".to_string(),
         _ => addr_scope.to_string(),
     }
@@ -200,15 +331,19 @@ fn get_address_flags(
     ret
 }
 
-fn parse_nl_msg_to_address(
+pub(crate) fn parse_nl_msg_to_address(
     nl_msg: AddressMessage,
+    numeric: bool,
 ) -> Result<CliAddressInfo, CliError> {
     let index = nl_msg.header.index;
     let family = nl_msg.header.family.to_string();
-    let mut local = String::new();
+    let mut local = None;
+    let mut address = None;
     let prefixlen = nl_msg.header.prefix_len;
     let mut broadcast = None;
-    let scope = addr_scope_to_cli_string(&nl_msg.header.scope);
+    let mut anycast = None;
+    let mut metric = None;
+    let scope = addr_scope_to_cli_string(&nl_msg.header.scope, numeric);
     let mut flags =
         AddressFlags::from_bits_retain(nl_msg.header.flags.bits().into());
     let mut label = String::new();
@@ -219,14 +354,24 @@ fn parse_nl_msg_to_address(
     for nla in nl_msg.attributes {
         match nla {
             AddressAttribute::Local(a) => {
-                local = a.to_string();
+                local = Some(a);
             }
-            AddressAttribute::Address(a) if local.is_empty() => {
-                local = a.to_string();
+            AddressAttribute::Address(a) => {
+                address = Some(a);
             }
             AddressAttribute::Broadcast(a) => {
                 broadcast = Some(a.to_string());
             }
+            AddressAttribute::Anycast(a) => {
+                anycast = Some(a.to_string());
+            }
+            AddressAttribute::Other(nla)
+                if nla.kind() == IFA_RT_PRIORITY && nla.value_len() == 4 =>
+            {
+                let mut buf = [0u8; 4];
+                nla.emit_value(&mut buf);
+                metric = Some(u32::from_ne_bytes(buf));
+            }
             AddressAttribute::Label(s) => {
                 label = s;
             }
@@ -238,7 +383,11 @@ fn parse_nl_msg_to_address(
                 flags = f;
             }
             AddressAttribute::Protocol(p) => {
-                protocol = p.to_string();
+                protocol = if numeric {
+                    u8::from(p).to_string()
+                } else {
+                    p.to_string()
+                };
             }
             _ => {
                 // println!("Remains {:?}", nla);
@@ -246,72 +395,168 @@ fn parse_nl_msg_to_address(
         }
     }
 
+    // Like iproute2 `print_addrinfo()`, `IFA_ADDRESS` is the peer address
+    // when it differs from `IFA_LOCAL`
+    let local_addr = local.or(address);
+    let (local, address) = match (local, address) {
+        (Some(local), Some(address)) if local != address => {
+            (local.to_string(), Some(address.to_string()))
+        }
+        (Some(local), _) => (local.to_string(), None),
+        (None, Some(address)) => (address.to_string(), None),
+        (None, None) => (String::new(), None),
+    };
+
     let cli_addr_info = CliAddressInfo {
         index,
         family,
         local,
+        address,
         prefixlen,
+        metric,
         broadcast,
+        anycast,
         scope,
         flags: get_address_flags(nl_msg.header.family, flags),
+        raw_flags: flags,
         label,
         valid_life_time,
         preferred_life_time,
         protocol,
+        raw_scope: u8::from(nl_msg.header.scope),
+        local_addr,
     };
 
     Ok(cli_addr_info)
 }
 
+// Like iproute2 `format_host()`, replace the IP address with hostname if
+// resolved.
+async fn resolve_address_hostnames(addrs: &mut [CliAddressInfo]) {
+    let ips: Vec<IpAddr> = addrs
+        .iter()
+        .flat_map(|addr| {
+            std::iter::once(addr.local.as_str())
+                .chain(addr.address.as_deref())
+                .chain(addr.broadcast.as_deref())
+                .chain(addr.anycast.as_deref())
+        })
+        .filter_map(|ip| ip.parse().ok())
+        .collect();
+
+    let hostnames = resolve_hostnames(&ips).await;
+
+    let lookup = |ip: &str| -> Option<String> {
+        hostnames.get(&ip.parse::<IpAddr>().ok()?).cloned()
+    };
+
+    for addr in addrs.iter_mut() {
+        if let Some(name) = lookup(&addr.local) {
+            addr.local = name;
+        }
+        if let Some(name) = addr.address.as_deref().and_then(lookup) {
+            addr.address = Some(name);
+        }
+        if let Some(name) = addr.broadcast.as_deref().and_then(lookup) {
+            addr.broadcast = Some(name);
+        }
+        if let Some(name) = addr.anycast.as_deref().and_then(lookup) {
+            addr.anycast = Some(name);
+        }
+    }
+}
+
 pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
     opts: &[&str],
+    family: AddressFamily,
     include_details: bool,
-) -> Result<Vec<CliLinkInfo>, CliError> {
-    let (connection, handle, _) = rtnetlink::new_connection()?;
-
-    tokio::spawn(connection);
-
+    numeric: bool,
+    resolve: bool,
+) -> Result<Vec<CliAddressLinkInfo>, CliError> {
+    let filter = AddressShowFilter::parse(opts, family)?;
     let mut address_get_handle = handle.address().get();
+    address_get_handle.message_mut().header.family = family;
 
-    if let Some(iface_name) = opts.first() {
-        let link_get_handle =
-            handle.link().get().match_name(iface_name.to_string());
-        let link =
-            link_get_handle.execute().try_next().await?.ok_or_else(|| {
-                CliError::from(
-                    format!("Device \"{iface_name}\" does not exist.").as_str(),
-                )
-            })?;
-        address_get_handle =
-            address_get_handle.set_link_index_filter(link.header.index);
+    if let Some(iface_name) =
+        LinkShowFilter::parse(&filter.link_opts)?.iface_name
+    {
+        let index =
+            query_iface_index(handle, &iface_name)
+                .await?
+                .ok_or_else(|| {
+                    CliError::from(
+                        format!("Device \"{iface_name}\" does not exist.")
+                            .as_str(),
+                    )
+                })?;
+        address_get_handle = address_get_handle.set_link_index_filter(index);
     }
 
-    let mut addresses = address_get_handle.execute();
     let mut addresses_infos: Vec<CliAddressInfo> = Vec::new();
 
-    while let Some(nl_msg) = addresses.try_next().await? {
-        addresses_infos.push(parse_nl_msg_to_address(nl_msg)?);
+    // Like iproute2, `-family link` shows links only
+    if family != AddressFamily::Packet {
+        let mut addresses = address_get_handle.execute();
+        while let Some(nl_msg) = addresses.try_next().await? {
+            // Kernel dumps all families when the requested family has no
+            // address dump support, e.g. bridge.
+            if family != AddressFamily::Unspec && nl_msg.header.family != family
+            {
+                continue;
+            }
+            addresses_infos.push(parse_nl_msg_to_address(nl_msg, numeric)?);
+        }
+    }
+
+    if resolve {
+        resolve_address_hostnames(&mut addresses_infos).await;
     }
 
-    let mut links_info: HashMap<u32, _> =
-        crate::link::handle_show(opts, include_details)
-            .await?
-            .into_iter()
-            .map(|mut link_info| {
-                link_info.show_only_addr_details();
-                link_info
-            })
-            .map(|link_info| (link_info.get_ifindex(), link_info))
-            .collect();
+    // TODO: iproute2 prints link statistics after the addresses for
+    // `ip -s address`
+    let mut links_info: HashMap<u32, _> = crate::link::handle_show(
+        handle,
+        &filter.link_opts,
+        include_details,
+        0,
+        CliNumberFormat::default(),
+        numeric,
+    )
+    .await?
+    .into_iter()
+    .map(|link_info| {
+        (
+            link_info.get_ifindex(),
+            CliAddressLinkInfo::new(link_info, family, include_details),
+        )
+    })
+    .collect();
 
+    let mut ifindexes_with_addr: HashSet<u32> = HashSet::new();
     for addr_info in addresses_infos {
-        if let Some(link_info) = links_info.get_mut(&addr_info.index) {
+        ifindexes_with_addr.insert(addr_info.index);
+        if let Some(link_info) = links_info.get_mut(&addr_info.index)
+            && filter.matches(&addr_info, link_info.link.get_ifname())
+        {
             link_info.add_address(addr_info);
         }
     }
 
-    let mut result: Vec<CliLinkInfo> = links_info.into_values().collect();
-    result.sort_by_key(|link| link.get_ifindex());
+    let mut result: Vec<CliAddressLinkInfo> =
+        links_info.into_values().collect();
+    // Like iproute2 `ipaddr_filter()`, links without matching address are
+    // not shown, but links without any address are still shown when
+    // family is not specified
+    result.retain(|link| {
+        link.has_address()
+            || (!ifindexes_with_addr.contains(&link.link.get_ifindex())
+                && matches!(
+                    family,
+                    AddressFamily::Unspec | AddressFamily::Packet
+                ))
+    });
+    result.sort_by_key(|link| link.link.get_ifindex());
 
     Ok(result)
 }
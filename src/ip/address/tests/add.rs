@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+// Add address by iproute2 and by us, the outputs of `ip address show` should
+// be identical.
+fn assert_address_add(dummy_name: &str, add_args: &[&str]) {
+    let show_args = ["-d", "-j", "address", "show", "dev", dummy_name];
+
+    exec_cmd(&[&["ip", "address", "add"], add_args].concat());
+    let expected_output = exec_cmd(&[&["ip"], &show_args[..]].concat());
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    let our_output = ip_rs_exec_cmd(&[&["address", "add"], add_args].concat());
+    assert!(our_output.is_empty());
+    let our_output = exec_cmd(&[&["ip"], &show_args[..]].concat());
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_add() {
+    let dummy_name = "atest-adummy0";
+    let label = format!("{dummy_name}:1");
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["192.0.2.1/24", "dev", dummy_name][..],
+            &["192.0.2.1/24", "brd", "+", "dev", dummy_name][..],
+            &["192.0.2.1/24", "broadcast", "-", "dev", dummy_name][..],
+            &["192.0.2.1/24", "brd", "192.0.2.127", "dev", dummy_name][..],
+            &["192.0.2.1", "peer", "192.0.2.2/32", "dev", dummy_name][..],
+            &["192.0.2.1/24", "dev", dummy_name, "label", &label][..],
+            &["127.0.0.2/8", "dev", dummy_name][..],
+            &["192.0.2.1/24", "dev", dummy_name, "scope", "link"][..],
+            &["192.0.2.1/24", "dev", dummy_name, "metric", "100"][..],
+            &["192.0.2.1/24", "dev", dummy_name, "noprefixroute"][..],
+            &[
+                "2001:db8::1/64",
+                "dev",
+                dummy_name,
+                "nodad",
+                "home",
+                "mngtmpaddr",
+                "proto",
+                "kernel_ra",
+            ][..],
+            &[
+                "local",
+                "2001:db8::2/64",
+                "dev",
+                dummy_name,
+                "valid_lft",
+                "forever",
+                "preferred_lft",
+                "0",
+            ][..],
+        ] {
+            assert_address_add(dummy_name, args);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_address_add_without_dev() {
+    let output = ip_rs_exec_cmd_output(&["address", "add", "192.0.2.1/24"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Not enough information: \"dev\" argument is required.")
+    );
+}
@@ -54,6 +54,240 @@ fn test_address_detailed_show_json() {
     });
 }
 
+#[test]
+fn test_address_show_oneline_lo() {
+    let expected_output = exec_cmd(&["ip", "-o", "address", "show", "lo"]);
+    let our_output = ip_rs_exec_cmd(&["-o", "address", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_show_dev_lo() {
+    let expected_output = exec_cmd(&["ip", "address", "show", "dev", "lo"]);
+    let our_output = ip_rs_exec_cmd(&["address", "show", "dev", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_show_ifindex_lo() {
+    let expected_output = exec_cmd(&["ip", "address", "show", "if1"]);
+    let our_output = ip_rs_exec_cmd(&["address", "show", "if1"]);
+
+    assert!(our_output.contains("lo"));
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_show_numeric() {
+    let dummy_name = "atest-dummy5";
+
+    with_dummy_iface(dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-N", "address", "show", dummy_name]);
+        let our_output = ip_rs_exec_cmd(&["-N", "address", "show", dummy_name]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+}
+
+#[test]
+fn test_address_show_resolve_lo() {
+    let expected_output = exec_cmd(&["ip", "-r", "address", "show", "lo"]);
+    let our_output = ip_rs_exec_cmd(&["-r", "address", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_show_family_lo() {
+    for family in ["-4", "-6", "-0", "-B", "-M"] {
+        let expected_output =
+            exec_cmd(&["ip", family, "address", "show", "lo"]);
+        let our_output = ip_rs_exec_cmd(&[family, "address", "show", "lo"]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    }
+}
+
+#[test]
+fn test_address_show_family_json_lo() {
+    for family in ["inet", "inet6", "link"] {
+        let expected_output =
+            exec_cmd(&["ip", "-j", "-family", family, "address", "show", "lo"]);
+        let our_output =
+            ip_rs_exec_cmd(&["-j", "-family", family, "address", "show", "lo"]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    }
+}
+
+#[test]
+fn test_address_show_family_details_lo() {
+    let expected_output =
+        exec_cmd(&["ip", "-6", "-d", "address", "show", "lo"]);
+    let our_output = ip_rs_exec_cmd(&["-6", "-d", "address", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_show_peer() {
+    let dummy_name = "atest-dummy6";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&[
+        "ip",
+        "addr",
+        "add",
+        "10.1.1.1",
+        "peer",
+        "10.1.1.2/32",
+        "dev",
+        dummy_name,
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&[][..], &["-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["address", "show", dummy_name]].concat(),
+            );
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["address", "show", dummy_name]].concat(),
+            );
+
+            assert!(our_output.contains("10.1.1.2"));
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_address_show_metric_and_proto() {
+    let dummy_name = "atest-dummy9";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&[
+        "ip",
+        "addr",
+        "add",
+        "192.168.9.1/24",
+        "brd",
+        "+",
+        "dev",
+        dummy_name,
+        "metric",
+        "20",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "addr",
+        "add",
+        "2001:db8:9::1/64",
+        "dev",
+        dummy_name,
+        "metric",
+        "30",
+        "proto",
+        "kernel_ll",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&[][..], &["-j"][..], &["-N"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["address", "show", dummy_name]].concat(),
+            );
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["address", "show", dummy_name]].concat(),
+            );
+
+            assert!(our_output.contains("metric"));
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_address_show_filters() {
+    let dummy_name = "atest-dummy7";
+
+    with_dummy_iface(dummy_name, || {
+        for filter in [
+            &["scope", "global"][..],
+            &["scope", "link"][..],
+            &["scope", "all"][..],
+            &["to", "192.168.1.2"][..],
+            &["to", "2001:db8:beef::/48"][..],
+            &["to", "10/8"][..],
+            &["label", "atest-dummy?"][..],
+            &["label", "atest-*[0-9]", "scope", "host"][..],
+        ] {
+            for args in [&[][..], &["-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[
+                        &["ip"],
+                        args,
+                        &["address", "show", "dev", dummy_name],
+                        filter,
+                    ]
+                    .concat(),
+                );
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["address", "show", "dev", dummy_name], filter]
+                        .concat(),
+                );
+
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_address_show_state_filters() {
+    let dummy_name = "atest-dummy8";
+
+    with_dummy_iface(dummy_name, || {
+        for filter in [
+            &["permanent"][..],
+            &["dynamic"][..],
+            &["primary"][..],
+            &["secondary"][..],
+            &["-home"][..],
+            &["mngtmpaddr", "dynamic"][..],
+            &["tentative"][..],
+            &["deprecated"][..],
+            &["dadfailed"][..],
+            &["temporary"][..],
+        ] {
+            for args in [&[][..], &["-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[
+                        &["ip"],
+                        args,
+                        &["address", "show", "dev", dummy_name],
+                        filter,
+                    ]
+                    .concat(),
+                );
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["address", "show", "dev", dummy_name], filter]
+                        .concat(),
+                );
+
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+}
+
 #[test]
 fn test_address_alias_a_s() {
     assert_alias_output(&["address", "show", "lo"], &["a", "s", "lo"]);
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+// Update the address by iproute2 and by us, the outputs of
+// `ip address show` should be identical.
+fn assert_address_update(dummy_name: &str, cmd: &str, args: &[&str]) {
+    let show_args = ["ip", "-j", "address", "show", "dev", dummy_name];
+    let setup = || {
+        exec_cmd(&[
+            "ip",
+            "address",
+            "add",
+            "2001:db8::1/64",
+            "dev",
+            dummy_name,
+            "nodad",
+        ]);
+        exec_cmd(&["ip", "address", "add", "192.0.2.1/24", "dev", dummy_name]);
+    };
+
+    setup();
+    exec_cmd(&[&["ip", "address", cmd], args].concat());
+    let expected_output = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    setup();
+    let our_output = ip_rs_exec_cmd(&[&["address", cmd], args].concat());
+    assert!(our_output.is_empty());
+    let our_output = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_change_and_replace() {
+    let dummy_name = "atest-cdummy0";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        for (cmd, args) in [
+            (
+                "change",
+                &[
+                    "2001:db8::1/64",
+                    "dev",
+                    dummy_name,
+                    "preferred_lft",
+                    "0",
+                    "noprefixroute",
+                ][..],
+            ),
+            (
+                "change",
+                &["192.0.2.1/24", "dev", dummy_name, "brd", "+"][..],
+            ),
+            (
+                "replace",
+                &["192.0.2.1/24", "dev", dummy_name, "valid_lft", "forever"][..],
+            ),
+            ("replace", &["198.51.100.1/24", "dev", dummy_name][..]),
+        ] {
+            assert_address_update(dummy_name, cmd, args);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+const ADDRESSES: [&str; 4] = [
+    "192.0.2.1/24",
+    "192.0.2.2/24",
+    "198.51.100.1/32",
+    "2001:db8::1/64",
+];
+
+fn add_addresses(dummy_name: &str) {
+    for addr in ADDRESSES {
+        exec_cmd(&["ip", "address", "add", addr, "dev", dummy_name, "nodad"]);
+    }
+}
+
+// Delete address by iproute2 and by us, the remaining addresses should be
+// identical.
+fn assert_address_delete(dummy_name: &str, del_args: &[&str]) {
+    let show_args = ["ip", "-j", "address", "show", "dev", dummy_name];
+
+    add_addresses(dummy_name);
+    exec_cmd(&[&["ip", "address", "delete"], del_args].concat());
+    let expected_output = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    add_addresses(dummy_name);
+    let our_output = ip_rs_exec_cmd(&[&["address", "del"], del_args].concat());
+    assert!(our_output.is_empty());
+    let our_output = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_address_delete() {
+    let dummy_name = "atest-ddummy0";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["192.0.2.1/24", "dev", dummy_name][..],
+            &["192.0.2.2/24", "dev", dummy_name][..],
+            &["dev", dummy_name, "local", "198.51.100.1/32"][..],
+            // Wildcard deletion ignoring the prefix length
+            &["192.0.2.2", "dev", dummy_name][..],
+            &["2001:db8::1/64", "dev", dummy_name][..],
+        ] {
+            assert_address_delete(dummy_name, args);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_address_delete_absent() {
+    let dummy_name = "atest-ddummy1";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let output = ip_rs_exec_cmd_output(&[
+        "address",
+        "delete",
+        "192.0.2.1/24",
+        "dev",
+        dummy_name,
+    ]);
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("RTNETLINK answers: Cannot assign requested address")
+    );
+}
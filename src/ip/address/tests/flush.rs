@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+const ADDRESSES: [&str; 4] = [
+    "192.0.2.1/24",
+    "192.0.2.2/24",
+    "198.51.100.1/24",
+    "2001:db8::1/64",
+];
+
+fn add_addresses(dummy_name: &str) {
+    for addr in ADDRESSES {
+        exec_cmd(&["ip", "address", "add", addr, "dev", dummy_name, "nodad"]);
+    }
+}
+
+// Flush by iproute2 and by us, the outputs and remaining addresses should
+// be identical.
+fn assert_address_flush(dummy_name: &str, args: &[&str]) {
+    let show_args = ["ip", "-j", "address", "show", "dev", dummy_name];
+
+    add_addresses(dummy_name);
+    let expected_output = exec_cmd(&[&["ip"], args].concat());
+    let expected_remains = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    add_addresses(dummy_name);
+    let our_output = ip_rs_exec_cmd(args);
+    let our_remains = exec_cmd(&show_args);
+    exec_cmd(&["ip", "address", "flush", "dev", dummy_name]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+    pretty_assertions::assert_eq!(expected_remains, our_remains);
+}
+
+#[test]
+fn test_address_flush() {
+    let dummy_name = "atest-fdummy0";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["address", "flush", "dev", dummy_name][..],
+            &["-4", "address", "flush", "dev", dummy_name][..],
+            &["-s", "address", "flush", "dev", dummy_name][..],
+            &["-s", "address", "flush", "dev", dummy_name, "primary"][..],
+            &["address", "flush", "dev", dummy_name, "secondary"][..],
+            &["address", "flush", "dev", dummy_name, "to", "192.0.2.0/24"][..],
+            &["-s", "address", "flush", "dev", dummy_name, "scope", "host"][..],
+            &["-force", "address", "flush", "dev", dummy_name][..],
+        ] {
+            assert_address_flush(dummy_name, args);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_address_flush_without_args() {
+    let output = ip_rs_exec_cmd_output(&["address", "flush"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Flush requires arguments.")
+    );
+}
@@ -1,4 +1,12 @@
 // SPDX-License-Identifier: MIT
 
+#[cfg(test)]
+mod add;
 #[cfg(test)]
 mod address;
+#[cfg(test)]
+mod change;
+#[cfg(test)]
+mod delete;
+#[cfg(test)]
+mod flush;
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT
+
+use std::ffi::OsString;
+
+// Ordered like iproute2 `main()`, see `iproute_rs::normalize_args()`
+const IPROUTE2_LONG_OPTIONS: [(&str, &str); 21] = [
+    ("loops", "--loops"),
+    ("family", "--family"),
+    ("human", "--human"),
+    ("human-readable", "--human"),
+    ("iec", "--iec"),
+    ("stats", "--stats"),
+    ("statistics", "--stats"),
+    ("details", "--details"),
+    ("resolve", "--resolve"),
+    ("oneline", "--oneline"),
+    ("timestamp", "--timestamp"),
+    ("tshort", "--tshort"),
+    ("Version", "--Version"),
+    ("force", "--force"),
+    ("batch", "--batch"),
+    ("brief", "--brief"),
+    ("json", "--json"),
+    ("pretty", "--pretty"),
+    ("color", "--color"),
+    ("help", "--help"),
+    ("Numeric", "--numeric"),
+];
+
+// The iproute2 shortcuts of `-family <name>`
+const IPROUTE2_FAMILY_OPTIONS: [(&str, &str); 5] = [
+    ("-4", "--family=inet"),
+    ("-6", "--family=inet6"),
+    ("-0", "--family=link"),
+    ("-M", "--family=mpls"),
+    ("-B", "--family=bridge"),
+];
+
+// The options taking the next argument as value
+const OPTIONS_WITH_VALUE: [&str; 6] =
+    ["--loops", "-l", "--family", "-f", "--batch", "-b"];
+
+/// Convert iproute2 style global options of `ip` to clap long options
+pub(crate) fn normalize_args<I>(args: I) -> Vec<OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    iproute_rs::normalize_args(
+        args,
+        &IPROUTE2_LONG_OPTIONS,
+        &IPROUTE2_FAMILY_OPTIONS,
+        &OPTIONS_WITH_VALUE,
+    )
+}
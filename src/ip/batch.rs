@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    ffi::OsString,
+    io::{BufRead, BufReader},
+};
+
+use iproute_rs::{CliError, print_output};
+
+use super::{context::NetlinkContext, dispatch};
+
+const WHITESPACES: [char; 4] = [' ', '\t', '\r', '\n'];
+
+/// Execute commands read from file (or stdin for `-`) like iproute2
+/// `batch()`, all commands share the same netlink connection.
+/// The global options of the command line apply to every command.
+/// Stop on first failed command unless `force` is set, in which case the
+/// failures are reported at the end.
+pub(crate) async fn handle_batch(
+    app: &mut clap::Command,
+    args: &[OsString],
+    file: &str,
+    force: bool,
+    ctx: &NetlinkContext,
+) -> Result<(), CliError> {
+    let reader: Box<dyn BufRead> = if file == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(std::fs::File::open(file).map_err(|e| {
+            CliError::from(
+                format!("Cannot open file \"{file}\" for reading: {e}")
+                    .as_str(),
+            )
+        })?))
+    };
+    let global_args = strip_batch_args(args);
+    let mut lines = reader.lines();
+    let mut line_no = 0usize;
+    let mut failed_count = 0usize;
+
+    while let Some(line) = read_cmd_line(&mut lines, &mut line_no)? {
+        let cmd_args = split_cmd_line(&line)?;
+        if cmd_args.is_empty() {
+            continue;
+        }
+        let result = match app.try_get_matches_from_mut(
+            global_args
+                .iter()
+                .cloned()
+                .chain(cmd_args.into_iter().map(OsString::from)),
+        ) {
+            Ok(matches) => dispatch(&matches, ctx).await,
+            Err(e) => Err(CliError::from(
+                e.render()
+                    .to_string()
+                    .trim_start_matches("error: ")
+                    .trim_end(),
+            )),
+        };
+        print_output(&result);
+        if result.is_err() {
+            let e = CliError::from(
+                format!("Command failed {file}:{line_no}").as_str(),
+            );
+            if !force {
+                return Err(e);
+            }
+            print_output(&Err(e));
+            failed_count += 1;
+        }
+    }
+    if failed_count > 0 {
+        Err(CliError::from(
+            format!("{failed_count} command(s) failed in batch mode").as_str(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The program name and global options without the `-batch <file>`.
+fn strip_batch_args(args: &[OsString]) -> Vec<OsString> {
+    let mut ret = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--batch" || arg == "-b" {
+            args.next();
+        } else if !arg.to_str().is_some_and(|a| a.starts_with("--batch=")) {
+            ret.push(arg.clone());
+        }
+    }
+    ret
+}
+
+/// Read a command like iproute2 `getcmdline()`: `#` starts a comment and
+/// trailing backslash continues the command on next line.
+/// Return None on end of file.
+fn read_cmd_line<I>(
+    lines: &mut I,
+    line_no: &mut usize,
+) -> Result<Option<String>, CliError>
+where
+    I: Iterator<Item = std::io::Result<String>>,
+{
+    let Some(line) = lines.next().transpose()? else {
+        return Ok(None);
+    };
+    *line_no += 1;
+    let mut cmd = strip_comment(line);
+
+    while let Some(stripped) = cmd.strip_suffix('\\') {
+        cmd.truncate(stripped.len());
+        let Some(line) = lines.next().transpose()? else {
+            return Err(CliError::from("Missing continuation line"));
+        };
+        *line_no += 1;
+        cmd.push_str(&strip_comment(line));
+    }
+    Ok(Some(cmd))
+}
+
+fn strip_comment(mut line: String) -> String {
+    if let Some(pos) = line.find('#') {
+        line.truncate(pos);
+    }
+    line
+}
+
+/// Split command into arguments like iproute2 `makeargs()`, an argument
+/// starting with `"` or `'` ends at the next same quote.
+fn split_cmd_line(line: &str) -> Result<Vec<String>, CliError> {
+    let mut ret = Vec::new();
+    let mut rest = line.trim_start_matches(WHITESPACES);
+
+    while !rest.is_empty() {
+        if let Some(quote) =
+            rest.chars().next().filter(|c| ['"', '\''].contains(c))
+        {
+            let quoted = &rest[1..];
+            let end = quoted
+                .find(quote)
+                .ok_or_else(|| CliError::from("Unterminated quoted string"))?;
+            ret.push(quoted[..end].to_string());
+            rest = &quoted[end + 1..];
+        } else {
+            let end = rest.find(WHITESPACES).unwrap_or(rest.len());
+            ret.push(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start_matches(WHITESPACES);
+    }
+    Ok(ret)
+}
@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    any::{Any, TypeId},
+    cell::{OnceCell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    os::fd::{AsRawFd, RawFd},
+    rc::Rc,
+};
+
+use futures_util::stream::{LocalBoxStream, Stream, StreamExt};
+use genetlink::{GenetlinkHandle, message::RawGenlMessage};
+use iproute_rs::CliError;
+use nix::libc;
+use rtnetlink::{
+    packet_core::{NetlinkDeserializable, NetlinkMessage, NetlinkSerializable},
+    packet_route::RouteNetlinkMessage,
+    proto::ConnectionHandle,
+    sys::{AsyncSocket, SocketAddr},
+};
+
+type NetlinkMessages<M> =
+    LocalBoxStream<'static, (NetlinkMessage<M>, SocketAddr)>;
+
+/// The netlink message types not covered by `rtnetlink::Handle`, e.g.
+/// `RTM_NEWNEXTHOP`, each has its own connection in [NetlinkContext].
+pub(crate) trait ProtoMessage:
+    NetlinkSerializable + NetlinkDeserializable + Debug + Unpin + Send + 'static
+{
+    /// The netlink protocol of the socket, e.g. `NETLINK_XFRM`
+    const PROTOCOL: isize;
+}
+
+/// A spawned netlink connection. The socket and the unsolicited messages
+/// are kept for monitor commands to subscribe multicast groups on it.
+pub(crate) struct NetlinkConnection<H, M> {
+    handle: H,
+    fd: RawFd,
+    messages: RefCell<Option<NetlinkMessages<M>>>,
+}
+
+impl<H, M> NetlinkConnection<H, M> {
+    fn new<S>(handle: H, fd: RawFd, messages: S) -> Self
+    where
+        S: Stream<Item = (NetlinkMessage<M>, SocketAddr)> + 'static,
+    {
+        Self {
+            handle,
+            fd,
+            messages: RefCell::new(Some(messages.boxed_local())),
+        }
+    }
+
+    pub(crate) fn handle(&self) -> &H {
+        &self.handle
+    }
+
+    /// Subscribe the multicast groups and take the unsolicited messages
+    /// of this connection, which could only be done once.
+    pub(crate) fn subscribe(
+        &self,
+        groups: &[u32],
+    ) -> Result<NetlinkMessages<M>, CliError> {
+        let messages = self.messages.borrow_mut().take().ok_or_else(|| {
+            CliError::from("netlink socket is already subscribed")
+        })?;
+        for group in groups {
+            add_membership(self.fd, *group)?;
+        }
+        Ok(messages)
+    }
+}
+
+/// Equal to `netlink_sys::Socket::add_membership()`, which is not reachable
+/// once the connection is spawned.
+fn add_membership(fd: RawFd, group: u32) -> Result<(), CliError> {
+    // SAFETY: `fd` is owned by the spawned connection which lives till exit,
+    // and the option value is a valid `u32`.
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_NETLINK,
+            libc::NETLINK_ADD_MEMBERSHIP,
+            (&group as *const u32).cast(),
+            std::mem::size_of::<u32>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+type ProtoConnection<M> = NetlinkConnection<ConnectionHandle<M>, M>;
+
+/// The netlink connections of this `ip` invocation, created once in main
+/// and passed to all command handlers, so each command uses a single socket
+/// per netlink protocol and batch mode reuses them for every command.
+///
+/// The rtnetlink connection is created upfront while the others are created
+/// on first use, as most commands never need them.
+pub(crate) struct NetlinkContext {
+    route: NetlinkConnection<rtnetlink::Handle, RouteNetlinkMessage>,
+    genl: OnceCell<NetlinkConnection<GenetlinkHandle, RawGenlMessage>>,
+    protos: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
+}
+
+impl NetlinkContext {
+    pub(crate) fn new() -> Result<Self, CliError> {
+        let (mut connection, handle, messages) = rtnetlink::new_connection()?;
+        let socket = connection.socket_mut().socket_mut();
+        // Like iproute2 `rtnl_set_strict_dump()`, request kernel to check
+        // and honor the filters of dump request. Failure is ignored as
+        // kernel before 4.20 has no such option, the dump result is filtered
+        // in userspace then.
+        let _ = socket.set_netlink_get_strict_chk(true);
+        let fd = socket.as_raw_fd();
+        tokio::spawn(connection);
+        Ok(Self {
+            route: NetlinkConnection::new(handle, fd, messages),
+            genl: OnceCell::new(),
+            protos: RefCell::new(HashMap::new()),
+        })
+    }
+
+    pub(crate) fn handle(&self) -> &rtnetlink::Handle {
+        self.route.handle()
+    }
+
+    pub(crate) fn route(
+        &self,
+    ) -> &NetlinkConnection<rtnetlink::Handle, RouteNetlinkMessage> {
+        &self.route
+    }
+
+    /// The generic netlink connection, created on first use
+    pub(crate) fn genl(
+        &self,
+    ) -> Result<&NetlinkConnection<GenetlinkHandle, RawGenlMessage>, CliError>
+    {
+        if let Some(genl) = self.genl.get() {
+            return Ok(genl);
+        }
+        let (mut connection, handle, messages) = genetlink::new_connection()?;
+        let fd = connection.socket_mut().socket_ref().as_raw_fd();
+        tokio::spawn(connection);
+        Ok(self
+            .genl
+            .get_or_init(|| NetlinkConnection::new(handle, fd, messages)))
+    }
+
+    /// The connection of specified message type, created on first use
+    pub(crate) fn proto<M>(&self) -> Result<Rc<ProtoConnection<M>>, CliError>
+    where
+        M: ProtoMessage,
+    {
+        if let Some(conn) = self.protos.borrow().get(&TypeId::of::<M>()) {
+            return conn.clone().downcast().map_err(|_| {
+                CliError::from("BUG: mismatched netlink connection type")
+            });
+        }
+        let (mut connection, handle, messages) =
+            rtnetlink::proto::new_connection::<M>(M::PROTOCOL)?;
+        let fd = connection.socket_mut().socket_ref().as_raw_fd();
+        tokio::spawn(connection);
+        let conn = Rc::new(NetlinkConnection::new(handle, fd, messages));
+        self.protos
+            .borrow_mut()
+            .insert(TypeId::of::<M>(), conn.clone());
+        Ok(conn)
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::AddressFamily;
+
+// Equal to iproute2 `read_family()`
+pub(crate) const FAMILY_NAMES: [&str; 5] =
+    ["inet", "inet6", "bridge", "mpls", "link"];
+
+/// The protocol family selected by `-family` or its shortcuts like `-4`,
+/// the last one wins like iproute2.
+pub(crate) fn get_family(matches: &clap::ArgMatches) -> AddressFamily {
+    match matches
+        .get_many::<String>("FAMILY")
+        .and_then(|mut names| names.next_back())
+        .map(String::as_str)
+    {
+        Some("inet") => AddressFamily::Inet,
+        Some("inet6") => AddressFamily::Inet6,
+        Some("bridge") => AddressFamily::Bridge,
+        Some("mpls") => AddressFamily::Mpls,
+        Some("link") => AddressFamily::Packet,
+        _ => AddressFamily::Unspec,
+    }
+}
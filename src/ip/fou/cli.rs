@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::handle_modify,
+    show::{CliFouInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct FouCommand;
+
+impl FouCommand {
+    pub(crate) const CMD: &'static str = "fou";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("Foo-over-UDP receive port configuration")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add receive port")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .allow_hyphen_values(true)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete receive port")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .allow_hyphen_values(true)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show receive ports")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliFouInfo>>, CliError> {
+        let family = get_family(matches);
+        for (name, adding) in [("add", true), ("delete", false)] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(ctx, &opts, family, adding).await?;
+                return Ok(None);
+            }
+        }
+
+        let opts: Vec<&str> = matches
+            .subcommand_matches("show")
+            .and_then(|matches| matches.get_many::<String>("options"))
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_show(ctx, &opts).await.map(Into::into)
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_i32, parse_ipv6,
+    parse_u8, parse_u16_be,
+};
+
+// Equal to `linux/fou.h`
+const FOU_GENL_NAME: &str = "fou";
+const FOU_GENL_VERSION: u8 = 0x1;
+
+const FOU_CMD_ADD: u8 = 1;
+const FOU_CMD_DEL: u8 = 2;
+const FOU_CMD_GET: u8 = 3;
+
+const FOU_ATTR_PORT: u16 = 1;
+const FOU_ATTR_AF: u16 = 2;
+const FOU_ATTR_IPPROTO: u16 = 3;
+const FOU_ATTR_TYPE: u16 = 4;
+const FOU_ATTR_REMCSUM_NOPARTIAL: u16 = 5;
+const FOU_ATTR_LOCAL_V4: u16 = 6;
+const FOU_ATTR_LOCAL_V6: u16 = 7;
+const FOU_ATTR_PEER_V4: u16 = 8;
+const FOU_ATTR_PEER_V6: u16 = 9;
+const FOU_ATTR_PEER_PORT: u16 = 10;
+const FOU_ATTR_IFINDEX: u16 = 11;
+
+pub(crate) const FOU_ENCAP_DIRECT: u8 = 1;
+pub(crate) const FOU_ENCAP_GUE: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FouCmd {
+    Add,
+    Del,
+    Get,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FouMessage {
+    pub(crate) cmd: FouCmd,
+    pub(crate) attributes: Vec<FouAttr>,
+}
+
+impl FouMessage {
+    pub(crate) fn new(cmd: FouCmd, attributes: Vec<FouAttr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for FouMessage {
+    fn family_name() -> &'static str {
+        FOU_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            FouCmd::Add => FOU_CMD_ADD,
+            FouCmd::Del => FOU_CMD_DEL,
+            FouCmd::Get => FOU_CMD_GET,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        FOU_GENL_VERSION
+    }
+}
+
+impl Emitable for FouMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for FouMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            FOU_CMD_ADD => FouCmd::Add,
+            FOU_CMD_DEL => FouCmd::Del,
+            FOU_CMD_GET => FouCmd::Get,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown fou command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid fou attribute")?;
+            attributes.push(FouAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FouAttr {
+    /// UDP port in host byte order
+    Port(u16),
+    Af(u8),
+    Ipproto(u8),
+    Type(u8),
+    RemcsumNopartial,
+    LocalV4(Ipv4Addr),
+    LocalV6(Ipv6Addr),
+    PeerV4(Ipv4Addr),
+    PeerV6(Ipv6Addr),
+    /// UDP port in host byte order
+    PeerPort(u16),
+    Ifindex(i32),
+    Other(DefaultNla),
+}
+
+impl Nla for FouAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Port(_) | Self::PeerPort(_) => 2,
+            Self::Af(_) | Self::Ipproto(_) | Self::Type(_) => 1,
+            Self::RemcsumNopartial => 0,
+            Self::LocalV4(_) | Self::PeerV4(_) | Self::Ifindex(_) => 4,
+            Self::LocalV6(_) | Self::PeerV6(_) => 16,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Port(_) => FOU_ATTR_PORT,
+            Self::Af(_) => FOU_ATTR_AF,
+            Self::Ipproto(_) => FOU_ATTR_IPPROTO,
+            Self::Type(_) => FOU_ATTR_TYPE,
+            Self::RemcsumNopartial => FOU_ATTR_REMCSUM_NOPARTIAL,
+            Self::LocalV4(_) => FOU_ATTR_LOCAL_V4,
+            Self::LocalV6(_) => FOU_ATTR_LOCAL_V6,
+            Self::PeerV4(_) => FOU_ATTR_PEER_V4,
+            Self::PeerV6(_) => FOU_ATTR_PEER_V6,
+            Self::PeerPort(_) => FOU_ATTR_PEER_PORT,
+            Self::Ifindex(_) => FOU_ATTR_IFINDEX,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Port(v) | Self::PeerPort(v) => {
+                buffer.copy_from_slice(&v.to_be_bytes())
+            }
+            Self::Af(v) | Self::Ipproto(v) | Self::Type(v) => buffer[0] = *v,
+            Self::RemcsumNopartial => (),
+            Self::LocalV4(v) | Self::PeerV4(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::LocalV6(v) | Self::PeerV6(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::Ifindex(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+fn parse_ipv4(payload: &[u8]) -> Result<Ipv4Addr, DecodeError> {
+    <[u8; 4]>::try_from(payload)
+        .map(Ipv4Addr::from)
+        .map_err(|_| DecodeError::invalid_ip_address(payload.len()))
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for FouAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            FOU_ATTR_PORT => Self::Port(parse_u16_be(payload)?),
+            FOU_ATTR_AF => Self::Af(parse_u8(payload)?),
+            FOU_ATTR_IPPROTO => Self::Ipproto(parse_u8(payload)?),
+            FOU_ATTR_TYPE => Self::Type(parse_u8(payload)?),
+            FOU_ATTR_REMCSUM_NOPARTIAL => Self::RemcsumNopartial,
+            FOU_ATTR_LOCAL_V4 => Self::LocalV4(parse_ipv4(payload)?),
+            FOU_ATTR_LOCAL_V6 => {
+                Self::LocalV6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            FOU_ATTR_PEER_V4 => Self::PeerV4(parse_ipv4(payload)?),
+            FOU_ATTR_PEER_V6 => {
+                Self::PeerV6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            FOU_ATTR_PEER_PORT => Self::PeerPort(parse_u16_be(payload)?),
+            FOU_ATTR_IFINDEX => Self::Ifindex(parse_i32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+
+use super::message::{
+    FOU_ENCAP_DIRECT, FOU_ENCAP_GUE, FouAttr, FouCmd, FouMessage,
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    link::parse_num,
+    prefix::parse_ip_addr,
+    route::{get_ifnames, ifname_to_index},
+    rule::ipproto_from_name,
+};
+
+// Equal to `AF_INET` and `AF_INET6` of `sys/socket.h`
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+
+/// Equal to iproute2 `fou_parse_opt()`
+async fn parse_fou_opts(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    adding: bool,
+) -> Result<Vec<FouAttr>, CliError> {
+    let mut port: Option<u16> = None;
+    let mut ipproto: Option<u8> = None;
+    let mut gue = false;
+    let mut family = if family == AddressFamily::Inet6 {
+        AddressFamily::Inet6
+    } else {
+        AddressFamily::Inet
+    };
+    let mut local: Option<IpAddr> = None;
+    let mut peer: Option<IpAddr> = None;
+    let mut peer_port: Option<u16> = None;
+    let mut ifindex: Option<u32> = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "port" => {
+                let value = next_opt(&mut opts)?;
+                port = Some(parse_port(value, "invalid port")?);
+            }
+            "ipproto" => {
+                let value = next_opt(&mut opts)?;
+                ipproto = Some(match ipproto_from_name(value) {
+                    Some(v) => v,
+                    None => parse_num(value, "bad ipproto")?,
+                });
+            }
+            "gue" => gue = true,
+            "-6" => family = AddressFamily::Inet6,
+            "local" => {
+                local = Some(parse_ip_addr(next_opt(&mut opts)?, family)?);
+            }
+            "peer" => {
+                peer = Some(parse_ip_addr(next_opt(&mut opts)?, family)?);
+            }
+            "peer_port" => {
+                let value = next_opt(&mut opts)?;
+                peer_port = Some(parse_port(value, "invalid peer port")?);
+            }
+            "dev" => {
+                let value = next_opt(&mut opts)?;
+                let ifnames = get_ifnames(handle).await?;
+                ifindex =
+                    Some(ifname_to_index(&ifnames, value).map_err(|_| {
+                        CliError::from(
+                            format!("ip fou: unknown device name \"{value}\"")
+                                .as_str(),
+                        )
+                    })?);
+            }
+            _ => {
+                return Err(CliError::from(
+                    format!("fou: unknown command \"{opt}\"?").as_str(),
+                ));
+            }
+        }
+    }
+
+    let Some(port) = port else {
+        return Err(CliError::from("fou: missing port"));
+    };
+    if ipproto.is_none() && !gue && adding {
+        return Err(CliError::from("fou: must set ipproto or gue"));
+    }
+    if ipproto.is_some() && gue {
+        return Err(CliError::from("fou: cannot set ipproto and gue"));
+    }
+    if peer.is_some() != peer_port.is_some() {
+        return Err(CliError::from("fou: both peer and peer port must be set"));
+    }
+
+    let mut attrs = vec![
+        FouAttr::Port(port),
+        FouAttr::Type(if gue { FOU_ENCAP_GUE } else { FOU_ENCAP_DIRECT }),
+        FouAttr::Af(if family == AddressFamily::Inet6 {
+            AF_INET6
+        } else {
+            AF_INET
+        }),
+    ];
+    if let Some(ipproto) = ipproto {
+        attrs.push(FouAttr::Ipproto(ipproto));
+    }
+    match local {
+        Some(IpAddr::V4(v)) => attrs.push(FouAttr::LocalV4(v)),
+        Some(IpAddr::V6(v)) => attrs.push(FouAttr::LocalV6(v)),
+        None => (),
+    }
+    match peer {
+        Some(IpAddr::V4(v)) => attrs.push(FouAttr::PeerV4(v)),
+        Some(IpAddr::V6(v)) => attrs.push(FouAttr::PeerV6(v)),
+        None => (),
+    }
+    if let Some(peer_port) = peer_port {
+        attrs.push(FouAttr::PeerPort(peer_port));
+    }
+    if let Some(ifindex) = ifindex {
+        attrs.push(FouAttr::Ifindex(ifindex as i32));
+    }
+    Ok(attrs)
+}
+
+// Like iproute2, the zero port is invalid
+fn parse_port(value: &str, error_msg: &str) -> Result<u16, CliError> {
+    let port: u16 = parse_num(value, error_msg)?;
+    if port == 0 {
+        return Err(CliError::from(
+            format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+        ));
+    }
+    Ok(port)
+}
+
+/// Equal to iproute2 `do_add()` and `do_del()` of `ip fou`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    adding: bool,
+) -> Result<(), CliError> {
+    let attrs = parse_fou_opts(ctx.handle(), opts, family, adding).await?;
+    let cmd = if adding { FouCmd::Add } else { FouCmd::Del };
+    genl_request(ctx, FouMessage::new(cmd, attrs), NLM_F_REQUEST | NLM_F_ACK)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::message::{FOU_ENCAP_GUE, FouAttr, FouCmd, FouMessage};
+use crate::{context::NetlinkContext, genl::genl_request, route::get_ifnames};
+
+// Equal to `AF_INET6` of `sys/socket.h`
+const AF_INET6: u8 = 10;
+
+/// Equal to iproute2 `print_fou_mapping()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliFouInfo {
+    port: u16,
+    // Like iproute2, shown as `"gue":null` in JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gue: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipproto: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+}
+
+impl std::fmt::Display for CliFouInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "port {}", self.port)?;
+        if self.gue.is_some() {
+            write!(f, " gue")?;
+        } else if let Some(ipproto) = self.ipproto {
+            write!(f, " ipproto {ipproto}")?;
+        }
+        if self.family.as_deref() == Some("inet6") {
+            write!(f, " -6")?;
+        }
+        for (name, addr) in [("local", self.local), ("peer", self.peer)] {
+            if let Some(addr) = addr {
+                let color = match addr {
+                    IpAddr::V4(_) => CliColor::Ipv4Addr,
+                    IpAddr::V6(_) => CliColor::Ipv6Addr,
+                };
+                write!(f, " {name} ")?;
+                write_with_color!(f, color, "{addr}")?;
+            }
+        }
+        if let Some(peer_port) = self.peer_port {
+            write!(f, " peer_port {peer_port}")?;
+        }
+        if let Some(dev) = self.dev.as_ref() {
+            write!(f, " dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{dev}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliFouInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliFouInfo {}
+
+impl CliFouInfo {
+    fn from_msg(
+        msg: FouMessage,
+        ifnames: &std::collections::HashMap<u32, String>,
+    ) -> Self {
+        let mut ret = Self::default();
+        let mut is_gue = false;
+        for attr in msg.attributes {
+            match attr {
+                FouAttr::Port(v) => ret.port = v,
+                FouAttr::Type(v) => is_gue = v == FOU_ENCAP_GUE,
+                FouAttr::Ipproto(v) => ret.ipproto = Some(v),
+                FouAttr::Af(v) => {
+                    ret.family = Some(
+                        if v == AF_INET6 { "inet6" } else { "inet" }
+                            .to_string(),
+                    )
+                }
+                FouAttr::LocalV4(v) => ret.local = Some(IpAddr::V4(v)),
+                FouAttr::LocalV6(v) => ret.local = Some(IpAddr::V6(v)),
+                FouAttr::PeerV4(v) => ret.peer = Some(IpAddr::V4(v)),
+                FouAttr::PeerV6(v) => ret.peer = Some(IpAddr::V6(v)),
+                FouAttr::PeerPort(v) => ret.peer_port = Some(v),
+                // Like iproute2 `ll_index_to_name()`
+                FouAttr::Ifindex(v) if v != 0 => {
+                    ret.dev = Some(
+                        ifnames
+                            .get(&(v as u32))
+                            .cloned()
+                            .unwrap_or_else(|| format!("if{v}")),
+                    )
+                }
+                _ => (),
+            }
+        }
+        if is_gue {
+            ret.gue = Some(());
+            ret.ipproto = None;
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `do_show()` of `ip fou`
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<Vec<CliFouInfo>, CliError> {
+    if !opts.is_empty() {
+        return Err(CliError::from(
+            "\"ip fou show\" does not take any arguments.",
+        ));
+    }
+    let msgs = genl_request(
+        ctx,
+        FouMessage::new(FouCmd::Get, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?;
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(msgs
+        .into_iter()
+        .map(|msg| CliFouInfo::from_msg(msg, &ifnames))
+        .collect())
+}
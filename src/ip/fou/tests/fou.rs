@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_fou;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_fou_add_show_del() {
+    let _lock = lock_net_test();
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&["fou", "add", "port", "15555", "gue"]);
+        ip_rs_exec_cmd(&["fou", "add", "port", "15556", "ipproto", "47"]);
+        ip_rs_exec_cmd(&[
+            "fou", "add", "port", "15557", "ipproto", "ipip", "-6",
+        ]);
+        ip_rs_exec_cmd(&[
+            "fou",
+            "add",
+            "port",
+            "15558",
+            "ipproto",
+            "4",
+            "local",
+            "127.0.0.1",
+            "peer",
+            "127.0.0.2",
+            "peer_port",
+            "15559",
+            "dev",
+            "lo",
+        ]);
+
+        for args in [
+            &["fou", "show"][..],
+            &["fou"][..],
+            &["-j", "fou", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        let output = ip_rs_exec_cmd(&["fou", "show"]);
+        assert!(output.contains("port 15555 gue\n"));
+        assert!(output.contains("port 15556 ipproto 47\n"));
+        assert!(output.contains("port 15557 ipproto 4 -6\n"));
+
+        ip_rs_exec_cmd(&["fou", "del", "port", "15555"]);
+        ip_rs_exec_cmd(&["fou", "del", "port", "15557", "-6"]);
+        let output = exec_cmd(&["ip", "fou", "show"]);
+        assert!(!output.contains("port 15555 "));
+        assert!(!output.contains("port 15557 "));
+    });
+
+    cleanup_fou(&["port", "15555"]);
+    cleanup_fou(&["port", "15556"]);
+    cleanup_fou(&["port", "15557", "-6"]);
+    cleanup_fou(&[
+        "port",
+        "15558",
+        "local",
+        "127.0.0.1",
+        "peer",
+        "127.0.0.2",
+        "peer_port",
+        "15559",
+        "dev",
+        "lo",
+    ]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_fou_invalid_args() {
+    for (args, error) in [
+        (&["fou", "add", "gue"][..], "fou: missing port"),
+        (
+            &["fou", "add", "port", "15560"][..],
+            "fou: must set ipproto or gue",
+        ),
+        (
+            &["fou", "add", "port", "15560", "gue", "ipproto", "4"][..],
+            "fou: cannot set ipproto and gue",
+        ),
+        (
+            &["fou", "add", "port", "0", "gue"][..],
+            "argument \"0\" is wrong: invalid port",
+        ),
+        (
+            &["fou", "add", "port", "70000", "gue"][..],
+            "argument \"70000\" is wrong: invalid port",
+        ),
+        (
+            &["fou", "add", "port", "15560", "ipproto", "foo"][..],
+            "argument \"foo\" is wrong: bad ipproto",
+        ),
+        (
+            &["fou", "add", "port", "15560", "gue", "peer", "127.0.0.1"][..],
+            "fou: both peer and peer port must be set",
+        ),
+        (
+            &["fou", "add", "port", "15560", "gue", "peer_port", "0"][..],
+            "argument \"0\" is wrong: invalid peer port",
+        ),
+        (
+            &["fou", "add", "port", "15560", "gue", "local", "::1"][..],
+            "inet address is expected rather than \"::1\".",
+        ),
+        (
+            &["fou", "add", "port", "15560", "gue", "foo"][..],
+            "fou: unknown command \"foo\"?",
+        ),
+        (&["fou", "del"][..], "fou: missing port"),
+        (
+            &["fou", "show", "port", "15560"][..],
+            "\"ip fou show\" does not take any arguments.",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
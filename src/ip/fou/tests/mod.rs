@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod fou;
+
+use crate::tests::exec_cmd;
+
+fn cleanup_fou(opts: &[&str]) {
+    exec_cmd(&[&["ip", "fou", "del"], opts].concat());
+}
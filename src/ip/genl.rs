@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Debug;
+
+use futures_util::TryStreamExt;
+use iproute_rs::{CliError, nl_error_to_cli};
+use netlink_packet_generic::{
+    GenlFamily, GenlHeader, GenlMessage,
+    ctrl::{
+        GenlCtrl, GenlCtrlCmd,
+        nlas::{GenlCtrlAttrs, McastGrpAttrs},
+    },
+};
+use rtnetlink::packet_core::{
+    Emitable, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    ParseableParametrized,
+};
+
+use crate::context::NetlinkContext;
+
+/// Send the generic netlink request with specified netlink flags, e.g.
+/// `NLM_F_REQUEST | NLM_F_DUMP`, and collect the payload of replies. The
+/// family ID is resolved by `F::family_name()`.
+pub(crate) async fn genl_request<F>(
+    ctx: &NetlinkContext,
+    payload: F,
+    flags: u16,
+) -> Result<Vec<F>, CliError>
+where
+    F: GenlFamily + Emitable + ParseableParametrized<[u8], GenlHeader> + Debug,
+{
+    let mut handle = ctx.genl()?.handle().clone();
+
+    let mut header = NetlinkHeader::default();
+    header.flags = flags;
+    let nl_msg = NetlinkMessage::new(
+        header,
+        NetlinkPayload::InnerMessage(GenlMessage::from_payload(payload)),
+    );
+
+    let mut ret = Vec::new();
+    let mut replies = handle.request(nl_msg).await?;
+    while let Some(reply) = replies
+        .try_next()
+        .await
+        .map_err(|e| CliError::from(format!("{e}").as_str()))?
+    {
+        match reply.payload {
+            NetlinkPayload::InnerMessage(genl_msg) => {
+                ret.push(genl_msg.payload)
+            }
+            NetlinkPayload::Error(e) => {
+                if let Some(e) = nl_error_to_cli(&e) {
+                    return Err(e);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
+
+/// Resolve the ID of multicast group of generic netlink family for
+/// subscribing events, like iproute2 `genl_add_mcast_grp()`.
+pub(crate) async fn resolve_mcast_group(
+    ctx: &NetlinkContext,
+    family_name: &str,
+    group_name: &str,
+) -> Result<u32, CliError> {
+    let msg = GenlCtrl {
+        cmd: GenlCtrlCmd::GetFamily,
+        nlas: vec![GenlCtrlAttrs::FamilyName(family_name.to_string())],
+    };
+    for reply in genl_request(ctx, msg, NLM_F_REQUEST).await? {
+        for nla in reply.nlas {
+            let GenlCtrlAttrs::McastGroups(groups) = nla else {
+                continue;
+            };
+            for group in groups {
+                let mut name = None;
+                let mut id = None;
+                for attr in group {
+                    match attr {
+                        McastGrpAttrs::Name(v) => name = Some(v),
+                        McastGrpAttrs::Id(v) => id = Some(v),
+                    }
+                }
+                if let (Some(name), Some(id)) = (name, id)
+                    && name == group_name
+                {
+                    return Ok(id);
+                }
+            }
+        }
+    }
+    Err(CliError::from(
+        format!(
+            "multicast group \"{group_name}\" of \"{family_name}\" not found"
+        )
+        .as_str(),
+    ))
+}
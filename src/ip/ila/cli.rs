@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::handle_modify,
+    show::{CliIlaInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+pub(crate) struct IlaCommand;
+
+impl IlaCommand {
+    pub(crate) const CMD: &'static str = "ila";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("Identifier-locator addressing translation")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add ILA mapping")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete ILA mapping")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("list")
+                    .about("list ILA mappings")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("l")
+                    .alias("lst")
+                    .alias("show")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliIlaInfo>>, CliError> {
+        for (name, adding) in [("add", true), ("delete", false)] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(ctx, &opts, adding).await?;
+                return Ok(None);
+            }
+        }
+
+        let opts: Vec<&str> = matches
+            .subcommand_matches("list")
+            .and_then(|matches| matches.get_many::<String>("options"))
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_show(ctx, &opts).await.map(Into::into)
+    }
+}
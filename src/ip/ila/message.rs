@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_i32, parse_u8,
+    parse_u64_be,
+};
+
+// Equal to `linux/ila.h`
+const ILA_GENL_NAME: &str = "ila";
+const ILA_GENL_VERSION: u8 = 0x1;
+
+const ILA_CMD_ADD: u8 = 1;
+const ILA_CMD_DEL: u8 = 2;
+const ILA_CMD_GET: u8 = 3;
+
+const ILA_ATTR_LOCATOR: u16 = 1;
+const ILA_ATTR_LOCATOR_MATCH: u16 = 3;
+const ILA_ATTR_IFINDEX: u16 = 4;
+const ILA_ATTR_CSUM_MODE: u16 = 7;
+const ILA_ATTR_IDENT_TYPE: u16 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IlaCmd {
+    Add,
+    Del,
+    Get,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IlaMessage {
+    pub(crate) cmd: IlaCmd,
+    pub(crate) attributes: Vec<IlaAttr>,
+}
+
+impl IlaMessage {
+    pub(crate) fn new(cmd: IlaCmd, attributes: Vec<IlaAttr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for IlaMessage {
+    fn family_name() -> &'static str {
+        ILA_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            IlaCmd::Add => ILA_CMD_ADD,
+            IlaCmd::Del => ILA_CMD_DEL,
+            IlaCmd::Get => ILA_CMD_GET,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        ILA_GENL_VERSION
+    }
+}
+
+impl Emitable for IlaMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for IlaMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            ILA_CMD_ADD => IlaCmd::Add,
+            ILA_CMD_DEL => IlaCmd::Del,
+            ILA_CMD_GET => IlaCmd::Get,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown ila command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid ila attribute")?;
+            attributes.push(IlaAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum IlaAttr {
+    /// The 64 bits locator emitted in network byte order
+    Locator(u64),
+    /// The 64 bits locator emitted in network byte order
+    LocatorMatch(u64),
+    Ifindex(i32),
+    CsumMode(u8),
+    IdentType(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for IlaAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Locator(_) | Self::LocatorMatch(_) => 8,
+            Self::Ifindex(_) => 4,
+            Self::CsumMode(_) | Self::IdentType(_) => 1,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Locator(_) => ILA_ATTR_LOCATOR,
+            Self::LocatorMatch(_) => ILA_ATTR_LOCATOR_MATCH,
+            Self::Ifindex(_) => ILA_ATTR_IFINDEX,
+            Self::CsumMode(_) => ILA_ATTR_CSUM_MODE,
+            Self::IdentType(_) => ILA_ATTR_IDENT_TYPE,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Locator(v) | Self::LocatorMatch(v) => {
+                buffer.copy_from_slice(&v.to_be_bytes())
+            }
+            Self::Ifindex(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::CsumMode(v) | Self::IdentType(v) => buffer[0] = *v,
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for IlaAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            ILA_ATTR_LOCATOR => Self::Locator(parse_u64_be(payload)?),
+            ILA_ATTR_LOCATOR_MATCH => {
+                Self::LocatorMatch(parse_u64_be(payload)?)
+            }
+            ILA_ATTR_IFINDEX => Self::Ifindex(parse_i32(payload)?),
+            ILA_ATTR_CSUM_MODE => Self::CsumMode(parse_u8(payload)?),
+            ILA_ATTR_IDENT_TYPE => Self::IdentType(parse_u8(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
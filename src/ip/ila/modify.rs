@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+
+use super::{
+    message::{IlaAttr, IlaCmd, IlaMessage},
+    show::{csum_mode_from_name, ident_type_from_name, parse_addr64},
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// Equal to iproute2 `ila_parse_opt()`
+async fn parse_ila_opts(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    adding: bool,
+) -> Result<Vec<IlaAttr>, CliError> {
+    let mut locator: Option<u64> = None;
+    let mut locator_match: Option<u64> = None;
+    let mut csum_mode: Option<u8> = None;
+    let mut ident_type: Option<u8> = None;
+    let mut ifindex: Option<u32> = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "loc" => {
+                let value = next_opt(&mut opts)?;
+                locator = Some(parse_addr64(value).ok_or_else(|| {
+                    CliError::from(format!("Bad locator: {value}").as_str())
+                })?);
+            }
+            "loc_match" => {
+                let value = next_opt(&mut opts)?;
+                locator_match = Some(parse_addr64(value).ok_or_else(|| {
+                    CliError::from(
+                        format!("Bad locator to match: {value}").as_str(),
+                    )
+                })?);
+            }
+            "csum-mode" => {
+                let value = next_opt(&mut opts)?;
+                csum_mode =
+                    Some(csum_mode_from_name(value).ok_or_else(|| {
+                        CliError::from(
+                            format!("Bad csum-mode: {value}").as_str(),
+                        )
+                    })?);
+            }
+            "ident-type" => {
+                let value = next_opt(&mut opts)?;
+                ident_type =
+                    Some(ident_type_from_name(value).ok_or_else(|| {
+                        CliError::from(
+                            format!("Bad ident-type: {value}").as_str(),
+                        )
+                    })?);
+            }
+            "dev" => {
+                let value = next_opt(&mut opts)?;
+                let ifnames = get_ifnames(handle).await?;
+                ifindex =
+                    Some(ifname_to_index(&ifnames, value).map_err(|_| {
+                        CliError::from(
+                            format!("No such interface: {value}").as_str(),
+                        )
+                    })?);
+            }
+            _ => {
+                return Err(CliError::from(
+                    "Usage: ip ila add loc_match LOCATOR_MATCH loc LOCATOR [ \
+                     dev DEV ] OPTIONS",
+                ));
+            }
+        }
+    }
+
+    if locator.is_none() && adding {
+        return Err(CliError::from("ila: missing locator"));
+    }
+    let Some(locator_match) = locator_match else {
+        return Err(CliError::from("ila: missing locator0match"));
+    };
+
+    let mut attrs = vec![IlaAttr::LocatorMatch(locator_match)];
+    if let Some(locator) = locator {
+        attrs.push(IlaAttr::Locator(locator));
+    }
+    if let Some(ifindex) = ifindex {
+        attrs.push(IlaAttr::Ifindex(ifindex as i32));
+    }
+    if let Some(csum_mode) = csum_mode {
+        attrs.push(IlaAttr::CsumMode(csum_mode));
+    }
+    if let Some(ident_type) = ident_type {
+        attrs.push(IlaAttr::IdentType(ident_type));
+    }
+    Ok(attrs)
+}
+
+/// Equal to iproute2 `do_add()` and `do_del()` of `ip ila`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    adding: bool,
+) -> Result<(), CliError> {
+    let attrs = parse_ila_opts(ctx.handle(), opts, adding).await?;
+    let cmd = if adding { IlaCmd::Add } else { IlaCmd::Del };
+    genl_request(ctx, IlaMessage::new(cmd, attrs), NLM_F_REQUEST | NLM_F_ACK)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::message::{IlaAttr, IlaCmd, IlaMessage};
+use crate::{context::NetlinkContext, genl::genl_request, route::get_ifnames};
+
+// Equal to `ILA_CSUM_*` of `linux/ila.h`
+const ILA_CSUM_ADJUST_TRANSPORT: u8 = 0;
+const ILA_CSUM_NEUTRAL_MAP: u8 = 1;
+const ILA_CSUM_NO_ACTION: u8 = 2;
+const ILA_CSUM_NEUTRAL_MAP_AUTO: u8 = 3;
+
+// Equal to `ILA_ATYPE_*` of `linux/ila.h`
+const ILA_ATYPE_LUID: u8 = 1;
+const ILA_ATYPE_USE_FORMAT: u8 = 32;
+
+const CSUM_MODE_NAMES: [(u8, &str); 4] = [
+    (ILA_CSUM_ADJUST_TRANSPORT, "adj-transport"),
+    (ILA_CSUM_NEUTRAL_MAP, "neutral-map"),
+    (ILA_CSUM_NEUTRAL_MAP_AUTO, "neutral-map-auto"),
+    (ILA_CSUM_NO_ACTION, "no-action"),
+];
+
+const IDENT_TYPE_NAMES: [(u8, &str); 2] = [
+    (ILA_ATYPE_LUID, "luid"),
+    (ILA_ATYPE_USE_FORMAT, "use-format"),
+];
+
+// Equal to iproute2 `ila_csum_name2mode()`
+pub(crate) fn csum_mode_from_name(name: &str) -> Option<u8> {
+    CSUM_MODE_NAMES
+        .iter()
+        .find_map(|(mode, n)| (*n == name).then_some(*mode))
+}
+
+// Equal to iproute2 `ila_ident_name2type()`
+pub(crate) fn ident_type_from_name(name: &str) -> Option<u8> {
+    IDENT_TYPE_NAMES
+        .iter()
+        .find_map(|(ident_type, n)| (*n == name).then_some(*ident_type))
+}
+
+fn name_of(names: &[(u8, &'static str)], value: u8) -> &'static str {
+    names
+        .iter()
+        .find_map(|(v, name)| (*v == value).then_some(*name))
+        .unwrap_or("unknown")
+}
+
+/// Equal to iproute2 `get_addr64()`, the locator is up to four groups of
+/// 16 bits hex number separated by colon, the omitted groups are zero.
+pub(crate) fn parse_addr64(value: &str) -> Option<u64> {
+    let mut ret = 0u64;
+    let mut groups = value.split(':');
+    for i in 0..4 {
+        let group = match groups.next() {
+            Some(group) => u16::from_str_radix(group, 16).ok()?,
+            None => 0,
+        };
+        ret |= u64::from(group) << (48 - 16 * i);
+    }
+    if groups.next().is_some() {
+        return None;
+    }
+    Some(ret)
+}
+
+// Equal to iproute2 `addr64_n2a()`
+fn format_addr64(value: u64) -> String {
+    (0..4)
+        .map(|i| format!("{:x}", (value >> (48 - 16 * i)) & 0xffff))
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
+/// Equal to iproute2 `print_ila_mapping()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliIlaInfo {
+    locator_match: String,
+    locator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    csum_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ident_type: Option<String>,
+}
+
+impl std::fmt::Display for CliIlaInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<20}{:<20}", self.locator_match, self.locator)?;
+        match self.interface.as_ref() {
+            Some(ifname) => write!(f, "{ifname:<16}")?,
+            None => write!(f, "{:<10} ", "-")?,
+        }
+        // Like iproute2, no space between checksum mode and identifier type
+        match self.csum_mode.as_ref() {
+            Some(csum_mode) => write!(f, "{csum_mode}")?,
+            None => write!(f, "{:<10} ", "-")?,
+        }
+        write!(f, "{}", self.ident_type.as_deref().unwrap_or("-"))
+    }
+}
+
+impl CanDisplay for CliIlaInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliIlaInfo {}
+
+impl CliIlaInfo {
+    fn from_msg(msg: IlaMessage, ifnames: &HashMap<u32, String>) -> Self {
+        let mut ret = Self {
+            locator_match: "-".to_string(),
+            locator: "-".to_string(),
+            ..Default::default()
+        };
+        for attr in msg.attributes {
+            match attr {
+                IlaAttr::LocatorMatch(v) => {
+                    ret.locator_match = format_addr64(v)
+                }
+                IlaAttr::Locator(v) => ret.locator = format_addr64(v),
+                // Like iproute2 `ll_index_to_name()`
+                IlaAttr::Ifindex(v) => {
+                    ret.interface = Some(
+                        ifnames
+                            .get(&(v as u32))
+                            .cloned()
+                            .unwrap_or_else(|| format!("if{v}")),
+                    )
+                }
+                IlaAttr::CsumMode(v) => {
+                    ret.csum_mode = Some(name_of(&CSUM_MODE_NAMES, v).into())
+                }
+                IlaAttr::IdentType(v) => {
+                    ret.ident_type = Some(name_of(&IDENT_TYPE_NAMES, v).into())
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `do_list()` of `ip ila`
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<Vec<CliIlaInfo>, CliError> {
+    if !opts.is_empty() {
+        return Err(CliError::from(
+            "\"ip ila show\" does not take any arguments.",
+        ));
+    }
+    let msgs = genl_request(
+        ctx,
+        IlaMessage::new(IlaCmd::Get, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?;
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(msgs
+        .into_iter()
+        .map(|msg| CliIlaInfo::from_msg(msg, &ifnames))
+        .collect())
+}
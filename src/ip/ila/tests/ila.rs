@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_ila;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_ila_add_list_del() {
+    let _lock = lock_net_test();
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "ila",
+            "add",
+            "loc_match",
+            "2001:db8:1:0",
+            "loc",
+            "2001:db8:2:0",
+        ]);
+        ip_rs_exec_cmd(&[
+            "ila",
+            "add",
+            "loc_match",
+            "2001:db8:3:0",
+            "loc",
+            "2001:db8:4:0",
+            "dev",
+            "lo",
+            "csum-mode",
+            "neutral-map",
+            "ident-type",
+            "luid",
+        ]);
+
+        for args in [
+            &["ila", "list"][..],
+            &["ila"][..],
+            &["-j", "ila", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        let output = ip_rs_exec_cmd(&["ila", "list"]);
+        assert!(output.contains("2001:db8:1:0        2001:db8:2:0"));
+        assert!(output.contains("2001:db8:3:0        2001:db8:4:0"));
+
+        ip_rs_exec_cmd(&["ila", "del", "loc_match", "2001:db8:1:0"]);
+        let output = exec_cmd(&["ip", "ila", "list"]);
+        assert!(!output.contains("2001:db8:1:0 "));
+    });
+
+    cleanup_ila("2001:db8:1:0");
+    cleanup_ila("2001:db8:3:0");
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_ila_invalid_args() {
+    for (args, error) in [
+        (
+            &["ila", "add", "loc_match", "2001:db8:1:0"][..],
+            "ila: missing locator",
+        ),
+        (
+            &["ila", "add", "loc", "2001:db8:1:0"][..],
+            "ila: missing locator0match",
+        ),
+        (&["ila", "del"][..], "ila: missing locator0match"),
+        (
+            &["ila", "add", "loc_match", "2001:db8:1:0:0", "loc", "0"][..],
+            "Bad locator to match: 2001:db8:1:0:0",
+        ),
+        (
+            &["ila", "add", "loc_match", "0", "loc", "fffff"][..],
+            "Bad locator: fffff",
+        ),
+        (
+            &["ila", "add", "loc_match", "0", "loc", "0", "csum-mode", "x"][..],
+            "Bad csum-mode: x",
+        ),
+        (
+            &[
+                "ila",
+                "add",
+                "loc_match",
+                "0",
+                "loc",
+                "0",
+                "ident-type",
+                "x",
+            ][..],
+            "Bad ident-type: x",
+        ),
+        (
+            &["ila", "add", "loc_match", "0", "loc", "0", "foo"][..],
+            "Usage: ip ila add",
+        ),
+        (
+            &["ila", "list", "dev", "lo"][..],
+            "\"ip ila show\" does not take any arguments.",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod ila;
+
+use crate::tests::exec_cmd;
+
+fn cleanup_ila(loc_match: &str) {
+    exec_cmd(&["ip", "ila", "del", "loc_match", loc_match]);
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput};
+use serde::Serialize;
+
+use super::{
+    namespace::{
+        CliIoamNamespace, handle_namespace_add, handle_namespace_del,
+        handle_namespace_set, handle_namespace_show,
+    },
+    schema::{
+        CliIoamSchema, handle_schema_add, handle_schema_del, handle_schema_show,
+    },
+};
+use crate::{CliError, context::NetlinkContext};
+
+const IOAM_USAGE: &str = "\
+Usage:\tip ioam { COMMAND | help }
+\tip ioam namespace show
+\tip ioam namespace add ID [ data DATA32 ] [ wide DATA64 ]
+\tip ioam namespace del ID
+\tip ioam schema show
+\tip ioam schema add ID DATA
+\tip ioam schema del ID
+\tip ioam namespace set ID schema { ID | none }";
+
+fn usage() -> CliError {
+    CliError::from(IOAM_USAGE)
+}
+
+pub(crate) fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+// Like iproute2 `print_nl()` in oneline mode, every entry is ended by `\`
+// without newline.
+fn gen_oneline<T: CanDisplay>(entries: &[T]) -> String {
+    entries
+        .iter()
+        .map(|entry| format!("{}\\", entry.gen_string()))
+        .collect()
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliIoamOutput {
+    Namespace(Vec<CliIoamNamespace>),
+    Schema(Vec<CliIoamSchema>),
+}
+
+impl CanDisplay for CliIoamOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Namespace(entries) => entries.gen_string(),
+            Self::Schema(entries) => entries.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Namespace(entries) => gen_oneline(entries),
+            Self::Schema(entries) => gen_oneline(entries),
+        }
+    }
+}
+
+impl CanOutput for CliIoamOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+        .allow_hyphen_values(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+// Like iproute2, the commands of `ip ioam` are not abbreviated
+fn gen_sub_command(name: &'static str) -> clap::Command {
+    clap::Command::new(name).arg(gen_opts_arg())
+}
+
+fn incomplete_command() -> CliError {
+    CliError::from("Command line is not complete. Try option \"help\"")
+}
+
+pub(crate) struct IoamCommand;
+
+impl IoamCommand {
+    pub(crate) const CMD: &'static str = "ioam";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("IOAM6 namespace and schema configuration")
+            .alias("ioa")
+            .alias("io")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("namespace")
+                    .about("manage IOAM6 namespaces")
+                    .subcommand_required(false)
+                    .subcommand(gen_sub_command("show"))
+                    .subcommand(gen_sub_command("add"))
+                    .subcommand(gen_sub_command("del"))
+                    .subcommand(gen_sub_command("set")),
+            )
+            .subcommand(
+                clap::Command::new("schema")
+                    .about("manage IOAM6 schemas")
+                    .subcommand_required(false)
+                    .subcommand(gen_sub_command("show"))
+                    .subcommand(gen_sub_command("add"))
+                    .subcommand(gen_sub_command("del")),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliIoamOutput>, CliError> {
+        match matches.subcommand() {
+            Some(("namespace", matches)) => match matches.subcommand() {
+                Some(("show", _)) => handle_namespace_show(ctx)
+                    .await
+                    .map(CliIoamOutput::Namespace)
+                    .map(Some),
+                Some(("add", matches)) => {
+                    handle_namespace_add(ctx, &get_opts(matches)).await?;
+                    Ok(None)
+                }
+                Some(("del", matches)) => {
+                    handle_namespace_del(ctx, &get_opts(matches)).await?;
+                    Ok(None)
+                }
+                Some(("set", matches)) => {
+                    handle_namespace_set(ctx, &get_opts(matches)).await?;
+                    Ok(None)
+                }
+                _ => Err(incomplete_command()),
+            },
+            Some(("schema", matches)) => match matches.subcommand() {
+                Some(("show", _)) => handle_schema_show(ctx)
+                    .await
+                    .map(CliIoamOutput::Schema)
+                    .map(Some),
+                Some(("add", matches)) => {
+                    handle_schema_add(ctx, &get_opts(matches)).await?;
+                    Ok(None)
+                }
+                Some(("del", matches)) => {
+                    handle_schema_del(ctx, &get_opts(matches)).await?;
+                    Ok(None)
+                }
+                _ => Err(incomplete_command()),
+            },
+            _ => Err(usage()),
+        }
+    }
+}
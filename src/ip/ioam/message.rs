@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_u16, parse_u32,
+    parse_u64,
+};
+
+// Equal to `linux/ioam6_genl.h`
+const IOAM6_GENL_NAME: &str = "IOAM6";
+const IOAM6_GENL_VERSION: u8 = 0x1;
+
+const IOAM6_CMD_ADD_NAMESPACE: u8 = 1;
+const IOAM6_CMD_DEL_NAMESPACE: u8 = 2;
+const IOAM6_CMD_DUMP_NAMESPACES: u8 = 3;
+const IOAM6_CMD_ADD_SCHEMA: u8 = 4;
+const IOAM6_CMD_DEL_SCHEMA: u8 = 5;
+const IOAM6_CMD_DUMP_SCHEMAS: u8 = 6;
+const IOAM6_CMD_NS_SET_SCHEMA: u8 = 7;
+
+const IOAM6_ATTR_NS_ID: u16 = 1;
+const IOAM6_ATTR_NS_DATA: u16 = 2;
+const IOAM6_ATTR_NS_DATA_WIDE: u16 = 3;
+const IOAM6_ATTR_SC_ID: u16 = 4;
+const IOAM6_ATTR_SC_DATA: u16 = 5;
+const IOAM6_ATTR_SC_NONE: u16 = 6;
+
+pub(crate) const IOAM6_MAX_SCHEMA_DATA_LEN: usize = 255 * 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Ioam6Cmd {
+    AddNamespace,
+    DelNamespace,
+    DumpNamespaces,
+    AddSchema,
+    DelSchema,
+    DumpSchemas,
+    NsSetSchema,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Ioam6Message {
+    pub(crate) cmd: Ioam6Cmd,
+    pub(crate) attributes: Vec<Ioam6Attr>,
+}
+
+impl Ioam6Message {
+    pub(crate) fn new(cmd: Ioam6Cmd, attributes: Vec<Ioam6Attr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for Ioam6Message {
+    fn family_name() -> &'static str {
+        IOAM6_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            Ioam6Cmd::AddNamespace => IOAM6_CMD_ADD_NAMESPACE,
+            Ioam6Cmd::DelNamespace => IOAM6_CMD_DEL_NAMESPACE,
+            Ioam6Cmd::DumpNamespaces => IOAM6_CMD_DUMP_NAMESPACES,
+            Ioam6Cmd::AddSchema => IOAM6_CMD_ADD_SCHEMA,
+            Ioam6Cmd::DelSchema => IOAM6_CMD_DEL_SCHEMA,
+            Ioam6Cmd::DumpSchemas => IOAM6_CMD_DUMP_SCHEMAS,
+            Ioam6Cmd::NsSetSchema => IOAM6_CMD_NS_SET_SCHEMA,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        IOAM6_GENL_VERSION
+    }
+}
+
+impl Emitable for Ioam6Message {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for Ioam6Message {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            IOAM6_CMD_ADD_NAMESPACE => Ioam6Cmd::AddNamespace,
+            IOAM6_CMD_DEL_NAMESPACE => Ioam6Cmd::DelNamespace,
+            IOAM6_CMD_DUMP_NAMESPACES => Ioam6Cmd::DumpNamespaces,
+            IOAM6_CMD_ADD_SCHEMA => Ioam6Cmd::AddSchema,
+            IOAM6_CMD_DEL_SCHEMA => Ioam6Cmd::DelSchema,
+            IOAM6_CMD_DUMP_SCHEMAS => Ioam6Cmd::DumpSchemas,
+            IOAM6_CMD_NS_SET_SCHEMA => Ioam6Cmd::NsSetSchema,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown ioam6 command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid ioam6 attribute")?;
+            attributes.push(Ioam6Attr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Ioam6Attr {
+    NsId(u16),
+    NsData(u32),
+    NsDataWide(u64),
+    ScId(u32),
+    ScData(Vec<u8>),
+    ScNone,
+    Other(DefaultNla),
+}
+
+impl Nla for Ioam6Attr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::NsId(_) => 2,
+            Self::NsData(_) | Self::ScId(_) => 4,
+            Self::NsDataWide(_) => 8,
+            Self::ScData(v) => v.len(),
+            Self::ScNone => 0,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::NsId(_) => IOAM6_ATTR_NS_ID,
+            Self::NsData(_) => IOAM6_ATTR_NS_DATA,
+            Self::NsDataWide(_) => IOAM6_ATTR_NS_DATA_WIDE,
+            Self::ScId(_) => IOAM6_ATTR_SC_ID,
+            Self::ScData(_) => IOAM6_ATTR_SC_DATA,
+            Self::ScNone => IOAM6_ATTR_SC_NONE,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::NsId(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::NsData(v) | Self::ScId(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::NsDataWide(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::ScData(v) => buffer.copy_from_slice(v),
+            Self::ScNone => (),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for Ioam6Attr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IOAM6_ATTR_NS_ID => Self::NsId(parse_u16(payload)?),
+            IOAM6_ATTR_NS_DATA => Self::NsData(parse_u32(payload)?),
+            IOAM6_ATTR_NS_DATA_WIDE => Self::NsDataWide(parse_u64(payload)?),
+            IOAM6_ATTR_SC_ID => Self::ScId(parse_u32(payload)?),
+            IOAM6_ATTR_SC_DATA => Self::ScData(payload.to_vec()),
+            IOAM6_ATTR_SC_NONE => Self::ScNone,
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod message;
+mod namespace;
+mod schema;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::IoamCommand;
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::{
+    cli::invarg,
+    message::{Ioam6Attr, Ioam6Cmd, Ioam6Message},
+};
+use crate::{context::NetlinkContext, genl::genl_request, link::parse_num};
+
+// Like C `printf("%#0*x")` which has neither `0x` prefix nor the `0x` width
+// reserved for zero
+fn alt_hex_pad(value: u64, width: usize) -> String {
+    if value == 0 {
+        "0".repeat(width)
+    } else {
+        format!("{value:#0width$x}")
+    }
+}
+
+// Like iproute2 `print_hex()` using `%x` for JSON
+fn serialize_hex<S>(
+    data: &Option<u32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match data {
+        Some(v) => serializer.serialize_str(&format!("{v:x}")),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Like iproute2 `print_0xhex()` using `%#llx` for JSON
+fn serialize_0xhex<S>(
+    data: &Option<u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match data {
+        Some(0) => serializer.serialize_str("0"),
+        Some(v) => serializer.serialize_str(&format!("{v:#x}")),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Equal to iproute2 `print_namespace()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliIoamNamespace {
+    namespace: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_hex"
+    )]
+    data: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_0xhex"
+    )]
+    wide: Option<u64>,
+}
+
+impl std::fmt::Display for CliIoamNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "namespace {}", self.namespace)?;
+        if let Some(schema) = self.schema {
+            write!(f, " [schema {schema}]")?;
+        }
+        if let Some(data) = self.data {
+            write!(f, ", data {}", alt_hex_pad(data.into(), 10))?;
+        }
+        if let Some(wide) = self.wide {
+            write!(f, ", wide {}", alt_hex_pad(wide, 18))?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliIoamNamespace {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliIoamNamespace {}
+
+impl From<Ioam6Message> for CliIoamNamespace {
+    fn from(msg: Ioam6Message) -> Self {
+        let mut ret = Self::default();
+        for attr in msg.attributes {
+            match attr {
+                Ioam6Attr::NsId(v) => ret.namespace = v,
+                Ioam6Attr::ScId(v) => ret.schema = Some(v),
+                Ioam6Attr::NsData(v) => ret.data = Some(v),
+                Ioam6Attr::NsDataWide(v) => ret.wide = Some(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+pub(crate) async fn handle_namespace_show(
+    ctx: &NetlinkContext,
+) -> Result<Vec<CliIoamNamespace>, CliError> {
+    Ok(genl_request(
+        ctx,
+        Ioam6Message::new(Ioam6Cmd::DumpNamespaces, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .map(CliIoamNamespace::from)
+    .collect())
+}
+
+fn parse_ns_id(opts: &mut std::slice::Iter<'_, &str>) -> Result<u16, CliError> {
+    parse_num(next_opt(opts)?, "Invalid namespace ID")
+}
+
+// Like iproute2 using `get_u64()` with base 16, the `0x` prefix is optional
+fn parse_wide(value: &str) -> Result<u64, CliError> {
+    u64::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16)
+        .map_err(|_| invarg(value, "Invalid wide data"))
+}
+
+// Like iproute2 `ioam6_do_cmd()` for the non-dump commands
+pub(super) async fn ioam6_do_cmd(
+    ctx: &NetlinkContext,
+    cmd: Ioam6Cmd,
+    attributes: Vec<Ioam6Attr>,
+) -> Result<(), CliError> {
+    genl_request(
+        ctx,
+        Ioam6Message::new(cmd, attributes),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `do_ioam6()` for
+/// `namespace add ID [ data DATA32 ] [ wide DATA64 ]`
+pub(crate) async fn handle_namespace_add(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let mut attributes = vec![Ioam6Attr::NsId(parse_ns_id(&mut opts)?)];
+
+    // Like iproute2, the `wide` is only allowed after `data`
+    let mut maybe_wide = false;
+    if let Some(opt) = opts.next() {
+        match *opt {
+            "data" => {
+                attributes.push(Ioam6Attr::NsData(parse_num(
+                    next_opt(&mut opts)?,
+                    "Invalid data",
+                )?));
+                maybe_wide = true;
+            }
+            "wide" => attributes
+                .push(Ioam6Attr::NsDataWide(parse_wide(next_opt(&mut opts)?)?)),
+            _ => return Err(invarg(opt, "Unknown")),
+        }
+    }
+    if let Some(opt) = opts.next() {
+        if !maybe_wide || *opt != "wide" {
+            return Err(invarg(opt, "Unexpected argument"));
+        }
+        attributes
+            .push(Ioam6Attr::NsDataWide(parse_wide(next_opt(&mut opts)?)?));
+    }
+
+    ioam6_do_cmd(ctx, Ioam6Cmd::AddNamespace, attributes).await
+}
+
+/// Equal to iproute2 `do_ioam6()` for `namespace del ID`
+pub(crate) async fn handle_namespace_del(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let ns_id = parse_ns_id(&mut opts)?;
+    ioam6_do_cmd(ctx, Ioam6Cmd::DelNamespace, vec![Ioam6Attr::NsId(ns_id)])
+        .await
+}
+
+/// Equal to iproute2 `do_ioam6()` for
+/// `namespace set ID schema { ID | none }`
+pub(crate) async fn handle_namespace_set(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let mut attributes = vec![Ioam6Attr::NsId(parse_ns_id(&mut opts)?)];
+
+    let opt = next_opt(&mut opts)?;
+    if opt != "schema" {
+        return Err(invarg(opt, "Unknown"));
+    }
+    attributes.push(match next_opt(&mut opts)? {
+        "none" => Ioam6Attr::ScNone,
+        value => Ioam6Attr::ScId(parse_num(value, "Invalid schema ID")?),
+    });
+
+    ioam6_do_cmd(ctx, Ioam6Cmd::NsSetSchema, attributes).await
+}
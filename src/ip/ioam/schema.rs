@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::{
+    cli::invarg,
+    message::{IOAM6_MAX_SCHEMA_DATA_LEN, Ioam6Attr, Ioam6Cmd, Ioam6Message},
+    namespace::ioam6_do_cmd,
+};
+use crate::{context::NetlinkContext, genl::genl_request, link::parse_num};
+
+/// Equal to iproute2 `print_schema()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliIoamSchema {
+    schema: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<u16>,
+    data: Vec<u8>,
+}
+
+impl std::fmt::Display for CliIoamSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "schema {}", self.schema)?;
+        if let Some(namespace) = self.namespace {
+            write!(f, " [namespace {namespace}]")?;
+        }
+        write!(f, ", data:")?;
+        for byte in &self.data {
+            write!(f, " {byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliIoamSchema {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliIoamSchema {}
+
+impl From<Ioam6Message> for CliIoamSchema {
+    fn from(msg: Ioam6Message) -> Self {
+        let mut ret = Self::default();
+        for attr in msg.attributes {
+            match attr {
+                Ioam6Attr::ScId(v) => ret.schema = v,
+                Ioam6Attr::NsId(v) => ret.namespace = Some(v),
+                Ioam6Attr::ScData(v) => ret.data = v,
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+pub(crate) async fn handle_schema_show(
+    ctx: &NetlinkContext,
+) -> Result<Vec<CliIoamSchema>, CliError> {
+    Ok(genl_request(
+        ctx,
+        Ioam6Message::new(Ioam6Cmd::DumpSchemas, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .map(CliIoamSchema::from)
+    .collect())
+}
+
+fn parse_sc_id(opts: &mut std::slice::Iter<'_, &str>) -> Result<u32, CliError> {
+    parse_num(next_opt(opts)?, "Invalid schema ID")
+}
+
+/// Equal to iproute2 `do_ioam6()` for `schema add ID DATA`
+pub(crate) async fn handle_schema_add(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let sc_id = parse_sc_id(&mut opts)?;
+
+    let data = next_opt(&mut opts)?;
+    if data.len() > IOAM6_MAX_SCHEMA_DATA_LEN {
+        return Err(invarg(data, "Schema DATA too big"));
+    }
+
+    ioam6_do_cmd(
+        ctx,
+        Ioam6Cmd::AddSchema,
+        vec![
+            Ioam6Attr::ScId(sc_id),
+            Ioam6Attr::ScData(data.as_bytes().to_vec()),
+        ],
+    )
+    .await
+}
+
+/// Equal to iproute2 `do_ioam6()` for `schema del ID`
+pub(crate) async fn handle_schema_del(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let sc_id = parse_sc_id(&mut opts)?;
+    ioam6_do_cmd(ctx, Ioam6Cmd::DelSchema, vec![Ioam6Attr::ScId(sc_id)]).await
+}
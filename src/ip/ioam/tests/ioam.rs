@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    lock_net_test,
+};
+
+fn cleanup_ioam() {
+    for args in [
+        &["namespace", "del", "4001"][..],
+        &["namespace", "del", "4002"][..],
+        &["namespace", "del", "4003"][..],
+        &["schema", "del", "4001"][..],
+        &["schema", "del", "4002"][..],
+    ] {
+        let _ = std::process::Command::new("ip")
+            .args([&["ioam"][..], args].concat())
+            .output();
+    }
+}
+
+fn with_ioam_test<T>(test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    cleanup_ioam();
+    let result = std::panic::catch_unwind(test);
+    cleanup_ioam();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_ioam_namespace() {
+    with_ioam_test(|| {
+        ip_rs_exec_cmd(&[
+            "ioam",
+            "namespace",
+            "add",
+            "4001",
+            "data",
+            "0xdeadbeef",
+            "wide",
+            "1122334455667788",
+        ]);
+        ip_rs_exec_cmd(&["ioam", "namespace", "add", "4002", "wide", "0x5"]);
+        ip_rs_exec_cmd(&[
+            "ioam",
+            "namespace",
+            "add",
+            "4003",
+            "data",
+            "0",
+            "wide",
+            "0",
+        ]);
+        ip_rs_exec_cmd(&["ioam", "schema", "add", "4001", "hello"]);
+        ip_rs_exec_cmd(&["ioam", "namespace", "set", "4001", "schema", "4001"]);
+
+        for args in [&[][..], &["-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["ioam", "namespace", "show"]].concat(),
+            );
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["ioam", "namespace", "show"]].concat(),
+            );
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        let output = ip_rs_exec_cmd(&["ioam", "namespace", "show"]);
+        assert!(output.contains(
+            "namespace 4001 [schema 4001], data 0xdeadbeef, wide \
+             0x1122334455667788\n"
+        ));
+        assert!(output.contains("namespace 4002, wide 0x0000000000000005\n"));
+
+        // Like iproute2, the oneline output has no trailing newline
+        let expected_output =
+            exec_cmd(&["ip", "-o", "ioam", "namespace", "show"]);
+        let our_output = ip_rs_exec_cmd(&["-o", "ioam", "namespace", "show"]);
+        pretty_assertions::assert_eq!(
+            expected_output,
+            our_output.trim_end_matches('\n')
+        );
+
+        ip_rs_exec_cmd(&["ioam", "namespace", "set", "4001", "schema", "none"]);
+        ip_rs_exec_cmd(&["ioam", "namespace", "del", "4002"]);
+        let output = ip_rs_exec_cmd(&["ioam", "namespace", "show"]);
+        assert!(output.contains(
+            "namespace 4001, data 0xdeadbeef, wide 0x1122334455667788\n"
+        ));
+        assert!(!output.contains("namespace 4002"));
+
+        assert_alias_output(
+            &["ioam", "namespace", "show"],
+            &["io", "namespace", "show"],
+        );
+    });
+}
+
+#[test]
+fn test_ioam_schema() {
+    with_ioam_test(|| {
+        ip_rs_exec_cmd(&["ioam", "schema", "add", "4001", "hello"]);
+        ip_rs_exec_cmd(&["ioam", "schema", "add", "4002", "a\tb"]);
+        ip_rs_exec_cmd(&["ioam", "namespace", "add", "4001"]);
+        ip_rs_exec_cmd(&["ioam", "namespace", "set", "4001", "schema", "4002"]);
+
+        // The iproute2 generates invalid JSON for the schema data, hence
+        // only compare the text output.
+        let expected_output = exec_cmd(&["ip", "ioam", "schema", "show"]);
+        let our_output = ip_rs_exec_cmd(&["ioam", "schema", "show"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+        assert!(
+            our_output.contains("schema 4001, data: 68 65 6c 6c 6f 00 00 00\n")
+        );
+        assert!(
+            our_output
+                .contains("schema 4002 [namespace 4001], data: 61 09 62 00\n")
+        );
+
+        ip_rs_exec_cmd(&["ioam", "schema", "del", "4001"]);
+        let output = ip_rs_exec_cmd(&["ioam", "schema", "show"]);
+        assert!(!output.contains("schema 4001,"));
+    });
+}
+
+#[test]
+fn test_ioam_invalid_args() {
+    for (args, error) in [
+        (&["ioam"][..], "Usage:\tip ioam { COMMAND | help }"),
+        (
+            &["ioam", "namespace"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["ioam", "namespace", "add", "70000"][..],
+            "argument \"70000\" is wrong: Invalid namespace ID",
+        ),
+        (
+            &["ioam", "namespace", "add", "1", "foo"][..],
+            "argument \"foo\" is wrong: Unknown",
+        ),
+        (
+            &["ioam", "namespace", "add", "1", "wide", "1", "data", "2"][..],
+            "argument \"data\" is wrong: Unexpected argument",
+        ),
+        (
+            &["ioam", "namespace", "add", "1", "data", "1", "wide", "zz"][..],
+            "argument \"zz\" is wrong: Invalid wide data",
+        ),
+        (
+            &["ioam", "namespace", "set", "1", "foo", "2"][..],
+            "argument \"foo\" is wrong: Unknown",
+        ),
+        (
+            &["ioam", "namespace", "set", "1", "schema", "foo"][..],
+            "argument \"foo\" is wrong: Invalid schema ID",
+        ),
+        (
+            &["ioam", "schema", "add", "1"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+
+    let output = ip_rs_exec_cmd_output(&[
+        "ioam",
+        "schema",
+        "add",
+        "1",
+        &"x".repeat(1021),
+    ]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("is wrong: Schema DATA too big")
+    );
+}
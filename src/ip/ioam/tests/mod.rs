@@ -0,0 +1,4 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod ioam;
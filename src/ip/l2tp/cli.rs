@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::{handle_add, handle_del},
+    show::{CliL2tpInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+pub(crate) struct L2tpCommand;
+
+impl L2tpCommand {
+    pub(crate) const CMD: &'static str = "l2tp";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("L2TPv3 static unmanaged tunnel")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add L2TP tunnel or session")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete L2TP tunnel or session")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show L2TP tunnels or sessions")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliL2tpInfo>>, CliError> {
+        if let Some(matches) = matches.subcommand_matches("add") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_add(ctx, &opts).await?;
+            return Ok(None);
+        } else if let Some(matches) = matches.subcommand_matches("delete") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_del(ctx, &opts).await?;
+            return Ok(None);
+        }
+
+        let opts: Vec<&str> = matches
+            .subcommand_matches("show")
+            .and_then(|matches| matches.get_many::<String>("options"))
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_show(ctx, &opts).await.map(Into::into)
+    }
+}
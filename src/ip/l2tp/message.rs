@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_ipv6, parse_string,
+    parse_u8, parse_u16, parse_u32, parse_u64,
+};
+
+// Equal to `linux/l2tp.h`
+const L2TP_GENL_NAME: &str = "l2tp";
+const L2TP_GENL_VERSION: u8 = 0x1;
+
+const L2TP_CMD_TUNNEL_CREATE: u8 = 1;
+const L2TP_CMD_TUNNEL_DELETE: u8 = 2;
+const L2TP_CMD_TUNNEL_GET: u8 = 4;
+const L2TP_CMD_SESSION_CREATE: u8 = 5;
+const L2TP_CMD_SESSION_DELETE: u8 = 6;
+const L2TP_CMD_SESSION_GET: u8 = 8;
+
+const L2TP_ATTR_PW_TYPE: u16 = 1;
+const L2TP_ATTR_ENCAP_TYPE: u16 = 2;
+const L2TP_ATTR_L2SPEC_TYPE: u16 = 5;
+const L2TP_ATTR_L2SPEC_LEN: u16 = 6;
+const L2TP_ATTR_PROTO_VERSION: u16 = 7;
+const L2TP_ATTR_IFNAME: u16 = 8;
+const L2TP_ATTR_CONN_ID: u16 = 9;
+const L2TP_ATTR_PEER_CONN_ID: u16 = 10;
+const L2TP_ATTR_SESSION_ID: u16 = 11;
+const L2TP_ATTR_PEER_SESSION_ID: u16 = 12;
+const L2TP_ATTR_UDP_CSUM: u16 = 13;
+const L2TP_ATTR_COOKIE: u16 = 15;
+const L2TP_ATTR_PEER_COOKIE: u16 = 16;
+const L2TP_ATTR_RECV_SEQ: u16 = 18;
+const L2TP_ATTR_SEND_SEQ: u16 = 19;
+const L2TP_ATTR_RECV_TIMEOUT: u16 = 22;
+const L2TP_ATTR_IP_SADDR: u16 = 24;
+const L2TP_ATTR_IP_DADDR: u16 = 25;
+const L2TP_ATTR_UDP_SPORT: u16 = 26;
+const L2TP_ATTR_UDP_DPORT: u16 = 27;
+const L2TP_ATTR_IP6_SADDR: u16 = 31;
+const L2TP_ATTR_IP6_DADDR: u16 = 32;
+const L2TP_ATTR_UDP_ZERO_CSUM6_TX: u16 = 33;
+const L2TP_ATTR_UDP_ZERO_CSUM6_RX: u16 = 34;
+
+pub(crate) const L2TP_ENCAPTYPE_UDP: u16 = 0;
+pub(crate) const L2TP_ENCAPTYPE_IP: u16 = 1;
+
+pub(crate) const L2TP_PWTYPE_ETH: u16 = 5;
+
+pub(crate) const L2TP_L2SPECTYPE_NONE: u8 = 0;
+pub(crate) const L2TP_L2SPECTYPE_DEFAULT: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum L2tpCmd {
+    TunnelCreate,
+    TunnelDelete,
+    TunnelGet,
+    SessionCreate,
+    SessionDelete,
+    SessionGet,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct L2tpMessage {
+    pub(crate) cmd: L2tpCmd,
+    pub(crate) attributes: Vec<L2tpAttr>,
+}
+
+impl L2tpMessage {
+    pub(crate) fn new(cmd: L2tpCmd, attributes: Vec<L2tpAttr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for L2tpMessage {
+    fn family_name() -> &'static str {
+        L2TP_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            L2tpCmd::TunnelCreate => L2TP_CMD_TUNNEL_CREATE,
+            L2tpCmd::TunnelDelete => L2TP_CMD_TUNNEL_DELETE,
+            L2tpCmd::TunnelGet => L2TP_CMD_TUNNEL_GET,
+            L2tpCmd::SessionCreate => L2TP_CMD_SESSION_CREATE,
+            L2tpCmd::SessionDelete => L2TP_CMD_SESSION_DELETE,
+            L2tpCmd::SessionGet => L2TP_CMD_SESSION_GET,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        L2TP_GENL_VERSION
+    }
+}
+
+impl Emitable for L2tpMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for L2tpMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            L2TP_CMD_TUNNEL_CREATE => L2tpCmd::TunnelCreate,
+            L2TP_CMD_TUNNEL_DELETE => L2tpCmd::TunnelDelete,
+            L2TP_CMD_TUNNEL_GET => L2tpCmd::TunnelGet,
+            L2TP_CMD_SESSION_CREATE => L2tpCmd::SessionCreate,
+            L2TP_CMD_SESSION_DELETE => L2tpCmd::SessionDelete,
+            L2TP_CMD_SESSION_GET => L2tpCmd::SessionGet,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown l2tp command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid l2tp attribute")?;
+            attributes.push(L2tpAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum L2tpAttr {
+    PwType(u16),
+    EncapType(u16),
+    L2specType(u8),
+    L2specLen(u8),
+    ProtoVersion(u8),
+    Ifname(String),
+    ConnId(u32),
+    PeerConnId(u32),
+    SessionId(u32),
+    PeerSessionId(u32),
+    UdpCsum(bool),
+    Cookie(Vec<u8>),
+    PeerCookie(Vec<u8>),
+    RecvSeq(bool),
+    SendSeq(bool),
+    /// Reorder timeout in milliseconds
+    RecvTimeout(u64),
+    IpSaddr(Ipv4Addr),
+    IpDaddr(Ipv4Addr),
+    UdpSport(u16),
+    UdpDport(u16),
+    Ip6Saddr(Ipv6Addr),
+    Ip6Daddr(Ipv6Addr),
+    UdpZeroCsum6Tx,
+    UdpZeroCsum6Rx,
+    Other(DefaultNla),
+}
+
+impl Nla for L2tpAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::PwType(_)
+            | Self::EncapType(_)
+            | Self::UdpSport(_)
+            | Self::UdpDport(_) => 2,
+            Self::L2specType(_)
+            | Self::L2specLen(_)
+            | Self::ProtoVersion(_)
+            | Self::UdpCsum(_)
+            | Self::RecvSeq(_)
+            | Self::SendSeq(_) => 1,
+            // With the trailing NUL
+            Self::Ifname(v) => v.len() + 1,
+            Self::ConnId(_)
+            | Self::PeerConnId(_)
+            | Self::SessionId(_)
+            | Self::PeerSessionId(_)
+            | Self::IpSaddr(_)
+            | Self::IpDaddr(_) => 4,
+            Self::Cookie(v) | Self::PeerCookie(v) => v.len(),
+            Self::RecvTimeout(_) => 8,
+            Self::Ip6Saddr(_) | Self::Ip6Daddr(_) => 16,
+            Self::UdpZeroCsum6Tx | Self::UdpZeroCsum6Rx => 0,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::PwType(_) => L2TP_ATTR_PW_TYPE,
+            Self::EncapType(_) => L2TP_ATTR_ENCAP_TYPE,
+            Self::L2specType(_) => L2TP_ATTR_L2SPEC_TYPE,
+            Self::L2specLen(_) => L2TP_ATTR_L2SPEC_LEN,
+            Self::ProtoVersion(_) => L2TP_ATTR_PROTO_VERSION,
+            Self::Ifname(_) => L2TP_ATTR_IFNAME,
+            Self::ConnId(_) => L2TP_ATTR_CONN_ID,
+            Self::PeerConnId(_) => L2TP_ATTR_PEER_CONN_ID,
+            Self::SessionId(_) => L2TP_ATTR_SESSION_ID,
+            Self::PeerSessionId(_) => L2TP_ATTR_PEER_SESSION_ID,
+            Self::UdpCsum(_) => L2TP_ATTR_UDP_CSUM,
+            Self::Cookie(_) => L2TP_ATTR_COOKIE,
+            Self::PeerCookie(_) => L2TP_ATTR_PEER_COOKIE,
+            Self::RecvSeq(_) => L2TP_ATTR_RECV_SEQ,
+            Self::SendSeq(_) => L2TP_ATTR_SEND_SEQ,
+            Self::RecvTimeout(_) => L2TP_ATTR_RECV_TIMEOUT,
+            Self::IpSaddr(_) => L2TP_ATTR_IP_SADDR,
+            Self::IpDaddr(_) => L2TP_ATTR_IP_DADDR,
+            Self::UdpSport(_) => L2TP_ATTR_UDP_SPORT,
+            Self::UdpDport(_) => L2TP_ATTR_UDP_DPORT,
+            Self::Ip6Saddr(_) => L2TP_ATTR_IP6_SADDR,
+            Self::Ip6Daddr(_) => L2TP_ATTR_IP6_DADDR,
+            Self::UdpZeroCsum6Tx => L2TP_ATTR_UDP_ZERO_CSUM6_TX,
+            Self::UdpZeroCsum6Rx => L2TP_ATTR_UDP_ZERO_CSUM6_RX,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::PwType(v)
+            | Self::EncapType(v)
+            | Self::UdpSport(v)
+            | Self::UdpDport(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::L2specType(v)
+            | Self::L2specLen(v)
+            | Self::ProtoVersion(v) => buffer[0] = *v,
+            Self::UdpCsum(v) | Self::RecvSeq(v) | Self::SendSeq(v) => {
+                buffer[0] = (*v).into()
+            }
+            Self::Ifname(v) => {
+                buffer[..v.len()].copy_from_slice(v.as_bytes());
+                buffer[v.len()] = 0;
+            }
+            Self::ConnId(v)
+            | Self::PeerConnId(v)
+            | Self::SessionId(v)
+            | Self::PeerSessionId(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Cookie(v) | Self::PeerCookie(v) => buffer.copy_from_slice(v),
+            Self::RecvTimeout(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::IpSaddr(v) | Self::IpDaddr(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::Ip6Saddr(v) | Self::Ip6Daddr(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::UdpZeroCsum6Tx | Self::UdpZeroCsum6Rx => (),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+fn parse_ipv4(payload: &[u8]) -> Result<Ipv4Addr, DecodeError> {
+    <[u8; 4]>::try_from(payload)
+        .map(Ipv4Addr::from)
+        .map_err(|_| DecodeError::invalid_ip_address(payload.len()))
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for L2tpAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            L2TP_ATTR_PW_TYPE => Self::PwType(parse_u16(payload)?),
+            L2TP_ATTR_ENCAP_TYPE => Self::EncapType(parse_u16(payload)?),
+            L2TP_ATTR_L2SPEC_TYPE => Self::L2specType(parse_u8(payload)?),
+            L2TP_ATTR_L2SPEC_LEN => Self::L2specLen(parse_u8(payload)?),
+            L2TP_ATTR_PROTO_VERSION => Self::ProtoVersion(parse_u8(payload)?),
+            L2TP_ATTR_IFNAME => Self::Ifname(parse_string(payload)?),
+            L2TP_ATTR_CONN_ID => Self::ConnId(parse_u32(payload)?),
+            L2TP_ATTR_PEER_CONN_ID => Self::PeerConnId(parse_u32(payload)?),
+            L2TP_ATTR_SESSION_ID => Self::SessionId(parse_u32(payload)?),
+            L2TP_ATTR_PEER_SESSION_ID => {
+                Self::PeerSessionId(parse_u32(payload)?)
+            }
+            L2TP_ATTR_UDP_CSUM => Self::UdpCsum(parse_u8(payload)? != 0),
+            L2TP_ATTR_COOKIE => Self::Cookie(payload.to_vec()),
+            L2TP_ATTR_PEER_COOKIE => Self::PeerCookie(payload.to_vec()),
+            L2TP_ATTR_RECV_SEQ => Self::RecvSeq(parse_u8(payload)? != 0),
+            L2TP_ATTR_SEND_SEQ => Self::SendSeq(parse_u8(payload)? != 0),
+            L2TP_ATTR_RECV_TIMEOUT => Self::RecvTimeout(parse_u64(payload)?),
+            L2TP_ATTR_IP_SADDR => Self::IpSaddr(parse_ipv4(payload)?),
+            L2TP_ATTR_IP_DADDR => Self::IpDaddr(parse_ipv4(payload)?),
+            L2TP_ATTR_UDP_SPORT => Self::UdpSport(parse_u16(payload)?),
+            L2TP_ATTR_UDP_DPORT => Self::UdpDport(parse_u16(payload)?),
+            L2TP_ATTR_IP6_SADDR => {
+                Self::Ip6Saddr(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            L2TP_ATTR_IP6_DADDR => {
+                Self::Ip6Daddr(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            L2TP_ATTR_UDP_ZERO_CSUM6_TX => Self::UdpZeroCsum6Tx,
+            L2TP_ATTR_UDP_ZERO_CSUM6_RX => Self::UdpZeroCsum6Rx,
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod message;
+mod modify;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::L2tpCommand;
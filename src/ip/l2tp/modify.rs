@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    io::Write,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+
+use super::message::{
+    L2TP_ENCAPTYPE_IP, L2TP_ENCAPTYPE_UDP, L2TP_L2SPECTYPE_DEFAULT,
+    L2TP_L2SPECTYPE_NONE, L2TP_PWTYPE_ETH, L2tpAttr, L2tpCmd, L2tpMessage,
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    link::{check_ifname, parse_num},
+    prefix::parse_ip_addr,
+};
+
+/// Equal to iproute2 `struct l2tp_parm` filled by `parse_args()`, shared
+/// by `add`, `del` and `show`.
+#[derive(Debug)]
+pub(crate) struct L2tpOpts {
+    pub(crate) tunnel: bool,
+    pub(crate) session: bool,
+    pub(crate) tunnel_id: u32,
+    pub(crate) peer_tunnel_id: u32,
+    pub(crate) session_id: u32,
+    pub(crate) peer_session_id: u32,
+    encap: u16,
+    ifname: Option<String>,
+    local: Option<IpAddr>,
+    peer: Option<IpAddr>,
+    udp_sport: u16,
+    udp_dport: u16,
+    udp_csum: bool,
+    udp6_csum_tx: bool,
+    udp6_csum_rx: bool,
+    cookie: Vec<u8>,
+    peer_cookie: Vec<u8>,
+    l2spec_type: u8,
+    l2spec_len: u8,
+    recv_seq: bool,
+    send_seq: bool,
+}
+
+impl Default for L2tpOpts {
+    fn default() -> Self {
+        Self {
+            tunnel: false,
+            session: false,
+            tunnel_id: 0,
+            peer_tunnel_id: 0,
+            session_id: 0,
+            peer_session_id: 0,
+            encap: L2TP_ENCAPTYPE_UDP,
+            ifname: None,
+            local: None,
+            peer: None,
+            udp_sport: 0,
+            udp_dport: 0,
+            udp_csum: false,
+            udp6_csum_tx: true,
+            udp6_csum_rx: true,
+            cookie: Vec::new(),
+            peer_cookie: Vec::new(),
+            l2spec_type: L2TP_L2SPECTYPE_DEFAULT,
+            l2spec_len: 4,
+            recv_seq: false,
+            send_seq: false,
+        }
+    }
+}
+
+impl L2tpOpts {
+    pub(crate) fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "encap" => {
+                    ret.encap = match next_opt(&mut opts)? {
+                        "ip" => L2TP_ENCAPTYPE_IP,
+                        "udp" => L2TP_ENCAPTYPE_UDP,
+                        encap => {
+                            return Err(CliError::from(
+                                format!(
+                                    "Unknown tunnel encapsulation \"{encap}\""
+                                )
+                                .as_str(),
+                            ));
+                        }
+                    };
+                }
+                "name" => {
+                    let value = next_opt(&mut opts)?;
+                    check_ifname(value, "\"name\"")?;
+                    ret.ifname = Some(value.to_string());
+                }
+                "remote" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.peer =
+                        Some(parse_addr(value, "invalid remote address")?);
+                }
+                "local" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.local =
+                        Some(parse_addr(value, "invalid local address")?);
+                }
+                "tid" | "tunnel_id" => {
+                    ret.tunnel_id =
+                        parse_num(next_opt(&mut opts)?, "invalid ID")?;
+                }
+                "ptid" | "peer_tunnel_id" => {
+                    ret.peer_tunnel_id =
+                        parse_num(next_opt(&mut opts)?, "invalid ID")?;
+                }
+                "sid" | "session_id" => {
+                    ret.session_id =
+                        parse_num(next_opt(&mut opts)?, "invalid ID")?;
+                }
+                "psid" | "peer_session_id" => {
+                    ret.peer_session_id =
+                        parse_num(next_opt(&mut opts)?, "invalid ID")?;
+                }
+                "udp_sport" => {
+                    ret.udp_sport =
+                        parse_num(next_opt(&mut opts)?, "invalid port")?;
+                }
+                "udp_dport" => {
+                    ret.udp_dport =
+                        parse_num(next_opt(&mut opts)?, "invalid port")?;
+                }
+                "udp_csum" | "udp6_csum_tx" | "udp6_csum_rx" => {
+                    let value = next_opt(&mut opts)?;
+                    let enabled = match value {
+                        "on" => true,
+                        "off" => false,
+                        _ => {
+                            return Err(CliError::from(
+                                format!(
+                                    "argument \"{value}\" is wrong: invalid \
+                                     option for {opt}"
+                                )
+                                .as_str(),
+                            ));
+                        }
+                    };
+                    match *opt {
+                        "udp_csum" => ret.udp_csum = enabled,
+                        "udp6_csum_tx" => ret.udp6_csum_tx = enabled,
+                        _ => ret.udp6_csum_rx = enabled,
+                    }
+                }
+                // Like iproute2, the offset is not supported by kernel
+                "offset" | "peer_offset" => {
+                    next_opt(&mut opts)?;
+                    writeln!(std::io::stderr(), "Ignoring option \"{opt}\"")
+                        .ok();
+                }
+                "cookie" => {
+                    ret.cookie = parse_cookie(next_opt(&mut opts)?)?;
+                }
+                "peer_cookie" => {
+                    ret.peer_cookie = parse_cookie(next_opt(&mut opts)?)?;
+                }
+                "l2spec_type" => {
+                    (ret.l2spec_type, ret.l2spec_len) =
+                        match next_opt(&mut opts)? {
+                            "none" => (L2TP_L2SPECTYPE_NONE, 0),
+                            "default" => (L2TP_L2SPECTYPE_DEFAULT, 4),
+                            value => {
+                                return Err(CliError::from(
+                                    format!(
+                                        "Unknown layer2specific header type \
+                                         \"{value}\""
+                                    )
+                                    .as_str(),
+                                ));
+                            }
+                        };
+                }
+                "seq" => {
+                    (ret.recv_seq, ret.send_seq) = match next_opt(&mut opts)? {
+                        "both" => (true, true),
+                        "recv" => (true, false),
+                        "send" => (false, true),
+                        "none" => (false, false),
+                        value => {
+                            return Err(CliError::from(
+                                format!("Unknown seq value \"{value}\"")
+                                    .as_str(),
+                            ));
+                        }
+                    };
+                }
+                "tunnel" => ret.tunnel = true,
+                "session" => ret.session = true,
+                _ => {
+                    return Err(CliError::from(
+                        format!("Unknown command: {opt}").as_str(),
+                    ));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    pub(crate) fn check_object(&self) -> Result<(), CliError> {
+        if !self.tunnel && !self.session {
+            Err(missing_arg("tunnel or session"))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Equal to iproute2 `create_tunnel()`
+    fn gen_tunnel_attrs(&self) -> Vec<L2tpAttr> {
+        let mut attrs = vec![
+            L2tpAttr::ConnId(self.tunnel_id),
+            L2tpAttr::PeerConnId(self.peer_tunnel_id),
+            L2tpAttr::ProtoVersion(3),
+            L2tpAttr::EncapType(self.encap),
+        ];
+        for (addr, is_local) in [(self.local, true), (self.peer, false)] {
+            match (addr, is_local) {
+                (Some(IpAddr::V4(v)), true) => attrs.push(L2tpAttr::IpSaddr(v)),
+                (Some(IpAddr::V4(v)), false) => {
+                    attrs.push(L2tpAttr::IpDaddr(v))
+                }
+                (Some(IpAddr::V6(v)), true) => {
+                    attrs.push(L2tpAttr::Ip6Saddr(v))
+                }
+                (Some(IpAddr::V6(v)), false) => {
+                    attrs.push(L2tpAttr::Ip6Daddr(v))
+                }
+                (None, _) => (),
+            }
+        }
+        if self.encap == L2TP_ENCAPTYPE_UDP {
+            attrs.push(L2tpAttr::UdpSport(self.udp_sport));
+            attrs.push(L2tpAttr::UdpDport(self.udp_dport));
+            if self.udp_csum {
+                attrs.push(L2tpAttr::UdpCsum(true));
+            }
+            if !self.udp6_csum_tx {
+                attrs.push(L2tpAttr::UdpZeroCsum6Tx);
+            }
+            if !self.udp6_csum_rx {
+                attrs.push(L2tpAttr::UdpZeroCsum6Rx);
+            }
+        }
+        attrs
+    }
+
+    // Equal to iproute2 `create_session()`, only ethernet pseudowire is
+    // supported.
+    fn gen_session_attrs(&self) -> Vec<L2tpAttr> {
+        let mut attrs = vec![
+            L2tpAttr::ConnId(self.tunnel_id),
+            L2tpAttr::PeerConnId(self.peer_tunnel_id),
+            L2tpAttr::SessionId(self.session_id),
+            L2tpAttr::PeerSessionId(self.peer_session_id),
+            L2tpAttr::PwType(L2TP_PWTYPE_ETH),
+            L2tpAttr::L2specType(self.l2spec_type),
+            L2tpAttr::L2specLen(self.l2spec_len),
+        ];
+        if self.recv_seq {
+            attrs.push(L2tpAttr::RecvSeq(true));
+        }
+        if self.send_seq {
+            attrs.push(L2tpAttr::SendSeq(true));
+        }
+        if !self.cookie.is_empty() {
+            attrs.push(L2tpAttr::Cookie(self.cookie.clone()));
+        }
+        if !self.peer_cookie.is_empty() {
+            attrs.push(L2tpAttr::PeerCookie(self.peer_cookie.clone()));
+        }
+        if let Some(ifname) = self.ifname.as_ref() {
+            attrs.push(L2tpAttr::Ifname(ifname.to_string()));
+        }
+        attrs
+    }
+}
+
+fn missing_arg(key: &str) -> CliError {
+    CliError::from(format!("argument \"{key}\" is required").as_str())
+}
+
+// Like iproute2 `get_addr()` with `ADDR := { IP_ADDRESS | any }`
+fn parse_addr(value: &str, error_msg: &str) -> Result<IpAddr, CliError> {
+    if value == "any" {
+        return Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    }
+    parse_ip_addr(value, AddressFamily::Unspec).map_err(|_| {
+        CliError::from(
+            format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+        )
+    })
+}
+
+// The cookie is 4 or 8 bytes in hex string
+fn parse_cookie(value: &str) -> Result<Vec<u8>, CliError> {
+    if value.len() != 8 && value.len() != 16 {
+        return Err(CliError::from(
+            format!(
+                "argument \"{value}\" is wrong: cookie must be either 8 or 16 \
+                 hex digits"
+            )
+            .as_str(),
+        ));
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            value
+                .get(i..i + 2)
+                .and_then(|v| u8::from_str_radix(v, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| {
+            CliError::from(
+                format!(
+                    "argument \"{value}\" is wrong: cookie must be a hex \
+                     string"
+                )
+                .as_str(),
+            )
+        })
+}
+
+async fn send_l2tp_request(
+    ctx: &NetlinkContext,
+    cmd: L2tpCmd,
+    attrs: Vec<L2tpAttr>,
+) -> Result<(), CliError> {
+    genl_request(ctx, L2tpMessage::new(cmd, attrs), NLM_F_REQUEST | NLM_F_ACK)
+        .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `do_add()` of `ip l2tp`
+pub(crate) async fn handle_add(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let opts = L2tpOpts::parse(opts)?;
+    opts.check_object()?;
+    if opts.tunnel_id == 0 {
+        return Err(missing_arg("tunnel_id"));
+    }
+    if opts.session && opts.peer_session_id == 0 {
+        return Err(missing_arg("peer_session_id"));
+    }
+    if opts.session && opts.session_id == 0 {
+        return Err(missing_arg("session_id"));
+    }
+    if opts.tunnel && opts.peer_tunnel_id == 0 {
+        return Err(missing_arg("peer_tunnel_id"));
+    }
+
+    if opts.tunnel {
+        if opts.local.is_none() {
+            return Err(missing_arg("local"));
+        }
+        if opts.peer.is_none() {
+            return Err(missing_arg("remote"));
+        }
+        if opts.encap == L2TP_ENCAPTYPE_UDP {
+            if opts.udp_sport == 0 {
+                return Err(missing_arg("udp_sport"));
+            }
+            if opts.udp_dport == 0 {
+                return Err(missing_arg("udp_dport"));
+            }
+        }
+        send_l2tp_request(ctx, L2tpCmd::TunnelCreate, opts.gen_tunnel_attrs())
+            .await?;
+    }
+    if opts.session {
+        send_l2tp_request(
+            ctx,
+            L2tpCmd::SessionCreate,
+            opts.gen_session_attrs(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Equal to iproute2 `do_del()` of `ip l2tp`
+pub(crate) async fn handle_del(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let opts = L2tpOpts::parse(opts)?;
+    opts.check_object()?;
+    if opts.tunnel && opts.tunnel_id == 0 {
+        return Err(missing_arg("tunnel_id"));
+    }
+    if opts.session && opts.session_id == 0 {
+        return Err(missing_arg("session_id"));
+    }
+
+    // Like iproute2, the session is deleted when `session_id` specified
+    if opts.session_id != 0 {
+        send_l2tp_request(
+            ctx,
+            L2tpCmd::SessionDelete,
+            vec![
+                L2tpAttr::ConnId(opts.tunnel_id),
+                L2tpAttr::SessionId(opts.session_id),
+            ],
+        )
+        .await
+    } else {
+        send_l2tp_request(
+            ctx,
+            L2tpCmd::TunnelDelete,
+            vec![L2tpAttr::ConnId(opts.tunnel_id)],
+        )
+        .await
+    }
+}
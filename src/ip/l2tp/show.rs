@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::{
+    message::{
+        L2TP_ENCAPTYPE_IP, L2TP_ENCAPTYPE_UDP, L2tpAttr, L2tpCmd, L2tpMessage,
+    },
+    modify::L2tpOpts,
+};
+use crate::{context::NetlinkContext, genl::genl_request};
+
+fn ip_color(addr: &IpAddr) -> CliColor {
+    match addr {
+        IpAddr::V4(_) => CliColor::Ipv4Addr,
+        IpAddr::V6(_) => CliColor::Ipv6Addr,
+    }
+}
+
+/// Equal to iproute2 `print_tunnel()` of `ip l2tp`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliL2tpTunnelInfo {
+    tunnel_id: u32,
+    encap: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer: Option<IpAddr>,
+    peer_tunnel: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum_tx: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checksum_rx: Option<bool>,
+}
+
+impl std::fmt::Display for CliL2tpTunnelInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Tunnel {}, encap {}", self.tunnel_id, self.encap)?;
+        write!(f, "  From ")?;
+        match self.local.as_ref() {
+            Some(local) => write_with_color!(f, ip_color(local), "{local}")?,
+            None => write!(f, "(null)")?,
+        }
+        write!(f, " to ")?;
+        match self.peer.as_ref() {
+            Some(peer) => write_with_color!(f, ip_color(peer), "{peer}")?,
+            None => write!(f, "(null)")?,
+        }
+        write!(f, "\n  Peer tunnel {}", self.peer_tunnel)?;
+        if let (Some(local_port), Some(peer_port)) =
+            (self.local_port, self.peer_port)
+        {
+            write!(f, "\n  UDP source / dest ports: {local_port}/{peer_port}")?;
+            if let Some(checksum) = self.checksum {
+                write!(
+                    f,
+                    "\n  UDP checksum: {}",
+                    if checksum { "enabled" } else { "disabled" }
+                )?;
+            } else if let (Some(tx), Some(rx)) =
+                (self.checksum_tx, self.checksum_rx)
+            {
+                write!(
+                    f,
+                    "\n  UDP checksum: {}",
+                    match (tx, rx) {
+                        (true, true) => "enabled",
+                        (true, false) => "tx",
+                        (false, true) => "rx",
+                        (false, false) => "disabled",
+                    }
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CliL2tpTunnelInfo {
+    fn from_msg(msg: &L2tpMessage) -> Self {
+        let mut ret = Self {
+            tunnel_id: 0,
+            encap: String::new(),
+            local: None,
+            peer: None,
+            peer_tunnel: 0,
+            local_port: None,
+            peer_port: None,
+            checksum: None,
+            checksum_tx: None,
+            checksum_rx: None,
+        };
+        let mut encap = None;
+        let mut local_port = 0;
+        let mut peer_port = 0;
+        let mut udp_csum = false;
+        let mut udp6_csum_tx = true;
+        let mut udp6_csum_rx = true;
+        for attr in msg.attributes.iter() {
+            match attr {
+                L2tpAttr::ConnId(v) => ret.tunnel_id = *v,
+                L2tpAttr::PeerConnId(v) => ret.peer_tunnel = *v,
+                L2tpAttr::EncapType(v) => encap = Some(*v),
+                L2tpAttr::IpSaddr(v) => ret.local = Some(IpAddr::V4(*v)),
+                L2tpAttr::IpDaddr(v) => ret.peer = Some(IpAddr::V4(*v)),
+                L2tpAttr::Ip6Saddr(v) => ret.local = Some(IpAddr::V6(*v)),
+                L2tpAttr::Ip6Daddr(v) => ret.peer = Some(IpAddr::V6(*v)),
+                L2tpAttr::UdpSport(v) => local_port = *v,
+                L2tpAttr::UdpDport(v) => peer_port = *v,
+                L2tpAttr::UdpCsum(v) => udp_csum = *v,
+                L2tpAttr::UdpZeroCsum6Tx => udp6_csum_tx = false,
+                L2tpAttr::UdpZeroCsum6Rx => udp6_csum_rx = false,
+                _ => (),
+            }
+        }
+        // Like iproute2, the encapsulation is UDP when not reported
+        let encap = encap.unwrap_or(L2TP_ENCAPTYPE_UDP);
+        ret.encap = match encap {
+            L2TP_ENCAPTYPE_UDP => "UDP",
+            L2TP_ENCAPTYPE_IP => "IP",
+            _ => "??",
+        }
+        .to_string();
+        if encap == L2TP_ENCAPTYPE_UDP {
+            ret.local_port = Some(local_port);
+            ret.peer_port = Some(peer_port);
+            match ret.local {
+                Some(IpAddr::V4(_)) => ret.checksum = Some(udp_csum),
+                Some(IpAddr::V6(_)) => {
+                    ret.checksum_tx = Some(udp6_csum_tx);
+                    ret.checksum_rx = Some(udp6_csum_rx);
+                }
+                None => (),
+            }
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `print_session()` of `ip l2tp`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliL2tpSessionInfo {
+    session_id: u32,
+    tunnel_id: u32,
+    peer_session_id: u32,
+    peer_tunnel_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cookie: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peer_cookie: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reorder_timeout: Option<u64>,
+    // Like iproute2, shown as `"send_seq":null` in JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    send_seq: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recv_seq: Option<()>,
+}
+
+impl std::fmt::Display for CliL2tpSessionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Session {} in tunnel {}",
+            self.session_id, self.tunnel_id
+        )?;
+        write!(
+            f,
+            "  Peer session {}, tunnel {}",
+            self.peer_session_id, self.peer_tunnel_id
+        )?;
+        if let Some(ifname) = self.interface.as_ref() {
+            write!(f, "\n  interface name: ")?;
+            write_with_color!(f, CliColor::IfaceName, "{ifname}")?;
+        }
+        // Like iproute2, the offsets are shown for legacy scripts
+        write!(f, "\n  offset 0, peer offset 0")?;
+        if let Some(cookie) = self.cookie.as_ref() {
+            write!(f, "\n  cookie {cookie}")?;
+        }
+        if let Some(peer_cookie) = self.peer_cookie.as_ref() {
+            write!(f, "\n  peer cookie {peer_cookie}")?;
+        }
+        if let Some(reorder_timeout) = self.reorder_timeout {
+            write!(f, "\n  reorder timeout: {reorder_timeout}")?;
+        }
+        if self.send_seq.is_some() || self.recv_seq.is_some() {
+            write!(f, "\n  sequence numbering:")?;
+            if self.send_seq.is_some() {
+                write!(f, " send")?;
+            }
+            if self.recv_seq.is_some() {
+                write!(f, " recv")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CliL2tpSessionInfo {
+    fn from_msg(msg: &L2tpMessage) -> Self {
+        let mut ret = Self::default();
+        for attr in msg.attributes.iter() {
+            match attr {
+                L2tpAttr::SessionId(v) => ret.session_id = *v,
+                L2tpAttr::ConnId(v) => ret.tunnel_id = *v,
+                L2tpAttr::PeerSessionId(v) => ret.peer_session_id = *v,
+                L2tpAttr::PeerConnId(v) => ret.peer_tunnel_id = *v,
+                L2tpAttr::Ifname(v) => ret.interface = Some(v.to_string()),
+                L2tpAttr::Cookie(v) if !v.is_empty() => {
+                    ret.cookie = Some(format_cookie(v))
+                }
+                L2tpAttr::PeerCookie(v) if !v.is_empty() => {
+                    ret.peer_cookie = Some(format_cookie(v))
+                }
+                L2tpAttr::RecvTimeout(v) if *v != 0 => {
+                    ret.reorder_timeout = Some(*v)
+                }
+                L2tpAttr::SendSeq(true) => ret.send_seq = Some(()),
+                L2tpAttr::RecvSeq(true) => ret.recv_seq = Some(()),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// Equal to iproute2 `print_cookie()`
+fn format_cookie(cookie: &[u8]) -> String {
+    cookie.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub(crate) enum CliL2tpInfo {
+    Tunnel(CliL2tpTunnelInfo),
+    Session(CliL2tpSessionInfo),
+}
+
+impl std::fmt::Display for CliL2tpInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tunnel(v) => write!(f, "{v}"),
+            Self::Session(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl CanDisplay for CliL2tpInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliL2tpInfo {}
+
+/// Equal to iproute2 `do_show()` of `ip l2tp`, the tunnels or sessions are
+/// filtered by `tunnel_id` and `session_id`.
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<Vec<CliL2tpInfo>, CliError> {
+    let opts = L2tpOpts::parse(opts)?;
+    opts.check_object()?;
+
+    let mut ret = Vec::new();
+    if opts.tunnel {
+        for msg in genl_request(
+            ctx,
+            L2tpMessage::new(L2tpCmd::TunnelGet, Vec::new()),
+            NLM_F_REQUEST | NLM_F_DUMP,
+        )
+        .await?
+        {
+            let tunnel = CliL2tpTunnelInfo::from_msg(&msg);
+            if opts.tunnel_id == 0 || opts.tunnel_id == tunnel.tunnel_id {
+                ret.push(CliL2tpInfo::Tunnel(tunnel));
+            }
+        }
+    }
+    if opts.session {
+        for msg in genl_request(
+            ctx,
+            L2tpMessage::new(L2tpCmd::SessionGet, Vec::new()),
+            NLM_F_REQUEST | NLM_F_DUMP,
+        )
+        .await?
+        {
+            let session = CliL2tpSessionInfo::from_msg(&msg);
+            if (opts.tunnel_id == 0 || opts.tunnel_id == session.tunnel_id)
+                && (opts.session_id == 0
+                    || opts.session_id == session.session_id)
+            {
+                ret.push(CliL2tpInfo::Session(session));
+            }
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_l2tp_tunnel;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_l2tp_add_show_del() {
+    let _lock = lock_net_test();
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "l2tp",
+            "add",
+            "tunnel",
+            "tunnel_id",
+            "3000",
+            "peer_tunnel_id",
+            "4000",
+            "encap",
+            "udp",
+            "local",
+            "127.0.0.1",
+            "remote",
+            "127.0.0.2",
+            "udp_sport",
+            "5000",
+            "udp_dport",
+            "6000",
+        ]);
+        ip_rs_exec_cmd(&[
+            "l2tp",
+            "add",
+            "tunnel",
+            "tunnel_id",
+            "3001",
+            "peer_tunnel_id",
+            "4001",
+            "encap",
+            "ip",
+            "local",
+            "::1",
+            "remote",
+            "::1",
+        ]);
+        ip_rs_exec_cmd(&[
+            "l2tp",
+            "add",
+            "session",
+            "name",
+            "l2tptest0",
+            "tunnel_id",
+            "3000",
+            "session_id",
+            "1000",
+            "peer_session_id",
+            "2000",
+            "cookie",
+            "0102030405060708",
+            "seq",
+            "both",
+        ]);
+
+        for args in [
+            &["l2tp", "show", "tunnel"][..],
+            &["l2tp", "show", "tunnel", "tunnel_id", "3001"][..],
+            &["l2tp", "show", "session"][..],
+            &["l2tp", "show", "session", "tunnel_id", "3000"][..],
+            &["-j", "l2tp", "show", "tunnel"][..],
+            &["-j", "l2tp", "show", "session"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        let output =
+            ip_rs_exec_cmd(&["l2tp", "show", "tunnel", "tunnel_id", "3000"]);
+        assert!(output.starts_with("Tunnel 3000, encap UDP\n"));
+        assert!(output.contains("  From 127.0.0.1 to 127.0.0.2\n"));
+
+        ip_rs_exec_cmd(&[
+            "l2tp",
+            "del",
+            "session",
+            "tunnel_id",
+            "3000",
+            "session_id",
+            "1000",
+        ]);
+        ip_rs_exec_cmd(&["l2tp", "del", "tunnel", "tunnel_id", "3001"]);
+        let output = exec_cmd(&["ip", "l2tp", "show", "session"]);
+        assert!(!output.contains("Session 1000 in tunnel 3000"));
+        let output = exec_cmd(&["ip", "l2tp", "show", "tunnel"]);
+        assert!(!output.contains("Tunnel 3001,"));
+    });
+
+    cleanup_l2tp_tunnel("3000");
+    cleanup_l2tp_tunnel("3001");
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_l2tp_invalid_args() {
+    for (args, error) in [
+        (
+            &["l2tp", "add", "tunnel_id", "1"][..],
+            "argument \"tunnel or session\" is required",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "peer_tunnel_id", "1"][..],
+            "argument \"tunnel_id\" is required",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "tunnel_id", "1"][..],
+            "argument \"peer_tunnel_id\" is required",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "tid", "1", "ptid", "1"][..],
+            "argument \"local\" is required",
+        ),
+        (
+            &[
+                "l2tp",
+                "add",
+                "tunnel",
+                "tid",
+                "1",
+                "ptid",
+                "1",
+                "local",
+                "127.0.0.1",
+                "remote",
+                "127.0.0.2",
+            ][..],
+            "argument \"udp_sport\" is required",
+        ),
+        (
+            &["l2tp", "add", "session", "tid", "1", "sid", "1"][..],
+            "argument \"peer_session_id\" is required",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "tid", "foo"][..],
+            "argument \"foo\" is wrong: invalid ID",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "local", "foo"][..],
+            "argument \"foo\" is wrong: invalid local address",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "encap", "foo"][..],
+            "Unknown tunnel encapsulation \"foo\"",
+        ),
+        (
+            &["l2tp", "add", "tunnel", "udp_csum", "foo"][..],
+            "argument \"foo\" is wrong: invalid option for udp_csum",
+        ),
+        (
+            &["l2tp", "add", "session", "cookie", "010203"][..],
+            "argument \"010203\" is wrong: cookie must be either 8 or 16 hex \
+             digits",
+        ),
+        (
+            &["l2tp", "add", "session", "cookie", "0102030x"][..],
+            "argument \"0102030x\" is wrong: cookie must be a hex string",
+        ),
+        (
+            &["l2tp", "add", "session", "seq", "foo"][..],
+            "Unknown seq value \"foo\"",
+        ),
+        (
+            &["l2tp", "add", "session", "l2spec_type", "foo"][..],
+            "Unknown layer2specific header type \"foo\"",
+        ),
+        (
+            &["l2tp", "add", "session", "name", "a/b"][..],
+            "argument \"a/b\" is wrong: \"name\" not a valid ifname",
+        ),
+        (&["l2tp", "show", "foo"][..], "Unknown command: foo"),
+        (
+            &["l2tp", "del", "session", "tunnel_id", "1"][..],
+            "argument \"session_id\" is required",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod l2tp;
+
+use crate::tests::exec_cmd;
+
+fn cleanup_l2tp_tunnel(tunnel_id: &str) {
+    exec_cmd(&["ip", "l2tp", "del", "tunnel", "tunnel_id", tunnel_id]);
+}
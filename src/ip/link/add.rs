@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::stream::TryStreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::link::{LinkAttribute, LinkInfo, LinkMessage};
+
+use super::set::parse_link_type;
+
+/// Options of `ip link add` following the argument grammar of iproute2
+/// `iplink_parse()`.
+#[derive(Debug, Default)]
+struct LinkAddOptions {
+    iface_name: Option<String>,
+    link: Option<String>,
+    link_info: Option<Vec<LinkInfo>>,
+}
+
+impl LinkAddOptions {
+    fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts_iter = opts.iter();
+
+        while let Some(opt) = opts_iter.next() {
+            match *opt {
+                "link" => {
+                    ret.link = Some(next_opt(&mut opts_iter)?.to_string());
+                }
+                "type" => {
+                    let kind = next_opt(&mut opts_iter)?;
+                    // Like iproute2, all remaining options belong to the
+                    // link type
+                    let kind_opts: Vec<&str> =
+                        opts_iter.by_ref().copied().collect();
+                    ret.link_info = Some(parse_link_type(kind, &kind_opts)?);
+                }
+                _ => {
+                    let opt = if matches!(*opt, "name" | "dev") {
+                        next_opt(&mut opts_iter)?
+                    } else {
+                        opt
+                    };
+                    if ret.iface_name.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "Either \"dev\" is duplicate, or \"{opt}\" is \
+                                 a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.iface_name = Some(opt.to_string());
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+pub(crate) async fn handle_add(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let opts = LinkAddOptions::parse(opts)?;
+    let Some(iface_name) = opts.iface_name else {
+        return Err(CliError::from(
+            "Not enough information: \"dev\" argument is required.",
+        ));
+    };
+    let Some(link_info) = opts.link_info else {
+        return Err(CliError::from(
+            "Not enough information: \"type\" argument is required.",
+        ));
+    };
+
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.attributes.push(LinkAttribute::IfName(iface_name));
+    if let Some(link) = opts.link {
+        let link_index = handle
+            .link()
+            .get()
+            .match_name(link.clone())
+            .execute()
+            .try_next()
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                CliError::from(
+                    format!("Cannot find device \"{link}\"").as_str(),
+                )
+            })?
+            .header
+            .index;
+        nl_msg.attributes.push(LinkAttribute::Link(link_index));
+    }
+    nl_msg.attributes.push(LinkAttribute::LinkInfo(link_info));
+
+    handle.link().add(nl_msg).execute().await?;
+    Ok(())
+}
@@ -1,8 +1,55 @@
 // SPDX-License-Identifier: MIT
 
-use iproute_rs::CliError;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliError, CliNumberFormat,
+    link::{CliLinkInfo, CliLinkInfoBrief},
+};
+use serde::Serialize;
 
-use super::show::{CliLinkInfo, handle_show};
+use super::{add::handle_add, set::handle_set, show::handle_show};
+use crate::context::NetlinkContext;
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliLinkOutput {
+    Full(Vec<CliLinkInfo>),
+    Brief(Vec<CliLinkInfoBrief>),
+}
+
+impl From<Vec<CliLinkInfo>> for CliLinkOutput {
+    fn from(links: Vec<CliLinkInfo>) -> Self {
+        Self::Full(links)
+    }
+}
+
+impl CliLinkOutput {
+    fn into_brief(self) -> Self {
+        match self {
+            Self::Full(links) => {
+                Self::Brief(links.into_iter().map(Into::into).collect())
+            }
+            Self::Brief(_) => self,
+        }
+    }
+}
+
+impl CanDisplay for CliLinkOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Full(links) => links.gen_string(),
+            Self::Brief(links) => links.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Full(links) => links.gen_oneline_string(),
+            Self::Brief(links) => links.gen_oneline_string(),
+        }
+    }
+}
+
+impl CanOutput for CliLinkOutput {}
 
 pub(crate) struct LinkCommand;
 
@@ -43,25 +90,80 @@ impl LinkCommand {
             .subcommand(
                 clap::Command::new("change")
                     .alias("set")
-                    .about("change device attributes"),
+                    .about("change device attributes")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
             )
     }
 
     pub(crate) async fn handle(
         matches: &clap::ArgMatches,
-    ) -> Result<Vec<CliLinkInfo>, CliError> {
-        if let Some(matches) = matches.subcommand_matches("add") {
-            println!("HAHA {matches:?}");
-            todo!()
-        } else if let Some(matches) = matches.subcommand_matches("show") {
-            let opts: Vec<&str> = matches
-                .get_many::<String>("options")
-                .unwrap_or_default()
-                .map(String::as_str)
-                .collect();
-            handle_show(&opts, matches.get_flag("DETAILS")).await
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliLinkOutput>, CliError> {
+        let handle = ctx.handle();
+        let output: Option<CliLinkOutput> =
+            if let Some(matches) = matches.subcommand_matches("add") {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_add(handle, &opts).await?;
+                None
+            } else if let Some(matches) = matches.subcommand_matches("change") {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_set(handle, &opts).await?;
+                None
+            } else if let Some(matches) = matches.subcommand_matches("show") {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                Some(
+                    handle_show(
+                        handle,
+                        &opts,
+                        matches.get_flag("DETAILS"),
+                        matches.get_count("STATS"),
+                        CliNumberFormat::new(
+                            matches.get_flag("HUMAN"),
+                            matches.get_flag("IEC"),
+                        ),
+                        matches.get_flag("NUMERIC"),
+                    )
+                    .await?
+                    .into(),
+                )
+            } else {
+                Some(
+                    handle_show(
+                        handle,
+                        &[],
+                        matches.get_flag("DETAILS"),
+                        matches.get_count("STATS"),
+                        CliNumberFormat::new(
+                            matches.get_flag("HUMAN"),
+                            matches.get_flag("IEC"),
+                        ),
+                        matches.get_flag("NUMERIC"),
+                    )
+                    .await?
+                    .into(),
+                )
+            };
+
+        if matches.get_flag("BRIEF") {
+            Ok(output.map(CliLinkOutput::into_brief))
         } else {
-            handle_show(&[], matches.get_flag("DETAILS")).await
+            Ok(output)
         }
     }
 }
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::stream::TryStreamExt;
+use iproute_rs::{
+    CliError,
+    link::{CliLinkInfo, LinkDumpFilter},
+    next_opt,
+};
+use rtnetlink::packet_route::link::LinkMessage;
+
+const PORT_KIND_SUFFIX: &str = "_slave";
+const IFNAMSIZ: usize = 16;
+
+/// Filters of `ip link show` following the argument grammar of iproute2
+/// `iplink_filter_req()` and `ipaddr_list_flush_or_save()`.
+#[derive(Debug, Default)]
+pub(crate) struct LinkShowFilter {
+    pub(crate) iface_name: Option<String>,
+    controller_index: Option<u32>,
+    kind: Option<String>,
+    port_kind: Option<String>,
+    controller: Option<ControllerFilter>,
+}
+
+#[derive(Debug)]
+enum ControllerFilter {
+    /// `master <dev>`: only ports of specified controller
+    Name(String),
+    /// `nomaster`: only interfaces without controller
+    None,
+}
+
+impl LinkShowFilter {
+    pub(crate) fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "type" => {
+                    let kind = next_opt(&mut opts)?;
+                    // Like iproute2, `type bridge_slave` filters on the port
+                    // kind of the controller instead of the link kind.
+                    if let Some(port_kind) = kind.strip_suffix(PORT_KIND_SUFFIX)
+                    {
+                        ret.port_kind = Some(port_kind.to_string());
+                    } else {
+                        ret.kind = Some(kind.to_string());
+                    }
+                }
+                "master" => {
+                    ret.controller = Some(ControllerFilter::Name(
+                        next_opt(&mut opts)?.to_string(),
+                    ));
+                }
+                "nomaster" => ret.controller = Some(ControllerFilter::None),
+                _ => {
+                    let opt = if *opt == "dev" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if ret.iface_name.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "Either \"dev\" is duplicate, or \"{opt}\" is \
+                                 a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.iface_name = Some(opt.to_string());
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Resolve the controller referred by filter to index and generate
+    /// the kernel side filter of link dump.
+    pub(crate) async fn resolve(
+        &mut self,
+        handle: &rtnetlink::Handle,
+    ) -> Result<LinkDumpFilter, CliError> {
+        if let Some(ControllerFilter::Name(name)) = self.controller.as_ref() {
+            self.controller_index = query_iface_index(handle, name).await?;
+            if self.controller_index.is_none() {
+                return Err(CliError::from(
+                    format!(
+                        "argument \"{name}\" is wrong: Device does not exist"
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+        Ok(LinkDumpFilter {
+            ifname: self.iface_name.clone(),
+            ifindex: None,
+            controller_ifindex: self.controller_index,
+            kind: self.kind.clone(),
+        })
+    }
+
+    /// The filters kernel could not do, the others are done by
+    /// [iproute_rs::link::get_links].
+    pub(crate) fn matches(&self, link: &CliLinkInfo) -> bool {
+        if let Some(port_kind) = self.port_kind.as_deref()
+            && link.get_port_kind() != Some(port_kind)
+        {
+            return false;
+        }
+        match self.controller.as_ref() {
+            Some(ControllerFilter::None) => {
+                link.get_controller_ifindex().is_none()
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Like iproute2 `ll_idx_a2n()`, an interface could also be referred as
+/// `if<index>` when no interface has such name.
+pub(crate) fn parse_iface_index(name: &str) -> Option<u32> {
+    name.strip_prefix("if")?.parse().ok()
+}
+
+// Equal to iproute2 `check_ifname()`
+pub(crate) fn check_ifname(name: &str, keyword: &str) -> Result<(), CliError> {
+    if name.is_empty()
+        || name.len() >= IFNAMSIZ
+        || name.chars().any(|c| c == '/' || c.is_ascii_whitespace())
+    {
+        Err(CliError::from(
+            format!(
+                "argument \"{name}\" is wrong: {keyword} not a valid ifname"
+            )
+            .as_str(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Query kernel for the index of interface like iproute2
+/// `ll_name_to_index()`, `None` if no such interface.
+pub(crate) async fn query_iface_index(
+    handle: &rtnetlink::Handle,
+    name: &str,
+) -> Result<Option<u32>, CliError> {
+    let mut link =
+        get_link(handle.link().get().match_name(name.to_string())).await?;
+    if link.is_none()
+        && let Some(index) = parse_iface_index(name)
+    {
+        link = get_link(handle.link().get().match_index(index)).await?;
+    }
+    Ok(link.map(|l| l.header.index))
+}
+
+// Kernel replies ENODEV for nonexistent interface, any other failure is
+// reported as it is.
+async fn get_link(
+    request: rtnetlink::LinkGetRequest,
+) -> Result<Option<LinkMessage>, CliError> {
+    match request.execute().try_next().await {
+        Ok(link) => Ok(link),
+        Err(rtnetlink::Error::NetlinkError(e))
+            if e.raw_code() == -(nix::errno::Errno::ENODEV as i32) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,5 +1,10 @@
 // SPDX-License-Identifier: MIT
 
-pub(super) mod bond;
-pub(super) mod bridge;
-pub(super) mod vlan;
+mod bond;
+mod bridge;
+mod vlan;
+
+pub(crate) use self::{
+    bond::parse_bond_port_opts, bridge::parse_bridge_port_opts,
+    vlan::parse_vlan_opts,
+};
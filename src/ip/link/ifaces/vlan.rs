@@ -1,63 +1,96 @@
 // SPDX-License-Identifier: MIT
 
-use rtnetlink::packet_route::link::{InfoVlan, VlanFlags};
-use serde::Serialize;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::link::{
+    InfoVlan, VlanFlags, VlanProtocol, VlanQosMapping,
+};
 
-#[derive(Serialize)]
-pub(crate) struct CliLinkInfoDataVlan {
-    protocol: String,
-    id: u16,
-    flags: Vec<String>,
+use crate::link::set::{parse_num, parse_on_off};
+
+// Like iproute2 `vlan_parse_qos_map()`, consume all the following `FROM:TO`
+// mappings.
+fn parse_qos_map(
+    name: &str,
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<Vec<VlanQosMapping>, CliError> {
+    let mut ret = Vec::new();
+    while let Some((from, to)) =
+        opts.as_slice().first().and_then(|opt| opt.split_once(':'))
+    {
+        opts.next();
+        let error_msg = format!("invalid {name}");
+        ret.push(VlanQosMapping::Mapping {
+            from: parse_num(from, &error_msg)?,
+            to: parse_num(to, &error_msg)?,
+        });
+    }
+    if ret.is_empty() {
+        return Err(CliError::from(format!("invalid {name}").as_str()));
+    }
+    Ok(ret)
 }
 
-impl From<&[InfoVlan]> for CliLinkInfoDataVlan {
-    fn from(info: &[InfoVlan]) -> Self {
-        let mut id = 0;
-        let mut flags = Vec::new();
-        let mut protocol = String::new();
+/// Parse options of `ip link add/set type vlan` following iproute2
+/// `vlan_parse_opt()`.
+pub(crate) fn parse_vlan_opts(
+    opts: &[&str],
+) -> Result<Vec<InfoVlan>, CliError> {
+    let mut ret = Vec::new();
+    let mut flags = VlanFlags::empty();
+    let mut mask = VlanFlags::empty();
+    let mut opts = opts.iter();
 
-        for nla in info {
-            match nla {
-                InfoVlan::Id(v) => id = *v,
-                InfoVlan::Flags((flags_val, _)) => {
-                    if flags_val.contains(VlanFlags::ReorderHdr) {
-                        flags.push("REORDER_HDR".to_string());
-                    }
-                    if flags_val.contains(VlanFlags::Gvrp) {
-                        flags.push("GVRP".to_string());
-                    }
-                    if flags_val.contains(VlanFlags::LooseBinding) {
-                        flags.push("LOOSE_BINDING".to_string());
-                    }
-                    if flags_val.contains(VlanFlags::Mvrp) {
-                        flags.push("MVRP".to_string());
-                    }
-                    if flags_val.contains(VlanFlags::BridgeBinding) {
-                        flags.push("BRIDGE_BINDING".to_string());
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "protocol" => {
+                let proto = next_opt(&mut opts)?;
+                ret.push(InfoVlan::Protocol(
+                    match proto.to_lowercase().as_str() {
+                        "802.1q" => VlanProtocol::Ieee8021Q,
+                        "802.1ad" => VlanProtocol::Ieee8021Ad,
+                        _ => {
+                            return Err(CliError::from(
+                                format!(
+                                    "argument \"{proto}\" is wrong: protocol \
+                                     is invalid"
+                                )
+                                .as_str(),
+                            ));
+                        }
+                    },
+                ));
+            }
+            "id" => ret.push(InfoVlan::Id(parse_num(
+                next_opt(&mut opts)?,
+                "id is invalid",
+            )?)),
+            "ingress-qos-map" => {
+                ret.push(InfoVlan::IngressQos(parse_qos_map(opt, &mut opts)?))
+            }
+            "egress-qos-map" => {
+                ret.push(InfoVlan::EgressQos(parse_qos_map(opt, &mut opts)?))
+            }
+            name => {
+                let flag = match name {
+                    "reorder_hdr" => VlanFlags::ReorderHdr,
+                    "gvrp" => VlanFlags::Gvrp,
+                    "mvrp" => VlanFlags::Mvrp,
+                    "loose_binding" => VlanFlags::LooseBinding,
+                    "bridge_binding" => VlanFlags::BridgeBinding,
+                    _ => {
+                        return Err(CliError::from(
+                            format!("vlan: unknown command \"{name}\"?")
+                                .as_str(),
+                        ));
                     }
-                }
-                InfoVlan::Protocol(v) => {
-                    protocol = v.to_string().to_uppercase();
-                }
-                _ => (),
+                };
+                mask |= flag;
+                flags.set(flag, parse_on_off(name, next_opt(&mut opts)?)?);
             }
         }
-
-        Self {
-            id,
-            flags,
-            protocol,
-        }
     }
-}
-
-impl std::fmt::Display for CliLinkInfoDataVlan {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "protocol {} ", self.protocol)?;
-        write!(f, "id {} ", self.id)?;
-        if !self.flags.is_empty() {
-            write!(f, "<{}>", self.flags.as_slice().join(","))?;
-        }
-        Ok(())
+    if !mask.is_empty() {
+        ret.push(InfoVlan::Flags((flags, mask)));
     }
+    Ok(ret)
 }
@@ -1,10 +1,10 @@
 // SPDX-License-Identifier: MIT
 
+mod add;
 mod cli;
-mod detail;
-mod flags;
+mod filter;
 mod ifaces;
-mod link_info;
+mod set;
 mod show;
 
 #[cfg(test)]
@@ -12,5 +12,9 @@ mod tests;
 
 pub(crate) use self::{
     cli::LinkCommand,
-    show::{CliLinkInfo, handle_show},
+    filter::{
+        LinkShowFilter, check_ifname, parse_iface_index, query_iface_index,
+    },
+    set::{parse_num, parse_on_off},
+    show::handle_show,
 };
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::stream::TryStreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::link::{
+    InfoData, InfoKind, InfoPortData, LinkAttribute, LinkInfo, LinkMessage,
+};
+
+use super::ifaces::{
+    parse_bond_port_opts, parse_bridge_port_opts, parse_vlan_opts,
+};
+
+/// Options of `ip link set` following the argument grammar of iproute2
+/// `iplink_parse()`.
+#[derive(Debug, Default)]
+struct LinkSetOptions {
+    iface_name: Option<String>,
+    link_info: Option<Vec<LinkInfo>>,
+}
+
+impl LinkSetOptions {
+    fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts_iter = opts.iter();
+
+        while let Some(opt) = opts_iter.next() {
+            match *opt {
+                "type" => {
+                    let kind = next_opt(&mut opts_iter)?;
+                    // Like iproute2, all remaining options belong to the
+                    // link type
+                    let kind_opts: Vec<&str> =
+                        opts_iter.by_ref().copied().collect();
+                    ret.link_info = Some(parse_link_type(kind, &kind_opts)?);
+                }
+                _ => {
+                    let opt = if *opt == "dev" {
+                        next_opt(&mut opts_iter)?
+                    } else {
+                        opt
+                    };
+                    if ret.iface_name.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "Either \"dev\" is duplicate, or \"{opt}\" is \
+                                 a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.iface_name = Some(opt.to_string());
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+pub(crate) fn parse_on_off(name: &str, value: &str) -> Result<bool, CliError> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(CliError::from(
+            format!(
+                "argument of \"{name}\" must be \"on\" or \"off\", not \
+                 \"{value}\""
+            )
+            .as_str(),
+        )),
+    }
+}
+
+// Like iproute2 `get_unsigned()` and `get_s32()`, hexadecimal with `0x`
+// prefix is supported
+pub(crate) fn parse_num<T: TryFrom<i64>>(
+    value: &str,
+    error_msg: &str,
+) -> Result<T, CliError> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(v) => (true, v),
+        None => (false, value),
+    };
+    let parsed = if let Some(hex) = digits.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()
+    } else {
+        digits.parse::<i64>().ok()
+    };
+    parsed
+        .map(|v| if negative { -v } else { v })
+        .and_then(|v| T::try_from(v).ok())
+        .ok_or_else(|| {
+            CliError::from(
+                format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+            )
+        })
+}
+
+/// Parse `type KIND [OPTIONS]` of `ip link add` and `ip link set`
+pub(super) fn parse_link_type(
+    kind: &str,
+    opts: &[&str],
+) -> Result<Vec<LinkInfo>, CliError> {
+    Ok(match kind {
+        "bridge_slave" => vec![LinkInfo::PortData(InfoPortData::BridgePort(
+            parse_bridge_port_opts(opts)?,
+        ))],
+        "bond_slave" => vec![LinkInfo::PortData(InfoPortData::BondPort(
+            parse_bond_port_opts(opts)?,
+        ))],
+        "vlan" => vec![
+            LinkInfo::Kind(InfoKind::Vlan),
+            LinkInfo::Data(InfoData::Vlan(parse_vlan_opts(opts)?)),
+        ],
+        _ if opts.is_empty() => {
+            vec![LinkInfo::Kind(InfoKind::Other(kind.to_string()))]
+        }
+        _ => {
+            return Err(CliError::from(
+                format!(
+                    "Options of link type \"{kind}\" are not supported yet"
+                )
+                .as_str(),
+            ));
+        }
+    })
+}
+
+pub(crate) async fn handle_set(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let opts = LinkSetOptions::parse(opts)?;
+    let Some(iface_name) = opts.iface_name else {
+        return Err(CliError::from(
+            "Not enough information: \"dev\" argument is required.",
+        ));
+    };
+
+    let link = handle
+        .link()
+        .get()
+        .match_name(iface_name.clone())
+        .execute()
+        .try_next()
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| {
+            CliError::from(
+                format!("Cannot find device \"{iface_name}\"").as_str(),
+            )
+        })?;
+
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = link.header.index;
+    if let Some(link_info) = opts.link_info {
+        nl_msg.attributes.push(LinkAttribute::LinkInfo(link_info));
+    }
+
+    handle.link().set(nl_msg).execute().await?;
+    Ok(())
+}
@@ -62,19 +62,141 @@ fn test_link_detailed_show_json_bond_port() {
     })
 }
 
+#[test]
+fn test_link_detailed_show_bond_802_3ad() {
+    let bond_name = "test-bond4";
+    let dummy_name = "test-bnd-dummy4";
+
+    with_bond_iface_opts(bond_name, dummy_name, &["mode", "802.3ad"], || {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", bond_name]].concat(),
+            );
+
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["link", "show", bond_name]].concat());
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    })
+}
+
+#[test]
+fn test_link_detailed_show_bond_port_802_3ad() {
+    let bond_name = "test-bond6";
+    let dummy_name = "test-bnd-dummy6";
+
+    with_bond_iface_opts(bond_name, dummy_name, &["mode", "802.3ad"], || {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", dummy_name]].concat(),
+            );
+
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["link", "show", dummy_name]].concat());
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    })
+}
+
+#[test]
+fn test_link_detailed_show_bond_active_backup() {
+    let bond_name = "test-bond5";
+    let dummy_name = "test-bnd-dummy5";
+
+    with_bond_iface_opts(
+        bond_name,
+        dummy_name,
+        &[
+            "mode",
+            "active-backup",
+            "arp_interval",
+            "100",
+            "arp_ip_target",
+            "192.0.2.1,192.0.2.2",
+        ],
+        || {
+            exec_cmd(&[
+                "ip", "link", "set", bond_name, "type", "bond", "primary",
+                dummy_name,
+            ]);
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", bond_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", bond_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+#[test]
+fn test_link_set_bond_port() {
+    let bond_name = "test-bond7";
+    let dummy_name = "test-bnd-dummy7";
+
+    with_bond_iface(bond_name, dummy_name, || {
+        ip_rs_exec_cmd(&[
+            "link",
+            "set",
+            "dev",
+            dummy_name,
+            "type",
+            "bond_slave",
+            "queue_id",
+            "3",
+            "prio",
+            "-5",
+        ]);
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", dummy_name]].concat(),
+            );
+
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["link", "show", dummy_name]].concat());
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+        let expected_output =
+            exec_cmd(&["ip", "-d", "link", "show", dummy_name]);
+        assert!(expected_output.contains(" queue_id 3 prio -5 "));
+    })
+}
+
 fn with_bond_iface<T>(bond_name: &str, dummy_name: &str, test: T)
 where
     T: FnOnce() + std::panic::UnwindSafe,
 {
-    // create bridge using dummy interface
+    with_bond_iface_opts(bond_name, dummy_name, &[], test)
+}
+
+fn with_bond_iface_opts<T>(
+    bond_name: &str,
+    dummy_name: &str,
+    bond_opts: &[&str],
+    test: T,
+) where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    // create bond using dummy interface
     exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
-    exec_cmd(&["ip", "link", "add", bond_name, "type", "bond"]);
+    exec_cmd(
+        &[&["ip", "link", "add", bond_name, "type", "bond"], bond_opts]
+            .concat(),
+    );
     exec_cmd(&["ip", "link", "set", "dev", dummy_name, "master", bond_name]);
 
     exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
     exec_cmd(&["ip", "link", "set", bond_name, "up"]);
 
-    // Wait 1 second for bridge ID to be stable
+    // Wait 1 second for bond state to be stable
     std::thread::sleep(std::time::Duration::from_secs(1));
 
     let result = std::panic::catch_unwind(|| {
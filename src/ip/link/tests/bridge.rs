@@ -165,6 +165,84 @@ fn test_link_detailed_show_json_bridge_port() {
     })
 }
 
+#[test]
+fn test_link_set_bridge_port() {
+    let br_name = "test-br4";
+    let dummy_name = "test-dummy4";
+    with_bridge_iface(br_name, dummy_name, || {
+        ip_rs_exec_cmd(&[
+            "link",
+            "set",
+            "dev",
+            dummy_name,
+            "type",
+            "bridge_slave",
+            "cost",
+            "10",
+            "hairpin",
+            "on",
+            "isolated",
+            "on",
+        ]);
+
+        let expected_output =
+            exec_cmd(&["ip", "-d", "link", "show", dummy_name]);
+
+        let our_output = ip_rs_exec_cmd(&["-d", "link", "show", dummy_name]);
+
+        assert!(expected_output.contains(" cost 10 hairpin on "));
+        assert!(expected_output.contains(" isolated on "));
+        pretty_assertions::assert_eq!(
+            normalize_timers(&expected_output),
+            normalize_timers(&our_output)
+        );
+    })
+}
+
+#[test]
+fn test_link_detailed_show_bridge_port_vlan_tunnel() {
+    let br_name = "test-br5";
+    let dummy_name = "test-dummy5";
+    with_bridge_iface(br_name, dummy_name, || {
+        ip_rs_exec_cmd(&[
+            "link",
+            "set",
+            "dev",
+            dummy_name,
+            "type",
+            "bridge_slave",
+            "vlan_tunnel",
+            "on",
+        ]);
+        exec_cmd(&["bridge", "vlan", "add", "dev", dummy_name, "vid", "10"]);
+        exec_cmd(&[
+            "bridge",
+            "vlan",
+            "add",
+            "dev",
+            dummy_name,
+            "vid",
+            "10",
+            "tunnel_info",
+            "id",
+            "1000",
+        ]);
+
+        // iproute2 does not show the VLAN tunnel mappings
+        let our_output = ip_rs_exec_cmd(&["-d", "link", "show", dummy_name]);
+        assert!(
+            our_output.contains(" vlan_tunnel on vlan_tunnel_map 10:1000 ")
+        );
+
+        let our_output =
+            ip_rs_exec_cmd(&["-d", "-j", "link", "show", dummy_name]);
+        assert!(
+            our_output
+                .contains(r#""vlan_tunnel_map":[{"vlan":10,"tunid":1000}]"#)
+        );
+    })
+}
+
 /// Since all test cases are running simultaneously, please make sure `br_name`
 /// and `dummy_name` are unique among tests.
 fn with_bridge_iface<T>(br_name: &str, dummy_name: &str, test: T)
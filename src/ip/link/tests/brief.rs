@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{assert_alias_output, exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_show_brief_lo() {
+    let expected_output = exec_cmd(&["ip", "-br", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-br", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_brief_lo_json() {
+    let expected_output = exec_cmd(&["ip", "-br", "-j", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-br", "-j", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_brief_lo_color_always() {
+    let expected_output =
+        exec_cmd(&["ip", "-br", "-c=always", "link", "show", "lo"]);
+
+    let our_output =
+        ip_rs_exec_cmd(&["-br", "-c=always", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_brief_alias() {
+    assert_alias_output(
+        &["-brief", "link", "show", "lo"],
+        &["-br", "link", "show", "lo"],
+    );
+}
+
+#[test]
+fn test_link_show_json_details_alias() {
+    assert_alias_output(
+        &["-j", "-d", "link", "show", "lo"],
+        &["-json", "-details", "link", "show", "lo"],
+    );
+}
+
+#[test]
+fn test_link_show_color_alias() {
+    assert_alias_output(
+        &["-br", "-c=always", "link", "show", "lo"],
+        &["-br", "-color", "link", "show", "lo"],
+    );
+    assert_alias_output(
+        &["-br", "-c=never", "link", "show", "lo"],
+        &["-br", "-col=never", "link", "show", "lo"],
+    );
+}
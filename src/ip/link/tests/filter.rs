@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_show_type_filter_match() {
+    let br_name = "test-fbr0";
+    let dummy_name = "test-fdummy0";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "link", "show", br_name, "type", "bridge"]);
+        let our_output =
+            ip_rs_exec_cmd(&["link", "show", br_name, "type", "bridge"]);
+
+        assert!(!our_output.is_empty());
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_type_filter_mismatch() {
+    let br_name = "test-fbr1";
+    let dummy_name = "test-fdummy1";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "link", "show", dummy_name, "type", "bridge"]);
+        let our_output =
+            ip_rs_exec_cmd(&["link", "show", dummy_name, "type", "bridge"]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_type_filter_port_kind() {
+    let br_name = "test-fbr2";
+    let dummy_name = "test-fdummy2";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output = exec_cmd(&[
+            "ip",
+            "link",
+            "show",
+            dummy_name,
+            "type",
+            "bridge_slave",
+        ]);
+        let our_output = ip_rs_exec_cmd(&[
+            "link",
+            "show",
+            dummy_name,
+            "type",
+            "bridge_slave",
+        ]);
+
+        assert!(!our_output.is_empty());
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_master_filter() {
+    let br_name = "test-fbr3";
+    let dummy_name = "test-fdummy3";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "link", "show", "master", br_name]);
+        let our_output = ip_rs_exec_cmd(&["link", "show", "master", br_name]);
+
+        assert!(our_output.contains(dummy_name));
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_nomaster_filter() {
+    let br_name = "test-fbr4";
+    let dummy_name = "test-fdummy4";
+
+    with_bridge_port(br_name, dummy_name, || {
+        for iface_name in [br_name, dummy_name] {
+            let expected_output =
+                exec_cmd(&["ip", "link", "show", iface_name, "nomaster"]);
+            let our_output =
+                ip_rs_exec_cmd(&["link", "show", iface_name, "nomaster"]);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    })
+}
+
+#[test]
+fn test_link_show_dev_keyword() {
+    let br_name = "test-fbr5";
+    let dummy_name = "test-fdummy5";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "link", "show", "dev", dummy_name]);
+        let our_output = ip_rs_exec_cmd(&["link", "show", "dev", dummy_name]);
+
+        assert!(our_output.contains(dummy_name));
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_master_filter_details() {
+    let br_name = "test-fbr6";
+    let dummy_name = "test-fdummy6";
+
+    with_bridge_port(br_name, dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-d", "link", "show", "master", br_name]);
+        let our_output =
+            ip_rs_exec_cmd(&["-d", "link", "show", "master", br_name]);
+
+        assert!(our_output.contains(&format!("master {br_name}")));
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+/// Since all test cases are running simultaneously, please make sure `br_name`
+/// and `dummy_name` are unique among tests.
+fn with_bridge_port<T>(br_name: &str, dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "add", br_name, "type", "bridge"]);
+    exec_cmd(&["ip", "link", "set", "dev", dummy_name, "master", br_name]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    exec_cmd(&["ip", "link", "del", br_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_gre() {
+    let gre_name = "test-gre0";
+
+    with_gre_iface(
+        gre_name,
+        &[
+            "type",
+            "gre",
+            "remote",
+            "192.0.2.1",
+            "local",
+            "192.0.2.2",
+            "ttl",
+            "64",
+            "key",
+            "10",
+            "csum",
+        ],
+        || {
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", gre_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", gre_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+#[test]
+fn test_link_detailed_show_gretap() {
+    let gretap_name = "test-gretap0";
+
+    with_gre_iface(
+        gretap_name,
+        &[
+            "type",
+            "gretap",
+            "remote",
+            "192.0.2.3",
+            "ikey",
+            "1.2.3.4",
+            "oseq",
+            "tos",
+            "0x10",
+            "nopmtudisc",
+        ],
+        || {
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", gretap_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", gretap_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+#[test]
+fn test_link_detailed_show_ip6gre() {
+    let gre_name = "test-ip6gre0";
+
+    with_gre_iface(
+        gre_name,
+        &[
+            "type",
+            "ip6gre",
+            "remote",
+            "2001:db8::1",
+            "local",
+            "2001:db8::2",
+            "hoplimit",
+            "64",
+            "encaplimit",
+            "4",
+            "tclass",
+            "0x10",
+            "flowlabel",
+            "0x12345",
+        ],
+        || {
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", gre_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", gre_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+#[test]
+fn test_link_detailed_show_ip6gretap() {
+    let gretap_name = "test-ip6gretap0";
+
+    with_gre_iface(
+        gretap_name,
+        &[
+            "type",
+            "ip6gretap",
+            "remote",
+            "2001:db8::3",
+            "encaplimit",
+            "none",
+            "tclass",
+            "inherit",
+            "flowlabel",
+            "inherit",
+            "dscp",
+            "inherit",
+            "allow-localremote",
+        ],
+        || {
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", gretap_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", gretap_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+fn with_gre_iface<T>(name: &str, opts: &[&str], test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&[&["ip", "link", "add", name], opts].concat());
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT
+
+use super::{assert_detailed_link_show, with_link_ifaces};
+
+#[test]
+fn test_link_detailed_show_hsr() {
+    let hsr_name = "test-hsr0";
+    let dummy1_name = "test-hsr-dum0";
+    let dummy2_name = "test-hsr-dum1";
+
+    with_link_ifaces(
+        &[
+            (dummy1_name, &["type", "dummy"]),
+            (dummy2_name, &["type", "dummy"]),
+            (
+                hsr_name,
+                &[
+                    "type",
+                    "hsr",
+                    "slave1",
+                    dummy1_name,
+                    "slave2",
+                    dummy2_name,
+                    "supervision",
+                    "45",
+                    "version",
+                    "1",
+                ],
+            ),
+        ],
+        || assert_detailed_link_show(hsr_name),
+    )
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_ipvlan() {
+    let ipvlan_name = "test-ipvl0";
+    let dummy_name = "test-ipvl-dum0";
+
+    with_ipvlan_iface(ipvlan_name, dummy_name, &["mode", "l3s"], || {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", ipvlan_name]].concat(),
+            );
+
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["link", "show", ipvlan_name]].concat(),
+            );
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    })
+}
+
+#[test]
+fn test_link_detailed_show_ipvlan_private() {
+    let ipvlan_name = "test-ipvl1";
+    let dummy_name = "test-ipvl-dum1";
+
+    with_ipvlan_iface(
+        ipvlan_name,
+        dummy_name,
+        &["mode", "l2", "private"],
+        || {
+            for args in [&["-d"][..], &["-d", "-j"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["link", "show", ipvlan_name]].concat(),
+                );
+
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["link", "show", ipvlan_name]].concat(),
+                );
+
+                pretty_assertions::assert_eq!(&expected_output, &our_output);
+            }
+        },
+    )
+}
+
+fn with_ipvlan_iface<T>(
+    ipvlan_name: &str,
+    dummy_name: &str,
+    ipvlan_opts: &[&str],
+    test: T,
+) where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(
+        &[
+            &[
+                "ip",
+                "link",
+                "add",
+                "link",
+                dummy_name,
+                ipvlan_name,
+                "type",
+                "ipvlan",
+            ],
+            ipvlan_opts,
+        ]
+        .concat(),
+    );
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up, the ipvlan is removed along with its lower interface
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
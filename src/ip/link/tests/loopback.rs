@@ -20,6 +20,69 @@ fn test_link_show_lo_json() {
     pretty_assertions::assert_eq!(expected_output, our_output);
 }
 
+#[test]
+fn test_link_show_lo_oneline() {
+    let expected_output = exec_cmd(&["ip", "-o", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-o", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_lo_oneline_long_option() {
+    let expected_output = exec_cmd(&["ip", "-oneline", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-oneline", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_lo_timestamp() {
+    let expected_output = exec_cmd(&["ip", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-timestamp", "link", "show", "lo"]);
+    let (timestamp, our_output) = our_output
+        .split_once('\n')
+        .expect("No timestamp line found");
+
+    assert!(timestamp.starts_with("Timestamp: "));
+    assert!(timestamp.ends_with(" usec"));
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_lo_tshort() {
+    let expected_output = exec_cmd(&["ip", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-ts", "link", "show", "lo"]);
+    let (timestamp, our_output) = our_output
+        .split_once("] ")
+        .expect("No timestamp prefix found");
+
+    assert!(timestamp.starts_with('['));
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_lo_numeric() {
+    let expected_output = exec_cmd(&["ip", "-N", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-N", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_link_show_lo_numeric_json() {
+    let expected_output = exec_cmd(&["ip", "-N", "-j", "link", "show", "lo"]);
+
+    let our_output = ip_rs_exec_cmd(&["-N", "-j", "link", "show", "lo"]);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
 #[test]
 fn test_link_alias_l_l() {
     assert_alias_output(&["link", "show", "lo"], &["l", "l", "lo"]);
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_macsec() {
+    let macsec_name = "test-macsec0";
+    let dummy_name = "test-macsec-dum0";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "add",
+        "link",
+        dummy_name,
+        macsec_name,
+        "type",
+        "macsec",
+        "port",
+        "11",
+        "encrypt",
+        "on",
+        "replay",
+        "on",
+        "window",
+        "8",
+        "validate",
+        "check",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", macsec_name]].concat(),
+            );
+
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["link", "show", macsec_name]].concat(),
+            );
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    });
+
+    // clean up, the macsec is removed along with its lower interface
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
@@ -2,5 +2,58 @@
 
 mod bond;
 mod bridge;
+mod brief;
 mod color;
+mod filter;
+mod gre;
+mod hsr;
+mod ipvlan;
 mod loopback;
+mod macsec;
+mod stats;
+mod tun;
+mod unknown_kind;
+mod veth;
+mod vlan;
+mod vrf;
+mod vti;
+mod wireguard;
+mod xfrm;
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+/// Create the interfaces by `ip link add <name> <args>` in the order given,
+/// run the test and delete them in reverse order. Since all test cases are
+/// running simultaneously, please make sure interface names are unique among
+/// tests.
+fn with_link_ifaces<T>(ifaces: &[(&str, &[&str])], test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    for (name, args) in ifaces {
+        exec_cmd(&[&["ip", "link", "add", name], *args].concat());
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    for (name, _) in ifaces.iter().rev() {
+        exec_cmd(&["ip", "link", "del", name]);
+    }
+    assert!(result.is_ok())
+}
+
+/// Compare `ip -d link show` and its JSON output with iproute2
+fn assert_detailed_link_show(iface_name: &str) {
+    for args in [&["-d"][..], &["-d", "-j"][..]] {
+        let expected_output =
+            exec_cmd(&[&["ip"], args, &["link", "show", iface_name]].concat());
+
+        let our_output =
+            ip_rs_exec_cmd(&[args, &["link", "show", iface_name]].concat());
+
+        pretty_assertions::assert_eq!(&expected_output, &our_output);
+    }
+}
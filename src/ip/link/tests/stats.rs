@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_show_stats() {
+    let dummy_name = "test-sdummy0";
+
+    with_dummy_iface(dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-s", "link", "show", dummy_name]);
+        let our_output = ip_rs_exec_cmd(&["-s", "link", "show", dummy_name]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_stats_error_details() {
+    let dummy_name = "test-sdummy1";
+
+    with_dummy_iface(dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-s", "-s", "link", "show", dummy_name]);
+        let our_output =
+            ip_rs_exec_cmd(&["-s", "-s", "link", "show", dummy_name]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_stats_error_details_json() {
+    let dummy_name = "test-sdummy2";
+
+    with_dummy_iface(dummy_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-s", "-s", "-j", "link", "show", dummy_name]);
+        let our_output =
+            ip_rs_exec_cmd(&["-s", "-s", "-j", "link", "show", dummy_name]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    })
+}
+
+#[test]
+fn test_link_show_stats_human() {
+    let dummy_name = "test-sdummy4";
+
+    with_dummy_iface(dummy_name, || {
+        for args in [
+            ["-s", "-s", "-h", "link", "show", dummy_name],
+            ["-s", "-s", "-human", "link", "show", dummy_name],
+            ["-s", "-h", "-iec", "link", "show", dummy_name],
+        ] {
+            let expected_output =
+                exec_cmd(&[&["ip"], args.as_slice()].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    })
+}
+
+/// Since all test cases are running simultaneously, please make sure
+/// `dummy_name` is unique among tests.
+fn with_dummy_iface<T>(dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_tap() {
+    let tap_name = "test-tap0";
+
+    with_tuntap_iface(tap_name, &["mode", "tap", "user", "0"], || {
+        let expected_output = exec_cmd(&["ip", "-d", "link", "show", tap_name]);
+
+        let our_output = ip_rs_exec_cmd(&["-d", "link", "show", tap_name]);
+
+        pretty_assertions::assert_eq!(&expected_output, &our_output);
+    })
+}
+
+#[test]
+fn test_link_detailed_show_json_tun_multi_queue() {
+    let tun_name = "test-tun1";
+
+    with_tuntap_iface(
+        tun_name,
+        &["mode", "tun", "multi_queue", "group", "0"],
+        || {
+            let expected_output =
+                exec_cmd(&["ip", "-d", "-j", "link", "show", tun_name]);
+
+            let our_output =
+                ip_rs_exec_cmd(&["-d", "-j", "link", "show", tun_name]);
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        },
+    )
+}
+
+fn with_tuntap_iface<T>(name: &str, opts: &[&str], test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&[&["ip", "tuntap", "add", "dev", name], opts].concat());
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+
+use super::{assert_detailed_link_show, with_link_ifaces};
+
+// The nlmon kind has no link info data parser, only the kind should be shown
+#[test]
+fn test_link_detailed_show_unknown_kind() {
+    let nlmon_name = "test-nlmon0";
+
+    with_link_ifaces(&[(nlmon_name, &["type", "nlmon"])], || {
+        assert_detailed_link_show(nlmon_name)
+    })
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_veth() {
+    let veth_name = "test-veth0";
+    let peer_name = "test-veth0-ep";
+
+    with_veth_iface(veth_name, peer_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-d", "link", "show", veth_name]);
+
+        let our_output = ip_rs_exec_cmd(&["-d", "link", "show", veth_name]);
+
+        pretty_assertions::assert_eq!(&expected_output, &our_output);
+    })
+}
+
+#[test]
+fn test_link_detailed_show_json_veth() {
+    let veth_name = "test-veth1";
+    let peer_name = "test-veth1-ep";
+
+    with_veth_iface(veth_name, peer_name, || {
+        let expected_output =
+            exec_cmd(&["ip", "-d", "-j", "link", "show", peer_name]);
+
+        let our_output =
+            ip_rs_exec_cmd(&["-d", "-j", "link", "show", peer_name]);
+
+        pretty_assertions::assert_eq!(&expected_output, &our_output);
+    })
+}
+
+fn with_veth_iface<T>(veth_name: &str, peer_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        peer_name,
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up, the peer is removed along with it
+    exec_cmd(&["ip", "link", "del", veth_name]);
+    assert!(result.is_ok())
+}
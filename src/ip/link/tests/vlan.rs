@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_add_vlan_qos_map() {
+    let dummy_name = "test-vdummy0";
+    let vlan_name = "test-vlan0";
+
+    with_dummy_iface(dummy_name, vlan_name, || {
+        ip_rs_exec_cmd(&[
+            "link",
+            "add",
+            "link",
+            dummy_name,
+            "name",
+            vlan_name,
+            "type",
+            "vlan",
+            "id",
+            "10",
+            "ingress-qos-map",
+            "1:2",
+            "3:4",
+            "egress-qos-map",
+            "2:5",
+        ]);
+        assert!(
+            exec_cmd(&["ip", "-d", "link", "show", vlan_name])
+                .contains("ingress-qos-map { 1:2 3:4 }")
+        );
+        assert_link_show(vlan_name);
+
+        ip_rs_exec_cmd(&[
+            "link",
+            "set",
+            "dev",
+            vlan_name,
+            "type",
+            "vlan",
+            "egress-qos-map",
+            "6:7",
+        ]);
+        assert!(
+            exec_cmd(&["ip", "-d", "link", "show", vlan_name])
+                .contains("egress-qos-map { 2:5 6:7 }")
+        );
+        assert_link_show(vlan_name);
+    })
+}
+
+fn assert_link_show(iface_name: &str) {
+    for args in [&["-d"][..], &["-d", "-j"][..]] {
+        let expected_output =
+            exec_cmd(&[&["ip"], args, &["link", "show", iface_name]].concat());
+
+        let our_output =
+            ip_rs_exec_cmd(&[args, &["link", "show", iface_name]].concat());
+
+        pretty_assertions::assert_eq!(&expected_output, &our_output);
+    }
+}
+
+/// Since all test cases are running simultaneously, please make sure
+/// `dummy_name` and `vlan_name` are unique among tests.
+fn with_dummy_iface<T>(dummy_name: &str, vlan_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", vlan_name]);
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd};
+
+#[test]
+fn test_link_detailed_show_vrf() {
+    let vrf_name = "test-vrf0";
+    let dummy_name = "test-vrf-dum0";
+
+    with_vrf_iface(vrf_name, dummy_name, || {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", vrf_name]].concat(),
+            );
+
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["link", "show", vrf_name]].concat());
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    })
+}
+
+#[test]
+fn test_link_detailed_show_vrf_port() {
+    let vrf_name = "test-vrf1";
+    let dummy_name = "test-vrf-dum1";
+
+    with_vrf_iface(vrf_name, dummy_name, || {
+        for args in [&["-d"][..], &["-d", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["link", "show", dummy_name]].concat(),
+            );
+
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["link", "show", dummy_name]].concat());
+
+            pretty_assertions::assert_eq!(&expected_output, &our_output);
+        }
+    })
+}
+
+fn with_vrf_iface<T>(vrf_name: &str, dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", vrf_name, "type", "vrf", "table", "100"]);
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "master", vrf_name]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    exec_cmd(&["ip", "link", "del", vrf_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT
+
+use super::{assert_detailed_link_show, with_link_ifaces};
+
+#[test]
+fn test_link_detailed_show_vti() {
+    let vti_name = "test-vti0";
+
+    with_link_ifaces(
+        &[(
+            vti_name,
+            &[
+                "type",
+                "vti",
+                "remote",
+                "192.0.2.1",
+                "local",
+                "192.0.2.2",
+                "ikey",
+                "5",
+                "okey",
+                "1.2.3.4",
+            ],
+        )],
+        || assert_detailed_link_show(vti_name),
+    )
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MIT
+
+use super::{assert_detailed_link_show, with_link_ifaces};
+
+// Like iproute2, only the kind is shown, the wireguard options like
+// listen-port are shown by the `wg` tool.
+#[test]
+fn test_link_detailed_show_wireguard() {
+    let wg_name = "test-wg0";
+
+    with_link_ifaces(&[(wg_name, &["type", "wireguard"])], || {
+        assert_detailed_link_show(wg_name)
+    })
+}
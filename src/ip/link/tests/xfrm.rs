@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT
+
+use super::{assert_detailed_link_show, with_link_ifaces};
+
+#[test]
+fn test_link_detailed_show_xfrm() {
+    let xfrm_name = "test-xfrm0";
+    let dummy_name = "test-xfrm-dum0";
+
+    with_link_ifaces(
+        &[
+            (dummy_name, &["type", "dummy"]),
+            (
+                xfrm_name,
+                &["type", "xfrm", "dev", dummy_name, "if_id", "0x10"],
+            ),
+        ],
+        || assert_detailed_link_show(xfrm_name),
+    )
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::{MacsecModifyCmd, handle_modify},
+    show::{CliMacsecInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+pub(crate) struct MacsecCommand;
+
+impl MacsecCommand {
+    pub(crate) const CMD: &'static str = "macsec";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("MACsec device configuration")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show MACsec devices")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add secure channel or association")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("set")
+                    .about("change secure channel or association")
+                    .alias("se")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete secure channel or association")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliMacsecInfo>>, CliError> {
+        for (name, cmd) in [
+            ("add", MacsecModifyCmd::Add),
+            ("set", MacsecModifyCmd::Set),
+            ("delete", MacsecModifyCmd::Del),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(ctx, cmd, &opts).await?;
+                return Ok(None);
+            }
+        }
+
+        let opts: Vec<&str> = matches
+            .subcommand_matches("show")
+            .and_then(|matches| matches.get_many::<String>("options"))
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_show(ctx, &opts, matches.get_count("STATS") > 0)
+            .await
+            .map(Into::into)
+    }
+}
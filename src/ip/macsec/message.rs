@@ -0,0 +1,609 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_u8, parse_u32,
+    parse_u64, parse_u64_be,
+};
+
+// Equal to `linux/if_macsec.h`
+const MACSEC_GENL_NAME: &str = "macsec";
+const MACSEC_GENL_VERSION: u8 = 0x1;
+
+const MACSEC_CMD_GET_TXSC: u8 = 0;
+const MACSEC_CMD_ADD_RXSC: u8 = 1;
+const MACSEC_CMD_DEL_RXSC: u8 = 2;
+const MACSEC_CMD_UPD_RXSC: u8 = 3;
+const MACSEC_CMD_ADD_TXSA: u8 = 4;
+const MACSEC_CMD_DEL_TXSA: u8 = 5;
+const MACSEC_CMD_UPD_TXSA: u8 = 6;
+const MACSEC_CMD_ADD_RXSA: u8 = 7;
+const MACSEC_CMD_DEL_RXSA: u8 = 8;
+const MACSEC_CMD_UPD_RXSA: u8 = 9;
+
+const MACSEC_ATTR_IFINDEX: u16 = 1;
+const MACSEC_ATTR_RXSC_CONFIG: u16 = 2;
+const MACSEC_ATTR_SA_CONFIG: u16 = 3;
+const MACSEC_ATTR_SECY: u16 = 4;
+const MACSEC_ATTR_TXSA_LIST: u16 = 5;
+const MACSEC_ATTR_RXSC_LIST: u16 = 6;
+const MACSEC_ATTR_TXSC_STATS: u16 = 7;
+const MACSEC_ATTR_SECY_STATS: u16 = 8;
+const MACSEC_ATTR_OFFLOAD: u16 = 9;
+
+const MACSEC_SECY_ATTR_SCI: u16 = 1;
+const MACSEC_SECY_ATTR_ENCODING_SA: u16 = 2;
+const MACSEC_SECY_ATTR_WINDOW: u16 = 3;
+const MACSEC_SECY_ATTR_CIPHER_SUITE: u16 = 4;
+const MACSEC_SECY_ATTR_ICV_LEN: u16 = 5;
+const MACSEC_SECY_ATTR_PROTECT: u16 = 6;
+const MACSEC_SECY_ATTR_REPLAY: u16 = 7;
+const MACSEC_SECY_ATTR_VALIDATE: u16 = 9;
+const MACSEC_SECY_ATTR_ENCRYPT: u16 = 10;
+const MACSEC_SECY_ATTR_INC_SCI: u16 = 11;
+const MACSEC_SECY_ATTR_ES: u16 = 12;
+const MACSEC_SECY_ATTR_SCB: u16 = 13;
+
+const MACSEC_RXSC_ATTR_SCI: u16 = 1;
+const MACSEC_RXSC_ATTR_ACTIVE: u16 = 2;
+const MACSEC_RXSC_ATTR_SA_LIST: u16 = 3;
+const MACSEC_RXSC_ATTR_STATS: u16 = 4;
+
+const MACSEC_SA_ATTR_AN: u16 = 1;
+const MACSEC_SA_ATTR_ACTIVE: u16 = 2;
+const MACSEC_SA_ATTR_PN: u16 = 3;
+const MACSEC_SA_ATTR_KEY: u16 = 4;
+const MACSEC_SA_ATTR_KEYID: u16 = 5;
+const MACSEC_SA_ATTR_STATS: u16 = 6;
+
+const MACSEC_OFFLOAD_ATTR_TYPE: u16 = 1;
+
+pub(crate) const MACSEC_KEYID_LEN: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MacsecCmd {
+    GetTxsc,
+    AddRxsc,
+    DelRxsc,
+    UpdRxsc,
+    AddTxsa,
+    DelTxsa,
+    UpdTxsa,
+    AddRxsa,
+    DelRxsa,
+    UpdRxsa,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MacsecMessage {
+    pub(crate) cmd: MacsecCmd,
+    pub(crate) attributes: Vec<MacsecAttr>,
+}
+
+impl MacsecMessage {
+    pub(crate) fn new(cmd: MacsecCmd, attributes: Vec<MacsecAttr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for MacsecMessage {
+    fn family_name() -> &'static str {
+        MACSEC_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            MacsecCmd::GetTxsc => MACSEC_CMD_GET_TXSC,
+            MacsecCmd::AddRxsc => MACSEC_CMD_ADD_RXSC,
+            MacsecCmd::DelRxsc => MACSEC_CMD_DEL_RXSC,
+            MacsecCmd::UpdRxsc => MACSEC_CMD_UPD_RXSC,
+            MacsecCmd::AddTxsa => MACSEC_CMD_ADD_TXSA,
+            MacsecCmd::DelTxsa => MACSEC_CMD_DEL_TXSA,
+            MacsecCmd::UpdTxsa => MACSEC_CMD_UPD_TXSA,
+            MacsecCmd::AddRxsa => MACSEC_CMD_ADD_RXSA,
+            MacsecCmd::DelRxsa => MACSEC_CMD_DEL_RXSA,
+            MacsecCmd::UpdRxsa => MACSEC_CMD_UPD_RXSA,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        MACSEC_GENL_VERSION
+    }
+}
+
+impl Emitable for MacsecMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for MacsecMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            MACSEC_CMD_GET_TXSC => MacsecCmd::GetTxsc,
+            MACSEC_CMD_ADD_RXSC => MacsecCmd::AddRxsc,
+            MACSEC_CMD_DEL_RXSC => MacsecCmd::DelRxsc,
+            MACSEC_CMD_UPD_RXSC => MacsecCmd::UpdRxsc,
+            MACSEC_CMD_ADD_TXSA => MacsecCmd::AddTxsa,
+            MACSEC_CMD_DEL_TXSA => MacsecCmd::DelTxsa,
+            MACSEC_CMD_UPD_TXSA => MacsecCmd::UpdTxsa,
+            MACSEC_CMD_ADD_RXSA => MacsecCmd::AddRxsa,
+            MACSEC_CMD_DEL_RXSA => MacsecCmd::DelRxsa,
+            MACSEC_CMD_UPD_RXSA => MacsecCmd::UpdRxsa,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown macsec command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid macsec attribute")?;
+            attributes.push(MacsecAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+fn parse_nested<T>(payload: &[u8], ctx: &str) -> Result<Vec<T>, DecodeError>
+where
+    T: for<'a> Parseable<NlaBuffer<&'a [u8]>>,
+{
+    let mut ret = Vec::new();
+    for nla in NlasIterator::new(payload) {
+        let nla = nla.context(ctx)?;
+        ret.push(T::parse(&nla)?);
+    }
+    Ok(ret)
+}
+
+// The kernel nests each entry of the SA and RX SC lists with the
+// attribute type of its one-based position.
+fn parse_nested_list<T>(
+    payload: &[u8],
+    ctx: &str,
+) -> Result<Vec<Vec<T>>, DecodeError>
+where
+    T: for<'a> Parseable<NlaBuffer<&'a [u8]>>,
+{
+    let mut ret = Vec::new();
+    for nla in NlasIterator::new(payload) {
+        let nla = nla.context(ctx)?;
+        ret.push(parse_nested(nla.value(), ctx)?);
+    }
+    Ok(ret)
+}
+
+fn nested_list_len<T: Nla>(list: &[Vec<T>]) -> usize {
+    list.iter()
+        .enumerate()
+        .map(|(i, attrs)| MacsecListEntry(i as u16 + 1, attrs).buffer_len())
+        .sum()
+}
+
+fn emit_nested_list<T: Nla>(list: &[Vec<T>], buffer: &mut [u8]) {
+    let entries: Vec<MacsecListEntry<'_, T>> = list
+        .iter()
+        .enumerate()
+        .map(|(i, attrs)| MacsecListEntry(i as u16 + 1, attrs))
+        .collect();
+    entries.as_slice().emit(buffer)
+}
+
+struct MacsecListEntry<'a, T>(u16, &'a [T]);
+
+impl<T: Nla> Nla for MacsecListEntry<'_, T> {
+    fn value_len(&self) -> usize {
+        self.1.buffer_len()
+    }
+
+    fn kind(&self) -> u16 {
+        self.0
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.1.emit(buffer)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MacsecAttr {
+    Ifindex(u32),
+    RxscConfig(Vec<MacsecRxscAttr>),
+    SaConfig(Vec<MacsecSaAttr>),
+    Secy(Vec<MacsecSecyAttr>),
+    TxsaList(Vec<Vec<MacsecSaAttr>>),
+    RxscList(Vec<Vec<MacsecRxscAttr>>),
+    TxscStats(Vec<MacsecStat>),
+    SecyStats(Vec<MacsecStat>),
+    /// The `MACSEC_OFFLOAD_ATTR_TYPE` of nested `MACSEC_ATTR_OFFLOAD`
+    Offload(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for MacsecAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Ifindex(_) => 4,
+            Self::RxscConfig(v) => v.as_slice().buffer_len(),
+            Self::SaConfig(v) => v.as_slice().buffer_len(),
+            Self::Secy(v) => v.as_slice().buffer_len(),
+            Self::TxsaList(v) => nested_list_len(v),
+            Self::RxscList(v) => nested_list_len(v),
+            Self::TxscStats(v) | Self::SecyStats(v) => {
+                v.as_slice().buffer_len()
+            }
+            Self::Offload(v) => {
+                DefaultNla::new(MACSEC_OFFLOAD_ATTR_TYPE, vec![*v]).buffer_len()
+            }
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Ifindex(_) => MACSEC_ATTR_IFINDEX,
+            Self::RxscConfig(_) => MACSEC_ATTR_RXSC_CONFIG,
+            Self::SaConfig(_) => MACSEC_ATTR_SA_CONFIG,
+            Self::Secy(_) => MACSEC_ATTR_SECY,
+            Self::TxsaList(_) => MACSEC_ATTR_TXSA_LIST,
+            Self::RxscList(_) => MACSEC_ATTR_RXSC_LIST,
+            Self::TxscStats(_) => MACSEC_ATTR_TXSC_STATS,
+            Self::SecyStats(_) => MACSEC_ATTR_SECY_STATS,
+            Self::Offload(_) => MACSEC_ATTR_OFFLOAD,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Ifindex(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::RxscConfig(v) => v.as_slice().emit(buffer),
+            Self::SaConfig(v) => v.as_slice().emit(buffer),
+            Self::Secy(v) => v.as_slice().emit(buffer),
+            Self::TxsaList(v) => emit_nested_list(v, buffer),
+            Self::RxscList(v) => emit_nested_list(v, buffer),
+            Self::TxscStats(v) | Self::SecyStats(v) => {
+                v.as_slice().emit(buffer)
+            }
+            Self::Offload(v) => {
+                DefaultNla::new(MACSEC_OFFLOAD_ATTR_TYPE, vec![*v]).emit(buffer)
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MacsecAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MACSEC_ATTR_IFINDEX => Self::Ifindex(parse_u32(payload)?),
+            MACSEC_ATTR_RXSC_CONFIG => Self::RxscConfig(parse_nested(
+                payload,
+                "invalid macsec RX SC attribute",
+            )?),
+            MACSEC_ATTR_SA_CONFIG => Self::SaConfig(parse_nested(
+                payload,
+                "invalid macsec SA attribute",
+            )?),
+            MACSEC_ATTR_SECY => Self::Secy(parse_nested(
+                payload,
+                "invalid macsec SecY attribute",
+            )?),
+            MACSEC_ATTR_TXSA_LIST => Self::TxsaList(parse_nested_list(
+                payload,
+                "invalid macsec TX SA list",
+            )?),
+            MACSEC_ATTR_RXSC_LIST => Self::RxscList(parse_nested_list(
+                payload,
+                "invalid macsec RX SC list",
+            )?),
+            MACSEC_ATTR_TXSC_STATS => Self::TxscStats(parse_nested(
+                payload,
+                "invalid macsec TX SC stats",
+            )?),
+            MACSEC_ATTR_SECY_STATS => Self::SecyStats(parse_nested(
+                payload,
+                "invalid macsec SecY stats",
+            )?),
+            MACSEC_ATTR_OFFLOAD => {
+                let mut offload = 0;
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid macsec offload")?;
+                    if nla.kind() == MACSEC_OFFLOAD_ATTR_TYPE {
+                        offload = parse_u8(nla.value())?;
+                    }
+                }
+                Self::Offload(offload)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `MACSEC_ATTR_SECY`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MacsecSecyAttr {
+    /// SCI in host byte order
+    Sci(u64),
+    EncodingSa(u8),
+    Window(u32),
+    CipherSuite(u64),
+    IcvLen(u8),
+    Protect(bool),
+    Replay(bool),
+    Validate(u8),
+    Encrypt(bool),
+    IncSci(bool),
+    Es(bool),
+    Scb(bool),
+    Other(DefaultNla),
+}
+
+impl Nla for MacsecSecyAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Sci(_) | Self::CipherSuite(_) => 8,
+            Self::Window(_) => 4,
+            Self::EncodingSa(_)
+            | Self::IcvLen(_)
+            | Self::Protect(_)
+            | Self::Replay(_)
+            | Self::Validate(_)
+            | Self::Encrypt(_)
+            | Self::IncSci(_)
+            | Self::Es(_)
+            | Self::Scb(_) => 1,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Sci(_) => MACSEC_SECY_ATTR_SCI,
+            Self::EncodingSa(_) => MACSEC_SECY_ATTR_ENCODING_SA,
+            Self::Window(_) => MACSEC_SECY_ATTR_WINDOW,
+            Self::CipherSuite(_) => MACSEC_SECY_ATTR_CIPHER_SUITE,
+            Self::IcvLen(_) => MACSEC_SECY_ATTR_ICV_LEN,
+            Self::Protect(_) => MACSEC_SECY_ATTR_PROTECT,
+            Self::Replay(_) => MACSEC_SECY_ATTR_REPLAY,
+            Self::Validate(_) => MACSEC_SECY_ATTR_VALIDATE,
+            Self::Encrypt(_) => MACSEC_SECY_ATTR_ENCRYPT,
+            Self::IncSci(_) => MACSEC_SECY_ATTR_INC_SCI,
+            Self::Es(_) => MACSEC_SECY_ATTR_ES,
+            Self::Scb(_) => MACSEC_SECY_ATTR_SCB,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Sci(v) => buffer.copy_from_slice(&v.to_be_bytes()),
+            Self::CipherSuite(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Window(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::EncodingSa(v) | Self::IcvLen(v) | Self::Validate(v) => {
+                buffer[0] = *v
+            }
+            Self::Protect(v)
+            | Self::Replay(v)
+            | Self::Encrypt(v)
+            | Self::IncSci(v)
+            | Self::Es(v)
+            | Self::Scb(v) => buffer[0] = u8::from(*v),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MacsecSecyAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MACSEC_SECY_ATTR_SCI => Self::Sci(parse_u64_be(payload)?),
+            MACSEC_SECY_ATTR_ENCODING_SA => {
+                Self::EncodingSa(parse_u8(payload)?)
+            }
+            MACSEC_SECY_ATTR_WINDOW => Self::Window(parse_u32(payload)?),
+            MACSEC_SECY_ATTR_CIPHER_SUITE => {
+                Self::CipherSuite(parse_u64(payload)?)
+            }
+            MACSEC_SECY_ATTR_ICV_LEN => Self::IcvLen(parse_u8(payload)?),
+            MACSEC_SECY_ATTR_PROTECT => Self::Protect(parse_u8(payload)? != 0),
+            MACSEC_SECY_ATTR_REPLAY => Self::Replay(parse_u8(payload)? != 0),
+            MACSEC_SECY_ATTR_VALIDATE => Self::Validate(parse_u8(payload)?),
+            MACSEC_SECY_ATTR_ENCRYPT => Self::Encrypt(parse_u8(payload)? != 0),
+            MACSEC_SECY_ATTR_INC_SCI => Self::IncSci(parse_u8(payload)? != 0),
+            MACSEC_SECY_ATTR_ES => Self::Es(parse_u8(payload)? != 0),
+            MACSEC_SECY_ATTR_SCB => Self::Scb(parse_u8(payload)? != 0),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `MACSEC_ATTR_RXSC_CONFIG` and the entries of
+/// `MACSEC_ATTR_RXSC_LIST`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MacsecRxscAttr {
+    /// SCI in host byte order
+    Sci(u64),
+    Active(bool),
+    SaList(Vec<Vec<MacsecSaAttr>>),
+    Stats(Vec<MacsecStat>),
+    Other(DefaultNla),
+}
+
+impl Nla for MacsecRxscAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Sci(_) => 8,
+            Self::Active(_) => 1,
+            Self::SaList(v) => nested_list_len(v),
+            Self::Stats(v) => v.as_slice().buffer_len(),
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Sci(_) => MACSEC_RXSC_ATTR_SCI,
+            Self::Active(_) => MACSEC_RXSC_ATTR_ACTIVE,
+            Self::SaList(_) => MACSEC_RXSC_ATTR_SA_LIST,
+            Self::Stats(_) => MACSEC_RXSC_ATTR_STATS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Sci(v) => buffer.copy_from_slice(&v.to_be_bytes()),
+            Self::Active(v) => buffer[0] = u8::from(*v),
+            Self::SaList(v) => emit_nested_list(v, buffer),
+            Self::Stats(v) => v.as_slice().emit(buffer),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MacsecRxscAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MACSEC_RXSC_ATTR_SCI => Self::Sci(parse_u64_be(payload)?),
+            MACSEC_RXSC_ATTR_ACTIVE => Self::Active(parse_u8(payload)? != 0),
+            MACSEC_RXSC_ATTR_SA_LIST => Self::SaList(parse_nested_list(
+                payload,
+                "invalid macsec RX SA list",
+            )?),
+            MACSEC_RXSC_ATTR_STATS => Self::Stats(parse_nested(
+                payload,
+                "invalid macsec RX SC stats",
+            )?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `MACSEC_ATTR_SA_CONFIG` and the entries of SA
+/// lists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MacsecSaAttr {
+    An(u8),
+    Active(bool),
+    Pn(u32),
+    Key(Vec<u8>),
+    KeyId([u8; MACSEC_KEYID_LEN]),
+    Stats(Vec<MacsecStat>),
+    Other(DefaultNla),
+}
+
+impl Nla for MacsecSaAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::An(_) | Self::Active(_) => 1,
+            Self::Pn(_) => 4,
+            Self::Key(v) => v.len(),
+            Self::KeyId(_) => MACSEC_KEYID_LEN,
+            Self::Stats(v) => v.as_slice().buffer_len(),
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::An(_) => MACSEC_SA_ATTR_AN,
+            Self::Active(_) => MACSEC_SA_ATTR_ACTIVE,
+            Self::Pn(_) => MACSEC_SA_ATTR_PN,
+            Self::Key(_) => MACSEC_SA_ATTR_KEY,
+            Self::KeyId(_) => MACSEC_SA_ATTR_KEYID,
+            Self::Stats(_) => MACSEC_SA_ATTR_STATS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::An(v) => buffer[0] = *v,
+            Self::Active(v) => buffer[0] = u8::from(*v),
+            Self::Pn(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Key(v) => buffer.copy_from_slice(v),
+            Self::KeyId(v) => buffer.copy_from_slice(v),
+            Self::Stats(v) => v.as_slice().emit(buffer),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MacsecSaAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MACSEC_SA_ATTR_AN => Self::An(parse_u8(payload)?),
+            MACSEC_SA_ATTR_ACTIVE => Self::Active(parse_u8(payload)? != 0),
+            // Only the lower 32 bits are shown for extended packet number
+            MACSEC_SA_ATTR_PN => Self::Pn(parse_u32(payload)?),
+            MACSEC_SA_ATTR_KEY => Self::Key(payload.to_vec()),
+            MACSEC_SA_ATTR_KEYID => Self::KeyId(
+                <[u8; MACSEC_KEYID_LEN]>::try_from(payload).map_err(|_| {
+                    DecodeError::from(format!(
+                        "invalid macsec key ID length {}",
+                        payload.len()
+                    ))
+                })?,
+            ),
+            MACSEC_SA_ATTR_STATS => {
+                Self::Stats(parse_nested(payload, "invalid macsec SA stats")?)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The counter of nested `MACSEC_*_STATS_ATTR_*`, the kernel reports the
+/// SA counters in 32 bits and others in 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MacsecStat {
+    pub(crate) kind: u16,
+    pub(crate) value: u64,
+}
+
+impl MacsecStat {
+    pub(crate) fn new(kind: u16, value: u64) -> Self {
+        Self { kind, value }
+    }
+}
+
+impl Nla for MacsecStat {
+    fn value_len(&self) -> usize {
+        8
+    }
+
+    fn kind(&self) -> u16 {
+        self.kind
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.value.to_ne_bytes())
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MacsecStat {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        let value = match payload.len() {
+            4 => u64::from(parse_u32(payload)?),
+            // The padding attribute has no payload
+            0 => 0,
+            _ => parse_u64(payload)?,
+        };
+        Ok(Self::new(buf.kind(), value))
+    }
+}
@@ -0,0 +1,379 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+
+use super::message::{
+    MACSEC_KEYID_LEN, MacsecAttr, MacsecCmd, MacsecMessage, MacsecRxscAttr,
+    MacsecSaAttr,
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    link::parse_num,
+    neigh::parse_lladdr,
+    route::{get_ifnames, ifname_to_index},
+};
+
+// Equal to `MACSEC_MAX_KEY_LEN` of `linux/if_macsec.h`
+const MACSEC_MAX_KEY_LEN: usize = 128;
+
+const MACSEC_USAGE: &str = "\
+Usage: ip macsec add DEV tx sa { 0..3 } [ OPTS ] key ID KEY
+       ip macsec set DEV tx sa { 0..3 } [ OPTS ]
+       ip macsec del DEV tx sa { 0..3 }
+       ip macsec add DEV rx SCI [ on | off ]
+       ip macsec set DEV rx SCI [ on | off ]
+       ip macsec del DEV rx SCI
+       ip macsec add DEV rx SCI sa { 0..3 } [ OPTS ] key ID KEY
+       ip macsec set DEV rx SCI sa { 0..3 } [ OPTS ]
+       ip macsec del DEV rx SCI sa { 0..3 }
+       ip macsec show
+       ip macsec show DEV
+where  OPTS := [ pn <u32> ] [ on | off ]
+       ID   := 128-bit hex string
+       KEY  := 128-bit or 256-bit hex string
+       SCI  := { sci <u64> | port { 1..2^16-1 } address <lladdr> }";
+
+pub(crate) fn usage() -> CliError {
+    CliError::from(MACSEC_USAGE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MacsecModifyCmd {
+    Add,
+    Set,
+    Del,
+}
+
+impl MacsecModifyCmd {
+    // Equal to iproute2 `macsec_commands[][][]`
+    fn nl_cmd(self, sa_set: bool, tx: bool) -> MacsecCmd {
+        match (self, sa_set, tx) {
+            (Self::Add, _, true) => MacsecCmd::AddTxsa,
+            (Self::Set, _, true) => MacsecCmd::UpdTxsa,
+            (Self::Del, _, true) => MacsecCmd::DelTxsa,
+            (Self::Add, true, false) => MacsecCmd::AddRxsa,
+            (Self::Set, true, false) => MacsecCmd::UpdRxsa,
+            (Self::Del, true, false) => MacsecCmd::DelRxsa,
+            (Self::Add, false, false) => MacsecCmd::AddRxsc,
+            (Self::Set, false, false) => MacsecCmd::UpdRxsc,
+            (Self::Del, false, false) => MacsecCmd::DelRxsc,
+        }
+    }
+}
+
+fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+fn duparg(key: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("either \"{key}\" is duplicate, or \"{value}\" is a garbage.")
+            .as_str(),
+    )
+}
+
+/// Equal to iproute2 `hexstring_a2n()`, the string should hold the even
+/// number of hex digits without any separator.
+fn parse_hexstring(value: &str, max_len: usize) -> Option<Vec<u8>> {
+    if value.is_empty()
+        || !value.len().is_multiple_of(2)
+        || value.len() / 2 > max_len
+        || !value.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Equal to iproute2 `struct sa_desc`
+#[derive(Debug, Default)]
+struct MacsecSaDesc {
+    an: u8,
+    pn: u32,
+    key_id: [u8; MACSEC_KEYID_LEN],
+    key: Vec<u8>,
+    active: Option<bool>,
+}
+
+impl MacsecSaDesc {
+    /// Equal to iproute2 `parse_sa_args()`
+    fn parse_opts(&mut self, opts: &[&str]) -> Result<(), CliError> {
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "pn" => {
+                    if self.pn != 0 {
+                        return Err(duparg("pn", "pn"));
+                    }
+                    let value = next_opt(&mut opts)?;
+                    self.pn = parse_num(value, "expected pn")?;
+                    if self.pn == 0 {
+                        return Err(invarg(value, "expected pn != 0"));
+                    }
+                }
+                "key" => {
+                    let value = next_opt(&mut opts)?;
+                    let key_id = parse_hexstring(value, MACSEC_KEYID_LEN)
+                        .ok_or_else(|| invarg(value, "expected key id"))?;
+                    self.key_id = [0; MACSEC_KEYID_LEN];
+                    self.key_id[..key_id.len()].copy_from_slice(&key_id);
+                    let value = next_opt(&mut opts)?;
+                    self.key = parse_hexstring(value, MACSEC_MAX_KEY_LEN)
+                        .ok_or_else(|| invarg(value, "expected key"))?;
+                }
+                "on" | "off" => {
+                    if self.active.is_some() {
+                        return Err(duparg("on/off", opt));
+                    }
+                    self.active = Some(*opt == "on");
+                }
+                _ => {
+                    return Err(CliError::from(
+                        format!("macsec: unknown command \"{opt}\"?").as_str(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Equal to iproute2 `check_sa_args()`
+    fn check(&self, cmd: MacsecModifyCmd) -> Result<(), CliError> {
+        match cmd {
+            MacsecModifyCmd::Add => {
+                if self.key.is_empty() {
+                    return Err(CliError::from("cannot create SA without key"));
+                }
+                if self.pn == 0 {
+                    return Err(CliError::from(
+                        "must specify a packet number != 0",
+                    ));
+                }
+            }
+            MacsecModifyCmd::Set => {
+                if !self.key.is_empty() {
+                    return Err(CliError::from("cannot change key on SA"));
+                }
+            }
+            MacsecModifyCmd::Del => (),
+        }
+        Ok(())
+    }
+
+    fn gen_attrs(&self, cmd: MacsecModifyCmd) -> Vec<MacsecSaAttr> {
+        let mut attrs = vec![MacsecSaAttr::An(self.an)];
+        if cmd != MacsecModifyCmd::Del {
+            if self.pn != 0 {
+                attrs.push(MacsecSaAttr::Pn(self.pn));
+            }
+            if !self.key.is_empty() {
+                attrs.push(MacsecSaAttr::KeyId(self.key_id));
+                attrs.push(MacsecSaAttr::Key(self.key.clone()));
+            }
+            if let Some(active) = self.active {
+                attrs.push(MacsecSaAttr::Active(active));
+            }
+        }
+        attrs
+    }
+}
+
+/// Equal to iproute2 `get_sa()`, return the remaining options after
+/// `sa { 0..3 }` or `None` if `sa` is not the next option.
+fn parse_an<'a, 'b>(
+    opts: &'a [&'b str],
+) -> Result<Option<(u8, &'a [&'b str])>, CliError> {
+    match opts {
+        ["sa", value, rest @ ..] => match parse_num::<u8>(value, "") {
+            Ok(an) if an <= 3 => Ok(Some((an, rest))),
+            _ => Err(invarg(value, "expected an { 0..3 }")),
+        },
+        ["sa"] => Err(CliError::from(
+            "Command line is not complete. Try option \"help\"",
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Equal to iproute2 `get_sci_portaddr()`, the SCI is either specified
+/// directly or generated from the MAC address and port. Return the SCI in
+/// host byte order and the remaining options.
+fn parse_sci<'a, 'b>(
+    opts: &'a [&'b str],
+) -> Result<(u64, &'a [&'b str]), CliError> {
+    let mut sci: Option<u64> = None;
+    let mut port: Option<u16> = None;
+    let mut address: Option<Vec<u8>> = None;
+    let mut consumed = 0;
+
+    let mut iter = opts.iter();
+    while let Some(opt) = iter.next() {
+        match *opt {
+            "sci" => {
+                let value = next_opt(&mut iter)?;
+                sci = Some(
+                    u64::from_str_radix(
+                        value.strip_prefix("0x").unwrap_or(value),
+                        16,
+                    )
+                    .map_err(|_| invarg(value, "expected sci"))?,
+                );
+            }
+            "port" => {
+                let value = next_opt(&mut iter)?;
+                let num: u16 = parse_num(value, "expected port")?;
+                if num == 0 {
+                    return Err(invarg(value, "expected port != 0"));
+                }
+                port = Some(num);
+            }
+            "address" => {
+                let value = next_opt(&mut iter)?;
+                address = Some(
+                    parse_lladdr(value)
+                        .ok()
+                        .filter(|v| v.len() == 6)
+                        .ok_or_else(|| invarg(value, "expected lladdr"))?,
+                );
+            }
+            _ => break,
+        }
+        consumed = opts.len() - iter.len();
+    }
+
+    let sci = match (sci, port, address) {
+        (Some(sci), _, _) => sci,
+        // Like kernel `make_sci()`, the SCI is MAC address followed by port
+        (None, Some(port), Some(address)) => {
+            let mut bytes = [0u8; 8];
+            bytes[..6].copy_from_slice(&address);
+            bytes[6..].copy_from_slice(&port.to_be_bytes());
+            u64::from_be_bytes(bytes)
+        }
+        _ => {
+            return Err(CliError::from("expected sci, port, or address"));
+        }
+    };
+    Ok((sci, &opts[consumed..]))
+}
+
+async fn macsec_modify(
+    ctx: &NetlinkContext,
+    cmd: MacsecCmd,
+    attributes: Vec<MacsecAttr>,
+) -> Result<(), CliError> {
+    let msg = MacsecMessage::new(cmd, attributes);
+    genl_request(ctx, msg, NLM_F_REQUEST | NLM_F_ACK).await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `do_modify_txsa()`
+async fn modify_txsa(
+    ctx: &NetlinkContext,
+    cmd: MacsecModifyCmd,
+    opts: &[&str],
+    ifindex: u32,
+) -> Result<(), CliError> {
+    let Some((an, opts)) = parse_an(opts)? else {
+        return Err(usage());
+    };
+    let mut txsa = MacsecSaDesc {
+        an,
+        ..Default::default()
+    };
+    if cmd != MacsecModifyCmd::Del {
+        txsa.parse_opts(opts)?;
+        txsa.check(cmd)?;
+    }
+    macsec_modify(
+        ctx,
+        cmd.nl_cmd(true, true),
+        vec![
+            MacsecAttr::Ifindex(ifindex),
+            MacsecAttr::SaConfig(txsa.gen_attrs(cmd)),
+        ],
+    )
+    .await
+}
+
+/// Equal to iproute2 `do_modify_rxsci()`
+async fn modify_rxsci(
+    ctx: &NetlinkContext,
+    cmd: MacsecModifyCmd,
+    opts: &[&str],
+    ifindex: u32,
+) -> Result<(), CliError> {
+    let (sci, opts) = parse_sci(opts)?;
+    let mut rxsc_attrs = vec![MacsecRxscAttr::Sci(sci)];
+    let mut attrs = vec![MacsecAttr::Ifindex(ifindex)];
+
+    let sa = parse_an(opts)?;
+    let sa_set = sa.is_some();
+    if let Some((an, opts)) = sa {
+        let mut rxsa = MacsecSaDesc {
+            an,
+            ..Default::default()
+        };
+        if cmd != MacsecModifyCmd::Del {
+            rxsa.parse_opts(opts)?;
+            rxsa.check(cmd)?;
+        }
+        attrs.push(MacsecAttr::RxscConfig(rxsc_attrs));
+        attrs.push(MacsecAttr::SaConfig(rxsa.gen_attrs(cmd)));
+    } else {
+        if cmd != MacsecModifyCmd::Del {
+            // Equal to iproute2 `parse_rxsc_args()`
+            let mut active: Option<bool> = None;
+            for opt in opts {
+                match *opt {
+                    "on" | "off" => {
+                        if active.is_some() {
+                            return Err(duparg("on/off", opt));
+                        }
+                        active = Some(*opt == "on");
+                    }
+                    _ => {
+                        return Err(CliError::from(
+                            format!("macsec: unknown command \"{opt}\"?")
+                                .as_str(),
+                        ));
+                    }
+                }
+            }
+            if let Some(active) = active {
+                rxsc_attrs.push(MacsecRxscAttr::Active(active));
+            }
+        }
+        attrs.push(MacsecAttr::RxscConfig(rxsc_attrs));
+    }
+
+    macsec_modify(ctx, cmd.nl_cmd(sa_set, false), attrs).await
+}
+
+/// Equal to iproute2 `do_modify()` of `ip macsec`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    cmd: MacsecModifyCmd,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let Some((dev, opts)) = opts.split_first() else {
+        return Err(usage());
+    };
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let ifindex = ifname_to_index(&ifnames, dev).map_err(|_| {
+        CliError::from(format!("Device \"{dev}\" does not exist.").as_str())
+    })?;
+
+    match opts.split_first() {
+        Some((&"tx", opts)) => modify_txsa(ctx, cmd, opts, ifindex).await,
+        Some((&"rx", opts)) => modify_rxsci(ctx, cmd, opts, ifindex).await,
+        _ => Err(usage()),
+    }
+}
@@ -0,0 +1,504 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::{Serialize, ser::SerializeMap};
+
+use super::{
+    message::{
+        MacsecAttr, MacsecCmd, MacsecMessage, MacsecRxscAttr, MacsecSaAttr,
+        MacsecSecyAttr, MacsecStat,
+    },
+    modify::usage,
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    route::{get_ifnames, ifname_to_index},
+};
+
+// Equal to `MACSEC_*_CIPHER_ID` of `linux/if_macsec.h`
+const MACSEC_DEFAULT_CIPHER_ID: u64 = 0x0080020001000001;
+const MACSEC_CIPHER_ID_GCM_AES_128: u64 = 0x0080C20001000001;
+const MACSEC_CIPHER_ID_GCM_AES_256: u64 = 0x0080C20001000002;
+const MACSEC_CIPHER_ID_GCM_AES_XPN_128: u64 = 0x0080C20001000003;
+const MACSEC_CIPHER_ID_GCM_AES_XPN_256: u64 = 0x0080C20001000004;
+
+// Equal to iproute2 `txsc_stats_names[]`
+const TXSC_STATS_NAMES: &[(u16, &str)] = &[
+    (1, "OutPktsProtected"),
+    (2, "OutPktsEncrypted"),
+    (3, "OutOctetsProtected"),
+    (4, "OutOctetsEncrypted"),
+];
+
+// Equal to iproute2 `secy_stats_names[]`
+const SECY_STATS_NAMES: &[(u16, &str)] = &[
+    (1, "OutPktsUntagged"),
+    (2, "InPktsUntagged"),
+    (3, "OutPktsTooLong"),
+    (4, "InPktsNoTag"),
+    (5, "InPktsBadTag"),
+    (6, "InPktsUnknownSCI"),
+    (7, "InPktsNoSCI"),
+    (8, "InPktsOverrun"),
+];
+
+// Equal to iproute2 `rxsc_stats_names[]`
+const RXSC_STATS_NAMES: &[(u16, &str)] = &[
+    (1, "InOctetsValidated"),
+    (2, "InOctetsDecrypted"),
+    (3, "InPktsUnchecked"),
+    (4, "InPktsDelayed"),
+    (5, "InPktsOK"),
+    (6, "InPktsInvalid"),
+    (7, "InPktsLate"),
+    (8, "InPktsNotValid"),
+    (9, "InPktsNotUsingSA"),
+    (10, "InPktsUnusedSA"),
+];
+
+// Equal to iproute2 `txsa_stats_names[]`
+const TXSA_STATS_NAMES: &[(u16, &str)] =
+    &[(6, "OutPktsProtected"), (7, "OutPktsEncrypted")];
+
+// Equal to iproute2 `rxsa_stats_names[]`
+const RXSA_STATS_NAMES: &[(u16, &str)] = &[
+    (1, "InPktsOK"),
+    (2, "InPktsInvalid"),
+    (3, "InPktsNotValid"),
+    (4, "InPktsNotUsingSA"),
+    (5, "InPktsUnusedSA"),
+];
+
+const TX_PREFIX: &str = "    ";
+const SA_PREFIX: &str = "        ";
+
+// Equal to iproute2 `cs_id_to_name()`
+fn cipher_suite_name(cid: u64) -> &'static str {
+    match cid {
+        MACSEC_DEFAULT_CIPHER_ID | MACSEC_CIPHER_ID_GCM_AES_128 => {
+            "GCM-AES-128"
+        }
+        MACSEC_CIPHER_ID_GCM_AES_256 => "GCM-AES-256",
+        MACSEC_CIPHER_ID_GCM_AES_XPN_128 => "GCM-AES-XPN-128",
+        MACSEC_CIPHER_ID_GCM_AES_XPN_256 => "GCM-AES-XPN-256",
+        _ => "(unknown)",
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+// Like iproute2 `print_0xhex()`, the SCI is a hex string in JSON
+fn serialize_sci<S>(sci: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&format!("{sci:#x}"))
+}
+
+/// Equal to iproute2 `print_stats()` of `ip macsec`, the counters not
+/// reported by kernel are shown as `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliMacsecStats {
+    counters: Vec<(&'static str, Option<u64>)>,
+}
+
+impl CliMacsecStats {
+    fn new(names: &[(u16, &'static str)], stats: &[MacsecStat]) -> Self {
+        Self {
+            counters: names
+                .iter()
+                .map(|(kind, name)| {
+                    (
+                        *name,
+                        stats
+                            .iter()
+                            .find(|stat| stat.kind == *kind)
+                            .map(|stat| stat.value),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn write(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        prefix: &str,
+    ) -> std::fmt::Result {
+        write!(f, "{prefix}stats:")?;
+        for (name, _) in self.counters.iter() {
+            write!(f, " {name}")?;
+        }
+        write!(f, "\n{prefix}      ")?;
+        for (name, value) in self.counters.iter() {
+            let pad = name.len() + 1;
+            match value {
+                Some(value) => write!(f, "{value:>pad$}")?,
+                None => write!(f, "{:>pad$}", "-")?,
+            }
+        }
+        writeln!(f)
+    }
+}
+
+impl Serialize for CliMacsecStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for (name, value) in self.counters.iter() {
+            if let Some(value) = value {
+                map.serialize_entry(name, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMacsecSaInfo {
+    an: u8,
+    pn: u32,
+    active: bool,
+    key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<CliMacsecStats>,
+}
+
+impl CliMacsecSaInfo {
+    fn from_attrs(
+        attrs: &[MacsecSaAttr],
+        stats_names: &[(u16, &'static str)],
+        show_stats: bool,
+    ) -> Self {
+        let mut ret = Self::default();
+        for attr in attrs {
+            match attr {
+                MacsecSaAttr::An(v) => ret.an = *v,
+                MacsecSaAttr::Pn(v) => ret.pn = *v,
+                MacsecSaAttr::Active(v) => ret.active = *v,
+                MacsecSaAttr::KeyId(v) => {
+                    ret.key = v.iter().map(|b| format!("{b:02x}")).collect()
+                }
+                MacsecSaAttr::Stats(v) if show_stats => {
+                    ret.stats = Some(CliMacsecStats::new(stats_names, v))
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    fn write(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{SA_PREFIX}{}: PN {}, state {}, key {}",
+            self.an,
+            self.pn,
+            on_off(self.active),
+            self.key
+        )?;
+        if let Some(stats) = self.stats.as_ref() {
+            stats.write(f, SA_PREFIX)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMacsecRxscInfo {
+    #[serde(serialize_with = "serialize_sci")]
+    sci: u64,
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<CliMacsecStats>,
+    sa_list: Vec<CliMacsecSaInfo>,
+}
+
+impl CliMacsecRxscInfo {
+    fn from_attrs(attrs: &[MacsecRxscAttr], show_stats: bool) -> Self {
+        let mut ret = Self::default();
+        for attr in attrs {
+            match attr {
+                MacsecRxscAttr::Sci(v) => ret.sci = *v,
+                MacsecRxscAttr::Active(v) => ret.active = *v,
+                MacsecRxscAttr::Stats(v) if show_stats => {
+                    ret.stats = Some(CliMacsecStats::new(RXSC_STATS_NAMES, v))
+                }
+                MacsecRxscAttr::SaList(v) => {
+                    ret.sa_list = v
+                        .iter()
+                        .map(|attrs| {
+                            CliMacsecSaInfo::from_attrs(
+                                attrs,
+                                RXSA_STATS_NAMES,
+                                show_stats,
+                            )
+                        })
+                        .collect()
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `process()` of `ip macsec show`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMacsecInfo {
+    ifindex: u32,
+    ifname: String,
+    protect: bool,
+    validate: String,
+    // Like iproute2, the `sc` and `sa` flags are reported from the
+    // encoding SA of SecY
+    sc: bool,
+    sa: bool,
+    encrypt: bool,
+    send_sci: bool,
+    end_station: bool,
+    scb: bool,
+    replay: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    window: Option<u32>,
+    cipher_suite: String,
+    icv_length: u8,
+    #[serde(serialize_with = "serialize_sci")]
+    sci: u64,
+    encoding_sa: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<CliMacsecStats>,
+    #[serde(skip)]
+    secy_stats: Option<CliMacsecStats>,
+    #[serde(skip)]
+    txsc_stats: Option<CliMacsecStats>,
+    sa_list: Vec<CliMacsecSaInfo>,
+    rx_sc: Vec<CliMacsecRxscInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offload: Option<String>,
+}
+
+impl std::fmt::Display for CliMacsecInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.ifindex)?;
+        write_with_color!(f, CliColor::IfaceName, "{}", self.ifname)?;
+        write!(f, ": ")?;
+        write!(f, "protect {} ", on_off(self.protect))?;
+        write!(f, "validate {} ", self.validate)?;
+        for (name, value) in [
+            ("sc", self.sc),
+            ("sa", self.sa),
+            ("encrypt", self.encrypt),
+            ("send_sci", self.send_sci),
+            ("end_station", self.end_station),
+            ("scb", self.scb),
+            ("replay", self.replay),
+        ] {
+            write!(f, "{name} {} ", on_off(value))?;
+        }
+        if let Some(window) = self.window {
+            write!(f, "window {window} ")?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "    cipher suite: {}, using ICV length {}",
+            self.cipher_suite, self.icv_length
+        )?;
+
+        writeln!(
+            f,
+            "{TX_PREFIX}TXSC: {:016x} on SA {}",
+            self.sci, self.encoding_sa
+        )?;
+        if let Some(stats) = self.secy_stats.as_ref() {
+            stats.write(f, TX_PREFIX)?;
+        }
+        if let Some(stats) = self.txsc_stats.as_ref() {
+            stats.write(f, TX_PREFIX)?;
+        }
+        for sa in self.sa_list.iter() {
+            sa.write(f)?;
+        }
+
+        for rxsc in self.rx_sc.iter() {
+            writeln!(
+                f,
+                "{TX_PREFIX}RXSC: {:016x}, state {}",
+                rxsc.sci,
+                on_off(rxsc.active)
+            )?;
+            if let Some(stats) = rxsc.stats.as_ref() {
+                stats.write(f, TX_PREFIX)?;
+            }
+            for sa in rxsc.sa_list.iter() {
+                sa.write(f)?;
+            }
+        }
+        // The trailing new line is appended when joining entries
+        if let Some(offload) = self.offload.as_ref() {
+            write!(f, "    offload: {offload} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMacsecInfo {
+    fn gen_string(&self) -> String {
+        self.to_string().trim_end_matches('\n').to_string()
+    }
+}
+
+impl CanOutput for CliMacsecInfo {}
+
+impl CliMacsecInfo {
+    fn from_msg(
+        msg: &MacsecMessage,
+        ifnames: &HashMap<u32, String>,
+        show_stats: bool,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut has_secy = false;
+        for attr in msg.attributes.iter() {
+            match attr {
+                MacsecAttr::Ifindex(v) => ret.ifindex = *v,
+                MacsecAttr::Secy(attrs) => {
+                    has_secy = true;
+                    ret.apply_secy_attrs(attrs);
+                }
+                MacsecAttr::TxscStats(v) if show_stats => {
+                    ret.txsc_stats =
+                        Some(CliMacsecStats::new(TXSC_STATS_NAMES, v))
+                }
+                MacsecAttr::SecyStats(v) if show_stats => {
+                    ret.secy_stats =
+                        Some(CliMacsecStats::new(SECY_STATS_NAMES, v))
+                }
+                MacsecAttr::TxsaList(v) => {
+                    ret.sa_list = v
+                        .iter()
+                        .map(|attrs| {
+                            CliMacsecSaInfo::from_attrs(
+                                attrs,
+                                TXSA_STATS_NAMES,
+                                show_stats,
+                            )
+                        })
+                        .collect()
+                }
+                MacsecAttr::RxscList(v) => {
+                    ret.rx_sc = v
+                        .iter()
+                        .map(|attrs| {
+                            CliMacsecRxscInfo::from_attrs(attrs, show_stats)
+                        })
+                        .collect()
+                }
+                MacsecAttr::Offload(v) => {
+                    ret.offload = Some(
+                        match v {
+                            0 => "off",
+                            1 => "phy",
+                            2 => "mac",
+                            _ => "(unknown)",
+                        }
+                        .to_string(),
+                    )
+                }
+                _ => (),
+            }
+        }
+        if !has_secy || ret.ifindex == 0 {
+            return Err(CliError::from("incomplete dump message"));
+        }
+        ret.ifname = ifnames
+            .get(&ret.ifindex)
+            .cloned()
+            .unwrap_or_else(|| format!("if{}", ret.ifindex));
+        // Like iproute2, the JSON holds both SecY and TX SC counters in the
+        // same `stats` object
+        let counters: Vec<(&'static str, Option<u64>)> = ret
+            .secy_stats
+            .iter()
+            .chain(ret.txsc_stats.iter())
+            .flat_map(|stats| stats.counters.iter().copied())
+            .collect();
+        if !counters.is_empty() {
+            ret.stats = Some(CliMacsecStats { counters });
+        }
+        Ok(ret)
+    }
+
+    fn apply_secy_attrs(&mut self, attrs: &[MacsecSecyAttr]) {
+        for attr in attrs {
+            match attr {
+                MacsecSecyAttr::Sci(v) => self.sci = *v,
+                MacsecSecyAttr::EncodingSa(v) => {
+                    self.encoding_sa = *v;
+                    self.sc = *v != 0;
+                    self.sa = *v != 0;
+                }
+                MacsecSecyAttr::Window(v) => self.window = Some(*v),
+                MacsecSecyAttr::CipherSuite(v) => {
+                    self.cipher_suite = cipher_suite_name(*v).to_string()
+                }
+                MacsecSecyAttr::IcvLen(v) => self.icv_length = *v,
+                MacsecSecyAttr::Protect(v) => self.protect = *v,
+                MacsecSecyAttr::Replay(v) => self.replay = *v,
+                MacsecSecyAttr::Validate(v) => {
+                    self.validate = match v {
+                        0 => "disabled",
+                        1 => "check",
+                        2 => "strict",
+                        _ => "(unknown)",
+                    }
+                    .to_string()
+                }
+                MacsecSecyAttr::Encrypt(v) => self.encrypt = *v,
+                MacsecSecyAttr::IncSci(v) => self.send_sci = *v,
+                MacsecSecyAttr::Es(v) => self.end_station = *v,
+                MacsecSecyAttr::Scb(v) => self.scb = *v,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Equal to iproute2 `do_show()` of `ip macsec`
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    show_stats: bool,
+) -> Result<Vec<CliMacsecInfo>, CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let filter_ifindex = match opts {
+        [] => None,
+        [dev] => Some(ifname_to_index(&ifnames, dev).map_err(|_| {
+            CliError::from(format!("Device \"{dev}\" does not exist.").as_str())
+        })?),
+        _ => return Err(usage()),
+    };
+
+    let mut ret = Vec::new();
+    for msg in genl_request(
+        ctx,
+        MacsecMessage::new(MacsecCmd::GetTxsc, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    {
+        let info = CliMacsecInfo::from_msg(&msg, &ifnames, show_stats)?;
+        if filter_ifindex.is_none_or(|ifindex| ifindex == info.ifindex) {
+            ret.push(info);
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_macsec, setup_macsec};
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+const KEY_ID: &str = "01";
+const KEY: &str = "81818181818181818181818181818181";
+
+#[test]
+fn test_macsec_add_set_del() {
+    let macsec_name = "test-msec0";
+    let dummy_name = "test-msec-dum0";
+    setup_macsec(macsec_name, dummy_name);
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "add",
+            macsec_name,
+            "tx",
+            "sa",
+            "0",
+            "pn",
+            "1",
+            "on",
+            "key",
+            KEY_ID,
+            KEY,
+        ]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "add",
+            macsec_name,
+            "rx",
+            "port",
+            "1234",
+            "address",
+            "c6:19:52:8f:e6:a0",
+        ]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "add",
+            macsec_name,
+            "rx",
+            "port",
+            "1234",
+            "address",
+            "c6:19:52:8f:e6:a0",
+            "sa",
+            "1",
+            "pn",
+            "100",
+            "on",
+            "key",
+            "02",
+            KEY,
+        ]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "add",
+            macsec_name,
+            "rx",
+            "sci",
+            "1122334455667788",
+            "off",
+        ]);
+
+        for args in [&[][..], &["-s"][..], &["-j"][..], &["-s", "-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["macsec", "show", macsec_name]].concat(),
+            );
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["macsec", "show", macsec_name]].concat(),
+            );
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "set",
+            macsec_name,
+            "tx",
+            "sa",
+            "0",
+            "pn",
+            "5",
+            "off",
+        ]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "set",
+            macsec_name,
+            "rx",
+            "sci",
+            "1122334455667788",
+            "on",
+        ]);
+        let output = ip_rs_exec_cmd(&["macsec", "show", macsec_name]);
+        assert!(output.contains(&format!(
+            "        0: PN 5, state off, key {KEY_ID}{}\n",
+            "0".repeat(30)
+        )));
+        assert!(output.contains("    RXSC: 1122334455667788, state on\n"));
+        assert!(output.contains("    RXSC: c619528fe6a004d2, state on\n"));
+
+        ip_rs_exec_cmd(&["macsec", "del", macsec_name, "tx", "sa", "0"]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "del",
+            macsec_name,
+            "rx",
+            "port",
+            "1234",
+            "address",
+            "c6:19:52:8f:e6:a0",
+            "sa",
+            "1",
+        ]);
+        ip_rs_exec_cmd(&[
+            "macsec",
+            "del",
+            macsec_name,
+            "rx",
+            "sci",
+            "1122334455667788",
+        ]);
+        let expected_output = exec_cmd(&["ip", "macsec", "show", macsec_name]);
+        let our_output = ip_rs_exec_cmd(&["macsec", "show", macsec_name]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+        assert!(!our_output.contains("PN"));
+        assert!(!our_output.contains("1122334455667788"));
+    });
+
+    cleanup_macsec(dummy_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_macsec_invalid_args() {
+    for (args, error) in [
+        (&["macsec", "add"][..], "Usage: ip macsec add DEV tx sa"),
+        (
+            &["macsec", "add", "lo"][..],
+            "Usage: ip macsec add DEV tx sa",
+        ),
+        (
+            &["macsec", "add", "not-exist0", "tx", "sa", "0"][..],
+            "Device \"not-exist0\" does not exist.",
+        ),
+        (
+            &["macsec", "add", "lo", "tx", "sa", "4"][..],
+            "argument \"4\" is wrong: expected an { 0..3 }",
+        ),
+        (
+            &["macsec", "add", "lo", "tx", "sa", "0", "pn", "1"][..],
+            "cannot create SA without key",
+        ),
+        (
+            &["macsec", "add", "lo", "tx", "sa", "0", "key", "01", KEY][..],
+            "must specify a packet number != 0",
+        ),
+        (
+            &["macsec", "set", "lo", "tx", "sa", "0", "key", "01", KEY][..],
+            "cannot change key on SA",
+        ),
+        (
+            &["macsec", "set", "lo", "tx", "sa", "0", "pn", "0"][..],
+            "argument \"0\" is wrong: expected pn != 0",
+        ),
+        (
+            &["macsec", "set", "lo", "tx", "sa", "0", "key", "0", KEY][..],
+            "argument \"0\" is wrong: expected key id",
+        ),
+        (
+            &["macsec", "set", "lo", "tx", "sa", "0", "on", "off"][..],
+            "either \"on/off\" is duplicate, or \"off\" is a garbage.",
+        ),
+        (
+            &["macsec", "set", "lo", "tx", "sa", "0", "foo"][..],
+            "macsec: unknown command \"foo\"?",
+        ),
+        (
+            &["macsec", "add", "lo", "rx", "port", "1"][..],
+            "expected sci, port, or address",
+        ),
+        (
+            &["macsec", "add", "lo", "rx", "port", "0"][..],
+            "argument \"0\" is wrong: expected port != 0",
+        ),
+        (
+            &["macsec", "add", "lo", "rx", "sci", "xyz"][..],
+            "argument \"xyz\" is wrong: expected sci",
+        ),
+        (
+            &["macsec", "add", "lo", "rx", "address", "foo", "port", "1"][..],
+            "argument \"foo\" is wrong: expected lladdr",
+        ),
+        (
+            &["macsec", "show", "lo", "foo"][..],
+            "Usage: ip macsec add DEV tx sa",
+        ),
+        (
+            &["macsec", "show", "not-exist0"][..],
+            "Device \"not-exist0\" does not exist.",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod macsec;
+
+use crate::tests::exec_cmd;
+
+fn setup_macsec(macsec_name: &str, dummy_name: &str) {
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&[
+        "ip",
+        "link",
+        "add",
+        "link",
+        dummy_name,
+        macsec_name,
+        "type",
+        "macsec",
+        "port",
+        "11",
+        "encrypt",
+        "on",
+    ]);
+}
+
+// The macsec is removed along with its lower interface
+fn cleanup_macsec(dummy_name: &str) {
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+}
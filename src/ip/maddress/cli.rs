@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::{MaddrAction, handle_modify},
+    show::{CliMaddrInfo, handle_show},
+};
+use crate::{CliError, family::get_family};
+
+pub(crate) struct MaddressCommand;
+
+impl MaddressCommand {
+    pub(crate) const CMD: &'static str = "maddress";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("multicast addresses")
+            .alias("maddres")
+            .alias("maddre")
+            .alias("maddr")
+            .alias("madd")
+            .alias("mad")
+            .alias("ma")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show multicast addresses")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add link layer multicast address")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete link layer multicast address")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) fn handle(
+        matches: &clap::ArgMatches,
+    ) -> Result<Option<Vec<CliMaddrInfo>>, CliError> {
+        for (name, action) in
+            [("add", MaddrAction::Add), ("delete", MaddrAction::Delete)]
+        {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(&opts, get_family(matches), action)?;
+                return Ok(None);
+            }
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(&opts, get_family(matches)).map(Into::into)
+    }
+}
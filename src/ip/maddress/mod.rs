@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod modify;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::MaddressCommand;
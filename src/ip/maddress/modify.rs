@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+
+use std::os::fd::AsRawFd;
+
+use iproute_rs::{CliError, next_opt};
+use nix::{
+    errno::Errno,
+    libc,
+    sys::socket::{AddressFamily as SocketFamily, SockFlag, SockType, socket},
+};
+use rtnetlink::packet_route::AddressFamily;
+
+use crate::{address::duplicate_arg, link::check_ifname, neigh::parse_lladdr};
+
+// The size of `sa_data` in `struct sockaddr` holding the link layer address
+const SA_DATA_LEN: usize = 14;
+
+nix::ioctl_write_ptr_bad!(siocaddmulti, libc::SIOCADDMULTI, libc::ifreq);
+nix::ioctl_write_ptr_bad!(siocdelmulti, libc::SIOCDELMULTI, libc::ifreq);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MaddrAction {
+    Add,
+    Delete,
+}
+
+// Equal to the argument parsing of iproute2 `multiaddr_modify()`
+fn parse_modify_opts<'a>(
+    opts: &[&'a str],
+) -> Result<(&'a str, Vec<u8>), CliError> {
+    let mut dev = None;
+    let mut lladdr = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if *opt == "dev" {
+            let value = next_opt(&mut opts)?;
+            if dev.is_some() {
+                return Err(duplicate_arg("dev", value));
+            }
+            check_ifname(value, "\"dev\"")?;
+            dev = Some(value);
+        } else {
+            let value = if *opt == "address" {
+                next_opt(&mut opts)?
+            } else {
+                opt
+            };
+            if lladdr.is_some() {
+                return Err(duplicate_arg("address", value));
+            }
+            let mut addr = parse_lladdr(value)?;
+            addr.truncate(SA_DATA_LEN);
+            lladdr = Some(addr);
+        }
+    }
+    let Some(dev) = dev else {
+        return Err(CliError::from(
+            "Not enough information: \"dev\" is required.",
+        ));
+    };
+    Ok((dev, lladdr.unwrap_or_default()))
+}
+
+fn gen_ifreq(dev: &str, lladdr: &[u8]) -> libc::ifreq {
+    // SAFETY: `ifreq` is plain old data which is valid when zeroed
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(dev.as_bytes()) {
+        *dst = *src as libc::c_char;
+    }
+    // The `sa_family` should be `AF_UNSPEC` for `SIOCADDMULTI`
+    let mut hwaddr: libc::sockaddr = unsafe { std::mem::zeroed() };
+    for (dst, src) in hwaddr.sa_data.iter_mut().zip(lladdr) {
+        *dst = *src as libc::c_char;
+    }
+    ifr.ifr_ifru.ifru_hwaddr = hwaddr;
+    ifr
+}
+
+/// Add or remove the link layer multicast address through ioctl like
+/// iproute2 `multiaddr_modify()`, the kernel has no netlink interface for
+/// this.
+pub(crate) fn handle_modify(
+    opts: &[&str],
+    family: AddressFamily,
+    action: MaddrAction,
+) -> Result<(), CliError> {
+    let (dev, lladdr) = parse_modify_opts(opts)?;
+    let ifr = gen_ifreq(dev, &lladdr);
+
+    let family = match family {
+        AddressFamily::Inet6 => SocketFamily::Inet6,
+        AddressFamily::Packet => SocketFamily::Packet,
+        _ => SocketFamily::Inet,
+    };
+    let fd = socket(family, SockType::Datagram, SockFlag::SOCK_CLOEXEC, None)
+        .map_err(|e| {
+        CliError::from(format!("Cannot create socket: {}", e.desc()).as_str())
+    })?;
+
+    // SAFETY: the `ifreq` outlives the ioctl which only reads it
+    let result = unsafe {
+        match action {
+            MaddrAction::Add => siocaddmulti(fd.as_raw_fd(), &ifr),
+            MaddrAction::Delete => siocdelmulti(fd.as_raw_fd(), &ifr),
+        }
+    };
+    result.map(|_| ()).map_err(|e: Errno| {
+        CliError::from(format!("ioctl: {}", e.desc()).as_str())
+    })
+}
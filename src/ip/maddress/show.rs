@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    fmt::Write,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, mac_to_string, next_opt,
+    write_with_color,
+};
+use rtnetlink::packet_route::AddressFamily;
+use serde::Serialize;
+
+const PROC_DEV_MCAST: &str = "/proc/net/dev_mcast";
+const PROC_IGMP: &str = "/proc/net/igmp";
+const PROC_IGMP6: &str = "/proc/net/igmp6";
+
+/// The multicast addresses of an interface, equal to iproute2
+/// `print_mlist()`.
+#[derive(Serialize)]
+pub(crate) struct CliMaddrInfo {
+    ifindex: u32,
+    ifname: String,
+    maddr: Vec<CliMaddr>,
+}
+
+impl CliMaddrInfo {
+    fn header(&self) -> String {
+        let mut ret = format!("{}:\t", self.ifindex);
+        write_with_color!(ret, CliColor::IfaceName, "{}", self.ifname).ok();
+        ret
+    }
+}
+
+impl std::fmt::Display for CliMaddrInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.header())?;
+        for maddr in &self.maddr {
+            write!(f, "\n\t{maddr}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMaddrInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, the interface is repeated for each address
+    fn gen_oneline_string(&self) -> String {
+        let header = self.header();
+        self.maddr
+            .iter()
+            .map(|maddr| format!("{header}\\\t{maddr}"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl CanOutput for CliMaddrInfo {}
+
+/// Equal to iproute2 `print_maddr()`
+#[derive(Serialize)]
+struct CliMaddr {
+    #[serde(flatten)]
+    address: CliMaddrAddress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    users: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features: Option<&'static str>,
+}
+
+impl std::fmt::Display for CliMaddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.address)?;
+        if let Some(users) = self.users {
+            write!(f, " users {users}")?;
+        }
+        if let Some(features) = self.features {
+            write!(f, " {features}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CliMaddrAddress {
+    Link {
+        link: String,
+    },
+    Ip {
+        family: &'static str,
+        address: IpAddr,
+    },
+}
+
+impl std::fmt::Display for CliMaddrAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Link { link } => {
+                write!(f, "link  ")?;
+                write_with_color!(f, CliColor::Mac, "{link}")
+            }
+            Self::Ip { family, address } => {
+                write!(f, "{family:<5} ")?;
+                let color = if address.is_ipv4() {
+                    CliColor::Ipv4Addr
+                } else {
+                    CliColor::Ipv6Addr
+                };
+                write_with_color!(f, color, "{address}")
+            }
+        }
+    }
+}
+
+/// The multicast address read from procfs before grouped by interface,
+/// equal to iproute2 `struct ma_info`.
+struct MaddrEntry {
+    index: u32,
+    name: String,
+    maddr: CliMaddr,
+}
+
+impl MaddrEntry {
+    fn new(
+        index: u32,
+        name: &str,
+        address: CliMaddrAddress,
+        users: u32,
+    ) -> Self {
+        Self {
+            index,
+            name: name.to_string(),
+            maddr: CliMaddr {
+                address,
+                users: (users != 1).then_some(users),
+                features: None,
+            },
+        }
+    }
+}
+
+// Equal to iproute2 `parse_hex()`, the hex string without separator
+fn parse_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn read_proc_file(path: &str) -> Option<String> {
+    std::fs::read_to_string(path)
+        .inspect_err(|e| log::debug!("Failed to read {path}: {e}"))
+        .ok()
+}
+
+// Equal to iproute2 `read_dev_mcast()`
+fn read_dev_mcast(entries: &mut Vec<MaddrEntry>) {
+    let Some(content) = read_proc_file(PROC_DEV_MCAST) else {
+        return;
+    };
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [index, name, users, st, hexa, ..] = fields.as_slice() else {
+            continue;
+        };
+        let (Ok(index), Ok(users), Some(lladdr)) =
+            (index.parse(), users.parse(), parse_hex(hexa))
+        else {
+            continue;
+        };
+        let mut entry = MaddrEntry::new(
+            index,
+            name,
+            CliMaddrAddress::Link {
+                link: mac_to_string(&lladdr),
+            },
+            users,
+        );
+        if *st != "0" {
+            entry.maddr.features = Some("static");
+        }
+        entries.push(entry);
+    }
+}
+
+/// Equal to iproute2 `read_igmp()`. The groups are listed under the line
+/// of their interface, the address is printed by kernel as `__be32` in
+/// host byte order.
+fn read_igmp(entries: &mut Vec<MaddrEntry>) {
+    let Some(content) = read_proc_file(PROC_IGMP) else {
+        return;
+    };
+    let mut iface: Option<(u32, String)> = None;
+    // The first line is the header
+    for line in content.lines().skip(1) {
+        let mut fields = line.split_whitespace();
+        if !line.starts_with('\t') {
+            iface = fields.next().and_then(|index| index.parse().ok()).zip(
+                fields.next().map(|name| {
+                    name.strip_suffix(':').unwrap_or(name).to_string()
+                }),
+            );
+            continue;
+        }
+        let Some((index, name)) = iface.as_ref() else {
+            continue;
+        };
+        let (Some(Ok(addr)), Some(Ok(users))) = (
+            fields.next().map(|v| u32::from_str_radix(v, 16)),
+            fields.next().map(str::parse),
+        ) else {
+            continue;
+        };
+        entries.push(MaddrEntry::new(
+            *index,
+            name,
+            CliMaddrAddress::Ip {
+                family: "inet",
+                address: Ipv4Addr::from(addr.to_ne_bytes()).into(),
+            },
+            users,
+        ));
+    }
+}
+
+// Equal to iproute2 `read_igmp6()`
+fn read_igmp6(entries: &mut Vec<MaddrEntry>) {
+    let Some(content) = read_proc_file(PROC_IGMP6) else {
+        return;
+    };
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [index, name, hexa, users, ..] = fields.as_slice() else {
+            continue;
+        };
+        let (Ok(index), Ok(users), Some(Ok(addr))) = (
+            index.parse(),
+            users.parse(),
+            parse_hex(hexa).map(<[u8; 16]>::try_from),
+        ) else {
+            continue;
+        };
+        entries.push(MaddrEntry::new(
+            index,
+            name,
+            CliMaddrAddress::Ip {
+                family: "inet6",
+                address: Ipv6Addr::from(addr).into(),
+            },
+            users,
+        ));
+    }
+}
+
+// Equal to the argument parsing of iproute2 `multiaddr_list()`
+fn parse_show_opts<'a>(opts: &[&'a str]) -> Result<Option<&'a str>, CliError> {
+    let mut dev = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        let value = if *opt == "dev" {
+            next_opt(&mut opts)?
+        } else {
+            opt
+        };
+        if dev.is_some() {
+            return Err(CliError::from(
+                format!(
+                    "either \"dev\" is duplicate, or \"{value}\" is a garbage."
+                )
+                .as_str(),
+            ));
+        }
+        dev = Some(value);
+    }
+    Ok(dev)
+}
+
+pub(crate) fn handle_show(
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<Vec<CliMaddrInfo>, CliError> {
+    let dev = parse_show_opts(opts)?;
+
+    let mut entries = Vec::new();
+    if matches!(family, AddressFamily::Unspec | AddressFamily::Packet) {
+        read_dev_mcast(&mut entries);
+    }
+    if matches!(family, AddressFamily::Unspec | AddressFamily::Inet) {
+        read_igmp(&mut entries);
+    }
+    if matches!(family, AddressFamily::Unspec | AddressFamily::Inet6) {
+        read_igmp6(&mut entries);
+    }
+    if let Some(dev) = dev {
+        entries.retain(|entry| entry.name == dev);
+    }
+    // Like iproute2 `maddr_ins()`, the addresses are grouped by interface
+    // index while keeping the order they were read
+    entries.sort_by_key(|entry| entry.index);
+
+    let mut ret: Vec<CliMaddrInfo> = Vec::new();
+    for entry in entries {
+        match ret.last_mut() {
+            Some(info) if info.ifindex == entry.index => {
+                info.maddr.push(entry.maddr);
+            }
+            _ => ret.push(CliMaddrInfo {
+                ifindex: entry.index,
+                ifname: entry.name,
+                maddr: vec![entry.maddr],
+            }),
+        }
+    }
+    Ok(ret)
+}
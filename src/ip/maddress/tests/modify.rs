@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_maddr_add_del() {
+    let veth_name = "matest-veth1";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "maddr",
+            "add",
+            "01:00:5e:01:02:04",
+            "dev",
+            veth_name,
+        ]);
+        ip_rs_exec_cmd(&[
+            "maddr",
+            "add",
+            "address",
+            "33:33:00:01:02:04",
+            "dev",
+            veth_name,
+        ]);
+        let output = exec_cmd(&["ip", "maddr", "show", "dev", veth_name]);
+        assert!(output.contains("\tlink  01:00:5e:01:02:04 static\n"));
+        assert!(output.contains("\tlink  33:33:00:01:02:04 static\n"));
+
+        ip_rs_exec_cmd(&[
+            "maddr",
+            "del",
+            "01:00:5e:01:02:04",
+            "dev",
+            veth_name,
+        ]);
+        let output = exec_cmd(&["ip", "maddr", "show", "dev", veth_name]);
+        assert!(!output.contains("01:00:5e:01:02:04"));
+        assert!(output.contains("\tlink  33:33:00:01:02:04 static\n"));
+
+        // The address is already removed
+        let output = ip_rs_exec_cmd_output(&[
+            "maddr",
+            "delete",
+            "01:00:5e:01:02:04",
+            "dev",
+            veth_name,
+        ]);
+        assert!(!output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stderr)
+                .contains("ioctl: No such file or directory")
+        );
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_maddr_modify_invalid_args() {
+    for (args, error_msg) in [
+        (&["zz", "dev", "lo"][..], "\"zz\" is invalid lladdr."),
+        (
+            &["01:00:5e:01:02:03"][..],
+            "Not enough information: \"dev\" is required.",
+        ),
+        (
+            &["01:00:5e:01:02:03", "dev", "lo", "dev", "lo"][..],
+            "duplicate \"dev\": \"lo\" is the second value.",
+        ),
+        (
+            &["01:00:5e:01:02:03", "33:33:00:00:00:01", "dev", "lo"][..],
+            "duplicate \"address\": \"33:33:00:00:00:01\" is the second value.",
+        ),
+        (
+            &["01:00:5e:01:02:03", "dev", "a/b"][..],
+            "argument \"a/b\" is wrong: \"dev\" not a valid ifname",
+        ),
+        (&["address"][..], "Command line is not complete"),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["maddr", "add"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
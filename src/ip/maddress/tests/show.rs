@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_maddr_show() {
+    let veth_name = "matest-veth0";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+    exec_cmd(&["ip", "maddr", "add", "01:00:5e:01:02:03", "dev", veth_name]);
+
+    let result = std::panic::catch_unwind(|| {
+        // Other interfaces might join or leave groups at any time, hence
+        // only the test interface is compared.
+        for args in [
+            &["maddr", "show", "dev", veth_name][..],
+            &["maddress", "list", veth_name][..],
+            &["-4", "maddr", "show", "dev", veth_name][..],
+            &["-6", "maddr", "show", "dev", veth_name][..],
+            &["-0", "maddr", "show", "dev", veth_name][..],
+            &["-o", "maddr", "show", "dev", veth_name][..],
+            &["-j", "maddr", "show", "dev", veth_name][..],
+            &["maddr", "show", "dev", "matest-absent0"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        let our_output = ip_rs_exec_cmd(&["maddr"]);
+        assert!(our_output.contains(&format!(":\t{veth_name}\n")));
+        assert!(our_output.contains("\tlink  01:00:5e:01:02:03 static"));
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_maddr_show_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["lo", "eth0"][..],
+            "either \"dev\" is duplicate, or \"eth0\" is a garbage.",
+        ),
+        (&["dev"][..], "Command line is not complete"),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["maddr", "show"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -1,23 +1,73 @@
 // SPDX-License-Identifier: MIT
 
 mod address;
+mod args;
+mod batch;
+mod context;
+mod family;
+mod fou;
+mod genl;
+mod ila;
+mod ioam;
+mod l2tp;
 mod link;
+mod macsec;
+mod maddress;
+mod monitor;
+mod mptcp;
+mod mroute;
+mod neigh;
+mod netns;
+mod nexthop;
+mod ntable;
+mod prefix;
+mod route;
+mod rule;
+mod sr;
+mod stats;
+mod tcp_metrics;
+mod token;
+mod tunnel;
+mod tuntap;
+mod vrf;
+mod xfrm;
 
 #[cfg(test)]
 mod tests;
 
 use std::io::IsTerminal;
 
-use iproute_rs::{CliColor, CliError, OutputFormat, print_result_and_exit};
+use iproute_rs::{
+    CliColor, CliError, CliTimestamp, OutputFormat, gen_output_string,
+    print_output, print_result_and_exit,
+};
 
-use self::{address::AddressCommand, link::LinkCommand};
+use self::{
+    address::AddressCommand, args::normalize_args, batch::handle_batch,
+    context::NetlinkContext, family::FAMILY_NAMES, fou::FouCommand,
+    ila::IlaCommand, ioam::IoamCommand, l2tp::L2tpCommand, link::LinkCommand,
+    macsec::MacsecCommand, maddress::MaddressCommand, monitor::MonitorCommand,
+    mptcp::MptcpCommand, mroute::MrouteCommand, neigh::NeighCommand,
+    netns::NetnsCommand, nexthop::NexthopCommand, ntable::NtableCommand,
+    route::RouteCommand, rule::RuleCommand, sr::SrCommand, stats::StatsCommand,
+    tcp_metrics::TcpMetricsCommand, token::TokenCommand, tunnel::TunnelCommand,
+    tuntap::TuntapCommand, vrf::VrfCommand, xfrm::XfrmCommand,
+};
 
-#[tokio::main(flavor = "current_thread")]
-async fn main() -> Result<(), CliError> {
-    let mut app = clap::Command::new("iproute-rs")
+fn gen_command() -> clap::Command {
+    clap::Command::new("iproute-rs")
         .version(clap::crate_version!())
         .author(clap::crate_authors!())
         .about("Command line of rust-netlink")
+        // The `-h` is reserved for `-human` like iproute2
+        .disable_help_flag(true)
+        .arg(
+            clap::Arg::new("HELP")
+                .long("help")
+                .help("Print help")
+                .action(clap::ArgAction::Help)
+                .global(true),
+        )
         .arg(
             clap::Arg::new("VERSION")
                 .long("Version")
@@ -28,6 +78,7 @@ async fn main() -> Result<(), CliError> {
         .arg(
             clap::Arg::new("JSON")
                 .short('j')
+                .long("json")
                 .help("JSON output")
                 .action(clap::ArgAction::SetTrue)
                 .global(true),
@@ -35,12 +86,24 @@ async fn main() -> Result<(), CliError> {
         .arg(
             clap::Arg::new("COLOR")
                 .short('c')
-                .help("Colorful output")
+                .long("color")
+                .help("Colorful output, `-color` alone means always")
                 .action(clap::ArgAction::Set)
                 .value_parser(["always", "auto", "never"])
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("always")
                 .default_value("auto")
                 .global(true),
         )
+        .arg(
+            clap::Arg::new("PRETTY")
+                .short('p')
+                .long("pretty")
+                .help("Pretty JSON output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
         .arg(
             clap::Arg::new("YAML")
                 .short('y')
@@ -51,23 +114,320 @@ async fn main() -> Result<(), CliError> {
         .arg(
             clap::Arg::new("DETAILS")
                 .short('d')
+                .long("details")
                 .help("Interface details")
                 .action(clap::ArgAction::SetTrue)
                 .global(true),
         )
-        .subcommand_required(true)
+        .arg(
+            clap::Arg::new("FAMILY")
+                .short('f')
+                .long("family")
+                .value_name("FAMILY")
+                .help(
+                    "Protocol family, -4, -6, -B, -M and -0 are shortcuts of \
+                     inet, inet6, bridge, mpls and link",
+                )
+                .value_parser(FAMILY_NAMES)
+                .action(clap::ArgAction::Append)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("ONELINE")
+                .short('o')
+                .long("oneline")
+                .help("Output each record on a single line")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("TIMESTAMP")
+                .short('t')
+                .long("timestamp")
+                .help("Print timestamp before output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("TSHORT")
+                .long("tshort")
+                .help("Print timestamp before output in short format")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("FORCE")
+                .long("force")
+                .help("Do not stop batch mode or flush on errors")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("BATCH")
+                .short('b')
+                .long("batch")
+                .value_name("FILE")
+                .help("Read commands from file, use '-' for stdin"),
+        )
+        .arg(
+            clap::Arg::new("BRIEF")
+                .long("brief")
+                .help("Brief output")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("HUMAN")
+                .short('h')
+                .long("human")
+                .visible_alias("human-readable")
+                .help("Human readable statistics counters")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("IEC")
+                .long("iec")
+                .help("Use IEC units (1024 based) for human readable numbers")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("NUMERIC")
+                .short('N')
+                .long("numeric")
+                .help(
+                    "Print numbers instead of names for protocols, scopes, etc",
+                )
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("RESOLVE")
+                .short('r')
+                .long("resolve")
+                .help("Resolve IP addresses to hostnames via DNS")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("STATS")
+                .short('s')
+                .long("stats")
+                .visible_alias("statistics")
+                .help("Statistics, specify twice for error details")
+                .action(clap::ArgAction::Count)
+                .global(true),
+        )
+        .arg(
+            clap::Arg::new("LOOPS")
+                .short('l')
+                .long("loops")
+                .value_name("COUNT")
+                .help("Maximum rounds of address flush, 0 for unlimited")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("10")
+                .global(true),
+        )
         .subcommand(LinkCommand::gen_command())
-        .subcommand(AddressCommand::gen_command());
-
-    let matches = app.get_matches_mut();
+        .subcommand(AddressCommand::gen_command())
+        .subcommand(RouteCommand::gen_command())
+        .subcommand(RuleCommand::gen_command())
+        .subcommand(NeighCommand::gen_command())
+        .subcommand(NexthopCommand::gen_command())
+        .subcommand(NtableCommand::gen_command())
+        .subcommand(MaddressCommand::gen_command())
+        .subcommand(MrouteCommand::gen_command())
+        .subcommand(TunnelCommand::gen_command())
+        .subcommand(TuntapCommand::gen_command())
+        .subcommand(TokenCommand::gen_command())
+        .subcommand(TcpMetricsCommand::gen_command())
+        .subcommand(FouCommand::gen_command())
+        .subcommand(IlaCommand::gen_command())
+        .subcommand(L2tpCommand::gen_command())
+        .subcommand(MacsecCommand::gen_command())
+        .subcommand(MptcpCommand::gen_command())
+        .subcommand(SrCommand::gen_command())
+        .subcommand(IoamCommand::gen_command())
+        .subcommand(StatsCommand::gen_command())
+        .subcommand(VrfCommand::gen_command())
+        .subcommand(XfrmCommand::gen_command())
+        .subcommand(NetnsCommand::gen_command())
+        .subcommand(MonitorCommand::gen_command())
+}
 
-    let fmt = if matches.get_flag("JSON") {
+fn get_output_format(matches: &clap::ArgMatches) -> OutputFormat {
+    if matches.get_flag("JSON") && matches.get_flag("PRETTY") {
+        OutputFormat::PrettyJson
+    } else if matches.get_flag("JSON") {
         OutputFormat::Json
     } else if matches.get_flag("YAML") {
         OutputFormat::Yaml
+    } else if matches.get_flag("ONELINE") {
+        OutputFormat::Oneline
     } else {
         OutputFormat::default()
-    };
+    }
+}
+
+/// Execute the object command, e.g. `link show`, and render its output.
+/// Shared by command line and batch mode.
+async fn dispatch(
+    matches: &clap::ArgMatches,
+    ctx: &NetlinkContext,
+) -> Result<String, CliError> {
+    let fmt = get_output_format(matches);
+    if let Some(matches) = matches.subcommand_matches(LinkCommand::CMD) {
+        Ok(gen_output_string(
+            &LinkCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(AddressCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &AddressCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(RouteCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &RouteCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(RuleCommand::CMD) {
+        Ok(gen_output_string(
+            &RuleCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(NeighCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &NeighCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(NexthopCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &NexthopCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(NtableCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &NtableCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(MaddressCommand::CMD)
+    {
+        Ok(gen_output_string(&MaddressCommand::handle(matches)?, fmt))
+    } else if let Some(matches) = matches.subcommand_matches(MrouteCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &MrouteCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(TunnelCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &TunnelCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(TuntapCommand::CMD)
+    {
+        Ok(gen_output_string(&TuntapCommand::handle(matches)?, fmt))
+    } else if let Some(matches) = matches.subcommand_matches(TokenCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &TokenCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(TcpMetricsCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &TcpMetricsCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(FouCommand::CMD) {
+        Ok(gen_output_string(
+            &FouCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(IlaCommand::CMD) {
+        Ok(gen_output_string(
+            &IlaCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(L2tpCommand::CMD) {
+        Ok(gen_output_string(
+            &L2tpCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(MacsecCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &MacsecCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(MptcpCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &MptcpCommand::handle(matches, ctx, fmt).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(SrCommand::CMD) {
+        Ok(gen_output_string(
+            &SrCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(IoamCommand::CMD) {
+        Ok(gen_output_string(
+            &IoamCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(StatsCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &StatsCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(VrfCommand::CMD) {
+        Ok(gen_output_string(
+            &VrfCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(XfrmCommand::CMD) {
+        Ok(gen_output_string(
+            &XfrmCommand::handle(matches, ctx, fmt).await?,
+            fmt,
+        ))
+    } else if let Some(matches) = matches.subcommand_matches(NetnsCommand::CMD)
+    {
+        Ok(gen_output_string(
+            &NetnsCommand::handle(matches, ctx).await?,
+            fmt,
+        ))
+    } else if let Some(matches) =
+        matches.subcommand_matches(MonitorCommand::CMD)
+    {
+        MonitorCommand::handle(matches, ctx, fmt)
+            .await
+            .map(|()| String::new())
+    } else {
+        Err(CliError::from("Object is not specified. Try \"ip help\""))
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), CliError> {
+    let mut app = gen_command();
+    let args = normalize_args(std::env::args_os());
+    let matches = app
+        .try_get_matches_from_mut(&args)
+        .unwrap_or_else(|e| e.exit());
 
     if let Some(color_str) = matches.get_one::<String>("COLOR")
         && (color_str == "always"
@@ -76,14 +436,41 @@ async fn main() -> Result<(), CliError> {
         CliColor::enable();
     }
 
+    if matches.get_flag("TSHORT") {
+        CliTimestamp::enable(true);
+    } else if matches.get_flag("TIMESTAMP") {
+        CliTimestamp::enable(false);
+    }
+
     if matches.get_flag("VERSION") {
-        print_result_and_exit(Ok(app.render_version().to_string()), fmt);
-    } else if let Some(matches) = matches.subcommand_matches(LinkCommand::CMD) {
-        print_result_and_exit(LinkCommand::handle(matches).await, fmt);
-    } else if let Some(matches) =
-        matches.subcommand_matches(AddressCommand::CMD)
-    {
-        print_result_and_exit(AddressCommand::handle(matches).await, fmt);
+        print_result_and_exit(
+            Ok(app.render_version().to_string()),
+            get_output_format(&matches),
+        );
+    } else if let Some(file) = matches.get_one::<String>("BATCH") {
+        let ctx = NetlinkContext::new()?;
+
+        let result = handle_batch(
+            &mut app,
+            &args,
+            file,
+            matches.get_flag("FORCE"),
+            &ctx,
+        )
+        .await
+        .map(|()| String::new());
+        print_output(&result);
+        if let Err(e) = result {
+            std::process::exit(e.code);
+        }
+    } else if matches.subcommand().is_some() {
+        let ctx = NetlinkContext::new()?;
+
+        let result = dispatch(&matches, &ctx).await;
+        print_output(&result);
+        if let Err(e) = result {
+            std::process::exit(e.code);
+        }
     } else {
         app.print_help()?;
         println!();
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, CliNumberFormat, OutputFormat};
+
+use super::{event::MonitorDisplayOptions, listen::handle_monitor};
+use crate::context::NetlinkContext;
+
+pub(crate) struct MonitorCommand;
+
+impl MonitorCommand {
+    pub(crate) const CMD: &'static str = "monitor";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("watch for netlink messages")
+            .alias("monito")
+            .alias("monit")
+            .alias("moni")
+            .alias("mon")
+            .alias("mo")
+            .arg(
+                clap::Arg::new("options")
+                    .action(clap::ArgAction::Append)
+                    .trailing_var_arg(true),
+            )
+    }
+
+    /// The monitor prints events by itself as they arrive, hence nothing is
+    /// returned.
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+        fmt: OutputFormat,
+    ) -> Result<(), CliError> {
+        let opts: Vec<&str> = matches
+            .get_many::<String>("options")
+            .unwrap_or_default()
+            .map(String::as_str)
+            .collect();
+        handle_monitor(
+            ctx,
+            &opts,
+            fmt,
+            MonitorDisplayOptions {
+                include_details: matches.get_flag("DETAILS"),
+                stats_level: matches.get_count("STATS"),
+                number_format: CliNumberFormat::new(
+                    matches.get_flag("HUMAN"),
+                    matches.get_flag("IEC"),
+                ),
+                numeric: matches.get_flag("NUMERIC"),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, CliNumberFormat,
+    link::{CliLinkInfo, parse_nl_msg_to_iface},
+    write_with_color,
+};
+use rtnetlink::packet_route::{
+    RouteNetlinkMessage,
+    address::AddressMessage,
+    link::{LinkAttribute, LinkMessage},
+    nsid::{NsidAttribute, NsidMessage},
+    route::{RouteFlags, RouteMessage},
+};
+use serde::Serialize;
+
+use crate::{
+    address::{CliAddressInfo, parse_nl_msg_to_address},
+    neigh::{CliNeighInfo, NeighShowFilter, parse_nl_msg_to_neigh},
+    route::{CliRouteInfo, RouteShowFilter, parse_nl_msg_to_route},
+    rule::{CliRuleInfo, parse_nl_msg_to_rule},
+};
+
+const RTNL_FAMILY_IPMR: u8 = 128;
+const RTNL_FAMILY_IP6MR: u8 = 129;
+
+/// The address event, equal to iproute2 `print_addrinfo()` with `oneline`
+/// filter which prefixes the interface.
+#[derive(Serialize)]
+pub(crate) struct CliMonitorAddress {
+    index: u32,
+    dev: String,
+    #[serde(flatten)]
+    info: CliAddressInfo,
+}
+
+impl std::fmt::Display for CliMonitorAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.index)?;
+        write_with_color!(f, CliColor::IfaceName, "{}", self.dev)?;
+        write!(f, "    {}", self.info)
+    }
+}
+
+/// Equal to iproute2 `print_nsid()`
+#[derive(Serialize)]
+pub(crate) struct CliMonitorNsid {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsid: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl std::fmt::Display for CliMonitorNsid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.nsid {
+            Some(nsid) => write!(f, "nsid {nsid} ")?,
+            None => write!(f, "nsid unassigned ")?,
+        }
+        if let Some(name) = &self.name {
+            write!(f, "(iproute2 netns name: {name})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliMonitorObject {
+    Link(Box<CliLinkInfo>),
+    Address(CliMonitorAddress),
+    Route(Box<CliRouteInfo>),
+    Neigh(CliNeighInfo),
+    Rule(CliRuleInfo),
+    Nsid(CliMonitorNsid),
+}
+
+impl std::fmt::Display for CliMonitorObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Link(v) => write!(f, "{v}"),
+            Self::Address(v) => write!(f, "{v}"),
+            Self::Route(v) => write!(f, "{v}"),
+            Self::Neigh(v) => write!(f, "{v}"),
+            Self::Rule(v) => write!(f, "{v}"),
+            Self::Nsid(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl CliMonitorObject {
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Link(v) => v.gen_oneline_string(),
+            _ => self.to_string().replace('\n', "\\"),
+        }
+    }
+
+    // Equal to the `print_headers()` labels of iproute2 `accept_msg()`
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Link(_) => "[LINK]",
+            Self::Address(_) => "[ADDR]",
+            Self::Route(_) => "[ROUTE]",
+            Self::Neigh(_) => "[NEIGH]",
+            Self::Rule(_) => "[RULE]",
+            Self::Nsid(_) => "[NSID]",
+        }
+    }
+}
+
+/// The netlink notification received by `ip monitor`
+#[derive(Serialize)]
+pub(crate) struct CliMonitorEvent {
+    /// Prefix the event with the object label like `[LINK]`
+    #[serde(skip)]
+    show_label: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deleted: bool,
+    #[serde(flatten)]
+    object: CliMonitorObject,
+}
+
+impl CliMonitorEvent {
+    fn prefix(&self) -> String {
+        format!(
+            "{}{}",
+            if self.show_label {
+                self.object.label()
+            } else {
+                ""
+            },
+            if self.deleted { "Deleted " } else { "" }
+        )
+    }
+}
+
+impl std::fmt::Display for CliMonitorEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix(), self.object)
+    }
+}
+
+impl CanDisplay for CliMonitorEvent {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        format!("{}{}", self.prefix(), self.object.gen_oneline_string())
+    }
+}
+
+impl CanOutput for CliMonitorEvent {}
+
+/// The display settings of `ip monitor` shared by all objects
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct MonitorDisplayOptions {
+    pub(crate) include_details: bool,
+    pub(crate) stats_level: u8,
+    pub(crate) number_format: CliNumberFormat,
+    pub(crate) numeric: bool,
+    /// Requested by `label` or `all`
+    pub(crate) show_label: bool,
+}
+
+/// The names remembered by `ip monitor` for resolving later events
+#[derive(Debug, Default)]
+pub(crate) struct MonitorCache {
+    pub(crate) ifnames: HashMap<u32, String>,
+    /// Like iproute2 `netns_map_init()`, the netns names indexed by nsid
+    pub(crate) netns_names: HashMap<i32, String>,
+}
+
+/// Track the interface names for resolving the interface index of later
+/// events like iproute2 `ll_remember_index()`.
+async fn parse_link_object(
+    nl_msg: LinkMessage,
+    deleted: bool,
+    cache: &mut MonitorCache,
+    opts: &MonitorDisplayOptions,
+) -> Result<CliMonitorObject, CliError> {
+    let ifindex = nl_msg.header.index;
+    if let Some(name) = nl_msg.attributes.iter().find_map(|attr| {
+        if let LinkAttribute::IfName(name) = attr {
+            Some(name.to_string())
+        } else {
+            None
+        }
+    }) {
+        cache.ifnames.insert(ifindex, name);
+    }
+
+    let mut iface = parse_nl_msg_to_iface(
+        nl_msg,
+        opts.include_details,
+        opts.stats_level,
+        opts.number_format,
+        opts.numeric,
+    )
+    .await?;
+    iface.resolve_iface_names(&cache.ifnames);
+    if deleted {
+        cache.ifnames.remove(&ifindex);
+    }
+    Ok(CliMonitorObject::Link(Box::new(iface)))
+}
+
+fn parse_address_object(
+    nl_msg: AddressMessage,
+    cache: &MonitorCache,
+    opts: &MonitorDisplayOptions,
+) -> Result<CliMonitorObject, CliError> {
+    let index = nl_msg.header.index;
+    // Like iproute2 `ll_index_to_name()`
+    let dev = cache
+        .ifnames
+        .get(&index)
+        .cloned()
+        .unwrap_or_else(|| format!("if{index}"));
+    Ok(CliMonitorObject::Address(CliMonitorAddress {
+        index,
+        dev,
+        info: parse_nl_msg_to_address(nl_msg, opts.numeric)?,
+    }))
+}
+
+/// Like iproute2 `accept_msg()`, the cloned routes are ignored and the
+/// multicast routes are not shown as route.
+fn parse_route_object(
+    nl_msg: RouteMessage,
+    cache: &MonitorCache,
+    opts: &MonitorDisplayOptions,
+) -> Option<CliMonitorObject> {
+    let family = u8::from(nl_msg.header.address_family);
+    if nl_msg.header.flags.contains(RouteFlags::Cloned)
+        || family == RTNL_FAMILY_IPMR
+        || family == RTNL_FAMILY_IP6MR
+    {
+        return None;
+    }
+    Some(CliMonitorObject::Route(Box::new(parse_nl_msg_to_route(
+        nl_msg,
+        &RouteShowFilter::new_unfiltered(),
+        &cache.ifnames,
+        opts.include_details,
+        opts.numeric,
+        opts.stats_level > 0,
+    ))))
+}
+
+/// The netns name is forgotten once its nsid is deleted like iproute2
+/// `netns_map_del()`.
+fn parse_nsid_object(
+    nl_msg: NsidMessage,
+    deleted: bool,
+    cache: &mut MonitorCache,
+) -> CliMonitorObject {
+    let nsid = nl_msg.attributes.iter().find_map(|attr| {
+        if let NsidAttribute::Id(id) = attr {
+            Some(*id)
+        } else {
+            None
+        }
+    });
+    let name = nsid.and_then(|nsid| {
+        if deleted {
+            cache.netns_names.remove(&nsid)
+        } else {
+            cache.netns_names.get(&nsid).cloned()
+        }
+    });
+    CliMonitorObject::Nsid(CliMonitorNsid {
+        nsid: nsid.filter(|nsid| *nsid >= 0),
+        name,
+    })
+}
+
+/// Convert the netlink notification to event, `None` for message not
+/// interested.
+pub(crate) async fn parse_nl_msg_to_event(
+    nl_msg: RouteNetlinkMessage,
+    cache: &mut MonitorCache,
+    opts: &MonitorDisplayOptions,
+) -> Result<Option<CliMonitorEvent>, CliError> {
+    let deleted = matches!(
+        nl_msg,
+        RouteNetlinkMessage::DelLink(_)
+            | RouteNetlinkMessage::DelAddress(_)
+            | RouteNetlinkMessage::DelRoute(_)
+            | RouteNetlinkMessage::DelNeighbour(_)
+            | RouteNetlinkMessage::DelRule(_)
+            | RouteNetlinkMessage::DelNsId(_)
+    );
+    let object = match nl_msg {
+        RouteNetlinkMessage::NewLink(m) | RouteNetlinkMessage::DelLink(m) => {
+            parse_link_object(m, deleted, cache, opts).await?
+        }
+        RouteNetlinkMessage::NewAddress(m)
+        | RouteNetlinkMessage::DelAddress(m) => {
+            parse_address_object(m, cache, opts)?
+        }
+        RouteNetlinkMessage::NewRoute(m) | RouteNetlinkMessage::DelRoute(m) => {
+            let Some(object) = parse_route_object(m, cache, opts) else {
+                return Ok(None);
+            };
+            object
+        }
+        RouteNetlinkMessage::NewNeighbour(m)
+        | RouteNetlinkMessage::DelNeighbour(m) => {
+            CliMonitorObject::Neigh(parse_nl_msg_to_neigh(
+                m,
+                &NeighShowFilter::new_unfiltered(),
+                &cache.ifnames,
+                opts.numeric,
+                opts.stats_level > 0,
+            ))
+        }
+        RouteNetlinkMessage::NewRule(m) | RouteNetlinkMessage::DelRule(m) => {
+            CliMonitorObject::Rule(parse_nl_msg_to_rule(
+                m,
+                opts.include_details,
+                opts.numeric,
+            ))
+        }
+        RouteNetlinkMessage::NewNsId(m) | RouteNetlinkMessage::DelNsId(m) => {
+            parse_nsid_object(m, deleted, cache)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(CliMonitorEvent {
+        show_label: opts.show_label,
+        deleted,
+        object,
+    }))
+}
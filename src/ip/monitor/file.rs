@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::Write;
+
+use iproute_rs::CliError;
+use nix::errno::Errno;
+use rtnetlink::{
+    packet_core::{NetlinkHeader, NetlinkMessage, NetlinkPayload},
+    packet_route::RouteNetlinkMessage,
+};
+
+use crate::route::{emit_nl_msg, split_nl_msgs};
+
+// Equal to iproute2 `NLMSG_TSTAMP` used by rtmon to record the receive time
+const NLMSG_TSTAMP: u16 = 15;
+const NLMSG_HDRLEN: usize = 16;
+
+/// The receive time of following events recorded in rtmon file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RtmonTimestamp {
+    secs: u32,
+    usecs: u32,
+}
+
+// Equal to iproute2 `print_nlmsg_timestamp()`
+impl std::fmt::Display for RtmonTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Timestamp: ")?;
+        if let Some(time) =
+            chrono::DateTime::from_timestamp(self.secs.into(), 0)
+        {
+            write!(
+                f,
+                "{}",
+                time.with_timezone(&chrono::Local)
+                    .format("%a %b %e %H:%M:%S %Y")
+            )?;
+        }
+        write!(f, " {} us", self.usecs)
+    }
+}
+
+impl RtmonTimestamp {
+    fn now() -> Self {
+        let now = chrono::Local::now();
+        Self {
+            secs: now.timestamp() as u32,
+            usecs: now.timestamp_subsec_micros(),
+        }
+    }
+}
+
+pub(crate) enum RtmonRecord {
+    Timestamp(RtmonTimestamp),
+    Message(RouteNetlinkMessage),
+}
+
+fn open_error(e: std::io::Error) -> CliError {
+    CliError::from(
+        format!(
+            "Cannot fopen: {}",
+            Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+        )
+        .as_str(),
+    )
+}
+
+/// Read the netlink messages saved by rtmon like iproute2
+/// `rtnl_from_file()`. The messages not supported are skipped.
+pub(crate) fn read_rtmon_file(
+    path: &str,
+) -> Result<Vec<RtmonRecord>, CliError> {
+    let data = std::fs::read(path).map_err(open_error)?;
+
+    let mut ret = Vec::new();
+    for buf in split_nl_msgs(&data)? {
+        let msg_type = u16::from_ne_bytes([buf[4], buf[5]]);
+        if msg_type == NLMSG_TSTAMP {
+            if let Some((secs, usecs)) =
+                buf[NLMSG_HDRLEN..].split_first_chunk::<4>()
+                && let Some(usecs) = usecs.first_chunk::<4>()
+            {
+                ret.push(RtmonRecord::Timestamp(RtmonTimestamp {
+                    secs: u32::from_ne_bytes(*secs),
+                    usecs: u32::from_ne_bytes(*usecs),
+                }));
+            }
+            continue;
+        }
+        match NetlinkMessage::<RouteNetlinkMessage>::deserialize(buf) {
+            Ok(NetlinkMessage {
+                payload: NetlinkPayload::InnerMessage(nl_msg),
+                ..
+            }) => ret.push(RtmonRecord::Message(nl_msg)),
+            Ok(_) => (),
+            Err(e) => {
+                log::debug!("Skipping netlink message type {msg_type}: {e}");
+            }
+        }
+    }
+    Ok(ret)
+}
+
+/// Save the netlink messages in the file format of iproute2 rtmon which
+/// could be replayed by `ip monitor file`.
+pub(crate) struct RtmonWriter {
+    file: std::fs::File,
+}
+
+impl RtmonWriter {
+    pub(crate) fn create(path: &str) -> Result<Self, CliError> {
+        Ok(Self {
+            file: std::fs::File::create(path).map_err(open_error)?,
+        })
+    }
+
+    // Equal to iproute2 `write_stamp()` of rtmon
+    pub(crate) fn write_stamp(&mut self) -> Result<(), CliError> {
+        let stamp = RtmonTimestamp::now();
+        let mut buf = Vec::with_capacity(NLMSG_HDRLEN + 8);
+        buf.extend_from_slice(&((NLMSG_HDRLEN + 8) as u32).to_ne_bytes());
+        buf.extend_from_slice(&NLMSG_TSTAMP.to_ne_bytes());
+        // The flags, sequence number and port id are all zero
+        buf.resize(NLMSG_HDRLEN, 0);
+        buf.extend_from_slice(&stamp.secs.to_ne_bytes());
+        buf.extend_from_slice(&stamp.usecs.to_ne_bytes());
+        self.file.write_all(&buf)?;
+        Ok(())
+    }
+
+    pub(crate) fn write_msg(
+        &mut self,
+        nl_msg: RouteNetlinkMessage,
+    ) -> Result<(), CliError> {
+        let mut buf = Vec::new();
+        emit_nl_msg(
+            &mut buf,
+            NetlinkMessage::new(
+                NetlinkHeader::default(),
+                NetlinkPayload::InnerMessage(nl_msg),
+            ),
+        );
+        self.file.write_all(&buf)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::stream::{StreamExt, TryStreamExt};
+use iproute_rs::{
+    CliError, OutputFormat, gen_output_string, next_opt, print_output,
+};
+use rtnetlink::{
+    packet_core::NetlinkPayload, packet_route::RouteNetlinkMessage,
+};
+
+use super::{
+    event::{MonitorCache, MonitorDisplayOptions, parse_nl_msg_to_event},
+    file::{RtmonRecord, RtmonWriter, read_rtmon_file},
+};
+use crate::{
+    context::NetlinkContext, netns::get_netns_names, route::get_ifnames,
+};
+
+const RTNLGRP_LINK: u32 = 1;
+const RTNLGRP_NEIGH: u32 = 3;
+const RTNLGRP_IPV4_IFADDR: u32 = 5;
+const RTNLGRP_IPV4_ROUTE: u32 = 7;
+const RTNLGRP_IPV4_RULE: u32 = 8;
+const RTNLGRP_IPV6_IFADDR: u32 = 9;
+const RTNLGRP_IPV6_ROUTE: u32 = 11;
+const RTNLGRP_IPV6_RULE: u32 = 19;
+const RTNLGRP_NSID: u32 = 28;
+
+/// The objects to monitor following the argument grammar of iproute2
+/// `do_ipmonitor()`
+#[derive(Debug, Default)]
+struct MonitorOptions {
+    link: bool,
+    address: bool,
+    route: bool,
+    neigh: bool,
+    rule: bool,
+    nsid: bool,
+    /// Prefix the events with object label
+    label: bool,
+    /// Replay the events saved in rtmon file instead of listening
+    file: Option<String>,
+    /// Save the events to rtmon file instead of printing
+    save: Option<String>,
+}
+
+impl MonitorOptions {
+    fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut object_specified = false;
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "link" => {
+                    ret.link = true;
+                    object_specified = true;
+                }
+                "address" | "addr" => {
+                    ret.address = true;
+                    object_specified = true;
+                }
+                "route" => {
+                    ret.route = true;
+                    object_specified = true;
+                }
+                "neigh" => {
+                    ret.neigh = true;
+                    object_specified = true;
+                }
+                "rule" => {
+                    ret.rule = true;
+                    object_specified = true;
+                }
+                "nsid" => {
+                    ret.nsid = true;
+                    object_specified = true;
+                }
+                "label" | "all" => ret.label = true,
+                "file" => ret.file = Some(next_opt(&mut opts)?.to_string()),
+                "save" => ret.save = Some(next_opt(&mut opts)?.to_string()),
+                _ => {
+                    return Err(CliError::from(
+                        format!(
+                            "Argument \"{opt}\" is unknown, try \"ip monitor \
+                             help\"."
+                        )
+                        .as_str(),
+                    ));
+                }
+            }
+        }
+        if !object_specified {
+            ret = Self {
+                link: true,
+                address: true,
+                route: true,
+                neigh: true,
+                rule: true,
+                nsid: true,
+                ..ret
+            };
+        }
+        Ok(ret)
+    }
+
+    fn groups(&self) -> Vec<u32> {
+        let mut ret = Vec::new();
+        if self.link {
+            ret.push(RTNLGRP_LINK);
+        }
+        if self.address {
+            ret.extend([RTNLGRP_IPV4_IFADDR, RTNLGRP_IPV6_IFADDR]);
+        }
+        if self.route {
+            ret.extend([RTNLGRP_IPV4_ROUTE, RTNLGRP_IPV6_ROUTE]);
+        }
+        if self.neigh {
+            ret.push(RTNLGRP_NEIGH);
+        }
+        if self.rule {
+            ret.extend([RTNLGRP_IPV4_RULE, RTNLGRP_IPV6_RULE]);
+        }
+        if self.nsid {
+            ret.push(RTNLGRP_NSID);
+        }
+        ret
+    }
+}
+
+/// Print the events saved in rtmon file like iproute2 `rtnl_from_file()`,
+/// the interface names are only resolved from the link events in the file.
+async fn replay_rtmon_file(
+    path: &str,
+    fmt: OutputFormat,
+    display_opts: &MonitorDisplayOptions,
+) -> Result<(), CliError> {
+    let mut cache = MonitorCache::default();
+    for record in read_rtmon_file(path)? {
+        match record {
+            RtmonRecord::Timestamp(stamp) => {
+                // The receive time has no JSON output in iproute2
+                if matches!(fmt, OutputFormat::Cli | OutputFormat::Oneline) {
+                    print_output(&Ok(stamp.to_string()));
+                }
+            }
+            RtmonRecord::Message(nl_msg) => {
+                if let Some(event) =
+                    parse_nl_msg_to_event(nl_msg, &mut cache, display_opts)
+                        .await?
+                {
+                    print_output(&Ok(gen_output_string(&event, fmt)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Like iproute2 rtmon, the links are dumped first so that the replay could
+/// resolve the interface names.
+async fn save_links(
+    handle: &rtnetlink::Handle,
+    writer: &mut RtmonWriter,
+) -> Result<(), CliError> {
+    writer.write_stamp()?;
+    let mut links = handle.link().get().execute();
+    while let Some(link) = links.try_next().await? {
+        writer.write_msg(RouteNetlinkMessage::NewLink(link))?;
+    }
+    Ok(())
+}
+
+/// Print the netlink notifications of specified objects until interrupted
+pub(crate) async fn handle_monitor(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    fmt: OutputFormat,
+    display_opts: MonitorDisplayOptions,
+) -> Result<(), CliError> {
+    let monitor_opts = MonitorOptions::parse(opts)?;
+    let display_opts = MonitorDisplayOptions {
+        show_label: monitor_opts.label,
+        ..display_opts
+    };
+    if let Some(path) = monitor_opts.file.as_deref() {
+        return replay_rtmon_file(path, fmt, &display_opts).await;
+    }
+
+    let mut writer = monitor_opts
+        .save
+        .as_deref()
+        .map(RtmonWriter::create)
+        .transpose()?;
+
+    let handle = ctx.handle();
+    let mut messages = ctx.route().subscribe(&monitor_opts.groups())?;
+
+    if let Some(writer) = writer.as_mut() {
+        save_links(handle, writer).await?;
+    }
+
+    let mut cache = MonitorCache {
+        ifnames: get_ifnames(handle).await?,
+        netns_names: if monitor_opts.nsid {
+            get_netns_names(handle).await?
+        } else {
+            Default::default()
+        },
+    };
+
+    while let Some((nl_msg, _)) = messages.next().await {
+        let NetlinkPayload::InnerMessage(nl_msg) = nl_msg.payload else {
+            continue;
+        };
+        if let Some(writer) = writer.as_mut() {
+            writer.write_stamp()?;
+            writer.write_msg(nl_msg)?;
+            continue;
+        }
+        // Like iproute2 `netns_get_name()`, the netns might be created after
+        // the monitor started
+        if let RouteNetlinkMessage::NewNsId(_) = nl_msg {
+            cache.netns_names.extend(get_netns_names(handle).await?);
+        }
+        if let Some(event) =
+            parse_nl_msg_to_event(nl_msg, &mut cache, &display_opts).await?
+        {
+            print_output(&Ok(gen_output_string(&event, fmt)));
+        }
+    }
+    Ok(())
+}
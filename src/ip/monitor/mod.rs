@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod event;
+mod file;
+mod listen;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::MonitorCommand;
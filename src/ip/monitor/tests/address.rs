@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, filter_events, monitor_both, setup_veth};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_address() {
+    let veth_name = "mtest-veth2";
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.63.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["monitor", "address"][..],
+            &["monitor", "address", "route"][..],
+            &["monitor"][..],
+        ] {
+            let (expected_output, our_output) = monitor_both(args, || {
+                for addr in ["198.18.64.1/24", "2001:db8:64::1/64"] {
+                    exec_cmd(&[
+                        "ip", "addr", "add", addr, "dev", veth_name, "nodad",
+                    ]);
+                    exec_cmd(&["ip", "addr", "del", addr, "dev", veth_name]);
+                }
+            });
+            let expected_events = filter_events(&expected_output, veth_name);
+
+            assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+            pretty_assertions::assert_eq!(
+                expected_events,
+                filter_events(&our_output, veth_name)
+            );
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    MONITOR_WAIT, cleanup_veth, filter_events, setup_veth, stop_monitor,
+};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, ip_rs_spawn, lock_net_test,
+};
+
+fn data_path(name: &str) -> String {
+    format!(
+        "{}/src/ip/monitor/tests/data/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+fn split_timestamps(output: &str) -> (Vec<&str>, Vec<&str>) {
+    output
+        .lines()
+        .partition(|line| line.starts_with("Timestamp: "))
+}
+
+// The `events.rtmon` is captured by iproute2 rtmon in a new netns with veth
+// pair created, the `events.txt` holds the output of iproute2 `ip monitor`
+// running in parallel after the initial link dump of rtmon. No kernel is
+// involved in this test.
+#[test]
+fn test_monitor_file_replay() {
+    let path = data_path("events.rtmon");
+    let expected_events = std::fs::read_to_string(data_path("events.txt"))
+        .expect("Failed to read expected events");
+
+    let expected_output = exec_cmd(&["ip", "monitor", "file", &path]);
+    let our_output = ip_rs_exec_cmd(&["monitor", "file", &path]);
+    let (expected_timestamps, _) = split_timestamps(&expected_output);
+    let (our_timestamps, our_events) = split_timestamps(&our_output);
+
+    pretty_assertions::assert_eq!(expected_timestamps, our_timestamps);
+    pretty_assertions::assert_eq!(
+        expected_events.lines().collect::<Vec<_>>(),
+        our_events
+    );
+}
+
+#[test]
+fn test_monitor_save() {
+    let veth_name = "mtest-veth7";
+    let expected_path = std::env::temp_dir().join("mtest-iproute2.rtmon");
+    let our_path = std::env::temp_dir().join("mtest-ip-rs.rtmon");
+    let expected_path = expected_path.to_str().unwrap();
+    let our_path = our_path.to_str().unwrap();
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.76.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        let expected = std::process::Command::new("rtmon")
+            .args(["file", expected_path, "address", "route"])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn iproute2 rtmon");
+        let ours =
+            ip_rs_spawn(&["monitor", "address", "route", "save", our_path]);
+        std::thread::sleep(MONITOR_WAIT);
+
+        exec_cmd(&["ip", "addr", "add", "198.18.77.1/24", "dev", veth_name]);
+        exec_cmd(&["ip", "addr", "del", "198.18.77.1/24", "dev", veth_name]);
+
+        std::thread::sleep(MONITOR_WAIT);
+        stop_monitor(expected);
+        stop_monitor(ours);
+
+        // Both files are replayed by iproute2 while the veth still exists,
+        // hence the interface names are resolved identically.
+        let expected_output =
+            exec_cmd(&["ip", "monitor", "file", expected_path]);
+        let our_output = exec_cmd(&["ip", "monitor", "file", our_path]);
+        let expected_events = filter_events(&expected_output, veth_name);
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, veth_name)
+        );
+        assert!(split_timestamps(&our_output).0.len() > 1);
+    });
+
+    cleanup_veth(veth_name);
+    std::fs::remove_file(expected_path).ok();
+    std::fs::remove_file(our_path).ok();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_monitor_file_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["file"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["file", "/nonexistent/mtest.rtmon"][..],
+            "Cannot fopen: No such file or directory",
+        ),
+        (
+            &["save", "/nonexistent/mtest.rtmon"][..],
+            "Cannot fopen: No such file or directory",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["monitor"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, filter_events, monitor_both, setup_veth};
+use crate::tests::{exec_cmd, lock_net_test};
+
+fn change_veth(veth_name: &str) {
+    exec_cmd(&["ip", "addr", "add", "198.18.71.2/24", "dev", veth_name]);
+    exec_cmd(&["ip", "addr", "del", "198.18.71.2/24", "dev", veth_name]);
+    exec_cmd(&["ip", "link", "set", veth_name, "mtu", "1400"]);
+}
+
+// The netconf events are not supported yet
+fn filter_label_events(output: &str, keyword: &str) -> Vec<String> {
+    filter_events(output, keyword)
+        .into_iter()
+        .filter(|e| !e.contains("[NETCONF]"))
+        .collect()
+}
+
+#[test]
+fn test_monitor_label() {
+    let veth_name = "mtest-veth5";
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.71.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["monitor", "all"][..],
+            &["monitor", "label", "link", "address"][..],
+            &["-o", "monitor", "label"][..],
+        ] {
+            let (expected_output, our_output) =
+                monitor_both(args, || change_veth(veth_name));
+            let expected_events =
+                filter_label_events(&expected_output, veth_name);
+
+            assert!(expected_events.iter().any(|e| e.starts_with("[LINK]")));
+            assert!(expected_events.iter().any(|e| e.starts_with("[ADDR]")));
+            pretty_assertions::assert_eq!(
+                expected_events,
+                filter_label_events(&our_output, veth_name)
+            );
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_monitor_tshort() {
+    let veth_name = "mtest-veth6";
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.72.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["-ts", "monitor", "label", "link"], || {
+                change_veth(veth_name)
+            });
+        let strip_timestamp = |output: &str| -> Vec<String> {
+            filter_events(output, veth_name)
+                .iter()
+                .map(|e| {
+                    let (timestamp, event) =
+                        e.split_once("] ").expect("No timestamp prefix found");
+                    assert!(timestamp.starts_with('['));
+                    event.to_string()
+                })
+                .collect()
+        };
+        let expected_events = strip_timestamp(&expected_output);
+
+        assert!(!expected_events.is_empty());
+        pretty_assertions::assert_eq!(
+            expected_events,
+            strip_timestamp(&our_output)
+        );
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
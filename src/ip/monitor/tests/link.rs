@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT
+
+use super::{filter_events, monitor_both};
+use crate::tests::{exec_cmd, ip_rs_exec_cmd_output, lock_net_test};
+
+fn change_links(veth_name: &str) {
+    let peer_name = format!("{veth_name}p");
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", veth_name, "up"]);
+    exec_cmd(&["ip", "link", "set", veth_name, "mtu", "1400"]);
+    exec_cmd(&["ip", "link", "del", veth_name]);
+}
+
+#[test]
+fn test_monitor_link() {
+    let veth_name = "mtest-veth0";
+    let _lock = lock_net_test();
+
+    for args in [&["monitor", "link"][..], &["-d", "monitor", "link"][..]] {
+        let (expected_output, our_output) =
+            monitor_both(args, || change_links(veth_name));
+        let expected_events = filter_events(&expected_output, veth_name);
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, veth_name)
+        );
+    }
+}
+
+#[test]
+fn test_monitor_link_json() {
+    let veth_name = "mtest-veth1";
+    let _lock = lock_net_test();
+
+    let (_, our_output) =
+        monitor_both(&["-j", "monitor", "link"], || change_links(veth_name));
+    let events: Vec<serde_json::Value> = our_output
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("Invalid JSON line"))
+        .filter(|e: &serde_json::Value| e["ifname"] == veth_name)
+        .collect();
+
+    assert!(events.len() >= 2);
+    assert!(events[0].get("deleted").is_none());
+    assert_eq!(events.last().unwrap()["deleted"], true);
+}
+
+#[test]
+fn test_monitor_invalid_args() {
+    let output = ip_rs_exec_cmd_output(&["monitor", "foo"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Argument \"foo\" is unknown, try \"ip monitor help\".")
+    );
+}
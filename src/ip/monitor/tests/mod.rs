@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod address;
+#[cfg(test)]
+mod file;
+#[cfg(test)]
+mod label;
+#[cfg(test)]
+mod link;
+#[cfg(test)]
+mod neigh;
+#[cfg(test)]
+mod nsid;
+#[cfg(test)]
+mod route;
+#[cfg(test)]
+mod rule;
+
+use std::{io::Read, process::Child, time::Duration};
+
+use crate::tests::{exec_cmd, ip_rs_spawn};
+
+// Wait for the monitors subscribing the netlink groups
+const MONITOR_WAIT: Duration = Duration::from_millis(500);
+
+fn setup_veth(veth_name: &str, addr: &str) {
+    let peer_name = format!("{veth_name}p");
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", veth_name, "up"]);
+    exec_cmd(&["ip", "addr", "add", addr, "dev", veth_name]);
+}
+
+fn cleanup_veth(veth_name: &str) {
+    exec_cmd(&["ip", "link", "del", veth_name]);
+}
+
+/// Start both iproute2 and ip-rs monitor, then run the changes and return
+/// their outputs
+fn monitor_both(args: &[&str], changes: impl FnOnce()) -> (String, String) {
+    let expected = std::process::Command::new("ip")
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn iproute2 monitor");
+    let ours = ip_rs_spawn(args);
+    std::thread::sleep(MONITOR_WAIT);
+
+    changes();
+
+    std::thread::sleep(MONITOR_WAIT);
+    (stop_monitor(expected), stop_monitor(ours))
+}
+
+fn stop_monitor(mut child: Child) -> String {
+    child.kill().ok();
+    child.wait().ok();
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output).ok();
+    }
+    output
+}
+
+/// Only keep the events containing the keyword as other tests might change
+/// the system in parallel. The event starts with line without indent.
+fn filter_events(output: &str, keyword: &str) -> Vec<String> {
+    let mut events: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with(' ') {
+            if let Some(event) = events.last_mut() {
+                event.push('\n');
+                event.push_str(line);
+            }
+        } else {
+            events.push(line.to_string());
+        }
+    }
+    events.retain(|e| e.contains(keyword));
+    events
+}
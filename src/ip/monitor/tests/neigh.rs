@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, filter_events, monitor_both, setup_veth};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_neigh() {
+    let veth_name = "mtest-veth4";
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.68.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        let (expected_output, our_output) =
+            monitor_both(&["monitor", "neigh"], || {
+                for neigh in [
+                    &["198.18.68.5", "lladdr", "00:23:45:67:89:2a"][..],
+                    &["2001:db8:68::5", "lladdr", "00:23:45:67:89:2b"][..],
+                    &[
+                        "198.18.68.6",
+                        "lladdr",
+                        "00:23:45:67:89:2c",
+                        "nud",
+                        "noarp",
+                    ][..],
+                ] {
+                    exec_cmd(
+                        &[&["ip", "neigh", "add"], neigh, &["dev", veth_name]]
+                            .concat(),
+                    );
+                    exec_cmd(&[
+                        "ip", "neigh", "del", neigh[0], "dev", veth_name,
+                    ]);
+                }
+            });
+        let expected_events = filter_events(&expected_output, veth_name);
+
+        assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output, veth_name)
+        );
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+
+use super::{filter_events, monitor_both};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_nsid() {
+    let ns_name = "mtest-ns0";
+    let _lock = lock_net_test();
+
+    let (expected_output, our_output) =
+        monitor_both(&["monitor", "nsid"], || {
+            exec_cmd(&["ip", "netns", "add", ns_name]);
+            exec_cmd(&["ip", "netns", "set", ns_name, "47"]);
+            exec_cmd(&["ip", "netns", "del", ns_name]);
+        });
+    let expected_events = filter_events(&expected_output, ns_name);
+
+    assert_eq!(expected_events.len(), 2);
+    pretty_assertions::assert_eq!(
+        expected_events,
+        filter_events(&our_output, ns_name)
+    );
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, filter_events, monitor_both, setup_veth};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_route() {
+    let veth_name = "mtest-veth3";
+    let _lock = lock_net_test();
+    setup_veth(veth_name, "198.18.65.1/24");
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&["monitor", "route"][..], &["-d", "monitor", "route"][..]]
+        {
+            let (expected_output, our_output) = monitor_both(args, || {
+                for route in [
+                    &["198.18.66.0/24", "via", "198.18.65.2"][..],
+                    &["198.18.67.0/24", "table", "100", "metric", "10"][..],
+                    &["2001:db8:66::/64", "proto", "static"][..],
+                ] {
+                    exec_cmd(
+                        &[&["ip", "route", "add"], route, &["dev", veth_name]]
+                            .concat(),
+                    );
+                    exec_cmd(
+                        &[&["ip", "route", "del"], route, &["dev", veth_name]]
+                            .concat(),
+                    );
+                }
+            });
+            let expected_events = filter_events(&expected_output, veth_name);
+
+            assert!(expected_events.iter().any(|e| e.starts_with("Deleted ")));
+            pretty_assertions::assert_eq!(
+                expected_events,
+                filter_events(&our_output, veth_name)
+            );
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+
+use super::{filter_events, monitor_both};
+use crate::tests::{exec_cmd, lock_net_test};
+
+#[test]
+fn test_monitor_rule() {
+    let _lock = lock_net_test();
+
+    let (expected_output, our_output) =
+        monitor_both(&["monitor", "rule"], || {
+            for rule in [
+                &["-4", "rule", "add", "pref", "557", "from", "198.18.69.0/24"]
+                    [..],
+                &["-6", "rule", "add", "pref", "557", "to", "2001:db8:69::/64"]
+                    [..],
+            ] {
+                exec_cmd(&[&["ip"], rule, &["lookup", "100"]].concat());
+                exec_cmd(&["ip", rule[0], "rule", "del", "pref", "557"]);
+            }
+        });
+    let expected_events = filter_events(&expected_output, "557:");
+
+    assert_eq!(expected_events.len(), 4);
+    pretty_assertions::assert_eq!(
+        expected_events,
+        filter_events(&our_output, "557:")
+    );
+}
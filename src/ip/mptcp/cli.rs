@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, OutputFormat};
+use serde::Serialize;
+
+use super::{
+    endpoint::{CliMptcpEndpoint, handle_flush, handle_modify, handle_show},
+    limits::{CliMptcpLimits, handle_limits},
+    message::MptcpPmCmd,
+    monitor::handle_monitor,
+};
+use crate::{CliError, context::NetlinkContext};
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliMptcpOutput {
+    Endpoints(Vec<CliMptcpEndpoint>),
+    Limits(CliMptcpLimits),
+}
+
+impl CanDisplay for CliMptcpOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Endpoints(entries) => entries.gen_string(),
+            Self::Limits(limits) => limits.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Endpoints(entries) => entries.gen_oneline_string(),
+            Self::Limits(limits) => limits.gen_oneline_string(),
+        }
+    }
+}
+
+impl CanOutput for CliMptcpOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+pub(crate) struct MptcpCommand;
+
+impl MptcpCommand {
+    pub(crate) const CMD: &'static str = "mptcp";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("Multipath TCP path manager configuration")
+            .subcommand_required(true)
+            .subcommand(
+                clap::Command::new("endpoint")
+                    .about("manage MPTCP endpoints")
+                    .subcommand_required(false)
+                    .subcommand(
+                        clap::Command::new("add")
+                            .about("add MPTCP endpoint")
+                            .alias("ad")
+                            .alias("a")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("change")
+                            .about("change flags of MPTCP endpoint")
+                            .alias("chang")
+                            .alias("chan")
+                            .alias("cha")
+                            .alias("ch")
+                            .alias("c")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("delete")
+                            .about("delete MPTCP endpoint")
+                            .alias("delet")
+                            .alias("dele")
+                            .alias("del")
+                            .alias("de")
+                            .alias("d")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("show")
+                            .about("show MPTCP endpoints")
+                            .alias("sho")
+                            .alias("sh")
+                            .alias("s")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("flush")
+                            .about("delete all MPTCP endpoints")
+                            .alias("flus")
+                            .alias("flu")
+                            .alias("fl")
+                            .alias("f")
+                            .arg(gen_opts_arg()),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("limits")
+                    .about("manage MPTCP path manager limits")
+                    .subcommand_required(false)
+                    .subcommand(
+                        clap::Command::new("set")
+                            .about("set MPTCP limits")
+                            .alias("se")
+                            .arg(gen_opts_arg()),
+                    )
+                    .subcommand(
+                        clap::Command::new("show")
+                            .about("show MPTCP limits")
+                            .alias("sho")
+                            .alias("sh")
+                            .alias("s")
+                            .arg(gen_opts_arg()),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("monitor")
+                    .about("watch for MPTCP path manager events")
+                    .alias("monito")
+                    .alias("monit")
+                    .alias("moni")
+                    .alias("mon")
+                    .alias("mo")
+                    .alias("m"),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+        fmt: OutputFormat,
+    ) -> Result<Option<CliMptcpOutput>, CliError> {
+        if let Some(matches) = matches.subcommand_matches("endpoint") {
+            Self::handle_endpoint(matches, ctx).await
+        } else if let Some(matches) = matches.subcommand_matches("limits") {
+            let (cmd, opts) =
+                if let Some(matches) = matches.subcommand_matches("set") {
+                    (MptcpPmCmd::SetLimits, get_opts(matches))
+                } else {
+                    (
+                        MptcpPmCmd::GetLimits,
+                        matches
+                            .subcommand_matches("show")
+                            .map(get_opts)
+                            .unwrap_or_default(),
+                    )
+                };
+            Ok(handle_limits(ctx, &opts, cmd)
+                .await?
+                .map(CliMptcpOutput::Limits))
+        } else {
+            // The monitor prints events by itself as they arrive
+            handle_monitor(ctx, fmt).await?;
+            Ok(None)
+        }
+    }
+
+    async fn handle_endpoint(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliMptcpOutput>, CliError> {
+        for (name, cmd) in [
+            ("add", MptcpPmCmd::AddAddr),
+            ("change", MptcpPmCmd::SetFlags),
+            ("delete", MptcpPmCmd::DelAddr),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                handle_modify(ctx, &get_opts(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+        if matches.subcommand_matches("flush").is_some() {
+            handle_flush(ctx).await?;
+            return Ok(None);
+        }
+
+        let opts = matches
+            .subcommand_matches("show")
+            .map(get_opts)
+            .unwrap_or_default();
+        handle_show(ctx, &opts)
+            .await
+            .map(CliMptcpOutput::Endpoints)
+            .map(Some)
+    }
+}
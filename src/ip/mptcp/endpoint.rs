@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+use serde::Serialize;
+
+use super::message::{
+    MPTCP_PM_ADDR_FLAG_BACKUP, MPTCP_PM_ADDR_FLAG_FULLMESH,
+    MPTCP_PM_ADDR_FLAG_IMPLICIT, MPTCP_PM_ADDR_FLAG_SIGNAL,
+    MPTCP_PM_ADDR_FLAG_SUBFLOW, MptcpAddrAttr, MptcpPmAttr, MptcpPmCmd,
+    MptcpPmMessage,
+};
+use crate::{
+    context::NetlinkContext,
+    genl::genl_request,
+    link::{check_ifname, parse_num},
+    prefix::parse_ip_addr,
+    route::{get_ifnames, ifname_to_index},
+};
+
+// Equal to `AF_INET` and `AF_INET6` of `sys/socket.h`
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+// Equal to iproute2 `mptcp_addr_flag_names[]`, the `no` prefixed flags only
+// take effect when changing an endpoint.
+const MPTCP_ADDR_FLAG_NAMES: [(&str, u32); 7] = [
+    ("signal", MPTCP_PM_ADDR_FLAG_SIGNAL),
+    ("subflow", MPTCP_PM_ADDR_FLAG_SUBFLOW),
+    ("backup", MPTCP_PM_ADDR_FLAG_BACKUP),
+    ("fullmesh", MPTCP_PM_ADDR_FLAG_FULLMESH),
+    ("implicit", MPTCP_PM_ADDR_FLAG_IMPLICIT),
+    ("nobackup", 0),
+    ("nofullmesh", 0),
+];
+
+pub(crate) fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+fn missarg(key: &str) -> CliError {
+    CliError::from(format!("argument \"{key}\" is required").as_str())
+}
+
+/// Equal to iproute2 `mptcp_parse_opt()`, return the attributes nested in
+/// `MPTCP_PM_ATTR_ADDR`.
+async fn parse_endpoint_opts(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    cmd: MptcpPmCmd,
+) -> Result<Vec<MptcpAddrAttr>, CliError> {
+    let adding = cmd == MptcpPmCmd::AddAddr;
+    let setting = cmd == MptcpPmCmd::SetFlags;
+    let deling = cmd == MptcpPmCmd::DelAddr;
+    let mut addr: Option<IpAddr> = None;
+    let mut id: Option<u8> = None;
+    let mut ifindex: u32 = 0;
+    let mut flags: u32 = 0;
+    let mut port: u16 = 0;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if let Some((_, flag)) =
+            MPTCP_ADDR_FLAG_NAMES.iter().find(|(name, _)| name == opt)
+        {
+            flags |= flag;
+            if adding
+                && flags & MPTCP_PM_ADDR_FLAG_SIGNAL != 0
+                && flags & MPTCP_PM_ADDR_FLAG_FULLMESH != 0
+            {
+                return Err(invarg(
+                    opt,
+                    "flags mustn't have both signal and fullmesh",
+                ));
+            }
+            // Only the `backup` and `fullmesh` flags could be changed
+            if setting
+                && flags
+                    & !(MPTCP_PM_ADDR_FLAG_BACKUP | MPTCP_PM_ADDR_FLAG_FULLMESH)
+                    != 0
+            {
+                return Err(invarg(
+                    opt,
+                    "invalid flags, backup and fullmesh only",
+                ));
+            }
+            continue;
+        }
+        match *opt {
+            "id" => id = Some(parse_num(next_opt(&mut opts)?, "invalid ID")?),
+            "dev" => {
+                let name = next_opt(&mut opts)?;
+                check_ifname(name, "dev")
+                    .map_err(|_| invarg(name, "invalid interface name"))?;
+                ifindex = ifname_to_index(&get_ifnames(handle).await?, name)
+                    .map_err(|_| invarg(name, "device does not exist"))?;
+            }
+            "port" => port = parse_num(next_opt(&mut opts)?, "expected port")?,
+            _ => {
+                addr = Some(
+                    parse_ip_addr(opt, AddressFamily::Unspec)
+                        .map_err(|_| invarg(opt, "unknown argument"))?,
+                );
+            }
+        }
+    }
+
+    if adding && addr.is_none() {
+        return Err(missarg("ADDRESS"));
+    }
+    if deling {
+        match id {
+            None => return Err(missarg("ID")),
+            Some(id) if id != 0 && addr.is_some() => {
+                return Err(invarg(
+                    "ADDRESS",
+                    "invalid for non-zero id address",
+                ));
+            }
+            Some(0) if addr.is_none() => {
+                return Err(invarg(
+                    "ID",
+                    "address is needed for deleting id 0 address",
+                ));
+            }
+            _ => (),
+        }
+    }
+    if adding && port != 0 && flags & MPTCP_PM_ADDR_FLAG_SIGNAL == 0 {
+        return Err(invarg("port", "flags must have signal when using port"));
+    }
+    if setting && id.is_some() && port != 0 {
+        return Err(invarg("port", "port can't be used with id"));
+    }
+
+    let mut attrs = Vec::new();
+    if let Some(id) = id {
+        attrs.push(MptcpAddrAttr::Id(id));
+    }
+    if flags != 0 {
+        attrs.push(MptcpAddrAttr::Flags(flags));
+    }
+    if ifindex != 0 {
+        attrs.push(MptcpAddrAttr::IfIdx(ifindex as i32));
+    }
+    if port != 0 {
+        attrs.push(MptcpAddrAttr::Port(port));
+    }
+    match addr {
+        Some(IpAddr::V4(addr)) => {
+            attrs.push(MptcpAddrAttr::Family(AF_INET));
+            attrs.push(MptcpAddrAttr::Addr4(addr));
+        }
+        Some(IpAddr::V6(addr)) => {
+            attrs.push(MptcpAddrAttr::Family(AF_INET6));
+            attrs.push(MptcpAddrAttr::Addr6(addr));
+        }
+        None => (),
+    }
+    Ok(attrs)
+}
+
+/// Equal to iproute2 `mptcp_addr_modify()`, used by `add`, `change` and
+/// `delete` of `ip mptcp endpoint`.
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    cmd: MptcpPmCmd,
+) -> Result<(), CliError> {
+    let attrs = parse_endpoint_opts(ctx.handle(), opts, cmd).await?;
+    genl_request(
+        ctx,
+        MptcpPmMessage::new(cmd, vec![MptcpPmAttr::Addr(attrs)]),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `mptcp_addr_flush()`
+pub(crate) async fn handle_flush(ctx: &NetlinkContext) -> Result<(), CliError> {
+    genl_request(
+        ctx,
+        MptcpPmMessage::new(MptcpPmCmd::FlushAddrs, Vec::new()),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `print_mptcp_addrinfo()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMptcpEndpoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u8>,
+    #[serde(skip)]
+    flags: u32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    signal: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    subflow: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    backup: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    fullmesh: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    implicit: bool,
+    /// Unknown flags in hex string like iproute2
+    #[serde(skip_serializing_if = "String::is_empty")]
+    rawflags: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+}
+
+impl std::fmt::Display for CliMptcpEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(address) = self.address {
+            write!(f, "{address} ")?;
+        }
+        if let Some(port) = self.port {
+            write!(f, "port {port} ")?;
+        }
+        if let Some(id) = self.id {
+            write!(f, "id {id} ")?;
+        }
+        for (name, flag) in MPTCP_ADDR_FLAG_NAMES {
+            if flag != 0 && self.flags & flag != 0 {
+                write!(f, "{name} ")?;
+            }
+        }
+        if !self.rawflags.is_empty() {
+            write!(f, "rawflags {} ", self.rawflags)?;
+        }
+        if let Some(dev) = self.dev.as_ref() {
+            write!(f, "dev {dev} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMptcpEndpoint {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliMptcpEndpoint {}
+
+impl CliMptcpEndpoint {
+    fn from_msg(msg: MptcpPmMessage, ifnames: &HashMap<u32, String>) -> Self {
+        let mut ret = Self::default();
+        let Some(attrs) = msg.attributes.into_iter().find_map(|attr| {
+            if let MptcpPmAttr::Addr(attrs) = attr {
+                Some(attrs)
+            } else {
+                None
+            }
+        }) else {
+            return ret;
+        };
+        for attr in attrs {
+            match attr {
+                MptcpAddrAttr::Addr4(v) => ret.address = Some(IpAddr::V4(v)),
+                MptcpAddrAttr::Addr6(v) => ret.address = Some(IpAddr::V6(v)),
+                MptcpAddrAttr::Port(v) => ret.port = Some(v),
+                MptcpAddrAttr::Id(v) => ret.id = Some(v),
+                MptcpAddrAttr::Flags(v) => ret.set_flags(v),
+                // Like iproute2 `ll_index_to_name()`
+                MptcpAddrAttr::IfIdx(v) if v != 0 => {
+                    ret.dev = Some(
+                        ifnames
+                            .get(&(v as u32))
+                            .cloned()
+                            .unwrap_or_else(|| format!("if{v}")),
+                    )
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    // Equal to iproute2 `print_mptcp_addr_flags()`
+    fn set_flags(&mut self, flags: u32) {
+        self.flags = flags;
+        self.signal = flags & MPTCP_PM_ADDR_FLAG_SIGNAL != 0;
+        self.subflow = flags & MPTCP_PM_ADDR_FLAG_SUBFLOW != 0;
+        self.backup = flags & MPTCP_PM_ADDR_FLAG_BACKUP != 0;
+        self.fullmesh = flags & MPTCP_PM_ADDR_FLAG_FULLMESH != 0;
+        self.implicit = flags & MPTCP_PM_ADDR_FLAG_IMPLICIT != 0;
+        let unknown = flags
+            & !MPTCP_ADDR_FLAG_NAMES
+                .iter()
+                .fold(0, |mask, (_, flag)| mask | flag);
+        if unknown != 0 {
+            self.rawflags = format!("{unknown:02x}");
+        }
+    }
+}
+
+/// Equal to iproute2 `mptcp_addr_show()`, dump all endpoints when no option
+/// specified.
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<Vec<CliMptcpEndpoint>, CliError> {
+    let (attributes, flags) = if opts.is_empty() {
+        (Vec::new(), NLM_F_REQUEST | NLM_F_DUMP)
+    } else {
+        let attrs =
+            parse_endpoint_opts(ctx.handle(), opts, MptcpPmCmd::GetAddr)
+                .await?;
+        (vec![MptcpPmAttr::Addr(attrs)], NLM_F_REQUEST)
+    };
+    let msgs = genl_request(
+        ctx,
+        MptcpPmMessage::new(MptcpPmCmd::GetAddr, attributes),
+        flags,
+    )
+    .await?;
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(msgs
+        .into_iter()
+        .map(|msg| CliMptcpEndpoint::from_msg(msg, &ifnames))
+        .collect())
+}
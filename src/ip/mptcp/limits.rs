@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::{
+    endpoint::invarg,
+    message::{MptcpPmAttr, MptcpPmCmd, MptcpPmMessage},
+};
+use crate::{context::NetlinkContext, genl::genl_request, link::parse_num};
+
+/// Equal to iproute2 `print_mptcp_limit()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMptcpLimits {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    add_addr_accepted: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subflows: Option<u32>,
+}
+
+impl std::fmt::Display for CliMptcpLimits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(v) = self.add_addr_accepted {
+            write!(f, "add_addr_accepted {v} ")?;
+        }
+        if let Some(v) = self.subflows {
+            write!(f, "subflows {v} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMptcpLimits {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliMptcpLimits {}
+
+impl From<MptcpPmMessage> for CliMptcpLimits {
+    fn from(msg: MptcpPmMessage) -> Self {
+        let mut ret = Self::default();
+        for attr in msg.attributes {
+            match attr {
+                MptcpPmAttr::RcvAddAddrs(v) => ret.add_addr_accepted = Some(v),
+                MptcpPmAttr::Subflows(v) => ret.subflows = Some(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `mptcp_limit_get_set()`. Like iproute2, the limits
+/// are also accepted by `show` although the kernel ignores them.
+pub(crate) async fn handle_limits(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    cmd: MptcpPmCmd,
+) -> Result<Option<CliMptcpLimits>, CliError> {
+    let mut add_addr_accepted: Option<u32> = None;
+    let mut subflows: Option<u32> = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "subflows" => {
+                subflows =
+                    Some(parse_num(next_opt(&mut opts)?, "invalid subflows")?);
+            }
+            "add_addr_accepted" => {
+                add_addr_accepted = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "invalid add_addr_accepted",
+                )?);
+            }
+            _ => return Err(invarg(opt, "unknown limit")),
+        }
+    }
+
+    let mut attributes = Vec::new();
+    if let Some(v) = add_addr_accepted {
+        attributes.push(MptcpPmAttr::RcvAddAddrs(v));
+    }
+    if let Some(v) = subflows {
+        attributes.push(MptcpPmAttr::Subflows(v));
+    }
+
+    let is_get = cmd == MptcpPmCmd::GetLimits;
+    let flags = if is_get {
+        NLM_F_REQUEST
+    } else {
+        NLM_F_REQUEST | NLM_F_ACK
+    };
+    let replies =
+        genl_request(ctx, MptcpPmMessage::new(cmd, attributes), flags).await?;
+    Ok(if is_get {
+        replies.into_iter().next().map(Into::into)
+    } else {
+        None
+    })
+}
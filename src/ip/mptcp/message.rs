@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_i32, parse_ipv6,
+    parse_u8, parse_u16, parse_u16_be, parse_u32,
+};
+
+// Equal to `linux/mptcp.h`
+const MPTCP_PM_NAME: &str = "mptcp_pm";
+const MPTCP_PM_VER: u8 = 0x1;
+pub(crate) const MPTCP_PM_EV_GRP_NAME: &str = "mptcp_pm_events";
+
+const MPTCP_PM_CMD_ADD_ADDR: u8 = 1;
+const MPTCP_PM_CMD_DEL_ADDR: u8 = 2;
+const MPTCP_PM_CMD_GET_ADDR: u8 = 3;
+const MPTCP_PM_CMD_FLUSH_ADDRS: u8 = 4;
+const MPTCP_PM_CMD_SET_LIMITS: u8 = 5;
+const MPTCP_PM_CMD_GET_LIMITS: u8 = 6;
+const MPTCP_PM_CMD_SET_FLAGS: u8 = 7;
+
+const MPTCP_PM_ATTR_ADDR: u16 = 1;
+const MPTCP_PM_ATTR_RCV_ADD_ADDRS: u16 = 2;
+const MPTCP_PM_ATTR_SUBFLOWS: u16 = 3;
+
+const MPTCP_PM_ADDR_ATTR_FAMILY: u16 = 1;
+const MPTCP_PM_ADDR_ATTR_ID: u16 = 2;
+const MPTCP_PM_ADDR_ATTR_ADDR4: u16 = 3;
+const MPTCP_PM_ADDR_ATTR_ADDR6: u16 = 4;
+const MPTCP_PM_ADDR_ATTR_PORT: u16 = 5;
+const MPTCP_PM_ADDR_ATTR_FLAGS: u16 = 6;
+const MPTCP_PM_ADDR_ATTR_IF_IDX: u16 = 7;
+
+pub(crate) const MPTCP_PM_ADDR_FLAG_SIGNAL: u32 = 1 << 0;
+pub(crate) const MPTCP_PM_ADDR_FLAG_SUBFLOW: u32 = 1 << 1;
+pub(crate) const MPTCP_PM_ADDR_FLAG_BACKUP: u32 = 1 << 2;
+pub(crate) const MPTCP_PM_ADDR_FLAG_FULLMESH: u32 = 1 << 3;
+pub(crate) const MPTCP_PM_ADDR_FLAG_IMPLICIT: u32 = 1 << 4;
+
+const MPTCP_ATTR_TOKEN: u16 = 1;
+const MPTCP_ATTR_FAMILY: u16 = 2;
+const MPTCP_ATTR_LOC_ID: u16 = 3;
+const MPTCP_ATTR_REM_ID: u16 = 4;
+const MPTCP_ATTR_SADDR4: u16 = 5;
+const MPTCP_ATTR_SADDR6: u16 = 6;
+const MPTCP_ATTR_DADDR4: u16 = 7;
+const MPTCP_ATTR_DADDR6: u16 = 8;
+const MPTCP_ATTR_SPORT: u16 = 9;
+const MPTCP_ATTR_DPORT: u16 = 10;
+const MPTCP_ATTR_BACKUP: u16 = 11;
+const MPTCP_ATTR_ERROR: u16 = 12;
+const MPTCP_ATTR_FLAGS: u16 = 13;
+const MPTCP_ATTR_TIMEOUT: u16 = 14;
+const MPTCP_ATTR_IF_IDX: u16 = 15;
+const MPTCP_ATTR_RESET_REASON: u16 = 16;
+const MPTCP_ATTR_RESET_FLAGS: u16 = 17;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MptcpPmCmd {
+    AddAddr,
+    DelAddr,
+    GetAddr,
+    FlushAddrs,
+    SetLimits,
+    GetLimits,
+    SetFlags,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MptcpPmMessage {
+    pub(crate) cmd: MptcpPmCmd,
+    pub(crate) attributes: Vec<MptcpPmAttr>,
+}
+
+impl MptcpPmMessage {
+    pub(crate) fn new(cmd: MptcpPmCmd, attributes: Vec<MptcpPmAttr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for MptcpPmMessage {
+    fn family_name() -> &'static str {
+        MPTCP_PM_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            MptcpPmCmd::AddAddr => MPTCP_PM_CMD_ADD_ADDR,
+            MptcpPmCmd::DelAddr => MPTCP_PM_CMD_DEL_ADDR,
+            MptcpPmCmd::GetAddr => MPTCP_PM_CMD_GET_ADDR,
+            MptcpPmCmd::FlushAddrs => MPTCP_PM_CMD_FLUSH_ADDRS,
+            MptcpPmCmd::SetLimits => MPTCP_PM_CMD_SET_LIMITS,
+            MptcpPmCmd::GetLimits => MPTCP_PM_CMD_GET_LIMITS,
+            MptcpPmCmd::SetFlags => MPTCP_PM_CMD_SET_FLAGS,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        MPTCP_PM_VER
+    }
+}
+
+impl Emitable for MptcpPmMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for MptcpPmMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            MPTCP_PM_CMD_ADD_ADDR => MptcpPmCmd::AddAddr,
+            MPTCP_PM_CMD_DEL_ADDR => MptcpPmCmd::DelAddr,
+            MPTCP_PM_CMD_GET_ADDR => MptcpPmCmd::GetAddr,
+            MPTCP_PM_CMD_FLUSH_ADDRS => MptcpPmCmd::FlushAddrs,
+            MPTCP_PM_CMD_SET_LIMITS => MptcpPmCmd::SetLimits,
+            MPTCP_PM_CMD_GET_LIMITS => MptcpPmCmd::GetLimits,
+            MPTCP_PM_CMD_SET_FLAGS => MptcpPmCmd::SetFlags,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown mptcp_pm command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid mptcp_pm attribute")?;
+            attributes.push(MptcpPmAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MptcpPmAttr {
+    Addr(Vec<MptcpAddrAttr>),
+    RcvAddAddrs(u32),
+    Subflows(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpPmAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Addr(v) => v.as_slice().buffer_len(),
+            Self::RcvAddAddrs(_) | Self::Subflows(_) => 4,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Addr(_) => MPTCP_PM_ATTR_ADDR,
+            Self::RcvAddAddrs(_) => MPTCP_PM_ATTR_RCV_ADD_ADDRS,
+            Self::Subflows(_) => MPTCP_PM_ATTR_SUBFLOWS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Addr(v) => v.as_slice().emit(buffer),
+            Self::RcvAddAddrs(v) | Self::Subflows(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+
+    // The kernel rejects `MPTCP_PM_ATTR_ADDR` without `NLA_F_NESTED`
+    fn is_nested(&self) -> bool {
+        matches!(self, Self::Addr(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MptcpPmAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MPTCP_PM_ATTR_ADDR => {
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid mptcp_pm address")?;
+                    attrs.push(MptcpAddrAttr::parse(&nla)?);
+                }
+                Self::Addr(attrs)
+            }
+            MPTCP_PM_ATTR_RCV_ADD_ADDRS => {
+                Self::RcvAddAddrs(parse_u32(payload)?)
+            }
+            MPTCP_PM_ATTR_SUBFLOWS => Self::Subflows(parse_u32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `MPTCP_PM_ATTR_ADDR`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MptcpAddrAttr {
+    Family(u16),
+    Id(u8),
+    Addr4(Ipv4Addr),
+    Addr6(Ipv6Addr),
+    /// Port in host byte order
+    Port(u16),
+    Flags(u32),
+    IfIdx(i32),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpAddrAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Family(_) | Self::Port(_) => 2,
+            Self::Id(_) => 1,
+            Self::Addr4(_) | Self::Flags(_) | Self::IfIdx(_) => 4,
+            Self::Addr6(_) => 16,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Family(_) => MPTCP_PM_ADDR_ATTR_FAMILY,
+            Self::Id(_) => MPTCP_PM_ADDR_ATTR_ID,
+            Self::Addr4(_) => MPTCP_PM_ADDR_ATTR_ADDR4,
+            Self::Addr6(_) => MPTCP_PM_ADDR_ATTR_ADDR6,
+            Self::Port(_) => MPTCP_PM_ADDR_ATTR_PORT,
+            Self::Flags(_) => MPTCP_PM_ADDR_ATTR_FLAGS,
+            Self::IfIdx(_) => MPTCP_PM_ADDR_ATTR_IF_IDX,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Family(v) | Self::Port(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Id(v) => buffer[0] = *v,
+            Self::Addr4(v) => buffer.copy_from_slice(&v.octets()),
+            Self::Addr6(v) => buffer.copy_from_slice(&v.octets()),
+            Self::Flags(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::IfIdx(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+fn parse_ipv4(payload: &[u8]) -> Result<Ipv4Addr, DecodeError> {
+    <[u8; 4]>::try_from(payload)
+        .map(Ipv4Addr::from)
+        .map_err(|_| DecodeError::invalid_ip_address(payload.len()))
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MptcpAddrAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MPTCP_PM_ADDR_ATTR_FAMILY => Self::Family(parse_u16(payload)?),
+            MPTCP_PM_ADDR_ATTR_ID => Self::Id(parse_u8(payload)?),
+            MPTCP_PM_ADDR_ATTR_ADDR4 => Self::Addr4(parse_ipv4(payload)?),
+            MPTCP_PM_ADDR_ATTR_ADDR6 => {
+                Self::Addr6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            MPTCP_PM_ADDR_ATTR_PORT => Self::Port(parse_u16(payload)?),
+            MPTCP_PM_ADDR_ATTR_FLAGS => Self::Flags(parse_u32(payload)?),
+            MPTCP_PM_ADDR_ATTR_IF_IDX => Self::IfIdx(parse_i32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The notification of `mptcp_pm_events` multicast group, the generic
+/// netlink command holds the `MPTCP_EVENT_*` type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MptcpEventMessage {
+    pub(crate) event: u8,
+    pub(crate) attributes: Vec<MptcpEventAttr>,
+}
+
+impl GenlFamily for MptcpEventMessage {
+    fn family_name() -> &'static str {
+        MPTCP_PM_NAME
+    }
+
+    fn command(&self) -> u8 {
+        self.event
+    }
+
+    fn version(&self) -> u8 {
+        MPTCP_PM_VER
+    }
+}
+
+impl Emitable for MptcpEventMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for MptcpEventMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid mptcp event attribute")?;
+            attributes.push(MptcpEventAttr::parse(&nla)?);
+        }
+        Ok(Self {
+            event: header.cmd,
+            attributes,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MptcpEventAttr {
+    Token(u32),
+    Family(u16),
+    LocId(u8),
+    RemId(u8),
+    Saddr4(Ipv4Addr),
+    Saddr6(Ipv6Addr),
+    Daddr4(Ipv4Addr),
+    Daddr6(Ipv6Addr),
+    /// Port in host byte order
+    Sport(u16),
+    /// Port in host byte order
+    Dport(u16),
+    Backup(u8),
+    Error(u8),
+    Flags(u16),
+    Timeout(u32),
+    IfIdx(i32),
+    ResetReason(u32),
+    ResetFlags(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpEventAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::LocId(_)
+            | Self::RemId(_)
+            | Self::Backup(_)
+            | Self::Error(_) => 1,
+            Self::Family(_)
+            | Self::Sport(_)
+            | Self::Dport(_)
+            | Self::Flags(_) => 2,
+            Self::Token(_)
+            | Self::Saddr4(_)
+            | Self::Daddr4(_)
+            | Self::Timeout(_)
+            | Self::IfIdx(_)
+            | Self::ResetReason(_)
+            | Self::ResetFlags(_) => 4,
+            Self::Saddr6(_) | Self::Daddr6(_) => 16,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Token(_) => MPTCP_ATTR_TOKEN,
+            Self::Family(_) => MPTCP_ATTR_FAMILY,
+            Self::LocId(_) => MPTCP_ATTR_LOC_ID,
+            Self::RemId(_) => MPTCP_ATTR_REM_ID,
+            Self::Saddr4(_) => MPTCP_ATTR_SADDR4,
+            Self::Saddr6(_) => MPTCP_ATTR_SADDR6,
+            Self::Daddr4(_) => MPTCP_ATTR_DADDR4,
+            Self::Daddr6(_) => MPTCP_ATTR_DADDR6,
+            Self::Sport(_) => MPTCP_ATTR_SPORT,
+            Self::Dport(_) => MPTCP_ATTR_DPORT,
+            Self::Backup(_) => MPTCP_ATTR_BACKUP,
+            Self::Error(_) => MPTCP_ATTR_ERROR,
+            Self::Flags(_) => MPTCP_ATTR_FLAGS,
+            Self::Timeout(_) => MPTCP_ATTR_TIMEOUT,
+            Self::IfIdx(_) => MPTCP_ATTR_IF_IDX,
+            Self::ResetReason(_) => MPTCP_ATTR_RESET_REASON,
+            Self::ResetFlags(_) => MPTCP_ATTR_RESET_FLAGS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::LocId(v)
+            | Self::RemId(v)
+            | Self::Backup(v)
+            | Self::Error(v) => buffer[0] = *v,
+            Self::Family(v) | Self::Flags(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Sport(v) | Self::Dport(v) => {
+                buffer.copy_from_slice(&v.to_be_bytes())
+            }
+            Self::Token(v)
+            | Self::Timeout(v)
+            | Self::ResetReason(v)
+            | Self::ResetFlags(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Saddr4(v) | Self::Daddr4(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::Saddr6(v) | Self::Daddr6(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::IfIdx(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for MptcpEventAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MPTCP_ATTR_TOKEN => Self::Token(parse_u32(payload)?),
+            MPTCP_ATTR_FAMILY => Self::Family(parse_u16(payload)?),
+            MPTCP_ATTR_LOC_ID => Self::LocId(parse_u8(payload)?),
+            MPTCP_ATTR_REM_ID => Self::RemId(parse_u8(payload)?),
+            MPTCP_ATTR_SADDR4 => Self::Saddr4(parse_ipv4(payload)?),
+            MPTCP_ATTR_SADDR6 => {
+                Self::Saddr6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            MPTCP_ATTR_DADDR4 => Self::Daddr4(parse_ipv4(payload)?),
+            MPTCP_ATTR_DADDR6 => {
+                Self::Daddr6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            MPTCP_ATTR_SPORT => Self::Sport(parse_u16_be(payload)?),
+            MPTCP_ATTR_DPORT => Self::Dport(parse_u16_be(payload)?),
+            MPTCP_ATTR_BACKUP => Self::Backup(parse_u8(payload)?),
+            MPTCP_ATTR_ERROR => Self::Error(parse_u8(payload)?),
+            MPTCP_ATTR_FLAGS => Self::Flags(parse_u16(payload)?),
+            MPTCP_ATTR_TIMEOUT => Self::Timeout(parse_u32(payload)?),
+            MPTCP_ATTR_IF_IDX => Self::IfIdx(parse_i32(payload)?),
+            MPTCP_ATTR_RESET_REASON => Self::ResetReason(parse_u32(payload)?),
+            MPTCP_ATTR_RESET_FLAGS => Self::ResetFlags(parse_u32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod endpoint;
+mod limits;
+mod message;
+mod monitor;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::MptcpCommand;
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliError, OutputFormat, gen_output_string,
+    print_output,
+};
+use netlink_packet_generic::GenlFamily;
+use rtnetlink::packet_core::NetlinkPayload;
+use serde::Serialize;
+
+use super::message::{MPTCP_PM_EV_GRP_NAME, MptcpEventAttr, MptcpEventMessage};
+use crate::{context::NetlinkContext, genl::resolve_mcast_group};
+
+// Equal to iproute2 `event_to_str[]`
+fn event_to_str(event: u8) -> Option<&'static str> {
+    Some(match event {
+        1 => "CREATED",
+        2 => "ESTABLISHED",
+        3 => "CLOSED",
+        6 => "ANNOUNCED",
+        7 => "REMOVED",
+        10 => "SF_ESTABLISHED",
+        11 => "SF_CLOSED",
+        13 => "SF_PRIO",
+        15 => "LISTENER_CREATED",
+        16 => "LISTENER_CLOSED",
+        _ => return None,
+    })
+}
+
+/// Equal to iproute2 `mptcp_monitor_msg()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliMptcpEvent {
+    event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remid: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locid: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saddr4: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    daddr4: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saddr6: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    daddr6: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sport: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dport: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ifindex: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reset_reason: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reset_flags: Option<u32>,
+    /// Unknown event type, printed without any attribute
+    #[serde(skip)]
+    unknown: Option<u8>,
+}
+
+impl std::fmt::Display for CliMptcpEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(event) = self.unknown {
+            return write!(f, "[UNKNOWN {event}]");
+        }
+        write!(f, "[{:>14}]", self.event)?;
+        // Like iproute2, the token is always printed
+        write!(f, " token={:08x}", self.token.unwrap_or_default())?;
+        if let Some(v) = self.remid {
+            write!(f, " remid={v}")?;
+        }
+        if let Some(v) = self.locid {
+            write!(f, " locid={v}")?;
+        }
+        for (name, addr) in [
+            ("saddr4", self.saddr4),
+            ("daddr4", self.daddr4),
+            ("saddr6", self.saddr6),
+            ("daddr6", self.daddr6),
+        ] {
+            if let Some(addr) = addr {
+                write!(f, " {name}={addr}")?;
+            }
+        }
+        if let Some(v) = self.sport {
+            write!(f, " sport={v}")?;
+        }
+        if let Some(v) = self.dport {
+            write!(f, " dport={v}")?;
+        }
+        if let Some(v) = self.backup {
+            write!(f, " backup={v}")?;
+        }
+        if let Some(v) = self.error {
+            write!(f, " error={v}")?;
+        }
+        if let Some(v) = self.flags {
+            write!(f, " flags={v:x}")?;
+        }
+        if let Some(v) = self.timeout {
+            write!(f, " timeout={v}")?;
+        }
+        if let Some(v) = self.ifindex {
+            write!(f, " ifindex={v}")?;
+        }
+        if let Some(v) = self.reset_reason {
+            write!(f, " reset_reason={v}")?;
+        }
+        if let Some(v) = self.reset_flags {
+            write!(f, " reset_flags=0x{v:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMptcpEvent {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliMptcpEvent {}
+
+impl From<MptcpEventMessage> for CliMptcpEvent {
+    fn from(msg: MptcpEventMessage) -> Self {
+        let mut ret = Self::default();
+        let Some(event) = event_to_str(msg.event) else {
+            ret.event = format!("UNKNOWN {}", msg.event);
+            ret.unknown = Some(msg.event);
+            return ret;
+        };
+        ret.event = event.to_string();
+        for attr in msg.attributes {
+            match attr {
+                MptcpEventAttr::Token(v) => ret.token = Some(v),
+                MptcpEventAttr::RemId(v) => ret.remid = Some(v),
+                MptcpEventAttr::LocId(v) => ret.locid = Some(v),
+                MptcpEventAttr::Saddr4(v) => ret.saddr4 = Some(v.into()),
+                MptcpEventAttr::Daddr4(v) => ret.daddr4 = Some(v.into()),
+                MptcpEventAttr::Saddr6(v) => ret.saddr6 = Some(v.into()),
+                MptcpEventAttr::Daddr6(v) => ret.daddr6 = Some(v.into()),
+                MptcpEventAttr::Sport(v) => ret.sport = Some(v),
+                MptcpEventAttr::Dport(v) => ret.dport = Some(v),
+                MptcpEventAttr::Backup(v) => ret.backup = Some(v),
+                MptcpEventAttr::Error(v) => ret.error = Some(v),
+                MptcpEventAttr::Flags(v) => ret.flags = Some(v),
+                MptcpEventAttr::Timeout(v) => ret.timeout = Some(v),
+                MptcpEventAttr::IfIdx(v) => ret.ifindex = Some(v),
+                MptcpEventAttr::ResetReason(v) => ret.reset_reason = Some(v),
+                MptcpEventAttr::ResetFlags(v) => ret.reset_flags = Some(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `mptcp_monitor()`, print the path manager events until
+/// interrupted.
+pub(crate) async fn handle_monitor(
+    ctx: &NetlinkContext,
+    fmt: OutputFormat,
+) -> Result<(), CliError> {
+    let group = resolve_mcast_group(
+        ctx,
+        MptcpEventMessage::family_name(),
+        MPTCP_PM_EV_GRP_NAME,
+    )
+    .await
+    .map_err(|_| CliError::from("can't subscribe to mptcp events"))?;
+
+    let genl = ctx.genl()?;
+    let mut messages = genl
+        .subscribe(&[group])
+        .map_err(|_| CliError::from("can't subscribe to mptcp events"))?;
+
+    let family_id = genl
+        .handle()
+        .resolve_family_id::<MptcpEventMessage>()
+        .await
+        .map_err(|e| CliError::from(format!("{e}").as_str()))?;
+
+    while let Some((nl_msg, _)) = messages.next().await {
+        if nl_msg.header.message_type != family_id {
+            continue;
+        }
+        let NetlinkPayload::InnerMessage(raw_msg) = nl_msg.payload else {
+            continue;
+        };
+        let event = match raw_msg.parse_into_genl::<MptcpEventMessage>() {
+            Ok(genl_msg) => CliMptcpEvent::from(genl_msg.payload),
+            Err(e) => {
+                log::debug!("Failed to parse mptcp event: {e}");
+                continue;
+            }
+        };
+        print_output(&Ok(gen_output_string(&event, fmt)));
+    }
+    Ok(())
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod mptcp;
+
+use crate::tests::exec_cmd;
+
+fn cleanup_mptcp_endpoints() {
+    exec_cmd(&["ip", "mptcp", "endpoint", "flush"]);
+}
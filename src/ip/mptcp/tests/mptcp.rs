@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_mptcp_endpoints;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_mptcp_endpoint_add_change_del() {
+    let _lock = lock_net_test();
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "mptcp",
+            "endpoint",
+            "add",
+            "198.51.100.1",
+            "id",
+            "5",
+            "signal",
+            "backup",
+        ]);
+        ip_rs_exec_cmd(&[
+            "mptcp",
+            "endpoint",
+            "add",
+            "2001:db8::1",
+            "dev",
+            "lo",
+            "id",
+            "6",
+            "subflow",
+            "fullmesh",
+        ]);
+
+        for args in [&[][..], &["-j"][..]] {
+            for show_args in [&["show"][..], &["show", "id", "5"][..]] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["mptcp", "endpoint"], show_args]
+                        .concat(),
+                );
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["mptcp", "endpoint"], show_args].concat(),
+                );
+
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        ip_rs_exec_cmd(&["mptcp", "endpoint", "change", "id", "5", "nobackup"]);
+        let output = ip_rs_exec_cmd(&["mptcp", "endpoint", "show", "id", "5"]);
+        pretty_assertions::assert_eq!(output, "198.51.100.1 id 5 signal \n");
+
+        ip_rs_exec_cmd(&["mptcp", "endpoint", "delete", "id", "6"]);
+        let output = ip_rs_exec_cmd(&["mptcp", "endpoint"]);
+        assert!(!output.contains("2001:db8::1"));
+
+        ip_rs_exec_cmd(&["mptcp", "endpoint", "flush"]);
+        let output = ip_rs_exec_cmd(&["mptcp", "endpoint", "show"]);
+        assert!(output.is_empty());
+    });
+
+    cleanup_mptcp_endpoints();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_mptcp_limits() {
+    let _lock = lock_net_test();
+
+    let orig_output = exec_cmd(&["ip", "mptcp", "limits", "show"]);
+    let orig: Vec<&str> = orig_output.split_whitespace().collect();
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&[
+            "mptcp",
+            "limits",
+            "set",
+            "subflows",
+            "3",
+            "add_addr_accepted",
+            "4",
+        ]);
+        let expected_output = exec_cmd(&["ip", "mptcp", "limits", "show"]);
+        let our_output = ip_rs_exec_cmd(&["mptcp", "limits", "show"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+        pretty_assertions::assert_eq!(
+            our_output,
+            "add_addr_accepted 4 subflows 3 \n"
+        );
+
+        // Without subcommand is equal to `show`
+        pretty_assertions::assert_eq!(
+            ip_rs_exec_cmd(&["mptcp", "limits"]),
+            our_output
+        );
+
+        let our_output = ip_rs_exec_cmd(&["-j", "mptcp", "limits", "show"]);
+        pretty_assertions::assert_eq!(
+            our_output,
+            "{\"add_addr_accepted\":4,\"subflows\":3}\n"
+        );
+    });
+
+    // Restore the original limits: `add_addr_accepted N subflows N`
+    exec_cmd(&[
+        "ip",
+        "mptcp",
+        "limits",
+        "set",
+        "add_addr_accepted",
+        orig[1],
+        "subflows",
+        orig[3],
+    ]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_mptcp_invalid_args() {
+    for (args, error) in [
+        (
+            &["mptcp", "endpoint", "add", "id", "1"][..],
+            "argument \"ADDRESS\" is required",
+        ),
+        (
+            &[
+                "mptcp",
+                "endpoint",
+                "add",
+                "198.51.100.1",
+                "signal",
+                "fullmesh",
+            ][..],
+            "argument \"fullmesh\" is wrong: flags mustn't have both signal \
+             and fullmesh",
+        ),
+        (
+            &["mptcp", "endpoint", "add", "198.51.100.1", "port", "1234"][..],
+            "argument \"port\" is wrong: flags must have signal when using \
+             port",
+        ),
+        (
+            &["mptcp", "endpoint", "add", "198.51.100.1", "id", "256"][..],
+            "argument \"256\" is wrong: invalid ID",
+        ),
+        (
+            &["mptcp", "endpoint", "add", "198.51.100.1", "port", "x"][..],
+            "argument \"x\" is wrong: expected port",
+        ),
+        (
+            &["mptcp", "endpoint", "add", "foo"][..],
+            "argument \"foo\" is wrong: unknown argument",
+        ),
+        (
+            &["mptcp", "endpoint", "change", "id", "1", "signal"][..],
+            "argument \"signal\" is wrong: invalid flags, backup and fullmesh \
+             only",
+        ),
+        (
+            &[
+                "mptcp", "endpoint", "change", "id", "1", "port", "1", "backup",
+            ][..],
+            "argument \"port\" is wrong: port can't be used with id",
+        ),
+        (
+            &["mptcp", "endpoint", "delete", "198.51.100.1"][..],
+            "argument \"ID\" is required",
+        ),
+        (
+            &["mptcp", "endpoint", "delete", "id", "1", "198.51.100.1"][..],
+            "argument \"ADDRESS\" is wrong: invalid for non-zero id address",
+        ),
+        (
+            &["mptcp", "endpoint", "delete", "id", "0"][..],
+            "argument \"ID\" is wrong: address is needed for deleting id 0 \
+             address",
+        ),
+        (
+            &["mptcp", "limits", "set", "subflows", "x"][..],
+            "argument \"x\" is wrong: invalid subflows",
+        ),
+        (
+            &["mptcp", "limits", "set", "add_addr_accepted", "-1"][..],
+            "argument \"-1\" is wrong: invalid add_addr_accepted",
+        ),
+        (
+            &["mptcp", "limits", "set", "foo", "1"][..],
+            "argument \"foo\" is wrong: unknown limit",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
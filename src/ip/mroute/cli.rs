@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MIT
+
+use super::show::{CliMrouteInfo, handle_show};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct MrouteCommand;
+
+impl MrouteCommand {
+    pub(crate) const CMD: &'static str = "mroute";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("multicast routing cache")
+            .alias("mrout")
+            .alias("mrou")
+            .alias("mro")
+            .alias("mr")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show multicast routing cache")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Vec<CliMrouteInfo>, CliError> {
+        let handle = ctx.handle();
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            handle,
+            &opts,
+            get_family(matches),
+            matches.get_flag("NUMERIC"),
+            matches.get_count("STATS") > 0,
+        )
+        .await
+    }
+}
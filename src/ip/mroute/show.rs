@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, write_with_color,
+};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    route::{
+        RouteAddress, RouteAttribute, RouteFlags, RouteMessage, RouteNextHop,
+        RouteType,
+    },
+};
+use serde::Serialize;
+
+use crate::{
+    prefix::CliIpPrefix,
+    route::{
+        RT_TABLE_DEFAULT, RT_TABLE_MAIN, get_ifnames, ifname_to_index,
+        parse_table, route_table, table_to_name,
+    },
+};
+
+const RTNL_FAMILY_IPMR: u8 = 128;
+const RTNL_FAMILY_IP6MR: u8 = 129;
+
+// The kernel reports the time since last use in `USER_HZ`, iproute2
+// `__jiffies_to_tv()` assumes it is 100.
+const USER_HZ: f64 = 100.0;
+
+/// Equal to iproute2 `print_mroute()`
+#[derive(Serialize)]
+pub(crate) struct CliMrouteInfo {
+    src: String,
+    /// The multicast group
+    dst: String,
+    iif: String,
+    /// The `iif` is colored only when resolved
+    #[serde(skip)]
+    iif_resolved: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    multipath: Option<Vec<CliMrouteOif>>,
+    state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offload: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    packets: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wrong_if: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CliMrouteOif {
+    oif: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u8>,
+}
+
+impl std::fmt::Display for CliMrouteInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<32} Iif: ", format!("({},{})", self.src, self.dst))?;
+        if self.iif_resolved {
+            write_with_color!(f, CliColor::IfaceName, "{:<10} ", self.iif)?;
+        } else {
+            write!(f, "{} ", self.iif)?;
+        }
+        if let Some(oifs) = &self.multipath {
+            for (i, oif) in oifs.iter().enumerate() {
+                if i == 0 {
+                    write!(f, "Oifs: ")?;
+                }
+                write_with_color!(f, CliColor::IfaceName, "{}", oif.oif)?;
+                match oif.ttl {
+                    Some(ttl) => write!(f, "(ttl {ttl}) ")?,
+                    None => write!(f, " ")?,
+                }
+            }
+        }
+        write!(f, " State: {}", self.state)?;
+        if self.offload.is_some() {
+            write!(f, " offload")?;
+        }
+        if let (Some(packets), Some(bytes)) = (self.packets, self.bytes) {
+            write!(f, "\n  {packets} packets, {bytes} bytes")?;
+            if let Some(wrong_if) = self.wrong_if {
+                write!(f, ", {wrong_if} arrived on wrong iif.")?;
+            }
+        }
+        if let Some(expires) = self.expires {
+            write!(f, ", Age {expires:.2}")?;
+        }
+        if let Some(table) = &self.table {
+            write!(f, " Table: {table}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliMrouteInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliMrouteInfo {}
+
+/// Filters of `ip mroute show` following the argument grammar of iproute2
+/// `mroute_list()`.
+#[derive(Debug)]
+struct MrouteShowFilter<'a> {
+    /// `0` for all tables
+    table: u32,
+    iif: Option<&'a str>,
+    src: Option<CliIpPrefix>,
+    group: Option<CliIpPrefix>,
+}
+
+impl<'a> MrouteShowFilter<'a> {
+    fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            // Like iproute2, the IPv4 multicast routes are shown from the
+            // default table for backward compatibility
+            table: if family == AddressFamily::Inet {
+                RT_TABLE_DEFAULT
+            } else {
+                0
+            },
+            iif: None,
+            src: None,
+            group: None,
+        };
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "table" => {
+                    let table = next_opt(&mut opts)?;
+                    ret.table = if table == "all" {
+                        0
+                    } else {
+                        parse_table(table, "table id value is invalid")?
+                    };
+                }
+                "iif" => {
+                    ret.iif = Some(next_opt(&mut opts)?);
+                }
+                "from" => {
+                    ret.src =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                _ => {
+                    let value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    ret.group = Some(CliIpPrefix::parse(value, family)?);
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+// The multicast route address is not parsed by family `RTNL_FAMILY_IPMR`
+// or `RTNL_FAMILY_IP6MR`, hence decode it by length.
+fn mroute_addr_to_ip(addr: &RouteAddress) -> Option<IpAddr> {
+    match addr {
+        RouteAddress::Inet(ip) => Some(IpAddr::V4(*ip)),
+        RouteAddress::Inet6(ip) => Some(IpAddr::V6(*ip)),
+        RouteAddress::Other(data) => {
+            if let Ok(octets) = <[u8; 4]>::try_from(data.as_slice()) {
+                Some(IpAddr::V4(Ipv4Addr::from(octets)))
+            } else if let Ok(octets) = <[u8; 16]>::try_from(data.as_slice()) {
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+// Like iproute2 `inet_addr_match_rta()`, the missing address only matches
+// the zero length prefix.
+fn prefix_matches(prefix: Option<&CliIpPrefix>, addr: Option<IpAddr>) -> bool {
+    match (prefix, addr) {
+        (None, _) => true,
+        (Some(prefix), None) => prefix.prefix_len == 0,
+        (Some(prefix), Some(addr)) => prefix.contains(&addr),
+    }
+}
+
+fn ifname(ifnames: &HashMap<u32, String>, index: u32) -> String {
+    ifnames
+        .get(&index)
+        .cloned()
+        .unwrap_or_else(|| format!("if{index}"))
+}
+
+fn parse_oifs(
+    nexthops: &[RouteNextHop],
+    ifnames: &HashMap<u32, String>,
+) -> Vec<CliMrouteOif> {
+    nexthops
+        .iter()
+        .map(|nexthop| CliMrouteOif {
+            oif: ifname(ifnames, nexthop.interface_index),
+            ttl: (nexthop.hops > 1).then_some(nexthop.hops),
+        })
+        .collect()
+}
+
+/// Convert the multicast route to the display form, `None` if filtered out
+fn parse_nl_msg_to_mroute(
+    nl_msg: RouteMessage,
+    filter: &MrouteShowFilter,
+    iif_index: Option<u32>,
+    ifnames: &HashMap<u32, String>,
+    numeric: bool,
+    show_stats: bool,
+) -> Option<CliMrouteInfo> {
+    let table = route_table(&nl_msg);
+    if filter.table > 0 && filter.table != table {
+        return None;
+    }
+
+    let mut src = None;
+    let mut group = None;
+    let mut iif = 0;
+    let mut multipath = None;
+    let mut mfc_stats = None;
+    let mut expires = None;
+    for attr in &nl_msg.attributes {
+        match attr {
+            RouteAttribute::Source(addr) => src = mroute_addr_to_ip(addr),
+            RouteAttribute::Destination(addr) => {
+                group = mroute_addr_to_ip(addr)
+            }
+            RouteAttribute::Iif(index) => iif = *index,
+            RouteAttribute::MultiPath(nexthops) => {
+                multipath = Some(parse_oifs(nexthops, ifnames))
+            }
+            RouteAttribute::MfcStats(stats) => mfc_stats = Some(*stats),
+            RouteAttribute::MulticastExpires(v) => expires = Some(*v),
+            _ => (),
+        }
+    }
+    if iif_index.is_some_and(|index| index != iif)
+        || !prefix_matches(filter.group.as_ref(), group)
+        || !prefix_matches(filter.src.as_ref(), src)
+    {
+        return None;
+    }
+
+    let flags = nl_msg.header.flags;
+    let mfc_stats = mfc_stats.filter(|_| show_stats);
+    Some(CliMrouteInfo {
+        src: src.map_or_else(|| "unknown".to_string(), |ip| ip.to_string()),
+        dst: group.map_or_else(|| "unknown".to_string(), |ip| ip.to_string()),
+        iif: if iif != 0 {
+            ifname(ifnames, iif)
+        } else {
+            "unresolved".to_string()
+        },
+        iif_resolved: iif != 0,
+        multipath,
+        state: if flags.contains(RouteFlags::Unresolved) {
+            "unresolved"
+        } else {
+            "resolved"
+        },
+        offload: flags.contains(RouteFlags::Offload).then_some(()),
+        packets: mfc_stats.map(|stats| stats.packets),
+        bytes: mfc_stats.map(|stats| stats.bytes),
+        wrong_if: mfc_stats
+            .map(|stats| stats.wrong_if)
+            .filter(|wrong_if| *wrong_if != 0),
+        expires: expires.filter(|_| show_stats).map(|v| v as f64 / USER_HZ),
+        table: (table != RT_TABLE_MAIN && filter.table == 0)
+            .then(|| table_to_name(table, numeric)),
+    })
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    numeric: bool,
+    show_stats: bool,
+) -> Result<Vec<CliMrouteInfo>, CliError> {
+    // Like iproute2, IPv4 is the default and other families have no
+    // multicast routing
+    let (family, mr_family) = match family {
+        AddressFamily::Unspec | AddressFamily::Inet => {
+            (AddressFamily::Inet, RTNL_FAMILY_IPMR)
+        }
+        AddressFamily::Inet6 => (AddressFamily::Inet6, RTNL_FAMILY_IP6MR),
+        _ => return Ok(Vec::new()),
+    };
+    let filter = MrouteShowFilter::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+    let iif_index = filter
+        .iif
+        .map(|iif| ifname_to_index(&ifnames, iif))
+        .transpose()?;
+
+    let mut nl_msg = RouteMessage::default();
+    nl_msg.header.address_family = AddressFamily::from(mr_family);
+
+    let mut ret = Vec::new();
+    let mut routes = handle.route().get(nl_msg).execute();
+    while let Some(nl_msg) = routes.try_next().await? {
+        if u8::from(nl_msg.header.address_family) != mr_family {
+            continue;
+        }
+        if nl_msg.header.kind != RouteType::Multicast {
+            return Err(CliError::from(
+                "Non multicast route received, kernel does support IP \
+                 multicast?",
+            ));
+        }
+        if let Some(mroute) = parse_nl_msg_to_mroute(
+            nl_msg, &filter, iif_index, &ifnames, numeric, show_stats,
+        ) {
+            ret.push(mroute);
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod show;
+
+use std::{
+    ffi::CString,
+    os::fd::{FromRawFd, OwnedFd, RawFd},
+};
+
+use nix::libc;
+
+use crate::tests::exec_cmd;
+
+// Equal to the socket options of `linux/mroute.h`
+const MRT_INIT: libc::c_int = 200;
+const MRT_ADD_VIF: libc::c_int = 202;
+const MRT_ADD_MFC: libc::c_int = 204;
+const VIFF_USE_IFINDEX: u8 = 0x8;
+const MAXVIFS: usize = 32;
+
+// Equal to `struct vifctl` with `VIFF_USE_IFINDEX`
+#[repr(C)]
+struct VifCtl {
+    vifi: u16,
+    flags: u8,
+    threshold: u8,
+    rate_limit: u32,
+    lcl_ifindex: i32,
+    rmt_addr: u32,
+}
+
+// Equal to `struct mfcctl`
+#[repr(C)]
+struct MfcCtl {
+    origin: [u8; 4],
+    mcastgrp: [u8; 4],
+    parent: u16,
+    ttls: [u8; MAXVIFS],
+    pkt_cnt: u32,
+    byte_cnt: u32,
+    wrong_if: u32,
+    expire: i32,
+}
+
+/// The IPv4 multicast routing daemon, the kernel removes its multicast
+/// routes once the socket is closed.
+pub(crate) struct Mrouter {
+    fd: OwnedFd,
+}
+
+impl Mrouter {
+    fn new() -> Self {
+        let fd = unsafe {
+            libc::socket(libc::AF_INET, libc::SOCK_RAW, libc::IPPROTO_IGMP)
+        };
+        assert!(fd >= 0, "Failed to create IGMP socket");
+        let ret = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+        };
+        ret.set_opt(MRT_INIT, &1i32);
+        ret
+    }
+
+    fn raw_fd(&self) -> RawFd {
+        std::os::fd::AsRawFd::as_raw_fd(&self.fd)
+    }
+
+    fn set_opt<T>(&self, name: libc::c_int, value: &T) {
+        let ret = unsafe {
+            libc::setsockopt(
+                self.raw_fd(),
+                libc::IPPROTO_IP,
+                name,
+                (value as *const T).cast(),
+                std::mem::size_of::<T>() as libc::socklen_t,
+            )
+        };
+        assert_eq!(
+            ret,
+            0,
+            "setsockopt {name} failed: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    fn add_vif(&self, vifi: u16, ifname: &str) {
+        let name = CString::new(ifname).unwrap();
+        let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        self.set_opt(
+            MRT_ADD_VIF,
+            &VifCtl {
+                vifi,
+                flags: VIFF_USE_IFINDEX,
+                threshold: 1,
+                rate_limit: 0,
+                lcl_ifindex: ifindex as i32,
+                rmt_addr: 0,
+            },
+        );
+    }
+
+    /// The `oifs` holds the virtual interface index and its TTL threshold
+    fn add_mfc(
+        &self,
+        origin: &str,
+        group: &str,
+        parent: u16,
+        oifs: &[(u16, u8)],
+    ) {
+        let mut ttls = [0u8; MAXVIFS];
+        for (vifi, ttl) in oifs {
+            ttls[usize::from(*vifi)] = *ttl;
+        }
+        self.set_opt(
+            MRT_ADD_MFC,
+            &MfcCtl {
+                origin: origin.parse::<std::net::Ipv4Addr>().unwrap().octets(),
+                mcastgrp: group.parse::<std::net::Ipv4Addr>().unwrap().octets(),
+                parent,
+                ttls,
+                pkt_cnt: 0,
+                byte_cnt: 0,
+                wrong_if: 0,
+                expire: 0,
+            },
+        );
+    }
+}
+
+// The multicast routing daemon is exclusive in network namespace, hence the
+// interfaces and routes are set up by single test.
+fn setup_mroute(veth_names: &[&str]) -> Mrouter {
+    for (i, veth_name) in veth_names.iter().enumerate() {
+        let peer_name = format!("{veth_name}p");
+        exec_cmd(&[
+            "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+            &peer_name,
+        ]);
+        exec_cmd(&[
+            "ip",
+            "addr",
+            "add",
+            &format!("198.18.7{i}.1/24"),
+            "dev",
+            veth_name,
+        ]);
+        exec_cmd(&["ip", "link", "set", veth_name, "up"]);
+    }
+
+    let mrouter = Mrouter::new();
+    for (i, veth_name) in veth_names.iter().enumerate() {
+        mrouter.add_vif(i as u16, veth_name);
+    }
+    mrouter.add_mfc("198.18.70.2", "239.1.2.3", 0, &[(1, 1), (2, 3)]);
+    mrouter.add_mfc("0.0.0.0", "239.1.2.4", 1, &[(2, 1)]);
+    mrouter
+}
+
+fn cleanup_mroute(veth_names: &[&str]) {
+    for veth_name in veth_names {
+        exec_cmd(&["ip", "link", "del", veth_name]);
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_mroute, setup_mroute};
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+#[test]
+fn test_mroute_show() {
+    let veth_names = ["mrtest-veth0", "mrtest-veth1", "mrtest-veth2"];
+    let mrouter = setup_mroute(&veth_names);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["mroute"][..],
+            &["mroute", "show"][..],
+            &["-4", "mroute", "list", "table", "all"][..],
+            &["mroute", "show", "iif", "mrtest-veth1"][..],
+            &["-j", "mroute", "show"][..],
+            &["-6", "mroute", "show"][..],
+            &["-0", "mroute", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The iproute2 6.1 fails to parse the `to` and `from` prefixes for
+        // multicast families, hence compare with the filtered full list.
+        let full_output = exec_cmd(&["ip", "mroute", "show"]);
+        let expected_output: String = full_output
+            .lines()
+            .filter(|line| line.starts_with("(198.18.70.2,239.1.2.3)"))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        for args in [
+            &["mroute", "show", "to", "239.1.2.3"][..],
+            &["mroute", "show", "239.1.2.0/24", "from", "198.18.70.0/24"][..],
+        ] {
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The age keeps changing
+        let our_output = ip_rs_exec_cmd(&["-s", "mroute", "show"]);
+        assert!(our_output.contains(
+            "Oifs: mrtest-veth1 mrtest-veth2(ttl 3)  State: resolved\n  0 \
+             packets, 0 bytes, Age "
+        ));
+    });
+
+    drop(mrouter);
+    cleanup_mroute(&veth_names);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_mroute_show_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["table", "foo"][..],
+            "argument \"foo\" is wrong: table id value is invalid",
+        ),
+        (
+            &["iif", "mrtest-absent0"][..],
+            "Cannot find device \"mrtest-absent0\"",
+        ),
+        (
+            &["to", "zz"][..],
+            "inet prefix is expected rather than \"zz\".",
+        ),
+        (&["from"][..], "Command line is not complete"),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["mroute", "show"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
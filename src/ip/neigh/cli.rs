@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    flush::handle_flush,
+    get::handle_get,
+    modify::{NeighModifyCmd, handle_modify},
+    show::{CliNeighInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct NeighCommand;
+
+impl NeighCommand {
+    pub(crate) const CMD: &'static str = "neighbour";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("neighbour/arp tables management")
+            .alias("neighbor")
+            .alias("neighbou")
+            .alias("neighbo")
+            .alias("neighb")
+            .alias("neigh")
+            .alias("neig")
+            .alias("nei")
+            .alias("ne")
+            .alias("n")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show neighbours")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("add").about("add new neighbour").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete neighbour")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("change")
+                    .about("change existing neighbour")
+                    .alias("chg")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("replace")
+                    .about("add or change neighbour")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("get").about("get neighbour entry").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+            .subcommand(
+                clap::Command::new("flush").about("flush neighbours").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliNeighInfo>>, CliError> {
+        let handle = ctx.handle();
+        for (subcommand, cmd) in [
+            ("add", NeighModifyCmd::Add),
+            ("delete", NeighModifyCmd::Delete),
+            ("change", NeighModifyCmd::Change),
+            ("replace", NeighModifyCmd::Replace),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(subcommand) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(handle, &opts, get_family(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("get") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            return handle_get(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_flag("NUMERIC"),
+                matches.get_flag("RESOLVE"),
+                matches.get_count("STATS") > 0,
+            )
+            .await
+            .map(Into::into);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("flush") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_flush(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_flag("NUMERIC"),
+                matches.get_count("STATS"),
+                matches.get_flag("FORCE"),
+            )
+            .await?;
+            return Ok(None);
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            handle,
+            &opts,
+            get_family(matches),
+            matches.get_flag("NUMERIC"),
+            matches.get_flag("RESOLVE"),
+            matches.get_count("STATS") > 0,
+        )
+        .await
+        .map(Into::into)
+    }
+}
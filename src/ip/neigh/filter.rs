@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    neighbour::{NeighbourAttribute, NeighbourMessage},
+};
+
+use super::{
+    names::{
+        NTF_EXT_LEARNED, NTF_PROXY, NUD_FAILED, NUD_NOARP, NUD_PERMANENT,
+        parse_nud_state,
+    },
+    show::neigh_addr_to_ip,
+};
+use crate::{
+    prefix::CliIpPrefix,
+    route::{ifname_to_index, parse_protocol},
+};
+
+// Like iproute2, the `0x100` bit of the state filter stands for
+// `NUD_NONE` which is zero
+const NUD_NONE_FILTER: u32 = 0x100;
+
+/// Filters of `ip neigh show` and `ip neigh flush` following the argument
+/// grammar of iproute2 `ipneigh_list_or_flush()`.
+#[derive(Debug)]
+pub(crate) struct NeighShowFilter<'a> {
+    pub(crate) family: AddressFamily,
+    dev: Option<&'a str>,
+    /// Resolved index of `dev`
+    pub(crate) index: Option<u32>,
+    master: Option<&'a str>,
+    /// Resolved index of `master` or `vrf`
+    pub(crate) master_index: Option<u32>,
+    /// Bitmask of the NUD states
+    state: u32,
+    unused_only: bool,
+    pub(crate) proxy: bool,
+    protocol: Option<u8>,
+    prefix: Option<CliIpPrefix>,
+}
+
+impl<'a> NeighShowFilter<'a> {
+    /// Like iproute2 `ipneigh_reset_filter()` used by `ip neigh get`,
+    /// nothing is filtered and the device is shown.
+    pub(crate) fn new_unfiltered() -> Self {
+        Self {
+            family: AddressFamily::Unspec,
+            dev: None,
+            index: None,
+            master: None,
+            master_index: None,
+            state: u32::MAX,
+            unused_only: false,
+            proxy: false,
+            protocol: None,
+            prefix: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+        is_flush: bool,
+    ) -> Result<Self, CliError> {
+        // Like iproute2, the `NOARP` entries are hidden by default and
+        // never flushed by `nud all`. The `PERMANENT` entries are only
+        // flushed when requested explicitly.
+        let noarp = u32::from(NUD_NOARP);
+        let (default_state, all_state) = if is_flush {
+            (!(u32::from(NUD_PERMANENT) | noarp), !noarp)
+        } else {
+            (0xff & !noarp, u32::MAX)
+        };
+        let mut ret = Self {
+            family,
+            state: default_state,
+            ..Self::new_unfiltered()
+        };
+        let mut state_given = false;
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => ret.dev = Some(next_opt(&mut opts)?),
+                // TODO: Check the `vrf` is a VRF interface like iproute2
+                "master" | "vrf" => ret.master = Some(next_opt(&mut opts)?),
+                "unused" => ret.unused_only = true,
+                "nud" => {
+                    let value = next_opt(&mut opts)?;
+                    if !state_given {
+                        state_given = true;
+                        ret.state = 0;
+                    }
+                    let state = if value == "all" {
+                        all_state
+                    } else {
+                        match u32::from(parse_nud_state(value)?) {
+                            0 => NUD_NONE_FILTER,
+                            v => v,
+                        }
+                    };
+                    ret.state |= state;
+                }
+                "proxy" => ret.proxy = true,
+                "protocol" | "proto" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.protocol = if value == "all" {
+                        None
+                    } else {
+                        Some(parse_protocol(value, "invalid \"protocol\"")?)
+                    };
+                }
+                _ => {
+                    let value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    let prefix = CliIpPrefix::parse(value, ret.family)?;
+                    if ret.family == AddressFamily::Unspec {
+                        ret.family = if prefix.addr.is_ipv6() {
+                            AddressFamily::Inet6
+                        } else {
+                            AddressFamily::Inet
+                        };
+                    }
+                    ret.prefix = Some(prefix);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    pub(crate) fn resolve_dev(
+        &mut self,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<(), CliError> {
+        if let Some(dev) = self.dev {
+            self.index = Some(ifname_to_index(ifnames, dev)?);
+        }
+        if let Some(master) = self.master {
+            self.master_index = Some(ifname_to_index(ifnames, master)?);
+        }
+        Ok(())
+    }
+
+    /// Like iproute2, the `FAILED` entries are only flushed in the first
+    /// round as kernel might create them again.
+    pub(crate) fn exclude_failed(&mut self) {
+        self.state &= !u32::from(NUD_FAILED);
+    }
+
+    /// Like iproute2, the device is not shown when filtering on it.
+    pub(crate) fn hide_dev(&self) -> bool {
+        self.index.is_some()
+    }
+
+    // Equal to the filter of iproute2 `print_neigh()`
+    pub(crate) fn matches(&self, nl_msg: &NeighbourMessage) -> bool {
+        let header = &nl_msg.header;
+        let state = u16::from(header.state);
+        let flags = header.flags.bits();
+
+        if self.index.is_some_and(|i| i != header.ifindex) {
+            return false;
+        }
+        if u32::from(state) & self.state == 0
+            && flags & (NTF_PROXY | NTF_EXT_LEARNED) == 0
+            && (state != 0 || self.state & NUD_NONE_FILTER == 0)
+        {
+            return false;
+        }
+
+        let mut dst = None;
+        let mut protocol = 0;
+        let mut refcnt = 0;
+        for nla in nl_msg.attributes.iter() {
+            match nla {
+                NeighbourAttribute::Destination(addr) => {
+                    dst = neigh_addr_to_ip(addr);
+                }
+                NeighbourAttribute::Protocol(v) => protocol = u8::from(*v),
+                NeighbourAttribute::CacheInfo(v) => refcnt = v.refcnt,
+                _ => (),
+            }
+        }
+        // Like iproute2 `inet_addr_match_rta()`, the entry without
+        // destination matches any prefix
+        if let (Some(prefix), Some(dst)) = (self.prefix, dst)
+            && !prefix.contains(&dst)
+        {
+            return false;
+        }
+        if self.protocol.is_some_and(|p| p != protocol) {
+            return false;
+        }
+        !(self.unused_only && refcnt != 0)
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::Write;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::CliError;
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage, neighbour::NeighbourMessage,
+    },
+};
+
+use super::{
+    filter::NeighShowFilter,
+    show::{dump_neighbours, parse_nl_msg_to_neigh},
+};
+use crate::route::get_ifnames;
+
+// Equal to iproute2 `MAX_ROUNDS`
+const MAX_ROUNDS: u32 = 10;
+
+/// Like iproute2 `ipneigh_list_or_flush()`, dump and delete the matching
+/// entries round by round until nothing left or `MAX_ROUNDS` reached. The
+/// summary of each round is printed when `show_stats` is non-zero, and the
+/// deleted entries are printed also when it is larger than 1. The failure of
+/// deleting an entry stops the flush unless `force` is true.
+pub(crate) async fn handle_flush(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    numeric: bool,
+    show_stats: u8,
+    force: bool,
+) -> Result<(), CliError> {
+    if opts.is_empty() {
+        return Err(CliError::from("Flush requires arguments."));
+    }
+    let mut filter = NeighShowFilter::parse(opts, family, true)?;
+    let ifnames = get_ifnames(handle).await?;
+    filter.resolve_dev(&ifnames)?;
+
+    for round in 0..MAX_ROUNDS {
+        let flushed: Vec<NeighbourMessage> = dump_neighbours(handle, &filter)
+            .await?
+            .into_iter()
+            .filter(|nl_msg| filter.matches(nl_msg))
+            .collect();
+        if flushed.is_empty() {
+            if show_stats > 0 {
+                if round == 0 {
+                    writeln!(std::io::stdout(), "Nothing to flush.").ok();
+                } else {
+                    writeln!(
+                        std::io::stdout(),
+                        "*** Flush is complete after {round} round{} ***",
+                        if round > 1 { "s" } else { "" }
+                    )
+                    .ok();
+                }
+            }
+            return Ok(());
+        }
+
+        let count = flushed.len();
+        for nl_msg in flushed {
+            if show_stats > 1 {
+                let neigh = parse_nl_msg_to_neigh(
+                    nl_msg.clone(),
+                    &filter,
+                    &ifnames,
+                    numeric,
+                    true,
+                );
+                writeln!(std::io::stdout(), "{neigh}").ok();
+            }
+            if let Err(e) = delete_neighbour(handle, nl_msg).await {
+                if !force {
+                    return Err(e);
+                }
+                writeln!(std::io::stderr(), "{e}").ok();
+            }
+        }
+        if show_stats > 0 {
+            writeln!(
+                std::io::stdout(),
+                "\n*** Round {}, deleting {count} entries ***",
+                round + 1
+            )
+            .ok();
+        }
+        filter.exclude_failed();
+    }
+
+    Err(CliError::from(
+        format!("*** Flush not complete bailing out after {MAX_ROUNDS} rounds")
+            .as_str(),
+    ))
+}
+
+// The entry might be removed by kernel in the meantime, hence ignore the
+// already removed ones.
+async fn delete_neighbour(
+    handle: &rtnetlink::Handle,
+    nl_msg: NeighbourMessage,
+) -> Result<(), CliError> {
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::DelNeighbour(nl_msg)),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && let Some(code) = e.code
+            && code.get().abs() != nix::errno::Errno::ENOENT as i32
+        {
+            return Err(CliError::from(
+                format!(
+                    "Failed to send flush request: {}",
+                    CliError::from(rtnetlink::Error::NetlinkError(e)).msg
+                )
+                .as_str(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour::{NeighbourAttribute, NeighbourFlags, NeighbourMessage},
+    },
+};
+
+use super::{
+    filter::NeighShowFilter,
+    names::NTF_PROXY,
+    show::{CliNeighInfo, parse_nl_msg_to_neigh, resolve_neigh_hostnames},
+};
+use crate::{
+    address::duplicate_arg,
+    prefix::parse_ip_addr,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// Options of `ip neigh get` following the argument grammar of iproute2
+/// `ipneigh_get()`.
+#[derive(Debug, Default)]
+struct NeighGetOptions<'a> {
+    dst: Option<IpAddr>,
+    dev: Option<&'a str>,
+    proxy: bool,
+}
+
+impl<'a> NeighGetOptions<'a> {
+    fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => ret.dev = Some(next_opt(&mut opts)?),
+                "proxy" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.dst.is_some() {
+                        return Err(duplicate_arg("address", value));
+                    }
+                    ret.dst = Some(parse_ip_addr(value, family)?);
+                    ret.proxy = true;
+                }
+                _ => {
+                    let value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if ret.dst.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"to\" is duplicate, or \"{value}\" \
+                                 is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.dst = Some(parse_ip_addr(value, family)?);
+                }
+            }
+        }
+        // Like iproute2, the proxy entry could be queried without device
+        if (ret.dev.is_none() && !ret.proxy) || ret.dst.is_none() {
+            return Err(CliError::from(
+                "Device and address are required arguments.",
+            ));
+        }
+        Ok(ret)
+    }
+
+    fn to_nl_msg(
+        &self,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<NeighbourMessage, CliError> {
+        let mut nl_msg = NeighbourMessage::default();
+        if let Some(dst) = self.dst {
+            nl_msg.header.family = match dst {
+                IpAddr::V4(_) => AddressFamily::Inet,
+                IpAddr::V6(_) => AddressFamily::Inet6,
+            };
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Destination(dst.into()));
+        }
+        if self.proxy {
+            nl_msg.header.flags = NeighbourFlags::from_bits_retain(NTF_PROXY);
+        }
+        if let Some(dev) = self.dev {
+            nl_msg.header.ifindex = ifname_to_index(ifnames, dev)?;
+        }
+        Ok(nl_msg)
+    }
+}
+
+pub(crate) async fn handle_get(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    numeric: bool,
+    resolve: bool,
+    show_stats: bool,
+) -> Result<Vec<CliNeighInfo>, CliError> {
+    let get_opts = NeighGetOptions::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+
+    // The rtnetlink crate always dumps neighbours, hence compose the
+    // request for the single entry
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::GetNeighbour(
+            get_opts.to_nl_msg(&ifnames)?,
+        )),
+    );
+    request.header.flags = NLM_F_REQUEST;
+
+    let filter = NeighShowFilter::new_unfiltered();
+    let mut ret = Vec::new();
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        match msg.payload {
+            NetlinkPayload::InnerMessage(
+                RouteNetlinkMessage::NewNeighbour(nl_msg),
+            ) => {
+                ret.push(parse_nl_msg_to_neigh(
+                    nl_msg, &filter, &ifnames, numeric, show_stats,
+                ));
+            }
+            NetlinkPayload::Error(e) if e.code.is_some() => {
+                return Err(rtnetlink::Error::NetlinkError(e).into());
+            }
+            _ => (),
+        }
+    }
+
+    if resolve {
+        resolve_neigh_hostnames(&mut ret).await;
+    }
+    Ok(ret)
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod filter;
+mod flush;
+mod get;
+mod modify;
+mod names;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::NeighCommand,
+    filter::NeighShowFilter,
+    modify::parse_lladdr,
+    show::{CliNeighInfo, parse_nl_msg_to_neigh},
+};
@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REPLACE,
+        NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour::{
+            NeighbourAttribute, NeighbourFlags, NeighbourMessage,
+            NeighbourState,
+        },
+        route::RouteProtocol,
+    },
+};
+
+use super::names::{
+    NDA_FLAGS_EXT, NTF_EXT_LEARNED, NTF_EXT_MANAGED, NTF_PROXY, NTF_ROUTER,
+    NTF_USE, NUD_PERMANENT, parse_nud_state,
+};
+use crate::{
+    address::duplicate_arg,
+    prefix::parse_ip_addr,
+    route::{get_ifnames, ifname_to_index, parse_protocol},
+};
+
+// Equal to the buffer size used by iproute2 `ipneigh_modify()`
+const MAX_LLADDR_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NeighModifyCmd {
+    Add,
+    /// Update existing entry only
+    Change,
+    /// Update existing entry or create new one
+    Replace,
+    Delete,
+}
+
+/// Options of `ip neigh add`, `change`, `replace` and `delete` following
+/// the argument grammar of iproute2 `ipneigh_modify()`.
+#[derive(Debug)]
+struct NeighModifyOptions<'a> {
+    dst: Option<IpAddr>,
+    dev: Option<&'a str>,
+    /// `None` for `lladdr null` or not specified
+    lladdr: Option<Vec<u8>>,
+    state: u16,
+    flags: u8,
+    ext_flags: u32,
+    protocol: Option<u8>,
+}
+
+impl<'a> NeighModifyOptions<'a> {
+    fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            dst: None,
+            dev: None,
+            lladdr: None,
+            state: NUD_PERMANENT,
+            flags: 0,
+            ext_flags: 0,
+            protocol: None,
+        };
+        let mut lladdr_given = false;
+        // Like iproute2, `proxy` does not require the device
+        let mut dev_ok = false;
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "lladdr" => {
+                    let value = next_opt(&mut opts)?;
+                    if lladdr_given {
+                        return Err(duplicate_arg("lladdr", value));
+                    }
+                    lladdr_given = true;
+                    if value != "null" {
+                        ret.lladdr = Some(parse_lladdr(value)?);
+                    }
+                }
+                "nud" => ret.state = parse_nud_state(next_opt(&mut opts)?)?,
+                "proxy" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.dst.is_some() {
+                        return Err(duplicate_arg("address", value));
+                    }
+                    ret.dst = Some(parse_ip_addr(value, family)?);
+                    dev_ok = true;
+                    ret.flags |= NTF_PROXY;
+                }
+                "router" => ret.flags |= NTF_ROUTER,
+                "extern_learn" => ret.flags |= NTF_EXT_LEARNED,
+                "use" => ret.flags |= NTF_USE,
+                "managed" => ret.ext_flags |= NTF_EXT_MANAGED,
+                "dev" => {
+                    ret.dev = Some(next_opt(&mut opts)?);
+                    dev_ok = true;
+                }
+                "protocol" | "proto" => {
+                    ret.protocol = Some(parse_protocol(
+                        next_opt(&mut opts)?,
+                        "\"protocol\" value is invalid",
+                    )?);
+                }
+                _ => {
+                    let value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if ret.dst.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"to\" is duplicate, or \"{value}\" \
+                                 is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.dst = Some(parse_ip_addr(value, family)?);
+                }
+            }
+        }
+        if !dev_ok || ret.dst.is_none() {
+            return Err(CliError::from(
+                "Device and destination are required arguments.",
+            ));
+        }
+        Ok(ret)
+    }
+
+    fn to_nl_msg(
+        &self,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<NeighbourMessage, CliError> {
+        let mut nl_msg = NeighbourMessage::default();
+        nl_msg.header.state = NeighbourState::from(self.state);
+        nl_msg.header.flags = NeighbourFlags::from_bits_retain(self.flags);
+
+        if let Some(dst) = self.dst {
+            nl_msg.header.family = match dst {
+                IpAddr::V4(_) => AddressFamily::Inet,
+                IpAddr::V6(_) => AddressFamily::Inet6,
+            };
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Destination(dst.into()));
+        }
+        if self.ext_flags != 0 {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::Other(DefaultNla::new(
+                    NDA_FLAGS_EXT,
+                    self.ext_flags.to_ne_bytes().to_vec(),
+                )));
+        }
+        if let Some(lladdr) = self.lladdr.as_ref() {
+            nl_msg
+                .attributes
+                .push(NeighbourAttribute::LinkLocalAddress(lladdr.clone()));
+        }
+        if let Some(protocol) = self.protocol {
+            nl_msg.attributes.push(NeighbourAttribute::Protocol(
+                RouteProtocol::from(protocol),
+            ));
+        }
+        if let Some(dev) = self.dev {
+            nl_msg.header.ifindex = ifname_to_index(ifnames, dev)?;
+        }
+        Ok(nl_msg)
+    }
+}
+
+/// Equal to iproute2 `ll_addr_a2n()`, the link layer address is hex bytes
+/// separated by colon or an IPv4 address for IP tunnels.
+pub(crate) fn parse_lladdr(value: &str) -> Result<Vec<u8>, CliError> {
+    let invalid_lladdr = |value: &str| {
+        CliError::from(format!("\"{value}\" is invalid lladdr.").as_str())
+    };
+
+    if value.contains('.') {
+        return value
+            .parse::<Ipv4Addr>()
+            .map(|addr| addr.octets().to_vec())
+            .map_err(|_| invalid_lladdr(value));
+    }
+
+    let mut ret = Vec::new();
+    for component in value.split(':').take(MAX_LLADDR_LEN) {
+        // Like `sscanf("%x")`, the trailing garbage is ignored
+        let digits = component
+            .strip_prefix("0x")
+            .or_else(|| component.strip_prefix("0X"))
+            .unwrap_or(component);
+        let end = digits
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(digits.len());
+        match u32::from_str_radix(&digits[..end], 16) {
+            Ok(byte) if byte <= u32::from(u8::MAX) => ret.push(byte as u8),
+            _ => return Err(invalid_lladdr(component)),
+        }
+    }
+    Ok(ret)
+}
+
+pub(crate) async fn handle_modify(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    cmd: NeighModifyCmd,
+) -> Result<(), CliError> {
+    let options = NeighModifyOptions::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+    let nl_msg = options.to_nl_msg(&ifnames)?;
+
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(match cmd {
+            NeighModifyCmd::Delete => RouteNetlinkMessage::DelNeighbour(nl_msg),
+            _ => RouteNetlinkMessage::NewNeighbour(nl_msg),
+        }),
+    );
+    request.header.flags = NLM_F_REQUEST
+        | NLM_F_ACK
+        | match cmd {
+            NeighModifyCmd::Add => NLM_F_CREATE | NLM_F_EXCL,
+            NeighModifyCmd::Change => NLM_F_REPLACE,
+            NeighModifyCmd::Replace => NLM_F_CREATE | NLM_F_REPLACE,
+            NeighModifyCmd::Delete => 0,
+        };
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::CliError;
+
+use crate::link::parse_num;
+
+pub(super) const NUD_INCOMPLETE: u16 = 0x01;
+pub(super) const NUD_REACHABLE: u16 = 0x02;
+pub(super) const NUD_STALE: u16 = 0x04;
+pub(super) const NUD_DELAY: u16 = 0x08;
+pub(super) const NUD_PROBE: u16 = 0x10;
+pub(super) const NUD_FAILED: u16 = 0x20;
+pub(super) const NUD_NOARP: u16 = 0x40;
+pub(super) const NUD_PERMANENT: u16 = 0x80;
+pub(super) const NUD_NONE: u16 = 0x00;
+
+pub(super) const NTF_USE: u8 = 0x01;
+pub(super) const NTF_PROXY: u8 = 0x08;
+pub(super) const NTF_EXT_LEARNED: u8 = 0x10;
+pub(super) const NTF_OFFLOADED: u8 = 0x20;
+pub(super) const NTF_ROUTER: u8 = 0x80;
+
+// The `NDA_FLAGS_EXT` is not supported by netlink-packet-route yet
+pub(super) const NDA_FLAGS_EXT: u16 = 15;
+pub(super) const NTF_EXT_MANAGED: u32 = 0x1;
+
+// Equal to the order of iproute2 `print_neigh_state()`
+const NUD_STATE_NAMES: [(u16, &str); 8] = [
+    (NUD_INCOMPLETE, "INCOMPLETE"),
+    (NUD_REACHABLE, "REACHABLE"),
+    (NUD_STALE, "STALE"),
+    (NUD_DELAY, "DELAY"),
+    (NUD_PROBE, "PROBE"),
+    (NUD_FAILED, "FAILED"),
+    (NUD_NOARP, "NOARP"),
+    (NUD_PERMANENT, "PERMANENT"),
+];
+
+/// Like iproute2 `print_neigh_state()`, the unknown bits are shown in hex.
+pub(super) fn nud_state_to_names(mut state: u16) -> Vec<String> {
+    let mut ret = Vec::new();
+    for (flag, name) in NUD_STATE_NAMES {
+        if state & flag != 0 {
+            state &= !flag;
+            ret.push(name.to_string());
+        }
+    }
+    if state != 0 {
+        ret.push(format!("{state:x}"));
+    }
+    ret
+}
+
+// Equal to iproute2 `nud_state_a2n()`
+pub(super) fn parse_nud_state(value: &str) -> Result<u16, CliError> {
+    let error_msg = "nud state is bad";
+    Ok(match value {
+        "permanent" => NUD_PERMANENT,
+        "reachable" => NUD_REACHABLE,
+        "noarp" => NUD_NOARP,
+        "none" => NUD_NONE,
+        "stale" => NUD_STALE,
+        "incomplete" => NUD_INCOMPLETE,
+        "delay" => NUD_DELAY,
+        "probe" => NUD_PROBE,
+        "failed" => NUD_FAILED,
+        _ => {
+            let state: u16 = parse_num(value, error_msg)?;
+            // Only single state below 0x100 is allowed
+            if state >= 0x100 || state & state.wrapping_sub(1) != 0 {
+                return Err(CliError::from(
+                    format!("argument \"{value}\" is wrong: {error_msg}")
+                        .as_str(),
+                ));
+            }
+            state
+        }
+    })
+}
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, mac_to_string, resolve_hostnames,
+    write_with_color,
+};
+use rtnetlink::{
+    IpVersion,
+    packet_core::Nla,
+    packet_route::{
+        AddressFamily,
+        neighbour::{NeighbourAddress, NeighbourAttribute, NeighbourMessage},
+    },
+};
+use serde::Serialize;
+
+use super::{
+    filter::NeighShowFilter,
+    names::{
+        NDA_FLAGS_EXT, NTF_EXT_LEARNED, NTF_EXT_MANAGED, NTF_OFFLOADED,
+        NTF_PROXY, NTF_ROUTER, nud_state_to_names,
+    },
+};
+use crate::{
+    CliError,
+    route::{get_ifnames, protocol_to_name},
+};
+
+// Like iproute2 `get_user_hz()`, the cache info is in `USER_HZ` which is
+// always 100 on Linux
+const USER_HZ: u32 = 100;
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliNeighInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dst: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lladdr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    router: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extern_learn: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offload: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    managed: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refcnt: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    used: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confirmed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    probes: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    state: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+    #[serde(skip)]
+    family: String,
+}
+
+// Equal to iproute2 `print_neigh()`
+impl std::fmt::Display for CliNeighInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(dst) = &self.dst {
+            write_with_color!(
+                f,
+                CliColor::address_color(&self.family),
+                "{dst}"
+            )?;
+            write!(f, " ")?;
+        }
+        if let Some(dev) = &self.dev {
+            write!(f, "dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{dev}")?;
+            write!(f, " ")?;
+        }
+        if let Some(lladdr) = &self.lladdr {
+            write!(f, "lladdr ")?;
+            write_with_color!(f, CliColor::Mac, "{lladdr}")?;
+            write!(f, " ")?;
+        }
+        for (flag, name) in [
+            (self.router, "router"),
+            (self.proxy, "proxy"),
+            (self.extern_learn, "extern_learn"),
+            (self.offload, "offload"),
+            (self.managed, "managed"),
+        ] {
+            if flag.is_some() {
+                write!(f, "{name} ")?;
+            }
+        }
+        if let Some(refcnt) = self.refcnt {
+            write!(f, " ref {refcnt}")?;
+        }
+        if let (Some(used), Some(confirmed), Some(updated)) =
+            (self.used, self.confirmed, self.updated)
+        {
+            write!(f, " used {used}/{confirmed}/{updated}")?;
+        }
+        if let Some(probes) = self.probes {
+            write!(f, "probes {probes} ")?;
+        }
+        for state in self.state.iter() {
+            write!(f, "{state} ")?;
+        }
+        if let Some(protocol) = &self.protocol {
+            write!(f, "proto {protocol} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliNeighInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliNeighInfo {}
+
+pub(super) fn neigh_addr_to_ip(addr: &NeighbourAddress) -> Option<IpAddr> {
+    match addr {
+        NeighbourAddress::Inet(v) => Some(IpAddr::V4(*v)),
+        NeighbourAddress::Inet6(v) => Some(IpAddr::V6(*v)),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_nl_msg_to_neigh(
+    nl_msg: NeighbourMessage,
+    filter: &NeighShowFilter,
+    ifnames: &HashMap<u32, String>,
+    numeric: bool,
+    show_stats: bool,
+) -> CliNeighInfo {
+    let header = &nl_msg.header;
+    let flags = header.flags.bits();
+
+    let mut ret = CliNeighInfo {
+        family: header.family.to_string(),
+        state: nud_state_to_names(u16::from(header.state)),
+        ..Default::default()
+    };
+    if !filter.hide_dev() && header.ifindex != 0 {
+        ret.dev = Some(
+            ifnames
+                .get(&header.ifindex)
+                .cloned()
+                .unwrap_or_else(|| format!("if{}", header.ifindex)),
+        );
+    }
+    for (mask, value) in [
+        (NTF_ROUTER, &mut ret.router),
+        (NTF_PROXY, &mut ret.proxy),
+        (NTF_EXT_LEARNED, &mut ret.extern_learn),
+        (NTF_OFFLOADED, &mut ret.offload),
+    ] {
+        if flags & mask != 0 {
+            *value = Some(());
+        }
+    }
+
+    for nla in nl_msg.attributes.iter() {
+        match nla {
+            NeighbourAttribute::Destination(addr) => {
+                ret.dst = Some(match neigh_addr_to_ip(addr) {
+                    Some(ip) => ip.to_string(),
+                    None => match addr {
+                        NeighbourAddress::Other(v) => mac_to_string(v),
+                        _ => String::new(),
+                    },
+                });
+            }
+            NeighbourAttribute::LinkLocalAddress(v) => {
+                ret.lladdr = Some(mac_to_string(v));
+            }
+            NeighbourAttribute::CacheInfo(info) if show_stats => {
+                if info.refcnt != 0 {
+                    ret.refcnt = Some(info.refcnt);
+                }
+                ret.used = Some(info.used / USER_HZ);
+                ret.confirmed = Some(info.confirmed / USER_HZ);
+                ret.updated = Some(info.updated / USER_HZ);
+            }
+            NeighbourAttribute::Probes(v) if show_stats => {
+                ret.probes = Some(*v);
+            }
+            NeighbourAttribute::Protocol(v) => {
+                let protocol = u8::from(*v);
+                if protocol != 0 {
+                    ret.protocol = Some(protocol_to_name(protocol, numeric));
+                }
+            }
+            NeighbourAttribute::Other(nla) if nla.kind() == NDA_FLAGS_EXT => {
+                let mut buf = [0u8; 4];
+                if nla.value_len() == buf.len() {
+                    nla.emit_value(&mut buf);
+                    if u32::from_ne_bytes(buf) & NTF_EXT_MANAGED != 0 {
+                        ret.managed = Some(());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    ret
+}
+
+/// Dump the neighbours matching the family, device and proxy flag of
+/// the filter, the rest of filter is not applied.
+pub(super) async fn dump_neighbours(
+    handle: &rtnetlink::Handle,
+    filter: &NeighShowFilter<'_>,
+) -> Result<Vec<NeighbourMessage>, CliError> {
+    let mut request = handle.neighbours().get();
+    match filter.family {
+        AddressFamily::Inet => request = request.set_family(IpVersion::V4),
+        AddressFamily::Inet6 => request = request.set_family(IpVersion::V6),
+        _ => (),
+    }
+    if filter.proxy {
+        request = request.proxies();
+    }
+    // Like iproute2 `ipneigh_dump_filter()`, let kernel filter on the
+    // device and master
+    if let Some(index) = filter.index {
+        request
+            .message_mut()
+            .attributes
+            .push(NeighbourAttribute::IfIndex(index));
+    }
+    if let Some(index) = filter.master_index {
+        request
+            .message_mut()
+            .attributes
+            .push(NeighbourAttribute::Controller(index));
+    }
+
+    let mut ret = Vec::new();
+    let mut neighbours = request.execute();
+    while let Some(nl_msg) = neighbours.try_next().await? {
+        ret.push(nl_msg);
+    }
+    Ok(ret)
+}
+
+// Like iproute2 `format_host()`, replace the IP address with hostname if
+// resolved.
+pub(super) async fn resolve_neigh_hostnames(neighs: &mut [CliNeighInfo]) {
+    let ips: Vec<IpAddr> = neighs
+        .iter()
+        .filter_map(|neigh| neigh.dst.as_deref()?.parse().ok())
+        .collect();
+
+    let hostnames = resolve_hostnames(&ips).await;
+
+    for neigh in neighs.iter_mut() {
+        if let Some(name) = neigh
+            .dst
+            .as_deref()
+            .and_then(|ip| hostnames.get(&ip.parse::<IpAddr>().ok()?))
+        {
+            neigh.dst = Some(name.clone());
+        }
+    }
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    numeric: bool,
+    resolve: bool,
+    show_stats: bool,
+) -> Result<Vec<CliNeighInfo>, CliError> {
+    let mut filter = NeighShowFilter::parse(opts, family, false)?;
+    let ifnames = get_ifnames(handle).await?;
+    filter.resolve_dev(&ifnames)?;
+
+    let mut ret: Vec<CliNeighInfo> = dump_neighbours(handle, &filter)
+        .await?
+        .into_iter()
+        .filter(|nl_msg| filter.matches(nl_msg))
+        .map(|nl_msg| {
+            parse_nl_msg_to_neigh(
+                nl_msg, &filter, &ifnames, numeric, show_stats,
+            )
+        })
+        .collect();
+
+    if resolve {
+        resolve_neigh_hostnames(&mut ret).await;
+    }
+    Ok(ret)
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+const FLUSH_TEST_ENTRIES: [&[&str]; 6] = [
+    &["198.18.50.2", "lladdr", "00:23:45:67:89:1a"],
+    &["198.18.50.3", "lladdr", "00:23:45:67:89:1b", "nud", "stale"],
+    &["198.18.50.4", "lladdr", "00:23:45:67:89:1c", "nud", "noarp"],
+    &["198.18.50.5", "nud", "failed"],
+    &["198.18.50.6", "lladdr", "00:23:45:67:89:1d", "nud", "none"],
+    &[
+        "2001:db8:50::2",
+        "lladdr",
+        "00:23:45:67:89:1e",
+        "nud",
+        "stale",
+    ],
+];
+
+fn add_neighbours(veth_name: &str) {
+    for args in FLUSH_TEST_ENTRIES {
+        exec_cmd(
+            &[&["ip", "neigh", "add"], args, &["dev", veth_name]].concat(),
+        );
+    }
+}
+
+// Like iproute2, `nud all` does not include the `NOARP` entries
+fn remove_neighbours(veth_name: &str) {
+    exec_cmd(&[
+        "ip", "neigh", "flush", "dev", veth_name, "nud", "all", "nud", "noarp",
+    ]);
+}
+
+// Flush the neighbours by iproute2 and by us, both the output of flush and
+// the remaining entries should be identical.
+fn assert_neigh_flush(veth_name: &str, args: &[&str]) {
+    let show_args = ["ip", "neigh", "show", "dev", veth_name, "nud", "all"];
+
+    add_neighbours(veth_name);
+    let expected_flush_output =
+        exec_cmd(&[&["ip"], args, &["dev", veth_name]].concat());
+    let expected_output = exec_cmd(&show_args);
+    remove_neighbours(veth_name);
+
+    add_neighbours(veth_name);
+    let our_flush_output =
+        ip_rs_exec_cmd(&[args, &["dev", veth_name]].concat());
+    let our_output = exec_cmd(&show_args);
+    remove_neighbours(veth_name);
+
+    pretty_assertions::assert_eq!(expected_flush_output, our_flush_output);
+    pretty_assertions::assert_eq!(expected_output, our_output);
+}
+
+#[test]
+fn test_neigh_flush() {
+    let veth_name = "ntest-veth2";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["neigh", "flush"][..],
+            &["-s", "neigh", "flush"][..],
+            &["-s", "-s", "neigh", "flush", "nud", "all"][..],
+            &["-4", "neigh", "flush"][..],
+            &["-6", "neigh", "flush"][..],
+            &["neigh", "flush", "nud", "permanent"][..],
+            &["neigh", "flush", "nud", "stale", "nud", "none"][..],
+            &["neigh", "flush", "to", "198.18.50.0/30"][..],
+            &["neigh", "flush", "2001:db8:50::/64"][..],
+            &["-s", "neigh", "flush", "nud", "reachable"][..],
+        ] {
+            assert_neigh_flush(veth_name, args);
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_neigh_flush_invalid_args() {
+    for (args, error_msg) in [
+        (&[][..], "Flush requires arguments."),
+        (
+            &["nud", "foo"][..],
+            "argument \"foo\" is wrong: nud state is bad",
+        ),
+        (
+            &["dev", "ntest-absent0"][..],
+            "Cannot find device \"ntest-absent0\"",
+        ),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["neigh", "flush"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_neigh_get() {
+    let veth_name = "ntest-veth3";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["198.18.50.2", "lladdr", "00:23:45:67:89:1a", "nud", "noarp"][..],
+            &[
+                "198.18.50.3",
+                "lladdr",
+                "00:23:45:67:89:1b",
+                "proto",
+                "static",
+            ][..],
+            &["2001:db8:50::2", "lladdr", "00:23:45:67:89:1c"][..],
+            &["proxy", "198.18.50.9"][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "neigh", "add"], args, &["dev", veth_name]].concat(),
+            );
+        }
+
+        for args in [
+            &["neigh", "get", "198.18.50.2", "dev", veth_name][..],
+            &["neigh", "get", "to", "198.18.50.3", "dev", veth_name][..],
+            &["-s", "neigh", "get", "198.18.50.3", "dev", veth_name][..],
+            &["neigh", "get", "2001:db8:50::2", "dev", veth_name][..],
+            &["neigh", "get", "proxy", "198.18.50.9", "dev", veth_name][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // Like iproute2, kernel fails the query on absent entry
+        for args in [
+            &["neigh", "get", "198.18.50.5", "dev", veth_name][..],
+            &["neigh", "get", "198.18.50.9", "dev", veth_name][..],
+            &["neigh", "get", "proxy", "198.18.50.9"][..],
+        ] {
+            assert!(!ip_rs_exec_cmd_output(args).status.success());
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_neigh_get_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["198.18.50.2"][..],
+            "Device and address are required arguments.",
+        ),
+        (
+            &["dev", "lo"][..],
+            "Device and address are required arguments.",
+        ),
+        (
+            &["198.18.50.2", "198.18.50.3", "dev", "lo"][..],
+            "either \"to\" is duplicate, or \"198.18.50.3\" is a garbage.",
+        ),
+        (
+            &["foo", "dev", "lo"][..],
+            "any valid address is expected rather than \"foo\".",
+        ),
+        (
+            &["198.18.50.2", "dev", "ntest-absent0"][..],
+            "Cannot find device \"ntest-absent0\"",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["neigh", "get"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod flush;
+#[cfg(test)]
+mod get;
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use crate::tests::exec_cmd;
+
+// Unlike dummy interface, veth supports ARP which is required by the
+// neighbour states
+fn setup_veth(veth_name: &str) {
+    let peer_name = format!("{veth_name}p");
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", veth_name, "up"]);
+    exec_cmd(&["ip", "addr", "add", "198.18.50.1/24", "dev", veth_name]);
+}
+
+fn cleanup_veth(veth_name: &str) {
+    exec_cmd(&["ip", "link", "del", veth_name]);
+}
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+// Modify the neighbour by iproute2 and by us, the outputs of
+// `ip neigh show` should be identical.
+fn assert_neigh_modify(veth_name: &str, cmds: &[(&str, &[&str])]) {
+    let show_args = ["ip", "-j", "neigh", "show", "dev", veth_name];
+    let flush_args = ["ip", "neigh", "flush", "dev", veth_name, "nud", "all"];
+    let flush_proxy_args = ["ip", "neigh", "flush", "proxy", "dev", veth_name];
+
+    for (cmd, args) in cmds {
+        exec_cmd(&[&["ip", "neigh", cmd], *args, &["dev", veth_name]].concat());
+    }
+    let expected_output = exec_cmd(&show_args);
+    let expected_proxy_output =
+        exec_cmd(&["ip", "neigh", "show", "proxy", "dev", veth_name]);
+    exec_cmd(&flush_args);
+    exec_cmd(&flush_proxy_args);
+
+    for (cmd, args) in cmds {
+        let our_output = ip_rs_exec_cmd(
+            &[&["neigh", cmd], *args, &["dev", veth_name]].concat(),
+        );
+        assert!(our_output.is_empty());
+    }
+    let our_output = exec_cmd(&show_args);
+    let our_proxy_output =
+        exec_cmd(&["ip", "neigh", "show", "proxy", "dev", veth_name]);
+    exec_cmd(&flush_args);
+    exec_cmd(&flush_proxy_args);
+
+    pretty_assertions::assert_eq!(expected_output, our_output);
+    pretty_assertions::assert_eq!(expected_proxy_output, our_proxy_output);
+}
+
+#[test]
+fn test_neigh_modify() {
+    let veth_name = "ntest-veth1";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        for cmds in [
+            &[("add", &["198.18.50.2", "lladdr", "00:23:45:67:89:1a"][..])][..],
+            &[(
+                "add",
+                &[
+                    "to",
+                    "198.18.50.3",
+                    "lladdr",
+                    "0:23:45:67:89:b",
+                    "nud",
+                    "stale",
+                    "router",
+                ][..],
+            )][..],
+            &[(
+                "add",
+                &[
+                    "198.18.50.4",
+                    "lladdr",
+                    "00:23:45:67:89:1c",
+                    "extern_learn",
+                    "proto",
+                    "static",
+                ][..],
+            )][..],
+            &[("add", &["198.18.50.5", "nud", "failed"][..])][..],
+            &[("add", &["198.18.50.6", "managed"][..])][..],
+            &[(
+                "add",
+                &["2001:db8:50::2", "lladdr", "00:23:45:67:89:1e"][..],
+            )][..],
+            &[("add", &["proxy", "198.18.50.9"][..])][..],
+            &[
+                ("add", &["198.18.50.7", "lladdr", "00:23:45:67:89:1f"][..]),
+                (
+                    "change",
+                    &["198.18.50.7", "lladdr", "00:23:45:67:89:20"][..],
+                ),
+            ][..],
+            &[
+                (
+                    "replace",
+                    &["198.18.50.8", "lladdr", "00:23:45:67:89:21"][..],
+                ),
+                (
+                    "replace",
+                    &[
+                        "198.18.50.8",
+                        "lladdr",
+                        "00:23:45:67:89:22",
+                        "nud",
+                        "reachable",
+                    ][..],
+                ),
+            ][..],
+            &[
+                ("add", &["198.18.50.10", "lladdr", "00:23:45:67:89:23"][..]),
+                ("del", &["198.18.50.10"][..]),
+            ][..],
+        ] {
+            assert_neigh_modify(veth_name, cmds);
+        }
+
+        // Like iproute2, `change` and `delete` fail on absent entry
+        for cmd in ["change", "delete"] {
+            let output = ip_rs_exec_cmd_output(&[
+                "neigh",
+                cmd,
+                "198.18.50.11",
+                "lladdr",
+                "00:23:45:67:89:24",
+                "dev",
+                veth_name,
+            ]);
+            assert!(!output.status.success());
+            assert!(
+                String::from_utf8_lossy(&output.stderr)
+                    .contains("RTNETLINK answers:")
+            );
+        }
+
+        // Adding existing entry should fail
+        let args = [
+            "neigh",
+            "add",
+            "198.18.50.12",
+            "lladdr",
+            "00:23:45:67:89:25",
+            "dev",
+            veth_name,
+        ];
+        assert!(ip_rs_exec_cmd(&args).is_empty());
+        let output = ip_rs_exec_cmd_output(&args);
+        assert!(!output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stderr)
+                .contains("RTNETLINK answers: File exists")
+        );
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_neigh_modify_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["198.18.50.2", "lladdr", "00:zz", "dev", "lo"][..],
+            "\"zz\" is invalid lladdr.",
+        ),
+        (
+            &[
+                "198.18.50.2",
+                "lladdr",
+                "00:11",
+                "lladdr",
+                "00:22",
+                "dev",
+                "lo",
+            ][..],
+            "duplicate \"lladdr\": \"00:22\" is the second value.",
+        ),
+        (
+            &["198.18.50.2", "198.18.50.3", "dev", "lo"][..],
+            "either \"to\" is duplicate, or \"198.18.50.3\" is a garbage.",
+        ),
+        (
+            &["198.18.50.2"][..],
+            "Device and destination are required arguments.",
+        ),
+        (
+            &["dev", "lo"][..],
+            "Device and destination are required arguments.",
+        ),
+        (
+            &["foo", "dev", "lo"][..],
+            "any valid address is expected rather than \"foo\".",
+        ),
+        (
+            &["198.18.50.2", "nud", "foo", "dev", "lo"][..],
+            "argument \"foo\" is wrong: nud state is bad",
+        ),
+        (
+            &["198.18.50.2", "proto", "foo", "dev", "lo"][..],
+            "argument \"foo\" is wrong: \"protocol\" value is invalid",
+        ),
+        (
+            &["198.18.50.2", "dev", "ntest-absent0"][..],
+            "Cannot find device \"ntest-absent0\"",
+        ),
+        (&["198.18.50.2", "dev"][..], "Command line is not complete"),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["neigh", "add"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
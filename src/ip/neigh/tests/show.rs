@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_neigh_show() {
+    let veth_name = "ntest-veth0";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &[
+                "198.18.50.2",
+                "lladdr",
+                "00:23:45:67:89:1a",
+                "nud",
+                "permanent",
+            ][..],
+            &[
+                "198.18.50.3",
+                "lladdr",
+                "00:23:45:67:89:1b",
+                "nud",
+                "stale",
+                "router",
+            ][..],
+            &["198.18.50.4", "lladdr", "00:23:45:67:89:1c", "extern_learn"][..],
+            &[
+                "198.18.50.5",
+                "lladdr",
+                "00:23:45:67:89:1d",
+                "proto",
+                "static",
+            ][..],
+            &["198.18.50.6", "nud", "failed"][..],
+            &[
+                "2001:db8:50::2",
+                "lladdr",
+                "00:23:45:67:89:1e",
+                "nud",
+                "reachable",
+            ][..],
+            &["proxy", "198.18.50.9"][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "neigh", "add"], args, &["dev", veth_name]].concat(),
+            );
+        }
+
+        for args in [
+            &["neigh", "show", "dev", veth_name][..],
+            &["neighbour", "show", "dev", veth_name][..],
+            &["-4", "neigh", "show", "dev", veth_name][..],
+            &["-6", "neigh", "show", "dev", veth_name][..],
+            &["-j", "neigh", "show", "dev", veth_name][..],
+            &["neigh", "show", "198.18.50.0/29"][..],
+            &["neigh", "show", "to", "2001:db8:50::/64"][..],
+            &["-j", "neigh", "show", "198.18.50.0/24"][..],
+            &["neigh", "show", "dev", veth_name, "nud", "stale"][..],
+            &[
+                "neigh",
+                "show",
+                "dev",
+                veth_name,
+                "nud",
+                "permanent",
+                "nud",
+                "failed",
+            ][..],
+            &["neigh", "show", "dev", veth_name, "proto", "static"][..],
+            &["neigh", "show", "dev", veth_name, "unused"][..],
+            &["-N", "neigh", "show", "198.18.50.0/24"][..],
+            &["neigh", "show", "proxy", "dev", veth_name][..],
+            &["-s", "neigh", "show", "dev", veth_name, "nud", "permanent"][..],
+            &["-s", "-j", "neigh", "show", "dev", veth_name][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_neigh_show_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["nud", "foo"][..],
+            "argument \"foo\" is wrong: nud state is bad",
+        ),
+        (
+            &["nud", "0x3"][..],
+            "argument \"0x3\" is wrong: nud state is bad",
+        ),
+        (
+            &["dev", "ntest-absent0"][..],
+            "Cannot find device \"ntest-absent0\"",
+        ),
+        (
+            &["to", "foo"][..],
+            "any valid prefix is expected rather than \"foo\".",
+        ),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["neigh", "show"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
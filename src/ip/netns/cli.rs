@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput};
+use serde::Serialize;
+
+use super::{
+    exec::handle_exec,
+    identify::{handle_identify, handle_pids},
+    list::{CliNetnsInfo, handle_list},
+    modify::{handle_add, handle_attach, handle_delete},
+    nsid::handle_set,
+};
+use crate::{CliError, context::NetlinkContext};
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliNetnsOutput {
+    Full(Vec<CliNetnsInfo>),
+    /// Name of the network namespace found by `ip netns identify`
+    Names(Vec<String>),
+    /// Process IDs found by `ip netns pids`
+    Pids(Vec<u32>),
+}
+
+impl CanDisplay for CliNetnsOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Full(netnses) => netnses.gen_string(),
+            Self::Names(names) => names.join("\n"),
+            Self::Pids(pids) => pids
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Full(netnses) => netnses.gen_oneline_string(),
+            _ => self.gen_string(),
+        }
+    }
+}
+
+impl CanOutput for CliNetnsOutput {}
+
+pub(crate) struct NetnsCommand;
+
+impl NetnsCommand {
+    pub(crate) const CMD: &'static str = "netns";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("network namespace management")
+            .alias("netn")
+            .alias("net")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("list")
+                    .about("list named network namespaces")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("l")
+                    .alias("show")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("lst"),
+            )
+            .subcommand(
+                clap::Command::new("add")
+                    .about("create a named network namespace")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete a named network namespace")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("exec")
+                    .about("run command in a named network namespace")
+                    .alias("exe")
+                    .alias("ex")
+                    .alias("e")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .allow_hyphen_values(true)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("attach")
+                    .about("attach the network namespace of process to name")
+                    .alias("attac")
+                    .alias("atta")
+                    .alias("att")
+                    .alias("at")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("set")
+                    .about("assign the netns id of a named network namespace")
+                    .alias("se")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .allow_hyphen_values(true)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("identify")
+                    .about("show the network namespace name of process")
+                    .alias("identif")
+                    .alias("identi")
+                    .alias("ident")
+                    .alias("iden")
+                    .alias("ide")
+                    .alias("id")
+                    .alias("i")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("pids")
+                    .about("show processes in a named network namespace")
+                    .alias("pid")
+                    .alias("pi")
+                    .alias("p")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliNetnsOutput>, CliError> {
+        let handle = ctx.handle();
+        match matches.subcommand() {
+            Some(("add", matches)) => {
+                handle_add(&get_opts(matches))?;
+                Ok(None)
+            }
+            Some(("delete", matches)) => {
+                handle_delete(&get_opts(matches))?;
+                Ok(None)
+            }
+            Some(("attach", matches)) => {
+                handle_attach(&get_opts(matches))?;
+                Ok(None)
+            }
+            Some(("set", matches)) => {
+                handle_set(handle, &get_opts(matches)).await?;
+                Ok(None)
+            }
+            Some(("exec", matches)) => {
+                handle_exec(&get_opts(matches))?;
+                Ok(None)
+            }
+            Some(("identify", matches)) => handle_identify(&get_opts(matches))
+                .map(CliNetnsOutput::Names)
+                .map(Some),
+            Some(("pids", matches)) => handle_pids(&get_opts(matches))
+                .map(CliNetnsOutput::Pids)
+                .map(Some),
+            _ => handle_list(handle)
+                .await
+                .map(|netnses| netnses.map(CliNetnsOutput::Full)),
+        }
+    }
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
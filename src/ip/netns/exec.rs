@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+
+use std::{ffi::CString, io::Write};
+
+use iproute_rs::CliError;
+use nix::{
+    errno::Errno,
+    mount::{MntFlags, MsFlags, mount, umount2},
+    sched::{CloneFlags, setns, unshare},
+    sys::statvfs::{FsFlags, statvfs},
+    unistd::execvp,
+};
+
+use super::modify::{get_netns_name, get_netns_path};
+
+/// Equal to iproute2 `NETNS_ETC_DIR`
+const NETNS_ETC_DIR: &str = "/etc/netns";
+
+/// Equal to iproute2 `netns_switch()`, move current thread into the named
+/// network namespace with `/sys` and per-netns configure files of
+/// `/etc/netns/<name>/` mounted in a new mount namespace.
+fn netns_switch(name: &str) -> Result<(), CliError> {
+    let netns = std::fs::File::open(get_netns_path(name)).map_err(|e| {
+        CliError::from(
+            format!(
+                "Cannot open network namespace \"{name}\": {}",
+                Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+            )
+            .as_str(),
+        )
+    })?;
+    setns(&netns, CloneFlags::CLONE_NEWNET).map_err(|e| {
+        CliError::from(
+            format!(
+                "setting the network namespace \"{name}\" failed: {}",
+                e.desc()
+            )
+            .as_str(),
+        )
+    })?;
+
+    unshare(CloneFlags::CLONE_NEWNS).map_err(|e| {
+        CliError::from(format!("unshare failed: {}", e.desc()).as_str())
+    })?;
+    // Do not let any mounts propagate back to the parent
+    mount(
+        Some(""),
+        "/",
+        Some("none"),
+        MsFlags::MS_SLAVE | MsFlags::MS_REC,
+        None::<&str>,
+    )
+    .map_err(|e| {
+        CliError::from(
+            format!("\"mount --make-rslave /\" failed: {}", e.desc()).as_str(),
+        )
+    })?;
+
+    // Mount a version of /sys that describes the network namespace
+    let mut mount_flags = MsFlags::empty();
+    if umount2("/sys", MntFlags::MNT_DETACH).is_err()
+        && let Ok(stat) = statvfs("/sys")
+        && stat.flags().contains(FsFlags::ST_RDONLY)
+    {
+        // Read-only sysfs can not be shadowed with a read-write one
+        mount_flags = MsFlags::MS_RDONLY;
+    }
+    mount(Some(name), "/sys", Some("sysfs"), mount_flags, None::<&str>)
+        .map_err(|e| {
+            CliError::from(
+                format!("mount of /sys failed: {}", e.desc()).as_str(),
+            )
+        })?;
+
+    bind_etc(name);
+    Ok(())
+}
+
+/// Equal to iproute2 `bind_etc()`, bind mount files like `resolv.conf` in
+/// `/etc/netns/<name>/` over the ones in `/etc/`. Like iproute2, failure is
+/// reported without stopping the command.
+fn bind_etc(name: &str) {
+    let Ok(entries) = std::fs::read_dir(format!("{NETNS_ETC_DIR}/{name}"))
+    else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let netns_name = entry.path();
+        let etc_name = std::path::Path::new("/etc").join(entry.file_name());
+        if let Err(e) = mount(
+            Some(&netns_name),
+            &etc_name,
+            Some("none"),
+            MsFlags::MS_BIND,
+            None::<&str>,
+        ) {
+            writeln!(
+                std::io::stderr(),
+                "Bind {} -> {} failed: {}",
+                netns_name.display(),
+                etc_name.display(),
+                e.desc()
+            )
+            .ok();
+        }
+    }
+}
+
+/// Replace current process with the command, only return on failure
+pub(crate) fn exec_cmd(cmd: &[&str]) -> Result<(), CliError> {
+    let args = cmd
+        .iter()
+        .map(|arg| CString::new(*arg))
+        .collect::<Result<Vec<CString>, _>>()
+        .map_err(|e| CliError::from(format!("{e}").as_str()))?;
+    let Err(e) = execvp(&args[0], &args);
+    Err(CliError::from(
+        format!("exec of \"{}\" failed: {}", cmd[0], e.desc()).as_str(),
+    ))
+}
+
+/// Equal to iproute2 `netns_exec()`
+pub(crate) fn handle_exec(opts: &[&str]) -> Result<(), CliError> {
+    let name = get_netns_name(opts)?;
+    let cmd = &opts[1..];
+    if cmd.is_empty() {
+        return Err(CliError::from("No command specified"));
+    }
+    netns_switch(name)?;
+    exec_cmd(cmd)
+}
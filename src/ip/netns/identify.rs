@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+
+use std::os::unix::fs::MetadataExt;
+
+use iproute_rs::CliError;
+use nix::errno::Errno;
+
+use super::{
+    NETNS_RUN_DIR,
+    modify::{get_netns_name, get_netns_path},
+};
+
+/// The device and inode number identifying a network namespace
+fn get_netns_stat(path: &str) -> Result<(u64, u64), CliError> {
+    let io_err_to_cli = |msg: &str, e: std::io::Error| {
+        CliError::from(
+            format!(
+                "{msg}: {}",
+                Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+            )
+            .as_str(),
+        )
+    };
+    let file = std::fs::File::open(path)
+        .map_err(|e| io_err_to_cli("Cannot open network namespace", e))?;
+    let meta = file
+        .metadata()
+        .map_err(|e| io_err_to_cli("Stat of netns failed", e))?;
+    Ok((meta.dev(), meta.ino()))
+}
+
+fn is_same_netns(path: &str, netns_stat: (u64, u64)) -> bool {
+    std::fs::metadata(path)
+        .map(|meta| (meta.dev(), meta.ino()) == netns_stat)
+        .unwrap_or_default()
+}
+
+/// Equal to iproute2 `netns_identify()`, find the name of the network
+/// namespace the process is in, current process is used if PID omitted.
+pub(crate) fn handle_identify(opts: &[&str]) -> Result<Vec<String>, CliError> {
+    let pid = match opts {
+        [] => "self",
+        [pid] => {
+            if pid.parse::<u32>().is_err() {
+                return Err(CliError::from(
+                    format!("Specified string '{pid}' is not a pid").as_str(),
+                ));
+            }
+            pid
+        }
+        _ => return Err(CliError::from("extra arguments specified")),
+    };
+    let netns_stat = get_netns_stat(&format!("/proc/{pid}/ns/net"))?;
+
+    let entries = match std::fs::read_dir(NETNS_RUN_DIR) {
+        Ok(entries) => entries,
+        // Like iproute2, treat a missing directory as an empty directory
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // Like iproute2, only the last matching name is shown
+    let mut name = None;
+    for entry in entries {
+        let entry = entry?;
+        if is_same_netns(&entry.path().to_string_lossy(), netns_stat) {
+            name = Some(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    Ok(name.into_iter().collect())
+}
+
+/// Equal to iproute2 `netns_pids()`, list the processes in the named network
+/// namespace by comparing the inode of `/proc/<pid>/ns/net`.
+pub(crate) fn handle_pids(opts: &[&str]) -> Result<Vec<u32>, CliError> {
+    let name = get_netns_name(opts)?;
+    if opts.len() > 1 {
+        return Err(CliError::from("extra arguments specified"));
+    }
+    let netns_stat = get_netns_stat(&get_netns_path(name))?;
+
+    let entries = std::fs::read_dir("/proc").map_err(|e| {
+        CliError::from(
+            format!(
+                "Open of /proc failed: {}",
+                Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+            )
+            .as_str(),
+        )
+    })?;
+
+    let mut ret = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        if is_same_netns(&format!("/proc/{pid}/ns/net"), netns_stat) {
+            ret.push(pid);
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, os::fd::AsRawFd};
+
+use futures_util::future::try_join_all;
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use serde::Serialize;
+
+use super::{NETNS_RUN_DIR, nsid::get_netns_id_from_fd};
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliNetnsInfo {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+}
+
+impl std::fmt::Display for CliNetnsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(id) = self.id {
+            write!(f, " (id: {id})")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliNetnsInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliNetnsInfo {}
+
+/// Equal to iproute2 `netns_list()`, `None` is returned when
+/// `/run/netns` does not exist.
+pub(crate) async fn handle_list(
+    handle: &rtnetlink::Handle,
+) -> Result<Option<Vec<CliNetnsInfo>>, CliError> {
+    let entries = match std::fs::read_dir(NETNS_RUN_DIR) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut netnses = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        netnses.push((name, std::fs::File::open(entry.path()).ok()));
+    }
+
+    // Query the ids concurrently over the same connection instead of waiting
+    // a netlink round-trip per netns. The files are kept open until all
+    // replies are received as kernel resolves the netns by fd.
+    let ids = try_join_all(netnses.iter().map(|(_, file)| {
+        let mut handle = handle.clone();
+        async move {
+            let Some(file) = file.as_ref() else {
+                return Ok(None);
+            };
+            get_netns_id_from_fd(&mut handle, file.as_raw_fd() as u32).await
+        }
+    }))
+    .await?;
+
+    Ok(Some(
+        netnses
+            .into_iter()
+            .zip(ids)
+            .map(|((name, _), id)| CliNetnsInfo {
+                name,
+                // Like iproute2, the netns without id assigned is listed
+                // without id
+                id: id.filter(|id| *id >= 0),
+            })
+            .collect(),
+    ))
+}
+
+/// Map the assigned netns ids to the names in `/run/netns` like iproute2
+/// `netns_map_init()`.
+pub(crate) async fn get_netns_names(
+    handle: &rtnetlink::Handle,
+) -> Result<HashMap<i32, String>, CliError> {
+    Ok(handle_list(handle)
+        .await?
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|netns| Some((netns.id?, netns.name)))
+        .collect())
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod exec;
+mod identify;
+mod list;
+mod modify;
+mod nsid;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::NetnsCommand, exec::exec_cmd,
+    identify::handle_identify as identify_netns, list::get_netns_names,
+};
+
+/// Equal to iproute2 `NETNS_RUN_DIR`
+const NETNS_RUN_DIR: &str = "/run/netns";
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::CliError;
+use nix::{
+    errno::Errno,
+    fcntl::OFlag,
+    mount::{MntFlags, MsFlags, mount, umount2},
+    sched::{CloneFlags, unshare},
+    sys::stat::Mode,
+    unistd::{close, mkdir, unlink},
+};
+
+use super::NETNS_RUN_DIR;
+
+/// Get the netns name from the first argument, invalid name is rejected like
+/// iproute2 `invalid_name()`.
+pub(super) fn get_netns_name<'a>(
+    opts: &[&'a str],
+) -> Result<&'a str, CliError> {
+    let Some(name) = opts.first() else {
+        return Err(CliError::from("No netns name specified"));
+    };
+    if name.contains('/') || *name == "." || *name == ".." {
+        return Err(CliError::from(
+            format!("Invalid netns name \"{name}\"").as_str(),
+        ));
+    }
+    Ok(name)
+}
+
+pub(super) fn get_netns_path(name: &str) -> String {
+    format!("{NETNS_RUN_DIR}/{name}")
+}
+
+/// Equal to iproute2 `create_netns_dir()`
+fn create_netns_dir() -> Result<(), CliError> {
+    match mkdir(
+        NETNS_RUN_DIR,
+        Mode::S_IRWXU
+            | Mode::S_IRGRP
+            | Mode::S_IXGRP
+            | Mode::S_IROTH
+            | Mode::S_IXOTH,
+    ) {
+        Ok(()) | Err(Errno::EEXIST) => Ok(()),
+        Err(e) => Err(CliError::from(
+            format!("mkdir {NETNS_RUN_DIR} failed: {}", e.desc()).as_str(),
+        )),
+    }
+}
+
+/// Make the mounts of `/run/netns` propagate between mount namespaces, the
+/// folder is bind mounted to itself if it is not a mount point yet.
+fn make_netns_dir_shared() -> Result<(), CliError> {
+    let mut made_mount = false;
+    loop {
+        match mount(
+            None::<&str>,
+            NETNS_RUN_DIR,
+            Some("none"),
+            MsFlags::MS_SHARED | MsFlags::MS_REC,
+            None::<&str>,
+        ) {
+            Ok(()) => return Ok(()),
+            Err(Errno::EINVAL) if !made_mount => {
+                mount(
+                    Some(NETNS_RUN_DIR),
+                    NETNS_RUN_DIR,
+                    Some("none"),
+                    MsFlags::MS_BIND | MsFlags::MS_REC,
+                    None::<&str>,
+                )
+                .map_err(|e| {
+                    CliError::from(
+                        format!(
+                            "mount --bind {NETNS_RUN_DIR} {NETNS_RUN_DIR} \
+                             failed: {}",
+                            e.desc()
+                        )
+                        .as_str(),
+                    )
+                })?;
+                made_mount = true;
+            }
+            Err(e) => {
+                return Err(CliError::from(
+                    format!(
+                        "mount --make-shared {NETNS_RUN_DIR} failed: {}",
+                        e.desc()
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+    }
+}
+
+fn bind_netns(proc_path: &str, netns_path: &str) -> Result<(), CliError> {
+    mount(
+        Some(proc_path),
+        netns_path,
+        Some("none"),
+        MsFlags::MS_BIND,
+        None::<&str>,
+    )
+    .map_err(|e| {
+        CliError::from(
+            format!("Bind {proc_path} -> {netns_path} failed: {}", e.desc())
+                .as_str(),
+        )
+    })
+}
+
+/// Create a new network namespace and bind mount it to `netns_path`.
+///
+/// The network namespace is per-thread, hence the namespace is created in a
+/// dedicated thread to leave the netns of the netlink socket untouched.
+fn bind_new_netns(netns_path: &str) -> Result<(), CliError> {
+    let netns_path = netns_path.to_string();
+    std::thread::spawn(move || {
+        unshare(CloneFlags::CLONE_NEWNET).map_err(|e| {
+            CliError::from(
+                format!(
+                    "Failed to create a new network namespace \
+                     \"{netns_path}\": {}",
+                    e.desc()
+                )
+                .as_str(),
+            )
+        })?;
+        bind_netns("/proc/thread-self/ns/net", &netns_path)
+    })
+    .join()
+    .unwrap_or_else(|_| {
+        Err(CliError::from("Failed to create a new network namespace"))
+    })
+}
+
+/// Equal to iproute2 `netns_add()`, new network namespace is created when
+/// `pid` is not defined, otherwise the netns of that process is attached.
+fn netns_add(name: &str, pid: Option<u32>) -> Result<(), CliError> {
+    let netns_path = get_netns_path(name);
+
+    create_netns_dir()?;
+    make_netns_dir_shared()?;
+
+    // Create the file as mount point of the network namespace
+    let fd = nix::fcntl::open(
+        netns_path.as_str(),
+        OFlag::O_RDONLY | OFlag::O_CREAT | OFlag::O_EXCL,
+        Mode::empty(),
+    )
+    .map_err(|e| {
+        CliError::from(
+            format!(
+                "Cannot create namespace file \"{netns_path}\": {}",
+                e.desc()
+            )
+            .as_str(),
+        )
+    })?;
+    close(fd).ok();
+
+    let result = match pid {
+        Some(pid) => bind_netns(&format!("/proc/{pid}/ns/net"), &netns_path),
+        None => bind_new_netns(&netns_path),
+    };
+    if let Err(e) = result {
+        // Like iproute2, remove the half created namespace
+        delete_netns(&netns_path).ok();
+        return Err(e);
+    }
+    Ok(())
+}
+
+pub(crate) fn handle_add(opts: &[&str]) -> Result<(), CliError> {
+    netns_add(get_netns_name(opts)?, None)
+}
+
+pub(crate) fn handle_attach(opts: &[&str]) -> Result<(), CliError> {
+    if opts.len() < 2 {
+        return Err(CliError::from("No netns name and PID specified"));
+    }
+    let name = get_netns_name(opts)?;
+    let Ok(pid) = opts[1].parse::<u32>() else {
+        return Err(CliError::from(
+            format!("Invalid PID: {}", opts[1]).as_str(),
+        ));
+    };
+    netns_add(name, Some(pid))
+}
+
+/// Equal to iproute2 `on_netns_del()`
+fn delete_netns(netns_path: &str) -> Result<(), CliError> {
+    // The lazy unmount detaches the namespace even when it is still in use
+    // by processes, failure here means it is not mounted at all, which is
+    // left to `unlink()` to report.
+    umount2(netns_path, MntFlags::MNT_DETACH).ok();
+    // The `EBUSY` is expected when the mount point is still referred in
+    // other mount namespace without mount propagation.
+    unlink(netns_path).map_err(|e| {
+        CliError::from(
+            format!(
+                "Cannot remove namespace file \"{netns_path}\": {}",
+                e.desc()
+            )
+            .as_str(),
+        )
+    })
+}
+
+pub(crate) fn handle_delete(opts: &[&str]) -> Result<(), CliError> {
+    delete_netns(&get_netns_path(get_netns_name(opts)?))
+}
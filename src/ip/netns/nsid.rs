@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MIT
+
+use std::os::fd::AsRawFd;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::CliError;
+use nix::errno::Errno;
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        RouteNetlinkMessage,
+        nsid::{NsidAttribute, NsidMessage},
+    },
+};
+
+use super::modify::{get_netns_name, get_netns_path};
+use crate::link::parse_num;
+
+/// Try to resolve a netns id of the netns file descriptor using rtnetlink.
+/// Kernel replies `-1` for netns without id assigned.
+pub(crate) async fn get_netns_id_from_fd(
+    handle: &mut rtnetlink::Handle,
+    fd: u32,
+) -> Result<Option<i32>, CliError> {
+    let mut nsid_msg = NsidMessage::default();
+    nsid_msg.attributes.push(NsidAttribute::Fd(fd));
+    let mut nsid_req = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::GetNsId(nsid_msg)),
+    );
+    nsid_req.header.flags = NLM_F_REQUEST;
+
+    let mut netns = handle.request(nsid_req)?;
+
+    if let Some(msg) = netns.next().await {
+        let NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewNsId(payload)) =
+            msg.payload
+        else {
+            return Ok(None);
+        };
+        for attr in payload.attributes {
+            if let NsidAttribute::Id(id) = attr {
+                return Ok(Some(id));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+// Equal to iproute2 `get_netnsid_from_str()`, `auto` means kernel should
+// allocate one.
+fn parse_nsid(value: &str) -> Result<i32, CliError> {
+    if value == "auto" {
+        return Ok(-1);
+    }
+    let nsid: i32 = parse_num(value, "Invalid \"netnsid\" value")?;
+    if nsid < 0 {
+        return Err(CliError::from(
+            format!(
+                "argument \"{value}\" is wrong: \"netnsid\" value should be \
+                 >= 0"
+            )
+            .as_str(),
+        ));
+    }
+    Ok(nsid)
+}
+
+/// Equal to iproute2 `netns_set()`
+pub(crate) async fn handle_set(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let name = get_netns_name(opts)?;
+    let Some(nsid) = opts.get(1) else {
+        return Err(CliError::from("No nsid specified"));
+    };
+    let nsid = parse_nsid(nsid)?;
+
+    let netns = std::fs::File::open(get_netns_path(name)).map_err(|e| {
+        CliError::from(
+            format!(
+                "Cannot open network namespace \"{name}\": {}",
+                Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+            )
+            .as_str(),
+        )
+    })?;
+
+    let mut nsid_msg = NsidMessage::default();
+    nsid_msg
+        .attributes
+        .push(NsidAttribute::Fd(netns.as_raw_fd() as u32));
+    nsid_msg.attributes.push(NsidAttribute::Id(nsid));
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewNsId(nsid_msg)),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
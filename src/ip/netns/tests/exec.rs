@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_netns_exec() {
+    let name = "nstest-exec0";
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", name]);
+
+    let result = std::panic::catch_unwind(|| {
+        exec_cmd(&[
+            "ip",
+            "-n",
+            name,
+            "link",
+            "add",
+            "nstest-veth0",
+            "type",
+            "veth",
+            "peer",
+            "name",
+            "nstest-veth0p",
+        ]);
+
+        for args in [
+            &["netns", "exec", name, "ip", "-br", "link"][..],
+            &["netns", "exec", name, "ls", "/sys/class/net"][..],
+            &["netns", "exec", name, "ip", "-j", "-d", "link"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The exit code of command is preserved
+        let output = ip_rs_exec_cmd_output(&[
+            "netns", "exec", name, "sh", "-c", "exit 3",
+        ]);
+        assert_eq!(output.status.code(), Some(3));
+    });
+
+    exec_cmd(&["ip", "netns", "del", name]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_netns_exec_etc_files() {
+    let name = "nstest-exec2";
+    let etc_dir = format!("/etc/netns/{name}");
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", name]);
+    std::fs::create_dir_all(&etc_dir).unwrap();
+    std::fs::write(
+        format!("{etc_dir}/resolv.conf"),
+        "nameserver 198.51.100.53\n",
+    )
+    .unwrap();
+    std::fs::write(format!("{etc_dir}/hosts"), "198.51.100.1 nstest-host\n")
+        .unwrap();
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["netns", "exec", name, "cat", "/etc/resolv.conf"][..],
+            &["netns", "exec", name, "cat", "/etc/hosts"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        assert_eq!(
+            ip_rs_exec_cmd(&["netns", "exec", name, "cat", "/etc/hosts"]),
+            "198.51.100.1 nstest-host\n"
+        );
+        // The bind mounts are not propagated back to current mount namespace
+        assert!(
+            !std::fs::read_to_string("/etc/hosts")
+                .unwrap()
+                .contains("nstest-host")
+        );
+    });
+
+    std::fs::remove_dir_all(&etc_dir).ok();
+    // Only removed when no other netns has configure files
+    std::fs::remove_dir("/etc/netns").ok();
+    exec_cmd(&["ip", "netns", "del", name]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_netns_exec_invalid_args() {
+    let name = "nstest-exec1";
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", name]);
+
+    for (args, error_msg) in [
+        (&[][..], "No netns name specified"),
+        (&[name][..], "No command specified"),
+        (
+            &["nstest-absent0", "ls"][..],
+            "Cannot open network namespace \"nstest-absent0\": No such file \
+             or directory",
+        ),
+        (
+            &[name, "nstest-absent-cmd"][..],
+            "exec of \"nstest-absent-cmd\" failed: No such file or directory",
+        ),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["netns", "exec"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+
+    exec_cmd(&["ip", "netns", "del", name]);
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_netns_identify_and_pids() {
+    let name = "nstest-pids0";
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", name]);
+    exec_cmd(&["ip", "netns", "add", "nstest-pids1"]);
+
+    let mut child = std::process::Command::new("ip")
+        .args(["netns", "exec", name, "sleep", "30"])
+        .spawn()
+        .expect("Failed to spawn process in netns");
+
+    let result = std::panic::catch_unwind(|| {
+        // Wait the process to switch netns
+        let pid = child.id().to_string();
+        for _ in 0..50 {
+            if exec_cmd(&["ip", "netns", "pids", name]).contains(&pid) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        for args in [
+            &["netns", "pids", name][..],
+            &["netns", "pids", "nstest-pids1"][..],
+            &["netns", "identify", pid.as_str()][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        assert_eq!(
+            ip_rs_exec_cmd(&["netns", "pids", name]),
+            format!("{pid}\n")
+        );
+        assert_eq!(
+            ip_rs_exec_cmd(&["netns", "identify", pid.as_str()]),
+            format!("{name}\n")
+        );
+        // The iproute2 prints empty line when current process is not in
+        // named netns, we print nothing instead
+        assert_eq!(ip_rs_exec_cmd(&["netns", "identify"]), "");
+    });
+
+    child.kill().ok();
+    child.wait().ok();
+    exec_cmd(&["ip", "netns", "del", name]);
+    exec_cmd(&["ip", "netns", "del", "nstest-pids1"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_netns_identify_and_pids_invalid_args() {
+    for (args, error_msg) in [
+        (
+            &["identify", "abc"][..],
+            "Specified string 'abc' is not a pid",
+        ),
+        (&["identify", "1", "2"][..], "extra arguments specified"),
+        (&["pids"][..], "No netns name specified"),
+        (
+            &["pids", "nstest-absent0", "1"][..],
+            "extra arguments specified",
+        ),
+        (
+            &["pids", "nstest-absent0"][..],
+            "Cannot open network namespace: No such file or directory",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["netns"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, lock_net_test,
+};
+
+#[test]
+fn test_netns_list() {
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", "nstest-list0"]);
+    exec_cmd(&["ip", "netns", "add", "nstest-list1"]);
+    exec_cmd(&["ip", "netns", "set", "nstest-list1", "97"]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["netns"][..],
+            &["netns", "list"][..],
+            &["-j", "netns", "list"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        let our_output = ip_rs_exec_cmd(&["netns", "list"]);
+        assert!(our_output.contains("nstest-list1 (id: 97)\n"));
+        assert!(our_output.contains("nstest-list0\n"));
+
+        assert_alias_output(&["netns", "list"], &["netns", "show"]);
+        assert_alias_output(&["netns", "list"], &["net", "lst"]);
+    });
+
+    exec_cmd(&["ip", "netns", "del", "nstest-list0"]);
+    exec_cmd(&["ip", "netns", "del", "nstest-list1"]);
+    assert!(result.is_ok());
+}
+
+// The netns ids are queried concurrently, make sure every netns still gets
+// its own id.
+#[test]
+fn test_netns_list_many() {
+    let _lock = lock_net_test();
+    let names: Vec<String> =
+        (0..32).map(|i| format!("nstest-many{i}")).collect();
+    for (i, name) in names.iter().enumerate() {
+        exec_cmd(&["ip", "netns", "add", name]);
+        exec_cmd(&["ip", "netns", "set", name, &(200 + i).to_string()]);
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [&["netns", "list"][..], &["-j", "netns", "list"][..]] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    for name in names.iter() {
+        exec_cmd(&["ip", "netns", "del", name]);
+    }
+    assert!(result.is_ok());
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod exec;
+#[cfg(test)]
+mod identify;
+#[cfg(test)]
+mod list;
+#[cfg(test)]
+mod modify;
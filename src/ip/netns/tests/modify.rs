@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_netns_add_del() {
+    let name = "nstest-modify0";
+    let _lock = lock_net_test();
+
+    ip_rs_exec_cmd(&["netns", "add", name]);
+    let result = std::panic::catch_unwind(|| {
+        assert!(exec_cmd(&["ip", "netns", "list"]).contains(name));
+
+        // The new netns only contains the loopback interface
+        let links =
+            ip_rs_exec_cmd(&["netns", "exec", name, "ip", "-br", "link"]);
+        assert!(links.starts_with("lo "));
+        assert_eq!(links.lines().count(), 1);
+
+        let output = ip_rs_exec_cmd_output(&["netns", "add", name]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(&format!(
+            "Cannot create namespace file \"/run/netns/{name}\": File exists"
+        )));
+    });
+
+    ip_rs_exec_cmd(&["netns", "del", name]);
+    assert!(result.is_ok());
+    assert!(!exec_cmd(&["ip", "netns", "list"]).contains(name));
+}
+
+#[test]
+fn test_netns_attach() {
+    let name = "nstest-attach0";
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", "nstest-attach1"]);
+
+    let mut child = std::process::Command::new("ip")
+        .args(["netns", "exec", "nstest-attach1", "sleep", "30"])
+        .spawn()
+        .expect("Failed to spawn process in netns");
+
+    let result = std::panic::catch_unwind(|| {
+        // Wait the process to switch netns
+        let pid = child.id().to_string();
+        for _ in 0..50 {
+            if exec_cmd(&["ip", "netns", "pids", "nstest-attach1"])
+                .contains(&pid)
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        ip_rs_exec_cmd(&["netns", "attach", name, pid.as_str()]);
+        // Both names refer to the same netns holding the process
+        pretty_assertions::assert_eq!(
+            exec_cmd(&["ip", "netns", "pids", name]),
+            format!("{pid}\n")
+        );
+        ip_rs_exec_cmd(&["netns", "del", name]);
+
+        // The mount point is removed on failure
+        let output =
+            ip_rs_exec_cmd_output(&["netns", "attach", name, "99999999"]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(&format!(
+            "Bind /proc/99999999/ns/net -> /run/netns/{name} failed: No such \
+             file or directory"
+        )));
+        assert!(!exec_cmd(&["ip", "netns", "list"]).contains(name));
+    });
+
+    child.kill().ok();
+    child.wait().ok();
+    exec_cmd(&["ip", "netns", "del", "nstest-attach1"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_netns_set() {
+    let name = "nstest-set0";
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "netns", "add", name]);
+    exec_cmd(&["ip", "netns", "add", "nstest-set1"]);
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&["netns", "set", name, "98"]);
+        ip_rs_exec_cmd(&["netns", "set", "nstest-set1", "auto"]);
+
+        let output = exec_cmd(&["ip", "netns", "list"]);
+        assert!(output.contains(&format!("{name} (id: 98)\n")));
+        assert!(output.contains("nstest-set1 (id: "));
+
+        // Kernel refuses to change assigned nsid
+        let output = ip_rs_exec_cmd_output(&["netns", "set", name, "99"]);
+        assert!(!output.status.success());
+    });
+
+    exec_cmd(&["ip", "netns", "del", name]);
+    exec_cmd(&["ip", "netns", "del", "nstest-set1"]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_netns_modify_invalid_args() {
+    for (args, error_msg) in [
+        (&["add"][..], "No netns name specified"),
+        (&["delete"][..], "No netns name specified"),
+        (&["add", "nstest/0"][..], "Invalid netns name \"nstest/0\""),
+        (&["add", ".."][..], "Invalid netns name \"..\""),
+        (
+            &["attach", "nstest-attach0"][..],
+            "No netns name and PID specified",
+        ),
+        (&["attach", "nstest-attach0", "abc"][..], "Invalid PID: abc"),
+        (&["set"][..], "No netns name specified"),
+        (&["set", "nstest-set0"][..], "No nsid specified"),
+        (
+            &["set", "nstest-set0", "foo"][..],
+            "argument \"foo\" is wrong: Invalid \"netnsid\" value",
+        ),
+        (
+            &["set", "nstest-set0", "-5"][..],
+            "argument \"-5\" is wrong: \"netnsid\" value should be >= 0",
+        ),
+        (
+            &["set", "nstest-absent0", "5"][..],
+            "Cannot open network namespace \"nstest-absent0\": No such file \
+             or directory",
+        ),
+        (
+            &["del", "nstest-absent0"][..],
+            "Cannot remove namespace file \"/run/netns/nstest-absent0\": No \
+             such file or directory",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["netns"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
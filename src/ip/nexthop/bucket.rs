@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt, nl_request};
+use rtnetlink::{
+    packet_core::{NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::{AddressFamily, route::RouteFlags},
+};
+use serde::Serialize;
+
+use super::{
+    message::{
+        NexthopAttr, NexthopMessage, NexthopNetlinkMessage,
+        NexthopResBucketAttr,
+    },
+    show::{CliSeconds, invarg, parse_dev, parse_id, usage},
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_num,
+    route::{get_ifnames, route_flags_to_names},
+};
+
+// Equal to iproute2 `print_nh_res_bucket()`
+#[derive(Serialize, Default)]
+struct CliNexthopBucket {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idle_time: Option<CliSeconds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nhid: Option<u32>,
+}
+
+/// Equal to iproute2 `print_nexthop_bucket()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliNexthopBucketInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bucket: Option<CliNexthopBucket>,
+    flags: Vec<String>,
+}
+
+impl std::fmt::Display for CliNexthopBucketInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = self.id {
+            write!(f, "id {id} ")?;
+        }
+        if let Some(bucket) = &self.bucket {
+            if let Some(v) = bucket.index {
+                write!(f, "index {v} ")?;
+            }
+            if let Some(v) = bucket.idle_time {
+                write!(f, "idle_time {v} ")?;
+            }
+            if let Some(v) = bucket.nhid {
+                write!(f, "nhid {v} ")?;
+            }
+        }
+        for flag in &self.flags {
+            write!(f, "{flag} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliNexthopBucketInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliNexthopBucketInfo {}
+
+impl From<NexthopMessage> for CliNexthopBucketInfo {
+    fn from(msg: NexthopMessage) -> Self {
+        let mut ret = Self {
+            flags: route_flags_to_names(RouteFlags::from_bits_retain(
+                msg.header.flags,
+            )),
+            ..Default::default()
+        };
+        for attr in msg.attributes {
+            match attr {
+                NexthopAttr::Id(v) => ret.id = Some(v),
+                NexthopAttr::ResBucket(attrs) => {
+                    let mut bucket = CliNexthopBucket::default();
+                    for attr in attrs {
+                        match attr {
+                            NexthopResBucketAttr::Index(v) => {
+                                bucket.index = Some(v)
+                            }
+                            NexthopResBucketAttr::IdleTime(v) => {
+                                bucket.idle_time =
+                                    Some(CliSeconds::from_clock_t(v))
+                            }
+                            NexthopResBucketAttr::NhId(v) => {
+                                bucket.nhid = Some(v)
+                            }
+                            _ => (),
+                        }
+                    }
+                    ret.bucket = Some(bucket);
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// Equal to iproute2 `ipnh_bucket_list()` and `nh_dump_bucket_filter()`
+fn parse_bucket_filter(
+    opts: &[&str],
+    ifnames: &HashMap<u32, String>,
+) -> Result<Vec<NexthopAttr>, CliError> {
+    let mut ifindex = None;
+    let mut master = None;
+    let mut id = None;
+    let mut nhid = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "dev" => ifindex = Some(parse_dev(ifnames, next_opt(&mut opts)?)?),
+            // Like `ip neigh`, the VRF is treated as master device
+            "master" | "vrf" => {
+                master = Some(parse_dev(ifnames, next_opt(&mut opts)?)?)
+            }
+            "id" => id = Some(parse_id(next_opt(&mut opts)?)?),
+            "nhid" => {
+                nhid = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "invalid nexthop id value",
+                )?)
+            }
+            _ => return Err(invarg(opt, "")),
+        }
+    }
+
+    let mut ret = Vec::new();
+    if let Some(ifindex) = ifindex {
+        ret.push(NexthopAttr::Oif(ifindex));
+    }
+    if let Some(master) = master {
+        ret.push(NexthopAttr::Master(master));
+    }
+    if let Some(id) = id {
+        ret.push(NexthopAttr::Id(id));
+    }
+    if let Some(nhid) = nhid {
+        ret.push(NexthopAttr::ResBucket(vec![NexthopResBucketAttr::NhId(
+            nhid,
+        )]));
+    }
+    Ok(ret)
+}
+
+pub(crate) async fn handle_bucket_list(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<Vec<CliNexthopBucketInfo>, CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let mut msg = NexthopMessage::default();
+    msg.header.family = u8::from(family);
+    msg.attributes = parse_bucket_filter(opts, &ifnames)?;
+    Ok(nl_request(
+        ctx.proto()?.handle(),
+        NexthopNetlinkMessage::GetNexthopBucket(msg),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .map(|msg| msg.into_message().into())
+    .collect())
+}
+
+/// Equal to iproute2 `ipnh_bucket_get()`
+pub(crate) async fn handle_bucket_get(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<Vec<CliNexthopBucketInfo>, CliError> {
+    let mut id = None;
+    let mut index = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "id" => id = Some(parse_id(next_opt(&mut opts)?)?),
+            "index" => {
+                index = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "invalid bucket index value",
+                )?)
+            }
+            _ => return Err(usage()),
+        }
+    }
+    let (Some(id), Some(index)) = (id, index) else {
+        return Err(usage());
+    };
+
+    let mut msg = NexthopMessage::default();
+    msg.header.family = u8::from(family);
+    msg.attributes = vec![
+        NexthopAttr::Id(id),
+        NexthopAttr::ResBucket(vec![NexthopResBucketAttr::Index(index)]),
+    ];
+    Ok(nl_request(
+        ctx.proto()?.handle(),
+        NexthopNetlinkMessage::GetNexthopBucket(msg),
+        NLM_F_REQUEST,
+    )
+    .await?
+    .into_iter()
+    .map(|msg| msg.into_message().into())
+    .collect())
+}
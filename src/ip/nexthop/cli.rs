@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput};
+use serde::Serialize;
+
+use super::{
+    bucket::{CliNexthopBucketInfo, handle_bucket_get, handle_bucket_list},
+    modify::{NexthopModifyCmd, handle_flush, handle_modify},
+    show::{CliNexthopInfo, NexthopShowFilter, handle_get, handle_show},
+};
+use crate::{
+    CliError, context::NetlinkContext, family::get_family, route::get_ifnames,
+};
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliNexthopOutput {
+    Nexthops(Vec<CliNexthopInfo>),
+    Buckets(Vec<CliNexthopBucketInfo>),
+}
+
+impl CanDisplay for CliNexthopOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Nexthops(entries) => entries.gen_string(),
+            Self::Buckets(entries) => entries.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Nexthops(entries) => entries.gen_oneline_string(),
+            Self::Buckets(entries) => entries.gen_oneline_string(),
+        }
+    }
+}
+
+impl CanOutput for CliNexthopOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+fn gen_list_command() -> clap::Command {
+    clap::Command::new("list")
+        .alias("lis")
+        .alias("li")
+        .alias("l")
+        .alias("show")
+        .alias("sho")
+        .alias("sh")
+        .alias("s")
+        .alias("lst")
+        .arg(gen_opts_arg())
+}
+
+pub(crate) struct NexthopCommand;
+
+impl NexthopCommand {
+    pub(crate) const CMD: &'static str = "nexthop";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("nexthop object management")
+            .alias("nextho")
+            .alias("nexth")
+            .alias("next")
+            .alias("nex")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add nexthop")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("replace")
+                    .about("add or replace nexthop")
+                    .alias("replac")
+                    .alias("repla")
+                    .alias("repl")
+                    .alias("rep")
+                    .alias("re")
+                    .alias("r")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete nexthop")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(gen_list_command().about("list nexthops"))
+            .subcommand(
+                clap::Command::new("get")
+                    .about("get nexthop by ID")
+                    .alias("ge")
+                    .alias("g")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("flush")
+                    .about("flush nexthops")
+                    .alias("flus")
+                    .alias("flu")
+                    .alias("fl")
+                    .alias("f")
+                    .arg(gen_opts_arg()),
+            )
+            .subcommand(
+                clap::Command::new("bucket")
+                    .about("show buckets of resilient nexthop group")
+                    .alias("bucke")
+                    .alias("buck")
+                    .alias("buc")
+                    .alias("bu")
+                    .alias("b")
+                    .subcommand_required(false)
+                    .subcommand(
+                        gen_list_command().about("list nexthop buckets"),
+                    )
+                    .subcommand(
+                        clap::Command::new("get")
+                            .about("get nexthop bucket by ID and index")
+                            .alias("ge")
+                            .alias("g")
+                            .arg(gen_opts_arg()),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliNexthopOutput>, CliError> {
+        for (name, cmd) in [
+            ("add", NexthopModifyCmd::Add),
+            ("replace", NexthopModifyCmd::Replace),
+            ("delete", NexthopModifyCmd::Del),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                handle_modify(
+                    ctx,
+                    &get_opts(matches),
+                    cmd,
+                    get_family(matches),
+                )
+                .await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("bucket") {
+            return Self::handle_bucket(matches, ctx).await;
+        }
+
+        if let Some(matches) = matches.subcommand_matches("get") {
+            return handle_get(
+                ctx,
+                &get_opts(matches),
+                get_family(matches),
+                matches.get_flag("DETAILS"),
+                matches.get_flag("NUMERIC"),
+            )
+            .await
+            .map(CliNexthopOutput::Nexthops)
+            .map(Some);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("flush") {
+            let opts = get_opts(matches);
+            let filter = NexthopShowFilter::parse(
+                &opts,
+                &get_ifnames(ctx.handle()).await?,
+            )?;
+            handle_flush(
+                ctx,
+                &filter,
+                opts.is_empty(),
+                get_family(matches),
+                matches.get_count("STATS") > 0,
+                matches.get_flag("FORCE"),
+            )
+            .await?;
+            return Ok(None);
+        }
+
+        let (matches, opts) =
+            if let Some(matches) = matches.subcommand_matches("list") {
+                (matches, get_opts(matches))
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            ctx,
+            &opts,
+            get_family(matches),
+            matches.get_flag("DETAILS"),
+            matches.get_flag("NUMERIC"),
+        )
+        .await
+        .map(CliNexthopOutput::Nexthops)
+        .map(Some)
+    }
+
+    async fn handle_bucket(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliNexthopOutput>, CliError> {
+        if let Some(matches) = matches.subcommand_matches("get") {
+            return handle_bucket_get(
+                ctx,
+                &get_opts(matches),
+                get_family(matches),
+            )
+            .await
+            .map(CliNexthopOutput::Buckets)
+            .map(Some);
+        }
+
+        let (matches, opts) =
+            if let Some(matches) = matches.subcommand_matches("list") {
+                (matches, get_opts(matches))
+            } else {
+                (matches, Vec::new())
+            };
+        handle_bucket_list(ctx, &opts, get_family(matches))
+            .await
+            .map(CliNexthopOutput::Buckets)
+            .map(Some)
+    }
+}
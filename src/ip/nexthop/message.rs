@@ -0,0 +1,451 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use rtnetlink::{
+    packet_core::{
+        DecodeError, DefaultNla, Emitable, ErrorContext, NetlinkDeserializable,
+        NetlinkHeader, NetlinkSerializable, Nla, NlaBuffer, NlasIterator,
+        Parseable, parse_u16, parse_u32, parse_u64,
+    },
+    sys::protocols::NETLINK_ROUTE,
+};
+
+use crate::context::ProtoMessage;
+
+// Equal to `linux/rtnetlink.h`
+const RTM_NEWNEXTHOP: u16 = 104;
+const RTM_DELNEXTHOP: u16 = 105;
+const RTM_GETNEXTHOP: u16 = 106;
+const RTM_NEWNEXTHOPBUCKET: u16 = 116;
+const RTM_GETNEXTHOPBUCKET: u16 = 118;
+
+// Equal to `linux/nexthop.h`
+const NHA_ID: u16 = 1;
+const NHA_GROUP: u16 = 2;
+const NHA_GROUP_TYPE: u16 = 3;
+const NHA_BLACKHOLE: u16 = 4;
+const NHA_OIF: u16 = 5;
+const NHA_GATEWAY: u16 = 6;
+const NHA_ENCAP_TYPE: u16 = 7;
+const NHA_ENCAP: u16 = 8;
+const NHA_GROUPS: u16 = 9;
+const NHA_MASTER: u16 = 10;
+const NHA_FDB: u16 = 11;
+const NHA_RES_GROUP: u16 = 12;
+const NHA_RES_BUCKET: u16 = 13;
+
+const NHA_RES_GROUP_BUCKETS: u16 = 1;
+const NHA_RES_GROUP_IDLE_TIMER: u16 = 2;
+const NHA_RES_GROUP_UNBALANCED_TIMER: u16 = 3;
+const NHA_RES_GROUP_UNBALANCED_TIME: u16 = 4;
+
+const NHA_RES_BUCKET_INDEX: u16 = 1;
+const NHA_RES_BUCKET_IDLE_TIME: u16 = 2;
+const NHA_RES_BUCKET_NH_ID: u16 = 3;
+
+pub(crate) const NEXTHOP_GRP_TYPE_MPATH: u16 = 0;
+pub(crate) const NEXTHOP_GRP_TYPE_RES: u16 = 1;
+
+// The size of `struct nhmsg`
+const NHMSG_LEN: usize = 8;
+// The size of `struct nexthop_grp`
+const NEXTHOP_GRP_LEN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NexthopNetlinkMessage {
+    NewNexthop(NexthopMessage),
+    DelNexthop(NexthopMessage),
+    GetNexthop(NexthopMessage),
+    /// The resilient group bucket shares the `struct nhmsg` header
+    NewNexthopBucket(NexthopMessage),
+    GetNexthopBucket(NexthopMessage),
+}
+
+impl NexthopNetlinkMessage {
+    fn message(&self) -> &NexthopMessage {
+        match self {
+            Self::NewNexthop(msg)
+            | Self::DelNexthop(msg)
+            | Self::GetNexthop(msg)
+            | Self::NewNexthopBucket(msg)
+            | Self::GetNexthopBucket(msg) => msg,
+        }
+    }
+
+    pub(crate) fn into_message(self) -> NexthopMessage {
+        match self {
+            Self::NewNexthop(msg)
+            | Self::DelNexthop(msg)
+            | Self::GetNexthop(msg)
+            | Self::NewNexthopBucket(msg)
+            | Self::GetNexthopBucket(msg) => msg,
+        }
+    }
+}
+
+impl NetlinkSerializable for NexthopNetlinkMessage {
+    fn message_type(&self) -> u16 {
+        match self {
+            Self::NewNexthop(_) => RTM_NEWNEXTHOP,
+            Self::DelNexthop(_) => RTM_DELNEXTHOP,
+            Self::GetNexthop(_) => RTM_GETNEXTHOP,
+            Self::NewNexthopBucket(_) => RTM_NEWNEXTHOPBUCKET,
+            Self::GetNexthopBucket(_) => RTM_GETNEXTHOPBUCKET,
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.message().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        self.message().emit(buffer)
+    }
+}
+
+impl NetlinkDeserializable for NexthopNetlinkMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        let msg = NexthopMessage::parse(payload)?;
+        Ok(match header.message_type {
+            RTM_NEWNEXTHOP => Self::NewNexthop(msg),
+            RTM_DELNEXTHOP => Self::DelNexthop(msg),
+            RTM_GETNEXTHOP => Self::GetNexthop(msg),
+            RTM_NEWNEXTHOPBUCKET => Self::NewNexthopBucket(msg),
+            RTM_GETNEXTHOPBUCKET => Self::GetNexthopBucket(msg),
+            kind => {
+                return Err(DecodeError::from(format!(
+                    "Unknown nexthop message type {kind}"
+                )));
+            }
+        })
+    }
+}
+
+impl ProtoMessage for NexthopNetlinkMessage {
+    const PROTOCOL: isize = NETLINK_ROUTE;
+}
+
+/// Equal to kernel `struct nhmsg`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct NexthopHeader {
+    pub(crate) family: u8,
+    pub(crate) scope: u8,
+    pub(crate) protocol: u8,
+    /// The `RTNH_F_*` flags
+    pub(crate) flags: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NexthopMessage {
+    pub(crate) header: NexthopHeader,
+    pub(crate) attributes: Vec<NexthopAttr>,
+}
+
+impl NexthopMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < NHMSG_LEN {
+            return Err(DecodeError::from(format!(
+                "Invalid nhmsg length {}",
+                buf.len()
+            )));
+        }
+        let header = NexthopHeader {
+            family: buf[0],
+            scope: buf[1],
+            protocol: buf[2],
+            flags: parse_u32(&buf[4..NHMSG_LEN])?,
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(&buf[NHMSG_LEN..]) {
+            let nla = nla.context("invalid nexthop attribute")?;
+            attributes.push(NexthopAttr::parse(&nla)?);
+        }
+        Ok(Self { header, attributes })
+    }
+}
+
+impl Emitable for NexthopMessage {
+    fn buffer_len(&self) -> usize {
+        NHMSG_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.header.family;
+        buffer[1] = self.header.scope;
+        buffer[2] = self.header.protocol;
+        buffer[3] = 0;
+        buffer[4..NHMSG_LEN].copy_from_slice(&self.header.flags.to_ne_bytes());
+        self.attributes.as_slice().emit(&mut buffer[NHMSG_LEN..]);
+    }
+}
+
+/// Equal to kernel `struct nexthop_grp`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NexthopGroupEntry {
+    pub(crate) id: u32,
+    /// The weight in range of 1 to 256, stored as `weight - 1` in kernel
+    pub(crate) weight: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NexthopAttr {
+    Id(u32),
+    Group(Vec<NexthopGroupEntry>),
+    GroupType(u16),
+    Blackhole,
+    Oif(u32),
+    Gateway(IpAddr),
+    EncapType(u16),
+    /// The kernel places `NHA_ENCAP` ahead of `NHA_ENCAP_TYPE`, hence the
+    /// raw attributes are kept for decoding along with the type later.
+    Encap(Vec<u8>),
+    /// Dump filter for groups only
+    Groups,
+    Master(u32),
+    Fdb,
+    ResGroup(Vec<NexthopResGroupAttr>),
+    ResBucket(Vec<NexthopResBucketAttr>),
+    Other(DefaultNla),
+}
+
+impl Nla for NexthopAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Id(_) | Self::Oif(_) | Self::Master(_) => 4,
+            Self::Group(v) => v.len() * NEXTHOP_GRP_LEN,
+            Self::GroupType(_) | Self::EncapType(_) => 2,
+            Self::Blackhole | Self::Groups | Self::Fdb => 0,
+            Self::Gateway(IpAddr::V4(_)) => 4,
+            Self::Gateway(IpAddr::V6(_)) => 16,
+            Self::Encap(v) => v.len(),
+            Self::ResGroup(v) => v.as_slice().buffer_len(),
+            Self::ResBucket(v) => v.as_slice().buffer_len(),
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Id(_) => NHA_ID,
+            Self::Group(_) => NHA_GROUP,
+            Self::GroupType(_) => NHA_GROUP_TYPE,
+            Self::Blackhole => NHA_BLACKHOLE,
+            Self::Oif(_) => NHA_OIF,
+            Self::Gateway(_) => NHA_GATEWAY,
+            Self::EncapType(_) => NHA_ENCAP_TYPE,
+            Self::Encap(_) => NHA_ENCAP,
+            Self::Groups => NHA_GROUPS,
+            Self::Master(_) => NHA_MASTER,
+            Self::Fdb => NHA_FDB,
+            Self::ResGroup(_) => NHA_RES_GROUP,
+            Self::ResBucket(_) => NHA_RES_BUCKET,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Id(v) | Self::Oif(v) | Self::Master(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Group(entries) => {
+                for (entry, buf) in
+                    entries.iter().zip(buffer.chunks_mut(NEXTHOP_GRP_LEN))
+                {
+                    buf.fill(0);
+                    buf[..4].copy_from_slice(&entry.id.to_ne_bytes());
+                    buf[4] = entry.weight.saturating_sub(1) as u8;
+                }
+            }
+            Self::GroupType(v) | Self::EncapType(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Blackhole | Self::Groups | Self::Fdb => (),
+            Self::Gateway(IpAddr::V4(v)) => buffer.copy_from_slice(&v.octets()),
+            Self::Gateway(IpAddr::V6(v)) => buffer.copy_from_slice(&v.octets()),
+            Self::Encap(v) => buffer.copy_from_slice(v),
+            Self::ResGroup(v) => v.as_slice().emit(buffer),
+            Self::ResBucket(v) => v.as_slice().emit(buffer),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+
+    // Like iproute2 `addattr_nest()`
+    fn is_nested(&self) -> bool {
+        matches!(
+            self,
+            Self::Encap(_) | Self::ResGroup(_) | Self::ResBucket(_)
+        )
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for NexthopAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NHA_ID => Self::Id(parse_u32(payload)?),
+            NHA_GROUP => Self::Group(
+                payload
+                    .chunks_exact(NEXTHOP_GRP_LEN)
+                    .map(|entry| {
+                        Ok(NexthopGroupEntry {
+                            id: parse_u32(&entry[..4])?,
+                            weight: u16::from(entry[4]) + 1,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, DecodeError>>()?,
+            ),
+            NHA_GROUP_TYPE => Self::GroupType(parse_u16(payload)?),
+            NHA_BLACKHOLE => Self::Blackhole,
+            NHA_OIF => Self::Oif(parse_u32(payload)?),
+            NHA_GATEWAY => Self::Gateway(
+                if let Ok(octets) = <[u8; 4]>::try_from(payload) {
+                    IpAddr::V4(Ipv4Addr::from(octets))
+                } else if let Ok(octets) = <[u8; 16]>::try_from(payload) {
+                    IpAddr::V6(Ipv6Addr::from(octets))
+                } else {
+                    return Err(DecodeError::invalid_ip_address(payload.len()));
+                },
+            ),
+            NHA_ENCAP_TYPE => Self::EncapType(parse_u16(payload)?),
+            NHA_ENCAP => Self::Encap(payload.to_vec()),
+            NHA_GROUPS => Self::Groups,
+            NHA_MASTER => Self::Master(parse_u32(payload)?),
+            NHA_FDB => Self::Fdb,
+            NHA_RES_GROUP => {
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid nexthop resilient group")?;
+                    attrs.push(NexthopResGroupAttr::parse(&nla)?);
+                }
+                Self::ResGroup(attrs)
+            }
+            NHA_RES_BUCKET => {
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid nexthop bucket")?;
+                    attrs.push(NexthopResBucketAttr::parse(&nla)?);
+                }
+                Self::ResBucket(attrs)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `NHA_RES_GROUP`, the timers are in `clock_t`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NexthopResGroupAttr {
+    Buckets(u16),
+    IdleTimer(u32),
+    UnbalancedTimer(u32),
+    UnbalancedTime(u64),
+    Other(DefaultNla),
+}
+
+impl Nla for NexthopResGroupAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Buckets(_) => 2,
+            Self::IdleTimer(_) | Self::UnbalancedTimer(_) => 4,
+            Self::UnbalancedTime(_) => 8,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Buckets(_) => NHA_RES_GROUP_BUCKETS,
+            Self::IdleTimer(_) => NHA_RES_GROUP_IDLE_TIMER,
+            Self::UnbalancedTimer(_) => NHA_RES_GROUP_UNBALANCED_TIMER,
+            Self::UnbalancedTime(_) => NHA_RES_GROUP_UNBALANCED_TIME,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Buckets(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::IdleTimer(v) | Self::UnbalancedTimer(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::UnbalancedTime(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for NexthopResGroupAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NHA_RES_GROUP_BUCKETS => Self::Buckets(parse_u16(payload)?),
+            NHA_RES_GROUP_IDLE_TIMER => Self::IdleTimer(parse_u32(payload)?),
+            NHA_RES_GROUP_UNBALANCED_TIMER => {
+                Self::UnbalancedTimer(parse_u32(payload)?)
+            }
+            NHA_RES_GROUP_UNBALANCED_TIME => {
+                Self::UnbalancedTime(parse_u64(payload)?)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attribute of `NHA_RES_BUCKET`, the idle time is in `clock_t`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NexthopResBucketAttr {
+    Index(u16),
+    IdleTime(u64),
+    NhId(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for NexthopResBucketAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Index(_) => 2,
+            Self::IdleTime(_) => 8,
+            Self::NhId(_) => 4,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Index(_) => NHA_RES_BUCKET_INDEX,
+            Self::IdleTime(_) => NHA_RES_BUCKET_IDLE_TIME,
+            Self::NhId(_) => NHA_RES_BUCKET_NH_ID,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Index(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::IdleTime(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::NhId(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>>
+    for NexthopResBucketAttr
+{
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NHA_RES_BUCKET_INDEX => Self::Index(parse_u16(payload)?),
+            NHA_RES_BUCKET_IDLE_TIME => Self::IdleTime(parse_u64(payload)?),
+            NHA_RES_BUCKET_NH_ID => Self::NhId(parse_u32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
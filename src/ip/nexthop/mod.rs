@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+
+mod bucket;
+mod cli;
+mod message;
+mod modify;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::NexthopCommand;
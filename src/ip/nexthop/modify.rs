@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT
+
+use std::{io::Write, net::IpAddr};
+
+use iproute_rs::{CliError, next_opt, nl_request};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REPLACE, NLM_F_REQUEST,
+    },
+    packet_route::AddressFamily,
+};
+
+use super::{
+    message::{
+        NEXTHOP_GRP_TYPE_MPATH, NEXTHOP_GRP_TYPE_RES, NexthopAttr,
+        NexthopGroupEntry, NexthopMessage, NexthopNetlinkMessage,
+        NexthopResGroupAttr,
+    },
+    show::{NexthopShowFilter, dump_nexthops, invarg, parse_dev, parse_id},
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_num,
+    prefix::parse_ip_addr,
+    route::{RouteEncapOptions, get_ifnames, parse_protocol},
+};
+
+// Equal to `RTNH_F_ONLINK` of `linux/rtnetlink.h`
+const RTNH_F_ONLINK: u32 = 4;
+
+// Like iproute2 `parse_nh_group_type_res()`, the timers are passed in
+// `clock_t`
+const USER_HZ: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NexthopModifyCmd {
+    Add,
+    Replace,
+    Del,
+}
+
+impl NexthopModifyCmd {
+    fn flags(self) -> u16 {
+        match self {
+            Self::Add => NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
+            Self::Replace => {
+                NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE
+            }
+            Self::Del => NLM_F_REQUEST | NLM_F_ACK,
+        }
+    }
+}
+
+// Equal to iproute2 `add_nh_group_attr()`, the `GROUP` is
+// `id[,weight]/id[,weight]/...`
+fn parse_group(value: &str) -> Result<Vec<NexthopGroupEntry>, CliError> {
+    let mut ret = Vec::new();
+    for entry in value.split('/') {
+        let (id, weight) = match entry.split_once(',') {
+            Some((id, weight)) => (id, Some(weight)),
+            None => (entry, None),
+        };
+        let id = parse_num(id, "")
+            .map_err(|_| invarg(value, "\"group\" value is invalid"))?;
+        let weight = match weight {
+            Some(weight) => parse_num::<u16>(weight, "")
+                .ok()
+                .filter(|w| (1..=256).contains(w))
+                .ok_or_else(|| invarg(weight, "\"weight\" is invalid"))?,
+            None => 1,
+        };
+        ret.push(NexthopGroupEntry { id, weight });
+    }
+    Ok(ret)
+}
+
+// Equal to iproute2 `parse_nh_group_type_res()`, the arguments not
+// belonging to the resilient group are left in `opts`.
+fn parse_res_group(
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<Vec<NexthopResGroupAttr>, CliError> {
+    let mut ret = Vec::new();
+    while let Some(opt) = opts.as_slice().first() {
+        match *opt {
+            "buckets" => {
+                opts.next();
+                ret.push(NexthopResGroupAttr::Buckets(parse_num(
+                    next_opt(opts)?,
+                    "invalid buckets value",
+                )?));
+            }
+            "idle_timer" | "unbalanced_timer" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                let error_msg = if *opt == "idle_timer" {
+                    "invalid idle timer value"
+                } else {
+                    "invalid unbalanced timer value"
+                };
+                let timer = parse_num::<u32>(value, error_msg)?
+                    .checked_mul(USER_HZ)
+                    .ok_or_else(|| invarg(value, error_msg))?;
+                ret.push(if *opt == "idle_timer" {
+                    NexthopResGroupAttr::IdleTimer(timer)
+                } else {
+                    NexthopResGroupAttr::UnbalancedTimer(timer)
+                });
+            }
+            _ => break,
+        }
+    }
+    Ok(ret)
+}
+
+/// Equal to iproute2 `ipnh_modify()`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    cmd: NexthopModifyCmd,
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let mut msg = NexthopMessage::default();
+    let mut family = family;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "id" => msg
+                .attributes
+                .push(NexthopAttr::Id(parse_id(next_opt(&mut opts)?)?)),
+            "dev" => {
+                let name = next_opt(&mut opts)?;
+                let ifindex =
+                    parse_dev(&get_ifnames(ctx.handle()).await?, name)?;
+                msg.attributes.push(NexthopAttr::Oif(ifindex));
+                if family == AddressFamily::Unspec {
+                    family = AddressFamily::Inet;
+                }
+            }
+            "via" => {
+                let value = next_opt(&mut opts)?;
+                let addr = parse_ip_addr(value, family)?;
+                let addr_family = match addr {
+                    IpAddr::V4(_) => AddressFamily::Inet,
+                    IpAddr::V6(_) => AddressFamily::Inet6,
+                };
+                if family == AddressFamily::Unspec {
+                    family = addr_family;
+                } else if family != addr_family {
+                    return Err(invarg(value, "address family mismatch"));
+                }
+                msg.attributes.push(NexthopAttr::Gateway(addr));
+            }
+            "blackhole" => {
+                msg.attributes.push(NexthopAttr::Blackhole);
+                if family == AddressFamily::Unspec {
+                    family = AddressFamily::Inet;
+                }
+            }
+            "fdb" => msg.attributes.push(NexthopAttr::Fdb),
+            "onlink" => msg.header.flags |= RTNH_F_ONLINK,
+            "group" => msg
+                .attributes
+                .push(NexthopAttr::Group(parse_group(next_opt(&mut opts)?)?)),
+            "type" => {
+                let value = next_opt(&mut opts)?;
+                let kind = match value {
+                    "mpath" => NEXTHOP_GRP_TYPE_MPATH,
+                    "resilient" => NEXTHOP_GRP_TYPE_RES,
+                    _ => {
+                        return Err(invarg(value, "\"type\" value is invalid"));
+                    }
+                };
+                if kind == NEXTHOP_GRP_TYPE_RES {
+                    msg.attributes.push(NexthopAttr::ResGroup(
+                        parse_res_group(&mut opts)?,
+                    ));
+                }
+                msg.attributes.push(NexthopAttr::GroupType(kind));
+            }
+            "protocol" => {
+                msg.header.protocol = parse_protocol(
+                    next_opt(&mut opts)?,
+                    "\"protocol\" value is invalid",
+                )?;
+            }
+            "encap" => {
+                let (kind, encap) =
+                    RouteEncapOptions::parse(&mut opts)?.to_raw();
+                msg.attributes.push(NexthopAttr::EncapType(kind));
+                msg.attributes.push(NexthopAttr::Encap(encap));
+            }
+            _ => return Err(invarg(opt, "")),
+        }
+    }
+    msg.header.family = u8::from(family);
+
+    let nl_msg = if cmd == NexthopModifyCmd::Del {
+        NexthopNetlinkMessage::DelNexthop(msg)
+    } else {
+        NexthopNetlinkMessage::NewNexthop(msg)
+    };
+    nl_request(ctx.proto()?.handle(), nl_msg, cmd.flags()).await?;
+    Ok(())
+}
+
+// Like iproute2 `flush_nexthop()`, but the failure stops the flush unless
+// `force` is true, in which case it is reported and the flush continues.
+async fn flush_nexthops(
+    ctx: &NetlinkContext,
+    msgs: Vec<NexthopMessage>,
+    force: bool,
+) -> Result<usize, CliError> {
+    let conn = ctx.proto()?;
+    let mut flushed = 0;
+    for msg in msgs {
+        let Some(id) = msg.attributes.iter().find_map(|attr| match attr {
+            NexthopAttr::Id(id) => Some(*id),
+            _ => None,
+        }) else {
+            continue;
+        };
+        let mut del_msg = NexthopMessage::default();
+        del_msg.attributes.push(NexthopAttr::Id(id));
+        match nl_request(
+            conn.handle(),
+            NexthopNetlinkMessage::DelNexthop(del_msg),
+            NexthopModifyCmd::Del.flags(),
+        )
+        .await
+        {
+            Ok(_) => flushed += 1,
+            Err(e) => {
+                if !force {
+                    return Err(CliError::from(
+                        format!(
+                            "Failed to delete nexthop with id {id}: {}",
+                            e.msg
+                        )
+                        .as_str(),
+                    ));
+                }
+                writeln!(
+                    std::io::stderr(),
+                    "Failed to delete nexthop with id {id}"
+                )
+                .ok();
+            }
+        }
+    }
+    Ok(flushed)
+}
+
+/// Equal to iproute2 `ipnh_flush()`, when flushing all the groups are
+/// removed ahead of the nexthops they are referring to.
+pub(crate) async fn handle_flush(
+    ctx: &NetlinkContext,
+    filter: &NexthopShowFilter,
+    flush_all: bool,
+    family: AddressFamily,
+    show_stats: bool,
+    force: bool,
+) -> Result<(), CliError> {
+    let mut flushed = 0;
+    if flush_all {
+        flushed += flush_nexthops(
+            ctx,
+            dump_nexthops(ctx, &NexthopShowFilter::groups_only(), family)
+                .await?,
+            force,
+        )
+        .await?;
+    }
+    flushed +=
+        flush_nexthops(ctx, dump_nexthops(ctx, filter, family).await?, force)
+            .await?;
+
+    if show_stats {
+        if flushed == 0 {
+            writeln!(std::io::stdout(), "Nothing to flush").ok();
+        } else {
+            writeln!(std::io::stdout(), "Flushed {flushed} nexthops").ok();
+        }
+    }
+    Ok(())
+}
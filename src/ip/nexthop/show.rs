@@ -0,0 +1,515 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, nl_request,
+    write_with_color,
+};
+use rtnetlink::{
+    packet_core::{NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::{AddressFamily, route::RouteFlags},
+};
+use serde::Serialize;
+
+use super::message::{
+    NEXTHOP_GRP_TYPE_MPATH, NEXTHOP_GRP_TYPE_RES, NexthopAttr, NexthopMessage,
+    NexthopNetlinkMessage, NexthopResGroupAttr,
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_num,
+    route::{
+        CliRouteEncap, format_float, ifname_to_index, protocol_to_name,
+        route_flags_to_names, scope_to_name,
+    },
+};
+
+// Equal to `RT_SCOPE_UNIVERSE` and `RTPROT_UNSPEC`
+const RT_SCOPE_UNIVERSE: u8 = 0;
+const RTPROT_UNSPEC: u8 = 0;
+
+// The kernel reports the timers in `USER_HZ` which is 100 on all
+// architectures
+const USER_HZ: f64 = 100.0;
+
+const NEXTHOP_USAGE: &str = "\
+Usage: ip nexthop { list | flush } [ protocol ID ] SELECTOR
+       ip nexthop { add | replace } id ID NH [ protocol ID ]
+       ip nexthop { get | del } id ID
+       ip nexthop bucket list BUCKET_SELECTOR
+       ip nexthop bucket get id ID index INDEX
+SELECTOR := [ id ID ] [ dev DEV ] [ vrf NAME ] [ master DEV ]
+            [ groups ] [ fdb ]
+BUCKET_SELECTOR := SELECTOR | [ nhid ID ]
+NH := { blackhole | [ via ADDRESS ] [ dev DEV ] [ onlink ]
+        [ encap ENCAPTYPE ENCAPHDR ] |
+        group GROUP [ fdb ] [ type TYPE [ TYPE_ARGS ] ] }
+GROUP := [ <id[,weight]>/<id[,weight]>/... ]
+TYPE := { mpath | resilient }
+TYPE_ARGS := [ RESILIENT_ARGS ]
+RESILIENT_ARGS := [ buckets BUCKETS ] [ idle_timer IDLE ]
+                  [ unbalanced_timer UNBALANCED ]
+ENCAPTYPE := [ mpls ]
+ENCAPHDR := [ MPLSLABEL ]";
+
+pub(crate) fn usage() -> CliError {
+    CliError::from(NEXTHOP_USAGE)
+}
+
+pub(crate) fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+// Equal to iproute2 `ipnh_parse_id()`
+pub(crate) fn parse_id(value: &str) -> Result<u32, CliError> {
+    parse_num(value, "invalid id value")
+}
+
+// Like iproute2 `ll_name_to_index()`
+pub(crate) fn parse_dev(
+    ifnames: &HashMap<u32, String>,
+    name: &str,
+) -> Result<u32, CliError> {
+    ifname_to_index(ifnames, name)
+        .map_err(|_| invarg(name, "Device does not exist"))
+}
+
+/// The seconds converted from `clock_t`, shown by `%g` like iproute2
+/// `print_tv()` for both text and JSON.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CliSeconds(f64);
+
+impl CliSeconds {
+    pub(crate) fn from_clock_t(value: u64) -> Self {
+        Self(value as f64 / USER_HZ)
+    }
+}
+
+impl std::fmt::Display for CliSeconds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_float(self.0))
+    }
+}
+
+impl Serialize for CliSeconds {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let value = format_float(self.0);
+        match value.parse::<u64>() {
+            Ok(v) => serializer.serialize_u64(v),
+            Err(_) => serializer.serialize_f64(value.parse().unwrap_or(self.0)),
+        }
+    }
+}
+
+// Equal to iproute2 `print_nh_group()`, the weight is only shown when it is
+// not the default 1.
+#[derive(Serialize)]
+struct CliNexthopGroupEntry {
+    id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<u16>,
+}
+
+// Equal to iproute2 `print_nh_res_group()`
+#[derive(Serialize, Default)]
+struct CliNexthopResArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    idle_timer: Option<CliSeconds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unbalanced_timer: Option<CliSeconds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unbalanced_time: Option<CliSeconds>,
+}
+
+/// Equal to iproute2 `print_nexthop()`
+#[derive(Serialize, Default)]
+pub(crate) struct CliNexthopInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<Vec<CliNexthopGroupEntry>>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resilient_args: Option<CliNexthopResArgs>,
+    #[serde(flatten)]
+    encap: Option<CliRouteEncap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    /// Shown as JSON `null` like iproute2 `print_null()`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blackhole: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fdb: Option<()>,
+    #[serde(skip)]
+    family: String,
+}
+
+impl std::fmt::Display for CliNexthopInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(id) = self.id {
+            write!(f, "id {id} ")?;
+        }
+        if let Some(group) = &self.group {
+            write!(f, "group ")?;
+            for (i, entry) in group.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "/")?;
+                }
+                write!(f, "{}", entry.id)?;
+                if let Some(weight) = entry.weight {
+                    write!(f, ",{weight}")?;
+                }
+            }
+            write!(f, " ")?;
+        }
+        if let Some(kind) = &self.kind {
+            write!(f, "type {kind} ")?;
+        }
+        if let Some(args) = &self.resilient_args {
+            if let Some(v) = args.buckets {
+                write!(f, "buckets {v} ")?;
+            }
+            if let Some(v) = args.idle_timer {
+                write!(f, "idle_timer {v} ")?;
+            }
+            if let Some(v) = args.unbalanced_timer {
+                write!(f, "unbalanced_timer {v} ")?;
+            }
+            if let Some(v) = args.unbalanced_time {
+                write!(f, "unbalanced_time {v} ")?;
+            }
+        }
+        if let Some(encap) = &self.encap {
+            write!(f, "{encap}")?;
+        }
+        if let Some(gateway) = &self.gateway {
+            write!(f, "via ")?;
+            write_with_color!(
+                f,
+                CliColor::address_color(&self.family),
+                "{gateway}"
+            )?;
+            write!(f, " ")?;
+        }
+        if let Some(dev) = &self.dev {
+            write!(f, "dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{dev}")?;
+            write!(f, " ")?;
+        }
+        if let Some(scope) = &self.scope {
+            write!(f, "scope {scope} ")?;
+        }
+        if self.blackhole.is_some() {
+            write!(f, "blackhole ")?;
+        }
+        if let Some(protocol) = &self.protocol {
+            write!(f, "proto {protocol} ")?;
+        }
+        for flag in &self.flags {
+            write!(f, "{flag} ")?;
+        }
+        // Like iproute2, no trailing space after `fdb`
+        if self.fdb.is_some() {
+            write!(f, "fdb")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliNexthopInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliNexthopInfo {}
+
+fn family_to_name(family: u8) -> &'static str {
+    match AddressFamily::from(family) {
+        AddressFamily::Inet => "inet",
+        AddressFamily::Inet6 => "inet6",
+        _ => "",
+    }
+}
+
+impl CliNexthopInfo {
+    pub(crate) fn new(
+        msg: NexthopMessage,
+        ifnames: &HashMap<u32, String>,
+        include_details: bool,
+        numeric: bool,
+    ) -> Self {
+        let header = msg.header;
+        let mut ret = Self {
+            family: family_to_name(header.family).to_string(),
+            flags: route_flags_to_names(RouteFlags::from_bits_retain(
+                header.flags,
+            )),
+            ..Default::default()
+        };
+        if header.scope != RT_SCOPE_UNIVERSE || include_details {
+            ret.scope = Some(scope_to_name(header.scope, numeric));
+        }
+        if header.protocol != RTPROT_UNSPEC || include_details {
+            ret.protocol = Some(protocol_to_name(header.protocol, numeric));
+        }
+
+        let mut encap_type = None;
+        let mut encap = None;
+        for attr in msg.attributes {
+            match attr {
+                NexthopAttr::Id(v) => ret.id = Some(v),
+                NexthopAttr::Group(entries) => {
+                    ret.group = Some(
+                        entries
+                            .into_iter()
+                            .map(|e| CliNexthopGroupEntry {
+                                id: e.id,
+                                weight: (e.weight != 1).then_some(e.weight),
+                            })
+                            .collect(),
+                    );
+                }
+                NexthopAttr::GroupType(kind) => {
+                    // Like iproute2, the default multipath type is hidden
+                    ret.kind = match kind {
+                        NEXTHOP_GRP_TYPE_MPATH => None,
+                        NEXTHOP_GRP_TYPE_RES => Some("resilient".to_string()),
+                        _ => Some("<unknown type>".to_string()),
+                    };
+                }
+                NexthopAttr::ResGroup(attrs) => {
+                    let mut args = CliNexthopResArgs::default();
+                    for attr in attrs {
+                        match attr {
+                            NexthopResGroupAttr::Buckets(v) => {
+                                args.buckets = Some(v)
+                            }
+                            NexthopResGroupAttr::IdleTimer(v) => {
+                                args.idle_timer =
+                                    Some(CliSeconds::from_clock_t(v.into()))
+                            }
+                            NexthopResGroupAttr::UnbalancedTimer(v) => {
+                                args.unbalanced_timer =
+                                    Some(CliSeconds::from_clock_t(v.into()))
+                            }
+                            NexthopResGroupAttr::UnbalancedTime(v) => {
+                                args.unbalanced_time =
+                                    Some(CliSeconds::from_clock_t(v))
+                            }
+                            _ => (),
+                        }
+                    }
+                    ret.resilient_args = Some(args);
+                }
+                NexthopAttr::EncapType(v) => encap_type = Some(v),
+                NexthopAttr::Encap(v) => encap = Some(v),
+                NexthopAttr::Gateway(v) => ret.gateway = Some(v.to_string()),
+                NexthopAttr::Oif(v) => {
+                    ret.dev = Some(
+                        ifnames
+                            .get(&v)
+                            .cloned()
+                            .unwrap_or_else(|| format!("if{v}")),
+                    );
+                }
+                NexthopAttr::Blackhole => ret.blackhole = Some(()),
+                NexthopAttr::Fdb => ret.fdb = Some(()),
+                _ => (),
+            }
+        }
+        if let (Some(kind), Some(encap)) = (encap_type, encap) {
+            ret.encap = Some(CliRouteEncap::from_raw(kind, &encap));
+        }
+        ret
+    }
+}
+
+/// The `SELECTOR` of iproute2 `ipnh_list_flush()`
+#[derive(Debug, Default)]
+pub(crate) struct NexthopShowFilter {
+    pub(crate) id: Option<u32>,
+    ifindex: Option<u32>,
+    master: Option<u32>,
+    groups: bool,
+    fdb: bool,
+    protocol: Option<u8>,
+}
+
+impl NexthopShowFilter {
+    pub(crate) fn parse(
+        opts: &[&str],
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => {
+                    ret.ifindex =
+                        Some(parse_dev(ifnames, next_opt(&mut opts)?)?)
+                }
+                "groups" => ret.groups = true,
+                // Like `ip neigh`, the VRF is treated as master device
+                "master" | "vrf" => {
+                    ret.master = Some(parse_dev(ifnames, next_opt(&mut opts)?)?)
+                }
+                "id" => ret.id = Some(parse_id(next_opt(&mut opts)?)?),
+                "protocol" => {
+                    ret.protocol = Some(parse_num(
+                        next_opt(&mut opts)?,
+                        "invalid protocol value",
+                    )?)
+                }
+                "fdb" => ret.fdb = true,
+                _ => return Err(invarg(opt, "")),
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Only consider the groups when flushing all, like iproute2
+    /// `ipnh_flush()`.
+    pub(crate) fn groups_only() -> Self {
+        Self {
+            groups: true,
+            ..Default::default()
+        }
+    }
+
+    // Equal to iproute2 `nh_dump_filter()`
+    fn to_nlas(&self) -> Vec<NexthopAttr> {
+        let mut ret = Vec::new();
+        if let Some(ifindex) = self.ifindex {
+            ret.push(NexthopAttr::Oif(ifindex));
+        }
+        if self.groups {
+            ret.push(NexthopAttr::Groups);
+        }
+        if let Some(master) = self.master {
+            ret.push(NexthopAttr::Master(master));
+        }
+        if self.fdb {
+            ret.push(NexthopAttr::Fdb);
+        }
+        ret
+    }
+
+    // The protocol is filtered by user space like iproute2 `print_nexthop()`
+    fn matches(&self, msg: &NexthopMessage) -> bool {
+        if let Some(protocol) = self.protocol
+            && protocol != msg.header.protocol
+        {
+            return false;
+        }
+        if let Some(id) = self.id
+            && !msg.attributes.contains(&NexthopAttr::Id(id))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+pub(crate) async fn dump_nexthops(
+    ctx: &NetlinkContext,
+    filter: &NexthopShowFilter,
+    family: AddressFamily,
+) -> Result<Vec<NexthopMessage>, CliError> {
+    let mut msg = NexthopMessage::default();
+    msg.header.family = u8::from(family);
+    msg.attributes = filter.to_nlas();
+    Ok(nl_request(
+        ctx.proto()?.handle(),
+        NexthopNetlinkMessage::GetNexthop(msg),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .map(NexthopNetlinkMessage::into_message)
+    .filter(|msg| filter.matches(msg))
+    .collect())
+}
+
+// Equal to iproute2 `ipnh_get_id()`
+async fn get_nexthop(
+    ctx: &NetlinkContext,
+    id: u32,
+    family: AddressFamily,
+) -> Result<Vec<NexthopMessage>, CliError> {
+    let mut msg = NexthopMessage::default();
+    msg.header.family = u8::from(family);
+    msg.attributes.push(NexthopAttr::Id(id));
+    Ok(nl_request(
+        ctx.proto()?.handle(),
+        NexthopNetlinkMessage::GetNexthop(msg),
+        NLM_F_REQUEST,
+    )
+    .await?
+    .into_iter()
+    .map(NexthopNetlinkMessage::into_message)
+    .collect())
+}
+
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    include_details: bool,
+    numeric: bool,
+) -> Result<Vec<CliNexthopInfo>, CliError> {
+    let ifnames = crate::route::get_ifnames(ctx.handle()).await?;
+    let filter = NexthopShowFilter::parse(opts, &ifnames)?;
+    // Like iproute2, the `id` selector queries the single nexthop
+    let msgs = match filter.id {
+        Some(id) => get_nexthop(ctx, id, family).await?,
+        None => dump_nexthops(ctx, &filter, family).await?,
+    };
+    Ok(msgs
+        .into_iter()
+        .map(|msg| CliNexthopInfo::new(msg, &ifnames, include_details, numeric))
+        .collect())
+}
+
+/// Equal to iproute2 `ipnh_get()`
+pub(crate) async fn handle_get(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    include_details: bool,
+    numeric: bool,
+) -> Result<Vec<CliNexthopInfo>, CliError> {
+    let mut id = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "id" => id = Some(parse_id(next_opt(&mut opts)?)?),
+            _ => return Err(usage()),
+        }
+    }
+    let Some(id) = id else {
+        return Err(usage());
+    };
+    let ifnames = crate::route::get_ifnames(ctx.handle()).await?;
+    Ok(get_nexthop(ctx, id, family)
+        .await?
+        .into_iter()
+        .map(|msg| CliNexthopInfo::new(msg, &ifnames, include_details, numeric))
+        .collect())
+}
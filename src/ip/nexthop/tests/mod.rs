@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod nexthop;
+
+use crate::tests::{exec_cmd, lock_net_test};
+
+fn with_nexthop_iface<T>(dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
+    exec_cmd(&["ip", "addr", "add", "192.0.2.1/24", "dev", dummy_name]);
+    exec_cmd(&[
+        "ip",
+        "addr",
+        "add",
+        "2001:db8:1::1/64",
+        "dev",
+        dummy_name,
+        "nodad",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // The nexthops without device, e.g. `blackhole`, are not removed along
+    // with the interface
+    exec_cmd(&["ip", "nexthop", "flush"]);
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok());
+}
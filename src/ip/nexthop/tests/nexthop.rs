@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT
+
+use super::with_nexthop_iface;
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+};
+
+fn add_nexthops(dummy_name: &str) {
+    for args in [
+        &["id", "101", "via", "192.0.2.2", "dev", dummy_name][..],
+        &[
+            "id",
+            "102",
+            "via",
+            "2001:db8:1::2",
+            "dev",
+            dummy_name,
+            "onlink",
+        ][..],
+        &["id", "103", "blackhole"][..],
+        &["id", "104", "group", "101,5/103"][..],
+        &["id", "105", "dev", dummy_name, "protocol", "static"][..],
+        &["id", "106", "via", "192.0.2.3", "fdb"][..],
+        &["id", "107", "group", "106", "fdb"][..],
+    ] {
+        exec_cmd(&[&["ip", "nexthop", "add"], args].concat());
+    }
+}
+
+#[test]
+fn test_nexthop_show() {
+    let dummy_name = "nhtest-dummy0";
+
+    with_nexthop_iface(dummy_name, || {
+        add_nexthops(dummy_name);
+
+        for args in [&[][..], &["-d"][..], &["-j"][..], &["-d", "-j"][..]] {
+            for show_args in [
+                &[][..],
+                &["list", "dev", dummy_name][..],
+                &["list", "groups"][..],
+                &["list", "fdb"][..],
+                &["list", "protocol", "4"][..],
+                &["list", "id", "104"][..],
+                &["get", "id", "102"][..],
+            ] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["nexthop"], show_args].concat(),
+                );
+                let our_output =
+                    ip_rs_exec_cmd(&[args, &["nexthop"], show_args].concat());
+
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        let expected_output = exec_cmd(&["ip", "-4", "nexthop"]);
+        let our_output = ip_rs_exec_cmd(&["-4", "nexthop"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+
+        assert_alias_output(&["nexthop", "list"], &["nexthop", "show"]);
+        assert_alias_output(&["nexthop", "list"], &["nex", "lst"]);
+    });
+}
+
+#[test]
+fn test_nexthop_add_replace_del() {
+    let dummy_name = "nhtest-dummy1";
+
+    with_nexthop_iface(dummy_name, || {
+        ip_rs_exec_cmd(&[
+            "nexthop",
+            "add",
+            "id",
+            "201",
+            "via",
+            "192.0.2.2",
+            "dev",
+            dummy_name,
+            "protocol",
+            "static",
+        ]);
+        ip_rs_exec_cmd(&["nexthop", "add", "id", "202", "blackhole"]);
+        ip_rs_exec_cmd(&["nexthop", "add", "id", "203", "group", "201,3/202"]);
+        let output = ip_rs_exec_cmd(&["nexthop", "list", "id", "201"]);
+        pretty_assertions::assert_eq!(
+            output,
+            format!(
+                "id 201 via 192.0.2.2 dev {dummy_name} scope link proto \
+                 static \n"
+            )
+        );
+        let output = ip_rs_exec_cmd(&["nexthop", "list", "id", "203"]);
+        pretty_assertions::assert_eq!(output, "id 203 group 201,3/202 \n");
+
+        // Adding existing nexthop is rejected
+        let output = ip_rs_exec_cmd_output(&[
+            "nexthop",
+            "add",
+            "id",
+            "202",
+            "blackhole",
+        ]);
+        assert!(!output.status.success());
+
+        ip_rs_exec_cmd(&[
+            "nexthop",
+            "replace",
+            "id",
+            "201",
+            "via",
+            "192.0.2.3",
+            "dev",
+            dummy_name,
+            "onlink",
+        ]);
+        let output = ip_rs_exec_cmd(&["nexthop", "list", "id", "201"]);
+        pretty_assertions::assert_eq!(
+            output,
+            format!(
+                "id 201 via 192.0.2.3 dev {dummy_name} scope link onlink \n"
+            )
+        );
+
+        ip_rs_exec_cmd(&["nexthop", "del", "id", "203"]);
+        let output = ip_rs_exec_cmd(&["nexthop", "list", "groups"]);
+        assert!(output.is_empty());
+    });
+}
+
+#[test]
+fn test_nexthop_resilient_bucket() {
+    let dummy_name = "nhtest-dummy2";
+
+    with_nexthop_iface(dummy_name, || {
+        exec_cmd(&[
+            "ip",
+            "nexthop",
+            "add",
+            "id",
+            "301",
+            "via",
+            "192.0.2.2",
+            "dev",
+            dummy_name,
+        ]);
+        ip_rs_exec_cmd(&[
+            "nexthop",
+            "add",
+            "id",
+            "302",
+            "group",
+            "301",
+            "type",
+            "resilient",
+            "buckets",
+            "4",
+            "idle_timer",
+            "60",
+            "unbalanced_timer",
+            "120",
+        ]);
+
+        for args in [&[][..], &["-j"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["nexthop", "get", "id", "302"]].concat(),
+            );
+            let our_output = ip_rs_exec_cmd(
+                &[args, &["nexthop", "get", "id", "302"]].concat(),
+            );
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The idle time of bucket keeps growing, hence not compared with
+        // iproute2
+        let output = ip_rs_exec_cmd(&["nexthop", "bucket", "list"]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        for (i, line) in lines.iter().enumerate() {
+            assert!(line.starts_with(&format!("id 302 index {i} idle_time ")));
+            assert!(line.ends_with(" nhid 301 "));
+        }
+
+        let output = ip_rs_exec_cmd(&[
+            "nexthop", "bucket", "get", "id", "302", "index", "2",
+        ]);
+        assert!(output.starts_with("id 302 index 2 idle_time "));
+
+        let output =
+            ip_rs_exec_cmd(&["nexthop", "bucket", "list", "nhid", "999"]);
+        assert!(output.is_empty());
+    });
+}
+
+#[test]
+fn test_nexthop_flush() {
+    let dummy_name = "nhtest-dummy3";
+
+    with_nexthop_iface(dummy_name, || {
+        add_nexthops(dummy_name);
+
+        let output =
+            ip_rs_exec_cmd(&["-s", "nexthop", "flush", "dev", dummy_name]);
+        pretty_assertions::assert_eq!(output, "Flushed 3 nexthops\n");
+        let output = ip_rs_exec_cmd(&["nexthop", "list", "dev", dummy_name]);
+        assert!(output.is_empty());
+
+        ip_rs_exec_cmd(&["nexthop", "flush"]);
+        let output = ip_rs_exec_cmd(&["nexthop"]);
+        assert!(output.is_empty());
+
+        let output = ip_rs_exec_cmd(&["-s", "nexthop", "flush"]);
+        pretty_assertions::assert_eq!(output, "Nothing to flush\n");
+    });
+}
+
+#[test]
+fn test_nexthop_invalid_args() {
+    for (args, error) in [
+        (
+            &["nexthop", "add", "id", "x"][..],
+            "argument \"x\" is wrong: invalid id value",
+        ),
+        (
+            &["nexthop", "add", "id", "1", "foo"][..],
+            "argument \"foo\" is wrong: ",
+        ),
+        (
+            &["nexthop", "add", "id", "1", "group", "1,0"][..],
+            "argument \"0\" is wrong: \"weight\" is invalid",
+        ),
+        (
+            &["nexthop", "add", "id", "1", "group", "x"][..],
+            "argument \"x\" is wrong: \"group\" value is invalid",
+        ),
+        (
+            &["nexthop", "add", "id", "1", "group", "2", "type", "foo"][..],
+            "argument \"foo\" is wrong: \"type\" value is invalid",
+        ),
+        (
+            &[
+                "nexthop",
+                "add",
+                "id",
+                "1",
+                "group",
+                "2",
+                "type",
+                "resilient",
+                "buckets",
+                "x",
+            ][..],
+            "argument \"x\" is wrong: invalid buckets value",
+        ),
+        (
+            &[
+                "nexthop",
+                "add",
+                "id",
+                "1",
+                "via",
+                "192.0.2.1",
+                "via",
+                "::1",
+            ][..],
+            "inet address is expected rather than \"::1\"",
+        ),
+        (
+            &["nexthop", "get", "foo"][..],
+            "Usage: ip nexthop { list | flush }",
+        ),
+        (
+            &["nexthop", "bucket", "get", "id", "1"][..],
+            "Usage: ip nexthop { list | flush }",
+        ),
+        (
+            &["nexthop", "bucket", "get", "id", "1", "index", "x"][..],
+            "argument \"x\" is wrong: invalid bucket index value",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::handle_change,
+    show::{CliNtableInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct NtableCommand;
+
+impl NtableCommand {
+    pub(crate) const CMD: &'static str = "ntable";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("neighbour table configuration")
+            .alias("ntabl")
+            .alias("ntab")
+            .alias("nta")
+            .alias("nt")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show neighbour tables")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("change")
+                    .about("change neighbour table parameters")
+                    .alias("chang")
+                    .alias("chan")
+                    .alias("cha")
+                    .alias("ch")
+                    .alias("c")
+                    .alias("chg")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliNtableInfo>>, CliError> {
+        let handle = ctx.handle();
+        if let Some(matches) = matches.subcommand_matches("change") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_change(handle, &opts, get_family(matches)).await?;
+            return Ok(None);
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            handle,
+            &opts,
+            get_family(matches),
+            matches.get_count("STATS") > 0,
+        )
+        .await
+        .map(Into::into)
+    }
+}
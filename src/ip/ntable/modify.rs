@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_REPLACE, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour_table::{
+            NeighbourTableAttribute, NeighbourTableMessage,
+            NeighbourTableParameter,
+        },
+    },
+};
+
+use crate::{
+    address::duplicate_arg,
+    link::parse_num,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// Compose the request of `ip ntable change` following the argument
+/// grammar of iproute2 `ipntable_modify()`.
+fn parse_change_opts(
+    opts: &[&str],
+    family: AddressFamily,
+    ifnames: &HashMap<u32, String>,
+) -> Result<NeighbourTableMessage, CliError> {
+    let mut nl_msg = NeighbourTableMessage::default();
+    nl_msg.header.family = family;
+    let mut name_given = false;
+    let mut table_changed = false;
+    let mut parms = Vec::new();
+    let mut parms_changed = false;
+    let mut opts = opts.iter();
+
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "name" => {
+                let value = next_opt(&mut opts)?;
+                if name_given {
+                    return Err(duplicate_arg("NAME", value));
+                }
+                name_given = true;
+                nl_msg
+                    .attributes
+                    .push(NeighbourTableAttribute::Name(value.to_string()));
+            }
+            "thresh1" | "thresh2" | "thresh3" | "gc_int" => {
+                let value = next_opt(&mut opts)?;
+                nl_msg.attributes.push(match *opt {
+                    "thresh1" => NeighbourTableAttribute::Threshold1(
+                        parse_value(value, opt)?,
+                    ),
+                    "thresh2" => NeighbourTableAttribute::Threshold2(
+                        parse_value(value, opt)?,
+                    ),
+                    "thresh3" => NeighbourTableAttribute::Threshold3(
+                        parse_value(value, opt)?,
+                    ),
+                    _ => NeighbourTableAttribute::GcInterval(parse_value(
+                        value, opt,
+                    )?),
+                });
+                table_changed = true;
+            }
+            "dev" => {
+                parms.push(NeighbourTableParameter::Ifindex(ifname_to_index(
+                    ifnames,
+                    next_opt(&mut opts)?,
+                )?));
+            }
+            _ => {
+                parms.push(parse_parm(opt, &mut opts)?);
+                parms_changed = true;
+            }
+        }
+    }
+
+    if !name_given {
+        return Err(CliError::from("argument \"NAME\" is required"));
+    }
+    if !table_changed && !parms_changed {
+        return Err(CliError::from(
+            "Not enough information: changeable attributes required.",
+        ));
+    }
+    if !parms.is_empty() {
+        nl_msg
+            .attributes
+            .push(NeighbourTableAttribute::Parms(parms));
+    }
+    Ok(nl_msg)
+}
+
+// Equal to the `PARMS` of iproute2 `ipntable_modify()`
+fn parse_parm(
+    name: &str,
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<NeighbourTableParameter, CliError> {
+    Ok(match name {
+        "base_reachable" => {
+            NeighbourTableParameter::BaseReachableTime(next_value(opts, name)?)
+        }
+        "retrans" => {
+            NeighbourTableParameter::RetransTime(next_value(opts, name)?)
+        }
+        "gc_stale" => {
+            NeighbourTableParameter::GcStaletime(next_value(opts, name)?)
+        }
+        "delay_probe" => {
+            NeighbourTableParameter::DelayProbeTime(next_value(opts, name)?)
+        }
+        "queue" => NeighbourTableParameter::QueueLen(next_value(opts, name)?),
+        "app_probes" => {
+            NeighbourTableParameter::AppProbes(next_value(opts, name)?)
+        }
+        "ucast_probes" => {
+            NeighbourTableParameter::UcastProbes(next_value(opts, name)?)
+        }
+        "mcast_probes" => {
+            NeighbourTableParameter::McastProbes(next_value(opts, name)?)
+        }
+        "anycast_delay" => {
+            NeighbourTableParameter::AnycastDelay(next_value(opts, name)?)
+        }
+        "proxy_delay" => {
+            NeighbourTableParameter::ProxyDelay(next_value(opts, name)?)
+        }
+        "proxy_queue" => {
+            NeighbourTableParameter::ProxyQlen(next_value(opts, name)?)
+        }
+        "locktime" => {
+            NeighbourTableParameter::Locktime(next_value(opts, name)?)
+        }
+        _ => {
+            return Err(CliError::from(
+                format!("argument \"{name}\" is wrong: unknown").as_str(),
+            ));
+        }
+    })
+}
+
+fn next_value<T: TryFrom<i64>>(
+    opts: &mut std::slice::Iter<'_, &str>,
+    name: &str,
+) -> Result<T, CliError> {
+    parse_value(next_opt(opts)?, name)
+}
+
+fn parse_value<T: TryFrom<i64>>(
+    value: &str,
+    name: &str,
+) -> Result<T, CliError> {
+    parse_num(value, &format!("\"{name}\" value is invalid"))
+}
+
+pub(crate) async fn handle_change(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(handle).await?;
+    let nl_msg = parse_change_opts(opts, family, &ifnames)?;
+
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::SetNeighbourTable(
+            nl_msg,
+        )),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_REPLACE;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
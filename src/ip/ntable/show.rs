@@ -0,0 +1,528 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, write_with_color,
+};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_DUMP, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        neighbour_table::{
+            NeighbourTableAttribute, NeighbourTableConfig,
+            NeighbourTableMessage, NeighbourTableParameter,
+            NeighbourTableStats,
+        },
+    },
+};
+use serde::Serialize;
+
+use crate::route::{get_ifnames, ifname_to_index};
+
+// Equal to iproute2 `NONE_DEV`, used by `dev none` to show the global
+// parameters of neighbour tables only
+const NONE_DEV: u32 = u32::MAX;
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliNtableInfo {
+    family: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thresh1: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thresh2: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thresh3: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gc_interval: Option<u64>,
+    #[serde(flatten)]
+    config: Option<CliNtableConfig>,
+    #[serde(flatten)]
+    parms: Option<CliNtableParms>,
+    #[serde(flatten)]
+    stats: Option<CliNtableStats>,
+}
+
+// Equal to iproute2 `print_ndtconfig()`
+#[derive(Serialize)]
+struct CliNtableConfig {
+    key_length: u16,
+    entry_size: u16,
+    entries: u32,
+    last_flush: String,
+    last_rand: String,
+    hash_rnd: u32,
+    hash_mask: CliHashMask,
+    hash_chain_gc: u32,
+    proxy_qlen: u32,
+}
+
+/// Like iproute2 `print_0xhex()`, the hash mask is shown as `%08x` in text
+/// and `0x%x` in JSON.
+struct CliHashMask(u32);
+
+impl std::fmt::Display for CliHashMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}", self.0)
+    }
+}
+
+impl Serialize for CliHashMask {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&format!("{:#x}", self.0))
+    }
+}
+
+// Equal to iproute2 `print_ndtparams()`
+#[derive(Serialize, Default)]
+struct CliNtableParms {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refcnt: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reachable: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_reachable: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retrans: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gc_stale: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay_probe: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queue: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app_probes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ucast_probes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcast_probes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    anycast_delay: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy_delay: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proxy_queue: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locktime: Option<u64>,
+}
+
+// Equal to iproute2 `print_ndtstats()`
+#[derive(Serialize)]
+struct CliNtableStats {
+    allocs: u64,
+    destroys: u64,
+    hash_grows: u64,
+    res_failed: u64,
+    lookups: u64,
+    hits: u64,
+    rcv_probes_mcast: u64,
+    rcv_probes_ucast: u64,
+    periodic_gc_runs: u64,
+    forced_gc_runs: u64,
+    table_fulls: u64,
+}
+
+// Equal to iproute2 `print_ntable()`
+impl std::fmt::Display for CliNtableInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {} ", self.family, self.name)?;
+
+        if self.thresh1.is_some()
+            || self.thresh2.is_some()
+            || self.thresh3.is_some()
+            || self.gc_interval.is_some()
+        {
+            write!(f, "    ")?;
+            for (name, value) in [
+                ("thresh1", self.thresh1),
+                ("thresh2", self.thresh2),
+                ("thresh3", self.thresh3),
+            ] {
+                if let Some(value) = value {
+                    write!(f, "{name} {value} ")?;
+                }
+            }
+            if let Some(gc_interval) = self.gc_interval {
+                write!(f, "gc_int {gc_interval} ")?;
+            }
+            writeln!(f)?;
+        }
+        if let Some(config) = &self.config {
+            write!(f, "{config}")?;
+        }
+        if let Some(parms) = &self.parms {
+            write!(f, "{parms}")?;
+        }
+        if let Some(stats) = &self.stats {
+            write!(f, "{stats}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CliNtableConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "    config key_len {} entry_size {} entries {} ",
+            self.key_length, self.entry_size, self.entries
+        )?;
+        writeln!(
+            f,
+            "        last_flush {} last_rand {} ",
+            self.last_flush, self.last_rand
+        )?;
+        writeln!(
+            f,
+            "        hash_rnd {} hash_mask {} hash_chain_gc {} proxy_qlen {} ",
+            self.hash_rnd, self.hash_mask, self.hash_chain_gc, self.proxy_qlen
+        )
+    }
+}
+
+impl std::fmt::Display for CliNtableParms {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(dev) = &self.dev {
+            write!(f, "    dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{dev}")?;
+            writeln!(f, " ")?;
+        }
+        // The parameters are grouped into lines like iproute2
+        let lines: [&[(&str, Option<u64>)]; 4] = [
+            &[
+                ("refcnt", self.refcnt.map(u64::from)),
+                ("reachable", self.reachable),
+                ("base_reachable", self.base_reachable),
+                ("retrans", self.retrans),
+            ],
+            &[
+                ("gc_stale", self.gc_stale),
+                ("delay_probe", self.delay_probe),
+                ("queue", self.queue.map(u64::from)),
+            ],
+            &[
+                ("app_probes", self.app_probes.map(u64::from)),
+                ("ucast_probes", self.ucast_probes.map(u64::from)),
+                ("mcast_probes", self.mcast_probes.map(u64::from)),
+            ],
+            &[
+                ("anycast_delay", self.anycast_delay),
+                ("proxy_delay", self.proxy_delay),
+                ("proxy_queue", self.proxy_queue.map(u64::from)),
+                ("locktime", self.locktime),
+            ],
+        ];
+        for line in lines {
+            write!(f, "    ")?;
+            for (name, value) in line {
+                if let Some(value) = value {
+                    write!(f, "{name} {value} ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CliNtableStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "    stats allocs {} destroys {} hash_grows {} ",
+            self.allocs, self.destroys, self.hash_grows
+        )?;
+        writeln!(
+            f,
+            "    res_failed {} lookups {} hits {} ",
+            self.res_failed, self.lookups, self.hits
+        )?;
+        writeln!(
+            f,
+            "    rcv_probes_mcast {} rcv_probes_ucast {} ",
+            self.rcv_probes_mcast, self.rcv_probes_ucast
+        )?;
+        writeln!(
+            f,
+            "    periodic_gc_runs {} forced_gc_runs {} ",
+            self.periodic_gc_runs, self.forced_gc_runs
+        )?;
+        writeln!(f, "    table_fulls {} ", self.table_fulls)
+    }
+}
+
+impl CanDisplay for CliNtableInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliNtableInfo {}
+
+/// Filters of `ip ntable show` following the argument grammar of iproute2
+/// `ipntable_show()`.
+#[derive(Debug, Default)]
+struct NtableShowFilter<'a> {
+    /// `NONE_DEV` for the global parameters only
+    index: Option<u32>,
+    name: Option<&'a str>,
+}
+
+impl<'a> NtableShowFilter<'a> {
+    fn parse(
+        opts: &[&'a str],
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.index = Some(if value == "none" {
+                        NONE_DEV
+                    } else {
+                        ifname_to_index(ifnames, value).map_err(|_| {
+                            CliError::from(
+                                format!(
+                                    "argument \"{value}\" is wrong: \"DEV\" \
+                                     is invalid"
+                                )
+                                .as_str(),
+                            )
+                        })?
+                    });
+                }
+                "name" => ret.name = Some(next_opt(&mut opts)?),
+                _ => {
+                    return Err(CliError::from(
+                        format!("argument \"{opt}\" is wrong: unknown")
+                            .as_str(),
+                    ));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    // Equal to the filter of iproute2 `print_ntable()`
+    fn matches(&self, nl_msg: &NeighbourTableMessage) -> bool {
+        let mut name = None;
+        // `None` if no parameters included
+        let mut index = None;
+        for nla in nl_msg.attributes.iter() {
+            match nla {
+                NeighbourTableAttribute::Name(v) => name = Some(v.as_str()),
+                NeighbourTableAttribute::Parms(parms) => {
+                    index = Some(
+                        parms
+                            .iter()
+                            .find_map(|parm| match parm {
+                                NeighbourTableParameter::Ifindex(v) => Some(*v),
+                                _ => None,
+                            })
+                            .unwrap_or(NONE_DEV),
+                    );
+                }
+                _ => (),
+            }
+        }
+        if let (Some(filter_name), Some(name)) = (self.name, name)
+            && filter_name != name
+        {
+            return false;
+        }
+        self.index
+            .is_none_or(|i| index.is_none_or(|index| index == i))
+    }
+}
+
+/// Like iproute2, the kernel reports the age of last flush and last
+/// randomization in milliseconds which are shown as local time.
+fn age_to_time_string(age_ms: u32) -> String {
+    let secs = chrono::Local::now().timestamp() - i64::from(age_ms / 1000);
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+impl From<&NeighbourTableConfig> for CliNtableConfig {
+    fn from(config: &NeighbourTableConfig) -> Self {
+        Self {
+            key_length: config.key_len,
+            entry_size: config.entry_size,
+            entries: config.entries,
+            last_flush: age_to_time_string(config.last_flush),
+            last_rand: age_to_time_string(config.last_rand),
+            hash_rnd: config.hash_rnd,
+            hash_mask: CliHashMask(config.hash_mask),
+            hash_chain_gc: config.hash_chain_gc,
+            proxy_qlen: config.proxy_qlen,
+        }
+    }
+}
+
+impl From<&NeighbourTableStats> for CliNtableStats {
+    fn from(stats: &NeighbourTableStats) -> Self {
+        Self {
+            allocs: stats.allocs,
+            destroys: stats.destroys,
+            hash_grows: stats.hash_grows,
+            res_failed: stats.res_failed,
+            lookups: stats.lookups,
+            hits: stats.hits,
+            rcv_probes_mcast: stats.multicast_probes_received,
+            rcv_probes_ucast: stats.unicast_probes_received,
+            periodic_gc_runs: stats.periodic_gc_runs,
+            forced_gc_runs: stats.forced_gc_runs,
+            table_fulls: stats.table_fulls,
+        }
+    }
+}
+
+fn parse_parms(
+    parms: &[NeighbourTableParameter],
+    ifnames: &HashMap<u32, String>,
+) -> CliNtableParms {
+    let mut ret = CliNtableParms::default();
+    for parm in parms {
+        match parm {
+            // Like iproute2 `ll_index_to_name()`, index 0 is shown as `*`
+            NeighbourTableParameter::Ifindex(v) => {
+                ret.dev = Some(match v {
+                    0 => "*".to_string(),
+                    _ => ifnames
+                        .get(v)
+                        .cloned()
+                        .unwrap_or_else(|| format!("if{v}")),
+                });
+            }
+            NeighbourTableParameter::ReferenceCount(v) => ret.refcnt = Some(*v),
+            NeighbourTableParameter::ReachableTime(v) => {
+                ret.reachable = Some(*v)
+            }
+            NeighbourTableParameter::BaseReachableTime(v) => {
+                ret.base_reachable = Some(*v)
+            }
+            NeighbourTableParameter::RetransTime(v) => ret.retrans = Some(*v),
+            NeighbourTableParameter::GcStaletime(v) => ret.gc_stale = Some(*v),
+            NeighbourTableParameter::DelayProbeTime(v) => {
+                ret.delay_probe = Some(*v)
+            }
+            NeighbourTableParameter::QueueLen(v) => ret.queue = Some(*v),
+            NeighbourTableParameter::AppProbes(v) => ret.app_probes = Some(*v),
+            NeighbourTableParameter::UcastProbes(v) => {
+                ret.ucast_probes = Some(*v)
+            }
+            NeighbourTableParameter::McastProbes(v) => {
+                ret.mcast_probes = Some(*v)
+            }
+            NeighbourTableParameter::AnycastDelay(v) => {
+                ret.anycast_delay = Some(*v)
+            }
+            NeighbourTableParameter::ProxyDelay(v) => {
+                ret.proxy_delay = Some(*v)
+            }
+            NeighbourTableParameter::ProxyQlen(v) => ret.proxy_queue = Some(*v),
+            NeighbourTableParameter::Locktime(v) => ret.locktime = Some(*v),
+            _ => (),
+        }
+    }
+    ret
+}
+
+fn parse_nl_msg_to_ntable(
+    nl_msg: &NeighbourTableMessage,
+    ifnames: &HashMap<u32, String>,
+    show_stats: bool,
+) -> CliNtableInfo {
+    let mut ret = CliNtableInfo {
+        family: nl_msg.header.family.to_string(),
+        ..Default::default()
+    };
+    for nla in nl_msg.attributes.iter() {
+        match nla {
+            NeighbourTableAttribute::Name(v) => ret.name = v.to_string(),
+            NeighbourTableAttribute::Threshold1(v) => ret.thresh1 = Some(*v),
+            NeighbourTableAttribute::Threshold2(v) => ret.thresh2 = Some(*v),
+            NeighbourTableAttribute::Threshold3(v) => ret.thresh3 = Some(*v),
+            NeighbourTableAttribute::GcInterval(v) => {
+                ret.gc_interval = Some(*v)
+            }
+            NeighbourTableAttribute::Config(v) if show_stats => {
+                ret.config = Some(v.into());
+            }
+            NeighbourTableAttribute::Parms(v) => {
+                ret.parms = Some(parse_parms(v, ifnames));
+            }
+            NeighbourTableAttribute::Stats(v) if show_stats => {
+                ret.stats = Some(v.into());
+            }
+            _ => (),
+        }
+    }
+    ret
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliNtableInfo>, CliError> {
+    let ifnames = get_ifnames(handle).await?;
+    let filter = NtableShowFilter::parse(opts, &ifnames)?;
+
+    // The rtnetlink crate has no neighbour table support, hence compose the
+    // dump request like iproute2 `rtnl_neightbldump_req()`
+    let mut nl_msg = NeighbourTableMessage::default();
+    nl_msg.header.family = family;
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::GetNeighbourTable(
+            nl_msg,
+        )),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+
+    let mut ret = Vec::new();
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        match msg.payload {
+            NetlinkPayload::InnerMessage(
+                RouteNetlinkMessage::NewNeighbourTable(nl_msg),
+            ) => {
+                if family != AddressFamily::Unspec
+                    && nl_msg.header.family != family
+                {
+                    continue;
+                }
+                if filter.matches(&nl_msg) {
+                    ret.push(parse_nl_msg_to_ntable(
+                        &nl_msg, &ifnames, show_stats,
+                    ));
+                }
+            }
+            NetlinkPayload::Error(e) if e.code.is_some() => {
+                return Err(rtnetlink::Error::NetlinkError(e).into());
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
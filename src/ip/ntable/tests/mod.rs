@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use crate::tests::exec_cmd;
+
+// The per-device parameters of neighbour table are only created for
+// interfaces with IP enabled
+fn setup_veth(veth_name: &str) {
+    let peer_name = format!("{veth_name}p");
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+    exec_cmd(&["ip", "link", "set", veth_name, "up"]);
+}
+
+fn cleanup_veth(veth_name: &str) {
+    exec_cmd(&["ip", "link", "del", veth_name]);
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+// The `reachable` time is randomized by kernel whenever the interface is
+// created, hence removed before comparing.
+fn get_ntable_parms(veth_name: &str) -> Vec<serde_json::Value> {
+    let output = exec_cmd(&["ip", "-j", "ntable", "show", "dev", veth_name]);
+    let mut parms: Vec<serde_json::Value> =
+        serde_json::from_str(&output).expect("Invalid JSON output");
+    for parm in parms.iter_mut() {
+        if let Some(parm) = parm.as_object_mut() {
+            parm.remove("reachable");
+        }
+    }
+    parms
+}
+
+// Change the neighbour table parameters of the test interface by iproute2
+// and by us, the parameters should be identical.
+fn assert_ntable_change(veth_name: &str, args: &[&str]) {
+    let args = [&["ntable", "change"], args, &["dev", veth_name]].concat();
+
+    setup_veth(veth_name);
+    exec_cmd(&[&["ip"], args.as_slice()].concat());
+    let expected_parms = get_ntable_parms(veth_name);
+    cleanup_veth(veth_name);
+
+    setup_veth(veth_name);
+    let our_output = ip_rs_exec_cmd(&args);
+    let our_parms = get_ntable_parms(veth_name);
+    cleanup_veth(veth_name);
+
+    assert!(our_output.is_empty());
+    pretty_assertions::assert_eq!(expected_parms, our_parms);
+}
+
+#[test]
+fn test_ntable_change() {
+    let veth_name = "nttest-veth1";
+    let _lock = lock_net_test();
+
+    for args in [
+        &["name", "arp_cache", "queue", "50", "retrans", "2000"][..],
+        &[
+            "name",
+            "arp_cache",
+            "base_reachable",
+            "20000",
+            "gc_stale",
+            "30000",
+            "delay_probe",
+            "3000",
+        ][..],
+        &[
+            "name",
+            "ndisc_cache",
+            "app_probes",
+            "1",
+            "ucast_probes",
+            "5",
+            "mcast_probes",
+            "0x4",
+        ][..],
+        &[
+            "name",
+            "arp_cache",
+            "anycast_delay",
+            "500",
+            "proxy_delay",
+            "400",
+            "proxy_queue",
+            "32",
+            "locktime",
+            "2000",
+        ][..],
+    ] {
+        assert_ntable_change(veth_name, args);
+    }
+}
+
+#[test]
+fn test_ntable_change_invalid_args() {
+    for (args, error_msg) in [
+        (&["thresh1", "10"][..], "argument \"NAME\" is required"),
+        (
+            &["name", "arp_cache"][..],
+            "Not enough information: changeable attributes required.",
+        ),
+        (
+            &["name", "arp_cache", "dev", "lo"][..],
+            "Not enough information: changeable attributes required.",
+        ),
+        (
+            &["name", "arp_cache", "name", "x"][..],
+            "duplicate \"NAME\": \"x\" is the second value.",
+        ),
+        (
+            &["name", "arp_cache", "foo", "1"][..],
+            "argument \"foo\" is wrong: unknown",
+        ),
+        (
+            &["name", "arp_cache", "thresh1", "x"][..],
+            "argument \"x\" is wrong: \"thresh1\" value is invalid",
+        ),
+        (
+            &["name", "arp_cache", "gc_int", "x"][..],
+            "argument \"x\" is wrong: \"gc_int\" value is invalid",
+        ),
+        (
+            &["name", "arp_cache", "dev", "nttest-absent0", "queue", "5"][..],
+            "Cannot find device \"nttest-absent0\"",
+        ),
+        (
+            &["name", "foo_cache", "thresh1", "10"][..],
+            "RTNETLINK answers: No such file or directory",
+        ),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["ntable", "change"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_ntable_show() {
+    let veth_name = "nttest-veth0";
+    let _lock = lock_net_test();
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        // The `reachable` time is randomized by kernel, hence only the
+        // test interface and the global parameters are compared. The
+        // `refcnt` and statistics of global tables change with traffic.
+        for args in [
+            &["ntable", "show", "dev", veth_name][..],
+            &["-4", "ntable", "show", "dev", veth_name][..],
+            &["-6", "ntable", "show", "dev", veth_name][..],
+            &["-j", "ntable", "show", "dev", veth_name][..],
+            &["ntable", "show", "dev", veth_name, "name", "arp_cache"][..],
+            &["ntable", "list", "name", "ndisc_cache", "dev", veth_name][..],
+            &["ntable", "show", "dev", veth_name, "name", "absent"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The global parameters have no device
+        let our_output = ip_rs_exec_cmd(&["ntable", "show", "dev", "none"]);
+        assert!(our_output.contains("thresh1"));
+        assert!(!our_output.contains("dev"));
+
+        let our_output = ip_rs_exec_cmd(&["-s", "ntable", "show"]);
+        assert!(our_output.contains("config key_len"));
+        assert!(our_output.contains("stats allocs"));
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_ntable_show_invalid_args() {
+    for (args, error_msg) in [
+        (&["foo"][..], "argument \"foo\" is wrong: unknown"),
+        (
+            &["dev", "nttest-absent0"][..],
+            "argument \"nttest-absent0\" is wrong: \"DEV\" is invalid",
+        ),
+        (&["name"][..], "Command line is not complete"),
+    ] {
+        let output =
+            ip_rs_exec_cmd_output(&[&["ntable", "show"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
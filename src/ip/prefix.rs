@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use iproute_rs::CliError;
+use rtnetlink::packet_route::AddressFamily;
+
+/// IP prefix like `192.0.2.0/24` following the grammar of iproute2
+/// `get_prefix()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CliIpPrefix {
+    pub(crate) addr: IpAddr,
+    pub(crate) prefix_len: u8,
+}
+
+impl CliIpPrefix {
+    pub(crate) fn parse(
+        value: &str,
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        Self::parse_inner(value, family).ok_or_else(|| {
+            let family_name = family_name_verbose(family);
+            CliError::from(
+                format!(
+                    "{family_name} prefix is expected rather than \"{value}\"."
+                )
+                .as_str(),
+            )
+        })
+    }
+
+    fn parse_inner(value: &str, family: AddressFamily) -> Option<Self> {
+        // Like iproute2, `default`, `all` and `any` stand for the zero
+        // length prefix
+        if matches!(value, "default" | "all" | "any") {
+            let addr = if family == AddressFamily::Inet6 {
+                IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+            } else {
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+            };
+            return Some(Self {
+                addr,
+                prefix_len: 0,
+            });
+        }
+
+        let (addr, prefix_len) = match value.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (value, None),
+        };
+        let addr = parse_ip_addr(addr, family).ok()?;
+        let max_len = max_prefix_len(&addr);
+        let prefix_len = match prefix_len {
+            Some(v) => parse_netmask(v)?,
+            None => max_len,
+        };
+        if prefix_len > max_len {
+            return None;
+        }
+        Some(Self { addr, prefix_len })
+    }
+
+    /// Like iproute2 `inet_addr_match()`, check whether the leading
+    /// `prefix_len` bits of the address are identical to this prefix.
+    pub(crate) fn contains(&self, addr: &IpAddr) -> bool {
+        if self.prefix_len == 0 {
+            return true;
+        }
+        match (self.addr, addr) {
+            (IpAddr::V4(prefix), IpAddr::V4(addr)) => {
+                let mask = u32::MAX << (32 - u32::from(self.prefix_len));
+                u32::from(prefix) & mask == u32::from(*addr) & mask
+            }
+            (IpAddr::V6(prefix), IpAddr::V6(addr)) => {
+                let mask = u128::MAX << (128 - u32::from(self.prefix_len));
+                u128::from(prefix) & mask == u128::from(*addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Like iproute2 `get_addr()`, parse IP address of specified family.
+pub(crate) fn parse_ip_addr(
+    value: &str,
+    family: AddressFamily,
+) -> Result<IpAddr, CliError> {
+    let addr = if value.contains(':') {
+        value.parse().ok().map(IpAddr::V6)
+    } else {
+        parse_ipv4(value).map(IpAddr::V4)
+    };
+    match (family, addr) {
+        (AddressFamily::Unspec, Some(addr))
+        | (AddressFamily::Inet, Some(addr @ IpAddr::V4(_)))
+        | (AddressFamily::Inet6, Some(addr @ IpAddr::V6(_))) => Ok(addr),
+        _ => {
+            let family_name = family_name_verbose(family);
+            Err(CliError::from(
+                format!(
+                    "{family_name} address is expected rather than \
+                     \"{value}\"."
+                )
+                .as_str(),
+            ))
+        }
+    }
+}
+
+// Equal to iproute2 `family_name_verbose()`
+fn family_name_verbose(family: AddressFamily) -> &'static str {
+    match family {
+        AddressFamily::Inet => "inet",
+        AddressFamily::Inet6 => "inet6",
+        _ => "any valid",
+    }
+}
+
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+// Like iproute2 `get_addr_ipv4()`, the trailing zero octets could be
+// omitted, e.g. `10/8`.
+fn parse_ipv4(value: &str) -> Option<Ipv4Addr> {
+    let mut octets = [0u8; 4];
+    for (i, part) in value.split('.').enumerate() {
+        *octets.get_mut(i)? = part.parse().ok()?;
+    }
+    Some(Ipv4Addr::from(octets))
+}
+
+// Like iproute2 `get_netmask()`, both prefix length and dotted IPv4 netmask
+// are supported.
+fn parse_netmask(value: &str) -> Option<u8> {
+    if let Ok(v) = value.parse::<u8>() {
+        return Some(v);
+    }
+    let mask = u32::from(value.parse::<Ipv4Addr>().ok()?);
+    let prefix_len = mask.leading_ones();
+    (mask.checked_shl(prefix_len).unwrap_or(0) == 0).then_some(prefix_len as u8)
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    get::handle_get,
+    modify::{RouteModifyCmd, handle_modify},
+    save::{handle_restore, handle_save, handle_showdump},
+    show::{CliRouteInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct RouteCommand;
+
+impl RouteCommand {
+    pub(crate) const CMD: &'static str = "route";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("routing table management")
+            .alias("rout")
+            .alias("rou")
+            .alias("ro")
+            .alias("r")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show routes")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("get")
+                    .about("get a single route")
+                    .alias("ge")
+                    .alias("g")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("add").about("add new route").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete route")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("change")
+                    .about("change existing route")
+                    .alias("chg")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("replace")
+                    .about("change or add route")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("append")
+                    .about("add route after the existing ones")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("prepend")
+                    .about("add route before the existing ones")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("save")
+                    .about("save routes as binary stream to stdout")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("restore")
+                    .about("restore routes from binary stream of stdin"),
+            )
+            .subcommand(
+                clap::Command::new("showdump")
+                    .about("show routes in binary stream of stdin"),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliRouteInfo>>, CliError> {
+        let handle = ctx.handle();
+        for (subcommand, cmd) in [
+            ("add", RouteModifyCmd::Add),
+            ("change", RouteModifyCmd::Change),
+            ("replace", RouteModifyCmd::Replace),
+            ("append", RouteModifyCmd::Append),
+            ("prepend", RouteModifyCmd::Prepend),
+            ("delete", RouteModifyCmd::Delete),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(subcommand) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(handle, &opts, get_family(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("save") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_save(handle, &opts, get_family(matches)).await?;
+            return Ok(None);
+        }
+
+        if matches.subcommand_matches("restore").is_some() {
+            handle_restore(handle).await?;
+            return Ok(None);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("showdump") {
+            return handle_showdump(
+                handle,
+                matches.get_flag("DETAILS"),
+                matches.get_flag("NUMERIC"),
+                matches.get_count("STATS") > 0,
+            )
+            .await
+            .map(Into::into);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("get") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            return handle_get(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_flag("DETAILS"),
+                matches.get_flag("NUMERIC"),
+                matches.get_flag("RESOLVE"),
+                matches.get_count("STATS") > 0,
+            )
+            .await
+            .map(Into::into);
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            handle,
+            &opts,
+            get_family(matches),
+            matches.get_flag("DETAILS"),
+            matches.get_flag("NUMERIC"),
+            matches.get_flag("RESOLVE"),
+            matches.get_count("STATS") > 0,
+        )
+        .await
+        .map(Into::into)
+    }
+}
@@ -0,0 +1,624 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use iproute_rs::{CliColor, CliError, next_opt, write_with_color};
+use rtnetlink::{
+    packet_core::{DefaultNla, Nla, NlasIterator, ParseableParametrized},
+    packet_route::{
+        AddressFamily,
+        route::{
+            MplsLabel, RouteAttribute, RouteLwEnCapType, RouteLwTunnelEncap,
+            RouteMplsIpTunnel, RouteSeg6IpTunnel, Seg6Header, Seg6Mode,
+        },
+    },
+};
+use serde::{Serialize, ser::SerializeMap};
+
+use super::names::parse_tos;
+use crate::{link::parse_num, prefix::parse_ip_addr};
+
+const LWTUNNEL_ENCAP_MPLS: u16 = 1;
+const LWTUNNEL_ENCAP_IP: u16 = 2;
+const LWTUNNEL_ENCAP_IP6: u16 = 4;
+const LWTUNNEL_ENCAP_SEG6: u16 = 5;
+
+// The `LWTUNNEL_IP6_*` share the same values with `LWTUNNEL_IP_*`
+const LWTUNNEL_IP_ID: u16 = 1;
+const LWTUNNEL_IP_DST: u16 = 2;
+const LWTUNNEL_IP_SRC: u16 = 3;
+const LWTUNNEL_IP_TTL: u16 = 4;
+const LWTUNNEL_IP_TOS: u16 = 5;
+const LWTUNNEL_IP_FLAGS: u16 = 6;
+
+// The `TUNNEL_*` flags in network order, equal to iproute2
+// `print_encap_ip()`
+const TUNNEL_FLAG_NAMES: [(u16, &str); 3] =
+    [(0x04, "key"), (0x01, "csum"), (0x08, "seq")];
+
+// Equal to iproute2 `format_encap_type()` indexed by `LWTUNNEL_ENCAP_*`
+const ENCAP_TYPE_NAMES: [&str; 11] = [
+    "",
+    "mpls",
+    "ip",
+    "ila",
+    "ip6",
+    "seg6",
+    "bpf",
+    "seg6local",
+    "rpl",
+    "ioam6",
+    "xfrm",
+];
+
+// Equal to iproute2 `format_seg6mode_type()` indexed by `SEG6_IPTUN_MODE_*`
+const SEG6_MODE_NAMES: [&str; 5] =
+    ["inline", "encap", "l2encap", "encap.red", "l2encap.red"];
+
+// The MPLS label is 20 bits long
+const MPLS_LABEL_MAX: u32 = (1 << 20) - 1;
+
+/// Lightweight tunnel encapsulation of route, equal to iproute2
+/// `lwt_print_encap()`
+pub(crate) struct CliRouteEncap {
+    kind: String,
+    info: CliRouteEncapInfo,
+}
+
+enum CliRouteEncapInfo {
+    Mpls {
+        dst: Option<String>,
+        ttl: Option<u8>,
+    },
+    Ip(CliRouteEncapIp),
+    Seg6 {
+        mode: String,
+        segs: Vec<Ipv6Addr>,
+    },
+    /// Encapsulation not decoded, only the type is shown
+    Unknown,
+}
+
+// Equal to iproute2 `print_encap_ip()` and `print_encap_ip6()`
+#[derive(Default)]
+struct CliRouteEncapIp {
+    is_ipv6: bool,
+    id: Option<u64>,
+    src: Option<IpAddr>,
+    dst: Option<IpAddr>,
+    /// The hop limit of IPv6
+    ttl: Option<u8>,
+    /// The traffic class of IPv6
+    tos: Option<u8>,
+    flags: u16,
+}
+
+impl CliRouteEncap {
+    /// Like iproute2, the encapsulation is shown only when both
+    /// `RTA_ENCAP_TYPE` and `RTA_ENCAP` exist.
+    pub(super) fn from_nlas(nlas: &[RouteAttribute]) -> Option<Self> {
+        let mut kind = None;
+        let mut encap = None;
+        for nla in nlas {
+            match nla {
+                RouteAttribute::EncapType(k) => kind = Some(*k),
+                RouteAttribute::Encap(e) => encap = Some(e.as_slice()),
+                _ => (),
+            }
+        }
+        Some(Self::new(kind?, encap?))
+    }
+
+    /// Decode the raw nested attributes of encapsulation which is not
+    /// covered by the rtnetlink crate, e.g. `NHA_ENCAP` of nexthop.
+    pub(crate) fn from_raw(kind: u16, encap: &[u8]) -> Self {
+        let kind = RouteLwEnCapType::from(kind);
+        let encap: Vec<RouteLwTunnelEncap> = NlasIterator::new(encap)
+            .filter_map(Result::ok)
+            .filter_map(|nla| {
+                RouteLwTunnelEncap::parse_with_param(&nla, kind).ok()
+            })
+            .collect();
+        Self::new(kind, &encap)
+    }
+
+    fn new(kind: RouteLwEnCapType, encap: &[RouteLwTunnelEncap]) -> Self {
+        let kind = u16::from(kind);
+        let info = match kind {
+            LWTUNNEL_ENCAP_MPLS => {
+                let mut dst = None;
+                let mut ttl = None;
+                for nla in encap {
+                    match nla {
+                        RouteLwTunnelEncap::Mpls(
+                            RouteMplsIpTunnel::Destination(labels),
+                        ) => dst = Some(mpls_labels_to_string(labels)),
+                        RouteLwTunnelEncap::Mpls(RouteMplsIpTunnel::Ttl(t)) => {
+                            ttl = Some(*t);
+                        }
+                        _ => (),
+                    }
+                }
+                CliRouteEncapInfo::Mpls { dst, ttl }
+            }
+            LWTUNNEL_ENCAP_IP | LWTUNNEL_ENCAP_IP6 => CliRouteEncapInfo::Ip(
+                CliRouteEncapIp::new(kind == LWTUNNEL_ENCAP_IP6, encap),
+            ),
+            LWTUNNEL_ENCAP_SEG6 => encap
+                .iter()
+                .find_map(|nla| {
+                    if let RouteLwTunnelEncap::Seg6(RouteSeg6IpTunnel::Seg6(
+                        header,
+                    )) = nla
+                    {
+                        // Like iproute2 `print_srh()`, the segments are
+                        // stored in reverse order
+                        Some(CliRouteEncapInfo::Seg6 {
+                            mode: seg6_mode_to_name(header.mode),
+                            segs: header
+                                .segments
+                                .iter()
+                                .rev()
+                                .copied()
+                                .collect(),
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(CliRouteEncapInfo::Unknown),
+            _ => CliRouteEncapInfo::Unknown,
+        };
+        Self {
+            kind: ENCAP_TYPE_NAMES
+                .get(usize::from(kind))
+                .filter(|name| !name.is_empty())
+                .unwrap_or(&"unknown")
+                .to_string(),
+            info,
+        }
+    }
+}
+
+impl CliRouteEncapIp {
+    // The rtnetlink crate does not decode the IP tunnel encapsulation,
+    // hence parse the raw attributes
+    fn new(is_ipv6: bool, encap: &[RouteLwTunnelEncap]) -> Self {
+        let mut ret = Self {
+            is_ipv6,
+            ..Default::default()
+        };
+        for nla in encap {
+            let RouteLwTunnelEncap::Other(nla) = nla else {
+                continue;
+            };
+            let mut buf = vec![0u8; nla.value_len()];
+            nla.emit_value(&mut buf);
+            match nla.kind() {
+                LWTUNNEL_IP_ID => {
+                    ret.id = <[u8; 8]>::try_from(buf.as_slice())
+                        .ok()
+                        .map(u64::from_be_bytes);
+                }
+                LWTUNNEL_IP_DST => ret.dst = bytes_to_ip(&buf),
+                LWTUNNEL_IP_SRC => ret.src = bytes_to_ip(&buf),
+                LWTUNNEL_IP_TTL => ret.ttl = buf.first().copied(),
+                LWTUNNEL_IP_TOS => ret.tos = buf.first().copied(),
+                LWTUNNEL_IP_FLAGS => {
+                    if let Ok(flags) = <[u8; 2]>::try_from(buf.as_slice()) {
+                        ret.flags = u16::from_be_bytes(flags);
+                    }
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    fn ttl_name(&self) -> &'static str {
+        if self.is_ipv6 { "hoplimit" } else { "ttl" }
+    }
+
+    fn tos_name(&self) -> &'static str {
+        if self.is_ipv6 { "tc" } else { "tos" }
+    }
+
+    fn flag_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        TUNNEL_FLAG_NAMES
+            .iter()
+            .filter(|(flag, _)| self.flags & flag != 0)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl std::fmt::Display for CliRouteEncap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Like iproute2, the encapsulation is prefixed by extra space
+        write!(f, " encap {} ", self.kind)?;
+        match &self.info {
+            CliRouteEncapInfo::Mpls { dst, ttl } => {
+                if let Some(dst) = dst {
+                    write!(f, " {dst} ")?;
+                }
+                if let Some(ttl) = ttl {
+                    write!(f, "ttl {ttl} ")?;
+                }
+            }
+            CliRouteEncapInfo::Ip(ip) => write!(f, "{ip}")?,
+            CliRouteEncapInfo::Seg6 { mode, segs } => {
+                write!(f, "mode {mode} segs {} [ ", segs.len())?;
+                for seg in segs {
+                    write!(f, "{seg} ")?;
+                }
+                write!(f, "] ")?;
+            }
+            CliRouteEncapInfo::Unknown => (),
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CliRouteEncapIp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let addr_color = CliColor::address_color(if self.is_ipv6 {
+            "inet6"
+        } else {
+            "inet"
+        });
+        if let Some(id) = self.id {
+            write!(f, "id {id} ")?;
+        }
+        if let Some(src) = self.src {
+            write!(f, "src ")?;
+            write_with_color!(f, addr_color, "{src}")?;
+            write!(f, " ")?;
+        }
+        if let Some(dst) = self.dst {
+            write!(f, "dst ")?;
+            write_with_color!(f, addr_color, "{dst}")?;
+            write!(f, " ")?;
+        }
+        if let Some(ttl) = self.ttl {
+            write!(f, "{} {ttl} ", self.ttl_name())?;
+        }
+        if let Some(tos) = self.tos {
+            write!(f, "{} {tos} ", self.tos_name())?;
+        }
+        for name in self.flag_names() {
+            write!(f, "{name} ")?;
+        }
+        Ok(())
+    }
+}
+
+// Like iproute2, the encapsulation is flattened into the route JSON object,
+// hence the `dst` of IP tunnel could be duplicate with the route one.
+impl Serialize for CliRouteEncap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("encap", &self.kind)?;
+        match &self.info {
+            CliRouteEncapInfo::Mpls { dst, ttl } => {
+                if let Some(dst) = dst {
+                    map.serialize_entry("dst", dst)?;
+                }
+                if let Some(ttl) = ttl {
+                    map.serialize_entry("ttl", ttl)?;
+                }
+            }
+            CliRouteEncapInfo::Ip(ip) => {
+                if let Some(id) = ip.id {
+                    map.serialize_entry("id", &id)?;
+                }
+                if let Some(src) = ip.src {
+                    map.serialize_entry("src", &src.to_string())?;
+                }
+                if let Some(dst) = ip.dst {
+                    map.serialize_entry("dst", &dst.to_string())?;
+                }
+                if let Some(ttl) = ip.ttl {
+                    map.serialize_entry(ip.ttl_name(), &ttl)?;
+                }
+                if let Some(tos) = ip.tos {
+                    map.serialize_entry(ip.tos_name(), &tos)?;
+                }
+                for name in ip.flag_names() {
+                    map.serialize_entry(name, &true)?;
+                }
+            }
+            CliRouteEncapInfo::Seg6 { mode, segs } => {
+                map.serialize_entry("mode", mode)?;
+                map.serialize_entry(
+                    "segs",
+                    &segs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                )?;
+            }
+            CliRouteEncapInfo::Unknown => (),
+        }
+        map.end()
+    }
+}
+
+/// The `encap TYPE ENCAPHDR` of `ip route add` following the argument
+/// grammar of iproute2 `lwt_parse_encap()`
+#[derive(Debug)]
+pub(crate) struct RouteEncapOptions {
+    kind: RouteLwEnCapType,
+    encap: Vec<RouteLwTunnelEncap>,
+}
+
+impl RouteEncapOptions {
+    /// Parse the arguments following `encap`, the arguments not belonging
+    /// to the encapsulation are left in `opts`.
+    pub(crate) fn parse(
+        opts: &mut std::slice::Iter<'_, &str>,
+    ) -> Result<Self, CliError> {
+        let value = next_opt(opts)?;
+        let kind = ENCAP_TYPE_NAMES
+            .iter()
+            .position(|name| !name.is_empty() && *name == value)
+            .ok_or_else(|| {
+                invalid_arg(value, "\"encap type\" value is invalid")
+            })? as u16;
+        // Like iproute2, at least two arguments are expected after the
+        // encapsulation type
+        if opts.as_slice().len() == 1 {
+            return Err(CliError::from(
+                "unexpected end of line after \"encap\"",
+            ));
+        }
+        let encap = match kind {
+            LWTUNNEL_ENCAP_MPLS => parse_encap_mpls(opts)?,
+            LWTUNNEL_ENCAP_IP => parse_encap_ip(opts, AddressFamily::Inet)?,
+            LWTUNNEL_ENCAP_IP6 => parse_encap_ip(opts, AddressFamily::Inet6)?,
+            LWTUNNEL_ENCAP_SEG6 => parse_encap_seg6(opts)?,
+            _ => {
+                return Err(CliError::from(
+                    format!("encap type \"{value}\" is not supported").as_str(),
+                ));
+            }
+        };
+        Ok(Self {
+            kind: RouteLwEnCapType::from(kind),
+            encap,
+        })
+    }
+
+    pub(super) fn to_nlas(&self) -> [RouteAttribute; 2] {
+        [
+            RouteAttribute::EncapType(self.kind),
+            RouteAttribute::Encap(self.encap.clone()),
+        ]
+    }
+
+    /// The encapsulation type and its raw nested attributes for netlink
+    /// messages other than route, e.g. `NHA_ENCAP_TYPE` and `NHA_ENCAP`.
+    pub(crate) fn to_raw(&self) -> (u16, Vec<u8>) {
+        let nla = RouteAttribute::Encap(self.encap.clone());
+        let mut buf = vec![0u8; nla.value_len()];
+        nla.emit_value(&mut buf);
+        (u16::from(self.kind), buf)
+    }
+}
+
+// Equal to iproute2 `parse_encap_mpls()`
+fn parse_encap_mpls(
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<Vec<RouteLwTunnelEncap>, CliError> {
+    let value = next_opt(opts)?;
+    let mut ret = vec![RouteLwTunnelEncap::Mpls(
+        RouteMplsIpTunnel::Destination(parse_mpls_labels(value)?),
+    )];
+    if opts.as_slice().first() == Some(&"ttl") {
+        opts.next();
+        let value = next_opt(opts)?;
+        ret.push(RouteLwTunnelEncap::Mpls(RouteMplsIpTunnel::Ttl(parse_num(
+            value,
+            "\"ttl\" value is invalid",
+        )?)));
+    }
+    Ok(ret)
+}
+
+// Equal to iproute2 `parse_encap_ip()` and `parse_encap_ip6()`
+fn parse_encap_ip(
+    opts: &mut std::slice::Iter<'_, &str>,
+    family: AddressFamily,
+) -> Result<Vec<RouteLwTunnelEncap>, CliError> {
+    let is_ipv6 = family == AddressFamily::Inet6;
+    let (ttl_name, tos_name) = if is_ipv6 {
+        ("hoplimit", "tc")
+    } else {
+        ("ttl", "tos")
+    };
+    let mut ret = Vec::new();
+    let mut flags = 0u16;
+    let mut push = |kind: u16, value: &[u8]| {
+        ret.push(RouteLwTunnelEncap::Other(DefaultNla::new(
+            kind,
+            value.to_vec(),
+        )));
+    };
+
+    while let Some(opt) = opts.as_slice().first() {
+        match *opt {
+            "id" => {
+                opts.next();
+                let id: u64 =
+                    parse_num(next_opt(opts)?, "\"id\" value is invalid")?;
+                push(LWTUNNEL_IP_ID, &id.to_be_bytes());
+            }
+            "dst" | "src" => {
+                opts.next();
+                let kind = if *opt == "dst" {
+                    LWTUNNEL_IP_DST
+                } else {
+                    LWTUNNEL_IP_SRC
+                };
+                match parse_ip_addr(next_opt(opts)?, family)? {
+                    IpAddr::V4(ip) => push(kind, &ip.octets()),
+                    IpAddr::V6(ip) => push(kind, &ip.octets()),
+                }
+            }
+            name if name == ttl_name => {
+                opts.next();
+                let ttl: u8 = parse_num(
+                    next_opt(opts)?,
+                    &format!("\"{ttl_name}\" value is invalid"),
+                )?;
+                push(LWTUNNEL_IP_TTL, &[ttl]);
+            }
+            name if name == tos_name => {
+                opts.next();
+                let value = next_opt(opts)?;
+                let tos = parse_tos(value).map_err(|_| {
+                    invalid_arg(
+                        value,
+                        &format!("\"{tos_name}\" value is invalid"),
+                    )
+                })?;
+                push(LWTUNNEL_IP_TOS, &[tos]);
+            }
+            name => {
+                let Some((flag, _)) =
+                    TUNNEL_FLAG_NAMES.iter().find(|(_, n)| *n == name)
+                else {
+                    break;
+                };
+                opts.next();
+                flags |= flag;
+            }
+        }
+    }
+    if flags != 0 {
+        push(LWTUNNEL_IP_FLAGS, &flags.to_be_bytes());
+    }
+    Ok(ret)
+}
+
+// Equal to iproute2 `parse_encap_seg6()`
+fn parse_encap_seg6(
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<Vec<RouteLwTunnelEncap>, CliError> {
+    let mut mode = None;
+    let mut segs = "";
+    while let Some(opt) = opts.as_slice().first() {
+        match *opt {
+            "mode" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                mode = Some(
+                    SEG6_MODE_NAMES
+                        .iter()
+                        .position(|name| *name == value)
+                        .ok_or_else(|| {
+                            invalid_arg(value, "\"mode\" value is invalid")
+                        })?,
+                );
+            }
+            "segs" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                if mode.is_none() {
+                    return Err(invalid_arg(
+                        value,
+                        "\"segs\" provided before \"mode\"",
+                    ));
+                }
+                segs = value;
+            }
+            _ => break,
+        }
+    }
+    let mode = mode.unwrap_or_default();
+
+    // Like iproute2 `parse_srh()`, the segments are stored in reverse order
+    // and the inline mode reserves the first one for the original
+    // destination
+    let mut segments = Vec::new();
+    if mode == 0 {
+        segments.push(Ipv6Addr::UNSPECIFIED);
+    }
+    for seg in segs.split(',').rev() {
+        match parse_ip_addr(seg, AddressFamily::Inet6)? {
+            IpAddr::V6(ip) => segments.push(ip),
+            IpAddr::V4(_) => unreachable!(),
+        }
+    }
+    Ok(vec![RouteLwTunnelEncap::Seg6(RouteSeg6IpTunnel::Seg6(
+        Seg6Header {
+            mode: match mode {
+                0 => Seg6Mode::Inline,
+                1 => Seg6Mode::Encap,
+                2 => Seg6Mode::L2Encap,
+                3 => Seg6Mode::EncapRed,
+                _ => Seg6Mode::L2EncapRed,
+            },
+            segments,
+        },
+    ))])
+}
+
+fn seg6_mode_to_name(mode: Seg6Mode) -> String {
+    let name = match mode {
+        Seg6Mode::Inline => "inline",
+        Seg6Mode::Encap => "encap",
+        Seg6Mode::L2Encap => "l2encap",
+        Seg6Mode::EncapRed => "encap.red",
+        Seg6Mode::L2EncapRed => "l2encap.red",
+        _ => "<unknown>",
+    };
+    name.to_string()
+}
+
+// Like iproute2 `mpls_ntop()`, the labels are separated by `/`
+fn mpls_labels_to_string(labels: &[MplsLabel]) -> String {
+    labels
+        .iter()
+        .map(|l| l.label.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Like iproute2 `mpls_pton()`, the bottom of stack is set on the last
+// label
+fn parse_mpls_labels(value: &str) -> Result<Vec<MplsLabel>, CliError> {
+    let values: Vec<&str> = value.split('/').collect();
+    let mut ret = Vec::new();
+    for (i, v) in values.iter().enumerate() {
+        let label = v
+            .parse::<u32>()
+            .ok()
+            .filter(|l| *l <= MPLS_LABEL_MAX)
+            .ok_or_else(|| {
+                CliError::from(
+                    format!(
+                        "mpls address is expected rather than \"{value}\"."
+                    )
+                    .as_str(),
+                )
+            })?;
+        let mut mpls_label = MplsLabel::default();
+        mpls_label.label = label;
+        mpls_label.bottom_of_stack = i + 1 == values.len();
+        ret.push(mpls_label);
+    }
+    Ok(ret)
+}
+
+fn bytes_to_ip(buf: &[u8]) -> Option<IpAddr> {
+    if let Ok(octets) = <[u8; 4]>::try_from(buf) {
+        Some(IpAddr::from(octets))
+    } else if let Ok(octets) = <[u8; 16]>::try_from(buf) {
+        Some(IpAddr::from(octets))
+    } else {
+        None
+    }
+}
+
+fn invalid_arg(value: &str, msg: &str) -> CliError {
+    CliError::from(format!("argument \"{value}\" is wrong: {msg}").as_str())
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    route::{RouteAttribute, RouteFlags, RouteMessage},
+};
+
+use super::{
+    names::{
+        RT_TABLE_LOCAL, RT_TABLE_MAIN, parse_protocol, parse_table, parse_type,
+    },
+    show::{ifname_to_index, route_addr_to_ip, route_table, route_via_to_ip},
+};
+use crate::{address::parse_scope, prefix::CliIpPrefix};
+
+const RTN_LOCAL: u8 = 2;
+
+/// Filters of `ip route show` following the argument grammar of iproute2
+/// `iproute_list_flush_or_save()`.
+#[derive(Debug)]
+pub(crate) struct RouteShowFilter<'a> {
+    /// `0` for all tables
+    pub(crate) table: u32,
+    dev: Option<&'a str>,
+    oif: Option<u32>,
+    /// `None` for `protocol all`
+    pub(crate) protocol: Option<u8>,
+    /// `None` for `scope all`
+    pub(crate) scope: Option<u8>,
+    kinds: Vec<u8>,
+    via: Option<CliIpPrefix>,
+    prefsrc: Option<CliIpPrefix>,
+    /// `root PREFIX`: routes inside the prefix
+    root: Option<CliIpPrefix>,
+    /// `match PREFIX`: routes covering the prefix
+    covering: Option<CliIpPrefix>,
+}
+
+impl Default for RouteShowFilter<'_> {
+    fn default() -> Self {
+        Self {
+            table: RT_TABLE_MAIN,
+            dev: None,
+            oif: None,
+            protocol: None,
+            scope: None,
+            kinds: Vec::new(),
+            via: None,
+            prefsrc: None,
+            root: None,
+            covering: None,
+        }
+    }
+}
+
+impl<'a> RouteShowFilter<'a> {
+    /// Like iproute2 `iproute_reset_filter()` used by `ip route get`, no
+    /// table is filtered, hence the table is shown unless it is main.
+    pub(crate) fn new_unfiltered() -> Self {
+        Self {
+            table: 0,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "table" => {
+                    let table = next_opt(&mut opts)?;
+                    // The `all` stands for table `0` which matches all
+                    // tables
+                    ret.table = if table == "all" {
+                        0
+                    } else {
+                        parse_table(table, "table id value is invalid")?
+                    };
+                }
+                "dev" | "oif" => {
+                    ret.dev = Some(next_opt(&mut opts)?);
+                }
+                "protocol" | "proto" => {
+                    let protocol = next_opt(&mut opts)?;
+                    ret.protocol = if protocol == "all" {
+                        None
+                    } else {
+                        Some(parse_protocol(protocol, "invalid \"protocol\"")?)
+                    };
+                }
+                "scope" => {
+                    let scope = next_opt(&mut opts)?;
+                    ret.scope = if scope == "all" {
+                        None
+                    } else {
+                        Some(parse_scope(scope, "invalid \"scope\"")?)
+                    };
+                }
+                "type" => {
+                    ret.kinds.push(parse_type(next_opt(&mut opts)?)?);
+                }
+                "via" => {
+                    ret.via =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "src" => {
+                    ret.prefsrc =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "root" => {
+                    ret.root =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "match" => {
+                    ret.covering =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                _ => {
+                    let mut value = *opt;
+                    if value == "to" {
+                        value = next_opt(&mut opts)?;
+                    }
+                    if value == "exact" {
+                        value = next_opt(&mut opts)?;
+                    }
+                    let prefix = CliIpPrefix::parse(value, family)?;
+                    ret.root = Some(prefix);
+                    ret.covering = Some(prefix);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Resolve the `dev` filter to interface index.
+    pub(crate) fn resolve_dev(
+        &mut self,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<(), CliError> {
+        let Some(dev) = self.dev else {
+            return Ok(());
+        };
+        let index = ifname_to_index(ifnames, dev)?;
+        self.oif = Some(index);
+        Ok(())
+    }
+
+    /// Like iproute2, the output interface is not shown when filtering on
+    /// it.
+    pub(crate) fn hide_dev(&self) -> bool {
+        self.oif.is_some()
+    }
+
+    /// Like iproute2, the gateway is not shown when filtering on a host
+    /// address of it.
+    pub(crate) fn hide_via(&self) -> bool {
+        self.via
+            .is_some_and(|p| p.prefix_len == max_prefix_len(&p.addr))
+    }
+
+    pub(crate) fn hide_prefsrc(&self) -> bool {
+        self.prefsrc
+            .is_some_and(|p| p.prefix_len == max_prefix_len(&p.addr))
+    }
+
+    /// Equal to iproute2 `filter_nlmsg()`
+    pub(crate) fn matches(&self, nl_msg: &RouteMessage) -> bool {
+        let header = &nl_msg.header;
+        let table = route_table(nl_msg);
+        let kind = u8::from(header.kind);
+
+        if header.address_family == AddressFamily::Inet6
+            && table != RT_TABLE_LOCAL
+        {
+            if self.table != 0 {
+                // Cloned IPv6 routes belong to the route cache
+                if header.flags.contains(RouteFlags::Cloned) {
+                    return false;
+                }
+                if self.table == RT_TABLE_LOCAL {
+                    if kind != RTN_LOCAL {
+                        return false;
+                    }
+                } else if self.table != table {
+                    return false;
+                }
+            }
+        } else if self.table != 0 && self.table != table {
+            return false;
+        }
+
+        if !self.kinds.is_empty() && !self.kinds.contains(&kind) {
+            return false;
+        }
+        if let Some(protocol) = self.protocol
+            && u8::from(header.protocol) != protocol
+        {
+            return false;
+        }
+        if let Some(scope) = self.scope
+            && u8::from(header.scope) != scope
+        {
+            return false;
+        }
+
+        let mut dst = None;
+        let mut oif = None;
+        let mut via = None;
+        let mut prefsrc = None;
+        for nla in nl_msg.attributes.iter() {
+            match nla {
+                RouteAttribute::Destination(a) => dst = route_addr_to_ip(a),
+                RouteAttribute::Oif(i) => oif = Some(*i),
+                RouteAttribute::Gateway(a) => via = route_addr_to_ip(a),
+                RouteAttribute::Via(v) => via = route_via_to_ip(v),
+                RouteAttribute::PrefSource(a) => prefsrc = route_addr_to_ip(a),
+                _ => (),
+            }
+        }
+        let family = header.address_family;
+        let dst = dst.unwrap_or_else(|| unspecified_addr(family));
+        let dst_len = header.destination_prefix_length;
+
+        if let Some(root) = self.root
+            && (!is_same_family(&root.addr, &dst)
+                || root.prefix_len > dst_len
+                || !root.contains(&dst))
+        {
+            return false;
+        }
+        if let Some(covering) = self.covering
+            && (!is_same_family(&covering.addr, &dst)
+                || covering.prefix_len < dst_len
+                || !CliIpPrefix {
+                    addr: covering.addr,
+                    prefix_len: dst_len,
+                }
+                .contains(&dst))
+        {
+            return false;
+        }
+        if let Some(index) = self.oif
+            && oif != Some(index)
+        {
+            return false;
+        }
+        if let Some(prefix) = self.via.filter(|p| p.prefix_len > 0)
+            && !prefix
+                .contains(&via.unwrap_or_else(|| unspecified_addr(family)))
+        {
+            return false;
+        }
+        if let Some(prefix) = self.prefsrc.filter(|p| p.prefix_len > 0)
+            && !prefix
+                .contains(&prefsrc.unwrap_or_else(|| unspecified_addr(family)))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    if addr.is_ipv4() { 32 } else { 128 }
+}
+
+fn is_same_family(a: &IpAddr, b: &IpAddr) -> bool {
+    a.is_ipv4() == b.is_ipv4()
+}
+
+fn unspecified_addr(family: AddressFamily) -> IpAddr {
+    if family == AddressFamily::Inet6 {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    }
+}
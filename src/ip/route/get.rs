@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, io::Write};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_REQUEST, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        route::{RouteAttribute, RouteFlags, RouteMessage},
+    },
+};
+
+use super::{
+    filter::RouteShowFilter,
+    names::parse_tos,
+    show::{
+        CliRouteInfo, get_ifnames, ifname_to_index, ip_to_route_addr,
+        parse_nl_msg_to_route, resolve_route_hostnames,
+    },
+};
+use crate::{link::parse_num, prefix::CliIpPrefix};
+
+/// Options of `ip route get` following the argument grammar of iproute2
+/// `iproute_get()`.
+#[derive(Debug, Default)]
+struct RouteGetOptions<'a> {
+    family: AddressFamily,
+    dst: Option<CliIpPrefix>,
+    src: Option<CliIpPrefix>,
+    iif: Option<&'a str>,
+    oif: Option<&'a str>,
+    mark: u32,
+    uid: Option<u32>,
+    tos: u8,
+    flags: RouteFlags,
+}
+
+impl<'a> RouteGetOptions<'a> {
+    fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            family,
+            ..Default::default()
+        };
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "tos" | "dsfield" => {
+                    ret.tos = parse_tos(next_opt(&mut opts)?)?;
+                }
+                "from" => {
+                    let prefix =
+                        CliIpPrefix::parse(next_opt(&mut opts)?, ret.family)?;
+                    ret.set_family(&prefix);
+                    ret.src = Some(prefix);
+                }
+                "iif" => ret.iif = Some(next_opt(&mut opts)?),
+                "oif" | "dev" => ret.oif = Some(next_opt(&mut opts)?),
+                "mark" => {
+                    ret.mark =
+                        parse_num(next_opt(&mut opts)?, "invalid mark value")?;
+                }
+                "uid" => {
+                    ret.uid =
+                        Some(parse_num(next_opt(&mut opts)?, "invalid UID")?);
+                }
+                "notify" => ret.flags |= RouteFlags::Notify,
+                "fibmatch" => ret.flags |= RouteFlags::FibMatch,
+                _ => {
+                    let value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    let mut prefix = CliIpPrefix::parse(value, ret.family)?;
+                    ret.set_family(&prefix);
+                    // Like iproute2, only host address is supported
+                    let host_len = if prefix.addr.is_ipv4() { 32 } else { 128 };
+                    if prefix.prefix_len != host_len {
+                        writeln!(
+                            std::io::stderr(),
+                            "Warning: /{} as prefix is invalid, only \
+                             /{host_len} (or none) is supported.",
+                            prefix.prefix_len
+                        )
+                        .ok();
+                        prefix.prefix_len = host_len;
+                    }
+                    ret.dst = Some(prefix);
+                }
+            }
+        }
+        if ret.dst.is_none() {
+            return Err(CliError::from("need at least a destination address"));
+        }
+        if ret.family == AddressFamily::Unspec {
+            ret.family = AddressFamily::Inet;
+        }
+        Ok(ret)
+    }
+
+    fn set_family(&mut self, prefix: &CliIpPrefix) {
+        if self.family == AddressFamily::Unspec {
+            self.family = if prefix.addr.is_ipv4() {
+                AddressFamily::Inet
+            } else {
+                AddressFamily::Inet6
+            };
+        }
+    }
+
+    fn to_nl_msg(
+        &self,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<RouteMessage, CliError> {
+        let mut nl_msg = RouteMessage::default();
+        nl_msg.header.address_family = self.family;
+        nl_msg.header.tos = self.tos;
+        nl_msg.header.flags = self.flags;
+        // Only IPv4 supports looking up the table of the matched route
+        if self.family == AddressFamily::Inet {
+            nl_msg.header.flags |= RouteFlags::LookupTable;
+        }
+        if let Some(dst) = self.dst {
+            nl_msg.header.destination_prefix_length = dst.prefix_len;
+            nl_msg
+                .attributes
+                .push(RouteAttribute::Destination(ip_to_route_addr(dst.addr)));
+        }
+        if let Some(src) = self.src {
+            nl_msg.header.source_prefix_length = src.prefix_len;
+            // The `from default` sets zero source prefix length only
+            if src.prefix_len != 0 {
+                nl_msg
+                    .attributes
+                    .push(RouteAttribute::Source(ip_to_route_addr(src.addr)));
+            }
+        }
+        if let Some(uid) = self.uid {
+            nl_msg.attributes.push(RouteAttribute::Uid(uid));
+        }
+        if let Some(iif) = self.iif {
+            nl_msg
+                .attributes
+                .push(RouteAttribute::Iif(ifname_to_index(ifnames, iif)?));
+        }
+        if let Some(oif) = self.oif {
+            nl_msg
+                .attributes
+                .push(RouteAttribute::Oif(ifname_to_index(ifnames, oif)?));
+        }
+        if self.mark != 0 {
+            nl_msg.attributes.push(RouteAttribute::Mark(self.mark));
+        }
+        Ok(nl_msg)
+    }
+}
+
+pub(crate) async fn handle_get(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    include_details: bool,
+    numeric: bool,
+    resolve: bool,
+    show_stats: bool,
+) -> Result<Vec<CliRouteInfo>, CliError> {
+    let get_opts = RouteGetOptions::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+
+    // The rtnetlink crate always dumps routes, hence compose the request
+    // for the kernel route lookup
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::GetRoute(
+            get_opts.to_nl_msg(&ifnames)?,
+        )),
+    );
+    request.header.flags = NLM_F_REQUEST;
+
+    let filter = RouteShowFilter::new_unfiltered();
+    let mut ret = Vec::new();
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        match msg.payload {
+            NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewRoute(
+                nl_msg,
+            )) => {
+                ret.push(parse_nl_msg_to_route(
+                    nl_msg,
+                    &filter,
+                    &ifnames,
+                    include_details,
+                    numeric,
+                    show_stats,
+                ));
+            }
+            NetlinkPayload::Error(e) if e.code.is_some() => {
+                return Err(rtnetlink::Error::NetlinkError(e).into());
+            }
+            _ => (),
+        }
+    }
+    if resolve {
+        resolve_route_hostnames(&mut ret).await;
+    }
+    Ok(ret)
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod encap;
+mod filter;
+mod get;
+mod modify;
+mod names;
+mod save;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::RouteCommand,
+    encap::{CliRouteEncap, RouteEncapOptions},
+    filter::RouteShowFilter,
+    names::{
+        RT_TABLE_DEFAULT, RT_TABLE_MAIN, parse_protocol, parse_realms,
+        parse_table, parse_tos, parse_type, protocol_to_name, realm_to_name,
+        scope_to_name, table_to_name, type_to_name,
+    },
+    save::{emit_nl_msg, read_nl_dump, split_nl_msgs, write_nl_dump},
+    show::{
+        CliRouteInfo, format_float, get_ifnames, ifname_to_index,
+        parse_nl_msg_to_route, route_flags_to_names, route_table,
+    },
+};
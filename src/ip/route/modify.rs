@@ -0,0 +1,712 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NLM_F_ACK, NLM_F_APPEND, NLM_F_CREATE, NLM_F_EXCL,
+        NLM_F_REPLACE, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        route::{
+            RouteAttribute, RouteFlags, RouteMessage, RouteMetric,
+            RouteNextHop, RouteNextHopFlags, RoutePreference, RouteProtocol,
+            RouteRealm, RouteScope, RouteType, RouteVia,
+        },
+    },
+};
+
+use super::{
+    encap::RouteEncapOptions,
+    names::{
+        METRIC_NAMES, RT_TABLE_LOCAL, RT_TABLE_MAIN, RTAX_CC_ALGO,
+        RTAX_FEATURE_ECN, RTAX_RTO_MIN, parse_protocol, parse_realms,
+        parse_table, parse_tos, parse_type,
+    },
+    show::{get_ifnames, ifname_to_index, ip_to_route_addr},
+};
+use crate::{
+    address::parse_scope,
+    link::parse_num,
+    prefix::{CliIpPrefix, parse_ip_addr},
+};
+
+const RTN_UNSPEC: u8 = 0;
+const RTN_UNICAST: u8 = 1;
+const RTN_LOCAL: u8 = 2;
+const RTN_BROADCAST: u8 = 3;
+const RTN_ANYCAST: u8 = 4;
+const RTN_MULTICAST: u8 = 5;
+const RTN_NAT: u8 = 10;
+const RTPROT_UNSPEC: u8 = 0;
+const RTPROT_BOOT: u8 = 3;
+const RT_SCOPE_UNIVERSE: u8 = 0;
+const RT_SCOPE_LINK: u8 = 253;
+const RT_SCOPE_HOST: u8 = 254;
+const RT_SCOPE_NOWHERE: u8 = 255;
+
+// Equal to iproute2 `ICMPV6_ROUTER_PREF_*`
+const ROUTE_PREF_NAMES: [(&str, u8); 3] =
+    [("low", 3), ("medium", 0), ("high", 1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RouteModifyCmd {
+    /// Create new route, fail if exists
+    Add,
+    /// Update existing route only
+    Change,
+    /// Update existing route or create new one
+    Replace,
+    /// Add route after the existing ones of the same destination
+    Append,
+    /// Add route before the existing ones of the same destination
+    Prepend,
+    Delete,
+}
+
+impl RouteModifyCmd {
+    // Equal to the netlink flags used by iproute2 `do_iproute()`
+    fn nl_flags(self) -> u16 {
+        match self {
+            Self::Add => NLM_F_CREATE | NLM_F_EXCL,
+            Self::Change => NLM_F_REPLACE,
+            Self::Replace => NLM_F_CREATE | NLM_F_REPLACE,
+            Self::Append => NLM_F_CREATE | NLM_F_APPEND,
+            Self::Prepend => NLM_F_CREATE,
+            Self::Delete => 0,
+        }
+    }
+}
+
+/// Options of `ip route add`, `change`, `replace`, `append`, `prepend` and
+/// `delete` following the argument grammar of iproute2 `iproute_modify()`.
+#[derive(Debug, Default)]
+struct RouteModifyOptions<'a> {
+    family: AddressFamily,
+    /// The `RTN_*` route type prefixing the destination
+    kind: Option<u8>,
+    dst: Option<CliIpPrefix>,
+    src: Option<CliIpPrefix>,
+    prefsrc: Option<IpAddr>,
+    gateway: Option<IpAddr>,
+    dev: Option<&'a str>,
+    tos: u8,
+    table: Option<u32>,
+    protocol: Option<u8>,
+    scope: Option<u8>,
+    metric: Option<u32>,
+    realm: Option<RouteRealm>,
+    pref: Option<u8>,
+    expires: Option<u32>,
+    flags: RouteFlags,
+    metrics: Vec<RouteMetric>,
+    /// Bits of `RTAX_*` not allowed to be updated by kernel
+    metrics_lock: u32,
+    encap: Option<RouteEncapOptions>,
+    nexthops: Vec<RouteNextHopOptions<'a>>,
+}
+
+/// The `nexthop NH` of multipath route following the argument grammar of
+/// iproute2 `parse_one_nh()`.
+#[derive(Debug, Default)]
+struct RouteNextHopOptions<'a> {
+    gateway: Option<IpAddr>,
+    dev: Option<&'a str>,
+    /// The weight minus one
+    hops: u8,
+    flags: RouteNextHopFlags,
+    realm: Option<RouteRealm>,
+    encap: Option<RouteEncapOptions>,
+}
+
+impl<'a> RouteModifyOptions<'a> {
+    fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            family,
+            ..Default::default()
+        };
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "src" => {
+                    let addr = parse_ip_addr(next_opt(&mut opts)?, ret.family)?;
+                    ret.set_family(&addr);
+                    ret.prefsrc = Some(addr);
+                }
+                "via" => {
+                    if ret.gateway.is_some() {
+                        return Err(CliError::from(
+                            "argument \"via\" is wrong: use nexthop syntax to \
+                             specify multiple via",
+                        ));
+                    }
+                    ret.gateway = Some(ret.parse_via(&mut opts)?);
+                }
+                "from" => {
+                    let prefix =
+                        CliIpPrefix::parse(next_opt(&mut opts)?, ret.family)?;
+                    ret.set_family(&prefix.addr);
+                    ret.src = Some(prefix);
+                }
+                "tos" | "dsfield" => {
+                    ret.tos = parse_tos(next_opt(&mut opts)?)?;
+                }
+                "expires" => {
+                    ret.expires = Some(parse_num(
+                        next_opt(&mut opts)?,
+                        "\"expires\" value is invalid",
+                    )?);
+                }
+                "metric" | "priority" | "preference" => {
+                    ret.metric = Some(parse_num(
+                        next_opt(&mut opts)?,
+                        "\"metric\" value is invalid",
+                    )?);
+                }
+                "realm" | "realms" => {
+                    ret.realm = Some(parse_realms(
+                        next_opt(&mut opts)?,
+                        "\"realm\" value is invalid",
+                    )?);
+                }
+                "scope" => {
+                    ret.scope = Some(parse_scope(
+                        next_opt(&mut opts)?,
+                        "invalid \"scope\" value",
+                    )?);
+                }
+                "onlink" => ret.flags |= RouteFlags::Onlink,
+                "pervasive" => ret.flags |= RouteFlags::Pervasive,
+                "protocol" | "proto" => {
+                    ret.protocol = Some(parse_protocol(
+                        next_opt(&mut opts)?,
+                        "\"protocol\" value is invalid",
+                    )?);
+                }
+                "table" => {
+                    ret.table = Some(parse_table(
+                        next_opt(&mut opts)?,
+                        "\"table\" value is invalid",
+                    )?);
+                }
+                "dev" | "oif" => ret.dev = Some(next_opt(&mut opts)?),
+                "mtu" | "window" | "rtt" | "rttvar" | "ssthresh" | "cwnd"
+                | "advmss" | "reordering" | "hoplimit" | "initcwnd"
+                | "features" | "rto_min" | "initrwnd" | "quickack"
+                | "congctl" | "fastopen_no_cookie" => {
+                    ret.parse_metric(opt, &mut opts)?;
+                }
+                "encap" => {
+                    ret.encap = Some(RouteEncapOptions::parse(&mut opts)?);
+                }
+                "nexthop" => {
+                    // Like iproute2, all the remaining arguments are
+                    // nexthops
+                    ret.parse_nexthops(&mut opts)?;
+                    break;
+                }
+                "pref" => {
+                    let value = next_opt(&mut opts)?;
+                    ret.pref = Some(
+                        ROUTE_PREF_NAMES
+                            .iter()
+                            .find(|(name, _)| *name == value)
+                            .map(|(_, pref)| *pref)
+                            .ok_or_else(|| {
+                                CliError::from(
+                                    format!(
+                                        "argument \"{value}\" is wrong: \
+                                         \"pref\" value is invalid"
+                                    )
+                                    .as_str(),
+                                )
+                            })?,
+                    );
+                }
+                _ => {
+                    let mut value = if *opt == "to" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    // Like iproute2, the destination could be prefixed by
+                    // the route type, e.g. `blackhole 10.0.0.0/8`
+                    if !value.starts_with(|c: char| c.is_ascii_digit())
+                        && let Ok(kind) = parse_type(value)
+                    {
+                        ret.kind = Some(kind);
+                        value = next_opt(&mut opts)?;
+                    }
+                    if ret.dst.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"to\" is duplicate, or \"{value}\" \
+                                 is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    let prefix = CliIpPrefix::parse(value, ret.family)?;
+                    ret.set_family(&prefix.addr);
+                    ret.dst = Some(prefix);
+                }
+            }
+        }
+        if ret.dst.is_none() {
+            return Err(CliError::from(
+                "Usage: ip route { add | del | change | append | replace } \
+                 ROUTE",
+            ));
+        }
+        if ret.family == AddressFamily::Unspec {
+            ret.family = AddressFamily::Inet;
+        }
+        Ok(ret)
+    }
+
+    // Like iproute2, most metrics could be prefixed by `lock` to prevent
+    // kernel from updating it.
+    fn parse_metric(
+        &mut self,
+        name: &str,
+        opts: &mut std::slice::Iter<'_, &'a str>,
+    ) -> Result<(), CliError> {
+        let index = METRIC_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .unwrap_or_default();
+        let mut value = next_opt(opts)?;
+        if value == "lock"
+            && !matches!(
+                name,
+                "features" | "rto_min" | "quickack" | "fastopen_no_cookie"
+            )
+        {
+            self.metrics_lock |= 1 << index;
+            value = next_opt(opts)?;
+        }
+        let invalid = |msg: &str| {
+            CliError::from(
+                format!("argument \"{value}\" is wrong: {msg}").as_str(),
+            )
+        };
+        // iproute2 names the `advmss` as `mss` in error
+        let error_msg = format!(
+            "\"{}\" value is invalid",
+            if name == "advmss" { "mss" } else { name }
+        );
+
+        let metric = match name {
+            "rtt" | "rttvar" | "rto_min" => {
+                let (time, raw) =
+                    parse_time_rtt(value).ok_or_else(|| invalid(&error_msg))?;
+                // The kernel stores RTT in 1/8 ms and RTT variance in
+                // 1/4 ms
+                match name {
+                    "rtt" => RouteMetric::Rtt(if raw {
+                        time
+                    } else {
+                        time.wrapping_mul(8)
+                    }),
+                    "rttvar" => RouteMetric::RttVar(if raw {
+                        time
+                    } else {
+                        time.wrapping_mul(4)
+                    }),
+                    _ => {
+                        // Like iproute2, the `rto_min` is always locked
+                        self.metrics_lock |= 1 << RTAX_RTO_MIN;
+                        RouteMetric::RtoMin(time)
+                    }
+                }
+            }
+            "features" => {
+                if value != "ecn" {
+                    return Err(invalid("\"features\" value not valid"));
+                }
+                RouteMetric::Features(RTAX_FEATURE_ECN)
+            }
+            "congctl" => RouteMetric::Other(DefaultNla::new(
+                RTAX_CC_ALGO,
+                value.as_bytes().to_vec(),
+            )),
+            _ => {
+                let num: u32 = parse_num(value, &error_msg)?;
+                match name {
+                    "mtu" => RouteMetric::Mtu(num),
+                    "window" => RouteMetric::Window(num),
+                    "ssthresh" => RouteMetric::SsThresh(num),
+                    "cwnd" => RouteMetric::Cwnd(num),
+                    "advmss" => RouteMetric::Advmss(num),
+                    "reordering" => RouteMetric::Reordering(num),
+                    "initcwnd" => RouteMetric::InitCwnd(num),
+                    "initrwnd" => RouteMetric::InitRwnd(num),
+                    "hoplimit" => {
+                        if num > 255 {
+                            return Err(invalid(&error_msg));
+                        }
+                        RouteMetric::Hoplimit(num)
+                    }
+                    _ => {
+                        if num > 1 {
+                            return Err(invalid(&format!(
+                                "\"{name}\" value should be 0 or 1"
+                            )));
+                        }
+                        if name == "quickack" {
+                            RouteMetric::QuickAck(num)
+                        } else {
+                            RouteMetric::FastopenNoCookie(num)
+                        }
+                    }
+                }
+            }
+        };
+        self.metrics.push(metric);
+        Ok(())
+    }
+
+    // The gateway could be of different family prefixed by the family name,
+    // e.g. IPv4 route via IPv6 gateway
+    fn parse_via(
+        &mut self,
+        opts: &mut std::slice::Iter<'_, &'a str>,
+    ) -> Result<IpAddr, CliError> {
+        let mut value = next_opt(opts)?;
+        let family = match value {
+            "inet" => AddressFamily::Inet,
+            "inet6" => AddressFamily::Inet6,
+            _ => AddressFamily::Unspec,
+        };
+        let family = if family == AddressFamily::Unspec {
+            self.family
+        } else {
+            value = next_opt(opts)?;
+            family
+        };
+        let addr = parse_ip_addr(value, family)?;
+        self.set_family(&addr);
+        Ok(addr)
+    }
+
+    fn parse_nexthops(
+        &mut self,
+        opts: &mut std::slice::Iter<'_, &'a str>,
+    ) -> Result<(), CliError> {
+        loop {
+            if opts.as_slice().is_empty() {
+                return Err(CliError::from(
+                    "unexpected end of line after \"nexthop\"",
+                ));
+            }
+            let mut nexthop = RouteNextHopOptions::default();
+            while let Some(opt) = opts.as_slice().first() {
+                match *opt {
+                    "via" => {
+                        opts.next();
+                        nexthop.gateway = Some(self.parse_via(opts)?);
+                    }
+                    "dev" => {
+                        opts.next();
+                        nexthop.dev = Some(next_opt(opts)?);
+                    }
+                    "weight" => {
+                        opts.next();
+                        let value = next_opt(opts)?;
+                        let weight: u32 =
+                            parse_num(value, "\"weight\" is invalid")?;
+                        if weight == 0 || weight > 256 {
+                            return Err(CliError::from(
+                                format!(
+                                    "argument \"{value}\" is wrong: \
+                                     \"weight\" is invalid"
+                                )
+                                .as_str(),
+                            ));
+                        }
+                        nexthop.hops = (weight - 1) as u8;
+                    }
+                    "onlink" => {
+                        opts.next();
+                        nexthop.flags |= RouteNextHopFlags::Onlink;
+                    }
+                    "realm" | "realms" => {
+                        opts.next();
+                        nexthop.realm = Some(parse_realms(
+                            next_opt(opts)?,
+                            "\"realm\" value is invalid",
+                        )?);
+                    }
+                    "encap" => {
+                        opts.next();
+                        nexthop.encap = Some(RouteEncapOptions::parse(opts)?);
+                    }
+                    _ => break,
+                }
+            }
+            self.nexthops.push(nexthop);
+
+            match opts.next() {
+                None => return Ok(()),
+                Some(&"nexthop") => (),
+                Some(opt) => {
+                    return Err(CliError::from(
+                        format!(
+                            "\"nexthop\" or end of line is expected instead \
+                             of \"{opt}\""
+                        )
+                        .as_str(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn set_family(&mut self, addr: &IpAddr) {
+        if self.family == AddressFamily::Unspec {
+            self.family = if addr.is_ipv4() {
+                AddressFamily::Inet
+            } else {
+                AddressFamily::Inet6
+            };
+        }
+    }
+
+    // Like iproute2 `iproute_modify()`, guess the scope from route type when
+    // not specified
+    fn default_scope(&self, cmd: RouteModifyCmd, kind: u8) -> u8 {
+        let is_delete = cmd == RouteModifyCmd::Delete;
+        if self.family == AddressFamily::Inet6 {
+            return RT_SCOPE_UNIVERSE;
+        }
+        match kind {
+            RTN_LOCAL | RTN_NAT => RT_SCOPE_HOST,
+            RTN_BROADCAST | RTN_MULTICAST | RTN_ANYCAST => RT_SCOPE_LINK,
+            RTN_UNSPEC | RTN_UNICAST
+                if !is_delete
+                    && self.gateway.is_none()
+                    && self.nexthops.is_empty() =>
+            {
+                RT_SCOPE_LINK
+            }
+            _ if is_delete => RT_SCOPE_NOWHERE,
+            _ => RT_SCOPE_UNIVERSE,
+        }
+    }
+
+    // Like iproute2, routes of local addresses go to the local table by
+    // default
+    fn default_table(&self) -> u32 {
+        match self.kind {
+            Some(RTN_LOCAL | RTN_BROADCAST | RTN_NAT | RTN_ANYCAST) => {
+                RT_TABLE_LOCAL
+            }
+            _ => RT_TABLE_MAIN,
+        }
+    }
+
+    fn to_nl_msg(
+        &self,
+        cmd: RouteModifyCmd,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<RouteMessage, CliError> {
+        let is_delete = cmd == RouteModifyCmd::Delete;
+        let mut nl_msg = RouteMessage::default();
+        nl_msg.header.address_family = self.family;
+        nl_msg.header.tos = self.tos;
+        nl_msg.header.flags = self.flags;
+        nl_msg.header.protocol =
+            RouteProtocol::from(self.protocol.unwrap_or(if is_delete {
+                RTPROT_UNSPEC
+            } else {
+                RTPROT_BOOT
+            }));
+        let kind = self.kind.unwrap_or(if is_delete {
+            RTN_UNSPEC
+        } else {
+            RTN_UNICAST
+        });
+        nl_msg.header.kind = RouteType::from(kind);
+        nl_msg.header.scope = RouteScope::from(
+            self.scope.unwrap_or_else(|| self.default_scope(cmd, kind)),
+        );
+
+        // Table ID larger than 255 can only be stored in `RTA_TABLE`
+        let table = self.table.unwrap_or_else(|| self.default_table());
+        match u8::try_from(table) {
+            Ok(table) => nl_msg.header.table = table,
+            Err(_) => {
+                nl_msg.header.table = 0;
+                nl_msg.attributes.push(RouteAttribute::Table(table));
+            }
+        }
+
+        if let Some(dst) = self.dst {
+            nl_msg.header.destination_prefix_length = dst.prefix_len;
+            // The `default` sets zero destination prefix length only
+            if dst.prefix_len != 0 {
+                nl_msg.attributes.push(RouteAttribute::Destination(
+                    ip_to_route_addr(dst.addr),
+                ));
+            }
+        }
+        if let Some(src) = self.src {
+            nl_msg.header.source_prefix_length = src.prefix_len;
+            if src.prefix_len != 0 {
+                nl_msg
+                    .attributes
+                    .push(RouteAttribute::Source(ip_to_route_addr(src.addr)));
+            }
+        }
+        if let Some(prefsrc) = self.prefsrc {
+            nl_msg
+                .attributes
+                .push(RouteAttribute::PrefSource(ip_to_route_addr(prefsrc)));
+        }
+        if let Some(gateway) = self.gateway {
+            nl_msg.attributes.push(gateway_to_nla(gateway, self.family));
+        }
+        if let Some(metric) = self.metric {
+            nl_msg.attributes.push(RouteAttribute::Priority(metric));
+        }
+        if let Some(realm) = self.realm {
+            nl_msg.attributes.push(RouteAttribute::Realm(realm));
+        }
+        if let Some(expires) = self.expires {
+            nl_msg.attributes.push(RouteAttribute::Expires(expires));
+        }
+        if let Some(pref) = self.pref {
+            nl_msg
+                .attributes
+                .push(RouteAttribute::Preference(RoutePreference::from(pref)));
+        }
+        if let Some(dev) = self.dev {
+            nl_msg
+                .attributes
+                .push(RouteAttribute::Oif(ifname_to_index(ifnames, dev)?));
+        }
+        if !self.metrics.is_empty() {
+            let mut metrics = self.metrics.clone();
+            if self.metrics_lock != 0 {
+                metrics.push(RouteMetric::Lock(self.metrics_lock));
+            }
+            nl_msg.attributes.push(RouteAttribute::Metrics(metrics));
+        }
+        if let Some(encap) = &self.encap {
+            nl_msg.attributes.extend(encap.to_nlas());
+        }
+        if !self.nexthops.is_empty() {
+            let mut nexthops = Vec::new();
+            for nexthop in self.nexthops.iter() {
+                nexthops.push(nexthop.to_route_next_hop(self.family, ifnames)?);
+            }
+            nl_msg.attributes.push(RouteAttribute::MultiPath(nexthops));
+        }
+        Ok(nl_msg)
+    }
+}
+
+impl RouteNextHopOptions<'_> {
+    fn to_route_next_hop(
+        &self,
+        family: AddressFamily,
+        ifnames: &HashMap<u32, String>,
+    ) -> Result<RouteNextHop, CliError> {
+        let mut ret = RouteNextHop::default();
+        ret.flags = self.flags;
+        ret.hops = self.hops;
+        if let Some(dev) = self.dev {
+            ret.interface_index = ifname_to_index(ifnames, dev)?;
+        }
+        if let Some(gateway) = self.gateway {
+            ret.attributes.push(gateway_to_nla(gateway, family));
+        }
+        if let Some(realm) = self.realm {
+            ret.attributes.push(RouteAttribute::Realm(realm));
+        }
+        if let Some(encap) = &self.encap {
+            ret.attributes.extend(encap.to_nlas());
+        }
+        Ok(ret)
+    }
+}
+
+// Like iproute2 `get_time_rtt()`, the time without unit is the raw value
+// stored in kernel, while the time with `s` or `ms` unit is in milliseconds.
+// The second item of returned tuple indicates whether it is raw.
+fn parse_time_rtt(value: &str) -> Option<(u32, bool)> {
+    let (num, unit) = value.split_at(
+        value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len()),
+    );
+    let mut time: f64 = if num.contains('.') {
+        num.parse().ok()?
+    } else {
+        num.parse::<u32>().ok()?.into()
+    };
+    match unit.to_ascii_lowercase().as_str() {
+        "" | "ms" | "msec" | "msecs" => (),
+        "s" | "sec" | "secs" => time *= 1000.0,
+        _ => return None,
+    }
+    Some((time.ceil() as u32, unit.is_empty()))
+}
+
+// Like iproute2, gateway of different family is stored in `RTA_VIA`
+fn gateway_to_nla(gateway: IpAddr, family: AddressFamily) -> RouteAttribute {
+    let is_same_family = match gateway {
+        IpAddr::V4(_) => family == AddressFamily::Inet,
+        IpAddr::V6(_) => family == AddressFamily::Inet6,
+    };
+    if is_same_family {
+        RouteAttribute::Gateway(ip_to_route_addr(gateway))
+    } else {
+        RouteAttribute::Via(match gateway {
+            IpAddr::V4(ip) => RouteVia::Inet(ip),
+            IpAddr::V6(ip) => RouteVia::Inet6(ip),
+        })
+    }
+}
+
+pub(crate) async fn handle_modify(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    cmd: RouteModifyCmd,
+) -> Result<(), CliError> {
+    let modify_opts = RouteModifyOptions::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+    let nl_msg = modify_opts.to_nl_msg(cmd, &ifnames)?;
+
+    // The rtnetlink crate has no request for `change`, `append` and
+    // `prepend`, hence compose the request with the netlink flags of
+    // iproute2.
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(if cmd == RouteModifyCmd::Delete {
+            RouteNetlinkMessage::DelRoute(nl_msg)
+        } else {
+            RouteNetlinkMessage::NewRoute(nl_msg)
+        }),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK | cmd.nl_flags();
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
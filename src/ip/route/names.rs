@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: MIT
+
+use std::{io::Write, path::Path, sync::OnceLock};
+
+use indexmap::IndexMap;
+use iproute_rs::CliError;
+use rtnetlink::packet_route::route::RouteRealm;
+
+use crate::{address::SCOPE_NAMES, link::parse_num};
+
+pub(crate) const RT_TABLE_DEFAULT: u32 = 253;
+pub(crate) const RT_TABLE_MAIN: u32 = 254;
+pub(crate) const RT_TABLE_LOCAL: u32 = 255;
+
+const RT_TABLES_PATH: &str = "/etc/iproute2/rt_tables";
+const RT_TABLES_DIR: &str = "/etc/iproute2/rt_tables.d";
+const RT_PROTOS_PATH: &str = "/etc/iproute2/rt_protos";
+const RT_PROTOS_DIR: &str = "/etc/iproute2/rt_protos.d";
+const RT_REALMS_PATH: &str = "/etc/iproute2/rt_realms";
+
+static TABLE_DB: OnceLock<IndexMap<u32, String>> = OnceLock::new();
+static PROTOCOL_DB: OnceLock<IndexMap<u32, String>> = OnceLock::new();
+static REALM_DB: OnceLock<IndexMap<u32, String>> = OnceLock::new();
+
+// Equal to iproute2 `rtnl_rtprot_tab` plus the entries of the shipped
+// `/etc/iproute2/rt_protos`
+const PROTOCOL_NAMES: [(&str, u8); 22] = [
+    ("unspec", 0),
+    ("redirect", 1),
+    ("kernel", 2),
+    ("boot", 3),
+    ("static", 4),
+    ("gated", 8),
+    ("ra", 9),
+    ("mrt", 10),
+    ("zebra", 11),
+    ("bird", 12),
+    ("dnrouted", 13),
+    ("xorp", 14),
+    ("ntk", 15),
+    ("dhcp", 16),
+    ("keepalived", 18),
+    ("babel", 42),
+    ("openr", 99),
+    ("bgp", 186),
+    ("isis", 187),
+    ("ospf", 188),
+    ("rip", 189),
+    ("eigrp", 192),
+];
+
+// Equal to iproute2 `rtnl_rtntype_n2a()`
+const TYPE_NAMES: [(&str, u8); 12] = [
+    ("none", 0),
+    ("unicast", 1),
+    ("local", 2),
+    ("broadcast", 3),
+    ("anycast", 4),
+    ("multicast", 5),
+    ("blackhole", 6),
+    ("unreachable", 7),
+    ("prohibit", 8),
+    ("throw", 9),
+    ("nat", 10),
+    ("xresolve", 11),
+];
+
+pub(crate) const RTAX_RTT: u16 = 4;
+pub(crate) const RTAX_RTTVAR: u16 = 5;
+pub(crate) const RTAX_HOPLIMIT: u16 = 10;
+pub(crate) const RTAX_FEATURES: u16 = 12;
+pub(crate) const RTAX_RTO_MIN: u16 = 13;
+pub(crate) const RTAX_CC_ALGO: u16 = 16;
+pub(crate) const RTAX_FEATURE_ECN: u32 = 1;
+
+// Equal to iproute2 `mx_names` indexed by `RTAX_*`
+pub(crate) const METRIC_NAMES: [&str; 18] = [
+    "",
+    "lock",
+    "mtu",
+    "window",
+    "rtt",
+    "rttvar",
+    "ssthresh",
+    "cwnd",
+    "advmss",
+    "reordering",
+    "hoplimit",
+    "initcwnd",
+    "features",
+    "rto_min",
+    "initrwnd",
+    "quickack",
+    "congctl",
+    "fastopen_no_cookie",
+];
+
+// Equal to iproute2 `rtnl_rttable_tab`
+const TABLE_NAMES: [(&str, u32); 4] = [
+    ("unspec", 0),
+    ("default", 253),
+    ("main", 254),
+    ("local", 255),
+];
+
+// Like iproute2 `rtnl_rtprot_initialize()`, the builtin names are
+// overridden by `/etc/iproute2/rt_protos` and then the `*.conf` files in
+// `/etc/iproute2/rt_protos.d`.
+fn protocol_db() -> &'static IndexMap<u32, String> {
+    PROTOCOL_DB.get_or_init(|| {
+        let mut db = load_db(
+            PROTOCOL_NAMES
+                .iter()
+                .map(|(name, id)| (u32::from(*id), *name)),
+            RT_PROTOS_PATH,
+            Some(RT_PROTOS_DIR),
+        );
+        // The protocol is stored in `u8`
+        db.retain(|id, _| *id <= u32::from(u8::MAX));
+        db
+    })
+}
+
+pub(crate) fn protocol_to_name(protocol: u8, numeric: bool) -> String {
+    match protocol_db().get(&u32::from(protocol)) {
+        Some(name) if !numeric => name.to_string(),
+        _ => protocol.to_string(),
+    }
+}
+
+/// Like iproute2 `rtnl_rtprot_a2n()`, both name and number are supported.
+pub(crate) fn parse_protocol(
+    value: &str,
+    error_msg: &str,
+) -> Result<u8, CliError> {
+    match protocol_db().iter().find(|(_, name)| *name == value) {
+        Some((id, _)) => Ok(*id as u8),
+        None => parse_num(value, error_msg),
+    }
+}
+
+// Like iproute2 `rtnl_rtrealm_initialize()`, the builtin `cosmos` is
+// overridden by `/etc/iproute2/rt_realms`.
+fn realm_db() -> &'static IndexMap<u32, String> {
+    REALM_DB.get_or_init(|| {
+        let mut db = load_db([(0, "cosmos")].into_iter(), RT_REALMS_PATH, None);
+        // Like iproute2 `rtnl_rtrealm_tab`, only realms up to 255 are named
+        db.retain(|id, _| *id <= u32::from(u8::MAX));
+        db
+    })
+}
+
+pub(crate) fn realm_to_name(realm: u16, numeric: bool) -> String {
+    match realm_db().get(&u32::from(realm)) {
+        Some(name) if !numeric => name.to_string(),
+        _ => realm.to_string(),
+    }
+}
+
+/// Like iproute2 `get_rt_realms_or_raw()`, the realms could be the raw
+/// 32 bits value or `[FROM/]TO` with each realm being a name or a number
+/// up to 255.
+pub(crate) fn parse_realms(
+    value: &str,
+    error_msg: &str,
+) -> Result<RouteRealm, CliError> {
+    let mut ret = RouteRealm::default();
+    if let Ok(raw) = parse_num::<u32>(value, error_msg) {
+        ret.source = (raw >> 16) as u16;
+        ret.destination = raw as u16;
+        return Ok(ret);
+    }
+
+    // Equal to iproute2 `rtnl_rtrealm_a2n()`
+    let parse_realm = |realm: &str| match realm_db()
+        .iter()
+        .find(|(_, name)| *name == realm)
+    {
+        Some((id, _)) => Some(*id as u16),
+        None => parse_num::<u8>(realm, error_msg).ok().map(u16::from),
+    };
+    let (source, destination) = match value.split_once('/') {
+        Some((from, to)) => (parse_realm(from), to),
+        None => (Some(0), value),
+    };
+    let destination = if destination.is_empty() {
+        Some(0)
+    } else {
+        parse_realm(destination)
+    };
+    match (source, destination) {
+        (Some(source), Some(destination)) => {
+            ret.source = source;
+            ret.destination = destination;
+            Ok(ret)
+        }
+        _ => Err(CliError::from(
+            format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+        )),
+    }
+}
+
+pub(crate) fn scope_to_name(scope: u8, numeric: bool) -> String {
+    match SCOPE_NAMES.iter().find(|(_, s)| *s == scope) {
+        Some((name, _)) if !numeric => name.to_string(),
+        _ => scope.to_string(),
+    }
+}
+
+pub(crate) fn type_to_name(kind: u8, numeric: bool) -> String {
+    match TYPE_NAMES.iter().find(|(_, t)| *t == kind) {
+        Some((name, _)) if !numeric => name.to_string(),
+        _ => kind.to_string(),
+    }
+}
+
+// Like iproute2 `rtnl_rtntype_a2n()`, `brd` is the alias of `broadcast`
+pub(crate) fn parse_type(value: &str) -> Result<u8, CliError> {
+    let value = if value == "brd" { "broadcast" } else { value };
+    match TYPE_NAMES.iter().find(|(n, _)| *n == value) {
+        Some((_, kind)) if *kind != 0 => Ok(*kind),
+        _ => parse_num(value, "node type value is invalid"),
+    }
+}
+
+// Like iproute2 `rtnl_dsfield_a2n()`, TOS is in hex without `0x` prefix
+// required
+pub(crate) fn parse_tos(value: &str) -> Result<u8, CliError> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    u8::from_str_radix(digits, 16).map_err(|_| {
+        CliError::from(
+            format!("argument \"{value}\" is wrong: TOS value is invalid")
+                .as_str(),
+        )
+    })
+}
+
+// Like iproute2 `rtnl_rttable_initialize()`, the builtin names are
+// overridden by `/etc/iproute2/rt_tables` and then the `*.conf` files in
+// `/etc/iproute2/rt_tables.d`.
+fn table_db() -> &'static IndexMap<u32, String> {
+    TABLE_DB.get_or_init(|| {
+        load_db(
+            TABLE_NAMES.iter().map(|(name, id)| (*id, *name)),
+            RT_TABLES_PATH,
+            Some(RT_TABLES_DIR),
+        )
+    })
+}
+
+// Load the builtin names, then the names in the file, then the names in
+// the `*.conf` files of the directory in alphabetical order.
+fn load_db<'a>(
+    builtin: impl Iterator<Item = (u32, &'a str)>,
+    path: &str,
+    dir: Option<&str>,
+) -> IndexMap<u32, String> {
+    let mut db: IndexMap<u32, String> =
+        builtin.map(|(id, name)| (id, name.to_string())).collect();
+    read_db(Path::new(path), &mut db);
+    if let Some(Ok(entries)) = dir.map(std::fs::read_dir) {
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            read_db(&path, &mut db);
+        }
+    }
+    db
+}
+
+// Equal to iproute2 `rtnl_tab_initialize()`, each line holds the ID in
+// decimal or hex format and the name, the remaining lines are ignored once
+// corrupted line found.
+fn read_db(path: &Path, db: &mut IndexMap<u32, String>) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+    for line in content.lines() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let id = fields.next().and_then(|id| match id.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => id.parse::<u32>().ok(),
+        });
+        match (id, fields.next()) {
+            (Some(id), Some(name)) => {
+                db.insert(id, name.to_string());
+            }
+            _ => {
+                writeln!(
+                    std::io::stderr(),
+                    "Database {} is corrupted at {line}",
+                    path.display()
+                )
+                .ok();
+                return;
+            }
+        }
+    }
+}
+
+pub(crate) fn table_to_name(table: u32, numeric: bool) -> String {
+    match table_db().get(&table) {
+        Some(name) if !numeric => name.to_string(),
+        _ => table.to_string(),
+    }
+}
+
+/// Like iproute2 `rtnl_rttable_a2n()`, both name and number are supported.
+pub(crate) fn parse_table(
+    value: &str,
+    error_msg: &str,
+) -> Result<u32, CliError> {
+    match table_db().iter().find(|(_, name)| *name == value) {
+        Some((id, _)) => Ok(*id),
+        None => parse_num(value, error_msg),
+    }
+}
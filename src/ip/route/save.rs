@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::{IsTerminal, Read, Write};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::CliError;
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_CREATE, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        route::{RouteAttribute, RouteMessage},
+    },
+};
+
+use super::{
+    filter::RouteShowFilter,
+    show::{CliRouteInfo, dump_routes, get_ifnames, parse_nl_msg_to_route},
+};
+
+// Equal to iproute2 `route_dump_magic` which is stored in host order
+const ROUTE_DUMP_MAGIC: u32 = 0x45311224;
+const NLMSG_HDRLEN: usize = 16;
+const RTN_LOCAL: u8 = 2;
+
+/// Like iproute2, `ip route save` dumps the routes as raw netlink messages
+/// prefixed by the magic number.
+pub(crate) async fn handle_save(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let mut filter = RouteShowFilter::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+    filter.resolve_dev(&ifnames)?;
+
+    let routes = dump_routes(handle, &filter, family).await?;
+    write_nl_dump(
+        ROUTE_DUMP_MAGIC,
+        routes.into_iter().map(RouteNetlinkMessage::NewRoute),
+    )
+}
+
+/// Write the netlink messages prefixed by the magic number to stdout like
+/// iproute2 `save_route_prep()` and `save_route()`.
+pub(crate) fn write_nl_dump(
+    magic: u32,
+    nl_msgs: impl Iterator<Item = RouteNetlinkMessage>,
+) -> Result<(), CliError> {
+    let mut stdout = std::io::stdout().lock();
+    if stdout.is_terminal() {
+        return Err(CliError::from("Not sending a binary stream to stdout"));
+    }
+
+    let mut buf = magic.to_ne_bytes().to_vec();
+    for nl_msg in nl_msgs {
+        emit_nl_msg(
+            &mut buf,
+            NetlinkMessage::new(
+                NetlinkHeader::default(),
+                NetlinkPayload::InnerMessage(nl_msg),
+            ),
+        );
+    }
+    stdout.write_all(&buf)?;
+    Ok(())
+}
+
+/// Append the serialized netlink message to the buffer
+pub(crate) fn emit_nl_msg(
+    buf: &mut Vec<u8>,
+    mut nl_msg: NetlinkMessage<RouteNetlinkMessage>,
+) {
+    nl_msg.finalize();
+    let start = buf.len();
+    buf.resize(start + nl_msg.buffer_len(), 0);
+    nl_msg.serialize(&mut buf[start..]);
+}
+
+pub(crate) async fn handle_restore(
+    handle: &rtnetlink::Handle,
+) -> Result<(), CliError> {
+    let routes = read_route_dump()?;
+
+    // Like iproute2 `restore_handler()`, restore the routes of local
+    // addresses first, then the ones of local networks, then the remote
+    // ones.
+    for prio in 0..3 {
+        for route in routes.iter().filter(|r| restore_prio_matches(r, prio)) {
+            let mut request = NetlinkMessage::new(
+                NetlinkHeader::default(),
+                NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewRoute(
+                    route.clone(),
+                )),
+            );
+            request.header.flags = NLM_F_REQUEST | NLM_F_CREATE | NLM_F_ACK;
+
+            let mut response = handle.clone().request(request)?;
+            while let Some(msg) = response.next().await {
+                if let NetlinkPayload::Error(e) = msg.payload
+                    && e.code.is_some()
+                    // Existing routes are ignored
+                    && e.raw_code() != -(nix::errno::Errno::EEXIST as i32)
+                {
+                    return Err(rtnetlink::Error::NetlinkError(e).into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn handle_showdump(
+    handle: &rtnetlink::Handle,
+    include_details: bool,
+    numeric: bool,
+    show_stats: bool,
+) -> Result<Vec<CliRouteInfo>, CliError> {
+    let routes = read_route_dump()?;
+    let ifnames = get_ifnames(handle).await?;
+    let filter = RouteShowFilter::new_unfiltered();
+
+    Ok(routes
+        .into_iter()
+        .map(|route| {
+            parse_nl_msg_to_route(
+                route,
+                &filter,
+                &ifnames,
+                include_details,
+                numeric,
+                show_stats,
+            )
+        })
+        .collect())
+}
+
+fn restore_prio_matches(route: &RouteMessage, prio: u8) -> bool {
+    let has_gateway = route
+        .attributes
+        .iter()
+        .any(|nla| matches!(nla, RouteAttribute::Gateway(_)));
+    let has_prefsrc = route
+        .attributes
+        .iter()
+        .any(|nla| matches!(nla, RouteAttribute::PrefSource(_)));
+    match prio {
+        0 => {
+            !has_gateway
+                && (!has_prefsrc || u8::from(route.header.kind) == RTN_LOCAL)
+        }
+        1 => !has_gateway && has_prefsrc,
+        _ => has_gateway,
+    }
+}
+
+fn read_route_dump() -> Result<Vec<RouteMessage>, CliError> {
+    Ok(read_nl_dump(ROUTE_DUMP_MAGIC, "route")?
+        .into_iter()
+        .filter_map(|nl_msg| match nl_msg {
+            RouteNetlinkMessage::NewRoute(route) => Some(route),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Equal to iproute2 `route_dump_check_magic()` and `rtnl_from_file()`,
+/// read the netlink messages of specified object dump from stdin.
+pub(crate) fn read_nl_dump(
+    expected_magic: u32,
+    object: &str,
+) -> Result<Vec<RouteNetlinkMessage>, CliError> {
+    let mut stdin = std::io::stdin().lock();
+    if stdin.is_terminal() {
+        return Err(CliError::from(
+            format!("Can't restore {object} dump from a terminal").as_str(),
+        ));
+    }
+    let mut data = Vec::new();
+    stdin.read_to_end(&mut data)?;
+
+    // Like iproute2 `fread()`, the magic could be partially read
+    let mut magic = [0u8; 4];
+    let magic_len = data.len().min(magic.len());
+    magic[..magic_len].copy_from_slice(&data[..magic_len]);
+    let elems = u8::from(magic_len == magic.len());
+    let magic = u32::from_ne_bytes(magic);
+    let data = &data[magic_len..];
+    if magic != expected_magic {
+        return Err(CliError::from(
+            format!("Magic mismatch ({elems} elems, {magic:x} magic)").as_str(),
+        ));
+    }
+
+    let mut ret = Vec::new();
+    for buf in split_nl_msgs(data)? {
+        let nl_msg = NetlinkMessage::<RouteNetlinkMessage>::deserialize(buf)
+            .map_err(|e| {
+                CliError::from(
+                    format!("Failed to parse {object} dump: {e}").as_str(),
+                )
+            })?;
+        if let NetlinkPayload::InnerMessage(nl_msg) = nl_msg.payload {
+            ret.push(nl_msg);
+        }
+    }
+    Ok(ret)
+}
+
+/// Split the concatenated netlink messages like iproute2
+/// `rtnl_from_file()`.
+pub(crate) fn split_nl_msgs(mut data: &[u8]) -> Result<Vec<&[u8]>, CliError> {
+    let mut ret = Vec::new();
+    while let Some(len) = data.first_chunk::<4>() {
+        let len = u32::from_ne_bytes(*len) as usize;
+        if len < NLMSG_HDRLEN {
+            return Err(CliError::from(
+                format!("!!!malformed message: len={len}").as_str(),
+            ));
+        }
+        if data.len() < len {
+            return Err(CliError::from("rtnl-from_file: truncated message"));
+        }
+        ret.push(&data[..len]);
+        // The netlink messages are aligned to 4 bytes
+        data = &data[((len + 3) & !3).min(data.len())..];
+    }
+    if !data.is_empty() {
+        return Err(CliError::from("rtnl-from_file: truncated message"));
+    }
+    Ok(ret)
+}
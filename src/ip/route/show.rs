@@ -0,0 +1,996 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliNumberFormat, resolve_hostnames,
+    write_with_color,
+};
+use rtnetlink::{
+    packet_core::Nla,
+    packet_route::{
+        AddressFamily,
+        route::{
+            RouteAddress, RouteAttribute, RouteCacheInfo, RouteFlags,
+            RouteMessage, RouteMetric, RouteNextHop, RouteRealm, RouteVia,
+        },
+    },
+};
+use serde::{Serialize, ser::SerializeMap};
+
+use super::{
+    encap::CliRouteEncap,
+    filter::RouteShowFilter,
+    names::{
+        METRIC_NAMES, RT_TABLE_MAIN, RTAX_CC_ALGO, RTAX_FEATURE_ECN,
+        RTAX_FEATURES, RTAX_HOPLIMIT, RTAX_RTO_MIN, RTAX_RTT, RTAX_RTTVAR,
+        protocol_to_name, realm_to_name, scope_to_name, table_to_name,
+        type_to_name,
+    },
+};
+use crate::{CliError, link::parse_iface_index};
+
+const RTN_UNICAST: u8 = 1;
+const RTPROT_BOOT: u8 = 3;
+const RT_SCOPE_UNIVERSE: u8 = 0;
+
+// The kernel reports the route cache time in `USER_HZ` which is 100 on all
+// architectures supported by Linux.
+const USER_HZ: u32 = 100;
+
+// Equal to iproute2 `print_rt_flags()`
+const ROUTE_FLAG_NAMES: [(RouteFlags, &str); 11] = [
+    (RouteFlags::Dead, "dead"),
+    (RouteFlags::Onlink, "onlink"),
+    (RouteFlags::Pervasive, "pervasive"),
+    (RouteFlags::Offload, "offload"),
+    (RouteFlags::Trap, "trap"),
+    (RouteFlags::Notify, "notify"),
+    (RouteFlags::Linkdown, "linkdown"),
+    (RouteFlags::Unresolved, "unresolved"),
+    (RouteFlags::RtOffload, "rt_offload"),
+    (RouteFlags::RtTrap, "rt_trap"),
+    (RouteFlags::RtOffloadFailed, "rt_offload_failed"),
+];
+
+// Equal to iproute2 `print_cache_flags()`
+const CACHE_FLAG_NAMES: [(u32, &str); 14] = [
+    (0x80000000, "local"),
+    (0x40000000, "reject"),
+    (0x20000000, "mc"),
+    (0x10000000, "brd"),
+    (0x08000000, "dst-nat"),
+    (0x00800000, "src-nat"),
+    (0x00400000, "masq"),
+    (0x00020000, "dst-direct"),
+    (0x04000000, "src-direct"),
+    (0x00040000, "redirected"),
+    (0x01000000, "redirect"),
+    (0x00200000, "fastroute"),
+    (0x00010000, "notify"),
+    (0x00080000, "proxy"),
+];
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliRouteInfo {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    dst: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[serde(flatten)]
+    encap: Option<CliRouteEncap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    via: Option<CliRouteVia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefsrc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metric: Option<u32>,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mark: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flow: Option<CliRouteFlow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    /// Cache flags of cloned IPv4 route
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache: Option<Vec<String>>,
+    #[serde(flatten)]
+    cache_info: CliRouteCacheInfo,
+    /// Like iproute2, the metrics are shown as single JSON object in array
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metrics: Option<[CliRouteMetrics; 1]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iif: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nexthops: Option<Vec<CliRouteNextHop>>,
+    #[serde(skip)]
+    family: String,
+}
+
+impl CliRouteInfo {
+    /// The `dst`, `gateway` and `prefsrc` addresses including the ones of
+    /// multipath nexthops
+    fn host_addrs_mut(&mut self) -> Vec<&mut String> {
+        let mut ret = vec![&mut self.dst];
+        ret.extend(self.gateway.as_mut());
+        ret.extend(self.prefsrc.as_mut());
+        for nexthop in self.nexthops.iter_mut().flatten() {
+            ret.extend(nexthop.gateway.as_mut());
+        }
+        ret
+    }
+}
+
+#[derive(Serialize)]
+struct CliRouteVia {
+    family: String,
+    host: String,
+}
+
+// Equal to iproute2 `print_rta_metrics()`
+struct CliRouteMetrics(Vec<CliRouteMetric>);
+
+struct CliRouteMetric {
+    index: u16,
+    locked: bool,
+    value: u32,
+    /// The `congctl` holds the name of congestion control algorithm
+    cc_algo: Option<String>,
+}
+
+// Equal to iproute2 `print_rta_multipath()`
+#[derive(Serialize)]
+struct CliRouteNextHop {
+    #[serde(flatten)]
+    encap: Option<CliRouteEncap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    via: Option<CliRouteVia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flow: Option<CliRouteFlow>,
+    dev: String,
+    /// Not applicable to MPLS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<u32>,
+    flags: Vec<String>,
+    #[serde(skip)]
+    family: String,
+}
+
+#[derive(Serialize)]
+struct CliRouteFlow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    to: String,
+}
+
+// Equal to iproute2 `print_rta_cacheinfo()`
+#[derive(Serialize, Default)]
+struct CliRouteCacheInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    users: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    used: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tsage: Option<u32>,
+}
+
+impl std::fmt::Display for CliRouteInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let addr_color = CliColor::address_color(&self.family);
+        if let Some(kind) = &self.kind {
+            write!(f, "{kind} ")?;
+        }
+        write_with_color!(f, addr_color, "{}", self.dst)?;
+        write!(f, " ")?;
+        if let Some(from) = &self.from {
+            write!(f, "from ")?;
+            write_with_color!(f, addr_color, "{from}")?;
+            write!(f, " ")?;
+        }
+        if let Some(encap) = &self.encap {
+            write!(f, "{encap}")?;
+        }
+        if let Some(tos) = &self.tos {
+            write!(f, "tos {tos} ")?;
+        }
+        if let Some(gateway) = &self.gateway {
+            write!(f, "via ")?;
+            write_with_color!(f, addr_color, "{gateway}")?;
+            write!(f, " ")?;
+        }
+        if let Some(via) = &self.via {
+            write!(f, "{via}")?;
+        }
+        if let Some(dev) = &self.dev {
+            write!(f, "dev ")?;
+            write_with_color!(f, CliColor::IfaceName, "{dev}")?;
+            write!(f, " ")?;
+        }
+        if let Some(table) = &self.table {
+            write!(f, "table {table} ")?;
+        }
+        if let Some(protocol) = &self.protocol {
+            write!(f, "proto {protocol} ")?;
+        }
+        if let Some(scope) = &self.scope {
+            write!(f, "scope {scope} ")?;
+        }
+        if let Some(prefsrc) = &self.prefsrc {
+            write!(f, "src ")?;
+            write_with_color!(f, addr_color, "{prefsrc}")?;
+            write!(f, " ")?;
+        }
+        if let Some(metric) = self.metric {
+            write!(f, "metric {metric} ")?;
+        }
+        for flag in &self.flags {
+            write!(f, "{flag} ")?;
+        }
+        if let Some(mark) = self.mark {
+            if mark >= 16 {
+                write!(f, "mark 0x{mark:x} ")?;
+            } else {
+                write!(f, "mark {mark} ")?;
+            }
+        }
+        if let Some(flow) = &self.flow {
+            write!(f, "{flow}")?;
+        }
+        if let Some(uid) = self.uid {
+            write!(f, "uid {uid} ")?;
+        }
+        if let Some(cache) = &self.cache {
+            write!(f, "\n    cache ")?;
+            if !cache.is_empty() {
+                write!(f, "<{}> ", cache.join(","))?;
+            }
+        }
+        write!(f, "{}", self.cache_info)?;
+        if let Some([metrics]) = &self.metrics {
+            write!(f, "{metrics}")?;
+        }
+        if let Some(iif) = &self.iif {
+            write!(f, "iif ")?;
+            write_with_color!(f, CliColor::IfaceName, "{iif}")?;
+            write!(f, " ")?;
+        }
+        if let Some(pref) = &self.pref {
+            write!(f, "pref {pref}")?;
+        }
+        for nexthop in self.nexthops.iter().flatten() {
+            write!(f, "\n\tnexthop {nexthop}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CliRouteMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for metric in &self.0 {
+            write!(f, "{} ", METRIC_NAMES[usize::from(metric.index)])?;
+            if metric.locked {
+                write!(f, "lock ")?;
+            }
+            let value = metric.value;
+            match metric.index {
+                RTAX_FEATURES => {
+                    if value & RTAX_FEATURE_ECN != 0 {
+                        write!(f, "ecn ")?;
+                    }
+                    // Like iproute2, the unknown features are shown with
+                    // the ECN bit included
+                    if value & !RTAX_FEATURE_ECN != 0 {
+                        write!(f, "0x{value:x} ")?;
+                    }
+                }
+                RTAX_RTT | RTAX_RTTVAR | RTAX_RTO_MIN => {
+                    if value >= 1000 {
+                        write!(f, "{}s ", format_seconds(value))?;
+                    } else {
+                        write!(f, "{value}ms ")?;
+                    }
+                }
+                RTAX_CC_ALGO => {
+                    write!(f, "{} ", metric.cc_algo.as_deref().unwrap_or(""))?;
+                }
+                _ => write!(f, "{value} ")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for CliRouteMetrics {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for metric in &self.0 {
+            let name = METRIC_NAMES[usize::from(metric.index)];
+            match metric.index {
+                RTAX_FEATURES => {
+                    if metric.value & RTAX_FEATURE_ECN != 0 {
+                        map.serialize_entry("ecn", &None::<()>)?;
+                    }
+                    if metric.value & !RTAX_FEATURE_ECN != 0 {
+                        map.serialize_entry(
+                            name,
+                            &format!("0x{:x}", metric.value),
+                        )?;
+                    }
+                }
+                RTAX_CC_ALGO => {
+                    map.serialize_entry(
+                        "congestion",
+                        metric.cc_algo.as_deref().unwrap_or(""),
+                    )?;
+                }
+                _ => map.serialize_entry(name, &metric.value)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl std::fmt::Display for CliRouteVia {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "via {} ", self.family)?;
+        write_with_color!(
+            f,
+            CliColor::address_color(&self.family),
+            "{}",
+            self.host
+        )?;
+        write!(f, " ")
+    }
+}
+
+impl std::fmt::Display for CliRouteFlow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.from {
+            Some(from) => write!(f, "realms {from}/{} ", self.to),
+            None => write!(f, "realm {} ", self.to),
+        }
+    }
+}
+
+impl std::fmt::Display for CliRouteNextHop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(encap) = &self.encap {
+            write!(f, "{encap}")?;
+        }
+        if let Some(gateway) = &self.gateway {
+            write!(f, "via ")?;
+            write_with_color!(
+                f,
+                CliColor::address_color(&self.family),
+                "{gateway}"
+            )?;
+            write!(f, " ")?;
+        }
+        if let Some(via) = &self.via {
+            write!(f, "{via}")?;
+        }
+        if let Some(flow) = &self.flow {
+            write!(f, "{flow}")?;
+        }
+        write!(f, "dev ")?;
+        write_with_color!(f, CliColor::IfaceName, "{}", self.dev)?;
+        write!(f, " ")?;
+        if let Some(weight) = self.weight {
+            write!(f, "weight {weight} ")?;
+        }
+        for flag in &self.flags {
+            write!(f, "{flag} ")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for CliRouteCacheInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(expires) = self.expires {
+            write!(f, "expires {expires}sec ")?;
+        }
+        if let Some(error) = self.error {
+            write!(f, "error {error} ")?;
+        }
+        if let Some(users) = self.users {
+            write!(f, "users {users} ")?;
+        }
+        if let Some(used) = self.used {
+            write!(f, "used {used} ")?;
+        }
+        if let Some(age) = self.age {
+            write!(f, "age {age}sec ")?;
+        }
+        if let Some(ipid) = &self.ipid {
+            write!(f, "ipid {ipid} ")?;
+        }
+        // Like iproute2, no space between `ts` and `tsage`
+        if let Some(ts) = &self.ts {
+            write!(f, "ts {ts}")?;
+        }
+        if let Some(tsage) = self.tsage {
+            write!(f, "tsage {tsage}sec ")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliRouteInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliRouteInfo {}
+
+impl CliRouteCacheInfo {
+    fn new(info: &RouteCacheInfo, show_stats: bool) -> Self {
+        let mut ret = Self::default();
+        if info.expires != 0 {
+            ret.expires = Some((info.expires / USER_HZ) as i32);
+        }
+        if info.error != 0 {
+            ret.error = Some(info.error);
+        }
+        if show_stats {
+            if info.clntref != 0 {
+                ret.users = Some(info.clntref);
+            }
+            if info.used != 0 {
+                ret.used = Some(info.used);
+            }
+            if info.last_use != 0 {
+                ret.age = Some(info.last_use / USER_HZ);
+            }
+        }
+        if info.id != 0 {
+            ret.ipid = Some(format!("0x{:04x}", info.id));
+        }
+        if info.ts != 0 || info.ts_age != 0 {
+            ret.ts = Some(format!("0x{:x}", info.ts));
+            ret.tsage = Some(info.ts_age);
+        }
+        ret
+    }
+}
+
+impl CliRouteVia {
+    fn new(via: &RouteVia) -> Option<Self> {
+        let family = match via {
+            RouteVia::Inet(_) => AddressFamily::Inet,
+            _ => AddressFamily::Inet6,
+        };
+        route_via_to_ip(via).map(|ip| Self {
+            family: family.to_string(),
+            host: ip.to_string(),
+        })
+    }
+}
+
+impl CliRouteMetrics {
+    fn new(metrics: &[RouteMetric]) -> Self {
+        let mut values: [Option<u32>; METRIC_NAMES.len()] = Default::default();
+        let mut cc_algo = None;
+        let mut lock = 0u32;
+        for metric in metrics {
+            let (index, value) = match metric {
+                RouteMetric::Lock(v) => {
+                    lock = *v;
+                    continue;
+                }
+                RouteMetric::Mtu(v) => (2, *v),
+                RouteMetric::Window(v) => (3, *v),
+                RouteMetric::Rtt(v) => (RTAX_RTT, *v),
+                RouteMetric::RttVar(v) => (RTAX_RTTVAR, *v),
+                RouteMetric::SsThresh(v) => (6, *v),
+                RouteMetric::Cwnd(v) => (7, *v),
+                RouteMetric::Advmss(v) => (8, *v),
+                RouteMetric::Reordering(v) => (9, *v),
+                RouteMetric::Hoplimit(v) => (RTAX_HOPLIMIT, *v),
+                RouteMetric::InitCwnd(v) => (11, *v),
+                RouteMetric::Features(v) => (RTAX_FEATURES, *v),
+                RouteMetric::RtoMin(v) => (RTAX_RTO_MIN, *v),
+                RouteMetric::InitRwnd(v) => (14, *v),
+                RouteMetric::QuickAck(v) => (15, *v),
+                // The kernel reports the algorithm name as string which is
+                // only decoded as u32 when it is 4 bytes long
+                RouteMetric::CcAlgo(v) => {
+                    cc_algo = Some(nla_str(&v.to_ne_bytes()));
+                    (RTAX_CC_ALGO, 0)
+                }
+                RouteMetric::FastopenNoCookie(v) => (17, *v),
+                RouteMetric::Other(nla) if nla.kind() == RTAX_CC_ALGO => {
+                    let mut buf = vec![0u8; nla.value_len()];
+                    nla.emit_value(&mut buf);
+                    cc_algo = Some(nla_str(&buf));
+                    (RTAX_CC_ALGO, 0)
+                }
+                _ => continue,
+            };
+            if let Some(slot) = values.get_mut(usize::from(index)) {
+                *slot = Some(value);
+            }
+        }
+
+        let mut ret = Vec::new();
+        for index in 2..METRIC_NAMES.len() as u16 {
+            let locked = lock & (1 << index) != 0;
+            let value = values[usize::from(index)];
+            if value.is_none() && !locked {
+                continue;
+            }
+            let mut value = value.unwrap_or_default();
+            match index {
+                // Unlimited hop limit
+                RTAX_HOPLIMIT if value == u32::MAX => continue,
+                // The kernel stores RTT in 1/8 ms and RTT variance in
+                // 1/4 ms
+                RTAX_RTT => value /= 8,
+                RTAX_RTTVAR => value /= 4,
+                _ => (),
+            }
+            ret.push(CliRouteMetric {
+                index,
+                locked,
+                value,
+                cc_algo: (index == RTAX_CC_ALGO)
+                    .then(|| cc_algo.clone().unwrap_or_default()),
+            });
+        }
+        Self(ret)
+    }
+}
+
+// The string attribute is terminated by NUL
+fn nla_str(buf: &[u8]) -> String {
+    let end = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..end]).to_string()
+}
+
+// Like `%g` of C `printf()` for the milliseconds in seconds, e.g. `1.5` and
+// `1234.57`.
+fn format_seconds(ms: u32) -> String {
+    format_float(f64::from(ms) / 1e3)
+}
+
+/// Like `%g` of C `printf()` with 6 significant digits, e.g. `0.01` and `60`
+pub(crate) fn format_float(value: f64) -> String {
+    let int_digits = value.trunc().to_string().len();
+    if int_digits > 6 {
+        let exp = int_digits - 1;
+        let mantissa = value / 10f64.powi(exp as i32);
+        let mantissa = format!("{mantissa:.5}");
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        return format!("{mantissa}e+{exp:02}");
+    }
+    let ret = format!("{value:.*}", 6 - int_digits);
+    if ret.contains('.') {
+        ret.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        ret
+    }
+}
+
+impl CliRouteFlow {
+    fn new(realm: &RouteRealm, numeric: bool) -> Self {
+        Self {
+            from: (realm.source != 0)
+                .then(|| realm_to_name(realm.source, numeric)),
+            to: realm_to_name(realm.destination, numeric),
+        }
+    }
+}
+
+impl CliRouteNextHop {
+    fn new(
+        nexthop: &RouteNextHop,
+        family: AddressFamily,
+        ifnames: &HashMap<u32, String>,
+        numeric: bool,
+    ) -> Self {
+        let mut ret = Self {
+            encap: CliRouteEncap::from_nlas(&nexthop.attributes),
+            gateway: None,
+            via: None,
+            flow: None,
+            // Like iproute2 `ll_index_to_name()`, index 0 is shown as `*`
+            dev: if nexthop.interface_index == 0 {
+                "*".to_string()
+            } else {
+                ifnames
+                    .get(&nexthop.interface_index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("if{}", nexthop.interface_index))
+            },
+            weight: (family != AddressFamily::Mpls)
+                .then_some(u32::from(nexthop.hops) + 1),
+            flags: route_flags_to_names(RouteFlags::from_bits_retain(
+                u32::from(nexthop.flags.bits()),
+            )),
+            family: family.to_string(),
+        };
+        for nla in nexthop.attributes.iter() {
+            match nla {
+                RouteAttribute::Gateway(a) => {
+                    ret.gateway = route_addr_to_ip(a).map(|ip| ip.to_string());
+                }
+                RouteAttribute::Via(v) => ret.via = CliRouteVia::new(v),
+                RouteAttribute::Realm(realm) => {
+                    ret.flow = Some(CliRouteFlow::new(realm, numeric));
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// The nexthop flags `RTNH_F_*` share the bits of the route flags
+pub(crate) fn route_flags_to_names(flags: RouteFlags) -> Vec<String> {
+    ROUTE_FLAG_NAMES
+        .iter()
+        .filter(|(flag, _)| flags.contains(*flag))
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+pub(super) fn route_addr_to_ip(addr: &RouteAddress) -> Option<IpAddr> {
+    match addr {
+        RouteAddress::Inet(ip) => Some(IpAddr::V4(*ip)),
+        RouteAddress::Inet6(ip) => Some(IpAddr::V6(*ip)),
+        _ => None,
+    }
+}
+
+pub(super) fn ip_to_route_addr(ip: IpAddr) -> RouteAddress {
+    match ip {
+        IpAddr::V4(ip) => RouteAddress::Inet(ip),
+        IpAddr::V6(ip) => RouteAddress::Inet6(ip),
+    }
+}
+
+pub(super) fn route_via_to_ip(via: &RouteVia) -> Option<IpAddr> {
+    match via {
+        RouteVia::Inet(ip) => Some(IpAddr::V4(*ip)),
+        RouteVia::Inet6(ip) => Some(IpAddr::V6(*ip)),
+        _ => None,
+    }
+}
+
+/// The `RTA_TABLE` holds the table ID larger than 255, fallback to the
+/// header one
+pub(crate) fn route_table(nl_msg: &RouteMessage) -> u32 {
+    nl_msg
+        .attributes
+        .iter()
+        .find_map(|nla| {
+            if let RouteAttribute::Table(t) = nla {
+                Some(*t)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(nl_msg.header.table.into())
+}
+
+// Like iproute2 `print_route()`, prefix is shown as `ADDR/LEN` while host
+// route is shown as the address only
+fn addr_with_prefix_len(
+    addr: Option<IpAddr>,
+    prefix_len: u8,
+    host_len: u8,
+) -> Option<String> {
+    match addr {
+        Some(addr) if prefix_len == host_len => Some(addr.to_string()),
+        Some(addr) => Some(format!("{addr}/{prefix_len}")),
+        None if prefix_len != 0 => Some(format!("0/{prefix_len}")),
+        None => None,
+    }
+}
+
+// The upper 16 bits of route flags are the `RTCF_*` flags of route cache,
+// unknown ones are shown in hex
+fn get_cache_flags(flags: u32) -> Vec<String> {
+    let mut flags = flags & !0xffff;
+    let mut ret = Vec::new();
+    for (flag, name) in CACHE_FLAG_NAMES {
+        if flags & flag != 0 {
+            flags &= !flag;
+            ret.push(name.to_string());
+        }
+    }
+    if flags != 0 {
+        ret.push(format!("{flags:x}"));
+    }
+    ret
+}
+
+// Equal to iproute2 `print_rt_pref()`
+fn route_pref_to_string(pref: u8) -> String {
+    match pref {
+        3 => "low".to_string(),
+        0 => "medium".to_string(),
+        1 => "high".to_string(),
+        _ => pref.to_string(),
+    }
+}
+
+pub(crate) fn parse_nl_msg_to_route(
+    nl_msg: RouteMessage,
+    filter: &RouteShowFilter,
+    ifnames: &HashMap<u32, String>,
+    include_details: bool,
+    numeric: bool,
+    show_stats: bool,
+) -> CliRouteInfo {
+    let header = &nl_msg.header;
+    let family = header.address_family;
+    let host_len = if family == AddressFamily::Inet6 {
+        128
+    } else {
+        32
+    };
+    let kind = u8::from(header.kind);
+    let protocol = u8::from(header.protocol);
+    let scope = u8::from(header.scope);
+    let table = route_table(&nl_msg);
+    let is_cloned = header.flags.contains(RouteFlags::Cloned);
+    let ifname = |index: u32| {
+        ifnames
+            .get(&index)
+            .cloned()
+            .unwrap_or_else(|| format!("if{index}"))
+    };
+
+    let mut ret = CliRouteInfo {
+        encap: CliRouteEncap::from_nlas(&nl_msg.attributes),
+        family: family.to_string(),
+        ..Default::default()
+    };
+
+    if kind != RTN_UNICAST || include_details {
+        ret.kind = Some(type_to_name(kind, numeric));
+    }
+    if header.tos != 0 {
+        ret.tos = Some(format!("0x{:02x}", header.tos));
+    }
+    if table != 0
+        && (table != RT_TABLE_MAIN || include_details)
+        && filter.table == 0
+    {
+        ret.table = Some(table_to_name(table, numeric));
+    }
+    if !is_cloned {
+        if (protocol != RTPROT_BOOT || include_details)
+            && filter.protocol.is_none()
+        {
+            ret.protocol = Some(protocol_to_name(protocol, numeric));
+        }
+        if (scope != RT_SCOPE_UNIVERSE || include_details)
+            && filter.scope.is_none()
+        {
+            ret.scope = Some(scope_to_name(scope, numeric));
+        }
+    }
+    ret.flags = route_flags_to_names(header.flags);
+    if family == AddressFamily::Inet && is_cloned {
+        ret.cache = Some(get_cache_flags(header.flags.bits()));
+    }
+
+    let mut dst = None;
+    let mut src = None;
+    for nla in nl_msg.attributes.iter() {
+        match nla {
+            RouteAttribute::Destination(a) => dst = route_addr_to_ip(a),
+            RouteAttribute::Source(a) => src = route_addr_to_ip(a),
+            RouteAttribute::Gateway(a) if !filter.hide_via() => {
+                ret.gateway = route_addr_to_ip(a).map(|ip| ip.to_string());
+            }
+            RouteAttribute::Via(v) => ret.via = CliRouteVia::new(v),
+            RouteAttribute::Oif(index) if !filter.hide_dev() => {
+                ret.dev = Some(ifname(*index));
+            }
+            RouteAttribute::PrefSource(a) if !filter.hide_prefsrc() => {
+                ret.prefsrc = route_addr_to_ip(a).map(|ip| ip.to_string());
+            }
+            RouteAttribute::Priority(metric) => {
+                ret.metric = Some(*metric);
+            }
+            RouteAttribute::Mark(mark) if *mark != 0 => {
+                ret.mark = Some(*mark);
+            }
+            RouteAttribute::Realm(realm) => {
+                ret.flow = Some(CliRouteFlow::new(realm, numeric));
+            }
+            RouteAttribute::Uid(uid) => {
+                ret.uid = Some(*uid);
+            }
+            RouteAttribute::CacheInfo(info)
+                if matches!(
+                    family,
+                    AddressFamily::Inet | AddressFamily::Inet6
+                ) =>
+            {
+                ret.cache_info = CliRouteCacheInfo::new(info, show_stats);
+            }
+            RouteAttribute::Metrics(metrics) => {
+                ret.metrics = Some([CliRouteMetrics::new(metrics)]);
+            }
+            RouteAttribute::Iif(index) => {
+                ret.iif = Some(ifname(*index));
+            }
+            RouteAttribute::Preference(pref) => {
+                ret.pref = Some(route_pref_to_string(u8::from(*pref)));
+            }
+            RouteAttribute::MultiPath(nexthops) => {
+                ret.nexthops = Some(
+                    nexthops
+                        .iter()
+                        .map(|nh| {
+                            CliRouteNextHop::new(nh, family, ifnames, numeric)
+                        })
+                        .collect(),
+                );
+            }
+            _ => (),
+        }
+    }
+    ret.dst =
+        addr_with_prefix_len(dst, header.destination_prefix_length, host_len)
+            .unwrap_or_else(|| "default".to_string());
+    ret.from = addr_with_prefix_len(src, header.source_prefix_length, host_len);
+    ret
+}
+
+pub(crate) async fn get_ifnames(
+    handle: &rtnetlink::Handle,
+) -> Result<HashMap<u32, String>, CliError> {
+    Ok(crate::link::handle_show(
+        handle,
+        &[],
+        false,
+        0,
+        CliNumberFormat::default(),
+        false,
+    )
+    .await?
+    .into_iter()
+    .map(|link| (link.get_ifindex(), link.get_ifname().to_string()))
+    .collect())
+}
+
+/// Like iproute2 `ll_name_to_index()`, the `ifN` is also accepted as the
+/// interface of index N.
+pub(crate) fn ifname_to_index(
+    ifnames: &HashMap<u32, String>,
+    name: &str,
+) -> Result<u32, CliError> {
+    ifnames
+        .iter()
+        .find_map(|(index, ifname)| (ifname == name).then_some(*index))
+        .or_else(|| parse_iface_index(name).filter(|i| ifnames.contains_key(i)))
+        .ok_or_else(|| {
+            CliError::from(format!("Cannot find device \"{name}\"").as_str())
+        })
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    include_details: bool,
+    numeric: bool,
+    resolve: bool,
+    show_stats: bool,
+) -> Result<Vec<CliRouteInfo>, CliError> {
+    let mut filter = RouteShowFilter::parse(opts, family)?;
+    let ifnames = get_ifnames(handle).await?;
+    filter.resolve_dev(&ifnames)?;
+
+    let mut ret: Vec<CliRouteInfo> = dump_routes(handle, &filter, family)
+        .await?
+        .into_iter()
+        .map(|nl_msg| {
+            parse_nl_msg_to_route(
+                nl_msg,
+                &filter,
+                &ifnames,
+                include_details,
+                numeric,
+                show_stats,
+            )
+        })
+        .collect();
+    if resolve {
+        resolve_route_hostnames(&mut ret).await;
+    }
+    Ok(ret)
+}
+
+// Like iproute2 `format_host_rta()`, replace the IP addresses with
+// hostnames if resolved. The destination of network route is left
+// untouched as it carries the prefix length.
+pub(super) async fn resolve_route_hostnames(routes: &mut [CliRouteInfo]) {
+    let ips: Vec<IpAddr> = routes
+        .iter_mut()
+        .flat_map(CliRouteInfo::host_addrs_mut)
+        .filter_map(|addr| addr.parse().ok())
+        .collect();
+
+    let hostnames = resolve_hostnames(&ips).await;
+
+    for addr in routes.iter_mut().flat_map(CliRouteInfo::host_addrs_mut) {
+        if let Some(name) = addr
+            .parse::<IpAddr>()
+            .ok()
+            .and_then(|ip| hostnames.get(&ip))
+        {
+            *addr = name.clone();
+        }
+    }
+}
+
+/// Dump the routes matching the filter, shared by `ip route show` and
+/// `ip route save`
+pub(super) async fn dump_routes(
+    handle: &rtnetlink::Handle,
+    filter: &RouteShowFilter<'_>,
+    family: AddressFamily,
+) -> Result<Vec<RouteMessage>, CliError> {
+    // Like iproute2, IPv4 is the default family unless showing all tables
+    let family = if family == AddressFamily::Unspec && filter.table != 0 {
+        AddressFamily::Inet
+    } else {
+        family
+    };
+
+    let mut nl_msg = RouteMessage::default();
+    nl_msg.header.address_family = family;
+
+    let mut ret = Vec::new();
+    let mut routes = handle.route().get(nl_msg).execute();
+    while let Some(nl_msg) = routes.try_next().await? {
+        if family != AddressFamily::Unspec
+            && nl_msg.header.address_family != family
+        {
+            continue;
+        }
+        if filter.matches(&nl_msg) {
+            ret.push(nl_msg);
+        }
+    }
+    Ok(ret)
+}
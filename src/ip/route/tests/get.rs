@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_route_get() {
+    let dummy_name = "rtest-gdummy0";
+    let _lock = lock_net_test();
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
+
+    let result = std::panic::catch_unwind(|| {
+        exec_cmd(&["ip", "addr", "add", "198.51.100.1/24", "dev", dummy_name]);
+        exec_cmd(&[
+            "ip",
+            "addr",
+            "add",
+            "2001:db8:1::1/64",
+            "dev",
+            dummy_name,
+            "nodad",
+        ]);
+        exec_cmd(&[
+            "ip",
+            "route",
+            "add",
+            "203.0.113.0/24",
+            "via",
+            "198.51.100.254",
+            "dev",
+            dummy_name,
+        ]);
+
+        for args in [
+            &["route", "get", "203.0.113.5"][..],
+            &["-j", "route", "get", "203.0.113.5"][..],
+            &["-d", "route", "get", "203.0.113.5"][..],
+            &["route", "get", "fibmatch", "203.0.113.5"][..],
+            &["-j", "route", "get", "fibmatch", "203.0.113.5"][..],
+            &["route", "get", "198.51.100.1"][..],
+            &["route", "get", "to", "203.0.113.5", "mark", "5"][..],
+            &["route", "get", "203.0.113.5", "mark", "20"][..],
+            &["route", "get", "203.0.113.5", "from", "198.51.100.1"][..],
+            &["route", "get", "203.0.113.5", "oif", dummy_name][..],
+            &["route", "get", "203.0.113.5", "uid", "1000"][..],
+            &["route", "get", "2001:db8:1::5"][..],
+            &["-j", "route", "get", "2001:db8:1::5"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_route_get_without_address() {
+    let output = ip_rs_exec_cmd_output(&["route", "get"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("need at least a destination address")
+    );
+}
+
+#[test]
+fn test_route_get_absent_dev() {
+    let output = ip_rs_exec_cmd_output(&[
+        "route",
+        "get",
+        "192.0.2.1",
+        "oif",
+        "rtest-absent1",
+    ]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Cannot find device \"rtest-absent1\"")
+    );
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod get;
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod route;
+#[cfg(test)]
+mod save;
+
+// Routes without output device, e.g. `blackhole`, are not removed along
+// with the dummy interface. The `ip route flush` fails when the table
+// never existed, hence the exit status is ignored.
+fn flush_route_table(table: &str) {
+    for family in ["-4", "-6"] {
+        std::process::Command::new("ip")
+            .args([family, "route", "flush", "table", table])
+            .output()
+            .ok();
+    }
+}
@@ -0,0 +1,593 @@
+// SPDX-License-Identifier: MIT
+
+use super::flush_route_table;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+// Table for the routes without output device
+const SPECIAL_ROUTE_TABLE: &str = "1201";
+
+fn setup_dummy(dummy_name: &str) {
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
+    exec_cmd(&["ip", "addr", "add", "198.51.100.1/24", "dev", dummy_name]);
+    exec_cmd(&[
+        "ip",
+        "addr",
+        "add",
+        "2001:db8:1::1/64",
+        "dev",
+        dummy_name,
+        "nodad",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "route",
+        "add",
+        "203.0.113.0/24",
+        "via",
+        "198.51.100.254",
+        "dev",
+        dummy_name,
+        "metric",
+        "100",
+    ]);
+}
+
+fn cleanup_dummy(dummy_name: &str) {
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    flush_route_table(SPECIAL_ROUTE_TABLE);
+}
+
+// Modify the routes by iproute2 and by us, the outputs of `ip route show`
+// with specified filter should be identical.
+fn assert_route_update(
+    dummy_name: &str,
+    cmd: &str,
+    args: &[&str],
+    show_filter: &[&str],
+) {
+    let _lock = lock_net_test();
+    let show_args = [
+        &["ip", "-d", "-j", "route", "show", "table", "all"],
+        show_filter,
+    ]
+    .concat();
+
+    setup_dummy(dummy_name);
+    exec_cmd(&[&["ip", "route", cmd], args].concat());
+    let expected_output = exec_cmd(&show_args);
+    cleanup_dummy(dummy_name);
+
+    setup_dummy(dummy_name);
+    let result = std::panic::catch_unwind(|| {
+        let our_output = ip_rs_exec_cmd(&[&["route", cmd], args].concat());
+        assert!(our_output.is_empty());
+        let our_output = exec_cmd(&show_args);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+    cleanup_dummy(dummy_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_route_add() {
+    let dummy_name = "rtest-mdummy0";
+
+    for args in [
+        &["198.18.2.0/24", "dev", dummy_name][..],
+        &["to", "198.18.2.1", "dev", dummy_name, "scope", "host"][..],
+        &[
+            "192.0.2.0/24",
+            "via",
+            "198.51.100.254",
+            "dev",
+            dummy_name,
+            "proto",
+            "static",
+            "metric",
+            "200",
+            "table",
+            "100",
+        ][..],
+        &[
+            "default",
+            "via",
+            "198.51.100.254",
+            "dev",
+            dummy_name,
+            "table",
+            "1000",
+        ][..],
+        &[
+            "192.0.2.128/25",
+            "via",
+            "192.0.2.254",
+            "dev",
+            dummy_name,
+            "onlink",
+        ][..],
+        &[
+            "192.0.2.64/26",
+            "via",
+            "inet6",
+            "fe80::1",
+            "dev",
+            dummy_name,
+        ][..],
+        &[
+            "192.0.2.0/25",
+            "dev",
+            dummy_name,
+            "src",
+            "198.51.100.1",
+            "tos",
+            "0x10",
+        ][..],
+        &[
+            "2001:db8:2::/64",
+            "via",
+            "2001:db8:1::fe",
+            "dev",
+            dummy_name,
+            "pref",
+            "high",
+        ][..],
+    ] {
+        assert_route_update(dummy_name, "add", args, &["dev", dummy_name]);
+    }
+}
+
+#[test]
+fn test_route_add_metrics() {
+    let dummy_name = "rtest-mdummy4";
+
+    for args in [
+        &[
+            "198.18.2.0/24",
+            "dev",
+            dummy_name,
+            "mtu",
+            "lock",
+            "1400",
+            "advmss",
+            "1300",
+            "hoplimit",
+            "60",
+            "initcwnd",
+            "10",
+            "features",
+            "ecn",
+        ][..],
+        &[
+            "198.18.2.0/24",
+            "dev",
+            dummy_name,
+            "rtt",
+            "1.5s",
+            "rttvar",
+            "lock",
+            "100",
+            "rto_min",
+            "200ms",
+            "quickack",
+            "1",
+        ][..],
+        &["2001:db8:100::/64", "dev", dummy_name, "mtu", "1300"][..],
+    ] {
+        assert_route_update(dummy_name, "add", args, &["dev", dummy_name]);
+    }
+}
+
+#[test]
+fn test_route_add_special_types() {
+    let dummy_name = "rtest-mdummy5";
+
+    for (args, show_filter) in [
+        (
+            &[
+                "blackhole",
+                "198.18.4.0/24",
+                "table",
+                SPECIAL_ROUTE_TABLE,
+                "metric",
+                "5",
+            ][..],
+            &["table", SPECIAL_ROUTE_TABLE][..],
+        ),
+        (
+            &["unreachable", "198.18.5.0/24", "table", SPECIAL_ROUTE_TABLE][..],
+            &["table", SPECIAL_ROUTE_TABLE][..],
+        ),
+        (
+            &[
+                "prohibit",
+                "to",
+                "198.18.6.0/24",
+                "table",
+                SPECIAL_ROUTE_TABLE,
+            ][..],
+            &["table", SPECIAL_ROUTE_TABLE][..],
+        ),
+        (
+            &["throw", "198.18.7.0/24", "table", SPECIAL_ROUTE_TABLE][..],
+            &["table", SPECIAL_ROUTE_TABLE][..],
+        ),
+        (
+            &[
+                "blackhole",
+                "2001:db8:101::/64",
+                "table",
+                SPECIAL_ROUTE_TABLE,
+            ][..],
+            &["table", SPECIAL_ROUTE_TABLE][..],
+        ),
+        (
+            &["local", "198.18.8.1", "dev", dummy_name][..],
+            &["dev", dummy_name][..],
+        ),
+        (
+            &["broadcast", "198.18.8.255", "dev", dummy_name][..],
+            &["dev", dummy_name][..],
+        ),
+        (
+            &["multicast", "224.1.0.0/16", "dev", dummy_name][..],
+            &["dev", dummy_name][..],
+        ),
+    ] {
+        assert_route_update(dummy_name, "add", args, show_filter);
+    }
+}
+
+#[test]
+fn test_route_add_encap() {
+    let dummy_name = "rtest-mdummy6";
+
+    for args in [
+        &[
+            "198.18.9.0/24",
+            "encap",
+            "seg6",
+            "mode",
+            "encap",
+            "segs",
+            "2001:db8::1,2001:db8::2",
+            "dev",
+            dummy_name,
+        ][..],
+        &[
+            "198.18.10.0/24",
+            "encap",
+            "ip",
+            "id",
+            "100",
+            "dst",
+            "198.51.100.9",
+            "tos",
+            "0x10",
+            "seq",
+            "dev",
+            dummy_name,
+        ][..],
+        &[
+            "198.18.11.0/24",
+            "encap",
+            "ip6",
+            "dst",
+            "2001:db8::5",
+            "hoplimit",
+            "4",
+            "dev",
+            dummy_name,
+        ][..],
+        &[
+            "2001:db8:103::/64",
+            "encap",
+            "seg6",
+            "mode",
+            "inline",
+            "segs",
+            "2001:db8::1",
+            "dev",
+            dummy_name,
+        ][..],
+    ] {
+        assert_route_update(dummy_name, "add", args, &["dev", dummy_name]);
+    }
+}
+
+#[test]
+fn test_route_add_realms() {
+    let dummy_name = "rtest-mdummy7";
+
+    for args in [
+        &["198.18.4.0/24", "dev", dummy_name, "realm", "5"][..],
+        &["198.18.4.0/24", "dev", dummy_name, "realms", "3/cosmos"][..],
+        &["198.18.4.0/24", "dev", dummy_name, "realm", "0x10005"][..],
+        &["198.18.4.0/24", "dev", dummy_name, "realms", "4/"][..],
+    ] {
+        assert_route_update(dummy_name, "add", args, &["dev", dummy_name]);
+    }
+
+    // Multipath routes have no `RTA_OIF`, hence cannot be filtered by `dev`
+    assert_route_update(
+        dummy_name,
+        "add",
+        &[
+            "198.18.4.0/24",
+            "nexthop",
+            "via",
+            "198.51.100.254",
+            "dev",
+            dummy_name,
+            "realms",
+            "7/1",
+            "nexthop",
+            "via",
+            "198.51.100.253",
+            "dev",
+            dummy_name,
+            "realm",
+            "8",
+        ],
+        &["root", "198.18.4.0/24"],
+    );
+}
+
+#[test]
+fn test_route_change_replace_append_prepend() {
+    let dummy_name = "rtest-mdummy1";
+
+    for (cmd, args) in [
+        (
+            "change",
+            &[
+                "203.0.113.0/24",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+                "metric",
+                "100",
+            ][..],
+        ),
+        (
+            "replace",
+            &[
+                "203.0.113.0/24",
+                "dev",
+                dummy_name,
+                "metric",
+                "100",
+                "proto",
+                "static",
+            ][..],
+        ),
+        ("replace", &["198.18.2.0/24", "dev", dummy_name][..]),
+        (
+            "append",
+            &[
+                "203.0.113.0/24",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+                "metric",
+                "100",
+            ][..],
+        ),
+        (
+            "prepend",
+            &[
+                "203.0.113.0/24",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+                "metric",
+                "100",
+            ][..],
+        ),
+        ("delete", &["203.0.113.0/24", "dev", dummy_name][..]),
+        ("del", &["203.0.113.0/24", "metric", "100"][..]),
+    ] {
+        assert_route_update(dummy_name, cmd, args, &["dev", dummy_name]);
+    }
+}
+
+#[test]
+fn test_route_add_multipath() {
+    let dummy_name = "rtest-mdummy3";
+
+    // Multipath routes have no `RTA_OIF`, hence cannot be filtered by `dev`
+    for (cmd, args, show_filter) in [
+        (
+            "add",
+            &[
+                "198.18.0.0/24",
+                "proto",
+                "static",
+                "nexthop",
+                "via",
+                "198.51.100.254",
+                "dev",
+                dummy_name,
+                "weight",
+                "2",
+                "nexthop",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+                "onlink",
+            ][..],
+            &["root", "198.18.0.0/15"][..],
+        ),
+        (
+            "add",
+            &[
+                "198.18.1.0/24",
+                "nexthop",
+                "via",
+                "inet6",
+                "fe80::1",
+                "dev",
+                dummy_name,
+                "nexthop",
+                "dev",
+                dummy_name,
+            ][..],
+            &["root", "198.18.0.0/15"][..],
+        ),
+        (
+            "add",
+            &[
+                "2001:db8:100::/64",
+                "nexthop",
+                "via",
+                "2001:db8:1::fe",
+                "dev",
+                dummy_name,
+                "weight",
+                "3",
+                "nexthop",
+                "via",
+                "2001:db8:1::fd",
+                "dev",
+                dummy_name,
+            ][..],
+            &["root", "2001:db8:100::/48"][..],
+        ),
+        (
+            "replace",
+            &[
+                "203.0.113.0/24",
+                "metric",
+                "100",
+                "nexthop",
+                "via",
+                "198.51.100.254",
+                "dev",
+                dummy_name,
+                "nexthop",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+            ][..],
+            &["exact", "203.0.113.0/24"][..],
+        ),
+    ] {
+        assert_route_update(dummy_name, cmd, args, show_filter);
+    }
+}
+
+#[test]
+fn test_route_add_invalid_nexthop() {
+    for (args, error_msg) in [
+        (
+            &["192.0.2.0/24", "nexthop"][..],
+            "unexpected end of line after \"nexthop\"",
+        ),
+        (
+            &["192.0.2.0/24", "nexthop", "dev", "lo", "foo"][..],
+            "\"nexthop\" or end of line is expected instead of \"foo\"",
+        ),
+        (
+            &["192.0.2.0/24", "nexthop", "dev", "lo", "weight", "257"][..],
+            "argument \"257\" is wrong: \"weight\" is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "via", "192.0.2.1", "via", "192.0.2.2"][..],
+            "use nexthop syntax to specify multiple via",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["route", "add"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
+
+#[test]
+fn test_route_change_absent_route() {
+    let dummy_name = "rtest-mdummy2";
+    let _lock = lock_net_test();
+
+    setup_dummy(dummy_name);
+    let output = ip_rs_exec_cmd_output(&[
+        "route",
+        "change",
+        "198.18.3.0/24",
+        "dev",
+        dummy_name,
+    ]);
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_route_add_invalid_args() {
+    for (args, error_msg) in [
+        (&["dev", "lo"][..], "Usage: ip route"),
+        (
+            &["192.0.2.0/24", "dev", "lo", "proto", "foo"][..],
+            "argument \"foo\" is wrong: \"protocol\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "dev", "lo", "table", "foo"][..],
+            "argument \"foo\" is wrong: \"table\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "dev", "rtest-absent2"][..],
+            "Cannot find device \"rtest-absent2\"",
+        ),
+        (
+            &["192.0.2.0/24", "hoplimit", "256"][..],
+            "argument \"256\" is wrong: \"hoplimit\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "quickack", "2"][..],
+            "argument \"2\" is wrong: \"quickack\" value should be 0 or 1",
+        ),
+        (
+            &["192.0.2.0/24", "rtt", "1m"][..],
+            "argument \"1m\" is wrong: \"rtt\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "dev", "lo", "realms", "1/256"][..],
+            "argument \"1/256\" is wrong: \"realm\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "dev", "lo", "realm", "foo"][..],
+            "argument \"foo\" is wrong: \"realm\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "encap", "foo", "dev", "lo"][..],
+            "argument \"foo\" is wrong: \"encap type\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "encap", "mpls", "100"][..],
+            "unexpected end of line after \"encap\"",
+        ),
+        (
+            &["192.0.2.0/24", "encap", "ip", "ttl", "300"][..],
+            "argument \"300\" is wrong: \"ttl\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "encap", "seg6", "mode", "foo"][..],
+            "argument \"foo\" is wrong: \"mode\" value is invalid",
+        ),
+        (
+            &["192.0.2.0/24", "encap", "seg6", "segs", "2001:db8::1"][..],
+            "\"segs\" provided before \"mode\"",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["route", "add"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
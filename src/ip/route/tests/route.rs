@@ -0,0 +1,516 @@
+// SPDX-License-Identifier: MIT
+
+use super::flush_route_table;
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    lock_net_test,
+};
+
+fn with_routes<T>(dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
+
+    let result = std::panic::catch_unwind(|| {
+        exec_cmd(&["ip", "addr", "add", "198.51.100.1/24", "dev", dummy_name]);
+        exec_cmd(&[
+            "ip",
+            "addr",
+            "add",
+            "2001:db8:1::1/64",
+            "dev",
+            dummy_name,
+            "nodad",
+        ]);
+        for args in [
+            &[
+                "203.0.113.0/24",
+                "via",
+                "198.51.100.254",
+                "proto",
+                "static",
+                "metric",
+                "100",
+            ][..],
+            &["192.0.2.0/25", "scope", "link", "src", "198.51.100.1"][..],
+            &["192.0.2.128/25", "via", "192.0.2.254", "onlink"][..],
+            &["192.0.2.64/26", "via", "inet6", "fe80::1"][..],
+            &["2001:db8:2::/64", "via", "2001:db8:1::fe", "pref", "high"][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add"], args, &["dev", dummy_name]].concat(),
+            );
+        }
+
+        test();
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_route_show() {
+    let dummy_name = "rtest-dummy0";
+
+    with_routes(dummy_name, || {
+        for args in [
+            &[][..],
+            &["-d"][..],
+            &["-j"][..],
+            &["-d", "-j"][..],
+            &["-6"][..],
+            &["-6", "-j"][..],
+            &["-N", "-d"][..],
+        ] {
+            let args = [args, &["route", "show", "dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_filters() {
+    let dummy_name = "rtest-dummy1";
+
+    with_routes(dummy_name, || {
+        for args in [
+            &["proto", "static"][..],
+            &["proto", "all", "scope", "link"][..],
+            &["type", "unicast"][..],
+            &["via", "198.51.100.254"][..],
+            &["via", "198.51.100.0/24"][..],
+            &["src", "198.51.100.1"][..],
+            &["root", "192.0.2.0/24"][..],
+            &["match", "192.0.2.129"][..],
+            &["exact", "203.0.113.0/24"][..],
+            &["to", "192.0.2.0/25"][..],
+        ] {
+            let args =
+                [&["route", "show", "dev", dummy_name][..], args].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_table() {
+    let dummy_name = "rtest-dummy2";
+
+    with_routes(dummy_name, || {
+        exec_cmd(&[
+            "ip",
+            "route",
+            "add",
+            "192.0.2.0/24",
+            "via",
+            "198.51.100.254",
+            "dev",
+            dummy_name,
+            "table",
+            "100",
+        ]);
+        exec_cmd(&[
+            "ip",
+            "-6",
+            "route",
+            "add",
+            "2001:db8:3::/64",
+            "dev",
+            dummy_name,
+            "table",
+            "1000",
+        ]);
+        for args in [
+            &["route", "show", "table", "100"][..],
+            &["route", "show", "table", "main"][..],
+            &["route", "show", "table", "local"][..],
+            &["-6", "route", "show", "table", "local"][..],
+            &["-6", "route", "show", "table", "1000"][..],
+            &["route", "show", "table", "all"][..],
+            &["route", "show", "table", "0"][..],
+            &["-d", "route", "show", "table", "all"][..],
+            &["-j", "route", "show", "table", "all"][..],
+        ] {
+            let args = [args, &["dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+// The `dst` and `prefsrc` of local routes on loopback are resolvable to
+// `localhost` without DNS server
+#[test]
+fn test_route_show_resolve_lo() {
+    for args in [
+        &["-r", "route", "show", "table", "local", "dev", "lo"][..],
+        &["-r", "-j", "route", "show", "table", "local", "dev", "lo"][..],
+        &["-r", "route", "get", "127.0.0.1"][..],
+    ] {
+        let expected_output = exec_cmd(&[&["ip"], args].concat());
+        let our_output = ip_rs_exec_cmd(args);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    }
+}
+
+#[test]
+fn test_route_show_multipath() {
+    let dummy_name = "rtest-dummy3";
+
+    with_routes(dummy_name, || {
+        for args in [
+            &[
+                "198.18.0.0/24",
+                "proto",
+                "static",
+                "nexthop",
+                "via",
+                "198.51.100.254",
+                "dev",
+                dummy_name,
+                "weight",
+                "2",
+                "nexthop",
+                "via",
+                "198.51.100.253",
+                "dev",
+                dummy_name,
+                "onlink",
+            ][..],
+            &[
+                "198.18.1.0/24",
+                "nexthop",
+                "via",
+                "inet6",
+                "fe80::1",
+                "dev",
+                dummy_name,
+                "nexthop",
+                "dev",
+                dummy_name,
+            ][..],
+            &[
+                "2001:db8:100::/64",
+                "pref",
+                "high",
+                "nexthop",
+                "via",
+                "2001:db8:1::fe",
+                "dev",
+                dummy_name,
+                "weight",
+                "3",
+                "nexthop",
+                "via",
+                "2001:db8:1::fd",
+                "dev",
+                dummy_name,
+            ][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add", "table", "1100"], args].concat(),
+            );
+        }
+        for args in [
+            &[][..],
+            &["-d"][..],
+            &["-j"][..],
+            &["-o"][..],
+            &["-6"][..],
+            &["-6", "-j"][..],
+        ] {
+            let args = [args, &["route", "show", "table", "1100"]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_special_types() {
+    let dummy_name = "rtest-dummy5";
+
+    with_routes(dummy_name, || {
+        flush_route_table("1200");
+        for args in [
+            &["blackhole", "198.18.4.0/24", "metric", "5"][..],
+            &["unreachable", "198.18.5.0/24"][..],
+            &["prohibit", "198.18.6.0/24"][..],
+            &["throw", "198.18.7.0/24"][..],
+            &["blackhole", "2001:db8:101::/64"][..],
+            &["unreachable", "2001:db8:102::/64"][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add", "table", "1200"], args].concat(),
+            );
+        }
+        exec_cmd(&[
+            "ip",
+            "route",
+            "add",
+            "local",
+            "198.18.8.1",
+            "dev",
+            dummy_name,
+        ]);
+        exec_cmd(&[
+            "ip",
+            "route",
+            "add",
+            "broadcast",
+            "198.18.8.255",
+            "dev",
+            dummy_name,
+        ]);
+
+        for args in [
+            &["route", "show", "table", "1200"][..],
+            &["-d", "route", "show", "table", "1200"][..],
+            &["-j", "route", "show", "table", "1200"][..],
+            &["-6", "route", "show", "table", "1200"][..],
+            &["-6", "-j", "route", "show", "table", "1200"][..],
+            &["route", "show", "table", "1200", "type", "blackhole"][..],
+            &["route", "show", "table", "local", "dev", dummy_name][..],
+            &["-j", "route", "show", "table", "local", "dev", dummy_name][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        flush_route_table("1200");
+    });
+}
+
+#[test]
+fn test_route_show_encap() {
+    let dummy_name = "rtest-dummy6";
+
+    with_routes(dummy_name, || {
+        for args in [
+            &[
+                "198.18.9.0/24",
+                "encap",
+                "seg6",
+                "mode",
+                "encap",
+                "segs",
+                "2001:db8::1,2001:db8::2",
+            ][..],
+            &[
+                "198.18.10.0/24",
+                "encap",
+                "ip",
+                "id",
+                "100",
+                "dst",
+                "198.51.100.9",
+                "ttl",
+                "10",
+                "key",
+                "csum",
+            ][..],
+            &[
+                "198.18.11.0/24",
+                "encap",
+                "ip6",
+                "id",
+                "3",
+                "dst",
+                "2001:db8::5",
+                "hoplimit",
+                "4",
+                "tc",
+                "0x10",
+            ][..],
+            &[
+                "2001:db8:103::/64",
+                "encap",
+                "seg6",
+                "mode",
+                "inline",
+                "segs",
+                "2001:db8::1",
+            ][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add"], args, &["dev", dummy_name]].concat(),
+            );
+        }
+        for args in [
+            &[][..],
+            &["-d"][..],
+            &["-j"][..],
+            &["-6"][..],
+            &["-6", "-j"][..],
+        ] {
+            let args = [args, &["route", "show", "dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_metrics() {
+    let dummy_name = "rtest-dummy4";
+
+    with_routes(dummy_name, || {
+        for args in [
+            &[
+                "198.18.0.0/24",
+                "mtu",
+                "lock",
+                "1400",
+                "window",
+                "1000",
+                "rtt",
+                "1.5s",
+                "rttvar",
+                "100ms",
+                "ssthresh",
+                "10",
+                "cwnd",
+                "lock",
+                "20",
+                "advmss",
+                "1300",
+                "reordering",
+                "5",
+                "hoplimit",
+                "60",
+                "initcwnd",
+                "10",
+                "initrwnd",
+                "12",
+                "features",
+                "ecn",
+                "quickack",
+                "1",
+                "rto_min",
+                "200ms",
+                "fastopen_no_cookie",
+                "1",
+            ][..],
+            &["198.18.1.0/24", "rtt", "100", "rttvar", "1234567ms"][..],
+            &["2001:db8:100::/64", "mtu", "1300", "hoplimit", "10"][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add"], args, &["dev", dummy_name]].concat(),
+            );
+        }
+        for args in [&[][..], &["-j"][..], &["-6"][..], &["-6", "-j"][..]] {
+            let args = [args, &["route", "show", "dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_protocol() {
+    let dummy_name = "rtest-dummy7";
+
+    with_routes(dummy_name, || {
+        for (prefix, protocol) in [
+            ("198.18.20.0/24", "babel"),
+            ("198.18.21.0/24", "12"),
+            ("198.18.22.0/24", "201"),
+        ] {
+            exec_cmd(&[
+                "ip", "route", "add", prefix, "dev", dummy_name, "proto",
+                protocol,
+            ]);
+        }
+        for args in [
+            &["route", "show"][..],
+            &["-N", "route", "show"][..],
+            &["-j", "route", "show"][..],
+            &["route", "show", "proto", "babel"][..],
+            &["route", "show", "proto", "42"][..],
+            &["route", "show", "proto", "zebra"][..],
+            &["route", "show", "proto", "201"][..],
+        ] {
+            let args = [args, &["dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_realms() {
+    let dummy_name = "rtest-dummy8";
+
+    with_routes(dummy_name, || {
+        for (prefix, realms) in [
+            ("198.18.23.0/24", "5"),
+            ("198.18.24.0/24", "3/cosmos"),
+            ("198.18.25.0/24", "0x10005"),
+        ] {
+            exec_cmd(&[
+                "ip", "route", "add", prefix, "dev", dummy_name, "realms",
+                realms,
+            ]);
+        }
+        for args in [&[][..], &["-N"][..], &["-j"][..], &["-d", "-j"][..]] {
+            let args = [args, &["route", "show", "dev", dummy_name]].concat();
+            let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+            let our_output = ip_rs_exec_cmd(&args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+}
+
+#[test]
+fn test_route_show_invalid_table() {
+    let output = ip_rs_exec_cmd_output(&["route", "show", "table", "foo"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("argument \"foo\" is wrong: table id value is invalid")
+    );
+}
+
+#[test]
+fn test_route_show_absent_dev() {
+    let output =
+        ip_rs_exec_cmd_output(&["route", "show", "dev", "rtest-absent0"]);
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Cannot find device \"rtest-absent0\"")
+    );
+}
+
+#[test]
+fn test_route_alias_r_s() {
+    assert_alias_output(
+        &["route", "show", "dev", "lo"],
+        &["r", "s", "dev", "lo"],
+    );
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT
+
+use super::flush_route_table;
+use crate::tests::{
+    exec_cmd, exec_cmd_with_stdin, ip_rs_exec_cmd_output,
+    ip_rs_exec_cmd_with_stdin, lock_net_test,
+};
+
+const SAVE_TEST_TABLE: &str = "1300";
+
+fn with_saved_routes<T>(dummy_name: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+    exec_cmd(&["ip", "link", "set", dummy_name, "up"]);
+    flush_route_table(SAVE_TEST_TABLE);
+
+    let result = std::panic::catch_unwind(|| {
+        exec_cmd(&["ip", "addr", "add", "198.51.100.1/24", "dev", dummy_name]);
+        exec_cmd(&[
+            "ip",
+            "addr",
+            "add",
+            "2001:db8:1::1/64",
+            "dev",
+            dummy_name,
+            "nodad",
+        ]);
+        for args in [
+            &[
+                "203.0.113.0/24",
+                "via",
+                "198.51.100.254",
+                "dev",
+                dummy_name,
+                "proto",
+                "static",
+                "metric",
+                "100",
+            ][..],
+            &["198.18.12.0/24", "dev", dummy_name, "src", "198.51.100.1"][..],
+            &["blackhole", "198.18.13.0/24"][..],
+            &[
+                "2001:db8:2::/64",
+                "via",
+                "2001:db8:1::fe",
+                "dev",
+                dummy_name,
+            ][..],
+        ] {
+            exec_cmd(
+                &[&["ip", "route", "add", "table", SAVE_TEST_TABLE], args]
+                    .concat(),
+            );
+        }
+
+        test();
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    flush_route_table(SAVE_TEST_TABLE);
+    assert!(result.is_ok());
+}
+
+fn iproute2_save(args: &[&str]) -> Vec<u8> {
+    let output = exec_cmd_with_stdin(
+        &[&["ip"], args, &["route", "save", "table", SAVE_TEST_TABLE]].concat(),
+        &[],
+    );
+    assert!(output.status.success());
+    output.stdout
+}
+
+fn our_save(args: &[&str]) -> Vec<u8> {
+    let output = ip_rs_exec_cmd_output(
+        &[args, &["route", "save", "table", SAVE_TEST_TABLE]].concat(),
+    );
+    assert!(output.status.success());
+    output.stdout
+}
+
+#[test]
+fn test_route_save_showdump() {
+    let dummy_name = "rtest-sdummy0";
+
+    with_saved_routes(dummy_name, || {
+        for family_args in [&[][..], &["-6"][..]] {
+            let expected_dump = iproute2_save(family_args);
+            let our_dump = our_save(family_args);
+
+            for args in [&[][..], &["-d"][..], &["-j"][..]] {
+                let args = [args, &["route", "showdump"]].concat();
+                let expected_output = exec_cmd_with_stdin(
+                    &[&["ip"], &args[..]].concat(),
+                    &expected_dump,
+                );
+                assert!(expected_output.status.success());
+
+                // Our dump shown by iproute2
+                let output = exec_cmd_with_stdin(
+                    &[&["ip"], &args[..]].concat(),
+                    &our_dump,
+                );
+                pretty_assertions::assert_eq!(
+                    String::from_utf8_lossy(&expected_output.stdout),
+                    String::from_utf8_lossy(&output.stdout)
+                );
+
+                // The iproute2 dump shown by us
+                let output = ip_rs_exec_cmd_with_stdin(&args, &expected_dump);
+                assert!(output.status.success());
+                pretty_assertions::assert_eq!(
+                    String::from_utf8_lossy(&expected_output.stdout),
+                    String::from_utf8_lossy(&output.stdout)
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn test_route_restore() {
+    let dummy_name = "rtest-sdummy1";
+
+    with_saved_routes(dummy_name, || {
+        let show_args = ["ip", "route", "show", "table", SAVE_TEST_TABLE];
+        let expected_output = exec_cmd(&show_args);
+
+        for dump in [iproute2_save(&[]), our_save(&[])] {
+            exec_cmd(&["ip", "route", "flush", "table", SAVE_TEST_TABLE]);
+            let output =
+                ip_rs_exec_cmd_with_stdin(&["route", "restore"], &dump);
+            assert!(output.status.success());
+
+            pretty_assertions::assert_eq!(
+                expected_output,
+                exec_cmd(&show_args)
+            );
+
+            // Restoring existing routes is not a failure
+            let output =
+                ip_rs_exec_cmd_with_stdin(&["route", "restore"], &dump);
+            assert!(output.status.success());
+        }
+    });
+}
+
+#[test]
+fn test_route_showdump_magic_mismatch() {
+    let output = ip_rs_exec_cmd_with_stdin(&["route", "showdump"], b"foo");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Magic mismatch (0 elems, 6f6f66 magic)")
+    );
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::{RuleModifyCmd, handle_modify},
+    save::{handle_flush, handle_restore, handle_save},
+    show::{CliRuleInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct RuleCommand;
+
+impl RuleCommand {
+    pub(crate) const CMD: &'static str = "rule";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("routing policy database management")
+            .alias("rul")
+            .alias("ru")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show rules")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("add").about("add new rule").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete rule")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("flush").about("flush rules").arg(
+                    clap::Arg::new("options")
+                        .action(clap::ArgAction::Append)
+                        .trailing_var_arg(true),
+                ),
+            )
+            .subcommand(
+                clap::Command::new("save")
+                    .about("save rules as binary stream to stdout")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("restore")
+                    .about("restore rules from binary stream of stdin"),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliRuleInfo>>, CliError> {
+        let handle = ctx.handle();
+        for (subcommand, cmd) in [
+            ("add", RuleModifyCmd::Add),
+            ("delete", RuleModifyCmd::Delete),
+        ] {
+            if let Some(matches) = matches.subcommand_matches(subcommand) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(handle, &opts, get_family(matches), cmd).await?;
+                return Ok(None);
+            }
+        }
+
+        if let Some(matches) = matches.subcommand_matches("flush") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_flush(
+                handle,
+                &opts,
+                get_family(matches),
+                matches.get_flag("FORCE"),
+            )
+            .await?;
+            return Ok(None);
+        }
+
+        if let Some(matches) = matches.subcommand_matches("save") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_save(handle, &opts, get_family(matches)).await?;
+            return Ok(None);
+        }
+
+        if matches.subcommand_matches("restore").is_some() {
+            handle_restore(handle).await?;
+            return Ok(None);
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(
+            handle,
+            &opts,
+            get_family(matches),
+            matches.get_flag("DETAILS"),
+            matches.get_flag("NUMERIC"),
+            matches.get_flag("RESOLVE"),
+        )
+        .await
+        .map(Into::into)
+    }
+}
@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    rule::{RuleAttribute, RuleMessage, RulePortRange, RuleUidRange},
+};
+
+use super::{
+    modify::{parse_port_range, parse_uid_range},
+    names::{FIB_RULE_INVERT, parse_ipproto},
+};
+use crate::{
+    link::{check_ifname, parse_num},
+    prefix::CliIpPrefix,
+    route::{parse_protocol, parse_table, parse_tos},
+};
+
+/// Filters of `ip rule show`, `ip rule flush` and `ip rule save` following
+/// the argument grammar of iproute2 `iprule_list_flush_or_save()`.
+#[derive(Debug, Default)]
+pub(crate) struct RuleShowFilter<'a> {
+    pref: Option<u32>,
+    not: bool,
+    tos: Option<u8>,
+    /// `0` for any mark
+    fwmark: u32,
+    /// `0` for any mask
+    fwmask: u32,
+    iif: Option<&'a str>,
+    oif: Option<&'a str>,
+    l3mdev: bool,
+    uid_range: Option<RuleUidRange>,
+    /// `0` for any tunnel ID
+    tun_id: u64,
+    /// `0` for all tables
+    table: u32,
+    src: Option<CliIpPrefix>,
+    dst: Option<CliIpPrefix>,
+    /// `None` for `protocol all`
+    pub(crate) protocol: Option<u8>,
+    /// `0` for any IP protocol
+    ipproto: u8,
+    sport: Option<RulePortRange>,
+    dport: Option<RulePortRange>,
+}
+
+impl<'a> RuleShowFilter<'a> {
+    pub(crate) fn parse(
+        opts: &[&'a str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        // Like iproute2, the prefixes are IPv4 unless `-6` is specified
+        let family = if family == AddressFamily::Unspec {
+            AddressFamily::Inet
+        } else {
+            family
+        };
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "preference" | "pref" | "order" | "priority" | "prio" => {
+                    ret.pref = Some(parse_num(
+                        next_opt(&mut opts)?,
+                        "preference value is invalid",
+                    )?);
+                }
+                "not" => ret.not = true,
+                "tos" => {
+                    ret.tos = Some(parse_tos(next_opt(&mut opts)?)?);
+                }
+                "fwmark" => {
+                    let value = next_opt(&mut opts)?;
+                    let (mark, mask) = match value.split_once('/') {
+                        Some((mark, mask)) => (mark, Some(mask)),
+                        None => (value, None),
+                    };
+                    ret.fwmark = parse_num(mark, "fwmark value is invalid")?;
+                    if let Some(mask) = mask {
+                        ret.fwmask =
+                            parse_num(mask, "fwmask value is invalid")?;
+                    }
+                }
+                "dev" | "iif" => {
+                    let value = next_opt(&mut opts)?;
+                    check_ifname(value, "\"iif\"/\"dev\"")?;
+                    ret.iif = Some(value);
+                }
+                "oif" => {
+                    let value = next_opt(&mut opts)?;
+                    check_ifname(value, "\"oif\"")?;
+                    ret.oif = Some(value);
+                }
+                "l3mdev" => ret.l3mdev = true,
+                "uidrange" => {
+                    ret.uid_range =
+                        Some(parse_uid_range(next_opt(&mut opts)?)?);
+                }
+                "tun_id" => {
+                    ret.tun_id = parse_num(
+                        next_opt(&mut opts)?,
+                        "\"tun_id\" value is invalid",
+                    )?;
+                }
+                "lookup" | "table" => {
+                    ret.table = parse_table(
+                        next_opt(&mut opts)?,
+                        "table id value is invalid",
+                    )?;
+                }
+                "from" | "src" => {
+                    ret.src = Some(parse_prefix(
+                        next_opt(&mut opts)?,
+                        family,
+                        "from value is invalid",
+                    )?);
+                }
+                "protocol" | "proto" => {
+                    let protocol = next_opt(&mut opts)?;
+                    ret.protocol = if protocol == "all" {
+                        None
+                    } else {
+                        Some(parse_protocol(protocol, "invalid \"protocol\"")?)
+                    };
+                }
+                "ipproto" => {
+                    ret.ipproto = parse_ipproto(next_opt(&mut opts)?)?;
+                }
+                "sport" => {
+                    ret.sport = Some(parse_port_range(next_opt(&mut opts)?)?);
+                }
+                "dport" => {
+                    ret.dport = Some(parse_port_range(next_opt(&mut opts)?)?);
+                }
+                _ => {
+                    let mut value = *opt;
+                    if matches!(value, "dst" | "to") {
+                        value = next_opt(&mut opts)?;
+                    }
+                    ret.dst = Some(parse_prefix(
+                        value,
+                        family,
+                        "to value is invalid",
+                    )?);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    // Equal to iproute2 `filter_nlmsg()` with the protocol check of
+    // `print_rule()`
+    pub(crate) fn matches(&self, nl_msg: &RuleMessage) -> bool {
+        let header = &nl_msg.header;
+        let mut pref = 0;
+        let mut src = None;
+        let mut dst = None;
+        let mut fwmark = 0;
+        let mut fwmask = 0;
+        let mut iif = None;
+        let mut oif = None;
+        let mut l3mdev = false;
+        let mut uid_range = None;
+        let mut tun_id = 0;
+        let mut table = u32::from(header.table);
+        let mut protocol = None;
+        let mut ipproto = 0;
+        let mut sport = None;
+        let mut dport = None;
+        for nla in nl_msg.attributes.iter() {
+            match nla {
+                RuleAttribute::Priority(v) => pref = *v,
+                RuleAttribute::Source(v) => src = Some(*v),
+                RuleAttribute::Destination(v) => dst = Some(*v),
+                RuleAttribute::FwMark(v) => fwmark = *v,
+                RuleAttribute::FwMask(v) => fwmask = *v,
+                RuleAttribute::Iifname(v) => iif = Some(v.as_str()),
+                RuleAttribute::Oifname(v) => oif = Some(v.as_str()),
+                RuleAttribute::L3MDev(v) => l3mdev = *v,
+                RuleAttribute::UidRange(v) => uid_range = Some(v),
+                RuleAttribute::TunId(v) => tun_id = *v,
+                RuleAttribute::Table(v) => table = *v,
+                RuleAttribute::Protocol(v) => protocol = Some(u8::from(*v)),
+                RuleAttribute::IpProtocol(v) => ipproto = i32::from(*v) as u8,
+                RuleAttribute::SourcePortRange(v) => sport = Some(v),
+                RuleAttribute::DestinationPortRange(v) => dport = Some(v),
+                _ => (),
+            }
+        }
+
+        let prefix_matches =
+            |prefix: &Option<CliIpPrefix>, addr: Option<IpAddr>, len: u8| {
+                let Some(prefix) = prefix else {
+                    return true;
+                };
+                matches!(
+                    (prefix.addr, header.family),
+                    (IpAddr::V4(_), AddressFamily::Inet)
+                        | (IpAddr::V6(_), AddressFamily::Inet6)
+                ) && prefix.prefix_len <= len
+                    && addr.is_none_or(|addr| prefix.contains(&addr))
+            };
+        let range_matches =
+            |filter: &Option<RulePortRange>, range: Option<&RulePortRange>| {
+                match (filter, range) {
+                    (Some(filter), _) if filter.start == 0 => true,
+                    (Some(filter), Some(range)) => {
+                        filter.start == range.start && filter.end == range.end
+                    }
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                }
+            };
+
+        self.pref.is_none_or(|v| v == pref)
+            && (!self.not || header.flags.bits() & FIB_RULE_INVERT != 0)
+            && prefix_matches(&self.src, src, header.src_len)
+            && prefix_matches(&self.dst, dst, header.dst_len)
+            && self.tos.is_none_or(|v| v == header.tos)
+            && (self.fwmark == 0 || self.fwmark == fwmark)
+            && (self.fwmask == 0 || self.fwmask == fwmask)
+            && self.iif.is_none_or(|v| iif == Some(v))
+            && self.oif.is_none_or(|v| oif == Some(v))
+            && (!self.l3mdev || l3mdev)
+            && self.uid_range.as_ref().is_none_or(|v| {
+                uid_range.is_some_and(|r| r.start == v.start && r.end == v.end)
+            })
+            && (self.ipproto == 0 || self.ipproto == ipproto)
+            && range_matches(&self.sport, sport)
+            && range_matches(&self.dport, dport)
+            && (self.tun_id == 0 || self.tun_id == tun_id)
+            && (self.table == 0 || self.table == table)
+            && self
+                .protocol
+                .is_none_or(|v| protocol.is_none_or(|p| p == v))
+    }
+}
+
+fn parse_prefix(
+    value: &str,
+    family: AddressFamily,
+    error_msg: &str,
+) -> Result<CliIpPrefix, CliError> {
+    CliIpPrefix::parse(value, family).map_err(|_| {
+        CliError::from(
+            format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+        )
+    })
+}
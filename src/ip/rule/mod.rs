@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod filter;
+mod modify;
+mod names;
+mod save;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{
+    cli::RuleCommand,
+    names::{ipproto_from_name, ipproto_lookup},
+    show::{CliRuleInfo, parse_nl_msg_to_rule},
+};
@@ -0,0 +1,379 @@
+// SPDX-License-Identifier: MIT
+
+use std::{io::Write, net::IpAddr};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST,
+        NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, IpProtocol, RouteNetlinkMessage,
+        route::RouteProtocol,
+        rule::{
+            RuleAction, RuleAttribute, RuleFlags, RuleMessage, RulePortRange,
+            RuleUidRange,
+        },
+    },
+};
+
+use super::names::{
+    FIB_RULE_INVERT, FR_ACT_GOTO, FR_ACT_NOP, FR_ACT_TO_TBL, FR_ACT_UNSPEC,
+    RTN_NAT, parse_ipproto,
+};
+use crate::{
+    link::{check_ifname, parse_num},
+    prefix::{CliIpPrefix, parse_ip_addr},
+    route::{
+        RT_TABLE_MAIN, parse_protocol, parse_realms, parse_table, parse_tos,
+        parse_type,
+    },
+};
+
+// The `RTA_GATEWAY` used by the deprecated `nat` action
+const RTA_GATEWAY: u16 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RuleModifyCmd {
+    Add,
+    Delete,
+}
+
+impl std::fmt::Display for RuleModifyCmd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add => write!(f, "add"),
+            Self::Delete => write!(f, "del"),
+        }
+    }
+}
+
+/// The selector and action of `ip rule add` and `ip rule delete` following
+/// the argument grammar of iproute2 `iprule_modify()`.
+#[derive(Debug, Default)]
+struct RuleModifyOptions {
+    flags: u32,
+    tos: u8,
+    /// The `FR_ACT_*` action, `None` for the default of the command
+    action: Option<u8>,
+    /// The table specified or implied by the action
+    table_ok: bool,
+    table: u32,
+    src: Option<CliIpPrefix>,
+    dst: Option<CliIpPrefix>,
+    l3mdev: bool,
+    attributes: Vec<RuleAttribute>,
+}
+
+impl RuleModifyOptions {
+    fn parse(opts: &[&str], family: AddressFamily) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "not" => ret.flags |= FIB_RULE_INVERT,
+                "from" => {
+                    ret.src =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "to" => {
+                    ret.dst =
+                        Some(CliIpPrefix::parse(next_opt(&mut opts)?, family)?);
+                }
+                "preference" | "pref" | "order" | "priority" | "prio" => {
+                    ret.attributes.push(RuleAttribute::Priority(parse_num(
+                        next_opt(&mut opts)?,
+                        "preference value is invalid",
+                    )?));
+                }
+                "tos" | "dsfield" => {
+                    ret.tos = parse_tos(next_opt(&mut opts)?)?;
+                }
+                "fwmark" => {
+                    let value = next_opt(&mut opts)?;
+                    let (mark, mask) = match value.split_once('/') {
+                        Some((mark, mask)) => (mark, Some(mask)),
+                        None => (value, None),
+                    };
+                    ret.attributes.push(RuleAttribute::FwMark(parse_num(
+                        mark,
+                        "fwmark value is invalid",
+                    )?));
+                    if let Some(mask) = mask {
+                        ret.attributes.push(RuleAttribute::FwMask(parse_num(
+                            mask,
+                            "fwmask value is invalid",
+                        )?));
+                    }
+                }
+                "realms" | "realm" => {
+                    ret.attributes.push(RuleAttribute::Realm(parse_realms(
+                        next_opt(&mut opts)?,
+                        "invalid realms",
+                    )?));
+                }
+                "protocol" | "proto" => {
+                    ret.attributes.push(RuleAttribute::Protocol(
+                        RouteProtocol::from(parse_protocol(
+                            next_opt(&mut opts)?,
+                            "\"protocol\" value is invalid",
+                        )?),
+                    ));
+                }
+                "tun_id" => {
+                    ret.attributes.push(RuleAttribute::TunId(parse_num(
+                        next_opt(&mut opts)?,
+                        "\"tun_id\" value is invalid",
+                    )?));
+                }
+                "table" | "lookup" => {
+                    ret.table =
+                        parse_table(next_opt(&mut opts)?, "invalid table ID")?;
+                    ret.table_ok = true;
+                }
+                "suppress_prefixlength" | "sup_pl" => {
+                    let prefix_len: i32 = parse_num(
+                        next_opt(&mut opts)?,
+                        "suppress_prefixlength value is invalid",
+                    )?;
+                    if prefix_len < 0 {
+                        return Err(CliError::from(
+                            format!(
+                                "argument \"{prefix_len}\" is wrong: \
+                                 suppress_prefixlength value is invalid"
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.attributes.push(RuleAttribute::SuppressPrefixLen(
+                        prefix_len as u32,
+                    ));
+                }
+                "suppress_ifgroup" | "sup_group" => {
+                    let value = next_opt(&mut opts)?;
+                    // TODO: Read `/etc/iproute2/group` like link show
+                    let group: i32 = if value == "default" {
+                        0
+                    } else {
+                        parse_num(value, "Invalid \"suppress_ifgroup\" value")?
+                    };
+                    ret.attributes
+                        .push(RuleAttribute::SuppressIfGroup(group as u32));
+                }
+                "dev" | "iif" => {
+                    let value = next_opt(&mut opts)?;
+                    check_ifname(value, "\"iif\"/\"dev\"")?;
+                    ret.attributes
+                        .push(RuleAttribute::Iifname(value.to_string()));
+                }
+                "oif" => {
+                    let value = next_opt(&mut opts)?;
+                    check_ifname(value, "\"oif\"")?;
+                    ret.attributes
+                        .push(RuleAttribute::Oifname(value.to_string()));
+                }
+                "l3mdev" => {
+                    ret.attributes.push(RuleAttribute::L3MDev(true));
+                    ret.table_ok = true;
+                    ret.l3mdev = true;
+                }
+                "uidrange" => {
+                    ret.attributes.push(RuleAttribute::UidRange(
+                        parse_uid_range(next_opt(&mut opts)?)?,
+                    ));
+                }
+                "nat" | "map-to" => {
+                    let value = next_opt(&mut opts)?;
+                    writeln!(
+                        std::io::stderr(),
+                        "Warning: route NAT is deprecated"
+                    )
+                    .ok();
+                    let IpAddr::V4(addr) =
+                        parse_ip_addr(value, AddressFamily::Inet)?
+                    else {
+                        unreachable!()
+                    };
+                    ret.attributes.push(RuleAttribute::Other(DefaultNla::new(
+                        RTA_GATEWAY,
+                        addr.octets().to_vec(),
+                    )));
+                    ret.action = Some(RTN_NAT);
+                }
+                "ipproto" => {
+                    ret.attributes.push(RuleAttribute::IpProtocol(
+                        IpProtocol::from(i32::from(parse_ipproto(next_opt(
+                            &mut opts,
+                        )?)?)),
+                    ));
+                }
+                "sport" => {
+                    ret.attributes.push(RuleAttribute::SourcePortRange(
+                        parse_port_range(next_opt(&mut opts)?)?,
+                    ));
+                }
+                "dport" => {
+                    ret.attributes.push(RuleAttribute::DestinationPortRange(
+                        parse_port_range(next_opt(&mut opts)?)?,
+                    ));
+                }
+                _ => {
+                    let value = if *opt == "type" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    ret.action = Some(match value {
+                        "goto" => {
+                            ret.attributes.push(RuleAttribute::Goto(
+                                parse_num(
+                                    next_opt(&mut opts)?,
+                                    "invalid target",
+                                )?,
+                            ));
+                            FR_ACT_GOTO
+                        }
+                        "nop" => FR_ACT_NOP,
+                        _ => parse_type(value).map_err(|_| {
+                            CliError::from(
+                                format!(
+                                    "argument \"{value}\" is wrong: Failed to \
+                                     parse rule type"
+                                )
+                                .as_str(),
+                            )
+                        })?,
+                    });
+                    ret.table_ok = true;
+                }
+            }
+        }
+
+        if ret.l3mdev && ret.table != 0 {
+            return Err(CliError::from(
+                "table can not be specified for l3mdev rules",
+            ));
+        }
+        Ok(ret)
+    }
+
+    fn to_nl_msg(
+        &self,
+        cmd: RuleModifyCmd,
+        family: AddressFamily,
+    ) -> RuleMessage {
+        let mut nl_msg = RuleMessage::default();
+        // Like iproute2, IPv4 is the default family
+        nl_msg.header.family = if family == AddressFamily::Unspec {
+            AddressFamily::Inet
+        } else {
+            family
+        };
+        nl_msg.header.tos = self.tos;
+        nl_msg.header.flags = RuleFlags::from_bits_retain(self.flags);
+        nl_msg.header.action = RuleAction::from(self.action.unwrap_or(
+            if cmd == RuleModifyCmd::Add {
+                FR_ACT_TO_TBL
+            } else {
+                FR_ACT_UNSPEC
+            },
+        ));
+
+        // The zero length prefix, e.g. `from all`, matches any address
+        if let Some(src) = self.src.filter(|p| p.prefix_len != 0) {
+            nl_msg.header.src_len = src.prefix_len;
+            nl_msg.attributes.push(RuleAttribute::Source(src.addr));
+        }
+        if let Some(dst) = self.dst.filter(|p| p.prefix_len != 0) {
+            nl_msg.header.dst_len = dst.prefix_len;
+            nl_msg.attributes.push(RuleAttribute::Destination(dst.addr));
+        }
+
+        let table = if !self.table_ok && cmd == RuleModifyCmd::Add {
+            RT_TABLE_MAIN
+        } else {
+            self.table
+        };
+        // The table ID beyond `u8` is stored in `FRA_TABLE`
+        match u8::try_from(table) {
+            Ok(table) => nl_msg.header.table = table,
+            Err(_) => nl_msg.attributes.push(RuleAttribute::Table(table)),
+        }
+        nl_msg.attributes.extend(self.attributes.iter().cloned());
+        nl_msg
+    }
+}
+
+// Like iproute2 `sscanf("%u-%u")`
+pub(super) fn parse_uid_range(value: &str) -> Result<RuleUidRange, CliError> {
+    let Some((start, end)) = value
+        .split_once('-')
+        .and_then(|(s, e)| Some((s.parse().ok()?, e.parse().ok()?)))
+    else {
+        return Err(CliError::from(
+            format!("argument \"{value}\" is wrong: invalid UID range")
+                .as_str(),
+        ));
+    };
+    let mut ret = RuleUidRange::default();
+    ret.start = start;
+    ret.end = end;
+    Ok(ret)
+}
+
+// Like iproute2 `sscanf("%hu-%hu")`, the port range could be a single port
+pub(super) fn parse_port_range(value: &str) -> Result<RulePortRange, CliError> {
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (value, None),
+    };
+    let start: u16 = start.parse().map_err(|_| {
+        CliError::from(
+            format!("argument \"{value}\" is wrong: invalid port range")
+                .as_str(),
+        )
+    })?;
+    let mut ret = RulePortRange::default();
+    ret.start = start;
+    ret.end = end.and_then(|e| e.parse().ok()).unwrap_or(start);
+    Ok(ret)
+}
+
+pub(crate) async fn handle_modify(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    cmd: RuleModifyCmd,
+) -> Result<(), CliError> {
+    if opts.is_empty() {
+        return Err(CliError::from(
+            format!("\"ip rule {cmd}\" requires arguments.").as_str(),
+        ));
+    }
+    let nl_msg = RuleModifyOptions::parse(opts, family)?.to_nl_msg(cmd, family);
+
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(match cmd {
+            RuleModifyCmd::Add => RouteNetlinkMessage::NewRule(nl_msg),
+            RuleModifyCmd::Delete => RouteNetlinkMessage::DelRule(nl_msg),
+        }),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+    if cmd == RuleModifyCmd::Add {
+        request.header.flags |= NLM_F_CREATE | NLM_F_EXCL;
+    }
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e).into());
+        }
+    }
+    Ok(())
+}
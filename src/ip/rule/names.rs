@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::CliError;
+
+use crate::link::parse_num;
+
+pub(super) const FIB_RULE_INVERT: u32 = 0x2;
+pub(super) const FIB_RULE_UNRESOLVED: u32 = 0x4;
+pub(super) const FIB_RULE_IIF_DETACHED: u32 = 0x8;
+pub(super) const FIB_RULE_OIF_DETACHED: u32 = 0x10;
+
+pub(super) const FR_ACT_UNSPEC: u8 = 0;
+pub(super) const FR_ACT_TO_TBL: u8 = 1;
+pub(super) const FR_ACT_GOTO: u8 = 2;
+pub(super) const FR_ACT_NOP: u8 = 3;
+// iproute2 still supports the deprecated `nat` action
+pub(super) const RTN_NAT: u8 = 10;
+
+const PROTOCOLS_PATH: &str = "/etc/protocols";
+
+// The number and names (official name followed by aliases) of each entry
+// in `/etc/protocols`
+fn protocol_entries() -> Vec<(u8, Vec<String>)> {
+    let Ok(content) = std::fs::read_to_string(PROTOCOLS_PATH) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let number = fields.next()?.parse::<u8>().ok()?;
+            Some((
+                number,
+                std::iter::once(name)
+                    .chain(fields)
+                    .map(String::from)
+                    .collect(),
+            ))
+        })
+        .collect()
+}
+
+// Equal to iproute2 `inet_proto_n2a()` looking up `/etc/protocols` via
+// `getprotobynumber()`
+pub(super) fn ipproto_to_name(ipproto: u8, numeric: bool) -> String {
+    let name = if numeric {
+        None
+    } else {
+        ipproto_lookup(ipproto)
+    };
+    name.unwrap_or_else(|| format!("ipproto-{ipproto}"))
+}
+
+// Look up `/etc/protocols` via `getprotobynumber()`
+pub(crate) fn ipproto_lookup(ipproto: u8) -> Option<String> {
+    protocol_entries()
+        .into_iter()
+        .find(|(number, _)| *number == ipproto)
+        .and_then(|(_, names)| names.into_iter().next())
+}
+
+// Look up `/etc/protocols` via `getprotobyname()`
+pub(crate) fn ipproto_from_name(value: &str) -> Option<u8> {
+    protocol_entries()
+        .into_iter()
+        .find(|(_, names)| names.iter().any(|name| name == value))
+        .map(|(number, _)| number)
+}
+
+// Equal to iproute2 `inet_proto_a2n()`
+pub(super) fn parse_ipproto(value: &str) -> Result<u8, CliError> {
+    match ipproto_from_name(value) {
+        Some(number) => Ok(number),
+        None => parse_num(value, "Invalid \"ipproto\" value"),
+    }
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::Write;
+
+use futures_util::stream::StreamExt;
+use iproute_rs::CliError;
+use rtnetlink::{
+    packet_core::{
+        NLM_F_ACK, NLM_F_CREATE, NLM_F_REQUEST, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        rule::{RuleAttribute, RuleMessage},
+    },
+};
+
+use super::{filter::RuleShowFilter, show::dump_rules};
+use crate::route::{read_nl_dump, write_nl_dump};
+
+// Equal to iproute2 `rule_dump_magic` which is stored in host order
+const RULE_DUMP_MAGIC: u32 = 0x71706986;
+const RTPROT_KERNEL: u8 = 2;
+
+/// Like iproute2 `flush_rule()`, the rule without priority, i.e. the
+/// `local` rule of priority 0, is never flushed. The `main` and `default`
+/// rules created by kernel are kept unless the `protocol` filter is
+/// specified. The failure of deleting a rule stops the flush unless `force`
+/// is true.
+pub(crate) async fn handle_flush(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    force: bool,
+) -> Result<(), CliError> {
+    let filter = RuleShowFilter::parse(opts, family)?;
+
+    for rule in dump_rules(handle, family)
+        .await?
+        .into_iter()
+        .filter(|rule| filter.matches(rule))
+    {
+        let mut has_priority = false;
+        let mut protocol = 0;
+        for nla in rule.attributes.iter() {
+            match nla {
+                RuleAttribute::Priority(_) => has_priority = true,
+                RuleAttribute::Protocol(v) => protocol = u8::from(*v),
+                _ => (),
+            }
+        }
+        if !has_priority
+            || (protocol == RTPROT_KERNEL && filter.protocol.is_none())
+        {
+            continue;
+        }
+        if let Err(e) =
+            send_rule_request(handle, RouteNetlinkMessage::DelRule(rule), 0)
+                .await
+        {
+            if !force {
+                return Err(e.into());
+            }
+            writeln!(std::io::stderr(), "{}", CliError::from(e)).ok();
+        }
+    }
+    Ok(())
+}
+
+/// Like iproute2, `ip rule save` dumps the rules as raw netlink messages
+/// prefixed by the magic number.
+pub(crate) async fn handle_save(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let filter = RuleShowFilter::parse(opts, family)?;
+    let rules = dump_rules(handle, family).await?;
+    write_nl_dump(
+        RULE_DUMP_MAGIC,
+        rules
+            .into_iter()
+            .filter(|rule| filter.matches(rule))
+            .map(RouteNetlinkMessage::NewRule),
+    )
+}
+
+/// Like iproute2 `restore_handler()`, the existing rules are ignored.
+pub(crate) async fn handle_restore(
+    handle: &rtnetlink::Handle,
+) -> Result<(), CliError> {
+    let rules: Vec<RuleMessage> = read_nl_dump(RULE_DUMP_MAGIC, "rule")?
+        .into_iter()
+        .filter_map(|nl_msg| match nl_msg {
+            RouteNetlinkMessage::NewRule(rule) => Some(rule),
+            _ => None,
+        })
+        .collect();
+
+    for rule in rules {
+        match send_rule_request(
+            handle,
+            RouteNetlinkMessage::NewRule(rule),
+            NLM_F_CREATE,
+        )
+        .await
+        {
+            Err(rtnetlink::Error::NetlinkError(e))
+                if e.raw_code() == -(nix::errno::Errno::EEXIST as i32) => {}
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+async fn send_rule_request(
+    handle: &rtnetlink::Handle,
+    nl_msg: RouteNetlinkMessage,
+    flags: u16,
+) -> Result<(), rtnetlink::Error> {
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(nl_msg),
+    );
+    request.header.flags = NLM_F_REQUEST | NLM_F_ACK | flags;
+
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        if let NetlinkPayload::Error(e) = msg.payload
+            && e.code.is_some()
+        {
+            return Err(rtnetlink::Error::NetlinkError(e));
+        }
+    }
+    Ok(())
+}
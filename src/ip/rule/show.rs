@@ -0,0 +1,509 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, link::resolve_ip_link_group_name,
+    resolve_hostnames, write_with_color,
+};
+use rtnetlink::{
+    IpVersion,
+    packet_route::{
+        AddressFamily,
+        rule::{RuleAttribute, RuleMessage},
+    },
+};
+use serde::Serialize;
+
+use super::{
+    filter::RuleShowFilter,
+    names::{
+        FIB_RULE_IIF_DETACHED, FIB_RULE_INVERT, FIB_RULE_OIF_DETACHED,
+        FIB_RULE_UNRESOLVED, FR_ACT_GOTO, FR_ACT_NOP, FR_ACT_TO_TBL, RTN_NAT,
+        ipproto_to_name,
+    },
+};
+use crate::{
+    CliError,
+    route::{protocol_to_name, realm_to_name, table_to_name, type_to_name},
+};
+
+const RTPROT_KERNEL: u8 = 2;
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliRuleInfo {
+    priority: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not: Option<()>,
+    src: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    srclen: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dst: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dstlen: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fwmark: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fwmask: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iif: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iif_detached: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oif: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oif_detached: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    l3mdev: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid_start: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid_end: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipproto: Option<String>,
+    #[serde(flatten)]
+    sport: Option<CliRulePortRange>,
+    #[serde(flatten)]
+    dport: Option<CliRulePortRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tun_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suppress_prefixlen: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suppress_ifgroup: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flow_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flow_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    masquerade: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    goto: Option<CliRuleGoto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unresolved: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nop: Option<()>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+    #[serde(skip)]
+    family: String,
+}
+
+impl CliRuleInfo {
+    /// The `src` and `dst` host addresses, i.e. without prefix length
+    fn host_addrs_mut(&mut self) -> Vec<&mut String> {
+        let mut ret = Vec::new();
+        if self.srclen.is_none() {
+            ret.push(&mut self.src);
+        }
+        if self.dstlen.is_none() {
+            ret.extend(self.dst.as_mut());
+        }
+        ret
+    }
+}
+
+/// Like iproute2, the `goto` without target priority is shown as `none`.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CliRuleGoto {
+    Priority(u32),
+    None(&'static str),
+}
+
+impl std::fmt::Display for CliRuleGoto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Priority(v) => write!(f, "{v}"),
+            Self::None(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// Like iproute2, the port range of single port is shown as the port only,
+/// which is also the JSON key, e.g. `sport` or `sport_start` and
+/// `sport_end`.
+struct CliRulePortRange {
+    name: &'static str,
+    start: u16,
+    end: u16,
+}
+
+impl Serialize for CliRulePortRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        if self.start == self.end {
+            map.serialize_entry(self.name, &self.start)?;
+        } else {
+            map.serialize_entry(&format!("{}_start", self.name), &self.start)?;
+            map.serialize_entry(&format!("{}_end", self.name), &self.end)?;
+        }
+        map.end()
+    }
+}
+
+impl std::fmt::Display for CliRulePortRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, " {} {}", self.name, self.start)?;
+        if self.start != self.end {
+            write!(f, "-{}", self.end)?;
+        }
+        Ok(())
+    }
+}
+
+// Equal to iproute2 `print_rule()`
+impl std::fmt::Display for CliRuleInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let addr_color = CliColor::address_color(&self.family);
+        write!(f, "{}:\t", self.priority)?;
+        if self.not.is_some() {
+            write!(f, "not ")?;
+        }
+        write!(f, "from ")?;
+        if self.src == "all" {
+            write!(f, "all")?;
+        } else {
+            write_with_color!(f, addr_color, "{}", self.src)?;
+        }
+        if let Some(srclen) = self.srclen {
+            write!(f, "/{srclen}")?;
+        }
+        if let Some(dst) = &self.dst {
+            write!(f, " to ")?;
+            write_with_color!(f, addr_color, "{dst}")?;
+        }
+        if let Some(dstlen) = self.dstlen {
+            write!(f, "/{dstlen}")?;
+        }
+        if let Some(tos) = &self.tos {
+            write!(f, " tos {tos}")?;
+        }
+        if let Some(fwmark) = &self.fwmark {
+            write!(f, " fwmark {fwmark}")?;
+        }
+        if let Some(fwmask) = &self.fwmask {
+            write!(f, "/{fwmask}")?;
+        }
+        if let Some(iif) = &self.iif {
+            write!(f, " iif ")?;
+            write_with_color!(f, CliColor::IfaceName, "{iif}")?;
+        }
+        if self.iif_detached.is_some() {
+            write!(f, " [detached]")?;
+        }
+        if let Some(oif) = &self.oif {
+            write!(f, " oif ")?;
+            write_with_color!(f, CliColor::IfaceName, "{oif}")?;
+        }
+        if self.oif_detached.is_some() {
+            write!(f, " [detached]")?;
+        }
+        if self.l3mdev.is_some() {
+            write!(f, " lookup [l3mdev-table]")?;
+        }
+        if let (Some(start), Some(end)) = (self.uid_start, self.uid_end) {
+            write!(f, " uidrange {start}-{end}")?;
+        }
+        if let Some(ipproto) = &self.ipproto {
+            write!(f, " ipproto {ipproto}")?;
+        }
+        if let Some(sport) = &self.sport {
+            write!(f, "{sport}")?;
+        }
+        if let Some(dport) = &self.dport {
+            write!(f, "{dport}")?;
+        }
+        if let Some(tun_id) = self.tun_id {
+            write!(f, " tun_id {tun_id}")?;
+        }
+        if let Some(table) = &self.table {
+            write!(f, " lookup {table}")?;
+        }
+        if let Some(prefix_len) = self.suppress_prefixlen {
+            write!(f, " suppress_prefixlength {prefix_len}")?;
+        }
+        if let Some(group) = &self.suppress_ifgroup {
+            write!(f, " suppress_ifgroup {group}")?;
+        }
+        if let Some(to) = &self.flow_to {
+            match &self.flow_from {
+                Some(from) => write!(f, " realms {from}/{to}")?,
+                None => write!(f, " realms {to}")?,
+            }
+        }
+        if self.masquerade.is_some() {
+            write!(f, " masquerade")?;
+        }
+        if let Some(goto) = &self.goto {
+            write!(f, " goto {goto}")?;
+        }
+        if self.unresolved.is_some() {
+            write!(f, " [unresolved]")?;
+        }
+        if self.nop.is_some() {
+            write!(f, " nop")?;
+        }
+        if let Some(action) = &self.action {
+            write!(f, " {action}")?;
+        }
+        if let Some(protocol) = &self.protocol {
+            write!(f, " proto {protocol}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliRuleInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliRuleInfo {}
+
+// Like iproute2 `print_0xhex()` using `%#llx`, zero has no `0x` prefix
+fn to_0xhex(value: u32) -> String {
+    if value == 0 {
+        "0".to_string()
+    } else {
+        format!("{value:#x}")
+    }
+}
+
+pub(crate) fn parse_nl_msg_to_rule(
+    nl_msg: RuleMessage,
+    include_details: bool,
+    numeric: bool,
+) -> CliRuleInfo {
+    let header = &nl_msg.header;
+    let family = header.family;
+    let host_len = if family == AddressFamily::Inet6 {
+        128
+    } else {
+        32
+    };
+    let flags = header.flags.bits();
+    let action = u8::from(header.action);
+
+    let mut ret = CliRuleInfo {
+        family: family.to_string(),
+        src: "all".to_string(),
+        ..Default::default()
+    };
+    if flags & FIB_RULE_INVERT != 0 {
+        ret.not = Some(());
+    }
+    if header.tos != 0 {
+        ret.tos = Some(format!("0x{:02x}", header.tos));
+    }
+
+    let mut src: Option<IpAddr> = None;
+    let mut dst: Option<IpAddr> = None;
+    let mut fwmark = None;
+    let mut fwmask = None;
+    let mut table = u32::from(header.table);
+    let mut goto = None;
+    let mut suppress_prefixlen = None;
+    let mut suppress_ifgroup = None;
+    for nla in nl_msg.attributes.iter() {
+        match nla {
+            RuleAttribute::Priority(v) => ret.priority = *v,
+            RuleAttribute::Source(v) => src = Some(*v),
+            RuleAttribute::Destination(v) => dst = Some(*v),
+            RuleAttribute::FwMark(v) => fwmark = Some(*v),
+            RuleAttribute::FwMask(v) => fwmask = Some(*v),
+            RuleAttribute::Iifname(v) => {
+                ret.iif = Some(v.to_string());
+                if flags & FIB_RULE_IIF_DETACHED != 0 {
+                    ret.iif_detached = Some(());
+                }
+            }
+            RuleAttribute::Oifname(v) => {
+                ret.oif = Some(v.to_string());
+                if flags & FIB_RULE_OIF_DETACHED != 0 {
+                    ret.oif_detached = Some(());
+                }
+            }
+            RuleAttribute::L3MDev(true) => ret.l3mdev = Some(()),
+            RuleAttribute::UidRange(range) => {
+                ret.uid_start = Some(range.start);
+                ret.uid_end = Some(range.end);
+            }
+            RuleAttribute::IpProtocol(v) => {
+                ret.ipproto =
+                    Some(ipproto_to_name(i32::from(*v) as u8, numeric));
+            }
+            RuleAttribute::SourcePortRange(range) => {
+                ret.sport = Some(CliRulePortRange {
+                    name: "sport",
+                    start: range.start,
+                    end: range.end,
+                });
+            }
+            RuleAttribute::DestinationPortRange(range) => {
+                ret.dport = Some(CliRulePortRange {
+                    name: "dport",
+                    start: range.start,
+                    end: range.end,
+                });
+            }
+            RuleAttribute::TunId(v) => ret.tun_id = Some(*v),
+            RuleAttribute::Table(v) => table = *v,
+            RuleAttribute::SuppressPrefixLen(v) => {
+                suppress_prefixlen = Some(*v)
+            }
+            RuleAttribute::SuppressIfGroup(v) => suppress_ifgroup = Some(*v),
+            RuleAttribute::Realm(realm) => {
+                if realm.source != 0 {
+                    ret.flow_from = Some(realm_to_name(realm.source, numeric));
+                }
+                ret.flow_to = Some(realm_to_name(realm.destination, numeric));
+            }
+            RuleAttribute::Goto(v) => goto = Some(*v),
+            RuleAttribute::Protocol(v) => {
+                let protocol = u8::from(*v);
+                if (protocol != 0 && protocol != RTPROT_KERNEL)
+                    || include_details
+                {
+                    ret.protocol = Some(protocol_to_name(protocol, numeric));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(src) = src {
+        ret.src = src.to_string();
+        if header.src_len != host_len {
+            ret.srclen = Some(header.src_len);
+        }
+    } else if header.src_len != 0 {
+        ret.src = "0".to_string();
+        ret.srclen = Some(header.src_len);
+    }
+    if let Some(dst) = dst {
+        ret.dst = Some(dst.to_string());
+        if header.dst_len != host_len {
+            ret.dstlen = Some(header.dst_len);
+        }
+    } else if header.dst_len != 0 {
+        ret.dst = Some("0".to_string());
+        ret.dstlen = Some(header.dst_len);
+    }
+
+    if fwmark.is_some() || fwmask.is_some() {
+        ret.fwmark = Some(to_0xhex(fwmark.unwrap_or_default()));
+        ret.fwmask = fwmask.filter(|m| *m != u32::MAX).map(to_0xhex);
+    }
+
+    if table != 0 {
+        ret.table = Some(table_to_name(table, numeric));
+        // The `u32::MAX` means not set
+        ret.suppress_prefixlen = suppress_prefixlen
+            .filter(|v| *v != u32::MAX)
+            .map(|v| v as i32);
+        ret.suppress_ifgroup =
+            suppress_ifgroup.filter(|v| *v != u32::MAX).map(|v| {
+                if numeric {
+                    v.to_string()
+                } else {
+                    resolve_ip_link_group_name(v)
+                }
+            });
+    }
+
+    match action {
+        // Kernel never reports the gateway of `nat` action
+        RTN_NAT => ret.masquerade = Some(()),
+        FR_ACT_GOTO => {
+            ret.goto = Some(match goto {
+                Some(v) => CliRuleGoto::Priority(v),
+                None => CliRuleGoto::None("none"),
+            });
+            if flags & FIB_RULE_UNRESOLVED != 0 {
+                ret.unresolved = Some(());
+            }
+        }
+        FR_ACT_NOP => ret.nop = Some(()),
+        FR_ACT_TO_TBL => (),
+        _ => ret.action = Some(type_to_name(action, numeric)),
+    }
+    ret
+}
+
+/// Dump the rules of specified family, IPv4 is the default like iproute2
+pub(super) async fn dump_rules(
+    handle: &rtnetlink::Handle,
+    family: AddressFamily,
+) -> Result<Vec<RuleMessage>, CliError> {
+    let ip_version = if family == AddressFamily::Inet6 {
+        IpVersion::V6
+    } else {
+        IpVersion::V4
+    };
+
+    let mut ret = Vec::new();
+    let mut rules = handle.rule().get(ip_version).execute();
+    while let Some(nl_msg) = rules.try_next().await? {
+        ret.push(nl_msg);
+    }
+    Ok(ret)
+}
+
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    family: AddressFamily,
+    include_details: bool,
+    numeric: bool,
+    resolve: bool,
+) -> Result<Vec<CliRuleInfo>, CliError> {
+    let filter = RuleShowFilter::parse(opts, family)?;
+
+    let mut ret: Vec<CliRuleInfo> = dump_rules(handle, family)
+        .await?
+        .into_iter()
+        .filter(|nl_msg| filter.matches(nl_msg))
+        .map(|nl_msg| parse_nl_msg_to_rule(nl_msg, include_details, numeric))
+        .collect();
+    if resolve {
+        resolve_rule_hostnames(&mut ret).await;
+    }
+    Ok(ret)
+}
+
+// Like iproute2 `format_host_rta()`, replace the IP addresses with
+// hostnames if resolved. The prefixes with length are left untouched.
+async fn resolve_rule_hostnames(rules: &mut [CliRuleInfo]) {
+    let ips: Vec<IpAddr> = rules
+        .iter_mut()
+        .flat_map(CliRuleInfo::host_addrs_mut)
+        .filter_map(|addr| addr.parse().ok())
+        .collect();
+
+    let hostnames = resolve_hostnames(&ips).await;
+
+    for addr in rules.iter_mut().flat_map(CliRuleInfo::host_addrs_mut) {
+        if let Some(name) = addr
+            .parse::<IpAddr>()
+            .ok()
+            .and_then(|ip| hostnames.get(&ip))
+        {
+            *addr = name.clone();
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod save;
+#[cfg(test)]
+mod show;
+
+// Delete the IPv4 and IPv6 rules of priority in the range, the exit status
+// is ignored as the rule might not exist.
+fn delete_rules(prefs: std::ops::RangeInclusive<u32>) {
+    for family in ["-4", "-6"] {
+        for pref in prefs.clone() {
+            std::process::Command::new("ip")
+                .args([family, "rule", "del", "pref", &pref.to_string()])
+                .output()
+                .ok();
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: MIT
+
+use super::delete_rules;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+// Add the rule by iproute2 and by us, the outputs of `ip rule show` should
+// be identical.
+fn assert_rule_add(family: &str, args: &[&str]) {
+    let _lock = lock_net_test();
+    let show_args = ["ip", family, "-d", "-j", "rule", "show"];
+
+    exec_cmd(&[&["ip", family, "rule", "add"], args].concat());
+    let expected_output = exec_cmd(&show_args);
+    delete_rules(5100..=5199);
+
+    let result = std::panic::catch_unwind(|| {
+        let our_output =
+            ip_rs_exec_cmd(&[&[family, "rule", "add"], args].concat());
+        assert!(our_output.is_empty());
+        let our_output = exec_cmd(&show_args);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+    delete_rules(5100..=5199);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_rule_add() {
+    for args in [
+        &["pref", "5100", "from", "192.0.2.0/24", "table", "100"][..],
+        &[
+            "priority",
+            "5101",
+            "not",
+            "to",
+            "198.51.100.0/25",
+            "tos",
+            "0x10",
+            "fwmark",
+            "0x5/0xff",
+            "lookup",
+            "main",
+        ][..],
+        &[
+            "pref",
+            "5102",
+            "iif",
+            "rtest-rabsent2",
+            "oif",
+            "rtest-rabsent3",
+            "uidrange",
+            "100-200",
+            "table",
+            "1000",
+        ][..],
+        &[
+            "pref",
+            "5103",
+            "ipproto",
+            "tcp",
+            "sport",
+            "80",
+            "dport",
+            "1000-2000",
+            "proto",
+            "bird",
+        ][..],
+        &[
+            "pref",
+            "5104",
+            "fwmark",
+            "1",
+            "lookup",
+            "main",
+            "suppress_prefixlength",
+            "0",
+            "suppress_ifgroup",
+            "default",
+        ][..],
+        &["pref", "5105", "fwmark", "2", "realms", "3/5"][..],
+        &["pref", "5106", "fwmark", "3", "goto", "5199"][..],
+        &["pref", "5107", "fwmark", "4", "nop"][..],
+        &["pref", "5108", "fwmark", "5", "type", "blackhole"][..],
+        &["pref", "5109", "fwmark", "6", "prohibit"][..],
+        &["pref", "5110", "l3mdev"][..],
+        &["pref", "5111", "from", "all", "tun_id", "10", "table", "7"][..],
+    ] {
+        assert_rule_add("-4", args);
+    }
+
+    assert_rule_add(
+        "-6",
+        &[
+            "pref",
+            "5100",
+            "from",
+            "2001:db8::/32",
+            "sport",
+            "22",
+            "table",
+            "7",
+        ],
+    );
+}
+
+#[test]
+fn test_rule_del() {
+    let _lock = lock_net_test();
+
+    exec_cmd(&["ip", "rule", "add", "pref", "5150", "fwmark", "7"]);
+    exec_cmd(&["ip", "rule", "add", "pref", "5151", "table", "100"]);
+    exec_cmd(&["ip", "-6", "rule", "add", "pref", "5152", "table", "100"]);
+
+    let result = std::panic::catch_unwind(|| {
+        // The selector should match the existing rule
+        let output = ip_rs_exec_cmd_output(&[
+            "rule", "del", "pref", "5150", "fwmark", "8",
+        ]);
+        assert!(!output.status.success());
+
+        for args in [
+            &["rule", "del", "pref", "5150", "fwmark", "7"][..],
+            &["rule", "delete", "table", "100"][..],
+            &["-6", "rule", "del", "pref", "5152"][..],
+        ] {
+            assert!(ip_rs_exec_cmd(args).is_empty());
+        }
+        for family in ["-4", "-6"] {
+            let output = exec_cmd(&["ip", family, "rule", "show"]);
+            assert!(!output.contains("515"));
+        }
+    });
+
+    delete_rules(5150..=5152);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_rule_add_invalid_args() {
+    for (args, error_msg) in [
+        (&[][..], "\"ip rule add\" requires arguments."),
+        (
+            &["pref", "foo"][..],
+            "argument \"foo\" is wrong: preference value is invalid",
+        ),
+        (
+            &["fwmark", "1/foo"][..],
+            "argument \"foo\" is wrong: fwmask value is invalid",
+        ),
+        (
+            &["uidrange", "100"][..],
+            "argument \"100\" is wrong: invalid UID range",
+        ),
+        (
+            &["sport", "foo"][..],
+            "argument \"foo\" is wrong: invalid port range",
+        ),
+        (
+            &["ipproto", "foo"][..],
+            "argument \"foo\" is wrong: Invalid \"ipproto\" value",
+        ),
+        (
+            &["realms", "1/256"][..],
+            "argument \"1/256\" is wrong: invalid realms",
+        ),
+        (
+            &["suppress_prefixlength", "-1"][..],
+            "argument \"-1\" is wrong: suppress_prefixlength value is invalid",
+        ),
+        (
+            &["iif", "rtest-too-long-ifname"][..],
+            "argument \"rtest-too-long-ifname\" is wrong: \"iif\"/\"dev\" not \
+             a valid ifname",
+        ),
+        (
+            &["l3mdev", "table", "100"][..],
+            "table can not be specified for l3mdev rules",
+        ),
+        (
+            &["foo"][..],
+            "argument \"foo\" is wrong: Failed to parse rule type",
+        ),
+        (&["pref"][..], "Command line is not complete"),
+    ] {
+        let output = ip_rs_exec_cmd_output(&[&["rule", "add"], args].concat());
+
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error_msg));
+    }
+}
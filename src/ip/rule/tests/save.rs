@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+
+use super::delete_rules;
+use crate::tests::{
+    exec_cmd, exec_cmd_with_stdin, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    ip_rs_exec_cmd_with_stdin, lock_net_test,
+};
+
+const SAVE_TEST_TABLE: &str = "1301";
+
+const RULES: [&[&str]; 4] = [
+    &["pref", "5200", "from", "192.0.2.0/24", "proto", "static"],
+    &["pref", "5201", "to", "198.51.100.0/24", "fwmark", "0x3/0xf"],
+    &["pref", "5202", "iif", "rtest-rabsent4", "proto", "static"],
+    &["pref", "5203", "not", "uidrange", "10-20", "ipproto", "udp"],
+];
+
+fn add_rules() {
+    for rule in RULES {
+        exec_cmd(
+            &[&["ip", "rule", "add"], rule, &["table", SAVE_TEST_TABLE]]
+                .concat(),
+        );
+    }
+}
+
+fn with_saved_rules<T>(test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    delete_rules(5200..=5203);
+    add_rules();
+
+    let result = std::panic::catch_unwind(test);
+
+    delete_rules(5200..=5203);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_rule_show_filter() {
+    with_saved_rules(|| {
+        for filter in [
+            &["table", SAVE_TEST_TABLE][..],
+            &["pref", "5201"][..],
+            &["from", "192.0.2.0/24"][..],
+            &["to", "198.51.100.0/25"][..],
+            &["fwmark", "3"][..],
+            &["iif", "rtest-rabsent4"][..],
+            &["not"][..],
+            &["uidrange", "10-20"][..],
+            &["ipproto", "udp"][..],
+            &["proto", "static"][..],
+            &["proto", "kernel"][..],
+            &["lookup", "main"][..],
+        ] {
+            for args in [&[][..], &["-j"][..]] {
+                let args = [args, &["rule", "show"], filter].concat();
+                let expected_output = exec_cmd(&[&["ip"], &args[..]].concat());
+                let our_output = ip_rs_exec_cmd(&args);
+
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_rule_flush() {
+    with_saved_rules(|| {
+        let show_args = ["ip", "rule", "show"];
+        let flush_args = ["rule", "flush", "table", SAVE_TEST_TABLE];
+
+        exec_cmd(&[&["ip"], &flush_args[..], &["proto", "static"]].concat());
+        let expected_output = exec_cmd(&show_args);
+        delete_rules(5200..=5203);
+        add_rules();
+
+        let output =
+            ip_rs_exec_cmd(&[&flush_args[..], &["proto", "static"]].concat());
+        assert!(output.is_empty());
+        pretty_assertions::assert_eq!(expected_output, exec_cmd(&show_args));
+
+        assert!(ip_rs_exec_cmd(&flush_args).is_empty());
+        let output = exec_cmd(&show_args);
+        assert!(!output.contains("520"));
+
+        // The rules created by kernel are kept
+        for pref in ["0", "32766", "32767"] {
+            assert!(
+                ip_rs_exec_cmd(&["rule", "flush", "pref", pref]).is_empty()
+            );
+        }
+        pretty_assertions::assert_eq!(output, exec_cmd(&show_args));
+    });
+}
+
+#[test]
+fn test_rule_save_restore() {
+    with_saved_rules(|| {
+        let show_args = ["ip", "-d", "rule", "show", "table", SAVE_TEST_TABLE];
+        let expected_output = exec_cmd(&show_args);
+
+        let output = exec_cmd_with_stdin(
+            &["ip", "rule", "save", "table", SAVE_TEST_TABLE],
+            &[],
+        );
+        assert!(output.status.success());
+        let iproute2_dump = output.stdout;
+        let output =
+            ip_rs_exec_cmd_output(&["rule", "save", "table", SAVE_TEST_TABLE]);
+        assert!(output.status.success());
+        let our_dump = output.stdout;
+
+        for dump in [&iproute2_dump, &our_dump] {
+            delete_rules(5200..=5203);
+            let output = ip_rs_exec_cmd_with_stdin(&["rule", "restore"], dump);
+            assert!(output.status.success());
+            pretty_assertions::assert_eq!(
+                expected_output,
+                exec_cmd(&show_args)
+            );
+
+            // Restoring existing rules is not a failure
+            let output = ip_rs_exec_cmd_with_stdin(&["rule", "restore"], dump);
+            assert!(output.status.success());
+        }
+
+        // Our dump restored by iproute2
+        delete_rules(5200..=5203);
+        let output = exec_cmd_with_stdin(&["ip", "rule", "restore"], &our_dump);
+        assert!(output.status.success());
+        pretty_assertions::assert_eq!(expected_output, exec_cmd(&show_args));
+    });
+}
+
+#[test]
+fn test_rule_restore_magic_mismatch() {
+    let output = ip_rs_exec_cmd_with_stdin(&["rule", "restore"], b"foo");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Magic mismatch (0 elems, 6f6f66 magic)")
+    );
+}
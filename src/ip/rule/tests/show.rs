@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+
+use super::delete_rules;
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, lock_net_test};
+
+const RULES: [&[&str]; 12] = [
+    &[
+        "pref",
+        "5000",
+        "from",
+        "192.0.2.0/24",
+        "to",
+        "198.51.100.1",
+        "tos",
+        "0x10",
+        "fwmark",
+        "0x5/0xff",
+        "iif",
+        "rtest-rabsent0",
+        "oif",
+        "rtest-rabsent1",
+        "table",
+        "100",
+    ],
+    &[
+        "pref",
+        "5001",
+        "not",
+        "from",
+        "all",
+        "uidrange",
+        "100-200",
+        "ipproto",
+        "tcp",
+        "sport",
+        "80",
+        "dport",
+        "1000-2000",
+        "lookup",
+        "main",
+        "suppress_prefixlength",
+        "0",
+        "suppress_ifgroup",
+        "5",
+    ],
+    &["pref", "5002", "goto", "5003"],
+    &["pref", "5003", "blackhole"],
+    &["pref", "5004", "fwmark", "7", "prohibit"],
+    &["pref", "5005", "fwmark", "8", "unreachable"],
+    &["pref", "5006", "fwmark", "9", "nop", "proto", "bird"],
+    &["pref", "5007", "fwmark", "0x10", "goto", "5500"],
+    &[
+        "pref", "5008", "fwmark", "0x11", "realms", "3/5", "table", "100",
+    ],
+    &["pref", "5009", "l3mdev"],
+    &["pref", "5010", "ipproto", "200", "table", "5"],
+    &[
+        "pref",
+        "5011",
+        "fwmark",
+        "1",
+        "lookup",
+        "main",
+        "suppress_ifgroup",
+        "0",
+        "suppress_prefixlength",
+        "8",
+    ],
+];
+
+const RULES6: [&[&str]; 2] = [
+    &[
+        "pref",
+        "5000",
+        "from",
+        "2001:db8::/32",
+        "to",
+        "2001:db8:1::1",
+        "table",
+        "7",
+    ],
+    &[
+        "pref",
+        "5001",
+        "from",
+        "2001:db8::1/128",
+        "sport",
+        "22",
+        "table",
+        "7",
+    ],
+];
+
+#[test]
+fn test_rule_show() {
+    let _lock = lock_net_test();
+
+    for rule in RULES {
+        exec_cmd(&[&["ip", "rule", "add"], rule].concat());
+    }
+    for rule in RULES6 {
+        exec_cmd(&[&["ip", "-6", "rule", "add"], rule].concat());
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["rule"][..],
+            &["rule", "show"][..],
+            &["-d", "rule", "show"][..],
+            &["-N", "rule", "show"][..],
+            &["-r", "rule", "show"][..],
+            &["-j", "rule", "show"][..],
+            &["-d", "-j", "rule", "show"][..],
+            &["-6", "rule", "show"][..],
+            &["-6", "-j", "rule", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+    });
+
+    delete_rules(5000..=5011);
+    assert!(result.is_ok());
+}
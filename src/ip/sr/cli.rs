@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput};
+use serde::Serialize;
+
+use super::{
+    hmac::{CliSrHmac, handle_hmac_set, handle_hmac_show},
+    tunsrc::{CliSrTunsrc, handle_tunsrc_set, handle_tunsrc_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+const SR_USAGE: &str = "\
+Usage: ip sr { COMMAND | help }
+\t  ip sr hmac show
+\t  ip sr hmac set KEYID ALGO
+\t  ip sr tunsrc show
+\t  ip sr tunsrc set ADDRESS
+where  ALGO := { sha1 | sha256 }";
+
+fn usage() -> CliError {
+    CliError::from(SR_USAGE)
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliSrOutput {
+    Hmac(Vec<CliSrHmac>),
+    Tunsrc(Vec<CliSrTunsrc>),
+}
+
+impl CanDisplay for CliSrOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::Hmac(entries) => entries.gen_string(),
+            Self::Tunsrc(entries) => entries.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Hmac(entries) => entries.gen_oneline_string(),
+            Self::Tunsrc(entries) => entries.gen_oneline_string(),
+        }
+    }
+}
+
+impl CanOutput for CliSrOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+fn gen_show_set_commands(cmd: clap::Command) -> clap::Command {
+    cmd.subcommand_required(false)
+        .subcommand(
+            clap::Command::new("show")
+                .alias("sho")
+                .alias("sh")
+                .alias("s")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(clap::Command::new("set").alias("se").arg(gen_opts_arg()))
+}
+
+fn incomplete_command() -> CliError {
+    CliError::from("Command line is not complete. Try option \"help\"")
+}
+
+pub(crate) struct SrCommand;
+
+impl SrCommand {
+    pub(crate) const CMD: &'static str = "sr";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("IPv6 segment routing configuration")
+            .subcommand_required(false)
+            .subcommand(gen_show_set_commands(
+                clap::Command::new("hmac").about("manage SRv6 HMAC keys"),
+            ))
+            .subcommand(gen_show_set_commands(
+                clap::Command::new("tunsrc")
+                    .about("manage SRv6 tunnel source address"),
+            ))
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliSrOutput>, CliError> {
+        if let Some(matches) = matches.subcommand_matches("hmac") {
+            if let Some(matches) = matches.subcommand_matches("set") {
+                handle_hmac_set(ctx, &get_opts(matches)).await?;
+                Ok(None)
+            } else if matches.subcommand_matches("show").is_some() {
+                handle_hmac_show(ctx).await.map(CliSrOutput::Hmac).map(Some)
+            } else {
+                Err(incomplete_command())
+            }
+        } else if let Some(matches) = matches.subcommand_matches("tunsrc") {
+            if let Some(matches) = matches.subcommand_matches("set") {
+                handle_tunsrc_set(ctx, &get_opts(matches)).await?;
+                Ok(None)
+            } else if matches.subcommand_matches("show").is_some() {
+                handle_tunsrc_show(ctx)
+                    .await
+                    .map(CliSrOutput::Tunsrc)
+                    .map(Some)
+            } else {
+                Err(incomplete_command())
+            }
+        } else {
+            Err(usage())
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::{BufRead, IsTerminal, Write};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use nix::sys::termios::{LocalFlags, SetArg, tcgetattr, tcsetattr};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::message::{
+    SEG6_HMAC_ALGO_SHA1, SEG6_HMAC_ALGO_SHA256, Seg6Attr, Seg6Cmd, Seg6Message,
+};
+use crate::{context::NetlinkContext, genl::genl_request, link::parse_num};
+
+// Like iproute2 `print_dumphmac()`, the secret is truncated to 63 bytes
+const SEG6_HMAC_SECRET_MAX_PRINT: usize = 63;
+
+fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+fn algo_to_name(algo: u8) -> &'static str {
+    match algo {
+        SEG6_HMAC_ALGO_SHA1 => "sha1",
+        SEG6_HMAC_ALGO_SHA256 => "sha256",
+        _ => "<unknown>",
+    }
+}
+
+/// Equal to iproute2 `print_dumphmac()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliSrHmac {
+    hmac: u32,
+    algo: String,
+    secret: String,
+}
+
+impl std::fmt::Display for CliSrHmac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "hmac {} algo {} secret \"{}\"",
+            self.hmac, self.algo, self.secret
+        )
+    }
+}
+
+impl CanDisplay for CliSrHmac {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliSrHmac {}
+
+impl From<Seg6Message> for CliSrHmac {
+    fn from(msg: Seg6Message) -> Self {
+        let mut ret = Self::default();
+        let mut secret = Vec::new();
+        let mut secret_len = 0;
+        for attr in msg.attributes {
+            match attr {
+                Seg6Attr::HmacKeyId(v) => ret.hmac = v,
+                Seg6Attr::AlgId(v) => ret.algo = algo_to_name(v).to_string(),
+                Seg6Attr::Secret(v) => secret = v,
+                Seg6Attr::SecretLen(v) => secret_len = usize::from(v),
+                _ => (),
+            }
+        }
+        secret.truncate(secret_len.min(SEG6_HMAC_SECRET_MAX_PRINT));
+        if let Some(pos) = secret.iter().position(|c| *c == 0) {
+            secret.truncate(pos);
+        }
+        ret.secret = String::from_utf8_lossy(&secret).to_string();
+        ret
+    }
+}
+
+pub(crate) async fn handle_hmac_show(
+    ctx: &NetlinkContext,
+) -> Result<Vec<CliSrHmac>, CliError> {
+    Ok(genl_request(
+        ctx,
+        Seg6Message::new(Seg6Cmd::DumpHmac, Vec::new()),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .map(CliSrHmac::from)
+    .collect())
+}
+
+// Like `getpass()` used by iproute2, the prompt is printed to stderr and
+// the secret typed on terminal is not echoed.
+fn read_secret() -> Result<String, CliError> {
+    let mut stderr = std::io::stderr();
+    write!(stderr, "Enter secret for HMAC key ID (blank to delete): ").ok();
+    stderr.flush().ok();
+
+    let stdin = std::io::stdin();
+    let saved = if stdin.is_terminal() {
+        tcgetattr(&stdin).ok()
+    } else {
+        None
+    };
+    if let Some(saved) = &saved {
+        let mut termios = saved.clone();
+        termios.local_flags.remove(LocalFlags::ECHO);
+        tcsetattr(&stdin, SetArg::TCSAFLUSH, &termios).ok();
+    }
+
+    let mut line = String::new();
+    let result = stdin.lock().read_line(&mut line);
+
+    if let Some(saved) = &saved {
+        tcsetattr(&stdin, SetArg::TCSAFLUSH, saved).ok();
+        writeln!(stderr).ok();
+    }
+    result?;
+
+    Ok(line.strip_suffix('\n').unwrap_or(&line).to_string())
+}
+
+/// Equal to iproute2 `seg6_do_cmd()` for `hmac set KEYID ALGO`, the blank
+/// secret deletes the HMAC key.
+pub(crate) async fn handle_hmac_set(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+
+    let value = next_opt(&mut opts)?;
+    let key_id = parse_num::<u32>(value, "")
+        .ok()
+        .filter(|v| *v != 0)
+        .ok_or_else(|| invarg(value, "hmac KEYID value is invalid"))?;
+
+    let value = next_opt(&mut opts)?;
+    let algo = match value {
+        "sha1" => SEG6_HMAC_ALGO_SHA1,
+        "sha256" => SEG6_HMAC_ALGO_SHA256,
+        _ => return Err(invarg(value, "hmac ALGO value is invalid")),
+    };
+
+    let secret = read_secret()?.into_bytes();
+    let secret_len = u8::try_from(secret.len())
+        .map_err(|_| CliError::from("HMAC secret is too long"))?;
+
+    let mut attributes = vec![
+        Seg6Attr::HmacKeyId(key_id),
+        Seg6Attr::SecretLen(secret_len),
+        Seg6Attr::AlgId(algo),
+    ];
+    if !secret.is_empty() {
+        attributes.push(Seg6Attr::Secret(secret));
+    }
+
+    genl_request(
+        ctx,
+        Seg6Message::new(Seg6Cmd::SetHmac, attributes),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv6Addr;
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_i32, parse_ipv6,
+    parse_u8, parse_u32,
+};
+
+// Equal to `linux/seg6_genl.h`
+const SEG6_GENL_NAME: &str = "SEG6";
+const SEG6_GENL_VERSION: u8 = 0x1;
+
+const SEG6_CMD_SETHMAC: u8 = 1;
+const SEG6_CMD_DUMPHMAC: u8 = 2;
+const SEG6_CMD_SET_TUNSRC: u8 = 3;
+const SEG6_CMD_GET_TUNSRC: u8 = 4;
+
+const SEG6_ATTR_DST: u16 = 1;
+const SEG6_ATTR_DSTLEN: u16 = 2;
+const SEG6_ATTR_HMACKEYID: u16 = 3;
+const SEG6_ATTR_SECRET: u16 = 4;
+const SEG6_ATTR_SECRETLEN: u16 = 5;
+const SEG6_ATTR_ALGID: u16 = 6;
+
+// Equal to `linux/seg6_hmac.h`
+pub(crate) const SEG6_HMAC_ALGO_SHA1: u8 = 1;
+pub(crate) const SEG6_HMAC_ALGO_SHA256: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Seg6Cmd {
+    SetHmac,
+    DumpHmac,
+    SetTunsrc,
+    GetTunsrc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Seg6Message {
+    pub(crate) cmd: Seg6Cmd,
+    pub(crate) attributes: Vec<Seg6Attr>,
+}
+
+impl Seg6Message {
+    pub(crate) fn new(cmd: Seg6Cmd, attributes: Vec<Seg6Attr>) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for Seg6Message {
+    fn family_name() -> &'static str {
+        SEG6_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            Seg6Cmd::SetHmac => SEG6_CMD_SETHMAC,
+            Seg6Cmd::DumpHmac => SEG6_CMD_DUMPHMAC,
+            Seg6Cmd::SetTunsrc => SEG6_CMD_SET_TUNSRC,
+            Seg6Cmd::GetTunsrc => SEG6_CMD_GET_TUNSRC,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        SEG6_GENL_VERSION
+    }
+}
+
+impl Emitable for Seg6Message {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for Seg6Message {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            SEG6_CMD_SETHMAC => Seg6Cmd::SetHmac,
+            SEG6_CMD_DUMPHMAC => Seg6Cmd::DumpHmac,
+            SEG6_CMD_SET_TUNSRC => Seg6Cmd::SetTunsrc,
+            SEG6_CMD_GET_TUNSRC => Seg6Cmd::GetTunsrc,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown seg6 command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid seg6 attribute")?;
+            attributes.push(Seg6Attr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Seg6Attr {
+    Dst(Ipv6Addr),
+    DstLen(i32),
+    HmacKeyId(u32),
+    Secret(Vec<u8>),
+    SecretLen(u8),
+    AlgId(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for Seg6Attr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Dst(_) => 16,
+            Self::DstLen(_) | Self::HmacKeyId(_) => 4,
+            Self::Secret(v) => v.len(),
+            Self::SecretLen(_) | Self::AlgId(_) => 1,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Dst(_) => SEG6_ATTR_DST,
+            Self::DstLen(_) => SEG6_ATTR_DSTLEN,
+            Self::HmacKeyId(_) => SEG6_ATTR_HMACKEYID,
+            Self::Secret(_) => SEG6_ATTR_SECRET,
+            Self::SecretLen(_) => SEG6_ATTR_SECRETLEN,
+            Self::AlgId(_) => SEG6_ATTR_ALGID,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Dst(v) => buffer.copy_from_slice(&v.octets()),
+            Self::DstLen(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::HmacKeyId(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Secret(v) => buffer.copy_from_slice(v),
+            Self::SecretLen(v) | Self::AlgId(v) => buffer[0] = *v,
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for Seg6Attr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            SEG6_ATTR_DST => Self::Dst(Ipv6Addr::from(parse_ipv6(payload)?)),
+            SEG6_ATTR_DSTLEN => Self::DstLen(parse_i32(payload)?),
+            SEG6_ATTR_HMACKEYID => Self::HmacKeyId(parse_u32(payload)?),
+            SEG6_ATTR_SECRET => Self::Secret(payload.to_vec()),
+            SEG6_ATTR_SECRETLEN => Self::SecretLen(parse_u8(payload)?),
+            SEG6_ATTR_ALGID => Self::AlgId(parse_u8(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod hmac;
+mod message;
+mod tunsrc;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::SrCommand;
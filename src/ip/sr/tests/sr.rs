@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, ip_rs_exec_cmd_with_stdin,
+    lock_net_test,
+};
+
+#[test]
+fn test_sr_tunsrc() {
+    let _lock = lock_net_test();
+    let old_tunsrc = exec_cmd(&["ip", "sr", "tunsrc", "show"]);
+
+    ip_rs_exec_cmd(&["sr", "tunsrc", "set", "2001:db8::1"]);
+    for args in [&[][..], &["-j"][..]] {
+        let expected_output =
+            exec_cmd(&[&["ip"], args, &["sr", "tunsrc", "show"]].concat());
+        let our_output =
+            ip_rs_exec_cmd(&[args, &["sr", "tunsrc", "show"]].concat());
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    }
+    let output = ip_rs_exec_cmd(&["sr", "tunsrc", "show"]);
+    pretty_assertions::assert_eq!(output, "tunsrc addr 2001:db8::1\n");
+
+    let old_tunsrc = old_tunsrc
+        .trim()
+        .strip_prefix("tunsrc addr ")
+        .unwrap_or("::")
+        .to_string();
+    ip_rs_exec_cmd(&["sr", "tunsrc", "set", &old_tunsrc]);
+}
+
+#[test]
+fn test_sr_hmac() {
+    let _lock = lock_net_test();
+
+    let output = ip_rs_exec_cmd_with_stdin(
+        &["sr", "hmac", "set", "4001", "sha256"],
+        b"secret123\n",
+    );
+    assert!(output.status.success());
+    pretty_assertions::assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Enter secret for HMAC key ID (blank to delete): "
+    );
+    ip_rs_exec_cmd_with_stdin(
+        &["sr", "hmac", "set", "4002", "sha1"],
+        b"another\n",
+    );
+
+    for args in [&[][..], &["-j"][..]] {
+        let expected_output =
+            exec_cmd(&[&["ip"], args, &["sr", "hmac", "show"]].concat());
+        let our_output =
+            ip_rs_exec_cmd(&[args, &["sr", "hmac", "show"]].concat());
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    }
+    let output = ip_rs_exec_cmd(&["sr", "hmac", "show"]);
+    assert!(output.contains("hmac 4001 algo sha256 secret \"secret123\"\n"));
+    assert!(output.contains("hmac 4002 algo sha1 secret \"another\"\n"));
+
+    // Blank secret deletes the HMAC key
+    for key_id in ["4001", "4002"] {
+        ip_rs_exec_cmd_with_stdin(
+            &["sr", "hmac", "set", key_id, "sha1"],
+            b"\n",
+        );
+    }
+    let output = ip_rs_exec_cmd(&["sr", "hmac", "show"]);
+    assert!(!output.contains("hmac 4001 "));
+    assert!(!output.contains("hmac 4002 "));
+}
+
+#[test]
+fn test_sr_invalid_args() {
+    for (args, error) in [
+        (&["sr"][..], "Usage: ip sr { COMMAND | help }"),
+        (
+            &["sr", "hmac"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["sr", "hmac", "set", "1"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["sr", "hmac", "set", "0", "sha1"][..],
+            "argument \"0\" is wrong: hmac KEYID value is invalid",
+        ),
+        (
+            &["sr", "hmac", "set", "1", "md5"][..],
+            "argument \"md5\" is wrong: hmac ALGO value is invalid",
+        ),
+        (
+            &["sr", "tunsrc", "set", "192.0.2.1"][..],
+            "inet6 address is expected rather than \"192.0.2.1\".",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+use serde::Serialize;
+
+use super::message::{Seg6Attr, Seg6Cmd, Seg6Message};
+use crate::{
+    context::NetlinkContext, genl::genl_request, prefix::parse_ip_addr,
+};
+
+/// Equal to iproute2 `print_tunsrc()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliSrTunsrc {
+    tunsrc: Ipv6Addr,
+}
+
+impl std::fmt::Display for CliSrTunsrc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tunsrc addr {}", self.tunsrc)
+    }
+}
+
+impl CanDisplay for CliSrTunsrc {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliSrTunsrc {}
+
+pub(crate) async fn handle_tunsrc_show(
+    ctx: &NetlinkContext,
+) -> Result<Vec<CliSrTunsrc>, CliError> {
+    Ok(genl_request(
+        ctx,
+        Seg6Message::new(Seg6Cmd::GetTunsrc, Vec::new()),
+        NLM_F_REQUEST,
+    )
+    .await?
+    .into_iter()
+    .filter_map(|msg| {
+        msg.attributes.into_iter().find_map(|attr| match attr {
+            Seg6Attr::Dst(tunsrc) => Some(CliSrTunsrc { tunsrc }),
+            _ => None,
+        })
+    })
+    .collect())
+}
+
+/// Equal to iproute2 `seg6_do_cmd()` for `tunsrc set ADDRESS`
+pub(crate) async fn handle_tunsrc_set(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut opts = opts.iter();
+    let IpAddr::V6(addr) =
+        parse_ip_addr(next_opt(&mut opts)?, AddressFamily::Inet6)?
+    else {
+        unreachable!("parse_ip_addr() only returns IPv6 for Inet6");
+    };
+    genl_request(
+        ctx,
+        Seg6Message::new(Seg6Cmd::SetTunsrc, vec![Seg6Attr::Dst(addr)]),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CliNumberFormat};
+use serde::Serialize;
+
+use super::{
+    set::handle_set,
+    show::{CliStatsInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+const STATS_USAGE: &str = "\
+Usage: ip stats help
+       ip stats show [ dev DEV ] [ group GROUP [ subgroup SUBGROUP ] ... ] ...
+       ip stats set dev DEV l3_stats { on | off }
+GROUP := { link | offload }
+offload SUBGROUP := { cpu_hit | hw_stats_info | l3_stats }";
+
+fn usage() -> CliError {
+    CliError::from(STATS_USAGE)
+}
+
+pub(crate) fn unknown_option(opt: &str) -> CliError {
+    CliError::from(format!("What is \"{opt}\"?\n{STATS_USAGE}").as_str())
+}
+
+pub(crate) fn dev_duplicate(value: &str) -> CliError {
+    CliError::from(
+        format!("either \"dev\" is duplicate, or \"{value}\" is a garbage.")
+            .as_str(),
+    )
+}
+
+/// Stats of all matched links, which are printed in a single line with
+/// `-oneline`.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(crate) struct CliStatsList(Vec<CliStatsInfo>);
+
+impl CanDisplay for CliStatsList {
+    fn gen_string(&self) -> String {
+        self.0.gen_string()
+    }
+
+    // Like iproute2 `print_nl()` in oneline mode, every entry is ended by
+    // `\` without newline.
+    fn gen_oneline_string(&self) -> String {
+        self.0
+            .iter()
+            .map(|entry| format!("{}\\", entry.gen_oneline_string()))
+            .collect()
+    }
+}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+pub(crate) struct StatsCommand;
+
+impl StatsCommand {
+    pub(crate) const CMD: &'static str = "stats";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("interface statistics")
+            .alias("stat")
+            .alias("sta")
+            .alias("st")
+            .subcommand_required(false)
+            // Like iproute2, the commands are not abbreviated
+            .subcommand(clap::Command::new("show").arg(gen_opts_arg()))
+            .subcommand(clap::Command::new("set").arg(gen_opts_arg()))
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliStatsList>, CliError> {
+        let stats_level = matches.get_count("STATS");
+        let number_format = CliNumberFormat::new(
+            matches.get_flag("HUMAN"),
+            matches.get_flag("IEC"),
+        );
+        match matches.subcommand() {
+            Some(("show", matches)) => {
+                handle_show(ctx, &get_opts(matches), stats_level, number_format)
+                    .await
+                    .map(|entries| Some(CliStatsList(entries)))
+            }
+            None => handle_show(ctx, &[], stats_level, number_format)
+                .await
+                .map(|entries| Some(CliStatsList(entries))),
+            Some(("set", matches)) => {
+                handle_set(ctx, &get_opts(matches)).await?;
+                Ok(None)
+            }
+            _ => Err(usage()),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::{
+    packet_core::{
+        DecodeError, DefaultNla, Emitable, ErrorContext, NetlinkDeserializable,
+        NetlinkHeader, NetlinkSerializable, Nla, NlaBuffer, NlasIterator,
+        Parseable, parse_u8, parse_u32, parse_u64,
+    },
+    packet_route::link::Stats64,
+    sys::protocols::NETLINK_ROUTE,
+};
+
+use crate::context::ProtoMessage;
+
+// Equal to `linux/rtnetlink.h`
+const RTM_NEWSTATS: u16 = 92;
+const RTM_GETSTATS: u16 = 94;
+const RTM_SETSTATS: u16 = 95;
+
+// Equal to `linux/if_link.h`
+pub(crate) const IFLA_STATS_LINK_64: u16 = 1;
+pub(crate) const IFLA_STATS_LINK_OFFLOAD_XSTATS: u16 = 4;
+
+const IFLA_STATS_GET_FILTERS: u16 = 1;
+const IFLA_STATS_SET_OFFLOAD_XSTATS_L3_STATS: u16 = 2;
+
+pub(crate) const IFLA_OFFLOAD_XSTATS_CPU_HIT: u16 = 1;
+pub(crate) const IFLA_OFFLOAD_XSTATS_HW_S_INFO: u16 = 2;
+pub(crate) const IFLA_OFFLOAD_XSTATS_L3_STATS: u16 = 3;
+
+const IFLA_OFFLOAD_XSTATS_HW_S_INFO_REQUEST: u16 = 1;
+const IFLA_OFFLOAD_XSTATS_HW_S_INFO_USED: u16 = 2;
+
+// The size of `struct if_stats_msg`
+const IF_STATS_MSG_LEN: usize = 12;
+// The size of `struct rtnl_link_stats64` without `rx_otherhost_dropped`
+// which was introduced by kernel 5.19
+const RTNL_LINK_STATS64_MIN_LEN: usize = 24 * 8;
+// The size of `struct rtnl_hw_stats64`
+const RTNL_HW_STATS64_LEN: usize = 9 * 8;
+
+/// Equal to kernel `IFLA_STATS_FILTER_BIT()`
+pub(crate) fn stats_filter_bit(attr: u16) -> u32 {
+    1 << (attr - 1)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StatsNetlinkMessage {
+    New(StatsMessage),
+    Get(StatsMessage),
+    Set(StatsMessage),
+}
+
+impl StatsNetlinkMessage {
+    fn message(&self) -> &StatsMessage {
+        match self {
+            Self::New(msg) | Self::Get(msg) | Self::Set(msg) => msg,
+        }
+    }
+
+    pub(crate) fn into_message(self) -> StatsMessage {
+        match self {
+            Self::New(msg) | Self::Get(msg) | Self::Set(msg) => msg,
+        }
+    }
+}
+
+impl NetlinkSerializable for StatsNetlinkMessage {
+    fn message_type(&self) -> u16 {
+        match self {
+            Self::New(_) => RTM_NEWSTATS,
+            Self::Get(_) => RTM_GETSTATS,
+            Self::Set(_) => RTM_SETSTATS,
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.message().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        self.message().emit(buffer)
+    }
+}
+
+impl NetlinkDeserializable for StatsNetlinkMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        let msg = StatsMessage::parse(payload)?;
+        Ok(match header.message_type {
+            RTM_NEWSTATS => Self::New(msg),
+            RTM_GETSTATS => Self::Get(msg),
+            RTM_SETSTATS => Self::Set(msg),
+            kind => {
+                return Err(DecodeError::from(format!(
+                    "Unknown stats message type {kind}"
+                )));
+            }
+        })
+    }
+}
+
+impl ProtoMessage for StatsNetlinkMessage {
+    const PROTOCOL: isize = NETLINK_ROUTE;
+}
+
+/// Equal to kernel `struct if_stats_msg`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct StatsHeader {
+    pub(crate) family: u8,
+    pub(crate) ifindex: u32,
+    /// The `IFLA_STATS_FILTER_BIT()` of requested `IFLA_STATS_*`
+    pub(crate) filter_mask: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct StatsMessage {
+    pub(crate) header: StatsHeader,
+    pub(crate) attributes: Vec<StatsAttr>,
+}
+
+impl StatsMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        if buf.len() < IF_STATS_MSG_LEN {
+            return Err(DecodeError::from(format!(
+                "Invalid if_stats_msg length {}",
+                buf.len()
+            )));
+        }
+        let header = StatsHeader {
+            family: buf[0],
+            ifindex: parse_u32(&buf[4..8])?,
+            filter_mask: parse_u32(&buf[8..IF_STATS_MSG_LEN])?,
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(&buf[IF_STATS_MSG_LEN..]) {
+            let nla = nla.context("invalid stats attribute")?;
+            attributes.push(StatsAttr::parse(&nla)?);
+        }
+        Ok(Self { header, attributes })
+    }
+}
+
+impl Emitable for StatsMessage {
+    fn buffer_len(&self) -> usize {
+        IF_STATS_MSG_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.header.family;
+        buffer[1..4].fill(0);
+        buffer[4..8].copy_from_slice(&self.header.ifindex.to_ne_bytes());
+        buffer[8..IF_STATS_MSG_LEN]
+            .copy_from_slice(&self.header.filter_mask.to_ne_bytes());
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[IF_STATS_MSG_LEN..]);
+    }
+}
+
+/// The `IFLA_STATS_*` of replies. The `IFLA_STATS_GETSET_*` of requests
+/// share the same attribute number space, hence they are only emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StatsAttr {
+    Link64(Stats64),
+    OffloadXstats(Vec<OffloadXstatsAttr>),
+    /// The `IFLA_STATS_GET_FILTERS` holding the `IFLA_STATS_FILTER_BIT()`
+    /// mask of nested attributes for each `IFLA_STATS_*`
+    GetFilters(Vec<(u16, u32)>),
+    SetOffloadL3Stats(bool),
+    Other(DefaultNla),
+}
+
+impl Nla for StatsAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            // Only received from kernel
+            Self::Link64(_) | Self::OffloadXstats(_) => 0,
+            Self::GetFilters(v) => v.len() * 8,
+            Self::SetOffloadL3Stats(_) => 1,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Link64(_) => IFLA_STATS_LINK_64,
+            Self::OffloadXstats(_) => IFLA_STATS_LINK_OFFLOAD_XSTATS,
+            Self::GetFilters(_) => IFLA_STATS_GET_FILTERS,
+            Self::SetOffloadL3Stats(_) => {
+                IFLA_STATS_SET_OFFLOAD_XSTATS_L3_STATS
+            }
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, Self::GetFilters(_))
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Link64(_) | Self::OffloadXstats(_) => (),
+            Self::GetFilters(v) => {
+                // Each filter is a NLA of u32 which is 8 bytes in total
+                for ((kind, mask), buf) in v.iter().zip(buffer.chunks_mut(8)) {
+                    buf[0..2].copy_from_slice(&8u16.to_ne_bytes());
+                    buf[2..4].copy_from_slice(&kind.to_ne_bytes());
+                    buf[4..8].copy_from_slice(&mask.to_ne_bytes());
+                }
+            }
+            Self::SetOffloadL3Stats(v) => buffer[0] = (*v).into(),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for StatsAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_STATS_LINK_64 => Self::Link64(parse_stats64(payload)?),
+            IFLA_STATS_LINK_OFFLOAD_XSTATS => {
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid IFLA_STATS_LINK_OFFLOAD_XSTATS")?;
+                    attrs.push(OffloadXstatsAttr::parse(&nla)?);
+                }
+                Self::OffloadXstats(attrs)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Equal to kernel `struct rtnl_hw_stats64`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct HwStats64 {
+    pub(crate) rx_packets: u64,
+    pub(crate) tx_packets: u64,
+    pub(crate) rx_bytes: u64,
+    pub(crate) tx_bytes: u64,
+    pub(crate) rx_errors: u64,
+    pub(crate) tx_errors: u64,
+    pub(crate) rx_dropped: u64,
+    pub(crate) tx_dropped: u64,
+    pub(crate) multicast: u64,
+}
+
+/// The `IFLA_OFFLOAD_XSTATS_HW_S_INFO_*` of specified offload stats type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct HwStatsInfo {
+    pub(crate) kind: u16,
+    pub(crate) request: bool,
+    pub(crate) used: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OffloadXstatsAttr {
+    CpuHit(Stats64),
+    HwStatsInfo(Vec<HwStatsInfo>),
+    L3Stats(HwStats64),
+    Other(DefaultNla),
+}
+
+impl Nla for OffloadXstatsAttr {
+    // Only received from kernel
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Other(v) => v.value_len(),
+            _ => 0,
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::CpuHit(_) => IFLA_OFFLOAD_XSTATS_CPU_HIT,
+            Self::HwStatsInfo(_) => IFLA_OFFLOAD_XSTATS_HW_S_INFO,
+            Self::L3Stats(_) => IFLA_OFFLOAD_XSTATS_L3_STATS,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        if let Self::Other(v) = self {
+            v.emit_value(buffer)
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for OffloadXstatsAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_OFFLOAD_XSTATS_CPU_HIT => {
+                Self::CpuHit(parse_stats64(payload)?)
+            }
+            IFLA_OFFLOAD_XSTATS_HW_S_INFO => {
+                let mut infos = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla =
+                        nla.context("invalid IFLA_OFFLOAD_XSTATS_HW_S_INFO")?;
+                    infos.push(parse_hw_stats_info(&nla)?);
+                }
+                Self::HwStatsInfo(infos)
+            }
+            IFLA_OFFLOAD_XSTATS_L3_STATS => {
+                Self::L3Stats(parse_hw_stats64(payload)?)
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+fn parse_hw_stats_info<T: AsRef<[u8]> + ?Sized>(
+    buf: &NlaBuffer<&T>,
+) -> Result<HwStatsInfo, DecodeError> {
+    let mut ret = HwStatsInfo {
+        kind: buf.kind(),
+        ..Default::default()
+    };
+    for nla in NlasIterator::new(buf.value()) {
+        let nla = nla.context("invalid hardware stats info")?;
+        match nla.kind() {
+            IFLA_OFFLOAD_XSTATS_HW_S_INFO_REQUEST => {
+                ret.request = parse_u8(nla.value())? != 0
+            }
+            IFLA_OFFLOAD_XSTATS_HW_S_INFO_USED => {
+                ret.used = parse_u8(nla.value())? != 0
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_u64_array<const N: usize>(
+    buf: &[u8],
+    name: &str,
+) -> Result<[u64; N], DecodeError> {
+    if buf.len() < N * 8 {
+        return Err(DecodeError::from(format!(
+            "Invalid {name} length {}",
+            buf.len()
+        )));
+    }
+    let mut ret = [0u64; N];
+    for (value, chunk) in ret.iter_mut().zip(buf.chunks_exact(8)) {
+        *value = parse_u64(chunk)?;
+    }
+    Ok(ret)
+}
+
+fn parse_stats64(buf: &[u8]) -> Result<Stats64, DecodeError> {
+    let v: [u64; 24] = parse_u64_array(buf, "rtnl_link_stats64")?;
+    let mut s = Stats64::default();
+    s.rx_packets = v[0];
+    s.tx_packets = v[1];
+    s.rx_bytes = v[2];
+    s.tx_bytes = v[3];
+    s.rx_errors = v[4];
+    s.tx_errors = v[5];
+    s.rx_dropped = v[6];
+    s.tx_dropped = v[7];
+    s.multicast = v[8];
+    s.collisions = v[9];
+    s.rx_length_errors = v[10];
+    s.rx_over_errors = v[11];
+    s.rx_crc_errors = v[12];
+    s.rx_frame_errors = v[13];
+    s.rx_fifo_errors = v[14];
+    s.rx_missed_errors = v[15];
+    s.tx_aborted_errors = v[16];
+    s.tx_carrier_errors = v[17];
+    s.tx_fifo_errors = v[18];
+    s.tx_heartbeat_errors = v[19];
+    s.tx_window_errors = v[20];
+    s.rx_compressed = v[21];
+    s.tx_compressed = v[22];
+    s.rx_nohandler = v[23];
+    if let Some(chunk) = buf.get(RTNL_LINK_STATS64_MIN_LEN..)
+        && chunk.len() >= 8
+    {
+        s.rx_otherhost_dropped = parse_u64(&chunk[..8])?;
+    }
+    Ok(s)
+}
+
+fn parse_hw_stats64(buf: &[u8]) -> Result<HwStats64, DecodeError> {
+    let v: [u64; RTNL_HW_STATS64_LEN / 8] =
+        parse_u64_array(buf, "rtnl_hw_stats64")?;
+    Ok(HwStats64 {
+        rx_packets: v[0],
+        tx_packets: v[1],
+        rx_bytes: v[2],
+        tx_bytes: v[3],
+        rx_errors: v[4],
+        tx_errors: v[5],
+        rx_dropped: v[6],
+        tx_dropped: v[7],
+        multicast: v[8],
+    })
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod message;
+mod set;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::StatsCommand;
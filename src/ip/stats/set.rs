@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CliError, next_opt, nl_request};
+use rtnetlink::packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+
+use super::{
+    cli::{dev_duplicate, unknown_option},
+    message::{StatsAttr, StatsHeader, StatsMessage, StatsNetlinkMessage},
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_on_off,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// Equal to iproute2 `ipstats_set()`
+pub(crate) async fn handle_set(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut dev = None;
+    let mut l3_stats = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "dev" => {
+                let value = next_opt(&mut opts)?;
+                if dev.is_some() {
+                    return Err(dev_duplicate(value));
+                }
+                dev = Some(value);
+            }
+            "l3_stats" => {
+                l3_stats =
+                    Some(parse_on_off("l3_stats", next_opt(&mut opts)?)?);
+            }
+            _ => return Err(unknown_option(opt)),
+        }
+    }
+
+    let Some(dev) = dev else {
+        return Err(CliError::from(
+            "Not enough information: \"dev\" argument is required.",
+        ));
+    };
+    let Some(l3_stats) = l3_stats else {
+        return Err(CliError::from(
+            "Not enough information: stat type to toggle is required.",
+        ));
+    };
+    let ifindex = ifname_to_index(&get_ifnames(ctx.handle()).await?, dev)?;
+
+    nl_request(
+        ctx.proto()?.handle(),
+        StatsNetlinkMessage::Set(StatsMessage {
+            header: StatsHeader {
+                ifindex,
+                ..Default::default()
+            },
+            attributes: vec![StatsAttr::SetOffloadL3Stats(l3_stats)],
+        }),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,540 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliError, CliNumberFormat, link::CliLinkStats,
+    next_opt, nl_request,
+};
+use rtnetlink::packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use serde::Serialize;
+
+use super::{
+    cli::{dev_duplicate, unknown_option},
+    message::{
+        HwStats64, IFLA_OFFLOAD_XSTATS_CPU_HIT, IFLA_OFFLOAD_XSTATS_HW_S_INFO,
+        IFLA_OFFLOAD_XSTATS_L3_STATS, IFLA_STATS_LINK_64,
+        IFLA_STATS_LINK_OFFLOAD_XSTATS, OffloadXstatsAttr, StatsAttr,
+        StatsHeader, StatsMessage, StatsNetlinkMessage, stats_filter_bit,
+    },
+};
+use crate::{
+    context::NetlinkContext,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// The leaf stats of `group GROUP [ subgroup SUBGROUP ]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatsLeaf {
+    Link,
+    OffloadL3Stats,
+    OffloadHwStatsInfo,
+    OffloadCpuHit,
+}
+
+// Same order as iproute2 which sorts the selected stats by the address of
+// their descriptors.
+const STATS_LEAVES: [StatsLeaf; 4] = [
+    StatsLeaf::Link,
+    StatsLeaf::OffloadL3Stats,
+    StatsLeaf::OffloadHwStatsInfo,
+    StatsLeaf::OffloadCpuHit,
+];
+
+// The groups of iproute2 not supported yet
+const UNSUPPORTED_GROUPS: [&str; 3] = ["xstats", "xstats_slave", "afstats"];
+
+impl StatsLeaf {
+    fn group(&self) -> &'static str {
+        match self {
+            Self::Link => "link",
+            Self::OffloadL3Stats
+            | Self::OffloadHwStatsInfo
+            | Self::OffloadCpuHit => "offload",
+        }
+    }
+
+    fn subgroup(&self) -> Option<&'static str> {
+        match self {
+            Self::Link => None,
+            Self::OffloadL3Stats => Some("l3_stats"),
+            Self::OffloadHwStatsInfo => Some("hw_stats_info"),
+            Self::OffloadCpuHit => Some("cpu_hit"),
+        }
+    }
+}
+
+/// Equal to iproute2 `ipstats_show_main()`
+#[derive(Debug, Default)]
+struct StatsShowOptions<'a> {
+    dev: Option<&'a str>,
+    leaves: Vec<StatsLeaf>,
+}
+
+impl<'a> StatsShowOptions<'a> {
+    fn parse(opts: &[&'a str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "dev" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.dev.is_some() {
+                        return Err(dev_duplicate(value));
+                    }
+                    ret.dev = Some(value);
+                }
+                "group" => ret.parse_group(&mut opts)?,
+                _ => return Err(unknown_option(opt)),
+            }
+        }
+        // All stats are shown by default and sorted like iproute2
+        ret.leaves = STATS_LEAVES
+            .into_iter()
+            .filter(|leaf| ret.leaves.is_empty() || ret.leaves.contains(leaf))
+            .collect();
+        Ok(ret)
+    }
+
+    fn select(&mut self, leaf: StatsLeaf) {
+        if !self.leaves.contains(&leaf) {
+            self.leaves.push(leaf);
+        }
+    }
+
+    fn parse_group(
+        &mut self,
+        opts: &mut std::slice::Iter<'_, &'a str>,
+    ) -> Result<(), CliError> {
+        let group = next_opt(opts)?;
+        if UNSUPPORTED_GROUPS.contains(&group) {
+            return Err(CliError::from(
+                format!("group {group} is not supported yet").as_str(),
+            ));
+        }
+        let leaves: Vec<StatsLeaf> = STATS_LEAVES
+            .into_iter()
+            .filter(|leaf| leaf.group() == group)
+            .collect();
+        if leaves.is_empty() {
+            return Err(CliError::from(
+                format!("no group named {group} found inside top-level")
+                    .as_str(),
+            ));
+        }
+
+        let mut has_subgroup = false;
+        while opts.as_slice().first() == Some(&"subgroup") {
+            opts.next();
+            let subgroup = next_opt(opts)?;
+            if leaves.iter().all(|leaf| leaf.subgroup().is_none()) {
+                return Err(CliError::from(
+                    format!(
+                        "subgroup {subgroup} requested inside leaf group \
+                         {group}"
+                    )
+                    .as_str(),
+                ));
+            }
+            let Some(leaf) =
+                leaves.iter().find(|leaf| leaf.subgroup() == Some(subgroup))
+            else {
+                return Err(CliError::from(
+                    format!(
+                        "no subgroup named {subgroup} found inside {group}"
+                    )
+                    .as_str(),
+                ));
+            };
+            if opts.as_slice().first() == Some(&"suite") {
+                opts.next();
+                let suite = next_opt(opts)?;
+                return Err(CliError::from(
+                    format!(
+                        "suite {suite} requested inside leaf subgroup \
+                         {subgroup}"
+                    )
+                    .as_str(),
+                ));
+            }
+            self.select(*leaf);
+            has_subgroup = true;
+        }
+        if !has_subgroup {
+            for leaf in leaves {
+                self.select(leaf);
+            }
+        }
+        Ok(())
+    }
+
+    // Equal to iproute2 `ipstats_req_add_filters()`, only the requested
+    // offload stats are dumped by kernel.
+    fn gen_request(&self, ifindex: u32) -> StatsMessage {
+        let mut filter_mask = 0;
+        let mut offload_mask = 0;
+        for leaf in &self.leaves {
+            match leaf {
+                StatsLeaf::Link => {
+                    filter_mask |= stats_filter_bit(IFLA_STATS_LINK_64)
+                }
+                StatsLeaf::OffloadL3Stats => {
+                    offload_mask |=
+                        stats_filter_bit(IFLA_OFFLOAD_XSTATS_L3_STATS)
+                            | stats_filter_bit(IFLA_OFFLOAD_XSTATS_HW_S_INFO)
+                }
+                StatsLeaf::OffloadHwStatsInfo => {
+                    offload_mask |=
+                        stats_filter_bit(IFLA_OFFLOAD_XSTATS_HW_S_INFO)
+                }
+                StatsLeaf::OffloadCpuHit => {
+                    offload_mask |=
+                        stats_filter_bit(IFLA_OFFLOAD_XSTATS_CPU_HIT)
+                }
+            }
+        }
+        let mut attributes = Vec::new();
+        if offload_mask != 0 {
+            filter_mask |= stats_filter_bit(IFLA_STATS_LINK_OFFLOAD_XSTATS);
+            attributes.push(StatsAttr::GetFilters(vec![(
+                IFLA_STATS_LINK_OFFLOAD_XSTATS,
+                offload_mask,
+            )]));
+        }
+        StatsMessage {
+            header: StatsHeader {
+                ifindex,
+                filter_mask,
+                ..Default::default()
+            },
+            attributes,
+        }
+    }
+}
+
+/// Equal to iproute2 `print_hw_stats64()`
+#[derive(Serialize)]
+struct CliHwStats {
+    rx: CliHwStatsRx,
+    tx: CliHwStatsTx,
+    #[serde(skip)]
+    number_format: CliNumberFormat,
+}
+
+#[derive(Serialize)]
+struct CliHwStatsRx {
+    bytes: u64,
+    packets: u64,
+    errors: u64,
+    dropped: u64,
+    multicast: u64,
+}
+
+#[derive(Serialize)]
+struct CliHwStatsTx {
+    bytes: u64,
+    packets: u64,
+    errors: u64,
+    dropped: u64,
+}
+
+// Equal to the `cols` of iproute2 `print_hw_stats64()`
+const HW_STATS_COLUMN_MIN_WIDTH: [usize; 5] = [
+    "*X: bytes".len(),
+    "packets".len(),
+    "errors".len(),
+    "dropped".len(),
+    "overrun".len(),
+];
+
+impl CliHwStats {
+    fn new(s: &HwStats64, number_format: CliNumberFormat) -> Self {
+        Self {
+            rx: CliHwStatsRx {
+                bytes: s.rx_bytes,
+                packets: s.rx_packets,
+                errors: s.rx_errors,
+                dropped: s.rx_dropped,
+                multicast: s.multicast,
+            },
+            tx: CliHwStatsTx {
+                bytes: s.tx_bytes,
+                packets: s.tx_packets,
+                errors: s.tx_errors,
+                dropped: s.tx_dropped,
+            },
+            number_format,
+        }
+    }
+
+    // Equal to iproute2 `size_columns()`
+    fn column_widths(&self) -> [usize; 5] {
+        let mut cols = HW_STATS_COLUMN_MIN_WIDTH;
+        if self.number_format.is_human() {
+            return cols;
+        }
+        let rx = &self.rx;
+        let tx = &self.tx;
+        for row in [
+            [rx.bytes, rx.packets, rx.errors, rx.dropped, rx.multicast],
+            [tx.bytes, tx.packets, tx.errors, tx.dropped, 0],
+        ] {
+            for (col, val) in cols.iter_mut().zip(row) {
+                *col = (*col).max(val.to_string().len());
+            }
+        }
+        cols
+    }
+}
+
+impl std::fmt::Display for CliHwStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cols = self.column_widths();
+        let rx = &self.rx;
+        let tx = &self.tx;
+        let fmt = self.number_format;
+
+        writeln!(
+            f,
+            "    RX: {:>w0$} {:>w1$} {:>w2$} {:>w3$} {:>w4$}",
+            "bytes",
+            "packets",
+            "errors",
+            "dropped",
+            "mcast",
+            w0 = cols[0] - 4,
+            w1 = cols[1],
+            w2 = cols[2],
+            w3 = cols[3],
+            w4 = cols[4],
+        )?;
+        write!(f, "    ")?;
+        for (width, count) in cols.iter().zip([
+            rx.bytes,
+            rx.packets,
+            rx.errors,
+            rx.dropped,
+            rx.multicast,
+        ]) {
+            write!(f, "{:>width$} ", fmt.format(count))?;
+        }
+        writeln!(
+            f,
+            "\n    TX: {:>w0$} {:>w1$} {:>w2$} {:>w3$}",
+            "bytes",
+            "packets",
+            "errors",
+            "dropped",
+            w0 = cols[0] - 4,
+            w1 = cols[1],
+            w2 = cols[2],
+            w3 = cols[3],
+        )?;
+        write!(f, "    ")?;
+        for (width, count) in cols
+            .iter()
+            .zip([tx.bytes, tx.packets, tx.errors, tx.dropped])
+        {
+            write!(f, "{:>width$} ", fmt.format(count))?;
+        }
+        Ok(())
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+/// Equal to iproute2 `ipstats_show_hw_s_info_one()`
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliHwStatsInfo {
+    request: bool,
+    used: bool,
+}
+
+impl std::fmt::Display for CliHwStatsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} used {}", on_off(self.request), on_off(self.used))
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliHwStatsInfoList {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    l3_stats: Option<CliHwStatsInfo>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CliStatsGroup {
+    Link {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stats64: Option<CliLinkStats>,
+    },
+    L3Stats {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        info: Option<CliHwStatsInfo>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stats64: Option<CliHwStats>,
+    },
+    HwStatsInfo {
+        info: CliHwStatsInfoList,
+    },
+}
+
+/// Equal to iproute2 `ipstats_show_group()`
+#[derive(Serialize)]
+pub(crate) struct CliStatsInfo {
+    ifindex: u32,
+    ifname: String,
+    group: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subgroup: Option<&'static str>,
+    #[serde(flatten)]
+    stats: CliStatsGroup,
+}
+
+impl std::fmt::Display for CliStatsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: group {}", self.ifindex, self.ifname, self.group)?;
+        if let Some(subgroup) = self.subgroup {
+            write!(f, " subgroup {subgroup}")?;
+        }
+        match &self.stats {
+            // Like iproute2, the new line is printed even without stats
+            CliStatsGroup::Link { stats64 } => {
+                writeln!(f)?;
+                if let Some(stats) = stats64 {
+                    write!(f, "{stats}")?;
+                }
+            }
+            CliStatsGroup::L3Stats { info, stats64 } => {
+                if let Some(info) = info {
+                    write!(f, " {info}")?;
+                }
+                if let Some(stats) = stats64 {
+                    write!(f, "\n{stats}")?;
+                }
+            }
+            CliStatsGroup::HwStatsInfo { info } => {
+                if let Some(l3_stats) = info.l3_stats {
+                    write!(f, "\n    l3_stats {l3_stats}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliStatsInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliStatsInfo {}
+
+fn parse_nl_msg_to_stats(
+    msg: &StatsMessage,
+    leaf: StatsLeaf,
+    ifnames: &HashMap<u32, String>,
+    stats_level: u8,
+    number_format: CliNumberFormat,
+) -> CliStatsInfo {
+    let mut link64 = None;
+    let mut cpu_hit = None;
+    let mut l3_stats = None;
+    let mut l3_stats_info = None;
+    for attr in &msg.attributes {
+        match attr {
+            StatsAttr::Link64(s) => link64 = Some(s),
+            StatsAttr::OffloadXstats(attrs) => {
+                for attr in attrs {
+                    match attr {
+                        OffloadXstatsAttr::CpuHit(s) => cpu_hit = Some(s),
+                        OffloadXstatsAttr::L3Stats(s) => l3_stats = Some(s),
+                        OffloadXstatsAttr::HwStatsInfo(infos) => {
+                            l3_stats_info = infos
+                                .iter()
+                                .find(|i| {
+                                    i.kind == IFLA_OFFLOAD_XSTATS_L3_STATS
+                                })
+                                .map(|i| CliHwStatsInfo {
+                                    request: i.request,
+                                    used: i.used,
+                                });
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let to_link_stats =
+        |s| CliLinkStats::from_stats64(s, None, stats_level, number_format);
+    let ifindex = msg.header.ifindex;
+    CliStatsInfo {
+        ifindex,
+        ifname: ifnames
+            .get(&ifindex)
+            .cloned()
+            .unwrap_or_else(|| format!("if{ifindex}")),
+        group: leaf.group(),
+        subgroup: leaf.subgroup(),
+        stats: match leaf {
+            StatsLeaf::Link => CliStatsGroup::Link {
+                stats64: link64.map(to_link_stats),
+            },
+            StatsLeaf::OffloadCpuHit => CliStatsGroup::Link {
+                stats64: cpu_hit.map(to_link_stats),
+            },
+            StatsLeaf::OffloadL3Stats => CliStatsGroup::L3Stats {
+                info: l3_stats_info,
+                stats64: l3_stats.map(|s| CliHwStats::new(s, number_format)),
+            },
+            StatsLeaf::OffloadHwStatsInfo => CliStatsGroup::HwStatsInfo {
+                info: CliHwStatsInfoList {
+                    l3_stats: l3_stats_info,
+                },
+            },
+        },
+    }
+}
+
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    stats_level: u8,
+    number_format: CliNumberFormat,
+) -> Result<Vec<CliStatsInfo>, CliError> {
+    let opts = StatsShowOptions::parse(opts)?;
+    let ifnames = get_ifnames(ctx.handle()).await?;
+
+    let (ifindex, flags) = match opts.dev {
+        Some(dev) => (ifname_to_index(&ifnames, dev)?, NLM_F_REQUEST),
+        None => (0, NLM_F_REQUEST | NLM_F_DUMP),
+    };
+    let replies = nl_request(
+        ctx.proto()?.handle(),
+        StatsNetlinkMessage::Get(opts.gen_request(ifindex)),
+        flags,
+    )
+    .await?;
+
+    let mut ret = Vec::new();
+    for reply in replies {
+        let msg = reply.into_message();
+        for leaf in &opts.leaves {
+            ret.push(parse_nl_msg_to_stats(
+                &msg,
+                *leaf,
+                &ifnames,
+                stats_level,
+                number_format,
+            ));
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+#[test]
+fn test_stats_show_and_set() {
+    let dummy_name = "sttest-dummy0";
+
+    exec_cmd(&["ip", "link", "add", dummy_name, "type", "dummy"]);
+
+    let result = std::panic::catch_unwind(|| {
+        let output = ip_rs_exec_cmd(&[
+            "stats", "set", "dev", dummy_name, "l3_stats", "on",
+        ]);
+        assert!(output.is_empty());
+
+        for args in [&[][..], &["-s"][..], &["-s", "-s"][..], &["-j"][..]] {
+            for show_args in [
+                &["group", "link"][..],
+                &["group", "offload"][..],
+                &["group", "link", "group", "offload", "subgroup", "cpu_hit"][..],
+                &[
+                    "group",
+                    "offload",
+                    "subgroup",
+                    "hw_stats_info",
+                    "subgroup",
+                    "l3_stats",
+                ][..],
+            ] {
+                let show_args =
+                    [&["stats", "show", "dev", dummy_name][..], show_args]
+                        .concat();
+                let expected_output =
+                    exec_cmd(&[&["ip"], args, &show_args].concat());
+                let our_output = ip_rs_exec_cmd(&[args, &show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        // Like iproute2, the oneline output has no trailing newline
+        let show_args =
+            ["stats", "show", "dev", dummy_name, "group", "offload"];
+        let expected_output =
+            exec_cmd(&[&["ip", "-o"][..], &show_args].concat());
+        let our_output = ip_rs_exec_cmd(&[&["-o"][..], &show_args].concat());
+        pretty_assertions::assert_eq!(
+            expected_output,
+            our_output.trim_end_matches('\n')
+        );
+
+        let output = ip_rs_exec_cmd(&[
+            "stats",
+            "show",
+            "dev",
+            dummy_name,
+            "group",
+            "offload",
+            "subgroup",
+            "hw_stats_info",
+        ]);
+        assert!(output.ends_with("\n    l3_stats on used off\n"));
+
+        ip_rs_exec_cmd(&["stats", "set", "dev", dummy_name, "l3_stats", "off"]);
+        let output = exec_cmd(&[
+            "ip", "stats", "show", "dev", dummy_name, "group", "offload",
+            "subgroup", "l3_stats",
+        ]);
+        assert!(output.contains(" subgroup l3_stats off used off\n"));
+    });
+
+    exec_cmd(&["ip", "link", "del", dummy_name]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_stats_invalid_args() {
+    for (args, error) in [
+        (&["stats", "show", "foo"][..], "What is \"foo\"?"),
+        (
+            &["stats", "show", "dev", "lo", "dev", "lo"][..],
+            "either \"dev\" is duplicate, or \"lo\" is a garbage.",
+        ),
+        (
+            &["stats", "show", "dev", "sttest-absent0"][..],
+            "Cannot find device \"sttest-absent0\"",
+        ),
+        (
+            &["stats", "show", "group", "foo"][..],
+            "no group named foo found inside top-level",
+        ),
+        (
+            &["stats", "show", "group", "offload", "subgroup", "foo"][..],
+            "no subgroup named foo found inside offload",
+        ),
+        (
+            &["stats", "show", "group", "link", "subgroup", "foo"][..],
+            "subgroup foo requested inside leaf group link",
+        ),
+        (
+            &[
+                "stats", "show", "group", "offload", "subgroup", "l3_stats",
+                "suite", "foo",
+            ][..],
+            "suite foo requested inside leaf subgroup l3_stats",
+        ),
+        (
+            &["stats", "set", "dev", "lo"][..],
+            "Not enough information: stat type to toggle is required.",
+        ),
+        (
+            &["stats", "set", "l3_stats", "on"][..],
+            "Not enough information: \"dev\" argument is required.",
+        ),
+        (
+            &["stats", "set", "dev", "lo", "l3_stats", "foo"][..],
+            "argument of \"l3_stats\" must be",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
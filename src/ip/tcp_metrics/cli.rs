@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::AddressFamily;
+
+use super::{
+    modify::{handle_delete, handle_flush},
+    show::{CliTcpMetricsInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct TcpMetricsCommand;
+
+impl TcpMetricsCommand {
+    pub(crate) const CMD: &'static str = "tcp_metrics";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("management for TCP Metrics")
+            .alias("tcpmetrics")
+            .alias("tcpm")
+            .alias("tcp_m")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show TCP metrics entries")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("flush")
+                    .about("flush TCP metrics entries")
+                    .alias("flus")
+                    .alias("flu")
+                    .alias("fl")
+                    .alias("f")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete TCP metrics entry")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliTcpMetricsInfo>>, CliError> {
+        let family = get_family(matches);
+        if !matches!(
+            family,
+            AddressFamily::Unspec | AddressFamily::Inet | AddressFamily::Inet6
+        ) {
+            return Err(CliError::from(
+                format!("Unsupported protocol family: {}", u8::from(family))
+                    .as_str(),
+            ));
+        }
+
+        if let Some(matches) = matches.subcommand_matches("delete") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_delete(ctx, &opts, family).await?;
+            Ok(None)
+        } else if let Some(matches) = matches.subcommand_matches("flush") {
+            let opts: Vec<&str> = matches
+                .get_many::<String>("options")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_flush(ctx, &opts, family).await?;
+            Ok(None)
+        } else {
+            let opts: Vec<&str> = matches
+                .subcommand_matches("show")
+                .and_then(|matches| matches.get_many::<String>("options"))
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect();
+            handle_show(ctx, &opts, family).await.map(Into::into)
+        }
+    }
+}
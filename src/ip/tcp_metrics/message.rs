@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+use rtnetlink::packet_core::{
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, parse_ipv6, parse_u16,
+    parse_u32, parse_u64,
+};
+
+// Equal to `linux/tcp_metrics.h`
+const TCP_METRICS_GENL_NAME: &str = "tcp_metrics";
+const TCP_METRICS_GENL_VERSION: u8 = 0x1;
+
+const TCP_METRICS_CMD_GET: u8 = 1;
+const TCP_METRICS_CMD_DEL: u8 = 2;
+
+const TCP_METRICS_ATTR_ADDR_IPV4: u16 = 1;
+const TCP_METRICS_ATTR_ADDR_IPV6: u16 = 2;
+const TCP_METRICS_ATTR_AGE: u16 = 3;
+const TCP_METRICS_ATTR_TW_TSVAL: u16 = 4;
+const TCP_METRICS_ATTR_TW_TS_STAMP: u16 = 5;
+const TCP_METRICS_ATTR_VALS: u16 = 6;
+const TCP_METRICS_ATTR_FOPEN_MSS: u16 = 7;
+const TCP_METRICS_ATTR_FOPEN_SYN_DROPS: u16 = 8;
+const TCP_METRICS_ATTR_FOPEN_SYN_DROP_TS: u16 = 9;
+const TCP_METRICS_ATTR_FOPEN_COOKIE: u16 = 10;
+const TCP_METRICS_ATTR_SADDR_IPV4: u16 = 11;
+const TCP_METRICS_ATTR_SADDR_IPV6: u16 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TcpMetricsCmd {
+    Get,
+    Del,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TcpMetricsMessage {
+    pub(crate) cmd: TcpMetricsCmd,
+    pub(crate) attributes: Vec<TcpMetricsAttr>,
+}
+
+impl TcpMetricsMessage {
+    pub(crate) fn new(
+        cmd: TcpMetricsCmd,
+        attributes: Vec<TcpMetricsAttr>,
+    ) -> Self {
+        Self { cmd, attributes }
+    }
+}
+
+impl GenlFamily for TcpMetricsMessage {
+    fn family_name() -> &'static str {
+        TCP_METRICS_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        match self.cmd {
+            TcpMetricsCmd::Get => TCP_METRICS_CMD_GET,
+            TcpMetricsCmd::Del => TCP_METRICS_CMD_DEL,
+        }
+    }
+
+    fn version(&self) -> u8 {
+        TCP_METRICS_GENL_VERSION
+    }
+}
+
+impl Emitable for TcpMetricsMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for TcpMetricsMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        let cmd = match header.cmd {
+            TCP_METRICS_CMD_GET => TcpMetricsCmd::Get,
+            TCP_METRICS_CMD_DEL => TcpMetricsCmd::Del,
+            cmd => {
+                return Err(DecodeError::from(format!(
+                    "Unknown tcp_metrics command {cmd}"
+                )));
+            }
+        };
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(buf) {
+            let nla = nla.context("invalid tcp_metrics attribute")?;
+            attributes.push(TcpMetricsAttr::parse(&nla)?);
+        }
+        Ok(Self { cmd, attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TcpMetricsAttr {
+    AddrIpv4(Ipv4Addr),
+    AddrIpv6(Ipv6Addr),
+    /// Milliseconds since the metrics were updated
+    Age(u64),
+    TwTsVal(u32),
+    TwTsStamp(u32),
+    Vals(Vec<TcpMetric>),
+    FopenMss(u16),
+    FopenSynDrops(u16),
+    /// Milliseconds since the last SYN drop of TCP fast open
+    FopenSynDropTs(u64),
+    FopenCookie(Vec<u8>),
+    SaddrIpv4(Ipv4Addr),
+    SaddrIpv6(Ipv6Addr),
+    Other(DefaultNla),
+}
+
+impl Nla for TcpMetricsAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::AddrIpv4(_) | Self::SaddrIpv4(_) => 4,
+            Self::AddrIpv6(_) | Self::SaddrIpv6(_) => 16,
+            Self::Age(_) | Self::FopenSynDropTs(_) => 8,
+            Self::TwTsVal(_) | Self::TwTsStamp(_) => 4,
+            Self::FopenMss(_) | Self::FopenSynDrops(_) => 2,
+            Self::Vals(v) => v.as_slice().buffer_len(),
+            Self::FopenCookie(v) => v.len(),
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::AddrIpv4(_) => TCP_METRICS_ATTR_ADDR_IPV4,
+            Self::AddrIpv6(_) => TCP_METRICS_ATTR_ADDR_IPV6,
+            Self::Age(_) => TCP_METRICS_ATTR_AGE,
+            Self::TwTsVal(_) => TCP_METRICS_ATTR_TW_TSVAL,
+            Self::TwTsStamp(_) => TCP_METRICS_ATTR_TW_TS_STAMP,
+            Self::Vals(_) => TCP_METRICS_ATTR_VALS,
+            Self::FopenMss(_) => TCP_METRICS_ATTR_FOPEN_MSS,
+            Self::FopenSynDrops(_) => TCP_METRICS_ATTR_FOPEN_SYN_DROPS,
+            Self::FopenSynDropTs(_) => TCP_METRICS_ATTR_FOPEN_SYN_DROP_TS,
+            Self::FopenCookie(_) => TCP_METRICS_ATTR_FOPEN_COOKIE,
+            Self::SaddrIpv4(_) => TCP_METRICS_ATTR_SADDR_IPV4,
+            Self::SaddrIpv6(_) => TCP_METRICS_ATTR_SADDR_IPV6,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::AddrIpv4(v) | Self::SaddrIpv4(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::AddrIpv6(v) | Self::SaddrIpv6(v) => {
+                buffer.copy_from_slice(&v.octets())
+            }
+            Self::Age(v) | Self::FopenSynDropTs(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::TwTsVal(v) | Self::TwTsStamp(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::FopenMss(v) | Self::FopenSynDrops(v) => {
+                buffer.copy_from_slice(&v.to_ne_bytes())
+            }
+            Self::Vals(v) => v.as_slice().emit(buffer),
+            Self::FopenCookie(v) => buffer.copy_from_slice(v),
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+fn parse_ipv4(payload: &[u8]) -> Result<Ipv4Addr, DecodeError> {
+    <[u8; 4]>::try_from(payload)
+        .map(Ipv4Addr::from)
+        .map_err(|_| DecodeError::invalid_ip_address(payload.len()))
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for TcpMetricsAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCP_METRICS_ATTR_ADDR_IPV4 => Self::AddrIpv4(parse_ipv4(payload)?),
+            TCP_METRICS_ATTR_ADDR_IPV6 => {
+                Self::AddrIpv6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            TCP_METRICS_ATTR_AGE => Self::Age(parse_u64(payload)?),
+            TCP_METRICS_ATTR_TW_TSVAL => Self::TwTsVal(parse_u32(payload)?),
+            TCP_METRICS_ATTR_TW_TS_STAMP => {
+                Self::TwTsStamp(parse_u32(payload)?)
+            }
+            TCP_METRICS_ATTR_VALS => {
+                let mut metrics = Vec::new();
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid tcp_metrics value")?;
+                    metrics.push(TcpMetric {
+                        kind: nla.kind(),
+                        value: parse_u32(nla.value())?,
+                    });
+                }
+                Self::Vals(metrics)
+            }
+            TCP_METRICS_ATTR_FOPEN_MSS => Self::FopenMss(parse_u16(payload)?),
+            TCP_METRICS_ATTR_FOPEN_SYN_DROPS => {
+                Self::FopenSynDrops(parse_u16(payload)?)
+            }
+            TCP_METRICS_ATTR_FOPEN_SYN_DROP_TS => {
+                Self::FopenSynDropTs(parse_u64(payload)?)
+            }
+            TCP_METRICS_ATTR_FOPEN_COOKIE => {
+                Self::FopenCookie(payload.to_vec())
+            }
+            TCP_METRICS_ATTR_SADDR_IPV4 => {
+                Self::SaddrIpv4(parse_ipv4(payload)?)
+            }
+            TCP_METRICS_ATTR_SADDR_IPV6 => {
+                Self::SaddrIpv6(Ipv6Addr::from(parse_ipv6(payload)?))
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested metric of `TCP_METRICS_ATTR_VALS`, the `kind` is
+/// `TCP_METRIC_*` plus one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TcpMetric {
+    pub(crate) kind: u16,
+    pub(crate) value: u32,
+}
+
+impl Nla for TcpMetric {
+    fn value_len(&self) -> usize {
+        4
+    }
+
+    fn kind(&self) -> u16 {
+        self.kind
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.value.to_ne_bytes())
+    }
+}
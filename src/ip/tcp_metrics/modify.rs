@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::Write;
+
+use iproute_rs::CliError;
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+
+use super::{
+    message::{TcpMetricsAttr, TcpMetricsCmd, TcpMetricsMessage},
+    show::{TcpMetricsFilter, dump_tcp_metrics, gen_addr_attrs},
+};
+use crate::{context::NetlinkContext, genl::genl_request};
+
+async fn delete_tcp_metrics(
+    ctx: &NetlinkContext,
+    attributes: Vec<TcpMetricsAttr>,
+) -> Result<(), CliError> {
+    let msg = TcpMetricsMessage::new(TcpMetricsCmd::Del, attributes);
+    genl_request(ctx, msg, NLM_F_REQUEST | NLM_F_ACK).await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `tcpm_do_cmd()` for `delete`, only a specific entry
+/// could be deleted.
+pub(crate) async fn handle_delete(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let filter = TcpMetricsFilter::parse(opts, family)?;
+    let Some(daddr) = filter.daddr.as_ref() else {
+        return Err(CliError::from("argument \"address\" is required"));
+    };
+    let Some(attrs) = filter.gen_exact_attrs() else {
+        return Err(CliError::from(
+            format!(
+                "a specific IP address is expected rather than \"{}/{}\"",
+                daddr.addr, daddr.prefix_len
+            )
+            .as_str(),
+        ));
+    };
+    delete_tcp_metrics(ctx, attrs).await
+}
+
+/// Equal to iproute2 `tcpm_do_cmd()` for `flush`. Without any filter, all
+/// entries are flushed by single request. The specific destination address
+/// is deleted directly, otherwise the matching entries are deleted one by
+/// one.
+pub(crate) async fn handle_flush(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let filter = TcpMetricsFilter::parse(opts, family)?;
+
+    if filter.is_empty() {
+        return delete_tcp_metrics(ctx, Vec::new()).await;
+    }
+    if let Some(attrs) = filter.gen_exact_attrs() {
+        return delete_tcp_metrics(ctx, attrs).await;
+    }
+
+    let entries = dump_tcp_metrics(ctx, &filter).await?;
+    if entries.is_empty() {
+        writeln!(std::io::stdout(), "Nothing to flush.").ok();
+        return Ok(());
+    }
+    for entry in entries {
+        delete_tcp_metrics(ctx, gen_addr_attrs(entry.dst, entry.source))
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, write_with_color,
+};
+use rtnetlink::{
+    packet_core::{NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+use serde::{Serialize, Serializer};
+
+use super::message::{
+    TcpMetric, TcpMetricsAttr, TcpMetricsCmd, TcpMetricsMessage,
+};
+use crate::{context::NetlinkContext, genl::genl_request, prefix::CliIpPrefix};
+
+// Equal to `TCP_METRIC_*` plus one of `linux/tcp_metrics.h`
+const TCP_METRIC_RTT: u16 = 1;
+const TCP_METRIC_RTTVAR: u16 = 2;
+const TCP_METRIC_SSTHRESH: u16 = 3;
+const TCP_METRIC_CWND: u16 = 4;
+const TCP_METRIC_REORDERING: u16 = 5;
+const TCP_METRIC_RTT_US: u16 = 6;
+const TCP_METRIC_RTTVAR_US: u16 = 7;
+
+// Like iproute2, the JSON output use seconds in float
+fn serialize_msec<S: Serializer>(
+    value: &Option<u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serializer.serialize_f64(*v as f64 / 1000.0),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_usec<S: Serializer>(
+    value: &Option<u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serializer.serialize_f64(*v as f64 / 1000000.0),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Equal to iproute2 `process_msg()` of `ip tcp_metrics`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct CliTcpMetricsInfo {
+    pub(crate) dst: IpAddr,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_msec"
+    )]
+    age: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tw_ts: Option<u32>,
+    /// Seconds since the timestamp was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tw_ts_stamp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssthresh: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cwnd: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reordering: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_usec"
+    )]
+    rtt: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_usec"
+    )]
+    rttvar: Option<u64>,
+    // The `fopen_miss` is a typo of iproute2 kept for compatibility
+    #[serde(rename = "fopen_miss", skip_serializing_if = "Option::is_none")]
+    fopen_mss: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fopen_syn_drops: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_msec"
+    )]
+    fopen_syn_drop_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fo_cookie: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) source: Option<IpAddr>,
+}
+
+impl std::fmt::Display for CliTcpMetricsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let color = match self.dst {
+            IpAddr::V4(_) => CliColor::Ipv4Addr,
+            IpAddr::V6(_) => CliColor::Ipv6Addr,
+        };
+        write_with_color!(f, color, "{}", self.dst)?;
+        if let Some(age) = self.age {
+            write!(f, " age {:.3}sec", age as f64 / 1000.0)?;
+        }
+        if let (Some(tw_ts), Some(tw_ts_stamp)) = (self.tw_ts, self.tw_ts_stamp)
+        {
+            write!(f, " tw_ts {tw_ts}/{tw_ts_stamp}sec ago")?;
+        }
+        for (name, value) in [
+            ("ssthresh", self.ssthresh),
+            ("cwnd", self.cwnd),
+            ("reordering", self.reordering),
+        ] {
+            if let Some(value) = value {
+                write!(f, " {name} {value}")?;
+            }
+        }
+        if let Some(rtt) = self.rtt {
+            write!(f, " rtt {rtt}us")?;
+        }
+        if let Some(rttvar) = self.rttvar {
+            write!(f, " rttvar {rttvar}us")?;
+        }
+        if let Some(fopen_mss) = self.fopen_mss {
+            write!(f, " fo_mss {fopen_mss}")?;
+        }
+        if let Some(fopen_syn_drops) = self.fopen_syn_drops {
+            write!(
+                f,
+                " fo_syn_drops {fopen_syn_drops}/{:.3}usec ago",
+                self.fopen_syn_drop_ts.unwrap_or_default() as f64 / 1000.0
+            )?;
+        }
+        if let Some(fo_cookie) = self.fo_cookie.as_ref() {
+            write!(f, " fo_cookie {fo_cookie}")?;
+        }
+        if let Some(source) = self.source {
+            let color = match source {
+                IpAddr::V4(_) => CliColor::Ipv4Addr,
+                IpAddr::V6(_) => CliColor::Ipv6Addr,
+            };
+            write!(f, " source ")?;
+            write_with_color!(f, color, "{source}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliTcpMetricsInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliTcpMetricsInfo {}
+
+impl CliTcpMetricsInfo {
+    fn from_msg(msg: TcpMetricsMessage) -> Option<Self> {
+        let mut dst = None;
+        let mut ret = Self {
+            dst: IpAddr::from([0u8; 4]),
+            age: None,
+            tw_ts: None,
+            tw_ts_stamp: None,
+            ssthresh: None,
+            cwnd: None,
+            reordering: None,
+            rtt: None,
+            rttvar: None,
+            fopen_mss: None,
+            fopen_syn_drops: None,
+            fopen_syn_drop_ts: None,
+            fo_cookie: None,
+            source: None,
+        };
+        let mut tw_ts = None;
+        let mut tw_ts_stamp = None;
+        for attr in msg.attributes {
+            match attr {
+                TcpMetricsAttr::AddrIpv4(v) => dst = Some(IpAddr::V4(v)),
+                TcpMetricsAttr::AddrIpv6(v) => dst = Some(IpAddr::V6(v)),
+                TcpMetricsAttr::SaddrIpv4(v) => {
+                    ret.source = Some(IpAddr::V4(v))
+                }
+                TcpMetricsAttr::SaddrIpv6(v) => {
+                    ret.source = Some(IpAddr::V6(v))
+                }
+                TcpMetricsAttr::Age(v) => ret.age = Some(v),
+                TcpMetricsAttr::TwTsVal(v) => tw_ts = Some(v),
+                TcpMetricsAttr::TwTsStamp(v) => tw_ts_stamp = Some(v),
+                TcpMetricsAttr::Vals(metrics) => ret.apply_metrics(&metrics),
+                TcpMetricsAttr::FopenMss(v) => ret.fopen_mss = Some(v),
+                TcpMetricsAttr::FopenSynDrops(v) => {
+                    ret.fopen_syn_drops = Some(v)
+                }
+                TcpMetricsAttr::FopenSynDropTs(v) => {
+                    ret.fopen_syn_drop_ts = Some(v)
+                }
+                TcpMetricsAttr::FopenCookie(v) => {
+                    // Like iproute2, at most 16 bytes are shown
+                    ret.fo_cookie = Some(
+                        v.iter().take(16).map(|b| format!("{b:02x}")).collect(),
+                    )
+                }
+                _ => (),
+            }
+        }
+        ret.dst = dst?;
+
+        // Like iproute2, the TIME-WAIT timestamp is only shown when any of
+        // value or stamp is non-zero. The stamp is wall clock seconds.
+        if let Some(stamp) = tw_ts_stamp {
+            let tw_ts = tw_ts.unwrap_or_default();
+            if stamp != 0 || tw_ts != 0 {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or_default();
+                ret.tw_ts = Some(tw_ts);
+                ret.tw_ts_stamp = Some(now - i64::from(stamp as i32));
+            }
+        }
+        Some(ret)
+    }
+
+    // Like iproute2, the microseconds RTT takes precedence over the
+    // millisecond one, and zero RTT is not shown.
+    fn apply_metrics(&mut self, metrics: &[TcpMetric]) {
+        let mut rtt = 0u64;
+        let mut rttvar = 0u64;
+        for metric in metrics {
+            let value = metric.value;
+            match metric.kind {
+                TCP_METRIC_RTT if rtt == 0 => {
+                    rtt = (u64::from(value) * 1000) >> 3;
+                }
+                TCP_METRIC_RTTVAR if rttvar == 0 => {
+                    rttvar = (u64::from(value) * 1000) >> 2;
+                }
+                TCP_METRIC_RTT_US => rtt = u64::from(value) >> 3,
+                TCP_METRIC_RTTVAR_US => rttvar = u64::from(value) >> 2,
+                TCP_METRIC_SSTHRESH => self.ssthresh = Some(value),
+                TCP_METRIC_CWND => self.cwnd = Some(value),
+                TCP_METRIC_REORDERING => self.reordering = Some(value),
+                _ => (),
+            }
+        }
+        self.rtt = (rtt != 0).then_some(rtt);
+        self.rttvar = (rttvar != 0).then_some(rttvar);
+    }
+}
+
+/// The `[address] PREFIX` and `source PREFIX` arguments shared by `show`,
+/// `delete` and `flush`, equal to the argument parsing of iproute2
+/// `tcpm_do_cmd()`.
+#[derive(Debug, Default)]
+pub(crate) struct TcpMetricsFilter {
+    pub(crate) family: Option<AddressFamily>,
+    pub(crate) daddr: Option<CliIpPrefix>,
+    pub(crate) saddr: Option<CliIpPrefix>,
+}
+
+impl TcpMetricsFilter {
+    pub(crate) fn parse(
+        opts: &[&str],
+        family: AddressFamily,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self {
+            family: (family != AddressFamily::Unspec).then_some(family),
+            ..Default::default()
+        };
+        let mut opts = opts.iter();
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "src" | "source" => {
+                    let value = next_opt(&mut opts)?;
+                    if ret.saddr.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"source\" is duplicate, or \
+                                 \"{value}\" is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.saddr = Some(CliIpPrefix::parse(value, family)?);
+                }
+                _ => {
+                    let value = if matches!(*opt, "address" | "addr") {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if ret.daddr.is_some() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"address\" is duplicate, or \
+                                 \"{value}\" is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    ret.daddr = Some(CliIpPrefix::parse(value, family)?);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.family.is_none() && self.daddr.is_none() && self.saddr.is_none()
+    }
+
+    /// Like iproute2, the request is sent with address attributes instead
+    /// of dumping when destination is a specific address. The source is
+    /// included only when it is a specific address also.
+    pub(crate) fn gen_exact_attrs(&self) -> Option<Vec<TcpMetricsAttr>> {
+        let daddr = self.daddr.as_ref().filter(|p| is_host_prefix(p))?;
+        let saddr = self
+            .saddr
+            .as_ref()
+            .filter(|p| is_host_prefix(p))
+            .map(|p| p.addr);
+        Some(gen_addr_attrs(daddr.addr, saddr))
+    }
+
+    pub(crate) fn matches(&self, info: &CliTcpMetricsInfo) -> bool {
+        if let Some(family) = self.family {
+            let entry_family = match info.dst {
+                IpAddr::V4(_) => AddressFamily::Inet,
+                IpAddr::V6(_) => AddressFamily::Inet6,
+            };
+            if family != entry_family {
+                return false;
+            }
+        }
+        if let Some(daddr) = self.daddr.as_ref()
+            && !daddr.contains(&info.dst)
+        {
+            return false;
+        }
+        if let Some(saddr) = self.saddr.as_ref() {
+            match info.source.as_ref() {
+                Some(source) => saddr.contains(source),
+                None => false,
+            }
+        } else {
+            true
+        }
+    }
+}
+
+/// Generate the address attributes of a specific entry.
+pub(crate) fn gen_addr_attrs(
+    daddr: IpAddr,
+    saddr: Option<IpAddr>,
+) -> Vec<TcpMetricsAttr> {
+    let mut attrs = vec![match daddr {
+        IpAddr::V4(v) => TcpMetricsAttr::AddrIpv4(v),
+        IpAddr::V6(v) => TcpMetricsAttr::AddrIpv6(v),
+    }];
+    match saddr {
+        Some(IpAddr::V4(v)) => attrs.push(TcpMetricsAttr::SaddrIpv4(v)),
+        Some(IpAddr::V6(v)) => attrs.push(TcpMetricsAttr::SaddrIpv6(v)),
+        None => (),
+    }
+    attrs
+}
+
+fn is_host_prefix(prefix: &CliIpPrefix) -> bool {
+    prefix.prefix_len
+        == match prefix.addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+}
+
+/// Dump all the TCP metrics entries matching the filter.
+pub(crate) async fn dump_tcp_metrics(
+    ctx: &NetlinkContext,
+    filter: &TcpMetricsFilter,
+) -> Result<Vec<CliTcpMetricsInfo>, CliError> {
+    let msg = TcpMetricsMessage::new(TcpMetricsCmd::Get, Vec::new());
+    Ok(genl_request(ctx, msg, NLM_F_REQUEST | NLM_F_DUMP)
+        .await?
+        .into_iter()
+        .filter_map(CliTcpMetricsInfo::from_msg)
+        .filter(|info| filter.matches(info))
+        .collect())
+}
+
+/// Equal to iproute2 `tcpm_do_cmd()` for `show`, a specific destination
+/// address is queried directly instead of dumping.
+pub(crate) async fn handle_show(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+) -> Result<Vec<CliTcpMetricsInfo>, CliError> {
+    let filter = TcpMetricsFilter::parse(opts, family)?;
+
+    if let Some(attrs) = filter.gen_exact_attrs() {
+        let msg = TcpMetricsMessage::new(TcpMetricsCmd::Get, attrs);
+        return Ok(genl_request(ctx, msg, NLM_F_REQUEST)
+            .await?
+            .into_iter()
+            .filter_map(CliTcpMetricsInfo::from_msg)
+            .filter(|info| filter.matches(info))
+            .collect());
+    }
+
+    dump_tcp_metrics(ctx, &filter).await
+}
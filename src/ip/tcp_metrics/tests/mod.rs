@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+// Kernel saves the TCP metrics of the destination when the connection is
+// closed, the source address is always `127.0.0.1`.
+fn setup_tcp_metrics(dst: &str) {
+    let listener = TcpListener::bind((dst, 0)).unwrap();
+    let mut client =
+        TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server, _) = listener.accept().unwrap();
+    client.write_all(&[0u8; 65536]).unwrap();
+    let mut buf = vec![0u8; 65536];
+    server.read_exact(&mut buf).unwrap();
+    drop(client);
+    drop(server);
+    // Wait for kernel to save the metrics
+    std::thread::sleep(std::time::Duration::from_millis(200));
+}
+
+// The `age` changes between two invocations, hence remove it for comparing.
+fn strip_age(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| {
+            let mut words = Vec::new();
+            let mut iter = line.split(' ');
+            while let Some(word) = iter.next() {
+                if word == "age" {
+                    iter.next();
+                } else {
+                    words.push(word);
+                }
+            }
+            format!("{}\n", words.join(" "))
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+
+use super::setup_tcp_metrics;
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+#[test]
+fn test_tcp_metrics_delete() {
+    setup_tcp_metrics("127.0.0.41");
+
+    assert!(
+        !exec_cmd(&["ip", "tcp_metrics", "show", "127.0.0.41/32"]).is_empty()
+    );
+    let output = ip_rs_exec_cmd(&["tcp_metrics", "delete", "127.0.0.41"]);
+    assert!(output.is_empty());
+    pretty_assertions::assert_eq!(
+        exec_cmd(&["ip", "tcp_metrics", "show", "127.0.0.41/32"]),
+        ""
+    );
+
+    let output =
+        ip_rs_exec_cmd_output(&["tcp_metrics", "delete", "127.0.0.41"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("RTNETLINK answers: No such process"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_tcp_metrics_flush() {
+    setup_tcp_metrics("127.0.0.42");
+
+    // The reverse entry uses the test address as source
+    assert!(
+        !exec_cmd(&["ip", "tcp_metrics", "show", "src", "127.0.0.42"])
+            .is_empty()
+    );
+    let output =
+        ip_rs_exec_cmd(&["tcp_metrics", "flush", "src", "127.0.0.42/32"]);
+    assert!(output.is_empty());
+    pretty_assertions::assert_eq!(
+        exec_cmd(&["ip", "tcp_metrics", "show", "src", "127.0.0.42"]),
+        ""
+    );
+
+    let output = ip_rs_exec_cmd(&["tcp_metrics", "flush", "127.0.0.42/31"]);
+    assert!(output.is_empty());
+    pretty_assertions::assert_eq!(
+        exec_cmd(&["ip", "tcp_metrics", "show", "127.0.0.42/32"]),
+        ""
+    );
+
+    let output = ip_rs_exec_cmd(&["tcp_metrics", "flush", "127.0.0.42/31"]);
+    pretty_assertions::assert_eq!(output, "Nothing to flush.\n");
+}
+
+#[test]
+fn test_tcp_metrics_delete_invalid_args() {
+    for (args, error) in [
+        (
+            &["tcp_metrics", "delete"][..],
+            "argument \"address\" is required",
+        ),
+        (
+            &["tcp_metrics", "delete", "127.0.0.0/8"][..],
+            "a specific IP address is expected rather than \"127.0.0.0/8\"",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
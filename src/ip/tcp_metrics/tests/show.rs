@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+
+use super::{setup_tcp_metrics, strip_age};
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+#[test]
+fn test_tcp_metrics_show() {
+    setup_tcp_metrics("127.0.0.31");
+
+    for args in [
+        &["tcp_metrics", "show", "127.0.0.31"][..],
+        &["tcp_metrics", "show", "address", "127.0.0.31/32"][..],
+        &["tcpm", "list", "src", "127.0.0.31"][..],
+        &["tcp_metrics", "show", "127.0.0.31", "source", "127.0.0.0/8"][..],
+        &["-4", "tcp_metrics", "show", "127.0.0.31"][..],
+        &["-4", "tcp_metrics", "show", "127.0.0.31/32"][..],
+    ] {
+        let expected_output = exec_cmd(&[&["ip"], args].concat());
+        let our_output = ip_rs_exec_cmd(args);
+
+        pretty_assertions::assert_eq!(
+            strip_age(&expected_output),
+            strip_age(&our_output),
+            "{args:?}"
+        );
+    }
+    let output = strip_age(&ip_rs_exec_cmd(&["tcpm", "show", "127.0.0.31"]));
+    assert!(output.starts_with("127.0.0.31 "), "{output}");
+    assert!(output.ends_with(" source 127.0.0.1\n"), "{output}");
+
+    let output = ip_rs_exec_cmd(&["-j", "tcp_metrics", "show", "127.0.0.31"]);
+    assert!(output.contains("\"dst\":\"127.0.0.31\""), "{output}");
+    assert!(output.contains("\"source\":\"127.0.0.1\""), "{output}");
+
+    exec_cmd(&["ip", "tcp_metrics", "delete", "127.0.0.31"]);
+}
+
+#[test]
+fn test_tcp_metrics_invalid_args() {
+    for (args, error) in [
+        (
+            &["tcp_metrics", "show", "foo"][..],
+            "any valid prefix is expected rather than \"foo\".",
+        ),
+        (
+            &["-6", "tcp_metrics", "show", "127.0.0.1"][..],
+            "inet6 prefix is expected rather than \"127.0.0.1\".",
+        ),
+        (
+            &["tcp_metrics", "show", "127.0.0.1", "127.0.0.2"][..],
+            "either \"address\" is duplicate, or \"127.0.0.2\" is a garbage.",
+        ),
+        (
+            &["tcp_metrics", "show", "src", "127.0.0.1", "src", "::1"][..],
+            "either \"source\" is duplicate, or \"::1\" is a garbage.",
+        ),
+        (
+            &["tcp_metrics", "show", "src"][..],
+            "Command line is not complete. Try option \"help\"",
+        ),
+        (
+            &["-0", "tcp_metrics", "show"][..],
+            "Unsupported protocol family: 17",
+        ),
+        (
+            &["tcp_metrics", "show", "127.0.0.32"][..],
+            "RTNETLINK answers: No such process",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
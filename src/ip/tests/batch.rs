@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT
+
+use super::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+fn with_batch_file<T>(name: &str, content: &str, test: T)
+where
+    T: FnOnce(&str) + std::panic::UnwindSafe,
+{
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, content).expect("Failed to write batch file");
+    let path_str = path.to_str().expect("Not UTF-8 string").to_string();
+
+    let result = std::panic::catch_unwind(|| {
+        test(&path_str);
+    });
+
+    // clean up
+    std::fs::remove_file(&path).ok();
+    assert!(result.is_ok())
+}
+
+#[test]
+fn test_batch() {
+    with_batch_file(
+        "iproute-rs-test-batch",
+        "link show lo\naddress show lo\n",
+        |path| {
+            let expected_output = exec_cmd(&["ip", "-batch", path]);
+
+            let our_output = ip_rs_exec_cmd(&["-batch", path]);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        },
+    );
+}
+
+#[test]
+fn test_batch_with_global_options() {
+    with_batch_file(
+        "iproute-rs-test-batch-global-options",
+        "link show lo\naddress show lo\n",
+        |path| {
+            let expected_output = exec_cmd(&["ip", "-j", "-b", path]);
+
+            let our_output = ip_rs_exec_cmd(&["-j", "-b", path]);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        },
+    );
+}
+
+#[test]
+fn test_batch_comment_and_continuation() {
+    with_batch_file(
+        "iproute-rs-test-batch-comment",
+        "# comment line\n\nlink \\\nshow 'lo' # trailing comment\n",
+        |path| {
+            let expected_output = exec_cmd(&["ip", "-batch", path]);
+
+            let our_output = ip_rs_exec_cmd(&["-batch", path]);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        },
+    );
+}
+
+#[test]
+fn test_batch_stop_on_error() {
+    with_batch_file(
+        "iproute-rs-test-batch-stop-on-error",
+        "nosuchobject\nlink show lo\n",
+        |path| {
+            let output = ip_rs_exec_cmd_output(&["-batch", path]);
+
+            assert_eq!(output.status.code(), Some(1));
+            assert!(output.stdout.is_empty());
+            assert!(
+                String::from_utf8_lossy(&output.stderr)
+                    .contains(&format!("Command failed {path}:1"))
+            );
+        },
+    );
+}
+
+#[test]
+fn test_batch_force() {
+    with_batch_file(
+        "iproute-rs-test-batch-force",
+        "nosuchobject\nlink show lo\n",
+        |path| {
+            let expected_output = exec_cmd(&["ip", "link", "show", "lo"]);
+
+            let output = ip_rs_exec_cmd_output(&["-force", "-batch", path]);
+
+            assert_eq!(output.status.code(), Some(1));
+            pretty_assertions::assert_eq!(
+                expected_output,
+                String::from_utf8_lossy(&output.stdout)
+            );
+        },
+    );
+}
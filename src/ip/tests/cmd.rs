@@ -22,20 +22,7 @@ pub(crate) fn assert_alias_output(expected_args: &[&str], alias_args: &[&str]) {
 }
 
 pub(crate) fn ip_rs_exec_cmd(args: &[&str]) -> String {
-    let mut cur_exec_path =
-        std::env::current_exe().expect("No current exec path");
-
-    cur_exec_path.pop();
-    cur_exec_path.pop();
-
-    let output = std::process::Command::new(
-        cur_exec_path.join("ip").to_str().expect("Not UTF-8 string"),
-    )
-    .args(args)
-    .output()
-    .unwrap_or_else(|e| {
-        panic!("failed to execute ip-rs command {args:?}: {e}")
-    });
+    let output = ip_rs_exec_cmd_output(args);
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -45,3 +32,82 @@ pub(crate) fn ip_rs_exec_cmd(args: &[&str]) -> String {
     String::from_utf8(output.stdout)
         .expect("Failed to convert command output to String")
 }
+
+/// Execute without checking the exit status
+pub(crate) fn ip_rs_exec_cmd_output(args: &[&str]) -> std::process::Output {
+    std::process::Command::new(ip_rs_path())
+        .args(args)
+        .output()
+        .unwrap_or_else(|e| {
+            panic!("failed to execute ip-rs command {args:?}: {e}")
+        })
+}
+
+/// Spawn ip-rs with stdout captured, used by long running command like
+/// `ip monitor`
+pub(crate) fn ip_rs_spawn(args: &[&str]) -> std::process::Child {
+    std::process::Command::new(ip_rs_path())
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| {
+            panic!("failed to spawn ip-rs command {args:?}: {e}")
+        })
+}
+
+/// Execute with the data fed to stdin, without checking the exit status
+pub(crate) fn exec_cmd_with_stdin(
+    args: &[&str],
+    stdin: &[u8],
+) -> std::process::Output {
+    output_with_stdin(
+        std::process::Command::new(args[0]).args(&args[1..]),
+        stdin,
+    )
+    .unwrap_or_else(|e| panic!("failed to execute command {args:?}: {e}"))
+}
+
+/// Execute ip-rs with the data fed to stdin, without checking the exit
+/// status
+pub(crate) fn ip_rs_exec_cmd_with_stdin(
+    args: &[&str],
+    stdin: &[u8],
+) -> std::process::Output {
+    output_with_stdin(
+        std::process::Command::new(ip_rs_path()).args(args),
+        stdin,
+    )
+    .unwrap_or_else(|e| panic!("failed to execute ip-rs command {args:?}: {e}"))
+}
+
+fn output_with_stdin(
+    cmd: &mut std::process::Command,
+    stdin: &[u8],
+) -> std::io::Result<std::process::Output> {
+    let mut child = cmd
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    // The stdin is closed when dropped
+    std::io::Write::write_all(
+        &mut child.stdin.take().expect("No stdin of child"),
+        stdin,
+    )?;
+    child.wait_with_output()
+}
+
+fn ip_rs_path() -> String {
+    let mut cur_exec_path =
+        std::env::current_exe().expect("No current exec path");
+
+    cur_exec_path.pop();
+    cur_exec_path.pop();
+
+    cur_exec_path
+        .join("ip")
+        .to_str()
+        .expect("Not UTF-8 string")
+        .to_string()
+}
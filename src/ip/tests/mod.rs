@@ -1,5 +1,21 @@
 // SPDX-License-Identifier: MIT
 
+mod batch;
 mod cmd;
 
-pub(crate) use self::cmd::{assert_alias_output, exec_cmd, ip_rs_exec_cmd};
+use std::sync::{Mutex, MutexGuard};
+
+pub(crate) use self::cmd::{
+    assert_alias_output, exec_cmd, exec_cmd_with_stdin, ip_rs_exec_cmd,
+    ip_rs_exec_cmd_output, ip_rs_exec_cmd_with_stdin, ip_rs_spawn,
+};
+
+// The tests changing the global state of the network namespace, e.g.
+// routing tables, the interfaces of fixed names or the `/run/netns`
+// folder, should hold this lock to not run in parallel.
+static NET_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_net_test() -> MutexGuard<'static, ()> {
+    // The lock is poisoned by failed test which is harmless here
+    NET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
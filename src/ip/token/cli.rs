@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    set::handle_set,
+    show::{CliTokenInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext};
+
+pub(crate) struct TokenCommand;
+
+impl TokenCommand {
+    pub(crate) const CMD: &'static str = "token";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("tokenized interface identifier support")
+            .alias("toke")
+            .alias("tok")
+            .alias("to")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("list")
+                    .about("list interface tokens")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("l")
+                    .alias("lst")
+                    .alias("show")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("set")
+                    .about("set interface token")
+                    .alias("se")
+                    .alias("add")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete interface token")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("get")
+                    .about("get interface token")
+                    .alias("ge")
+                    .alias("g")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliTokenInfo>>, CliError> {
+        let handle = ctx.handle();
+        for (name, delete) in [("set", false), ("delete", true)] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_set(handle, &opts, delete).await?;
+                return Ok(None);
+            }
+        }
+
+        // The `get` is `list` filtered by `dev`
+        let opts: Vec<&str> = ["list", "get"]
+            .iter()
+            .find_map(|name| matches.subcommand_matches(name))
+            .map(|matches| {
+                matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect()
+            })
+            .unwrap_or_default();
+        handle_show(handle, &opts).await.map(Into::into)
+    }
+}
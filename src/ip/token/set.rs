@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+};
+
+use iproute_rs::{CliError, next_opt};
+use rtnetlink::packet_route::{
+    AddressFamily,
+    link::{AfSpecInet6, AfSpecUnspec, LinkAttribute, LinkMessage},
+};
+
+use crate::{
+    prefix::CliIpPrefix,
+    route::{get_ifnames, ifname_to_index},
+};
+
+/// The interface index of `dev`, with the error message of iproute2
+/// `iptoken_set()`
+pub(super) fn parse_token_dev(
+    ifnames: &HashMap<u32, String>,
+    dev: &str,
+) -> Result<u32, CliError> {
+    ifname_to_index(ifnames, dev).map_err(|_| {
+        CliError::from(
+            format!("argument \"{dev}\" is wrong: dev is invalid").as_str(),
+        )
+    })
+}
+
+/// Equal to iproute2 `iptoken_set()`, the token is cleared by `del`
+pub(crate) async fn handle_set(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    delete: bool,
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(handle).await?;
+    let mut token = delete.then_some(Ipv6Addr::UNSPECIFIED);
+    let mut ifindex = None;
+    let mut opts = opts.iter();
+
+    // Like iproute2, only the first token and `dev` are used
+    while let Some(opt) = opts.next() {
+        if *opt == "dev" {
+            let dev = next_opt(&mut opts)?;
+            if ifindex.is_none() {
+                ifindex = Some(parse_token_dev(&ifnames, dev)?);
+            }
+        } else if token.is_none() {
+            let prefix = CliIpPrefix::parse(opt, AddressFamily::Inet6)?;
+            if let IpAddr::V6(addr) = prefix.addr {
+                token = Some(addr);
+            }
+        }
+    }
+    let Some(token) = token else {
+        return Err(CliError::from(
+            "Not enough information: token is required.",
+        ));
+    };
+    let Some(ifindex) = ifindex else {
+        return Err(CliError::from(
+            "Not enough information: \"dev\" argument is required.",
+        ));
+    };
+
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.interface_family = AddressFamily::Inet6;
+    nl_msg.header.index = ifindex;
+    nl_msg.attributes.push(LinkAttribute::AfSpecUnspec(vec![
+        AfSpecUnspec::Inet6(vec![AfSpecInet6::Token(token)]),
+    ]));
+    handle.link().set(nl_msg).execute().await?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv6Addr;
+
+use futures_util::TryStreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliColor, CliError, next_opt, write_with_color,
+};
+use rtnetlink::packet_route::link::{
+    AfSpecInet6, AfSpecUnspec, LinkAttribute, LinkFlags, LinkMessage,
+};
+use serde::Serialize;
+
+use super::set::parse_token_dev;
+use crate::route::get_ifnames;
+
+/// Equal to iproute2 `print_token()`
+#[derive(Serialize)]
+pub(crate) struct CliTokenInfo {
+    token: Ipv6Addr,
+    ifname: String,
+}
+
+impl std::fmt::Display for CliTokenInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "token ")?;
+        write_with_color!(f, CliColor::Ipv6Addr, "{}", self.token)?;
+        write!(f, " dev ")?;
+        write_with_color!(f, CliColor::IfaceName, "{}", self.ifname)
+    }
+}
+
+impl CanDisplay for CliTokenInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliTokenInfo {}
+
+fn get_token(nl_msg: &LinkMessage) -> Option<Ipv6Addr> {
+    nl_msg.attributes.iter().find_map(|attr| {
+        let LinkAttribute::AfSpecUnspec(af_spec) = attr else {
+            return None;
+        };
+        af_spec.iter().find_map(|af| {
+            let AfSpecUnspec::Inet6(inet6) = af else {
+                return None;
+            };
+            inet6.iter().find_map(|nla| {
+                if let AfSpecInet6::Token(token) = nla {
+                    Some(*token)
+                } else {
+                    None
+                }
+            })
+        })
+    })
+}
+
+fn get_ifname(nl_msg: &LinkMessage) -> Option<String> {
+    nl_msg.attributes.iter().find_map(|attr| {
+        if let LinkAttribute::IfName(name) = attr {
+            Some(name.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Equal to iproute2 `iptoken_list()` used by both `list` and `get`
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<Vec<CliTokenInfo>, CliError> {
+    // Like iproute2, only the `dev` argument is used for filtering
+    let mut dev = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if *opt == "dev" {
+            dev = Some(next_opt(&mut opts)?);
+            break;
+        }
+    }
+    let ifindex = match dev {
+        Some(dev) => Some(parse_token_dev(&get_ifnames(handle).await?, dev)?),
+        None => None,
+    };
+
+    let mut ret = Vec::new();
+    let mut links = handle.link().get().execute();
+    while let Some(nl_msg) = links.try_next().await? {
+        if ifindex.is_some_and(|index| index != nl_msg.header.index)
+            || nl_msg
+                .header
+                .flags
+                .intersects(LinkFlags::Loopback | LinkFlags::Noarp)
+        {
+            continue;
+        }
+        // The interface without IPv6 has no token
+        if let (Some(token), Some(ifname)) =
+            (get_token(&nl_msg), get_ifname(&nl_msg))
+        {
+            ret.push(CliTokenInfo { token, ifname });
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod token;
+
+use crate::tests::exec_cmd;
+
+fn setup_veth(veth_name: &str) {
+    let peer_name = format!("{veth_name}p");
+    exec_cmd(&[
+        "ip", "link", "add", veth_name, "type", "veth", "peer", "name",
+        &peer_name,
+    ]);
+}
+
+fn cleanup_veth(veth_name: &str) {
+    exec_cmd(&["ip", "link", "del", veth_name]);
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+
+use super::{cleanup_veth, setup_veth};
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output};
+
+#[test]
+fn test_token_set_get_del() {
+    let veth_name = "tktest-veth0";
+    setup_veth(veth_name);
+
+    let result = std::panic::catch_unwind(|| {
+        ip_rs_exec_cmd(&["token", "set", "::1:2:3:4/64", "dev", veth_name]);
+        for args in [
+            &["token", "get", "dev", veth_name][..],
+            &["-j", "token", "get", "dev", veth_name][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+        let output = exec_cmd(&["ip", "token", "get", "dev", veth_name]);
+        pretty_assertions::assert_eq!(
+            output,
+            format!("token ::1:2:3:4 dev {veth_name}\n")
+        );
+        // Other tests might change interfaces, hence only check the token
+        // of test interface in full list.
+        for args in [&["token"][..], &["token", "list"][..]] {
+            let our_output = ip_rs_exec_cmd(args);
+            assert!(
+                our_output
+                    .contains(&format!("token ::1:2:3:4 dev {veth_name}\n"))
+            );
+        }
+        let our_output = ip_rs_exec_cmd(&["-j", "token", "show"]);
+        assert!(our_output.contains(&format!(
+            "{{\"token\":\"::1:2:3:4\",\"ifname\":\"{veth_name}\"}}"
+        )));
+
+        ip_rs_exec_cmd(&["token", "del", "dev", veth_name]);
+        let output = exec_cmd(&["ip", "token", "get", "dev", veth_name]);
+        pretty_assertions::assert_eq!(
+            output,
+            format!("token :: dev {veth_name}\n")
+        );
+    });
+
+    cleanup_veth(veth_name);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_token_invalid_args() {
+    for (args, error) in [
+        (
+            &["token", "set", "::1"][..],
+            "Not enough information: \"dev\" argument is required.",
+        ),
+        (
+            &["token", "set", "dev", "lo"][..],
+            "Not enough information: token is required.",
+        ),
+        (
+            &["token", "set", "::1", "dev", "tktest-nosuch"][..],
+            "argument \"tktest-nosuch\" is wrong: dev is invalid",
+        ),
+        (
+            &["token", "get", "dev", "tktest-nosuch"][..],
+            "argument \"tktest-nosuch\" is wrong: dev is invalid",
+        ),
+        (
+            &["token", "set", "foo", "dev", "lo"][..],
+            "inet6 prefix is expected rather than \"foo\".",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::AddressFamily;
+
+use super::{
+    ioctl::TunnelIoctl,
+    modify::{handle_add, handle_del},
+    show::{CliTunnelInfo, handle_show},
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+pub(crate) struct TunnelCommand;
+
+impl TunnelCommand {
+    pub(crate) const CMD: &'static str = "tunnel";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("tunnel over IP")
+            .alias("tunne")
+            .alias("tunn")
+            .alias("tun")
+            .alias("tu")
+            .alias("t")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add tunnel")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("change")
+                    .about("change tunnel")
+                    .alias("chang")
+                    .alias("chan")
+                    .alias("cha")
+                    .alias("ch")
+                    .alias("c")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete tunnel")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show tunnels")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<Vec<CliTunnelInfo>>, CliError> {
+        let handle = ctx.handle();
+        // Like iproute2 `do_iptunnel()`, only the IPv4 tunnels are
+        // supported
+        let family = get_family(matches);
+        if !matches!(family, AddressFamily::Unspec | AddressFamily::Inet) {
+            return Err(CliError::from(
+                format!("Unsupported protocol family: {}", u8::from(family))
+                    .as_str(),
+            ));
+        }
+
+        for name in ["add", "change", "delete"] {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                match name {
+                    "add" => {
+                        handle_add(handle, &opts, TunnelIoctl::Add).await?
+                    }
+                    "change" => {
+                        handle_add(handle, &opts, TunnelIoctl::Change).await?
+                    }
+                    _ => handle_del(handle, &opts).await?,
+                }
+                return Ok(None);
+            }
+        }
+
+        let (matches, opts): (_, Vec<&str>) =
+            if let Some(matches) = matches.subcommand_matches("show") {
+                (
+                    matches,
+                    matches
+                        .get_many::<String>("options")
+                        .unwrap_or_default()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            } else {
+                (matches, Vec::new())
+            };
+        handle_show(handle, &opts, matches.get_count("STATS") > 0)
+            .await
+            .map(Into::into)
+    }
+}
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    os::fd::{AsRawFd, OwnedFd},
+};
+
+use iproute_rs::CliError;
+use nix::{
+    errno::Errno,
+    libc,
+    sys::socket::{AddressFamily, SockFlag, SockType, socket},
+};
+
+const IFNAMSIZ: usize = 16;
+
+// Equal to the private ioctls of `linux/if_tunnel.h`
+const SIOCDEVPRIVATE: u32 = 0x89F0;
+const SIOCGETTUNNEL: u32 = SIOCDEVPRIVATE;
+const SIOCADDTUNNEL: u32 = SIOCDEVPRIVATE + 1;
+const SIOCDELTUNNEL: u32 = SIOCDEVPRIVATE + 2;
+const SIOCCHGTUNNEL: u32 = SIOCDEVPRIVATE + 3;
+const SIOCGET6RD: u32 = SIOCDEVPRIVATE + 8;
+
+nix::ioctl_write_ptr_bad!(siocgettunnel, SIOCGETTUNNEL, libc::ifreq);
+nix::ioctl_write_ptr_bad!(siocaddtunnel, SIOCADDTUNNEL, libc::ifreq);
+nix::ioctl_write_ptr_bad!(siocdeltunnel, SIOCDELTUNNEL, libc::ifreq);
+nix::ioctl_write_ptr_bad!(siocchgtunnel, SIOCCHGTUNNEL, libc::ifreq);
+nix::ioctl_write_ptr_bad!(siocget6rd, SIOCGET6RD, libc::ifreq);
+
+pub(super) const IPPROTO_IPIP: u8 = 4;
+pub(super) const IPPROTO_IPV6: u8 = 41;
+pub(super) const IPPROTO_GRE: u8 = 47;
+
+// The GRE flags are stored in network byte order
+pub(super) const GRE_CSUM: u16 = 0x8000u16.to_be();
+pub(super) const GRE_KEY: u16 = 0x2000u16.to_be();
+pub(super) const GRE_SEQ: u16 = 0x1000u16.to_be();
+// The VTI and ISATAP flags are not converted to network byte order by
+// kernel
+pub(super) const VTI_ISVTI: u16 = 0x0001;
+pub(super) const SIT_ISATAP: u16 = 0x0001;
+
+pub(super) const IP_DF: u16 = 0x4000u16.to_be();
+
+/// Equal to `struct iphdr`, the multi-byte fields are in network byte
+/// order.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct IpHeader {
+    /// The `ihl` is the lower 4 bits while `version` is the higher
+    pub(super) ihl_version: u8,
+    pub(super) tos: u8,
+    tot_len: u16,
+    id: u16,
+    pub(super) frag_off: u16,
+    pub(super) ttl: u8,
+    pub(super) protocol: u8,
+    check: u16,
+    pub(super) saddr: [u8; 4],
+    pub(super) daddr: [u8; 4],
+}
+
+/// Equal to `struct ip_tunnel_parm` used by the ioctl of IPv4 tunnels
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct IpTunnelParm {
+    pub(super) name: [u8; IFNAMSIZ],
+    pub(super) link: i32,
+    pub(super) i_flags: u16,
+    pub(super) o_flags: u16,
+    pub(super) i_key: u32,
+    pub(super) o_key: u32,
+    pub(super) iph: IpHeader,
+}
+
+impl IpTunnelParm {
+    pub(super) fn name(&self) -> String {
+        let len = self.name.iter().position(|c| *c == 0).unwrap_or(IFNAMSIZ);
+        String::from_utf8_lossy(&self.name[..len]).to_string()
+    }
+
+    pub(super) fn set_name(&mut self, name: &str) {
+        self.name = [0; IFNAMSIZ];
+        for (dst, src) in self
+            .name
+            .iter_mut()
+            .zip(name.as_bytes().iter().take(IFNAMSIZ - 1))
+        {
+            *dst = *src;
+        }
+    }
+
+    pub(super) fn local(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.iph.saddr)
+    }
+
+    pub(super) fn remote(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.iph.daddr)
+    }
+}
+
+/// Equal to `struct ip_tunnel_6rd`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct IpTunnel6rd {
+    prefix: [u8; 16],
+    relay_prefix: [u8; 4],
+    prefix_len: u16,
+    relay_prefix_len: u16,
+}
+
+impl IpTunnel6rd {
+    pub(super) fn prefix(&self) -> Option<(Ipv6Addr, u16)> {
+        (self.prefix_len != 0)
+            .then(|| (Ipv6Addr::from(self.prefix), self.prefix_len))
+    }
+
+    pub(super) fn relay_prefix(&self) -> Option<(Ipv4Addr, u16)> {
+        (self.relay_prefix != [0; 4])
+            .then(|| (Ipv4Addr::from(self.relay_prefix), self.relay_prefix_len))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TunnelIoctl {
+    Add,
+    Change,
+    Delete,
+}
+
+impl TunnelIoctl {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Add => "add tunnel",
+            Self::Change => "change tunnel",
+            Self::Delete => "delete tunnel",
+        }
+    }
+}
+
+fn tunnel_socket() -> Result<OwnedFd, CliError> {
+    socket(
+        AddressFamily::Inet,
+        SockType::Datagram,
+        SockFlag::SOCK_CLOEXEC,
+        None,
+    )
+    .map_err(|e| {
+        CliError::from(format!("create socket failed: {}", e.desc()).as_str())
+    })
+}
+
+// The `ifr_data` points to the tunnel parameters read or written by kernel
+fn gen_ifreq<T>(ifname: &str, data: &mut T) -> libc::ifreq {
+    // SAFETY: `ifreq` is plain old data which is valid when zeroed
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in ifr
+        .ifr_name
+        .iter_mut()
+        .zip(ifname.as_bytes().iter().take(IFNAMSIZ - 1))
+    {
+        *dst = *src as libc::c_char;
+    }
+    ifr.ifr_ifru.ifru_data = (data as *mut T).cast();
+    ifr
+}
+
+/// Equal to iproute2 `tnl_get_ioctl()`
+pub(super) fn get_tunnel(ifname: &str) -> Result<IpTunnelParm, CliError> {
+    let fd = tunnel_socket()?;
+    let mut parm = IpTunnelParm::default();
+    let ifr = gen_ifreq(ifname, &mut parm);
+    // SAFETY: the `ifreq` and the tunnel parameters it points to outlive
+    // the ioctl
+    unsafe { siocgettunnel(fd.as_raw_fd(), &ifr) }.map_err(|e: Errno| {
+        CliError::from(
+            format!("get tunnel \"{ifname}\" failed: {}", e.desc()).as_str(),
+        )
+    })?;
+    Ok(parm)
+}
+
+/// Equal to iproute2 `tnl_ioctl_get_6rd()`
+pub(super) fn get_6rd(ifname: &str) -> Option<IpTunnel6rd> {
+    let fd = tunnel_socket().ok()?;
+    let mut ip6rd = IpTunnel6rd::default();
+    let ifr = gen_ifreq(ifname, &mut ip6rd);
+    // SAFETY: the `ifreq` and the 6rd parameters it points to outlive the
+    // ioctl
+    unsafe { siocget6rd(fd.as_raw_fd(), &ifr) }.ok()?;
+    Some(ip6rd)
+}
+
+/// Equal to iproute2 `tnl_add_ioctl()` and `tnl_del_ioctl()`, the `ifname`
+/// is the tunnel to change or the base device of tunnel mode for adding.
+pub(super) fn modify_tunnel(
+    action: TunnelIoctl,
+    ifname: &str,
+    parm: &IpTunnelParm,
+) -> Result<(), CliError> {
+    let fd = tunnel_socket()?;
+    let mut parm = *parm;
+    let ifr = gen_ifreq(ifname, &mut parm);
+    // SAFETY: the `ifreq` and the tunnel parameters it points to outlive
+    // the ioctl
+    let result = unsafe {
+        match action {
+            TunnelIoctl::Add => siocaddtunnel(fd.as_raw_fd(), &ifr),
+            TunnelIoctl::Change => siocchgtunnel(fd.as_raw_fd(), &ifr),
+            TunnelIoctl::Delete => siocdeltunnel(fd.as_raw_fd(), &ifr),
+        }
+    };
+    result.map(|_| ()).map_err(|e: Errno| {
+        CliError::from(
+            format!("{} \"{ifname}\" failed: {}", action.name(), e.desc())
+                .as_str(),
+        )
+    })
+}
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::Ipv4Addr};
+
+use iproute_rs::{CliError, next_opt};
+
+use super::ioctl::{
+    GRE_CSUM, GRE_KEY, GRE_SEQ, IP_DF, IPPROTO_GRE, IPPROTO_IPIP, IPPROTO_IPV6,
+    IpTunnelParm, SIT_ISATAP, TunnelIoctl, VTI_ISVTI, get_tunnel,
+    modify_tunnel,
+};
+use crate::{
+    link::{check_ifname, parse_num},
+    route::{get_ifnames, ifname_to_index},
+};
+
+const IP_VERSION_IHL: u8 = 0x45;
+
+/// The arguments of `ip tunnel` following the grammar of iproute2
+/// `parse_args()`, shared by all the tunnel commands.
+pub(super) fn parse_tunnel_opts(
+    opts: &[&str],
+    action: Option<TunnelIoctl>,
+    ifnames: &HashMap<u32, String>,
+) -> Result<IpTunnelParm, CliError> {
+    let mut parm = IpTunnelParm::default();
+    parm.iph.ihl_version = IP_VERSION_IHL;
+    parm.iph.frag_off = IP_DF;
+    let mut name_given = false;
+    let mut isatap = false;
+    let mut medium = None;
+    let total = opts.len();
+    let mut opts = opts.iter();
+
+    while let Some(opt) = opts.next() {
+        let is_first = opts.len() + 1 == total;
+        match *opt {
+            "mode" => {
+                let mode = next_opt(&mut opts)?;
+                let proto = match mode {
+                    "ipip" | "ip/ip" => IPPROTO_IPIP,
+                    "gre" | "gre/ip" => IPPROTO_GRE,
+                    "sit" | "ipv6/ip" => IPPROTO_IPV6,
+                    "isatap" => {
+                        isatap = true;
+                        IPPROTO_IPV6
+                    }
+                    "vti" => {
+                        parm.i_flags |= VTI_ISVTI;
+                        IPPROTO_IPIP
+                    }
+                    _ => {
+                        return Err(CliError::from(
+                            format!("Unknown tunnel mode \"{mode}\"").as_str(),
+                        ));
+                    }
+                };
+                set_tunnel_proto(&mut parm, proto)?;
+            }
+            "key" | "ikey" | "okey" => {
+                let key = parse_key(opt, next_opt(&mut opts)?)?;
+                if *opt != "okey" {
+                    parm.i_flags |= GRE_KEY;
+                    parm.i_key = key;
+                }
+                if *opt != "ikey" {
+                    parm.o_flags |= GRE_KEY;
+                    parm.o_key = key;
+                }
+            }
+            "seq" | "iseq" | "oseq" | "csum" | "icsum" | "ocsum" => {
+                let flag = if opt.ends_with("seq") {
+                    GRE_SEQ
+                } else {
+                    GRE_CSUM
+                };
+                if !opt.starts_with('o') {
+                    parm.i_flags |= flag;
+                }
+                if !opt.starts_with('i') {
+                    parm.o_flags |= flag;
+                }
+            }
+            "nopmtudisc" => parm.iph.frag_off = 0,
+            "pmtudisc" => parm.iph.frag_off = IP_DF,
+            "remote" => {
+                parm.iph.daddr = parse_addr32(next_opt(&mut opts)?)?.octets();
+            }
+            "local" => {
+                parm.iph.saddr = parse_addr32(next_opt(&mut opts)?)?.octets();
+            }
+            "dev" => {
+                medium = Some(next_opt(&mut opts)?);
+            }
+            "ttl" | "hoplimit" | "hlim" => {
+                let ttl = next_opt(&mut opts)?;
+                if ttl != "inherit" {
+                    parm.iph.ttl = parse_num(ttl, "invalid TTL")?;
+                }
+            }
+            "tos" | "tclass" | "dsfield" => {
+                parm.iph.tos = parse_tos(next_opt(&mut opts)?)?;
+            }
+            _ => {
+                let name = if *opt == "name" {
+                    next_opt(&mut opts)?
+                } else {
+                    opt
+                };
+                if name_given {
+                    return Err(CliError::from(
+                        format!(
+                            "either \"name\" is duplicate, or \"{name}\" is a \
+                             garbage."
+                        )
+                        .as_str(),
+                    ));
+                }
+                check_ifname(name, "\"name\"")?;
+                name_given = true;
+                // Like iproute2, the tunnel to change is loaded first when
+                // it is the first argument
+                if action == Some(TunnelIoctl::Change) && is_first {
+                    let old_parm = get_tunnel(name)?;
+                    if old_parm.iph.ihl_version != IP_VERSION_IHL {
+                        return Err(CliError::from(
+                            format!(
+                                "argument \"{name}\" is wrong: \"name\" is \
+                                 not an ip tunnel"
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    parm = old_parm;
+                }
+                parm.set_name(name);
+            }
+        }
+    }
+
+    // Like iproute2, the tunnel mode could be guessed from the name
+    let name = parm.name();
+    if parm.iph.protocol == 0 {
+        if name.starts_with("gre") {
+            parm.iph.protocol = IPPROTO_GRE;
+        } else if name.starts_with("ipip") {
+            parm.iph.protocol = IPPROTO_IPIP;
+        } else if name.starts_with("sit") {
+            parm.iph.protocol = IPPROTO_IPV6;
+        } else if name.starts_with("isatap") {
+            parm.iph.protocol = IPPROTO_IPV6;
+            isatap = true;
+        } else if name.starts_with("vti") {
+            parm.iph.protocol = IPPROTO_IPIP;
+            parm.i_flags |= VTI_ISVTI;
+        }
+    }
+
+    if (parm.i_flags & GRE_KEY != 0 || parm.o_flags & GRE_KEY != 0)
+        && parm.i_flags & VTI_ISVTI == 0
+        && parm.iph.protocol != IPPROTO_GRE
+    {
+        return Err(CliError::from(
+            "Keys are not allowed with ipip and sit tunnels",
+        ));
+    }
+
+    if let Some(medium) = medium {
+        parm.link = ifname_to_index(ifnames, medium)? as i32;
+    }
+
+    // The multicast destination is used as key of broadcast GRE tunnel
+    let remote = parm.remote();
+    if remote.is_multicast() {
+        if parm.i_key == 0 {
+            parm.i_key = u32::from_ne_bytes(parm.iph.daddr);
+            parm.i_flags |= GRE_KEY;
+        }
+        if parm.o_key == 0 {
+            parm.o_key = u32::from_ne_bytes(parm.iph.daddr);
+            parm.o_flags |= GRE_KEY;
+        }
+        if parm.local().is_unspecified() {
+            return Err(CliError::from(
+                "A broadcast tunnel requires a source address",
+            ));
+        }
+    }
+    if isatap {
+        parm.i_flags |= SIT_ISATAP;
+    }
+    Ok(parm)
+}
+
+fn set_tunnel_proto(
+    parm: &mut IpTunnelParm,
+    proto: u8,
+) -> Result<(), CliError> {
+    if parm.iph.protocol != 0 && parm.iph.protocol != proto {
+        return Err(CliError::from(
+            "You managed to ask for more than one tunnel mode.",
+        ));
+    }
+    parm.iph.protocol = proto;
+    Ok(())
+}
+
+// Equal to iproute2 `get_addr32()`, `any` stands for the zero address
+fn parse_addr32(value: &str) -> Result<Ipv4Addr, CliError> {
+    if matches!(value, "any" | "default" | "all") {
+        return Ok(Ipv4Addr::UNSPECIFIED);
+    }
+    value.parse().map_err(|_| {
+        CliError::from(
+            format!("an IP address is expected rather than \"{value}\"")
+                .as_str(),
+        )
+    })
+}
+
+/// Equal to iproute2 `tnl_parse_key()`, the key is either dotted quad or
+/// number, returned in network byte order.
+fn parse_key(name: &str, value: &str) -> Result<u32, CliError> {
+    if value.contains('.') {
+        return Ok(u32::from_ne_bytes(parse_addr32(value)?.octets()));
+    }
+    parse_num::<u32>(value, "").map(u32::to_be).map_err(|_| {
+        CliError::from(
+            format!(
+                "invalid value for \"{name}\": \"{value}\"; it should be an \
+                 unsigned integer"
+            )
+            .as_str(),
+        )
+    })
+}
+
+/// Like the `tos` of iproute2 `parse_args()`, the lowest bit stands for
+/// `inherit` which could be combined with TOS like `inherit/0x10`.
+fn parse_tos(value: &str) -> Result<u8, CliError> {
+    let (inherit, dsfield) = match value.split_once('/') {
+        Some((inherit, dsfield)) => (inherit == "inherit", Some(dsfield)),
+        None if value == "inherit" => (true, None),
+        None => (false, Some(value)),
+    };
+    let mut tos = u8::from(inherit);
+    if let Some(dsfield) = dsfield {
+        let digits = dsfield
+            .strip_prefix("0x")
+            .or_else(|| dsfield.strip_prefix("0X"))
+            .unwrap_or(dsfield);
+        tos |= u8::from_str_radix(digits, 16).map_err(|_| {
+            CliError::from(
+                format!("argument \"{value}\" is wrong: bad TOS value")
+                    .as_str(),
+            )
+        })?;
+    }
+    Ok(tos)
+}
+
+// Equal to iproute2 `tnl_defname()`, the base device of tunnel mode
+fn default_tunnel_name(parm: &IpTunnelParm) -> Option<&'static str> {
+    match parm.iph.protocol {
+        IPPROTO_IPIP if parm.i_flags & VTI_ISVTI != 0 => Some("ip_vti0"),
+        IPPROTO_IPIP => Some("tunl0"),
+        IPPROTO_GRE => Some("gre0"),
+        IPPROTO_IPV6 => Some("sit0"),
+        _ => None,
+    }
+}
+
+/// Equal to iproute2 `do_add()` used by both `add` and `change`
+pub(super) async fn handle_add(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    action: TunnelIoctl,
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(handle).await?;
+    let parm = parse_tunnel_opts(opts, Some(action), &ifnames)?;
+
+    if parm.iph.ttl != 0 && parm.iph.frag_off == 0 {
+        return Err(CliError::from("ttl != 0 and nopmtudisc are incompatible"));
+    }
+    let Some(basedev) = default_tunnel_name(&parm) else {
+        return Err(CliError::from(
+            "cannot determine tunnel mode (ipip, gre, vti or sit)",
+        ));
+    };
+    let name = parm.name();
+    let ifname = if action == TunnelIoctl::Change && !name.is_empty() {
+        name.as_str()
+    } else {
+        basedev
+    };
+    modify_tunnel(action, ifname, &parm)
+}
+
+/// Equal to iproute2 `do_del()`
+pub(super) async fn handle_del(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(handle).await?;
+    let parm = parse_tunnel_opts(opts, Some(TunnelIoctl::Delete), &ifnames)?;
+    let name = parm.name();
+    let ifname = if name.is_empty() {
+        default_tunnel_name(&parm).unwrap_or_default()
+    } else {
+        name.as_str()
+    };
+    modify_tunnel(TunnelIoctl::Delete, ifname, &parm)
+}
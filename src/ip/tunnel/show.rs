@@ -0,0 +1,409 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use futures_util::TryStreamExt;
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use rtnetlink::packet_route::link::LinkAttribute;
+use serde::Serialize;
+
+use super::{
+    ioctl::{
+        GRE_CSUM, GRE_KEY, GRE_SEQ, IP_DF, IPPROTO_GRE, IPPROTO_IPIP,
+        IPPROTO_IPV6, IpTunnelParm, get_6rd, get_tunnel,
+    },
+    modify::parse_tunnel_opts,
+};
+
+const PROC_NET_DEV: &str = "/proc/net/dev";
+
+const ARPHRD_TUNNEL: u16 = 768;
+const ARPHRD_SIT: u16 = 776;
+const ARPHRD_IPGRE: u16 = 778;
+
+// Equal to the GRE flags of iproute2 `tnl_print_gre_flags()`
+const GRE_FLAG_DESCRIPTIONS: [(&str, &str); 4] = [
+    ("rx_drop_ooseq", "Drop packets out of sequence."),
+    ("rx_csum", "Checksum in received packet is required."),
+    ("tx_seq", "Sequence packets on output."),
+    ("tx_csum", "Checksum output packets."),
+];
+
+/// Equal to iproute2 `print_tunnel()`
+#[derive(Serialize)]
+pub(crate) struct CliTunnelInfo {
+    ifname: String,
+    mode: &'static str,
+    remote: Ipv4Addr,
+    local: Ipv4Addr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    /// `None` for `inherit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u8>,
+    /// The lowest bit stands for `inherit`
+    #[serde(skip)]
+    tos_inherit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nopmtudisc: Option<()>,
+    #[serde(rename = "6rd-prefix", skip_serializing_if = "Option::is_none")]
+    prefix_6rd: Option<Ipv6Addr>,
+    #[serde(rename = "6rd-prefixlen", skip_serializing_if = "Option::is_none")]
+    prefix_len_6rd: Option<u16>,
+    #[serde(
+        rename = "6rd-relay_prefix",
+        skip_serializing_if = "Option::is_none"
+    )]
+    relay_prefix_6rd: Option<Ipv4Addr>,
+    #[serde(
+        rename = "6rd-relay_prefixlen",
+        skip_serializing_if = "Option::is_none"
+    )]
+    relay_prefix_len_6rd: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ikey: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    okey: Option<u32>,
+    /// Only shown for GRE tunnels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<Vec<&'static str>>,
+    /// Like iproute2, the statistics are not included in JSON
+    #[serde(skip)]
+    stats: Option<CliTunnelStats>,
+}
+
+// The zero address is shown as `any` in text
+fn write_endpoint(
+    f: &mut std::fmt::Formatter<'_>,
+    addr: &Ipv4Addr,
+) -> std::fmt::Result {
+    if addr.is_unspecified() {
+        write!(f, "any")
+    } else {
+        write_with_color!(f, CliColor::Ipv4Addr, "{addr}")
+    }
+}
+
+impl std::fmt::Display for CliTunnelInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{}", self.ifname)?;
+        write!(f, ": {} remote ", self.mode)?;
+        write_endpoint(f, &self.remote)?;
+        write!(f, " local ")?;
+        write_endpoint(f, &self.local)?;
+        if let Some(dev) = &self.dev {
+            write!(f, " dev {dev}")?;
+        }
+        match self.ttl {
+            Some(ttl) => write!(f, " ttl {ttl}")?,
+            None => write!(f, " ttl inherit")?,
+        }
+        match (&self.tos, self.tos_inherit) {
+            (Some(tos), true) => write!(f, " tos inherit/{tos}")?,
+            (Some(tos), false) => write!(f, " tos {tos}")?,
+            (None, true) => write!(f, " tos inherit")?,
+            (None, false) => (),
+        }
+        if self.nopmtudisc.is_some() {
+            write!(f, " nopmtudisc")?;
+        }
+        if let (Some(prefix), Some(prefix_len)) =
+            (self.prefix_6rd, self.prefix_len_6rd)
+        {
+            write!(f, " 6rd-prefix {prefix}/{prefix_len}")?;
+            if let (Some(prefix), Some(prefix_len)) =
+                (self.relay_prefix_6rd, self.relay_prefix_len_6rd)
+            {
+                write!(f, " 6rd-relay_prefix {prefix}/{prefix_len}")?;
+            }
+        }
+        if let Some(key) = self.key {
+            write!(f, " key {key}")?;
+        }
+        if let Some(ikey) = self.ikey {
+            write!(f, " ikey {ikey}")?;
+        }
+        if let Some(okey) = self.okey {
+            write!(f, " okey {okey}")?;
+        }
+        for flag in self.flags.iter().flatten() {
+            if let Some((_, description)) =
+                GRE_FLAG_DESCRIPTIONS.iter().find(|(name, _)| name == flag)
+            {
+                write!(f, "\n  {description}")?;
+            }
+        }
+        if let Some(stats) = &self.stats {
+            write!(f, "\n{stats}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliTunnelInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliTunnelInfo {}
+
+/// The counters read from `/proc/net/dev`
+#[derive(Debug, Clone, Copy, Default)]
+struct CliTunnelStats {
+    rx_bytes: u64,
+    rx_packets: u64,
+    rx_errors: u64,
+    rx_fifo_errors: u64,
+    rx_frame_errors: u64,
+    multicast: u64,
+    tx_bytes: u64,
+    tx_packets: u64,
+    tx_errors: u64,
+    tx_dropped: u64,
+    collisions: u64,
+    tx_carrier_errors: u64,
+}
+
+impl CliTunnelStats {
+    // The columns after the interface name of `/proc/net/dev`
+    fn parse(value: &str) -> Option<Self> {
+        let fields = value
+            .split_whitespace()
+            .map(|v| v.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        let [
+            rx_bytes,
+            rx_packets,
+            rx_errors,
+            _rx_dropped,
+            rx_fifo_errors,
+            rx_frame_errors,
+            _rx_compressed,
+            multicast,
+            tx_bytes,
+            tx_packets,
+            tx_errors,
+            tx_dropped,
+            _tx_fifo_errors,
+            collisions,
+            tx_carrier_errors,
+            ..,
+        ] = fields.as_slice()
+        else {
+            return None;
+        };
+        Some(Self {
+            rx_bytes: *rx_bytes,
+            rx_packets: *rx_packets,
+            rx_errors: *rx_errors,
+            rx_fifo_errors: *rx_fifo_errors,
+            rx_frame_errors: *rx_frame_errors,
+            multicast: *multicast,
+            tx_bytes: *tx_bytes,
+            tx_packets: *tx_packets,
+            tx_errors: *tx_errors,
+            tx_dropped: *tx_dropped,
+            collisions: *collisions,
+            tx_carrier_errors: *tx_carrier_errors,
+        })
+    }
+}
+
+// Equal to iproute2 `tnl_print_stats()`
+impl std::fmt::Display for CliTunnelStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "RX: Packets    Bytes        Errors CsumErrs OutOfSeq Mcasts"
+        )?;
+        writeln!(
+            f,
+            "    {:<10} {:<12} {:<6} {:<8} {:<8} {:<8}",
+            self.rx_packets,
+            self.rx_bytes,
+            self.rx_errors,
+            self.rx_frame_errors,
+            self.rx_fifo_errors,
+            self.multicast
+        )?;
+        writeln!(
+            f,
+            "TX: Packets    Bytes        Errors DeadLoop NoRoute  NoBufs"
+        )?;
+        write!(
+            f,
+            "    {:<10} {:<12} {:<6} {:<8} {:<8} {:<6}",
+            self.tx_packets,
+            self.tx_bytes,
+            self.tx_errors,
+            self.collisions,
+            self.tx_carrier_errors,
+            self.tx_dropped
+        )
+    }
+}
+
+fn parse_tunnel_info(
+    parm: &IpTunnelParm,
+    ifnames: &HashMap<u32, String>,
+) -> CliTunnelInfo {
+    let mut ret = CliTunnelInfo {
+        ifname: parm.name(),
+        mode: match parm.iph.protocol {
+            IPPROTO_IPIP => "ip/ip",
+            IPPROTO_GRE => "gre/ip",
+            IPPROTO_IPV6 => "ipv6/ip",
+            _ => "unknown/ip",
+        },
+        remote: parm.remote(),
+        local: parm.local(),
+        dev: (parm.link != 0)
+            .then(|| ifnames.get(&(parm.link as u32)).cloned())
+            .flatten(),
+        ttl: (parm.iph.ttl != 0).then_some(parm.iph.ttl),
+        tos_inherit: parm.iph.tos & 1 != 0,
+        tos: (parm.iph.tos & !1 != 0)
+            .then(|| format!("0x{:02x}", parm.iph.tos & !1)),
+        nopmtudisc: (parm.iph.frag_off & IP_DF == 0).then_some(()),
+        prefix_6rd: None,
+        prefix_len_6rd: None,
+        relay_prefix_6rd: None,
+        relay_prefix_len_6rd: None,
+        key: None,
+        ikey: None,
+        okey: None,
+        flags: None,
+        stats: None,
+    };
+
+    if parm.iph.protocol == IPPROTO_IPV6
+        && let Some(ip6rd) = get_6rd(&ret.ifname)
+        && let Some((prefix, prefix_len)) = ip6rd.prefix()
+    {
+        ret.prefix_6rd = Some(prefix);
+        ret.prefix_len_6rd = Some(prefix_len);
+        if let Some((prefix, prefix_len)) = ip6rd.relay_prefix() {
+            ret.relay_prefix_6rd = Some(prefix);
+            ret.relay_prefix_len_6rd = Some(prefix_len);
+        }
+    }
+
+    // Equal to iproute2 `tnl_print_gre_flags()`
+    let ikey = (parm.i_flags & GRE_KEY != 0).then(|| u32::from_be(parm.i_key));
+    let okey = (parm.o_flags & GRE_KEY != 0).then(|| u32::from_be(parm.o_key));
+    if ikey.is_some() && ikey == okey {
+        ret.key = ikey;
+    } else {
+        ret.ikey = ikey;
+        ret.okey = okey;
+    }
+    if parm.iph.protocol == IPPROTO_GRE {
+        ret.flags = Some(
+            [
+                parm.i_flags & GRE_SEQ,
+                parm.i_flags & GRE_CSUM,
+                parm.o_flags & GRE_SEQ,
+                parm.o_flags & GRE_CSUM,
+            ]
+            .iter()
+            .zip(GRE_FLAG_DESCRIPTIONS)
+            .filter_map(|(flag, (name, _))| (*flag != 0).then_some(name))
+            .collect(),
+        );
+    }
+    ret
+}
+
+/// The interface index and link layer type indexed by interface name
+async fn get_link_types(
+    handle: &rtnetlink::Handle,
+) -> Result<HashMap<String, (u32, u16)>, CliError> {
+    let mut ret = HashMap::new();
+    let mut links = handle.link().get().execute();
+    while let Some(link) = links.try_next().await? {
+        if let Some(name) = link.attributes.iter().find_map(|attr| {
+            if let LinkAttribute::IfName(name) = attr {
+                Some(name.to_string())
+            } else {
+                None
+            }
+        }) {
+            ret.insert(
+                name,
+                (link.header.index, u16::from(link.header.link_layer_type)),
+            );
+        }
+    }
+    Ok(ret)
+}
+
+/// Like iproute2 `do_tunnels_list()`, the interfaces listed in
+/// `/proc/net/dev` are queried for tunnel parameters.
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+    show_stats: bool,
+) -> Result<Vec<CliTunnelInfo>, CliError> {
+    let links = get_link_types(handle).await?;
+    let ifnames: HashMap<u32, String> = links
+        .iter()
+        .map(|(name, (index, _))| (*index, name.to_string()))
+        .collect();
+    let filter = parse_tunnel_opts(opts, None, &ifnames)?;
+    let filter_name = filter.name();
+
+    let content = std::fs::read_to_string(PROC_NET_DEV).map_err(|e| {
+        CliError::from(format!("Failed to open {PROC_NET_DEV}: {e}").as_str())
+    })?;
+
+    let mut ret = Vec::new();
+    // The first two lines are the header
+    for line in content.lines().skip(2) {
+        let Some((name, stats)) = line.split_once(':') else {
+            return Err(CliError::from(
+                "Wrong format for /proc/net/dev. Giving up.",
+            ));
+        };
+        let name = name.trim();
+        if !filter_name.is_empty() && filter_name != name {
+            continue;
+        }
+        let Some((_, link_type)) = links.get(name) else {
+            continue;
+        };
+        if !matches!(*link_type, ARPHRD_TUNNEL | ARPHRD_IPGRE | ARPHRD_SIT) {
+            continue;
+        }
+        let parm = match get_tunnel(name) {
+            Ok(parm) => parm,
+            Err(e) => {
+                log::debug!("{e}");
+                continue;
+            }
+        };
+        // Equal to iproute2 `ip_tunnel_parm_match()`
+        if (filter.link != 0 && filter.link != parm.link)
+            || (filter.iph.daddr != [0; 4]
+                && filter.iph.daddr != parm.iph.daddr)
+            || (filter.iph.saddr != [0; 4]
+                && filter.iph.saddr != parm.iph.saddr)
+            || (filter.i_key != 0 && filter.i_key != parm.i_key)
+        {
+            continue;
+        }
+        let mut info = parse_tunnel_info(&parm, &ifnames);
+        if show_stats {
+            info.stats = CliTunnelStats::parse(stats);
+        }
+        ret.push(info);
+    }
+    Ok(ret)
+}
@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use crate::tests::exec_cmd;
+
+fn cleanup_tunnel(tunnel_name: &str) {
+    exec_cmd(&["ip", "tunnel", "del", tunnel_name]);
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_tunnel;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_tunnel_add_change_del() {
+    let _lock = lock_net_test();
+    ip_rs_exec_cmd(&[
+        "tunnel",
+        "add",
+        "tntest-gre1",
+        "mode",
+        "gre",
+        "remote",
+        "198.18.81.1",
+        "local",
+        "198.18.81.2",
+        "ttl",
+        "64",
+        "ikey",
+        "10",
+        "okey",
+        "1.2.3.4",
+        "seq",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        let output = exec_cmd(&["ip", "tunnel", "show", "tntest-gre1"]);
+        pretty_assertions::assert_eq!(
+            output,
+            "tntest-gre1: gre/ip remote 198.18.81.1 local 198.18.81.2 ttl 64 \
+             ikey 10 okey 16909060\n  Drop packets out of sequence.\n  \
+             Sequence packets on output.\n"
+        );
+
+        ip_rs_exec_cmd(&[
+            "tunnel",
+            "change",
+            "tntest-gre1",
+            "remote",
+            "198.18.81.3",
+            "ttl",
+            "inherit",
+        ]);
+        let output = exec_cmd(&["ip", "tunnel", "show", "tntest-gre1"]);
+        assert!(output.starts_with(
+            "tntest-gre1: gre/ip remote 198.18.81.3 local 198.18.81.2 ttl \
+             inherit ikey 10 okey 16909060\n"
+        ));
+    });
+
+    if result.is_err() {
+        cleanup_tunnel("tntest-gre1");
+    }
+    assert!(result.is_ok());
+
+    ip_rs_exec_cmd(&["tunnel", "del", "tntest-gre1"]);
+    let output = exec_cmd(&["ip", "tunnel", "show"]);
+    assert!(!output.contains("tntest-gre1"));
+}
+
+#[test]
+fn test_tunnel_invalid_args() {
+    for (args, error) in [
+        (
+            &["tunnel", "add", "tntest-x", "mode", "foo"][..],
+            "Unknown tunnel mode \"foo\"",
+        ),
+        (
+            &["tunnel", "add", "tntest-x", "mode", "ipip", "key", "1"][..],
+            "Keys are not allowed with ipip and sit tunnels",
+        ),
+        (
+            &["tunnel", "add", "tntest-x", "mode", "gre", "mode", "sit"][..],
+            "You managed to ask for more than one tunnel mode.",
+        ),
+        (
+            &[
+                "tunnel",
+                "add",
+                "tntest-x",
+                "mode",
+                "gre",
+                "remote",
+                "239.1.1.1",
+            ][..],
+            "A broadcast tunnel requires a source address",
+        ),
+        (
+            &[
+                "tunnel",
+                "add",
+                "tntest-x",
+                "mode",
+                "gre",
+                "ttl",
+                "10",
+                "nopmtudisc",
+            ][..],
+            "ttl != 0 and nopmtudisc are incompatible",
+        ),
+        (
+            &["tunnel", "add", "tntest-x", "mode", "gre", "ttl", "300"][..],
+            "argument \"300\" is wrong: invalid TTL",
+        ),
+        (
+            &["tunnel", "add", "tntest-x", "tntest-y"][..],
+            "either \"name\" is duplicate, or \"tntest-y\" is a garbage.",
+        ),
+        (
+            &["tunnel", "add", "tntest-x"][..],
+            "cannot determine tunnel mode (ipip, gre, vti or sit)",
+        ),
+        (
+            &["tunnel", "add", "tntest-x", "mode", "gre", "remote", "foo"][..],
+            "an IP address is expected rather than \"foo\"",
+        ),
+        (
+            &["-6", "tunnel", "show"][..],
+            "Unsupported protocol family: 10",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_tunnel;
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, lock_net_test};
+
+#[test]
+fn test_tunnel_show() {
+    let _lock = lock_net_test();
+    exec_cmd(&[
+        "ip",
+        "tunnel",
+        "add",
+        "tntest-gre0",
+        "mode",
+        "gre",
+        "remote",
+        "198.18.80.1",
+        "local",
+        "198.18.80.2",
+        "ttl",
+        "64",
+        "key",
+        "5",
+        "csum",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "tunnel",
+        "add",
+        "tntest-ipip0",
+        "mode",
+        "ipip",
+        "remote",
+        "198.18.80.3",
+        "tos",
+        "inherit",
+        "nopmtudisc",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["tunnel"][..],
+            &["tunnel", "show"][..],
+            &["tunnel", "show", "tntest-gre0"][..],
+            &["tunnel", "list", "name", "tntest-ipip0"][..],
+            &["tunnel", "show", "remote", "198.18.80.3"][..],
+            &["tunnel", "show", "local", "198.18.80.2"][..],
+            &["tunnel", "show", "key", "5"][..],
+            &["-j", "tunnel", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // No traffic is sent through the test tunnel
+        let expected_output =
+            exec_cmd(&["ip", "-s", "tunnel", "show", "tntest-gre0"]);
+        let our_output =
+            ip_rs_exec_cmd(&["-s", "tunnel", "show", "tntest-gre0"]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+
+    cleanup_tunnel("tntest-gre0");
+    cleanup_tunnel("tntest-ipip0");
+    assert!(result.is_ok());
+}
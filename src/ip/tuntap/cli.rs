@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+use super::{
+    modify::{TuntapAction, handle_modify},
+    show::{CliTuntapInfo, handle_show},
+};
+use crate::CliError;
+
+pub(crate) struct TuntapCommand;
+
+impl TuntapCommand {
+    pub(crate) const CMD: &'static str = "tuntap";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("manage TUN/TAP devices")
+            .alias("tunta")
+            .alias("tunt")
+            .alias("tap")
+            .alias("ta")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("add")
+                    .about("add persistent TUN/TAP device")
+                    .alias("ad")
+                    .alias("a")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("delete")
+                    .about("delete persistent TUN/TAP device")
+                    .alias("delet")
+                    .alias("dele")
+                    .alias("del")
+                    .alias("de")
+                    .alias("d")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show TUN/TAP devices")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("lst")
+                    .alias("ls")
+                    .alias("l")
+                    // Like iproute2, the arguments are accepted but ignored
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) fn handle(
+        matches: &clap::ArgMatches,
+    ) -> Result<Option<Vec<CliTuntapInfo>>, CliError> {
+        for (name, action) in
+            [("add", TuntapAction::Add), ("delete", TuntapAction::Delete)]
+        {
+            if let Some(matches) = matches.subcommand_matches(name) {
+                let opts: Vec<&str> = matches
+                    .get_many::<String>("options")
+                    .unwrap_or_default()
+                    .map(String::as_str)
+                    .collect();
+                handle_modify(&opts, action)?;
+                return Ok(None);
+            }
+        }
+
+        let matches = matches.subcommand_matches("show").unwrap_or(matches);
+        handle_show(matches.get_flag("DETAILS")).map(Into::into)
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+use std::os::fd::AsRawFd;
+
+use iproute_rs::{CliError, next_opt};
+use nix::{
+    errno::Errno,
+    libc,
+    unistd::{Group, User},
+};
+
+use crate::link::check_ifname;
+
+const TUN_DEV: &str = "/dev/net/tun";
+const IFNAMSIZ: usize = 16;
+
+// Equal to the flags of `linux/if_tun.h`
+pub(super) const IFF_TUN: u16 = 0x0001;
+pub(super) const IFF_TAP: u16 = 0x0002;
+pub(super) const IFF_MULTI_QUEUE: u16 = 0x0100;
+pub(super) const IFF_PERSIST: u16 = 0x0800;
+pub(super) const IFF_NO_PI: u16 = 0x1000;
+/// Shares the bit of `IFF_NO_PI` but only used for `tun_flags` of sysfs
+pub(super) const IFF_NOFILTER: u16 = 0x1000;
+pub(super) const IFF_ONE_QUEUE: u16 = 0x2000;
+pub(super) const IFF_VNET_HDR: u16 = 0x4000;
+const IFF_TUN_EXCL: u16 = 0x8000;
+
+const TUNSETIFF: libc::c_ulong =
+    nix::request_code_write!(b'T', 202, size_of::<libc::c_int>())
+        as libc::c_ulong;
+const TUNSETPERSIST: libc::c_ulong =
+    nix::request_code_write!(b'T', 203, size_of::<libc::c_int>())
+        as libc::c_ulong;
+const TUNSETOWNER: libc::c_ulong =
+    nix::request_code_write!(b'T', 204, size_of::<libc::c_int>())
+        as libc::c_ulong;
+const TUNSETGROUP: libc::c_ulong =
+    nix::request_code_write!(b'T', 206, size_of::<libc::c_int>())
+        as libc::c_ulong;
+
+nix::ioctl_write_ptr_bad!(tunsetiff, TUNSETIFF, libc::ifreq);
+nix::ioctl_write_int_bad!(tunsetpersist, TUNSETPERSIST);
+nix::ioctl_write_int_bad!(tunsetowner, TUNSETOWNER);
+nix::ioctl_write_int_bad!(tunsetgroup, TUNSETGROUP);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TuntapAction {
+    Add,
+    Delete,
+}
+
+/// The arguments of `ip tuntap add` and `ip tuntap del` following the
+/// grammar of iproute2 `parse_args()`
+#[derive(Debug, Default)]
+struct TuntapOpts {
+    name: String,
+    flags: u16,
+    user: Option<u32>,
+    group: Option<u32>,
+}
+
+impl TuntapOpts {
+    fn parse(opts: &[&str], action: TuntapAction) -> Result<Self, CliError> {
+        let mut ret = Self {
+            flags: IFF_NO_PI,
+            ..Default::default()
+        };
+        let mut opts = opts.iter();
+
+        while let Some(opt) = opts.next() {
+            match *opt {
+                "mode" => {
+                    let mode = next_opt(&mut opts)?;
+                    let (flag, other) = match mode {
+                        "tun" => (IFF_TUN, IFF_TAP),
+                        "tap" => (IFF_TAP, IFF_TUN),
+                        _ => {
+                            return Err(CliError::from(
+                                format!("Unknown tunnel mode \"{mode}\"")
+                                    .as_str(),
+                            ));
+                        }
+                    };
+                    if ret.flags & other != 0 {
+                        return Err(CliError::from(
+                            "You managed to ask for more than one tunnel mode.",
+                        ));
+                    }
+                    ret.flags |= flag;
+                }
+                // Like iproute2, the owner is only used by `add`
+                "user" if action == TuntapAction::Add => {
+                    ret.user = Some(parse_user(next_opt(&mut opts)?)?);
+                }
+                "group" if action == TuntapAction::Add => {
+                    ret.group = Some(parse_group(next_opt(&mut opts)?)?);
+                }
+                "pi" => ret.flags &= !IFF_NO_PI,
+                "one_queue" => ret.flags |= IFF_ONE_QUEUE,
+                "vnet_hdr" => ret.flags |= IFF_VNET_HDR,
+                "multi_queue" => ret.flags |= IFF_MULTI_QUEUE,
+                "dev" => {
+                    let name = next_opt(&mut opts)?;
+                    check_ifname(name, "\"dev\"")?;
+                    ret.name = name.to_string();
+                }
+                _ => {
+                    let name = if *opt == "name" {
+                        next_opt(&mut opts)?
+                    } else {
+                        opt
+                    };
+                    if !ret.name.is_empty() {
+                        return Err(CliError::from(
+                            format!(
+                                "either \"name\" is duplicate, or \"{name}\" \
+                                 is a garbage."
+                            )
+                            .as_str(),
+                        ));
+                    }
+                    check_ifname(name, "\"name\"")?;
+                    ret.name = name.to_string();
+                }
+            }
+        }
+
+        if ret.flags & (IFF_TUN | IFF_TAP) == 0 {
+            return Err(CliError::from("You failed to specify a tunnel mode"));
+        }
+        Ok(ret)
+    }
+}
+
+// Like iproute2, the user could be name or UID
+fn parse_user(value: &str) -> Result<u32, CliError> {
+    if let Ok(uid) = value.parse::<u32>() {
+        return Ok(uid);
+    }
+    match User::from_name(value) {
+        Ok(Some(user)) => Ok(user.uid.as_raw()),
+        _ => Err(CliError::from(format!("invalid user \"{value}\"").as_str())),
+    }
+}
+
+fn parse_group(value: &str) -> Result<u32, CliError> {
+    if let Ok(gid) = value.parse::<u32>() {
+        return Ok(gid);
+    }
+    match Group::from_name(value) {
+        Ok(Some(group)) => Ok(group.gid.as_raw()),
+        _ => Err(CliError::from(
+            format!("invalid group \"{value}\"").as_str(),
+        )),
+    }
+}
+
+fn ioctl_err(name: &str) -> impl FnOnce(Errno) -> CliError + '_ {
+    move |e| CliError::from(format!("ioctl({name}): {}", e.desc()).as_str())
+}
+
+/// Equal to iproute2 `tap_add_ioctl()` and `tap_del_ioctl()`, the device
+/// is attached then its persistence is changed.
+pub(crate) fn handle_modify(
+    opts: &[&str],
+    action: TuntapAction,
+) -> Result<(), CliError> {
+    let opts = TuntapOpts::parse(opts, action)?;
+
+    let tun = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TUN_DEV)
+        .map_err(|e| {
+            CliError::from(
+                format!(
+                    "open: {}",
+                    Errno::from_raw(e.raw_os_error().unwrap_or_default())
+                        .desc()
+                )
+                .as_str(),
+            )
+        })?;
+    let fd = tun.as_raw_fd();
+
+    // SAFETY: `ifreq` is plain old data which is valid when zeroed
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in ifr
+        .ifr_name
+        .iter_mut()
+        .zip(opts.name.as_bytes().iter().take(IFNAMSIZ - 1))
+    {
+        *dst = *src as libc::c_char;
+    }
+    let mut flags = opts.flags;
+    // The existing device should not be attached when adding
+    if action == TuntapAction::Add {
+        flags |= IFF_TUN_EXCL;
+    }
+    ifr.ifr_ifru.ifru_flags = flags as libc::c_short;
+
+    // SAFETY: the `ifreq` outlives the ioctl
+    unsafe { tunsetiff(fd, &ifr) }.map_err(ioctl_err("TUNSETIFF"))?;
+    if let Some(uid) = opts.user {
+        // SAFETY: the argument is passed by value
+        unsafe { tunsetowner(fd, uid as libc::c_int) }
+            .map_err(ioctl_err("TUNSETOWNER"))?;
+    }
+    if let Some(gid) = opts.group {
+        // SAFETY: the argument is passed by value
+        unsafe { tunsetgroup(fd, gid as libc::c_int) }
+            .map_err(ioctl_err("TUNSETGROUP"))?;
+    }
+    // SAFETY: the argument is passed by value
+    unsafe {
+        tunsetpersist(fd, libc::c_int::from(action == TuntapAction::Add))
+    }
+    .map_err(ioctl_err("TUNSETPERSIST"))?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use iproute_rs::{CanDisplay, CanOutput, CliColor, CliError, write_with_color};
+use serde::Serialize;
+
+use super::modify::{
+    IFF_MULTI_QUEUE, IFF_NO_PI, IFF_NOFILTER, IFF_ONE_QUEUE, IFF_PERSIST,
+    IFF_TAP, IFF_TUN, IFF_VNET_HDR,
+};
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+const TUN_DEV: &str = "/dev/net/tun";
+
+/// Equal to iproute2 `print_tuntap()`
+#[derive(Serialize)]
+pub(crate) struct CliTuntapInfo {
+    ifname: String,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<u32>,
+    /// Only shown with `-d`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processes: Option<Vec<CliTuntapProcess>>,
+    #[serde(skip)]
+    ifindex: u32,
+}
+
+#[derive(Serialize, Clone)]
+struct CliTuntapProcess {
+    name: String,
+    pid: u32,
+}
+
+impl std::fmt::Display for CliTuntapInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(f, CliColor::IfaceName, "{}", self.ifname)?;
+        write!(f, ":")?;
+        for flag in &self.flags {
+            write!(f, " {flag}")?;
+        }
+        if let Some(user) = self.user {
+            write!(f, " user {user}")?;
+        }
+        if let Some(group) = self.group {
+            write!(f, " group {group}")?;
+        }
+        if let Some(processes) = &self.processes {
+            write!(f, "\n\tAttached to processes:")?;
+            for process in processes {
+                write!(f, "{}({})", process.name, process.pid)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliTuntapInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliTuntapInfo {}
+
+// Equal to iproute2 `print_flags()`
+fn parse_tun_flags(flags: u16) -> Vec<String> {
+    let mut ret = Vec::new();
+    for (present, name) in [
+        (flags & IFF_TUN != 0, "tun"),
+        (flags & IFF_TAP != 0, "tap"),
+        (flags & IFF_NO_PI == 0, "pi"),
+        (flags & IFF_ONE_QUEUE != 0, "one_queue"),
+        (flags & IFF_MULTI_QUEUE != 0, "multi_queue"),
+        (flags & IFF_VNET_HDR != 0, "vnet_hdr"),
+        (flags & IFF_PERSIST != 0, "persist"),
+        (flags & IFF_NOFILTER == 0, "filter"),
+    ] {
+        if present {
+            ret.push(name.to_string());
+        }
+    }
+    let unknown = flags
+        & !(IFF_TUN
+            | IFF_TAP
+            | IFF_NO_PI
+            | IFF_ONE_QUEUE
+            | IFF_MULTI_QUEUE
+            | IFF_VNET_HDR
+            | IFF_PERSIST
+            | IFF_NOFILTER);
+    if unknown != 0 {
+        ret.push(format!("{unknown:#x}"));
+    }
+    ret
+}
+
+// Equal to iproute2 `read_prop()`, the value could be hex or decimal
+fn read_prop(ifname: &str, prop: &str) -> Option<i64> {
+    let content =
+        std::fs::read_to_string(format!("{SYS_CLASS_NET}/{ifname}/{prop}"))
+            .ok()?;
+    let value = content.trim();
+    if let Some(hex) = value.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Like iproute2 `show_processes()`, the processes holding `/dev/net/tun`
+/// are indexed by the interface name found in their `fdinfo`.
+fn get_tun_processes() -> HashMap<String, Vec<CliTuntapProcess>> {
+    let mut ret: HashMap<String, Vec<CliTuntapProcess>> = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return ret;
+    };
+    let mut pids: Vec<u32> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .filter(|pid| *pid != std::process::id())
+        .collect();
+    pids.sort_unstable();
+
+    for pid in pids {
+        let Ok(fds) = std::fs::read_dir(format!("/proc/{pid}/fd")) else {
+            continue;
+        };
+        for fd in fds.filter_map(|fd| fd.ok()) {
+            if std::fs::read_link(fd.path())
+                .map(|path| path.as_os_str() != TUN_DEV)
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            let Ok(fdinfo) = std::fs::read_to_string(format!(
+                "/proc/{pid}/fdinfo/{}",
+                fd.file_name().to_string_lossy()
+            )) else {
+                continue;
+            };
+            let Some(ifname) = fdinfo.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key == "iff").then(|| value.trim().to_string())
+            }) else {
+                continue;
+            };
+            let name = std::fs::read_to_string(format!("/proc/{pid}/comm"))
+                .map(|comm| comm.trim_end().to_string())
+                .unwrap_or_else(|_| "<NULL>".to_string());
+            ret.entry(ifname)
+                .or_default()
+                .push(CliTuntapProcess { name, pid });
+        }
+    }
+    ret
+}
+
+/// Like iproute2, the TUN/TAP devices are those with `tun_flags` in sysfs,
+/// listed in the order of interface index.
+pub(crate) fn handle_show(
+    show_details: bool,
+) -> Result<Vec<CliTuntapInfo>, CliError> {
+    let entries = std::fs::read_dir(SYS_CLASS_NET).map_err(|e| {
+        CliError::from(format!("Failed to read {SYS_CLASS_NET}: {e}").as_str())
+    })?;
+    let processes = if show_details {
+        get_tun_processes()
+    } else {
+        HashMap::new()
+    };
+
+    let mut ret = Vec::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let ifname = entry.file_name().to_string_lossy().to_string();
+        let (Some(flags), Some(owner), Some(group)) = (
+            read_prop(&ifname, "tun_flags"),
+            read_prop(&ifname, "owner"),
+            read_prop(&ifname, "group"),
+        ) else {
+            continue;
+        };
+        ret.push(CliTuntapInfo {
+            ifindex: read_prop(&ifname, "ifindex").unwrap_or_default() as u32,
+            flags: parse_tun_flags(flags as u16),
+            // The `-1` stands for no owner
+            user: u32::try_from(owner).ok(),
+            group: u32::try_from(group).ok(),
+            processes: show_details
+                .then(|| processes.get(&ifname).cloned().unwrap_or_default()),
+            ifname,
+        });
+    }
+    ret.sort_by_key(|tuntap| tuntap.ifindex);
+    Ok(ret)
+}
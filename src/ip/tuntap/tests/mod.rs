@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod modify;
+#[cfg(test)]
+mod show;
+
+use crate::tests::exec_cmd;
+
+// The device flags like `multi_queue` should match when deleting
+fn cleanup_tuntap(ifname: &str, opts: &[&str]) {
+    exec_cmd(&[&["ip", "tuntap", "del", "dev", ifname], opts].concat());
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_tuntap;
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output, lock_net_test,
+};
+
+#[test]
+fn test_tuntap_add_del() {
+    let _lock = lock_net_test();
+    ip_rs_exec_cmd(&[
+        "tuntap",
+        "add",
+        "tttest-tap1",
+        "mode",
+        "tap",
+        "user",
+        "root",
+        "group",
+        "0",
+        "vnet_hdr",
+    ]);
+    ip_rs_exec_cmd(&["tuntap", "add", "mode", "tun", "name", "tttest-tun1"]);
+
+    let result = std::panic::catch_unwind(|| {
+        let output = exec_cmd(&["ip", "tuntap", "show"]);
+        assert!(
+            output
+                .contains("tttest-tap1: tap vnet_hdr persist user 0 group 0\n")
+        );
+        assert!(output.contains("tttest-tun1: tun persist\n"));
+
+        // The device already exists
+        let output = ip_rs_exec_cmd_output(&[
+            "tuntap",
+            "add",
+            "dev",
+            "tttest-tun1",
+            "mode",
+            "tun",
+        ]);
+        assert!(!output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stderr)
+                .contains("ioctl(TUNSETIFF): Device or resource busy")
+        );
+    });
+
+    if result.is_err() {
+        cleanup_tuntap("tttest-tap1", &["mode", "tap"]);
+        cleanup_tuntap("tttest-tun1", &["mode", "tun"]);
+    }
+    assert!(result.is_ok());
+
+    ip_rs_exec_cmd(&["tuntap", "del", "tttest-tap1", "mode", "tap"]);
+    ip_rs_exec_cmd(&["tuntap", "delete", "dev", "tttest-tun1", "mode", "tun"]);
+    let output = exec_cmd(&["ip", "tuntap", "show"]);
+    assert!(!output.contains("tttest-tap1"));
+    assert!(!output.contains("tttest-tun1"));
+}
+
+#[test]
+fn test_tuntap_invalid_args() {
+    for (args, error) in [
+        (
+            &["tuntap", "add", "tttest-x"][..],
+            "You failed to specify a tunnel mode",
+        ),
+        (
+            &["tuntap", "add", "tttest-x", "mode", "tun", "mode", "tap"][..],
+            "You managed to ask for more than one tunnel mode.",
+        ),
+        (
+            &["tuntap", "add", "tttest-x", "mode", "foo"][..],
+            "Unknown tunnel mode \"foo\"",
+        ),
+        (
+            &["tuntap", "add", "mode", "tun", "user", "tttest-nouser"][..],
+            "invalid user \"tttest-nouser\"",
+        ),
+        (
+            &["tuntap", "add", "mode", "tun", "group", "tttest-nogroup"][..],
+            "invalid group \"tttest-nogroup\"",
+        ),
+        (
+            &["tuntap", "add", "tttest-x", "tttest-y", "mode", "tun"][..],
+            "either \"name\" is duplicate, or \"tttest-y\" is a garbage.",
+        ),
+        (
+            &["tuntap", "add", "mode", "tun", "name", "tttest-0123456789"][..],
+            "argument \"tttest-0123456789\" is wrong: \"name\" not a valid \
+             ifname",
+        ),
+        (
+            &["tuntap", "del", "tttest-x", "mode", "tun", "user", "0"][..],
+            "either \"name\" is duplicate, or \"user\" is a garbage.",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
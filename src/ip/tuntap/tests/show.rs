@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT
+
+use super::cleanup_tuntap;
+use crate::tests::{exec_cmd, ip_rs_exec_cmd, lock_net_test};
+
+#[test]
+fn test_tuntap_show() {
+    let _lock = lock_net_test();
+    exec_cmd(&[
+        "ip",
+        "tuntap",
+        "add",
+        "dev",
+        "tttest-tap0",
+        "mode",
+        "tap",
+        "user",
+        "0",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "tuntap",
+        "add",
+        "dev",
+        "tttest-tun0",
+        "mode",
+        "tun",
+        "pi",
+        "one_queue",
+        "multi_queue",
+        "vnet_hdr",
+        "group",
+        "0",
+    ]);
+
+    let result = std::panic::catch_unwind(|| {
+        for args in [
+            &["tuntap"][..],
+            &["tuntap", "show"][..],
+            &["tuntap", "list"][..],
+            &["-j", "tuntap", "show"][..],
+        ] {
+            let expected_output = exec_cmd(&[&["ip"], args].concat());
+            let our_output = ip_rs_exec_cmd(args);
+
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        // The iproute2 generates invalid JSON for the attached processes,
+        // hence only compare the text output.
+        let expected_output = exec_cmd(&["ip", "-d", "tuntap", "show"]);
+        let our_output = ip_rs_exec_cmd(&["-d", "tuntap", "show"]);
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+
+    cleanup_tuntap("tttest-tap0", &["mode", "tap"]);
+    cleanup_tuntap("tttest-tun0", &["mode", "tun", "multi_queue"]);
+    assert!(result.is_ok());
+}
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MIT
+
+use std::{io::Write, path::Path};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use nix::{
+    errno::Errno,
+    mount::{MsFlags, mount},
+};
+use serde::Serialize;
+
+use super::show::get_vrf;
+use crate::netns::identify_netns;
+
+/// Equal to iproute2 `MNT_CGRP2_PATH`, the cgroup2 is mounted here for
+/// iproute2's use if not mounted yet.
+const MNT_CGRP2_PATH: &str = "/var/run/cgroup2";
+
+/// Equal to iproute2 `CGRP_PROC_FILE`
+pub(crate) const CGRP_PROC_FILE: &str = "cgroup.procs";
+
+fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+fn io_err_desc(e: &std::io::Error) -> &'static str {
+    Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+}
+
+/// Equal to iproute2 `find_cgroup2_mount()` with `do_mount` set
+pub(crate) fn find_cgroup2_mount() -> Result<String, CliError> {
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace().skip(1);
+        if let (Some(path), Some("cgroup2")) = (fields.next(), fields.next()) {
+            return Ok(path.to_string());
+        }
+    }
+
+    std::fs::create_dir_all(MNT_CGRP2_PATH)
+        .map_err(|_| CliError::from("Failed to setup vrf cgroup2 directory"))?;
+    match mount(
+        Some("none"),
+        MNT_CGRP2_PATH,
+        Some("cgroup2"),
+        MsFlags::empty(),
+        None::<&str>,
+    ) {
+        // The EBUSY means already mounted
+        Ok(()) | Err(Errno::EBUSY) => Ok(MNT_CGRP2_PATH.to_string()),
+        Err(Errno::ENODEV) => Err(CliError::from(
+            "Failed to mount cgroup2. Are CGROUPS enabled in your kernel?",
+        )),
+        Err(e) => Err(CliError::from(
+            format!("Failed to mount cgroup2: {}", e.desc()).as_str(),
+        )),
+    }
+}
+
+/// Equal to iproute2 `ipvrf_get_netns()`, the name of current network
+/// namespace suffixed with `-ns`, or empty for unnamed network namespace.
+pub(crate) fn get_netns_dir_name() -> Result<String, CliError> {
+    let names = identify_netns(&[]).map_err(|e| {
+        CliError::from(
+            format!("Failed to get name of network namespace: {}", e.msg)
+                .as_str(),
+        )
+    })?;
+    Ok(names
+        .into_iter()
+        .next()
+        .map(|name| format!("{name}-ns"))
+        .unwrap_or_default())
+}
+
+/// Equal to iproute2 `vrf_path()`, the path of controller-less cgroup of
+/// current process relative to the cgroup2 mount, with `/vrf/NAME` removed.
+pub(crate) fn get_vrf_base_path() -> Result<String, CliError> {
+    let content =
+        std::fs::read_to_string("/proc/self/cgroup").map_err(|e| {
+            CliError::from(
+                format!("Failed to get base cgroup path: {}", io_err_desc(&e))
+                    .as_str(),
+            )
+        })?;
+    let Some(path) = content
+        .lines()
+        .find_map(|line| line.find("::/").map(|pos| &line[pos + 2..]))
+    else {
+        return Ok(String::new());
+    };
+    let path = match path.find("/vrf") {
+        Some(pos) => &path[..pos],
+        None => path,
+    };
+    Ok(if path == "/" {
+        String::new()
+    } else {
+        path.to_string()
+    })
+}
+
+/// Equal to iproute2 `ipvrf_identify()`, find the VRF of the process from
+/// its controller-less cgroup `PATH/vrf/NAME`. Current process is used if
+/// PID omitted.
+pub(crate) fn handle_identify(opts: &[&str]) -> Result<Vec<String>, CliError> {
+    let pid = match opts {
+        [] => std::process::id(),
+        [pid] => pid.parse().map_err(|_| invarg(pid, "Invalid pid"))?,
+        [_, extra, ..] => {
+            return Err(invarg(extra, "Extra arguments specified"));
+        }
+    };
+    let content = std::fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .map_err(|e| {
+            CliError::from(
+                format!(
+                    "Failed to lookup vrf association: {}",
+                    io_err_desc(&e)
+                )
+                .as_str(),
+            )
+        })?;
+    Ok(content
+        .lines()
+        .filter(|line| line.contains("::/"))
+        .find_map(|line| line.find("/vrf/").map(|pos| &line[pos + 5..]))
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect())
+}
+
+/// Equal to iproute2 `read_cgroup_pids()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliVrfPid {
+    pid: String,
+    comm: String,
+}
+
+impl std::fmt::Display for CliVrfPid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>5}  {}", self.pid, self.comm)
+    }
+}
+
+impl CanDisplay for CliVrfPid {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVrfPid {}
+
+// Equal to iproute2 `get_command_name()`
+fn get_command_name(pid: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{pid}/comm"))
+        .ok()
+        .map(|comm| comm.trim_end_matches('\n').to_string())
+}
+
+// Equal to iproute2 `read_cgroup_pids()`, read `PATH/vrf/NAME/cgroup.procs`
+fn read_cgroup_pids(cgroup_path: &Path, name: &str) -> Vec<CliVrfPid> {
+    let Ok(content) = std::fs::read_to_string(
+        cgroup_path.join("vrf").join(name).join(CGRP_PROC_FILE),
+    ) else {
+        // No cgroup file, nothing to show
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(|pid| CliVrfPid {
+            pid: pid.to_string(),
+            comm: get_command_name(pid)
+                .unwrap_or_else(|| "<terminated?>".to_string()),
+        })
+        .collect()
+}
+
+// Equal to iproute2 `recurse_dir()`, walk the cgroup2 mount looking for
+// `PATH[/NETNS]/vrf/NAME`.
+fn recurse_dir(
+    base_path: &Path,
+    name: &str,
+    netns: &str,
+    pids: &mut Vec<CliVrfPid>,
+) -> Result<(), CliError> {
+    let entries = std::fs::read_dir(base_path)
+        .map_err(|e| CliError::from(io_err_desc(&e)))?;
+    for entry in entries.flatten() {
+        if entry.file_name() == "vrf" {
+            // Found a `vrf` directory, only dump the pids if it is for
+            // current network namespace
+            if netns.is_empty()
+                || base_path.file_name().is_some_and(|dir| dir == netns)
+            {
+                pids.extend(read_cgroup_pids(base_path, name));
+            }
+            continue;
+        }
+        // The `file_type()` does not follow symbolic link like `lstat()`
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            recurse_dir(&entry.path(), name, netns, pids)?;
+        }
+    }
+    Ok(())
+}
+
+/// Equal to iproute2 `ipvrf_pids()`
+pub(crate) async fn handle_pids(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<Vec<CliVrfPid>, CliError> {
+    let [name] = opts else {
+        return Err(CliError::from("Invalid arguments"));
+    };
+    if get_vrf(handle, name).await.is_none() {
+        return Err(CliError::from("Invalid VRF name"));
+    }
+    let mnt = find_cgroup2_mount()?;
+    let netns = get_netns_dir_name()?;
+
+    let mut ret = Vec::new();
+    recurse_dir(Path::new(&mnt), name, &netns, &mut ret)?;
+    Ok(ret)
+}
+
+/// Write current process into the cgroup, like iproute2 `vrf_switch()`
+pub(crate) fn join_cgroup(path: &Path) -> Result<(), CliError> {
+    let mut fd = std::fs::OpenOptions::new()
+        .append(true)
+        .open(path.join(CGRP_PROC_FILE))
+        .map_err(|e| {
+            CliError::from(
+                format!(
+                    "Failed to open cgroups.procs file: {}.",
+                    io_err_desc(&e)
+                )
+                .as_str(),
+            )
+        })?;
+    write!(fd, "{}", std::process::id())
+        .map_err(|_| CliError::from("Failed to join cgroup"))
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput};
+use serde::Serialize;
+
+use super::{
+    cgroup::{CliVrfPid, handle_identify, handle_pids},
+    exec::handle_exec,
+    show::{
+        CliVrfInfo, CliVrfTable, gen_vrf_list_string, handle_show,
+        handle_show_table,
+    },
+};
+use crate::{CliError, context::NetlinkContext};
+
+const VRF_USAGE: &str = "\
+Usage:\tip vrf show [NAME] ...
+\tip vrf exec [NAME] cmd ...
+\tip vrf identify [PID]
+\tip vrf pids [NAME]";
+
+fn usage() -> CliError {
+    CliError::from(VRF_USAGE)
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliVrfOutput {
+    List(Vec<CliVrfInfo>),
+    Table(CliVrfTable),
+    /// Name of the VRF found by `ip vrf identify`
+    Names(Vec<String>),
+    Pids(Vec<CliVrfPid>),
+}
+
+impl CanDisplay for CliVrfOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::List(vrfs) => gen_vrf_list_string(vrfs),
+            Self::Table(table) => table.gen_string(),
+            Self::Names(names) => names.join("\n"),
+            Self::Pids(pids) => pids.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::Pids(pids) => pids.gen_oneline_string(),
+            _ => self.gen_string(),
+        }
+    }
+}
+
+impl CanOutput for CliVrfOutput {}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+pub(crate) struct VrfCommand;
+
+impl VrfCommand {
+    pub(crate) const CMD: &'static str = "vrf";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("VRF device management")
+            .subcommand_required(false)
+            .subcommand(
+                clap::Command::new("show")
+                    .about("show VRF devices")
+                    .alias("sho")
+                    .alias("sh")
+                    .alias("s")
+                    .alias("list")
+                    .alias("lis")
+                    .alias("li")
+                    .alias("l")
+                    .alias("lst")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("exec")
+                    .about("run command with sockets bound to VRF")
+                    .alias("exe")
+                    .alias("ex")
+                    .alias("e")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .allow_hyphen_values(true)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("identify")
+                    .about("show the VRF of process")
+                    .alias("identif")
+                    .alias("identi")
+                    .alias("ident")
+                    .alias("iden")
+                    .alias("ide")
+                    .alias("id")
+                    .alias("i")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+            .subcommand(
+                clap::Command::new("pids")
+                    .about("show processes bound to VRF")
+                    .alias("pid")
+                    .alias("pi")
+                    .alias("p")
+                    .arg(
+                        clap::Arg::new("options")
+                            .action(clap::ArgAction::Append)
+                            .trailing_var_arg(true),
+                    ),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliVrfOutput>, CliError> {
+        let handle = ctx.handle();
+        match matches.subcommand() {
+            Some(("exec", matches)) => {
+                handle_exec(handle, &get_opts(matches)).await?;
+                Ok(None)
+            }
+            Some(("identify", matches)) => handle_identify(&get_opts(matches))
+                .map(CliVrfOutput::Names)
+                .map(Some),
+            Some(("pids", matches)) => handle_pids(handle, &get_opts(matches))
+                .await
+                .map(CliVrfOutput::Pids)
+                .map(Some),
+            Some(("show", matches)) => match get_opts(matches).as_slice() {
+                [] => {
+                    handle_show(handle).await.map(CliVrfOutput::List).map(Some)
+                }
+                [name] => handle_show_table(handle, name)
+                    .await
+                    .map(CliVrfOutput::Table)
+                    .map(Some),
+                _ => Err(usage()),
+            },
+            _ => handle_show(handle).await.map(CliVrfOutput::List).map(Some),
+        }
+    }
+}
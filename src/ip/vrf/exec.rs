@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    path::{Path, PathBuf},
+};
+
+use iproute_rs::CliError;
+use nix::{errno::Errno, libc};
+
+use super::{
+    cgroup::{
+        find_cgroup2_mount, get_netns_dir_name, get_vrf_base_path, join_cgroup,
+    },
+    show::get_vrf,
+};
+use crate::netns::exec_cmd;
+
+// Equal to `linux/bpf.h`
+const BPF_PROG_LOAD: libc::c_int = 5;
+const BPF_PROG_ATTACH: libc::c_int = 8;
+const BPF_PROG_TYPE_CGROUP_SOCK: u32 = 9;
+const BPF_CGROUP_INET_SOCK_CREATE: u32 = 2;
+
+// Like iproute2 `bpf_log_buf`
+const BPF_LOG_BUF_SIZE: usize = 256 * 1024;
+
+/// Equal to `struct bpf_insn` of `linux/bpf.h`
+#[repr(C)]
+struct BpfInsn {
+    code: u8,
+    /// The `dst_reg` in lower 4 bits and `src_reg` in upper 4 bits
+    regs: u8,
+    off: i16,
+    imm: i32,
+}
+
+impl BpfInsn {
+    const fn new(code: u8, dst: u8, src: u8, off: i16, imm: i32) -> Self {
+        Self {
+            code,
+            regs: (src << 4) | dst,
+            off,
+            imm,
+        }
+    }
+}
+
+/// The `BPF_PROG_LOAD` part of `union bpf_attr` in `linux/bpf.h`
+#[repr(C)]
+#[derive(Default)]
+struct BpfProgLoadAttr {
+    prog_type: u32,
+    insn_cnt: u32,
+    insns: u64,
+    license: u64,
+    log_level: u32,
+    log_size: u32,
+    log_buf: u64,
+    kern_version: u32,
+    prog_flags: u32,
+}
+
+/// The `BPF_PROG_ATTACH` part of `union bpf_attr` in `linux/bpf.h`
+#[repr(C)]
+#[derive(Default)]
+struct BpfProgAttachAttr {
+    target_fd: u32,
+    attach_bpf_fd: u32,
+    attach_type: u32,
+    attach_flags: u32,
+}
+
+fn bpf<T>(cmd: libc::c_int, attr: &T) -> Result<libc::c_long, Errno> {
+    // SAFETY: The `attr` is a `#[repr(C)]` prefix of `union bpf_attr` which
+    // is valid during the syscall.
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_bpf,
+            cmd,
+            attr as *const T,
+            std::mem::size_of::<T>(),
+        )
+    };
+    if ret < 0 { Err(Errno::last()) } else { Ok(ret) }
+}
+
+/// Equal to iproute2 `prog_load()`, load BPF program setting
+/// `sk_bound_dev_if` of sockets to the VRF device.
+fn prog_load(ifindex: u32) -> Result<OwnedFd, CliError> {
+    // The `bound_dev_if` is the first member of `struct bpf_sock`
+    let prog = [
+        // BPF_MOV64_REG(BPF_REG_6, BPF_REG_1)
+        BpfInsn::new(0xbf, 6, 1, 0, 0),
+        // BPF_MOV64_IMM(BPF_REG_3, idx)
+        BpfInsn::new(0xb7, 3, 0, 0, ifindex as i32),
+        // BPF_MOV64_IMM(BPF_REG_2, offsetof(struct bpf_sock, bound_dev_if))
+        BpfInsn::new(0xb7, 2, 0, 0, 0),
+        // BPF_STX_MEM(BPF_W, BPF_REG_1, BPF_REG_3,
+        //             offsetof(struct bpf_sock, bound_dev_if))
+        BpfInsn::new(0x63, 1, 3, 0, 0),
+        // BPF_MOV64_IMM(BPF_REG_0, 1), the verdict
+        BpfInsn::new(0xb7, 0, 0, 0, 1),
+        // BPF_EXIT_INSN()
+        BpfInsn::new(0x95, 0, 0, 0, 0),
+    ];
+    let license = c"GPL";
+    let mut log_buf = vec![0u8; BPF_LOG_BUF_SIZE];
+    let attr = BpfProgLoadAttr {
+        prog_type: BPF_PROG_TYPE_CGROUP_SOCK,
+        insn_cnt: prog.len() as u32,
+        insns: prog.as_ptr() as u64,
+        license: license.as_ptr() as u64,
+        log_level: 1,
+        log_size: log_buf.len() as u32,
+        log_buf: log_buf.as_mut_ptr() as u64,
+        ..Default::default()
+    };
+    match bpf(BPF_PROG_LOAD, &attr) {
+        // SAFETY: The `BPF_PROG_LOAD` returns a new file descriptor
+        Ok(fd) => Ok(unsafe { OwnedFd::from_raw_fd(fd as i32) }),
+        Err(e) => {
+            let log_len = log_buf
+                .iter()
+                .position(|c| *c == 0)
+                .unwrap_or(log_buf.len());
+            let mut msg = format!(
+                "Failed to load BPF prog: '{}'\n{}",
+                e.desc(),
+                String::from_utf8_lossy(&log_buf[..log_len])
+            );
+            if e != Errno::EPERM {
+                msg.push_str("Kernel compiled with CGROUP_BPF enabled?");
+            }
+            Err(CliError::from(msg.as_str()))
+        }
+    }
+}
+
+/// Equal to iproute2 `vrf_configure_cgroup()`, attach the BPF program to
+/// the cgroup to affect socket creates.
+fn vrf_configure_cgroup(path: &Path, ifindex: u32) -> Result<(), CliError> {
+    let cgroup = std::fs::File::open(path).map_err(|e| {
+        CliError::from(
+            format!(
+                "Failed to open cgroup path: '{}'",
+                Errno::from_raw(e.raw_os_error().unwrap_or_default()).desc()
+            )
+            .as_str(),
+        )
+    })?;
+    let prog = prog_load(ifindex)?;
+    let attr = BpfProgAttachAttr {
+        target_fd: cgroup.as_raw_fd() as u32,
+        attach_bpf_fd: prog.as_raw_fd() as u32,
+        attach_type: BPF_CGROUP_INET_SOCK_CREATE,
+        ..Default::default()
+    };
+    bpf(BPF_PROG_ATTACH, &attr).map_err(|e| {
+        CliError::from(
+            format!("Failed to attach prog to cgroup: '{}'", e.desc()).as_str(),
+        )
+    })?;
+    Ok(())
+}
+
+/// Equal to iproute2 `vrf_switch()`, move current process into cgroup
+/// `MNT/PATH[/NETNS]/vrf/NAME` which binds new sockets to the VRF device.
+/// The `default` VRF moves the process out of any VRF.
+async fn vrf_switch(
+    handle: &rtnetlink::Handle,
+    name: &str,
+) -> Result<(), CliError> {
+    let ifindex = if name == "default" {
+        None
+    } else {
+        match get_vrf(handle, name).await {
+            Some((ifindex, _)) => Some(ifindex),
+            None => return Err(CliError::from("Invalid VRF name")),
+        }
+    };
+
+    let mnt = find_cgroup2_mount()?;
+    let netns = get_netns_dir_name()?;
+    let mut vpath = get_vrf_base_path()?;
+    // If path already ends in netns then don't add it again
+    if !netns.is_empty()
+        && let Some(base) = vpath.strip_suffix(netns.as_str())
+        && base.ends_with('/')
+    {
+        vpath.truncate(base.len() - 1);
+    }
+
+    let mut path = PathBuf::from(format!("{mnt}{vpath}"));
+    if !netns.is_empty() {
+        path.push(&netns);
+    }
+    path.push("vrf");
+    if ifindex.is_some() {
+        path.push(name);
+    }
+    std::fs::create_dir_all(&path)
+        .map_err(|_| CliError::from("Failed to setup vrf cgroup2 directory"))?;
+
+    if let Some(ifindex) = ifindex {
+        vrf_configure_cgroup(&path, ifindex)?;
+    }
+    join_cgroup(&path)
+}
+
+/// Equal to iproute2 `ipvrf_exec()`
+pub(crate) async fn handle_exec(
+    handle: &rtnetlink::Handle,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let Some(name) = opts.first() else {
+        return Err(CliError::from("No VRF name specified"));
+    };
+    let cmd = &opts[1..];
+    if cmd.is_empty() {
+        return Err(CliError::from("No command specified"));
+    }
+    vrf_switch(handle, name).await?;
+    exec_cmd(cmd)
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+mod cgroup;
+mod cli;
+mod exec;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::VrfCommand;
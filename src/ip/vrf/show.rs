@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MIT
+
+use futures_util::TryStreamExt;
+use iproute_rs::{CanDisplay, CanOutput, CliError};
+use rtnetlink::packet_route::link::{
+    InfoData, InfoKind, InfoVrf, LinkAttribute, LinkInfo, LinkMessage,
+};
+use serde::Serialize;
+
+// Equal to `RT_TABLE_MAIN` of `linux/rtnetlink.h`
+const RT_TABLE_MAIN: u32 = 254;
+
+/// Equal to iproute2 `ipvrf_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliVrfInfo {
+    name: String,
+    table: u32,
+}
+
+impl std::fmt::Display for CliVrfInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:<16} {:>5}", self.name, self.table)
+    }
+}
+
+impl CanDisplay for CliVrfInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVrfInfo {}
+
+/// Like iproute2 `ipvrf_show()`, the VRF list is printed with a header
+pub(crate) fn gen_vrf_list_string(vrfs: &[CliVrfInfo]) -> String {
+    let mut ret = format!("{:<16}  Table\n-----------------------\n", "Name");
+    if vrfs.is_empty() {
+        ret.push_str("No VRF has been configured");
+    } else {
+        ret.push_str(
+            &vrfs
+                .iter()
+                .map(CliVrfInfo::to_string)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+    }
+    ret
+}
+
+/// The table ID of VRF device specified by `ip vrf show NAME`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliVrfTable {
+    name: String,
+    table: u32,
+}
+
+impl std::fmt::Display for CliVrfTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.table)
+    }
+}
+
+impl CanDisplay for CliVrfTable {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliVrfTable {}
+
+fn get_ifname(nl_msg: &LinkMessage) -> Option<&str> {
+    nl_msg.attributes.iter().find_map(|attr| match attr {
+        LinkAttribute::IfName(name) => Some(name.as_str()),
+        _ => None,
+    })
+}
+
+// Equal to iproute2 `vrf_table_linkinfo()`, return `None` if the link is
+// not a VRF device.
+fn get_vrf_table(nl_msg: &LinkMessage) -> Option<u32> {
+    let infos = nl_msg.attributes.iter().find_map(|attr| match attr {
+        LinkAttribute::LinkInfo(infos) => Some(infos),
+        _ => None,
+    })?;
+    if !infos
+        .iter()
+        .any(|info| matches!(info, LinkInfo::Kind(InfoKind::Vrf)))
+    {
+        return None;
+    }
+    infos.iter().find_map(|info| match info {
+        LinkInfo::Data(InfoData::Vrf(attrs)) => {
+            attrs.iter().find_map(|attr| match attr {
+                InfoVrf::TableId(v) => Some(*v),
+                _ => None,
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Equal to iproute2 `name_is_vrf()` and `ipvrf_get_table()`, return the
+/// interface index and table ID of the VRF device or `None` if `name` is
+/// not a VRF device.
+pub(crate) async fn get_vrf(
+    handle: &rtnetlink::Handle,
+    name: &str,
+) -> Option<(u32, u32)> {
+    let mut links = handle.link().get().match_name(name.to_string()).execute();
+    let nl_msg = links.try_next().await.ok()??;
+    get_vrf_table(&nl_msg).map(|table| (nl_msg.header.index, table))
+}
+
+/// Equal to iproute2 `ipvrf_show()` with VRF name specified, like iproute2
+/// the `default` VRF refers to the main table.
+pub(crate) async fn handle_show_table(
+    handle: &rtnetlink::Handle,
+    name: &str,
+) -> Result<CliVrfTable, CliError> {
+    match get_vrf(handle, name).await {
+        Some((_, table)) => Ok(CliVrfTable {
+            name: name.to_string(),
+            table,
+        }),
+        None if name == "default" => Ok(CliVrfTable {
+            name: name.to_string(),
+            table: RT_TABLE_MAIN,
+        }),
+        None => Err(CliError::from("Invalid VRF")),
+    }
+}
+
+/// Equal to iproute2 `ipvrf_show()`
+pub(crate) async fn handle_show(
+    handle: &rtnetlink::Handle,
+) -> Result<Vec<CliVrfInfo>, CliError> {
+    let mut ret = Vec::new();
+    let mut links = handle.link().get().execute();
+    while let Some(nl_msg) = links.try_next().await? {
+        if let (Some(table), Some(name)) =
+            (get_vrf_table(&nl_msg), get_ifname(&nl_msg))
+        {
+            ret.push(CliVrfInfo {
+                name: name.to_string(),
+                table,
+            });
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod vrf;
+
+use crate::tests::exec_cmd;
+
+fn with_vrf_iface<T>(vrf_name: &str, table: &str, test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    exec_cmd(&["ip", "link", "add", vrf_name, "type", "vrf", "table", table]);
+    exec_cmd(&["ip", "link", "set", vrf_name, "up"]);
+
+    let result = std::panic::catch_unwind(|| {
+        test();
+    });
+
+    // clean up
+    exec_cmd(&["ip", "link", "del", vrf_name]);
+    assert!(result.is_ok())
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use super::with_vrf_iface;
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    ip_rs_spawn,
+};
+
+#[test]
+fn test_vrf_show() {
+    let vrf_name = "vrftest0";
+
+    with_vrf_iface(vrf_name, "1001", || {
+        for args in [&[][..], &["-j"][..]] {
+            for show_args in [&[][..], &["show"][..], &["show", vrf_name][..]] {
+                let expected_output =
+                    exec_cmd(&[&["ip"], args, &["vrf"], show_args].concat());
+                let our_output =
+                    ip_rs_exec_cmd(&[args, &["vrf"], show_args].concat());
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        let output = ip_rs_exec_cmd(&["vrf", "show", vrf_name]);
+        pretty_assertions::assert_eq!(output, format!("{vrf_name} 1001\n"));
+        let output = ip_rs_exec_cmd(&["vrf", "show", "default"]);
+        pretty_assertions::assert_eq!(output, "default 254\n");
+
+        assert_alias_output(&["vrf", "show"], &["vrf", "lst"]);
+    });
+}
+
+#[test]
+fn test_vrf_exec_identify() {
+    let vrf_name = "vrftest1";
+
+    with_vrf_iface(vrf_name, "1002", || {
+        let output =
+            ip_rs_exec_cmd(&["vrf", "exec", vrf_name, "ip", "vrf", "identify"]);
+        pretty_assertions::assert_eq!(output, format!("{vrf_name}\n"));
+
+        let output = ip_rs_exec_cmd(&[
+            "vrf", "exec", "default", "ip", "vrf", "identify",
+        ]);
+        assert!(output.is_empty());
+
+        let pid = std::process::id().to_string();
+        let expected_output = exec_cmd(&["ip", "vrf", "identify", &pid]);
+        let our_output = ip_rs_exec_cmd(&["vrf", "identify", &pid]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+    });
+}
+
+#[test]
+fn test_vrf_pids() {
+    let vrf_name = "vrftest2";
+
+    with_vrf_iface(vrf_name, "1003", || {
+        let output = ip_rs_exec_cmd(&["vrf", "pids", vrf_name]);
+        assert!(output.is_empty());
+
+        let mut child = ip_rs_spawn(&["vrf", "exec", vrf_name, "sleep", "30"]);
+        let pid = child.id().to_string();
+        let mut our_output = String::new();
+        for _ in 0..50 {
+            our_output = ip_rs_exec_cmd(&["vrf", "pids", vrf_name]);
+            if !our_output.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        let expected_output = exec_cmd(&["ip", "vrf", "pids", vrf_name]);
+        child.kill().ok();
+        child.wait().ok();
+
+        pretty_assertions::assert_eq!(expected_output, our_output);
+        pretty_assertions::assert_eq!(our_output, format!("{pid:>5}  sleep\n"));
+    });
+}
+
+#[test]
+fn test_vrf_invalid_args() {
+    for (args, error) in [
+        (&["vrf", "show", "vrftest-nonexist"][..], "Invalid VRF"),
+        (
+            &["vrf", "show", "a", "b"][..],
+            "Usage:\tip vrf show [NAME] ...",
+        ),
+        (&["vrf", "exec"][..], "No VRF name specified"),
+        (
+            &["vrf", "exec", "vrftest-nonexist"][..],
+            "No command specified",
+        ),
+        (
+            &["vrf", "exec", "vrftest-nonexist", "true"][..],
+            "Invalid VRF name",
+        ),
+        (&["vrf", "pids"][..], "Invalid arguments"),
+        (&["vrf", "pids", "vrftest-nonexist"][..], "Invalid VRF name"),
+        (
+            &["vrf", "identify", "x"][..],
+            "argument \"x\" is wrong: Invalid pid",
+        ),
+        (
+            &["vrf", "identify", "1", "2"][..],
+            "argument \"2\" is wrong: Extra arguments specified",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
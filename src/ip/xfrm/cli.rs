@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: MIT
+
+use iproute_rs::{CanDisplay, CanOutput, OutputFormat};
+use serde::Serialize;
+
+use super::{
+    monitor::handle_monitor,
+    policy::{self, CliXfrmPolicyInfo, CliXfrmSpdInfo},
+    state::{
+        CliXfrmSadInfo, CliXfrmStateInfo, handle_allocspi, handle_count,
+        handle_flush, handle_get_or_delete, handle_list, handle_modify,
+    },
+};
+use crate::{CliError, context::NetlinkContext, family::get_family};
+
+const XFRM_USAGE: &str = "\
+Usage: ip xfrm XFRM-OBJECT { COMMAND | help }
+where  XFRM-OBJECT := state | policy | monitor";
+
+fn usage() -> CliError {
+    CliError::from(XFRM_USAGE)
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum CliXfrmOutput {
+    States(Vec<CliXfrmStateInfo>),
+    Count(Vec<CliXfrmSadInfo>),
+    Policies(Vec<CliXfrmPolicyInfo>),
+    SpdCount(Vec<CliXfrmSpdInfo>),
+}
+
+impl CanDisplay for CliXfrmOutput {
+    fn gen_string(&self) -> String {
+        match self {
+            Self::States(states) => states.gen_string(),
+            Self::Count(counts) => counts.gen_string(),
+            Self::Policies(policies) => policies.gen_string(),
+            Self::SpdCount(counts) => counts.gen_string(),
+        }
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        match self {
+            Self::States(states) => states.gen_oneline_string(),
+            Self::Count(counts) => counts.gen_oneline_string(),
+            Self::Policies(policies) => policies.gen_oneline_string(),
+            Self::SpdCount(counts) => counts.gen_oneline_string(),
+        }
+    }
+}
+
+impl CanOutput for CliXfrmOutput {}
+
+fn gen_opts_arg() -> clap::Arg {
+    clap::Arg::new("options")
+        .action(clap::ArgAction::Append)
+        .trailing_var_arg(true)
+}
+
+fn get_opts(matches: &clap::ArgMatches) -> Vec<&str> {
+    matches
+        .get_many::<String>("options")
+        .unwrap_or_default()
+        .map(String::as_str)
+        .collect()
+}
+
+fn gen_state_command() -> clap::Command {
+    clap::Command::new("state")
+        .about("manage IPsec security associations")
+        .alias("sa")
+        .alias("stat")
+        .alias("sta")
+        .alias("st")
+        .alias("s")
+        .subcommand_required(false)
+        .subcommand(
+            clap::Command::new("add")
+                .about("add new state")
+                .alias("ad")
+                .alias("a")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("update")
+                .about("update existing state")
+                .alias("updat")
+                .alias("upda")
+                .alias("upd")
+                .alias("up")
+                .alias("u")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("allocspi")
+                .about("allocate SPI for new state")
+                .alias("allocsp")
+                .alias("allocs")
+                .alias("alloc")
+                .alias("allo")
+                .alias("all")
+                .alias("al")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("delete")
+                .about("delete state")
+                .alias("delet")
+                .alias("dele")
+                .alias("del")
+                .alias("de")
+                .alias("d")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("list")
+                .about("list states")
+                .alias("lis")
+                .alias("li")
+                .alias("l")
+                .alias("show")
+                .alias("sho")
+                .alias("sh")
+                .alias("s")
+                .alias("lst")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("get")
+                .about("get state")
+                .alias("ge")
+                .alias("g")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("flush")
+                .about("flush states")
+                .alias("flus")
+                .alias("flu")
+                .alias("fl")
+                .alias("f")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("count")
+                .about("show number of states")
+                .alias("coun")
+                .alias("cou")
+                .alias("co")
+                .alias("c")
+                .arg(gen_opts_arg()),
+        )
+}
+
+fn gen_policy_command() -> clap::Command {
+    clap::Command::new("policy")
+        .about("manage IPsec security policies")
+        .alias("polic")
+        .alias("poli")
+        .alias("pol")
+        .alias("po")
+        .alias("p")
+        .subcommand_required(false)
+        .subcommand(
+            clap::Command::new("add")
+                .about("add new policy")
+                .alias("ad")
+                .alias("a")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("update")
+                .about("update existing policy")
+                .alias("updat")
+                .alias("upda")
+                .alias("upd")
+                .alias("up")
+                .alias("u")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("delete")
+                .about("delete policy")
+                .alias("delet")
+                .alias("dele")
+                .alias("del")
+                .alias("de")
+                .alias("d")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("list")
+                .about("list policies")
+                .alias("lis")
+                .alias("li")
+                .alias("l")
+                .alias("show")
+                .alias("sho")
+                .alias("sh")
+                .alias("s")
+                .alias("lst")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("get")
+                .about("get policy")
+                .alias("ge")
+                .alias("g")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("flush")
+                .about("flush policies")
+                .alias("flus")
+                .alias("flu")
+                .alias("fl")
+                .alias("f")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("count")
+                .about("show number of policies")
+                .alias("coun")
+                .alias("cou")
+                .alias("co")
+                .alias("c")
+                .arg(gen_opts_arg()),
+        )
+        .subcommand(
+            clap::Command::new("set")
+                .about("set SPD hash thresholds")
+                .arg(gen_opts_arg()),
+        )
+}
+
+pub(crate) struct XfrmCommand;
+
+impl XfrmCommand {
+    pub(crate) const CMD: &'static str = "xfrm";
+
+    pub(crate) fn gen_command() -> clap::Command {
+        clap::Command::new(Self::CMD)
+            .about("IPsec transform framework management")
+            .alias("xfr")
+            .alias("xf")
+            .alias("x")
+            .subcommand_required(false)
+            .subcommand(gen_state_command())
+            .subcommand(gen_policy_command())
+            .subcommand(
+                clap::Command::new("monitor")
+                    .about("watch for XFRM events")
+                    .alias("monito")
+                    .alias("monit")
+                    .alias("moni")
+                    .alias("mon")
+                    .alias("mo")
+                    .alias("m")
+                    .arg(gen_opts_arg()),
+            )
+    }
+
+    pub(crate) async fn handle(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+        fmt: OutputFormat,
+    ) -> Result<Option<CliXfrmOutput>, CliError> {
+        match matches.subcommand() {
+            Some(("state", matches)) => Self::handle_state(matches, ctx).await,
+            Some(("policy", matches)) => {
+                Self::handle_policy(matches, ctx).await
+            }
+            Some(("monitor", sub_matches)) => {
+                // The monitor prints events by itself as they arrive
+                handle_monitor(
+                    ctx,
+                    &get_opts(sub_matches),
+                    fmt,
+                    matches.get_count("STATS") > 0,
+                )
+                .await?;
+                Ok(None)
+            }
+            _ => Err(usage()),
+        }
+    }
+
+    async fn handle_state(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliXfrmOutput>, CliError> {
+        let show_stats = matches.get_count("STATS");
+        let family = get_family(matches);
+        match matches.subcommand() {
+            Some((cmd @ ("add" | "update"), matches)) => {
+                handle_modify(ctx, &get_opts(matches), cmd == "update", family)
+                    .await?;
+                Ok(None)
+            }
+            Some(("allocspi", matches)) => {
+                handle_allocspi(ctx, &get_opts(matches), family, show_stats > 0)
+                    .await
+                    .map(CliXfrmOutput::States)
+                    .map(Some)
+            }
+            Some((cmd @ ("delete" | "get"), matches)) => handle_get_or_delete(
+                ctx,
+                &get_opts(matches),
+                cmd == "delete",
+                family,
+                show_stats > 0,
+            )
+            .await
+            .map(CliXfrmOutput::States)
+            .map(Some),
+            Some(("flush", matches)) => {
+                handle_flush(ctx, &get_opts(matches), show_stats).await?;
+                Ok(None)
+            }
+            Some(("count", _)) => handle_count(ctx, show_stats > 0)
+                .await
+                .map(CliXfrmOutput::Count)
+                .map(Some),
+            Some(("list", matches)) => {
+                handle_list(ctx, &get_opts(matches), family, show_stats > 0)
+                    .await
+                    .map(CliXfrmOutput::States)
+                    .map(Some)
+            }
+            _ => handle_list(ctx, &[], family, show_stats > 0)
+                .await
+                .map(CliXfrmOutput::States)
+                .map(Some),
+        }
+    }
+
+    async fn handle_policy(
+        matches: &clap::ArgMatches,
+        ctx: &NetlinkContext,
+    ) -> Result<Option<CliXfrmOutput>, CliError> {
+        let show_stats = matches.get_count("STATS");
+        let family = get_family(matches);
+        match matches.subcommand() {
+            Some((cmd @ ("add" | "update"), matches)) => {
+                policy::handle_modify(
+                    ctx,
+                    &get_opts(matches),
+                    cmd == "update",
+                    family,
+                )
+                .await?;
+                Ok(None)
+            }
+            Some((cmd @ ("delete" | "get"), matches)) => {
+                policy::handle_get_or_delete(
+                    ctx,
+                    &get_opts(matches),
+                    cmd == "delete",
+                    family,
+                    show_stats > 0,
+                )
+                .await
+                .map(CliXfrmOutput::Policies)
+                .map(Some)
+            }
+            Some(("flush", matches)) => {
+                policy::handle_flush(ctx, &get_opts(matches), show_stats)
+                    .await?;
+                Ok(None)
+            }
+            Some(("set", matches)) => {
+                policy::handle_set(ctx, &get_opts(matches)).await?;
+                Ok(None)
+            }
+            Some(("count", _)) => policy::handle_count(ctx, show_stats)
+                .await
+                .map(CliXfrmOutput::SpdCount)
+                .map(Some),
+            Some(("list", matches)) => policy::handle_list(
+                ctx,
+                &get_opts(matches),
+                family,
+                show_stats > 0,
+            )
+            .await
+            .map(CliXfrmOutput::Policies)
+            .map(Some),
+            _ => policy::handle_list(ctx, &[], family, show_stats > 0)
+                .await
+                .map(CliXfrmOutput::Policies)
+                .map(Some),
+        }
+    }
+}
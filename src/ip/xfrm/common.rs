@@ -0,0 +1,1290 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use iproute_rs::{CliError, next_opt, nl_request};
+use rtnetlink::packet_route::AddressFamily;
+use serde::Serialize;
+
+use super::message::{
+    AF_INET, AF_INET6, XFRM_INF, XFRM_OFFLOAD_INBOUND, XfrmAddress, XfrmAlgo,
+    XfrmAttr, XfrmId, XfrmLifetimeCfg, XfrmLifetimeCur, XfrmMark,
+    XfrmNetlinkMessage, XfrmSelector, XfrmStats, XfrmUserTmpl,
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_num,
+    prefix::CliIpPrefix,
+    rule::{ipproto_from_name, ipproto_lookup},
+};
+
+// Equal to `linux/in.h` and `linux/in6.h`
+const IPPROTO_ICMP: u8 = 1;
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+const IPPROTO_DCCP: u8 = 33;
+const IPPROTO_ROUTING: u8 = 43;
+const IPPROTO_GRE: u8 = 47;
+pub(crate) const IPPROTO_ESP: u8 = 50;
+pub(crate) const IPPROTO_AH: u8 = 51;
+const IPPROTO_ICMPV6: u8 = 58;
+const IPPROTO_DSTOPTS: u8 = 60;
+pub(crate) const IPPROTO_COMP: u8 = 108;
+const IPPROTO_SCTP: u8 = 132;
+const IPPROTO_MH: u8 = 135;
+const IPSEC_PROTO_ANY: u8 = 255;
+
+// Equal to iproute2 `xfrmproto_types[]`
+const XFRM_PROTO_NAMES: [(&str, u8); 6] = [
+    ("esp", IPPROTO_ESP),
+    ("ah", IPPROTO_AH),
+    ("comp", IPPROTO_COMP),
+    ("route2", IPPROTO_ROUTING),
+    ("hao", IPPROTO_DSTOPTS),
+    ("ipsec-any", IPSEC_PROTO_ANY),
+];
+
+pub(crate) const XFRM_MODE_TRANSPORT: u8 = 0;
+pub(crate) const XFRM_MODE_TUNNEL: u8 = 1;
+pub(crate) const XFRM_MODE_ROUTEOPTIMIZATION: u8 = 2;
+pub(crate) const XFRM_MODE_IN_TRIGGER: u8 = 3;
+pub(crate) const XFRM_MODE_BEET: u8 = 4;
+
+const XFRM_MODE_NAMES: [(&str, u8); 5] = [
+    ("transport", XFRM_MODE_TRANSPORT),
+    ("tunnel", XFRM_MODE_TUNNEL),
+    ("ro", XFRM_MODE_ROUTEOPTIMIZATION),
+    ("in_trigger", XFRM_MODE_IN_TRIGGER),
+    ("beet", XFRM_MODE_BEET),
+];
+
+// Equal to `linux/udp.h` and `linux/in.h`
+const XFRM_ENCAP_NAMES: [(&str, u16); 3] =
+    [("espinudp-nonike", 1), ("espinudp", 2), ("espintcp", 7)];
+
+/// Send the request over `NETLINK_XFRM` and collect the payload of replies
+pub(crate) async fn xfrm_request(
+    ctx: &NetlinkContext,
+    msg: XfrmNetlinkMessage,
+    flags: u16,
+) -> Result<Vec<XfrmNetlinkMessage>, CliError> {
+    nl_request(ctx.proto()?.handle(), msg, flags).await
+}
+
+pub(crate) fn invarg(value: &str, error_msg: &str) -> CliError {
+    CliError::from(
+        format!("argument \"{value}\" is wrong: {error_msg}").as_str(),
+    )
+}
+
+pub(crate) fn missarg(key: &str) -> CliError {
+    CliError::from(format!("argument \"{key}\" is required").as_str())
+}
+
+pub(crate) fn duparg(key: &str, value: &str) -> CliError {
+    CliError::from(
+        format!("duplicate \"{key}\": \"{value}\" is the second value.")
+            .as_str(),
+    )
+}
+
+/// Like iproute2 `XFRM_FLAG_PRINT()`, the unknown bits are appended in hex
+pub(crate) fn flags_to_names<T>(flags: T, names: &[(&str, T)]) -> Vec<String>
+where
+    T: Copy + Into<u32>,
+{
+    let mut ret = Vec::new();
+    let mut flags: u32 = flags.into();
+    for (name, flag) in names {
+        let flag: u32 = (*flag).into();
+        if flags & flag > 0 {
+            flags &= !flag;
+            ret.push(name.to_string());
+        }
+    }
+    if flags > 0 {
+        ret.push(format!("{flags:x}"));
+    }
+    ret
+}
+
+/// Equal to iproute2 `xfrm_state_flag_parse()` and
+/// `xfrm_policy_flag_parse()`, the flag list is either hex number or flag
+/// names. Parsing stops at the first word not being flag name.
+pub(crate) fn parse_flag_list<T>(
+    opts: &mut std::slice::Iter<'_, &str>,
+    names: &[(&str, T)],
+    error_msg: &str,
+) -> Result<T, CliError>
+where
+    T: Copy + Default + std::ops::BitOrAssign + TryFrom<u32>,
+{
+    let first = next_opt(&mut opts.clone())?;
+    if first.len() > 2
+        && let Some(hex) = first.strip_prefix("0x")
+    {
+        opts.next();
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(|v| T::try_from(v).ok())
+            .ok_or_else(|| invarg(first, error_msg));
+    }
+    let mut flags = T::default();
+    while let Some(flag) = opts.as_slice().first().and_then(|opt| {
+        names
+            .iter()
+            .find(|(name, _)| name == opt)
+            .map(|(_, flag)| *flag)
+    }) {
+        opts.next();
+        flags |= flag;
+    }
+    Ok(flags)
+}
+
+/// Equal to iproute2 `xfrm_xfrmproto_getbyname()`
+pub(crate) fn parse_xfrm_proto(value: &str) -> Result<u8, CliError> {
+    XFRM_PROTO_NAMES
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, proto)| *proto)
+        .ok_or_else(|| invarg(value, "XFRM-PROTO value is invalid"))
+}
+
+/// Equal to iproute2 `strxf_xfrmproto()`
+pub(crate) fn xfrm_proto_to_name(proto: u8) -> String {
+    XFRM_PROTO_NAMES
+        .iter()
+        .find(|(_, p)| *p == proto)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| proto.to_string())
+}
+
+/// Equal to iproute2 `xfrm_xfrmproto_is_ipsec()`
+pub(crate) fn xfrm_proto_is_ipsec(proto: u8) -> bool {
+    matches!(proto, IPPROTO_ESP | IPPROTO_AH | IPPROTO_COMP)
+}
+
+/// Equal to iproute2 `xfrm_xfrmproto_is_ro()`
+pub(crate) fn xfrm_proto_is_ro(proto: u8) -> bool {
+    matches!(proto, IPPROTO_ROUTING | IPPROTO_DSTOPTS)
+}
+
+/// Equal to iproute2 `xfrm_mode_parse()`
+pub(crate) fn parse_mode(value: &str) -> Result<u8, CliError> {
+    XFRM_MODE_NAMES
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, mode)| *mode)
+        .ok_or_else(|| invarg(value, "MODE value is invalid"))
+}
+
+pub(crate) fn mode_to_name(mode: u8) -> String {
+    XFRM_MODE_NAMES
+        .iter()
+        .find(|(_, m)| *m == mode)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| mode.to_string())
+}
+
+/// Equal to iproute2 `xfrm_encap_type_parse()`
+pub(crate) fn parse_encap_type(value: &str) -> Result<u16, CliError> {
+    XFRM_ENCAP_NAMES
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, kind)| *kind)
+        .ok_or_else(|| invarg(value, "ENCAP-TYPE value is invalid"))
+}
+
+/// Equal to iproute2 `strxf_proto()`
+pub(crate) fn proto_to_name(proto: u8) -> String {
+    ipproto_lookup(proto).unwrap_or_else(|| proto.to_string())
+}
+
+/// Equal to iproute2 `strxf_time()`
+fn time_to_string(secs: u64) -> String {
+    if secs == 0 {
+        return "-".to_string();
+    }
+    i64::try_from(secs)
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Equal to iproute2 `strxf_limit()`
+fn limit_to_string(limit: u64) -> String {
+    if limit == XFRM_INF {
+        "(INF)".to_string()
+    } else {
+        limit.to_string()
+    }
+}
+
+// Like C `printf("%#x")` which has no `0x` prefix for zero
+fn alt_hex(value: u32) -> String {
+    if value == 0 {
+        "0".to_string()
+    } else {
+        format!("{value:#x}")
+    }
+}
+
+// Equal to iproute2 `ll_index_to_name()`
+pub(crate) fn ifindex_to_name(
+    ifnames: &HashMap<u32, String>,
+    ifindex: i32,
+) -> String {
+    u32::try_from(ifindex)
+        .ok()
+        .and_then(|i| ifnames.get(&i))
+        .cloned()
+        .unwrap_or_else(|| format!("if{ifindex}"))
+}
+
+fn ifname_to_index(ifnames: &HashMap<u32, String>, name: &str) -> Option<i32> {
+    ifnames
+        .iter()
+        .find(|(_, n)| n.as_str() == name)
+        .and_then(|(i, _)| i32::try_from(*i).ok())
+}
+
+pub(crate) fn af_family(addr: &IpAddr) -> u16 {
+    match addr {
+        IpAddr::V4(_) => AF_INET,
+        IpAddr::V6(_) => AF_INET6,
+    }
+}
+
+pub(crate) fn address_family(family: u16) -> AddressFamily {
+    match family {
+        AF_INET => AddressFamily::Inet,
+        AF_INET6 => AddressFamily::Inet6,
+        _ => AddressFamily::Unspec,
+    }
+}
+
+fn is_id_keyword(opt: &str) -> bool {
+    matches!(opt, "src" | "dst" | "proto" | "spi")
+}
+
+/// The `ID := [ src ADDR ] [ dst ADDR ] [ proto XFRM-PROTO ] [ spi SPI ]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmIdOpts {
+    pub(crate) src: Option<CliIpPrefix>,
+    pub(crate) dst: Option<CliIpPrefix>,
+    pub(crate) proto: Option<u8>,
+    pub(crate) spi: Option<u32>,
+}
+
+impl XfrmIdOpts {
+    /// Equal to iproute2 `xfrm_id_parse()`, the `opt` is the first word
+    /// of ID, following words are consumed from `opts` as long as they are
+    /// ID keywords. When `loose` is false, the XFRM protocol is mandatory.
+    pub(crate) fn parse<'a>(
+        opt: &'a str,
+        opts: &mut std::slice::Iter<'_, &'a str>,
+        family: AddressFamily,
+        loose: bool,
+    ) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        if !is_id_keyword(opt) {
+            return Err(if loose {
+                invarg(opt, "unknown")
+            } else {
+                missarg("XFRM-PROTO")
+            });
+        }
+        let mut opt = opt;
+        let mut last_value;
+        loop {
+            let value = next_opt(opts)?;
+            last_value = value;
+            match opt {
+                "src" => ret.src = Some(CliIpPrefix::parse(value, family)?),
+                "dst" => ret.dst = Some(CliIpPrefix::parse(value, family)?),
+                "proto" => ret.proto = Some(parse_xfrm_proto(value)?),
+                _ => {
+                    ret.spi = Some(parse_num(
+                        value,
+                        "value after \"spi\" is invalid",
+                    )?)
+                }
+            }
+            match opts.as_slice().first() {
+                Some(next) if is_id_keyword(next) => {
+                    opt = next;
+                    opts.next();
+                }
+                _ => break,
+            }
+        }
+
+        if let (Some(src), Some(dst)) = (ret.src, ret.dst)
+            && af_family(&src.addr) != af_family(&dst.addr)
+        {
+            return Err(invarg(
+                last_value,
+                "the same address family is required between values after \
+                 \"src\" and \"dst\"",
+            ));
+        }
+
+        if let (Some(spi), Some(proto)) = (ret.spi, ret.proto)
+            && spi != 0
+            && proto != 0
+        {
+            if xfrm_proto_is_ro(proto) {
+                return Err(CliError::from(
+                    format!(
+                        "\"spi\" is invalid with XFRM-PROTO value \"{}\"",
+                        xfrm_proto_to_name(proto)
+                    )
+                    .as_str(),
+                ));
+            } else if proto == IPPROTO_COMP && spi >= 0x10000 {
+                return Err(CliError::from(
+                    format!(
+                        "SPI value is too large with XFRM-PROTO value \"{}\"",
+                        xfrm_proto_to_name(proto)
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+
+        if !loose && ret.proto.unwrap_or_default() == 0 {
+            return Err(missarg("XFRM-PROTO"));
+        }
+        Ok(ret)
+    }
+
+    /// The `AF_*` family of the addresses, `AF_UNSPEC` if none specified
+    pub(crate) fn family(&self) -> u16 {
+        self.dst
+            .or(self.src)
+            .map(|p| af_family(&p.addr))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn saddr(&self) -> XfrmAddress {
+        self.src
+            .map(|p| XfrmAddress::from_ip(p.addr))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn to_id(self) -> XfrmId {
+        XfrmId {
+            daddr: self
+                .dst
+                .map(|p| XfrmAddress::from_ip(p.addr))
+                .unwrap_or_default(),
+            spi: self.spi.unwrap_or_default(),
+            proto: self.proto.unwrap_or_default(),
+        }
+    }
+}
+
+/// Equal to iproute2 `xfrm_parse_mark()`, the `mark` keyword is consumed
+/// already. The mask defaults to `0xffffffff` when not specified.
+pub(crate) fn parse_mark(
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<XfrmMark, CliError> {
+    let value = parse_num(next_opt(opts)?, "MARK value is invalid")?;
+    let mask = if opts.as_slice().first() == Some(&"mask") {
+        opts.next();
+        parse_num(next_opt(opts)?, "MASK value is invalid")?
+    } else {
+        u32::MAX
+    };
+    Ok(XfrmMark { value, mask })
+}
+
+/// Equal to iproute2 `xfrm_lifetime_cfg_parse()`, the `limit` keyword is
+/// consumed already.
+pub(crate) fn parse_lifetime(
+    lft: &mut XfrmLifetimeCfg,
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<(), CliError> {
+    let name = next_opt(opts)?;
+    let field = match name {
+        "time-soft" => &mut lft.soft_add_expires_seconds,
+        "time-hard" => &mut lft.hard_add_expires_seconds,
+        "time-use-soft" => &mut lft.soft_use_expires_seconds,
+        "time-use-hard" => &mut lft.hard_use_expires_seconds,
+        "byte-soft" => &mut lft.soft_byte_limit,
+        "byte-hard" => &mut lft.hard_byte_limit,
+        "packet-soft" => &mut lft.soft_packet_limit,
+        "packet-hard" => &mut lft.hard_packet_limit,
+        _ => return Err(invarg(name, "LIMIT value is invalid")),
+    };
+    *field = parse_num(
+        next_opt(opts)?,
+        &format!("value after \"{name}\" is invalid"),
+    )?;
+    Ok(())
+}
+
+fn is_upspec_keyword(opt: &str) -> bool {
+    matches!(opt, "proto" | "sport" | "dport" | "type" | "code" | "key")
+}
+
+/// Equal to iproute2 `xfrm_selector_upspec_parse()`
+fn parse_upspec(
+    sel: &mut XfrmSelector,
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<(), CliError> {
+    let mut has_port = false;
+    let mut has_type = false;
+    let mut has_key = false;
+
+    while let Some(opt) = opts.as_slice().first().copied() {
+        if !is_upspec_keyword(opt) {
+            break;
+        }
+        opts.next();
+        let value = next_opt(opts)?;
+        match opt {
+            "proto" => {
+                sel.proto = if value == "any" {
+                    0
+                } else if let Some(proto) = ipproto_from_name(value) {
+                    proto
+                } else {
+                    parse_num(value, "PROTO value is invalid")?
+                };
+            }
+            "sport" | "dport" => {
+                let port = parse_num(
+                    value,
+                    &format!("value after \"{opt}\" is invalid"),
+                )?;
+                if opt == "sport" {
+                    sel.sport = port;
+                    sel.sport_mask = u16::MAX;
+                } else {
+                    sel.dport = port;
+                    sel.dport_mask = u16::MAX;
+                }
+                has_port = true;
+            }
+            "type" | "code" => {
+                // The ICMP type and code are stored in the source and
+                // destination port
+                let v = parse_num::<u8>(
+                    value,
+                    &format!("value after \"{opt}\" is invalid"),
+                )?;
+                if opt == "type" {
+                    sel.sport = v.into();
+                    sel.sport_mask = u16::MAX;
+                } else {
+                    sel.dport = v.into();
+                    sel.dport_mask = u16::MAX;
+                }
+                has_type = true;
+            }
+            _ => {
+                let key = if value.contains('.') {
+                    value.parse::<Ipv4Addr>().map(u32::from).map_err(|_| {
+                        CliError::from(
+                            format!(
+                                "an IP address is expected rather than \
+                                 \"{value}\""
+                            )
+                            .as_str(),
+                        )
+                    })?
+                } else {
+                    parse_num::<u32>(value, "").map_err(|_| {
+                        CliError::from("value after \"key\" is invalid")
+                    })?
+                };
+                sel.sport = (key >> 16) as u16;
+                sel.dport = (key & 0xffff) as u16;
+                sel.sport_mask = u16::MAX;
+                sel.dport_mask = u16::MAX;
+                has_key = true;
+            }
+        }
+    }
+
+    let proto_name = proto_to_name(sel.proto);
+    if has_port
+        && !matches!(
+            sel.proto,
+            IPPROTO_TCP | IPPROTO_UDP | IPPROTO_SCTP | IPPROTO_DCCP | 0
+        )
+    {
+        return Err(CliError::from(
+            format!(
+                "\"sport\" and \"dport\" are invalid with PROTO value \
+                 \"{proto_name}\""
+            )
+            .as_str(),
+        ));
+    }
+    if has_type
+        && !matches!(sel.proto, IPPROTO_ICMP | IPPROTO_ICMPV6 | IPPROTO_MH)
+    {
+        return Err(CliError::from(
+            format!(
+                "\"type\" and \"code\" are invalid with PROTO value \
+                 \"{proto_name}\""
+            )
+            .as_str(),
+        ));
+    }
+    if has_key && sel.proto != IPPROTO_GRE {
+        return Err(CliError::from(
+            format!("\"key\" is invalid with PROTO value \"{proto_name}\"")
+                .as_str(),
+        ));
+    }
+    Ok(())
+}
+
+/// Equal to iproute2 `xfrm_selector_parse()`, the `sel` keyword is consumed
+/// already. Parsing stops at the first word not belonging to selector.
+pub(crate) fn parse_selector(
+    opts: &mut std::slice::Iter<'_, &str>,
+    family: AddressFamily,
+    ifnames: &HashMap<u32, String>,
+) -> Result<XfrmSelector, CliError> {
+    // Like iproute2 `NEXT_ARG()`, the selector should not be empty
+    next_opt(&mut opts.clone())?;
+    let mut sel = XfrmSelector::default();
+    let mut src_family = None;
+    let mut dst_family = None;
+    let mut last_value = "";
+    let mut upspec_parsed = false;
+
+    while let Some(opt) = opts.as_slice().first().copied() {
+        match opt {
+            "src" | "dst" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                last_value = value;
+                let prefix = CliIpPrefix::parse(value, family)?;
+                sel.family = af_family(&prefix.addr);
+                if opt == "src" {
+                    sel.saddr = XfrmAddress::from_ip(prefix.addr);
+                    sel.prefixlen_s = prefix.prefix_len;
+                    src_family = Some(sel.family);
+                } else {
+                    sel.daddr = XfrmAddress::from_ip(prefix.addr);
+                    sel.prefixlen_d = prefix.prefix_len;
+                    dst_family = Some(sel.family);
+                }
+            }
+            "dev" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                last_value = value;
+                sel.ifindex = if value == "none" {
+                    0
+                } else {
+                    ifname_to_index(ifnames, value)
+                        .ok_or_else(|| invarg(value, "DEV value is invalid"))?
+                };
+            }
+            _ if !upspec_parsed && is_upspec_keyword(opt) => {
+                upspec_parsed = true;
+                parse_upspec(&mut sel, opts)?;
+            }
+            _ => break,
+        }
+    }
+
+    if let (Some(src), Some(dst)) = (src_family, dst_family)
+        && src != dst
+    {
+        return Err(invarg(
+            last_value,
+            "the same address family is required between values after \"src\" \
+             and \"dst\"",
+        ));
+    }
+    Ok(sel)
+}
+
+/// Equal to iproute2 `xfrm_selector_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmSelector {
+    src: IpAddr,
+    src_prefix_len: u8,
+    dst: IpAddr,
+    dst_prefix_len: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proto: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sport: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dport: Option<u16>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    icmp_type: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<u32>,
+    /// The raw mobility header destination port shown in statistics mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mh_dport: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dev: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+}
+
+impl CliXfrmSelector {
+    pub(crate) fn new(
+        sel: &XfrmSelector,
+        family: u16,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        let family = if sel.family == 0 { family } else { sel.family };
+        let mut ret = Self {
+            src: sel.saddr.to_ip(family),
+            src_prefix_len: sel.prefixlen_s,
+            dst: sel.daddr.to_ip(family),
+            dst_prefix_len: sel.prefixlen_d,
+            proto: (sel.proto != 0).then(|| proto_to_name(sel.proto)),
+            sport: None,
+            dport: None,
+            icmp_type: None,
+            code: None,
+            key: None,
+            mh_dport: None,
+            dev: (sel.ifindex > 0)
+                .then(|| ifindex_to_name(ifnames, sel.ifindex)),
+            uid: show_stats.then_some(sel.user),
+        };
+        let sport = (sel.sport_mask != 0).then_some(sel.sport);
+        let dport = (sel.dport_mask != 0).then_some(sel.dport);
+        match sel.proto {
+            IPPROTO_ICMP | IPPROTO_ICMPV6 => {
+                ret.icmp_type = sport;
+                ret.code = dport;
+            }
+            IPPROTO_GRE => {
+                if sport.is_some() || dport.is_some() {
+                    ret.key = Some(
+                        (u32::from(sel.sport) << 16) + u32::from(sel.dport),
+                    );
+                }
+            }
+            IPPROTO_MH => {
+                ret.icmp_type = sport;
+                if show_stats {
+                    // Like iproute2, the port is shown in network order
+                    ret.mh_dport = dport.map(u16::to_be);
+                }
+            }
+            _ => {
+                ret.sport = sport;
+                ret.dport = dport;
+            }
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliXfrmSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "src {}/{} ", self.src, self.src_prefix_len)?;
+        write!(f, "dst {}/{} ", self.dst, self.dst_prefix_len)?;
+        if let Some(v) = &self.proto {
+            write!(f, "proto {v} ")?;
+        }
+        if let Some(v) = self.sport {
+            write!(f, "sport {v} ")?;
+        }
+        if let Some(v) = self.dport {
+            write!(f, "dport {v} ")?;
+        }
+        if let Some(v) = self.icmp_type {
+            write!(f, "type {v} ")?;
+        }
+        if let Some(v) = self.code {
+            write!(f, "code {v} ")?;
+        }
+        if let Some(v) = self.key {
+            write!(f, "key {v} ")?;
+        }
+        if let Some(v) = self.mh_dport {
+            write!(f, "(dport) 0x{v:04x} ")?;
+        }
+        if let Some(v) = &self.dev {
+            write!(f, "dev {v} ")?;
+        }
+        if let Some(v) = self.uid {
+            write!(f, "uid {v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Equal to iproute2 `xfrm_lifetime_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmLifetime {
+    soft_byte_limit: u64,
+    hard_byte_limit: u64,
+    soft_packet_limit: u64,
+    hard_packet_limit: u64,
+    soft_add_expires_seconds: u64,
+    hard_add_expires_seconds: u64,
+    soft_use_expires_seconds: u64,
+    hard_use_expires_seconds: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current: Option<CliXfrmLifetimeCur>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliXfrmLifetimeCur {
+    bytes: u64,
+    packets: u64,
+    add_time: u64,
+    use_time: u64,
+}
+
+impl CliXfrmLifetime {
+    pub(crate) fn new(
+        cfg: &XfrmLifetimeCfg,
+        cur: Option<&XfrmLifetimeCur>,
+    ) -> Self {
+        Self {
+            soft_byte_limit: cfg.soft_byte_limit,
+            hard_byte_limit: cfg.hard_byte_limit,
+            soft_packet_limit: cfg.soft_packet_limit,
+            hard_packet_limit: cfg.hard_packet_limit,
+            soft_add_expires_seconds: cfg.soft_add_expires_seconds,
+            hard_add_expires_seconds: cfg.hard_add_expires_seconds,
+            soft_use_expires_seconds: cfg.soft_use_expires_seconds,
+            hard_use_expires_seconds: cfg.hard_use_expires_seconds,
+            current: cur.map(|cur| CliXfrmLifetimeCur {
+                bytes: cur.bytes,
+                packets: cur.packets,
+                add_time: cur.add_time,
+                use_time: cur.use_time,
+            }),
+        }
+    }
+
+    pub(crate) fn lines(&self, prefix: &str) -> Vec<String> {
+        let mut ret = vec![
+            format!("{prefix}lifetime config:"),
+            format!(
+                "{prefix}  limit: soft {}(bytes), hard {}(bytes)",
+                limit_to_string(self.soft_byte_limit),
+                limit_to_string(self.hard_byte_limit)
+            ),
+            format!(
+                "{prefix}  limit: soft {}(packets), hard {}(packets)",
+                limit_to_string(self.soft_packet_limit),
+                limit_to_string(self.hard_packet_limit)
+            ),
+            format!(
+                "{prefix}  expire add: soft {}(sec), hard {}(sec)",
+                self.soft_add_expires_seconds, self.hard_add_expires_seconds
+            ),
+            format!(
+                "{prefix}  expire use: soft {}(sec), hard {}(sec)",
+                self.soft_use_expires_seconds, self.hard_use_expires_seconds
+            ),
+        ];
+        if let Some(cur) = &self.current {
+            ret.push(format!("{prefix}lifetime current:"));
+            ret.push(format!(
+                "{prefix}  {}(bytes), {}(packets)",
+                cur.bytes, cur.packets
+            ));
+            ret.push(format!(
+                "{prefix}  add {} use {}",
+                time_to_string(cur.add_time),
+                time_to_string(cur.use_time)
+            ));
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `xfrm_stats_print()`
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CliXfrmStats {
+    replay_window: u32,
+    replay: u32,
+    failed: u32,
+}
+
+impl CliXfrmStats {
+    pub(crate) fn new(stats: &XfrmStats) -> Self {
+        Self {
+            replay_window: stats.replay_window,
+            replay: stats.replay,
+            failed: stats.integrity_failed,
+        }
+    }
+
+    pub(crate) fn lines(&self, prefix: &str) -> Vec<String> {
+        vec![
+            format!("{prefix}stats:"),
+            format!(
+                "{prefix}  replay-window {} replay {} failed {}",
+                self.replay_window, self.replay, self.failed
+            ),
+        ]
+    }
+}
+
+/// Equal to iproute2 `__xfrm_algo_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmAlgo {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    /// The key length in bits shown in statistics mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_len: Option<u32>,
+    /// The truncation length of `auth-trunc` or ICV length of `aead`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    len: Option<u32>,
+}
+
+impl CliXfrmAlgo {
+    fn new(
+        kind: &'static str,
+        algo: &XfrmAlgo,
+        nokeys: bool,
+        show_stats: bool,
+    ) -> Self {
+        let has_key = !algo.key.is_empty();
+        Self {
+            kind,
+            name: algo.name.clone(),
+            key: if nokeys {
+                Some("<<Keys hidden>>".to_string())
+            } else if has_key {
+                Some(format!(
+                    "0x{}",
+                    algo.key
+                        .iter()
+                        .map(|b| format!("{b:02x}"))
+                        .collect::<String>()
+                ))
+            } else {
+                None
+            },
+            key_len: (!nokeys && has_key && show_stats).then_some(algo.key_len),
+            len: algo.extra,
+        }
+    }
+}
+
+impl std::fmt::Display for CliXfrmAlgo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ", self.kind, self.name)?;
+        if let Some(v) = &self.key {
+            write!(f, "{v}")?;
+        }
+        if let Some(v) = self.key_len {
+            write!(f, " ({v} bits)")?;
+        }
+        if let Some(v) = self.len {
+            write!(f, " {v}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmEncap {
+    #[serde(rename = "type")]
+    kind: String,
+    sport: u16,
+    dport: u16,
+    addr: IpAddr,
+}
+
+impl std::fmt::Display for CliXfrmEncap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "encap type {} sport {} dport {} addr {}",
+            self.kind, self.sport, self.dport, self.addr
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliXfrmReplay {
+    seq: u32,
+    oseq: u32,
+    bitmap: u32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmReplayEsn {
+    seq_hi: u32,
+    seq: u32,
+    oseq_hi: u32,
+    oseq: u32,
+    replay_window: u32,
+    bitmap_length: u32,
+    bitmap: Vec<u32>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmOffload {
+    dev: String,
+    dir: &'static str,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliXfrmMark {
+    value: u32,
+    mask: u32,
+}
+
+/// Equal to iproute2 `strxf_share()`
+pub(crate) fn share_to_name(share: u8) -> String {
+    match share {
+        0 => "any".to_string(),
+        1 => "session".to_string(),
+        2 => "user".to_string(),
+        3 => "unique".to_string(),
+        _ => share.to_string(),
+    }
+}
+
+/// Equal to iproute2 `xfrm_tmpl_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmTmpl {
+    #[serde(flatten)]
+    id: CliXfrmIdInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    share: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enc_mask: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_mask: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comp_mask: Option<u32>,
+}
+
+impl CliXfrmTmpl {
+    fn new(tmpl: &XfrmUserTmpl, show_stats: bool) -> Self {
+        Self {
+            id: CliXfrmIdInfo::new(
+                &tmpl.saddr,
+                &tmpl.id,
+                tmpl.mode,
+                tmpl.reqid,
+                tmpl.family,
+                false,
+                show_stats,
+            ),
+            level: match tmpl.optional {
+                0 => show_stats.then(|| "required".to_string()),
+                1 => Some("use".to_string()),
+                v => Some(v.to_string()),
+            },
+            share: show_stats.then(|| share_to_name(tmpl.share)),
+            enc_mask: show_stats.then_some(tmpl.ealgos),
+            auth_mask: show_stats.then_some(tmpl.aalgos),
+            comp_mask: show_stats.then_some(tmpl.calgos),
+        }
+    }
+
+    fn lines(&self, prefix: &str) -> Vec<String> {
+        let mut ret = self.id.lines(prefix, &format!("{prefix}tmpl "));
+        if self.level.is_some() || self.share.is_some() {
+            let mut line = format!("{prefix}\t");
+            if let Some(v) = &self.level {
+                line.push_str(&format!("level {v} "));
+            }
+            if let Some(v) = &self.share {
+                line.push_str(&format!("share {v} "));
+            }
+            ret.push(line);
+        }
+        if let (Some(enc), Some(auth), Some(comp)) =
+            (self.enc_mask, self.auth_mask, self.comp_mask)
+        {
+            ret.push(format!(
+                "{prefix}\tenc-mask {enc:08x} auth-mask {auth:08x} comp-mask \
+                 {comp:08x}"
+            ));
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `xfrm_xfrma_print()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliXfrmAttrs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mark: Option<CliXfrmMark>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_mark: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_mark_mask: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    algos: Vec<CliXfrmAlgo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encap: Option<CliXfrmEncap>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tmpls: Vec<CliXfrmTmpl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    coa: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lastused: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replay: Option<CliXfrmReplay>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replay_esn: Option<CliXfrmReplayEsn>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offload: Option<CliXfrmOffload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tfcpad: Option<u32>,
+}
+
+impl CliXfrmAttrs {
+    pub(crate) fn new(
+        attrs: &[XfrmAttr],
+        family: u16,
+        nokeys: bool,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        let mut ret = Self::default();
+        let has_auth_trunc =
+            attrs.iter().any(|a| matches!(a, XfrmAttr::AlgAuthTrunc(_)));
+        // Like iproute2, the algorithms are shown in the order of auth,
+        // auth-trunc, aead, enc and comp.
+        let mut algos: Vec<(u8, CliXfrmAlgo)> = Vec::new();
+        for attr in attrs {
+            match attr {
+                XfrmAttr::Mark(v) => {
+                    ret.mark = Some(CliXfrmMark {
+                        value: v.value,
+                        mask: v.mask,
+                    })
+                }
+                XfrmAttr::SetMark(v) => ret.output_mark = Some(*v),
+                XfrmAttr::SetMarkMask(v) => ret.output_mark_mask = Some(*v),
+                XfrmAttr::AlgAuth(v) if !has_auth_trunc => algos
+                    .push((0, CliXfrmAlgo::new("auth", v, nokeys, show_stats))),
+                XfrmAttr::AlgAuthTrunc(v) => algos.push((
+                    1,
+                    CliXfrmAlgo::new("auth-trunc", v, nokeys, show_stats),
+                )),
+                XfrmAttr::AlgAead(v) => algos
+                    .push((2, CliXfrmAlgo::new("aead", v, nokeys, show_stats))),
+                XfrmAttr::AlgCrypt(v) => algos
+                    .push((3, CliXfrmAlgo::new("enc", v, nokeys, show_stats))),
+                XfrmAttr::AlgComp(v) => algos
+                    .push((4, CliXfrmAlgo::new("comp", v, nokeys, show_stats))),
+                XfrmAttr::Encap(v) => {
+                    ret.encap = Some(CliXfrmEncap {
+                        kind: XFRM_ENCAP_NAMES
+                            .iter()
+                            .find(|(_, kind)| *kind == v.encap_type)
+                            .map(|(name, _)| name.to_string())
+                            .unwrap_or_else(|| v.encap_type.to_string()),
+                        sport: v.sport,
+                        dport: v.dport,
+                        addr: v.oa.to_ip(family),
+                    })
+                }
+                XfrmAttr::Tmpl(v) => {
+                    ret.tmpls = v
+                        .iter()
+                        .map(|tmpl| CliXfrmTmpl::new(tmpl, show_stats))
+                        .collect()
+                }
+                XfrmAttr::CoAddr(v) => ret.coa = Some(v.to_ip(family)),
+                XfrmAttr::LastUsed(v) => ret.lastused = Some(*v),
+                XfrmAttr::ReplayVal(v) => {
+                    ret.replay = Some(CliXfrmReplay {
+                        seq: v.seq,
+                        oseq: v.oseq,
+                        bitmap: v.bitmap,
+                    })
+                }
+                XfrmAttr::ReplayEsnVal(v) => {
+                    ret.replay_esn = Some(CliXfrmReplayEsn {
+                        seq_hi: v.seq_hi,
+                        seq: v.seq,
+                        oseq_hi: v.oseq_hi,
+                        oseq: v.oseq,
+                        replay_window: v.replay_window,
+                        bitmap_length: v.bmp_len,
+                        bitmap: v.bmp.clone(),
+                    })
+                }
+                XfrmAttr::OffloadDev(v) => {
+                    ret.offload = Some(CliXfrmOffload {
+                        dev: ifindex_to_name(ifnames, v.ifindex),
+                        dir: if v.flags & XFRM_OFFLOAD_INBOUND > 0 {
+                            "in"
+                        } else {
+                            "out"
+                        },
+                    })
+                }
+                XfrmAttr::IfId(v) => ret.if_id = Some(*v),
+                XfrmAttr::TfcPad(v) => ret.tfcpad = Some(*v),
+                _ => (),
+            }
+        }
+        algos.sort_by_key(|(order, _)| *order);
+        ret.algos = algos.into_iter().map(|(_, algo)| algo).collect();
+        ret
+    }
+
+    pub(crate) fn lines(&self, prefix: &str) -> Vec<String> {
+        let mut ret = Vec::new();
+        // Like iproute2, the marks are indented by tab regardless of prefix
+        if let Some(v) = &self.mark {
+            ret.push(format!(
+                "\tmark {}/{} ",
+                alt_hex(v.value),
+                alt_hex(v.mask)
+            ));
+        }
+        if let Some(v) = self.output_mark {
+            let mut line = format!("\toutput-mark 0x{v:x}");
+            if let Some(mask) = self.output_mark_mask {
+                line.push_str(&format!("/0x{mask:x}"));
+            }
+            ret.push(line);
+        }
+        for algo in &self.algos {
+            ret.push(format!("{prefix}{algo}"));
+        }
+        if let Some(v) = &self.encap {
+            ret.push(format!("{prefix}{v}"));
+        }
+        for tmpl in &self.tmpls {
+            ret.extend(tmpl.lines(prefix));
+        }
+        if let Some(v) = &self.coa {
+            ret.push(format!("{prefix}coa {v}"));
+        }
+        if let Some(v) = self.lastused {
+            ret.push(format!("{prefix}lastused {}", time_to_string(v)));
+        }
+        if let Some(v) = &self.replay {
+            ret.push(format!(
+                "{prefix}anti-replay context: seq 0x{:x}, oseq 0x{:x}, bitmap \
+                 0x{:08x}",
+                v.seq, v.oseq, v.bitmap
+            ));
+        }
+        if let Some(v) = &self.replay_esn {
+            ret.push(format!("{prefix}anti-replay esn context:"));
+            ret.push(format!(
+                "{prefix} seq-hi 0x{:x}, seq 0x{:x}, oseq-hi 0x{:x}, oseq \
+                 0x{:x}",
+                v.seq_hi, v.seq, v.oseq_hi, v.oseq
+            ));
+            let mut line = format!(
+                "{prefix} replay_window {}, bitmap-length {}",
+                v.replay_window, v.bitmap_length
+            );
+            // Like iproute2, the bitmap is shown from the last word with 8
+            // words per line
+            for (i, word) in v.bitmap.iter().rev().enumerate() {
+                if i % 8 == 0 {
+                    ret.push(line);
+                    line = format!("{prefix} ");
+                }
+                line.push_str(&format!("{word:08x} "));
+            }
+            ret.push(line);
+        }
+        if let Some(v) = &self.offload {
+            ret.push(format!(
+                "{prefix}crypto offload parameters: dev {} dir {}",
+                v.dev, v.dir
+            ));
+        }
+        if let Some(v) = self.if_id {
+            ret.push(format!("{prefix}if_id {}", alt_hex(v)));
+        }
+        if let Some(v) = self.tfcpad {
+            ret.push(format!("{prefix}tfcpad {v}"));
+        }
+        ret
+    }
+}
+
+/// Equal to iproute2 `xfrm_id_info_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmIdInfo {
+    src: IpAddr,
+    dst: IpAddr,
+    proto: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spi: Option<u32>,
+    reqid: u32,
+    mode: String,
+    #[serde(skip)]
+    show_stats: bool,
+}
+
+impl CliXfrmIdInfo {
+    pub(crate) fn new(
+        saddr: &XfrmAddress,
+        id: &XfrmId,
+        mode: u8,
+        reqid: u32,
+        family: u16,
+        force_spi: bool,
+        show_stats: bool,
+    ) -> Self {
+        Self {
+            src: saddr.to_ip(family),
+            dst: id.daddr.to_ip(family),
+            proto: xfrm_proto_to_name(id.proto),
+            spi: (show_stats || force_spi || id.spi != 0).then_some(id.spi),
+            reqid,
+            mode: mode_to_name(mode),
+            show_stats,
+        }
+    }
+
+    /// The first line holding addresses is prefixed by `title` while the
+    /// second line by `prefix` and tab.
+    pub(crate) fn lines(&self, prefix: &str, title: &str) -> Vec<String> {
+        let mut line = format!("{prefix}\tproto {} ", self.proto);
+        if let Some(spi) = self.spi {
+            line.push_str(&format!("spi 0x{spi:08x}"));
+            if self.show_stats {
+                line.push_str(&format!("({spi})"));
+            }
+            line.push(' ');
+        }
+        line.push_str(&format!("reqid {}", self.reqid));
+        if self.show_stats {
+            line.push_str(&format!("(0x{:08x})", self.reqid));
+        }
+        line.push_str(&format!(" mode {}", self.mode));
+        vec![format!("{title}src {} dst {}", self.src, self.dst), line]
+    }
+}
@@ -0,0 +1,1573 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use rtnetlink::{
+    packet_core::{
+        DecodeError, DefaultNla, Emitable, ErrorContext, NetlinkDeserializable,
+        NetlinkHeader, NetlinkSerializable, Nla, NlaBuffer, NlasIterator,
+        Parseable, parse_u32, parse_u64,
+    },
+    sys::protocols::NETLINK_XFRM,
+};
+
+use crate::context::ProtoMessage;
+
+// Equal to `linux/xfrm.h`
+const XFRM_MSG_NEWSA: u16 = 0x10;
+const XFRM_MSG_DELSA: u16 = 0x11;
+const XFRM_MSG_GETSA: u16 = 0x12;
+const XFRM_MSG_NEWPOLICY: u16 = 0x13;
+const XFRM_MSG_DELPOLICY: u16 = 0x14;
+const XFRM_MSG_GETPOLICY: u16 = 0x15;
+const XFRM_MSG_ALLOCSPI: u16 = 0x16;
+const XFRM_MSG_ACQUIRE: u16 = 0x17;
+const XFRM_MSG_EXPIRE: u16 = 0x18;
+const XFRM_MSG_UPDPOLICY: u16 = 0x19;
+const XFRM_MSG_UPDSA: u16 = 0x1a;
+const XFRM_MSG_POLEXPIRE: u16 = 0x1b;
+const XFRM_MSG_FLUSHSA: u16 = 0x1c;
+const XFRM_MSG_FLUSHPOLICY: u16 = 0x1d;
+const XFRM_MSG_NEWAE: u16 = 0x1e;
+const XFRM_MSG_REPORT: u16 = 0x20;
+const XFRM_MSG_NEWSADINFO: u16 = 0x22;
+const XFRM_MSG_GETSADINFO: u16 = 0x23;
+const XFRM_MSG_NEWSPDINFO: u16 = 0x24;
+const XFRM_MSG_GETSPDINFO: u16 = 0x25;
+const XFRM_MSG_MAPPING: u16 = 0x26;
+
+const XFRMA_ALG_AUTH: u16 = 1;
+const XFRMA_ALG_CRYPT: u16 = 2;
+const XFRMA_ALG_COMP: u16 = 3;
+const XFRMA_ENCAP: u16 = 4;
+const XFRMA_TMPL: u16 = 5;
+const XFRMA_SA: u16 = 6;
+const XFRMA_POLICY: u16 = 7;
+const XFRMA_REPLAY_VAL: u16 = 10;
+const XFRMA_SRCADDR: u16 = 13;
+const XFRMA_COADDR: u16 = 14;
+const XFRMA_LASTUSED: u16 = 15;
+const XFRMA_POLICY_TYPE: u16 = 16;
+const XFRMA_ALG_AEAD: u16 = 18;
+const XFRMA_ALG_AUTH_TRUNC: u16 = 20;
+const XFRMA_MARK: u16 = 21;
+const XFRMA_TFCPAD: u16 = 22;
+const XFRMA_REPLAY_ESN_VAL: u16 = 23;
+const XFRMA_SA_EXTRA_FLAGS: u16 = 24;
+const XFRMA_PROTO: u16 = 25;
+const XFRMA_ADDRESS_FILTER: u16 = 26;
+const XFRMA_OFFLOAD_DEV: u16 = 28;
+const XFRMA_SET_MARK: u16 = 29;
+const XFRMA_SET_MARK_MASK: u16 = 30;
+const XFRMA_IF_ID: u16 = 31;
+
+const XFRMA_SAD_CNT: u16 = 1;
+const XFRMA_SAD_HINFO: u16 = 2;
+
+const XFRMA_SPD_INFO: u16 = 1;
+const XFRMA_SPD_HINFO: u16 = 2;
+const XFRMA_SPD_IPV4_HTHRESH: u16 = 3;
+const XFRMA_SPD_IPV6_HTHRESH: u16 = 4;
+
+pub(crate) const XFRM_INF: u64 = u64::MAX;
+
+pub(crate) const XFRM_STATE_NOECN: u8 = 1;
+pub(crate) const XFRM_STATE_DECAP_DSCP: u8 = 2;
+pub(crate) const XFRM_STATE_NOPMTUDISC: u8 = 4;
+pub(crate) const XFRM_STATE_WILDRECV: u8 = 8;
+pub(crate) const XFRM_STATE_ICMP: u8 = 16;
+pub(crate) const XFRM_STATE_AF_UNSPEC: u8 = 32;
+pub(crate) const XFRM_STATE_ALIGN4: u8 = 64;
+pub(crate) const XFRM_STATE_ESN: u8 = 128;
+
+pub(crate) const XFRM_SA_XFLAG_DONT_ENCAP_DSCP: u32 = 1;
+pub(crate) const XFRM_SA_XFLAG_OSEQ_MAY_WRAP: u32 = 2;
+
+pub(crate) const XFRM_OFFLOAD_INBOUND: u8 = 2;
+
+pub(crate) const XFRM_POLICY_IN: u8 = 0;
+pub(crate) const XFRM_POLICY_OUT: u8 = 1;
+pub(crate) const XFRM_POLICY_FWD: u8 = 2;
+pub(crate) const XFRM_POLICY_MAX: u8 = 3;
+
+pub(crate) const XFRM_POLICY_ALLOW: u8 = 0;
+pub(crate) const XFRM_POLICY_BLOCK: u8 = 1;
+
+pub(crate) const XFRM_POLICY_LOCALOK: u8 = 1;
+pub(crate) const XFRM_POLICY_ICMP: u8 = 2;
+
+pub(crate) const XFRM_POLICY_TYPE_MAIN: u8 = 0;
+pub(crate) const XFRM_POLICY_TYPE_SUB: u8 = 1;
+
+// Equal to `linux/socket.h`
+pub(crate) const AF_INET: u16 = 2;
+pub(crate) const AF_INET6: u16 = 10;
+
+// The size of kernel structures in `linux/xfrm.h`
+const XFRM_ADDRESS_LEN: usize = 16;
+const XFRM_SELECTOR_LEN: usize = 56;
+const XFRM_ID_LEN: usize = 24;
+const XFRM_LIFETIME_CFG_LEN: usize = 64;
+const XFRM_LIFETIME_CUR_LEN: usize = 32;
+const XFRM_USERSA_INFO_LEN: usize = 224;
+const XFRM_USERSA_ID_LEN: usize = 24;
+const XFRM_USERSPI_INFO_LEN: usize = 232;
+const XFRM_USERPOLICY_INFO_LEN: usize = 168;
+const XFRM_USERPOLICY_ID_LEN: usize = 64;
+const XFRM_USER_TMPL_LEN: usize = 64;
+const XFRM_USER_EXPIRE_LEN: usize = 232;
+const XFRM_USER_POLEXPIRE_LEN: usize = 176;
+const XFRM_USER_ACQUIRE_LEN: usize = 280;
+const XFRM_USER_REPORT_LEN: usize = 60;
+const XFRM_AEVENT_ID_LEN: usize = 48;
+const XFRM_USER_MAPPING_LEN: usize = 64;
+const XFRM_USERPOLICY_TYPE_LEN: usize = 6;
+const XFRM_ALGO_NAME_LEN: usize = 64;
+const XFRM_ENCAP_TMPL_LEN: usize = 24;
+const XFRM_MARK_LEN: usize = 8;
+const XFRM_REPLAY_STATE_LEN: usize = 12;
+const XFRM_REPLAY_STATE_ESN_LEN: usize = 24;
+const XFRM_USER_OFFLOAD_LEN: usize = 8;
+const XFRM_ADDRESS_FILTER_LEN: usize = 36;
+const XFRMU_SPDINFO_LEN: usize = 24;
+
+fn u16_at(buf: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn be16_at(buf: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn u32_at(buf: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+fn u64_at(buf: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[offset..offset + 8]);
+    u64::from_ne_bytes(bytes)
+}
+
+fn check_len(buf: &[u8], len: usize, name: &str) -> Result<(), DecodeError> {
+    if buf.len() < len {
+        Err(DecodeError::from(format!(
+            "Invalid {name} length {}",
+            buf.len()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XfrmNetlinkMessage {
+    NewSa(XfrmSaMessage),
+    UpdSa(XfrmSaMessage),
+    DelSa(XfrmSaIdMessage),
+    GetSa(XfrmSaIdMessage),
+    /// Dump request of `XFRM_MSG_GETSA` holding the filter attributes only
+    DumpSa(Vec<XfrmAttr>),
+    AllocSpi(XfrmSpiMessage),
+    /// The `struct xfrm_usersa_flush` holding the XFRM protocol
+    FlushSa(u8),
+    GetSadInfo,
+    NewSadInfo(Vec<XfrmSadInfoAttr>),
+    NewPolicy(XfrmPolicyMessage),
+    UpdPolicy(XfrmPolicyMessage),
+    DelPolicy(XfrmPolicyIdMessage),
+    GetPolicy(XfrmPolicyIdMessage),
+    /// Dump request of `XFRM_MSG_GETPOLICY` without payload
+    DumpPolicy,
+    /// The `XFRM_MSG_FLUSHPOLICY` holding the policy type attribute only
+    FlushPolicy(Vec<XfrmAttr>),
+    GetSpdInfo,
+    NewSpdInfo(Vec<XfrmSpdInfoAttr>),
+    /// The notifications below are only received from the kernel
+    Acquire(XfrmAcquireMessage),
+    Expire(XfrmExpireMessage),
+    PolExpire(XfrmPolExpireMessage),
+    NewAe(XfrmAeventId),
+    Report(XfrmReportMessage),
+    Mapping(XfrmUserMapping),
+}
+
+impl NetlinkSerializable for XfrmNetlinkMessage {
+    fn message_type(&self) -> u16 {
+        match self {
+            Self::NewSa(_) => XFRM_MSG_NEWSA,
+            Self::UpdSa(_) => XFRM_MSG_UPDSA,
+            Self::DelSa(_) => XFRM_MSG_DELSA,
+            Self::GetSa(_) | Self::DumpSa(_) => XFRM_MSG_GETSA,
+            Self::AllocSpi(_) => XFRM_MSG_ALLOCSPI,
+            Self::FlushSa(_) => XFRM_MSG_FLUSHSA,
+            Self::GetSadInfo => XFRM_MSG_GETSADINFO,
+            Self::NewSadInfo(_) => XFRM_MSG_NEWSADINFO,
+            Self::NewPolicy(_) => XFRM_MSG_NEWPOLICY,
+            Self::UpdPolicy(_) => XFRM_MSG_UPDPOLICY,
+            Self::DelPolicy(_) => XFRM_MSG_DELPOLICY,
+            Self::GetPolicy(_) | Self::DumpPolicy => XFRM_MSG_GETPOLICY,
+            Self::FlushPolicy(_) => XFRM_MSG_FLUSHPOLICY,
+            Self::GetSpdInfo => XFRM_MSG_GETSPDINFO,
+            Self::NewSpdInfo(_) => XFRM_MSG_NEWSPDINFO,
+            Self::Acquire(_) => XFRM_MSG_ACQUIRE,
+            Self::Expire(_) => XFRM_MSG_EXPIRE,
+            Self::PolExpire(_) => XFRM_MSG_POLEXPIRE,
+            Self::NewAe(_) => XFRM_MSG_NEWAE,
+            Self::Report(_) => XFRM_MSG_REPORT,
+            Self::Mapping(_) => XFRM_MSG_MAPPING,
+        }
+    }
+
+    fn buffer_len(&self) -> usize {
+        match self {
+            Self::NewSa(msg) | Self::UpdSa(msg) => msg.buffer_len(),
+            Self::DelSa(msg) | Self::GetSa(msg) => msg.buffer_len(),
+            Self::DumpSa(attrs) => attrs.as_slice().buffer_len(),
+            Self::AllocSpi(msg) => msg.buffer_len(),
+            Self::FlushSa(_) => 1,
+            Self::GetSadInfo | Self::GetSpdInfo => 4,
+            Self::NewSadInfo(attrs) => 4 + attrs.as_slice().buffer_len(),
+            Self::NewPolicy(msg) | Self::UpdPolicy(msg) => msg.buffer_len(),
+            Self::DelPolicy(msg) | Self::GetPolicy(msg) => msg.buffer_len(),
+            Self::DumpPolicy => 0,
+            Self::FlushPolicy(attrs) => attrs.as_slice().buffer_len(),
+            Self::NewSpdInfo(attrs) => 4 + attrs.as_slice().buffer_len(),
+            Self::Acquire(_)
+            | Self::Expire(_)
+            | Self::PolExpire(_)
+            | Self::NewAe(_)
+            | Self::Report(_)
+            | Self::Mapping(_) => 0,
+        }
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        match self {
+            Self::NewSa(msg) | Self::UpdSa(msg) => msg.emit(buffer),
+            Self::DelSa(msg) | Self::GetSa(msg) => msg.emit(buffer),
+            Self::DumpSa(attrs) => attrs.as_slice().emit(buffer),
+            Self::AllocSpi(msg) => msg.emit(buffer),
+            Self::FlushSa(proto) => buffer[0] = *proto,
+            // Like iproute2 `xfrm_sad_getinfo()`, request all the flags
+            Self::GetSadInfo | Self::GetSpdInfo => {
+                buffer[..4].copy_from_slice(&[0xff; 4])
+            }
+            Self::NewSadInfo(attrs) => {
+                buffer[..4].fill(0);
+                attrs.as_slice().emit(&mut buffer[4..]);
+            }
+            Self::NewPolicy(msg) | Self::UpdPolicy(msg) => msg.emit(buffer),
+            Self::DelPolicy(msg) | Self::GetPolicy(msg) => msg.emit(buffer),
+            Self::DumpPolicy => (),
+            Self::FlushPolicy(attrs) => attrs.as_slice().emit(buffer),
+            // Like iproute2 `xfrm_spd_setinfo()`, all the flags are set
+            Self::NewSpdInfo(attrs) => {
+                buffer[..4].copy_from_slice(&[0xff; 4]);
+                attrs.as_slice().emit(&mut buffer[4..]);
+            }
+            Self::Acquire(_)
+            | Self::Expire(_)
+            | Self::PolExpire(_)
+            | Self::NewAe(_)
+            | Self::Report(_)
+            | Self::Mapping(_) => (),
+        }
+    }
+}
+
+impl NetlinkDeserializable for XfrmNetlinkMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        Ok(match header.message_type {
+            XFRM_MSG_NEWSA => Self::NewSa(XfrmSaMessage::parse(payload)?),
+            XFRM_MSG_UPDSA => Self::UpdSa(XfrmSaMessage::parse(payload)?),
+            XFRM_MSG_DELSA => Self::DelSa(XfrmSaIdMessage::parse(payload)?),
+            XFRM_MSG_GETSA => Self::GetSa(XfrmSaIdMessage::parse(payload)?),
+            XFRM_MSG_NEWSADINFO => {
+                check_len(payload, 4, "sadinfo")?;
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(&payload[4..]) {
+                    let nla = nla.context("invalid sadinfo attribute")?;
+                    attrs.push(XfrmSadInfoAttr::parse(&nla)?);
+                }
+                Self::NewSadInfo(attrs)
+            }
+            XFRM_MSG_FLUSHSA => {
+                check_len(payload, 1, "xfrm_usersa_flush")?;
+                Self::FlushSa(payload[0])
+            }
+            XFRM_MSG_NEWPOLICY => {
+                Self::NewPolicy(XfrmPolicyMessage::parse(payload)?)
+            }
+            XFRM_MSG_UPDPOLICY => {
+                Self::UpdPolicy(XfrmPolicyMessage::parse(payload)?)
+            }
+            XFRM_MSG_DELPOLICY => {
+                Self::DelPolicy(XfrmPolicyIdMessage::parse(payload)?)
+            }
+            XFRM_MSG_GETPOLICY => {
+                Self::GetPolicy(XfrmPolicyIdMessage::parse(payload)?)
+            }
+            XFRM_MSG_FLUSHPOLICY => Self::FlushPolicy(parse_attrs(payload)?),
+            XFRM_MSG_ACQUIRE => {
+                Self::Acquire(XfrmAcquireMessage::parse(payload)?)
+            }
+            XFRM_MSG_EXPIRE => Self::Expire(XfrmExpireMessage::parse(payload)?),
+            XFRM_MSG_POLEXPIRE => {
+                Self::PolExpire(XfrmPolExpireMessage::parse(payload)?)
+            }
+            XFRM_MSG_NEWAE => Self::NewAe(XfrmAeventId::parse(payload)?),
+            XFRM_MSG_REPORT => Self::Report(XfrmReportMessage::parse(payload)?),
+            XFRM_MSG_MAPPING => Self::Mapping(XfrmUserMapping::parse(payload)?),
+            XFRM_MSG_NEWSPDINFO => {
+                check_len(payload, 4, "spdinfo")?;
+                let mut attrs = Vec::new();
+                for nla in NlasIterator::new(&payload[4..]) {
+                    let nla = nla.context("invalid spdinfo attribute")?;
+                    attrs.push(XfrmSpdInfoAttr::parse(&nla)?);
+                }
+                Self::NewSpdInfo(attrs)
+            }
+            kind => {
+                return Err(DecodeError::from(format!(
+                    "Unknown xfrm message type {kind}"
+                )));
+            }
+        })
+    }
+}
+
+impl ProtoMessage for XfrmNetlinkMessage {
+    const PROTOCOL: isize = NETLINK_XFRM;
+}
+
+fn parse_attrs(buf: &[u8]) -> Result<Vec<XfrmAttr>, DecodeError> {
+    let mut attrs = Vec::new();
+    for nla in NlasIterator::new(buf) {
+        let nla = nla.context("invalid xfrm attribute")?;
+        attrs.push(XfrmAttr::parse(&nla)?);
+    }
+    Ok(attrs)
+}
+
+/// Equal to kernel `xfrm_address_t`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmAddress(pub(crate) [u8; XFRM_ADDRESS_LEN]);
+
+impl XfrmAddress {
+    pub(crate) fn from_ip(addr: IpAddr) -> Self {
+        let mut ret = Self::default();
+        match addr {
+            IpAddr::V4(v) => ret.0[..4].copy_from_slice(&v.octets()),
+            IpAddr::V6(v) => ret.0.copy_from_slice(&v.octets()),
+        }
+        ret
+    }
+
+    /// Like iproute2 `rt_addr_n2a()`, the address is interpreted by the
+    /// `AF_*` family.
+    pub(crate) fn to_ip(self, family: u16) -> IpAddr {
+        if family == AF_INET6 {
+            IpAddr::V6(Ipv6Addr::from(self.0))
+        } else {
+            IpAddr::V4(Ipv4Addr::new(
+                self.0[0], self.0[1], self.0[2], self.0[3],
+            ))
+        }
+    }
+
+    fn parse(buf: &[u8]) -> Self {
+        let mut ret = Self::default();
+        ret.0.copy_from_slice(&buf[..XFRM_ADDRESS_LEN]);
+        ret
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_ADDRESS_LEN].copy_from_slice(&self.0);
+    }
+}
+
+/// Equal to kernel `struct xfrm_selector`, the ports are in host order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmSelector {
+    pub(crate) daddr: XfrmAddress,
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) dport: u16,
+    pub(crate) dport_mask: u16,
+    pub(crate) sport: u16,
+    pub(crate) sport_mask: u16,
+    pub(crate) family: u16,
+    pub(crate) prefixlen_d: u8,
+    pub(crate) prefixlen_s: u8,
+    pub(crate) proto: u8,
+    pub(crate) ifindex: i32,
+    pub(crate) user: u32,
+}
+
+impl XfrmSelector {
+    pub(crate) fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            daddr: XfrmAddress::parse(&buf[0..]),
+            saddr: XfrmAddress::parse(&buf[16..]),
+            dport: be16_at(buf, 32),
+            dport_mask: u16_at(buf, 34),
+            sport: be16_at(buf, 36),
+            sport_mask: u16_at(buf, 38),
+            family: u16_at(buf, 40),
+            prefixlen_d: buf[42],
+            prefixlen_s: buf[43],
+            proto: buf[44],
+            ifindex: u32_at(buf, 48) as i32,
+            user: u32_at(buf, 52),
+        }
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_SELECTOR_LEN].fill(0);
+        self.daddr.emit(&mut buf[0..]);
+        self.saddr.emit(&mut buf[16..]);
+        buf[32..34].copy_from_slice(&self.dport.to_be_bytes());
+        buf[34..36].copy_from_slice(&self.dport_mask.to_ne_bytes());
+        buf[36..38].copy_from_slice(&self.sport.to_be_bytes());
+        buf[38..40].copy_from_slice(&self.sport_mask.to_ne_bytes());
+        buf[40..42].copy_from_slice(&self.family.to_ne_bytes());
+        buf[42] = self.prefixlen_d;
+        buf[43] = self.prefixlen_s;
+        buf[44] = self.proto;
+        buf[48..52].copy_from_slice(&self.ifindex.to_ne_bytes());
+        buf[52..56].copy_from_slice(&self.user.to_ne_bytes());
+    }
+}
+
+/// Equal to kernel `struct xfrm_id`, the SPI is in host order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmId {
+    pub(crate) daddr: XfrmAddress,
+    pub(crate) spi: u32,
+    pub(crate) proto: u8,
+}
+
+impl XfrmId {
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            daddr: XfrmAddress::parse(buf),
+            spi: u32::from_be_bytes([buf[16], buf[17], buf[18], buf[19]]),
+            proto: buf[20],
+        }
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_ID_LEN].fill(0);
+        self.daddr.emit(buf);
+        buf[16..20].copy_from_slice(&self.spi.to_be_bytes());
+        buf[20] = self.proto;
+    }
+}
+
+/// Equal to kernel `struct xfrm_lifetime_cfg`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmLifetimeCfg {
+    pub(crate) soft_byte_limit: u64,
+    pub(crate) hard_byte_limit: u64,
+    pub(crate) soft_packet_limit: u64,
+    pub(crate) hard_packet_limit: u64,
+    pub(crate) soft_add_expires_seconds: u64,
+    pub(crate) hard_add_expires_seconds: u64,
+    pub(crate) soft_use_expires_seconds: u64,
+    pub(crate) hard_use_expires_seconds: u64,
+}
+
+impl XfrmLifetimeCfg {
+    /// Like iproute2, the byte and packet limits default to `XFRM_INF`
+    pub(crate) fn unlimited() -> Self {
+        Self {
+            soft_byte_limit: XFRM_INF,
+            hard_byte_limit: XFRM_INF,
+            soft_packet_limit: XFRM_INF,
+            hard_packet_limit: XFRM_INF,
+            ..Default::default()
+        }
+    }
+
+    fn fields(&self) -> [u64; 8] {
+        [
+            self.soft_byte_limit,
+            self.hard_byte_limit,
+            self.soft_packet_limit,
+            self.hard_packet_limit,
+            self.soft_add_expires_seconds,
+            self.hard_add_expires_seconds,
+            self.soft_use_expires_seconds,
+            self.hard_use_expires_seconds,
+        ]
+    }
+
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            soft_byte_limit: u64_at(buf, 0),
+            hard_byte_limit: u64_at(buf, 8),
+            soft_packet_limit: u64_at(buf, 16),
+            hard_packet_limit: u64_at(buf, 24),
+            soft_add_expires_seconds: u64_at(buf, 32),
+            hard_add_expires_seconds: u64_at(buf, 40),
+            soft_use_expires_seconds: u64_at(buf, 48),
+            hard_use_expires_seconds: u64_at(buf, 56),
+        }
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        for (value, chunk) in self.fields().iter().zip(buf.chunks_mut(8)) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+    }
+}
+
+/// Equal to kernel `struct xfrm_lifetime_cur`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmLifetimeCur {
+    pub(crate) bytes: u64,
+    pub(crate) packets: u64,
+    pub(crate) add_time: u64,
+    pub(crate) use_time: u64,
+}
+
+impl XfrmLifetimeCur {
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            bytes: u64_at(buf, 0),
+            packets: u64_at(buf, 8),
+            add_time: u64_at(buf, 16),
+            use_time: u64_at(buf, 24),
+        }
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        for (value, chunk) in
+            [self.bytes, self.packets, self.add_time, self.use_time]
+                .iter()
+                .zip(buf.chunks_mut(8))
+        {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+    }
+}
+
+/// Equal to kernel `struct xfrm_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmStats {
+    pub(crate) replay_window: u32,
+    pub(crate) replay: u32,
+    pub(crate) integrity_failed: u32,
+}
+
+/// Equal to kernel `struct xfrm_usersa_info`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUsersaInfo {
+    pub(crate) sel: XfrmSelector,
+    pub(crate) id: XfrmId,
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) lft: XfrmLifetimeCfg,
+    pub(crate) curlft: XfrmLifetimeCur,
+    pub(crate) stats: XfrmStats,
+    pub(crate) seq: u32,
+    pub(crate) reqid: u32,
+    pub(crate) family: u16,
+    pub(crate) mode: u8,
+    pub(crate) replay_window: u8,
+    pub(crate) flags: u8,
+}
+
+impl XfrmUsersaInfo {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USERSA_INFO_LEN, "xfrm_usersa_info")?;
+        Ok(Self {
+            sel: XfrmSelector::parse(&buf[0..]),
+            id: XfrmId::parse(&buf[56..]),
+            saddr: XfrmAddress::parse(&buf[80..]),
+            lft: XfrmLifetimeCfg::parse(&buf[96..]),
+            curlft: XfrmLifetimeCur::parse(&buf[160..]),
+            stats: XfrmStats {
+                replay_window: u32_at(buf, 192),
+                replay: u32_at(buf, 196),
+                integrity_failed: u32_at(buf, 200),
+            },
+            seq: u32_at(buf, 204),
+            reqid: u32_at(buf, 208),
+            family: u16_at(buf, 212),
+            mode: buf[214],
+            replay_window: buf[215],
+            flags: buf[216],
+        })
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_USERSA_INFO_LEN].fill(0);
+        self.sel.emit(&mut buf[0..]);
+        self.id.emit(&mut buf[56..]);
+        self.saddr.emit(&mut buf[80..]);
+        self.lft.emit(&mut buf[96..96 + XFRM_LIFETIME_CFG_LEN]);
+        self.curlft.emit(&mut buf[160..160 + XFRM_LIFETIME_CUR_LEN]);
+        buf[192..196].copy_from_slice(&self.stats.replay_window.to_ne_bytes());
+        buf[196..200].copy_from_slice(&self.stats.replay.to_ne_bytes());
+        buf[200..204]
+            .copy_from_slice(&self.stats.integrity_failed.to_ne_bytes());
+        buf[204..208].copy_from_slice(&self.seq.to_ne_bytes());
+        buf[208..212].copy_from_slice(&self.reqid.to_ne_bytes());
+        buf[212..214].copy_from_slice(&self.family.to_ne_bytes());
+        buf[214] = self.mode;
+        buf[215] = self.replay_window;
+        buf[216] = self.flags;
+    }
+}
+
+/// The `XFRM_MSG_NEWSA` and `XFRM_MSG_UPDSA` message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmSaMessage {
+    pub(crate) info: XfrmUsersaInfo,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmSaMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            info: XfrmUsersaInfo::parse(buf)?,
+            attributes: parse_attrs(&buf[XFRM_USERSA_INFO_LEN..])?,
+        })
+    }
+}
+
+impl Emitable for XfrmSaMessage {
+    fn buffer_len(&self) -> usize {
+        XFRM_USERSA_INFO_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.info.emit(buffer);
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[XFRM_USERSA_INFO_LEN..]);
+    }
+}
+
+/// Equal to kernel `struct xfrm_usersa_id`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUsersaId {
+    pub(crate) daddr: XfrmAddress,
+    pub(crate) spi: u32,
+    pub(crate) family: u16,
+    pub(crate) proto: u8,
+}
+
+impl XfrmUsersaId {
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            daddr: XfrmAddress::parse(buf),
+            spi: u32::from_be_bytes([buf[16], buf[17], buf[18], buf[19]]),
+            family: u16_at(buf, 20),
+            proto: buf[22],
+        }
+    }
+}
+
+/// The `XFRM_MSG_DELSA` and `XFRM_MSG_GETSA` message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmSaIdMessage {
+    pub(crate) id: XfrmUsersaId,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmSaIdMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USERSA_ID_LEN, "xfrm_usersa_id")?;
+        Ok(Self {
+            id: XfrmUsersaId::parse(buf),
+            attributes: parse_attrs(&buf[XFRM_USERSA_ID_LEN..])?,
+        })
+    }
+}
+
+impl Emitable for XfrmSaIdMessage {
+    fn buffer_len(&self) -> usize {
+        XFRM_USERSA_ID_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[..XFRM_USERSA_ID_LEN].fill(0);
+        self.id.daddr.emit(buffer);
+        buffer[16..20].copy_from_slice(&self.id.spi.to_be_bytes());
+        buffer[20..22].copy_from_slice(&self.id.family.to_ne_bytes());
+        buffer[22] = self.id.proto;
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[XFRM_USERSA_ID_LEN..]);
+    }
+}
+
+/// The `XFRM_MSG_ALLOCSPI` message holding `struct xfrm_userspi_info`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmSpiMessage {
+    pub(crate) info: XfrmUsersaInfo,
+    pub(crate) min: u32,
+    pub(crate) max: u32,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl Emitable for XfrmSpiMessage {
+    fn buffer_len(&self) -> usize {
+        XFRM_USERSPI_INFO_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.info.emit(buffer);
+        buffer[XFRM_USERSA_INFO_LEN..XFRM_USERSA_INFO_LEN + 4]
+            .copy_from_slice(&self.min.to_ne_bytes());
+        buffer[XFRM_USERSA_INFO_LEN + 4..XFRM_USERSPI_INFO_LEN]
+            .copy_from_slice(&self.max.to_ne_bytes());
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[XFRM_USERSPI_INFO_LEN..]);
+    }
+}
+
+/// Equal to kernel `struct xfrm_userpolicy_info`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUserpolicyInfo {
+    pub(crate) sel: XfrmSelector,
+    pub(crate) lft: XfrmLifetimeCfg,
+    pub(crate) curlft: XfrmLifetimeCur,
+    pub(crate) priority: u32,
+    pub(crate) index: u32,
+    pub(crate) dir: u8,
+    pub(crate) action: u8,
+    pub(crate) flags: u8,
+    pub(crate) share: u8,
+}
+
+impl XfrmUserpolicyInfo {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USERPOLICY_INFO_LEN, "xfrm_userpolicy_info")?;
+        Ok(Self {
+            sel: XfrmSelector::parse(&buf[0..]),
+            lft: XfrmLifetimeCfg::parse(&buf[56..]),
+            curlft: XfrmLifetimeCur::parse(&buf[120..]),
+            priority: u32_at(buf, 152),
+            index: u32_at(buf, 156),
+            dir: buf[160],
+            action: buf[161],
+            flags: buf[162],
+            share: buf[163],
+        })
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_USERPOLICY_INFO_LEN].fill(0);
+        self.sel.emit(&mut buf[0..]);
+        self.lft.emit(&mut buf[56..56 + XFRM_LIFETIME_CFG_LEN]);
+        self.curlft.emit(&mut buf[120..120 + XFRM_LIFETIME_CUR_LEN]);
+        buf[152..156].copy_from_slice(&self.priority.to_ne_bytes());
+        buf[156..160].copy_from_slice(&self.index.to_ne_bytes());
+        buf[160] = self.dir;
+        buf[161] = self.action;
+        buf[162] = self.flags;
+        buf[163] = self.share;
+    }
+}
+
+/// The `XFRM_MSG_NEWPOLICY` and `XFRM_MSG_UPDPOLICY` message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmPolicyMessage {
+    pub(crate) info: XfrmUserpolicyInfo,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmPolicyMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            info: XfrmUserpolicyInfo::parse(buf)?,
+            attributes: parse_attrs(&buf[XFRM_USERPOLICY_INFO_LEN..])?,
+        })
+    }
+}
+
+impl Emitable for XfrmPolicyMessage {
+    fn buffer_len(&self) -> usize {
+        XFRM_USERPOLICY_INFO_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.info.emit(buffer);
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[XFRM_USERPOLICY_INFO_LEN..]);
+    }
+}
+
+/// Equal to kernel `struct xfrm_userpolicy_id`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUserpolicyId {
+    pub(crate) sel: XfrmSelector,
+    pub(crate) index: u32,
+    pub(crate) dir: u8,
+}
+
+/// The `XFRM_MSG_DELPOLICY` and `XFRM_MSG_GETPOLICY` message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmPolicyIdMessage {
+    pub(crate) id: XfrmUserpolicyId,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmPolicyIdMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USERPOLICY_ID_LEN, "xfrm_userpolicy_id")?;
+        Ok(Self {
+            id: XfrmUserpolicyId {
+                sel: XfrmSelector::parse(buf),
+                index: u32_at(buf, 56),
+                dir: buf[60],
+            },
+            attributes: parse_attrs(&buf[XFRM_USERPOLICY_ID_LEN..])?,
+        })
+    }
+}
+
+impl Emitable for XfrmPolicyIdMessage {
+    fn buffer_len(&self) -> usize {
+        XFRM_USERPOLICY_ID_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[..XFRM_USERPOLICY_ID_LEN].fill(0);
+        self.id.sel.emit(buffer);
+        buffer[56..60].copy_from_slice(&self.id.index.to_ne_bytes());
+        buffer[60] = self.id.dir;
+        self.attributes
+            .as_slice()
+            .emit(&mut buffer[XFRM_USERPOLICY_ID_LEN..]);
+    }
+}
+
+/// The `XFRM_MSG_EXPIRE` notification holding `struct xfrm_user_expire`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmExpireMessage {
+    pub(crate) state: XfrmUsersaInfo,
+    pub(crate) hard: u8,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmExpireMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USER_EXPIRE_LEN, "xfrm_user_expire")?;
+        Ok(Self {
+            state: XfrmUsersaInfo::parse(buf)?,
+            hard: buf[XFRM_USERSA_INFO_LEN],
+            attributes: parse_attrs(&buf[XFRM_USER_EXPIRE_LEN..])?,
+        })
+    }
+}
+
+/// The `XFRM_MSG_POLEXPIRE` notification holding
+/// `struct xfrm_user_polexpire`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmPolExpireMessage {
+    pub(crate) pol: XfrmUserpolicyInfo,
+    pub(crate) hard: u8,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmPolExpireMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USER_POLEXPIRE_LEN, "xfrm_user_polexpire")?;
+        Ok(Self {
+            pol: XfrmUserpolicyInfo::parse(buf)?,
+            hard: buf[XFRM_USERPOLICY_INFO_LEN],
+            attributes: parse_attrs(&buf[XFRM_USER_POLEXPIRE_LEN..])?,
+        })
+    }
+}
+
+/// The `XFRM_MSG_ACQUIRE` notification holding `struct xfrm_user_acquire`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmAcquireMessage {
+    pub(crate) id: XfrmId,
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) sel: XfrmSelector,
+    pub(crate) policy: XfrmUserpolicyInfo,
+    pub(crate) aalgos: u32,
+    pub(crate) ealgos: u32,
+    pub(crate) calgos: u32,
+    pub(crate) seq: u32,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmAcquireMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USER_ACQUIRE_LEN, "xfrm_user_acquire")?;
+        Ok(Self {
+            id: XfrmId::parse(&buf[0..]),
+            saddr: XfrmAddress::parse(&buf[24..]),
+            sel: XfrmSelector::parse(&buf[40..]),
+            policy: XfrmUserpolicyInfo::parse(&buf[96..])?,
+            aalgos: u32_at(buf, 264),
+            ealgos: u32_at(buf, 268),
+            calgos: u32_at(buf, 272),
+            seq: u32_at(buf, 276),
+            attributes: parse_attrs(&buf[XFRM_USER_ACQUIRE_LEN..])?,
+        })
+    }
+}
+
+/// The `XFRM_MSG_REPORT` notification holding `struct xfrm_user_report`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmReportMessage {
+    pub(crate) proto: u8,
+    pub(crate) sel: XfrmSelector,
+    pub(crate) attributes: Vec<XfrmAttr>,
+}
+
+impl XfrmReportMessage {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USER_REPORT_LEN, "xfrm_user_report")?;
+        Ok(Self {
+            proto: buf[0],
+            sel: XfrmSelector::parse(&buf[4..]),
+            attributes: parse_attrs(&buf[XFRM_USER_REPORT_LEN..])?,
+        })
+    }
+}
+
+/// Equal to kernel `struct xfrm_aevent_id` of `XFRM_MSG_NEWAE`
+/// notification, the replay and lifetime attributes are ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmAeventId {
+    pub(crate) sa_id: XfrmUsersaId,
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) flags: u32,
+    pub(crate) reqid: u32,
+}
+
+impl XfrmAeventId {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_AEVENT_ID_LEN, "xfrm_aevent_id")?;
+        Ok(Self {
+            sa_id: XfrmUsersaId::parse(buf),
+            saddr: XfrmAddress::parse(&buf[24..]),
+            flags: u32_at(buf, 40),
+            reqid: u32_at(buf, 44),
+        })
+    }
+}
+
+/// Equal to kernel `struct xfrm_user_mapping` of `XFRM_MSG_MAPPING`
+/// notification, the ports are in host order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUserMapping {
+    pub(crate) id: XfrmUsersaId,
+    pub(crate) reqid: u32,
+    pub(crate) old_saddr: XfrmAddress,
+    pub(crate) new_saddr: XfrmAddress,
+    pub(crate) old_sport: u16,
+    pub(crate) new_sport: u16,
+}
+
+impl XfrmUserMapping {
+    fn parse(buf: &[u8]) -> Result<Self, DecodeError> {
+        check_len(buf, XFRM_USER_MAPPING_LEN, "xfrm_user_mapping")?;
+        Ok(Self {
+            id: XfrmUsersaId::parse(buf),
+            reqid: u32_at(buf, 24),
+            old_saddr: XfrmAddress::parse(&buf[28..]),
+            new_saddr: XfrmAddress::parse(&buf[44..]),
+            old_sport: be16_at(buf, 60),
+            new_sport: be16_at(buf, 62),
+        })
+    }
+}
+
+/// Equal to kernel `struct xfrm_user_tmpl`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUserTmpl {
+    pub(crate) id: XfrmId,
+    pub(crate) family: u16,
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) reqid: u32,
+    pub(crate) mode: u8,
+    pub(crate) share: u8,
+    pub(crate) optional: u8,
+    pub(crate) aalgos: u32,
+    pub(crate) ealgos: u32,
+    pub(crate) calgos: u32,
+}
+
+impl XfrmUserTmpl {
+    fn parse(buf: &[u8]) -> Self {
+        Self {
+            id: XfrmId::parse(buf),
+            family: u16_at(buf, 24),
+            saddr: XfrmAddress::parse(&buf[28..]),
+            reqid: u32_at(buf, 44),
+            mode: buf[48],
+            share: buf[49],
+            optional: buf[50],
+            aalgos: u32_at(buf, 52),
+            ealgos: u32_at(buf, 56),
+            calgos: u32_at(buf, 60),
+        }
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_USER_TMPL_LEN].fill(0);
+        self.id.emit(buf);
+        buf[24..26].copy_from_slice(&self.family.to_ne_bytes());
+        self.saddr.emit(&mut buf[28..]);
+        buf[44..48].copy_from_slice(&self.reqid.to_ne_bytes());
+        buf[48] = self.mode;
+        buf[49] = self.share;
+        buf[50] = self.optional;
+        buf[52..56].copy_from_slice(&self.aalgos.to_ne_bytes());
+        buf[56..60].copy_from_slice(&self.ealgos.to_ne_bytes());
+        buf[60..64].copy_from_slice(&self.calgos.to_ne_bytes());
+    }
+}
+
+/// Equal to kernel `struct xfrm_algo`, `struct xfrm_algo_auth` and
+/// `struct xfrm_algo_aead`. The `extra` is the truncation length of
+/// `XFRMA_ALG_AUTH_TRUNC` or ICV length of `XFRMA_ALG_AEAD`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmAlgo {
+    pub(crate) name: String,
+    /// In bits
+    pub(crate) key_len: u32,
+    pub(crate) extra: Option<u32>,
+    pub(crate) key: Vec<u8>,
+}
+
+impl XfrmAlgo {
+    fn header_len(&self) -> usize {
+        XFRM_ALGO_NAME_LEN + if self.extra.is_some() { 8 } else { 4 }
+    }
+
+    fn parse(buf: &[u8], with_extra: bool) -> Result<Self, DecodeError> {
+        let header_len = XFRM_ALGO_NAME_LEN + if with_extra { 8 } else { 4 };
+        check_len(buf, header_len, "xfrm_algo")?;
+        let name = &buf[..XFRM_ALGO_NAME_LEN];
+        let name_len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+        let key_len = u32_at(buf, XFRM_ALGO_NAME_LEN);
+        let key_end = (header_len + key_len as usize / 8).min(buf.len());
+        Ok(Self {
+            name: String::from_utf8_lossy(&name[..name_len]).to_string(),
+            key_len,
+            extra: with_extra.then(|| u32_at(buf, XFRM_ALGO_NAME_LEN + 4)),
+            key: buf[header_len..key_end].to_vec(),
+        })
+    }
+
+    fn emit(&self, buf: &mut [u8]) {
+        buf[..XFRM_ALGO_NAME_LEN].fill(0);
+        let name_len = self.name.len().min(XFRM_ALGO_NAME_LEN - 1);
+        buf[..name_len].copy_from_slice(&self.name.as_bytes()[..name_len]);
+        buf[XFRM_ALGO_NAME_LEN..XFRM_ALGO_NAME_LEN + 4]
+            .copy_from_slice(&self.key_len.to_ne_bytes());
+        if let Some(extra) = self.extra {
+            buf[XFRM_ALGO_NAME_LEN + 4..XFRM_ALGO_NAME_LEN + 8]
+                .copy_from_slice(&extra.to_ne_bytes());
+        }
+        let header_len = self.header_len();
+        buf[header_len..header_len + self.key.len()].copy_from_slice(&self.key);
+    }
+}
+
+/// Equal to kernel `struct xfrm_encap_tmpl`, the ports are in host order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmEncapTmpl {
+    pub(crate) encap_type: u16,
+    pub(crate) sport: u16,
+    pub(crate) dport: u16,
+    pub(crate) oa: XfrmAddress,
+}
+
+/// Equal to kernel `struct xfrm_mark`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmMark {
+    pub(crate) value: u32,
+    pub(crate) mask: u32,
+}
+
+/// Equal to kernel `struct xfrm_replay_state`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmReplayState {
+    pub(crate) oseq: u32,
+    pub(crate) seq: u32,
+    pub(crate) bitmap: u32,
+}
+
+/// Equal to kernel `struct xfrm_replay_state_esn`, the `bmp` could be
+/// shorter than `bmp_len` as kernel accepts request without bitmap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct XfrmReplayStateEsn {
+    pub(crate) bmp_len: u32,
+    pub(crate) oseq: u32,
+    pub(crate) seq: u32,
+    pub(crate) oseq_hi: u32,
+    pub(crate) seq_hi: u32,
+    pub(crate) replay_window: u32,
+    pub(crate) bmp: Vec<u32>,
+}
+
+/// Equal to kernel `struct xfrm_user_offload`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmUserOffload {
+    pub(crate) ifindex: i32,
+    pub(crate) flags: u8,
+}
+
+/// Equal to kernel `struct xfrm_address_filter`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmAddressFilter {
+    pub(crate) saddr: XfrmAddress,
+    pub(crate) daddr: XfrmAddress,
+    pub(crate) family: u16,
+    pub(crate) splen: u8,
+    pub(crate) dplen: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XfrmAttr {
+    AlgAuth(XfrmAlgo),
+    AlgCrypt(XfrmAlgo),
+    AlgComp(XfrmAlgo),
+    Encap(XfrmEncapTmpl),
+    Tmpl(Vec<XfrmUserTmpl>),
+    /// The deleted SA in `XFRM_MSG_DELSA` notification
+    Sa(Box<XfrmUsersaInfo>),
+    /// The deleted policy in `XFRM_MSG_DELPOLICY` notification
+    Policy(Box<XfrmUserpolicyInfo>),
+    ReplayVal(XfrmReplayState),
+    SrcAddr(XfrmAddress),
+    CoAddr(XfrmAddress),
+    LastUsed(u64),
+    /// Equal to kernel `struct xfrm_userpolicy_type`
+    PolicyType(u8),
+    AlgAead(XfrmAlgo),
+    AlgAuthTrunc(XfrmAlgo),
+    Mark(XfrmMark),
+    TfcPad(u32),
+    ReplayEsnVal(XfrmReplayStateEsn),
+    SaExtraFlags(u32),
+    Proto(u8),
+    AddressFilter(XfrmAddressFilter),
+    OffloadDev(XfrmUserOffload),
+    SetMark(u32),
+    SetMarkMask(u32),
+    IfId(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for XfrmAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::AlgAuth(v)
+            | Self::AlgCrypt(v)
+            | Self::AlgComp(v)
+            | Self::AlgAead(v)
+            | Self::AlgAuthTrunc(v) => v.header_len() + v.key.len(),
+            Self::Encap(_) => XFRM_ENCAP_TMPL_LEN,
+            Self::Tmpl(v) => v.len() * XFRM_USER_TMPL_LEN,
+            Self::PolicyType(_) => XFRM_USERPOLICY_TYPE_LEN,
+            Self::Sa(_) => XFRM_USERSA_INFO_LEN,
+            Self::Policy(_) => XFRM_USERPOLICY_INFO_LEN,
+            Self::ReplayVal(_) => XFRM_REPLAY_STATE_LEN,
+            Self::SrcAddr(_) | Self::CoAddr(_) => XFRM_ADDRESS_LEN,
+            Self::LastUsed(_) => 8,
+            Self::Mark(_) => XFRM_MARK_LEN,
+            Self::TfcPad(_)
+            | Self::SaExtraFlags(_)
+            | Self::SetMark(_)
+            | Self::SetMarkMask(_)
+            | Self::IfId(_) => 4,
+            Self::ReplayEsnVal(v) => {
+                XFRM_REPLAY_STATE_ESN_LEN + v.bmp.len() * 4
+            }
+            Self::Proto(_) => 1,
+            Self::AddressFilter(_) => XFRM_ADDRESS_FILTER_LEN,
+            Self::OffloadDev(_) => XFRM_USER_OFFLOAD_LEN,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::AlgAuth(_) => XFRMA_ALG_AUTH,
+            Self::AlgCrypt(_) => XFRMA_ALG_CRYPT,
+            Self::AlgComp(_) => XFRMA_ALG_COMP,
+            Self::Encap(_) => XFRMA_ENCAP,
+            Self::Tmpl(_) => XFRMA_TMPL,
+            Self::PolicyType(_) => XFRMA_POLICY_TYPE,
+            Self::Sa(_) => XFRMA_SA,
+            Self::Policy(_) => XFRMA_POLICY,
+            Self::ReplayVal(_) => XFRMA_REPLAY_VAL,
+            Self::SrcAddr(_) => XFRMA_SRCADDR,
+            Self::CoAddr(_) => XFRMA_COADDR,
+            Self::LastUsed(_) => XFRMA_LASTUSED,
+            Self::AlgAead(_) => XFRMA_ALG_AEAD,
+            Self::AlgAuthTrunc(_) => XFRMA_ALG_AUTH_TRUNC,
+            Self::Mark(_) => XFRMA_MARK,
+            Self::TfcPad(_) => XFRMA_TFCPAD,
+            Self::ReplayEsnVal(_) => XFRMA_REPLAY_ESN_VAL,
+            Self::SaExtraFlags(_) => XFRMA_SA_EXTRA_FLAGS,
+            Self::Proto(_) => XFRMA_PROTO,
+            Self::AddressFilter(_) => XFRMA_ADDRESS_FILTER,
+            Self::OffloadDev(_) => XFRMA_OFFLOAD_DEV,
+            Self::SetMark(_) => XFRMA_SET_MARK,
+            Self::SetMarkMask(_) => XFRMA_SET_MARK_MASK,
+            Self::IfId(_) => XFRMA_IF_ID,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::AlgAuth(v)
+            | Self::AlgCrypt(v)
+            | Self::AlgComp(v)
+            | Self::AlgAead(v)
+            | Self::AlgAuthTrunc(v) => v.emit(buffer),
+            Self::Encap(v) => {
+                buffer.fill(0);
+                buffer[0..2].copy_from_slice(&v.encap_type.to_ne_bytes());
+                buffer[2..4].copy_from_slice(&v.sport.to_be_bytes());
+                buffer[4..6].copy_from_slice(&v.dport.to_be_bytes());
+                v.oa.emit(&mut buffer[8..]);
+            }
+            Self::Tmpl(v) => {
+                for (tmpl, chunk) in
+                    v.iter().zip(buffer.chunks_mut(XFRM_USER_TMPL_LEN))
+                {
+                    tmpl.emit(chunk);
+                }
+            }
+            Self::PolicyType(v) => {
+                buffer.fill(0);
+                buffer[0] = *v;
+            }
+            Self::Sa(v) => v.emit(buffer),
+            Self::Policy(v) => v.emit(buffer),
+            Self::ReplayVal(v) => {
+                for (value, chunk) in
+                    [v.oseq, v.seq, v.bitmap].iter().zip(buffer.chunks_mut(4))
+                {
+                    chunk.copy_from_slice(&value.to_ne_bytes());
+                }
+            }
+            Self::SrcAddr(v) | Self::CoAddr(v) => v.emit(buffer),
+            Self::LastUsed(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::Mark(v) => {
+                buffer[0..4].copy_from_slice(&v.value.to_ne_bytes());
+                buffer[4..8].copy_from_slice(&v.mask.to_ne_bytes());
+            }
+            Self::TfcPad(v)
+            | Self::SaExtraFlags(v)
+            | Self::SetMark(v)
+            | Self::SetMarkMask(v)
+            | Self::IfId(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::ReplayEsnVal(v) => {
+                for (value, chunk) in [
+                    v.bmp_len,
+                    v.oseq,
+                    v.seq,
+                    v.oseq_hi,
+                    v.seq_hi,
+                    v.replay_window,
+                ]
+                .iter()
+                .chain(v.bmp.iter())
+                .zip(buffer.chunks_mut(4))
+                {
+                    chunk.copy_from_slice(&value.to_ne_bytes());
+                }
+            }
+            Self::Proto(v) => buffer[0] = *v,
+            Self::AddressFilter(v) => {
+                v.saddr.emit(&mut buffer[0..]);
+                v.daddr.emit(&mut buffer[16..]);
+                buffer[32..34].copy_from_slice(&v.family.to_ne_bytes());
+                buffer[34] = v.splen;
+                buffer[35] = v.dplen;
+            }
+            Self::OffloadDev(v) => {
+                buffer.fill(0);
+                buffer[0..4].copy_from_slice(&v.ifindex.to_ne_bytes());
+                buffer[4] = v.flags;
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for XfrmAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            XFRMA_ALG_AUTH => Self::AlgAuth(XfrmAlgo::parse(payload, false)?),
+            XFRMA_ALG_CRYPT => Self::AlgCrypt(XfrmAlgo::parse(payload, false)?),
+            XFRMA_ALG_COMP => Self::AlgComp(XfrmAlgo::parse(payload, false)?),
+            XFRMA_ALG_AEAD => Self::AlgAead(XfrmAlgo::parse(payload, true)?),
+            XFRMA_ALG_AUTH_TRUNC => {
+                Self::AlgAuthTrunc(XfrmAlgo::parse(payload, true)?)
+            }
+            XFRMA_ENCAP => {
+                check_len(payload, XFRM_ENCAP_TMPL_LEN, "xfrm_encap_tmpl")?;
+                Self::Encap(XfrmEncapTmpl {
+                    encap_type: u16_at(payload, 0),
+                    sport: be16_at(payload, 2),
+                    dport: be16_at(payload, 4),
+                    oa: XfrmAddress::parse(&payload[8..]),
+                })
+            }
+            XFRMA_TMPL => Self::Tmpl(
+                payload
+                    .chunks_exact(XFRM_USER_TMPL_LEN)
+                    .map(XfrmUserTmpl::parse)
+                    .collect(),
+            ),
+            XFRMA_POLICY_TYPE => {
+                check_len(
+                    payload,
+                    XFRM_USERPOLICY_TYPE_LEN,
+                    "xfrm_userpolicy_type",
+                )?;
+                Self::PolicyType(payload[0])
+            }
+            XFRMA_SA => Self::Sa(Box::new(XfrmUsersaInfo::parse(payload)?)),
+            XFRMA_POLICY => {
+                Self::Policy(Box::new(XfrmUserpolicyInfo::parse(payload)?))
+            }
+            XFRMA_REPLAY_VAL => {
+                check_len(payload, XFRM_REPLAY_STATE_LEN, "xfrm_replay_state")?;
+                Self::ReplayVal(XfrmReplayState {
+                    oseq: u32_at(payload, 0),
+                    seq: u32_at(payload, 4),
+                    bitmap: u32_at(payload, 8),
+                })
+            }
+            XFRMA_SRCADDR | XFRMA_COADDR => {
+                check_len(payload, XFRM_ADDRESS_LEN, "xfrm_address_t")?;
+                let addr = XfrmAddress::parse(payload);
+                if buf.kind() == XFRMA_SRCADDR {
+                    Self::SrcAddr(addr)
+                } else {
+                    Self::CoAddr(addr)
+                }
+            }
+            XFRMA_LASTUSED => Self::LastUsed(parse_u64(payload)?),
+            XFRMA_MARK => {
+                check_len(payload, XFRM_MARK_LEN, "xfrm_mark")?;
+                Self::Mark(XfrmMark {
+                    value: u32_at(payload, 0),
+                    mask: u32_at(payload, 4),
+                })
+            }
+            XFRMA_TFCPAD => Self::TfcPad(parse_u32(payload)?),
+            XFRMA_REPLAY_ESN_VAL => {
+                check_len(
+                    payload,
+                    XFRM_REPLAY_STATE_ESN_LEN,
+                    "xfrm_replay_state_esn",
+                )?;
+                let bmp_len = u32_at(payload, 0);
+                Self::ReplayEsnVal(XfrmReplayStateEsn {
+                    bmp_len,
+                    oseq: u32_at(payload, 4),
+                    seq: u32_at(payload, 8),
+                    oseq_hi: u32_at(payload, 12),
+                    seq_hi: u32_at(payload, 16),
+                    replay_window: u32_at(payload, 20),
+                    bmp: payload[XFRM_REPLAY_STATE_ESN_LEN..]
+                        .chunks_exact(4)
+                        .take(bmp_len as usize)
+                        .map(|chunk| u32_at(chunk, 0))
+                        .collect(),
+                })
+            }
+            XFRMA_SA_EXTRA_FLAGS => Self::SaExtraFlags(parse_u32(payload)?),
+            XFRMA_OFFLOAD_DEV => {
+                check_len(payload, XFRM_USER_OFFLOAD_LEN, "xfrm_user_offload")?;
+                Self::OffloadDev(XfrmUserOffload {
+                    ifindex: u32_at(payload, 0) as i32,
+                    flags: payload[4],
+                })
+            }
+            XFRMA_SET_MARK => Self::SetMark(parse_u32(payload)?),
+            XFRMA_SET_MARK_MASK => Self::SetMarkMask(parse_u32(payload)?),
+            XFRMA_IF_ID => Self::IfId(parse_u32(payload)?),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The attribute of `XFRM_MSG_NEWSADINFO`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XfrmSadInfoAttr {
+    Count(u32),
+    /// Equal to kernel `struct xfrmu_sadhinfo`, the bucket count and
+    /// maximum bucket count.
+    HashInfo(u32, u32),
+    Other(DefaultNla),
+}
+
+impl Nla for XfrmSadInfoAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Count(_) => 4,
+            Self::HashInfo(..) => 8,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Count(_) => XFRMA_SAD_CNT,
+            Self::HashInfo(..) => XFRMA_SAD_HINFO,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Count(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            Self::HashInfo(count, max) => {
+                buffer[0..4].copy_from_slice(&count.to_ne_bytes());
+                buffer[4..8].copy_from_slice(&max.to_ne_bytes());
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for XfrmSadInfoAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            XFRMA_SAD_CNT => Self::Count(parse_u32(payload)?),
+            XFRMA_SAD_HINFO => {
+                check_len(payload, 8, "xfrmu_sadhinfo")?;
+                Self::HashInfo(u32_at(payload, 0), u32_at(payload, 4))
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// Equal to kernel `struct xfrmu_spdinfo`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct XfrmSpdInfo {
+    pub(crate) incnt: u32,
+    pub(crate) outcnt: u32,
+    pub(crate) fwdcnt: u32,
+    pub(crate) inscnt: u32,
+    pub(crate) outscnt: u32,
+    pub(crate) fwdscnt: u32,
+}
+
+/// The attribute of `XFRM_MSG_NEWSPDINFO`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XfrmSpdInfoAttr {
+    Info(XfrmSpdInfo),
+    /// Equal to kernel `struct xfrmu_spdhinfo`, the bucket count and
+    /// maximum bucket count.
+    HashInfo(u32, u32),
+    /// Equal to kernel `struct xfrmu_spdhthresh`, the local and remote
+    /// prefix length thresholds.
+    Ipv4HashThresh(u8, u8),
+    Ipv6HashThresh(u8, u8),
+    Other(DefaultNla),
+}
+
+impl Nla for XfrmSpdInfoAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Info(_) => XFRMU_SPDINFO_LEN,
+            Self::HashInfo(..) => 8,
+            Self::Ipv4HashThresh(..) | Self::Ipv6HashThresh(..) => 2,
+            Self::Other(v) => v.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Info(_) => XFRMA_SPD_INFO,
+            Self::HashInfo(..) => XFRMA_SPD_HINFO,
+            Self::Ipv4HashThresh(..) => XFRMA_SPD_IPV4_HTHRESH,
+            Self::Ipv6HashThresh(..) => XFRMA_SPD_IPV6_HTHRESH,
+            Self::Other(v) => v.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Info(v) => {
+                for (value, chunk) in [
+                    v.incnt, v.outcnt, v.fwdcnt, v.inscnt, v.outscnt, v.fwdscnt,
+                ]
+                .iter()
+                .zip(buffer.chunks_mut(4))
+                {
+                    chunk.copy_from_slice(&value.to_ne_bytes());
+                }
+            }
+            Self::HashInfo(count, max) => {
+                buffer[0..4].copy_from_slice(&count.to_ne_bytes());
+                buffer[4..8].copy_from_slice(&max.to_ne_bytes());
+            }
+            Self::Ipv4HashThresh(lbits, rbits)
+            | Self::Ipv6HashThresh(lbits, rbits) => {
+                buffer[0] = *lbits;
+                buffer[1] = *rbits;
+            }
+            Self::Other(v) => v.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&T>> for XfrmSpdInfoAttr {
+    fn parse(buf: &NlaBuffer<&T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            XFRMA_SPD_INFO => {
+                check_len(payload, XFRMU_SPDINFO_LEN, "xfrmu_spdinfo")?;
+                Self::Info(XfrmSpdInfo {
+                    incnt: u32_at(payload, 0),
+                    outcnt: u32_at(payload, 4),
+                    fwdcnt: u32_at(payload, 8),
+                    inscnt: u32_at(payload, 12),
+                    outscnt: u32_at(payload, 16),
+                    fwdscnt: u32_at(payload, 20),
+                })
+            }
+            XFRMA_SPD_HINFO => {
+                check_len(payload, 8, "xfrmu_spdhinfo")?;
+                Self::HashInfo(u32_at(payload, 0), u32_at(payload, 4))
+            }
+            XFRMA_SPD_IPV4_HTHRESH | XFRMA_SPD_IPV6_HTHRESH => {
+                check_len(payload, 2, "xfrmu_spdhthresh")?;
+                if buf.kind() == XFRMA_SPD_IPV4_HTHRESH {
+                    Self::Ipv4HashThresh(payload[0], payload[1])
+                } else {
+                    Self::Ipv6HashThresh(payload[0], payload[1])
+                }
+            }
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
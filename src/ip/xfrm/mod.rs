@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+
+mod cli;
+mod common;
+mod message;
+mod monitor;
+mod policy;
+mod state;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::cli::XfrmCommand;
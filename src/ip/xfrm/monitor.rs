@@ -0,0 +1,556 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::IpAddr};
+
+use futures_util::stream::StreamExt;
+use iproute_rs::{
+    CanDisplay, CanOutput, CliError, OutputFormat, gen_output_string,
+    print_output,
+};
+use rtnetlink::packet_core::NetlinkPayload;
+use serde::Serialize;
+
+use super::{
+    common::{
+        CliXfrmAttrs, CliXfrmSelector, proto_to_name, xfrm_proto_to_name,
+    },
+    message::{
+        XfrmAcquireMessage, XfrmAeventId, XfrmAttr, XfrmNetlinkMessage,
+        XfrmReportMessage, XfrmUserMapping,
+    },
+    policy::{CliXfrmPolicyInfo, ptype_to_name},
+    state::CliXfrmStateInfo,
+};
+use crate::{context::NetlinkContext, route::get_ifnames};
+
+const XFRMNLGRP_ACQUIRE: u32 = 1;
+const XFRMNLGRP_EXPIRE: u32 = 2;
+const XFRMNLGRP_SA: u32 = 3;
+const XFRMNLGRP_POLICY: u32 = 4;
+const XFRMNLGRP_AEVENTS: u32 = 5;
+const XFRMNLGRP_REPORT: u32 = 6;
+const XFRMNLGRP_MIGRATE: u32 = 7;
+const XFRMNLGRP_MAPPING: u32 = 8;
+
+const XFRM_AE_CR: u32 = 16;
+const XFRM_AE_CE: u32 = 32;
+const XFRM_AE_CU: u32 = 64;
+
+/// The objects to monitor following the argument grammar of iproute2
+/// `do_xfrm_monitor()`
+#[derive(Debug, Default)]
+struct XfrmMonitorOptions {
+    nokeys: bool,
+    groups: Vec<u32>,
+}
+
+impl XfrmMonitorOptions {
+    fn parse(opts: &[&str]) -> Result<Self, CliError> {
+        let mut ret = Self::default();
+        for opt in opts {
+            let group = match *opt {
+                "nokeys" => {
+                    ret.nokeys = true;
+                    continue;
+                }
+                "all" => continue,
+                "acquire" => XFRMNLGRP_ACQUIRE,
+                "expire" => XFRMNLGRP_EXPIRE,
+                "SA" => XFRMNLGRP_SA,
+                "aevent" => XFRMNLGRP_AEVENTS,
+                "policy" => XFRMNLGRP_POLICY,
+                "report" => XFRMNLGRP_REPORT,
+                _ => {
+                    return Err(CliError::from(
+                        format!(
+                            "Argument \"{opt}\" is unknown, try \"ip xfrm \
+                             monitor help\"."
+                        )
+                        .as_str(),
+                    ));
+                }
+            };
+            if !ret.groups.contains(&group) {
+                ret.groups.push(group);
+            }
+        }
+        // Like iproute2, all the groups are monitored by default
+        if ret.groups.is_empty() {
+            ret.groups = vec![
+                XFRMNLGRP_ACQUIRE,
+                XFRMNLGRP_EXPIRE,
+                XFRMNLGRP_SA,
+                XFRMNLGRP_POLICY,
+                XFRMNLGRP_AEVENTS,
+                XFRMNLGRP_REPORT,
+                XFRMNLGRP_MIGRATE,
+                XFRMNLGRP_MAPPING,
+            ];
+        }
+        Ok(ret)
+    }
+}
+
+/// Equal to iproute2 `xfrm_acquire_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmAcquire {
+    proto: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spi: Option<u32>,
+    sel: CliXfrmSelector,
+    policy: CliXfrmPolicyInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enc_mask: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_mask: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comp_mask: Option<u32>,
+    #[serde(skip)]
+    show_stats: bool,
+}
+
+impl CliXfrmAcquire {
+    fn new(
+        msg: &XfrmAcquireMessage,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        let family = if msg.sel.family == 0 {
+            msg.policy.sel.family
+        } else {
+            msg.sel.family
+        };
+        Self {
+            proto: xfrm_proto_to_name(msg.id.proto),
+            spi: (show_stats || msg.id.spi != 0).then_some(msg.id.spi),
+            sel: CliXfrmSelector::new(&msg.sel, family, show_stats, ifnames),
+            policy: CliXfrmPolicyInfo::new(
+                &msg.policy,
+                &msg.attributes,
+                show_stats,
+                ifnames,
+            ),
+            seq: show_stats.then_some(msg.seq),
+            enc_mask: show_stats.then_some(msg.ealgos),
+            auth_mask: show_stats.then_some(msg.aalgos),
+            comp_mask: show_stats.then_some(msg.calgos),
+            show_stats,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut line = format!("acquire proto {} ", self.proto);
+        if let Some(spi) = self.spi {
+            line.push_str(&format!("spi 0x{spi:08x}"));
+            if self.show_stats {
+                line.push_str(&format!("({spi})"));
+            }
+            line.push(' ');
+        }
+        let mut ret = vec![line, format!("  sel {}", self.sel)];
+        ret.extend(self.policy.lines_with_title("    ", "  policy "));
+        let mut line = String::new();
+        if let Some(seq) = self.seq {
+            // Like iproute2, the sequence is shown by `0x%08u`
+            line.push_str(&format!("  seq 0x{seq:08} "));
+        }
+        if let (Some(enc), Some(auth), Some(comp)) =
+            (self.enc_mask, self.auth_mask, self.comp_mask)
+        {
+            line.push_str(&format!(
+                "enc-mask {enc:08x} auth-mask {auth:08x} comp-mask {comp:08x}"
+            ));
+        }
+        // Like iproute2, the line is printed even empty
+        ret.push(line);
+        ret
+    }
+}
+
+/// Equal to iproute2 `xfrm_report_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmReport {
+    proto: String,
+    sel: CliXfrmSelector,
+    #[serde(flatten)]
+    attrs: CliXfrmAttrs,
+}
+
+impl CliXfrmReport {
+    fn new(
+        msg: &XfrmReportMessage,
+        nokeys: bool,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        Self {
+            proto: xfrm_proto_to_name(msg.proto),
+            sel: CliXfrmSelector::new(
+                &msg.sel,
+                msg.sel.family,
+                show_stats,
+                ifnames,
+            ),
+            attrs: CliXfrmAttrs::new(
+                &msg.attributes,
+                msg.sel.family,
+                nokeys,
+                show_stats,
+                ifnames,
+            ),
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut ret = vec![
+            format!("report proto {} ", self.proto),
+            format!("  sel {}", self.sel),
+        ];
+        ret.extend(self.attrs.lines("  "));
+        ret
+    }
+}
+
+/// Equal to iproute2 `xfrm_usersa_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmUsersaId {
+    dst: IpAddr,
+    reqid: u32,
+    protocol: String,
+    spi: u32,
+}
+
+impl std::fmt::Display for CliXfrmUsersaId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "dst {}  reqid 0x{:x} protocol {}  SPI 0x{:x}",
+            self.dst, self.reqid, self.protocol, self.spi
+        )
+    }
+}
+
+/// Equal to iproute2 `xfrm_ae_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmAevent {
+    flags: u32,
+    src: IpAddr,
+    #[serde(flatten)]
+    id: CliXfrmUsersaId,
+}
+
+impl From<&XfrmAeventId> for CliXfrmAevent {
+    fn from(msg: &XfrmAeventId) -> Self {
+        Self {
+            flags: msg.flags,
+            src: msg.saddr.to_ip(msg.sa_id.family),
+            id: CliXfrmUsersaId {
+                dst: msg.sa_id.daddr.to_ip(msg.sa_id.family),
+                reqid: msg.reqid,
+                protocol: proto_to_name(msg.sa_id.proto),
+                spi: msg.sa_id.spi,
+            },
+        }
+    }
+}
+
+impl CliXfrmAevent {
+    fn lines(&self) -> Vec<String> {
+        // Equal to iproute2 `xfrm_ae_flags_print()`
+        let mut line = format!("Async event  (0x{:x}) ", self.flags);
+        for (flag, name) in [
+            (XFRM_AE_CR, "replay update"),
+            (XFRM_AE_CE, "timer expired"),
+            (XFRM_AE_CU, "policy updated"),
+        ] {
+            if self.flags & flag != 0 {
+                line.push_str(&format!(" {name} "));
+            }
+        }
+        vec![line, format!("\tsrc {} {}", self.src, self.id)]
+    }
+}
+
+/// Equal to iproute2 `xfrm_mapping_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmMapping {
+    old_src: IpAddr,
+    old_sport: u16,
+    new_src: IpAddr,
+    new_sport: u16,
+    #[serde(flatten)]
+    id: CliXfrmUsersaId,
+}
+
+impl From<&XfrmUserMapping> for CliXfrmMapping {
+    fn from(msg: &XfrmUserMapping) -> Self {
+        Self {
+            old_src: msg.old_saddr.to_ip(msg.id.family),
+            old_sport: msg.old_sport,
+            new_src: msg.new_saddr.to_ip(msg.id.family),
+            new_sport: msg.new_sport,
+            id: CliXfrmUsersaId {
+                dst: msg.id.daddr.to_ip(msg.id.family),
+                reqid: msg.reqid,
+                protocol: proto_to_name(msg.id.proto),
+                spi: msg.id.spi,
+            },
+        }
+    }
+}
+
+impl CliXfrmMapping {
+    fn lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Mapping change {}:{} -> {}:{}",
+                self.old_src, self.old_sport, self.new_src, self.new_sport
+            ),
+            format!("\t{}", self.id),
+        ]
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+enum CliXfrmMonitorObject {
+    State(Box<CliXfrmStateInfo>),
+    Policy(Box<CliXfrmPolicyInfo>),
+    Acquire(Box<CliXfrmAcquire>),
+    Report(Box<CliXfrmReport>),
+    /// Equal to iproute2 `xfrm_state_flush_print()`
+    FlushState {
+        proto: String,
+    },
+    /// Equal to iproute2 `xfrm_policy_flush_print()`
+    FlushPolicy {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ptype: Option<String>,
+    },
+    Aevent(CliXfrmAevent),
+    Mapping(CliXfrmMapping),
+}
+
+impl CliXfrmMonitorObject {
+    fn lines(&self) -> Vec<String> {
+        match self {
+            Self::State(v) => v.lines(),
+            Self::Policy(v) => v.lines(),
+            Self::Acquire(v) => v.lines(),
+            Self::Report(v) => v.lines(),
+            Self::FlushState { proto } => {
+                vec![format!("Flushed state proto {proto}")]
+            }
+            Self::FlushPolicy { ptype } => vec![match ptype {
+                Some(ptype) => format!("Flushed policy ptype {ptype} "),
+                None => "Flushed policy ".to_string(),
+            }],
+            Self::Aevent(v) => v.lines(),
+            Self::Mapping(v) => v.lines(),
+        }
+    }
+}
+
+/// The XFRM notification received by `ip xfrm monitor`, equal to iproute2
+/// `xfrm_accept_msg()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct CliXfrmMonitorEvent {
+    /// The `deleted`, `updated` or `expired` of states and policies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<&'static str>,
+    #[serde(flatten)]
+    object: CliXfrmMonitorObject,
+    /// Whether the hard lifetime is expired
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hard: Option<u8>,
+}
+
+impl CliXfrmMonitorEvent {
+    fn new(object: CliXfrmMonitorObject) -> Self {
+        Self {
+            event: None,
+            object,
+            hard: None,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut ret = self.object.lines();
+        if let Some(first) = ret.first_mut() {
+            match self.event {
+                Some("deleted") => first.insert_str(0, "Deleted "),
+                Some("updated") => first.insert_str(0, "Updated "),
+                Some("expired") => first.insert_str(0, "Expired "),
+                _ => (),
+            }
+        }
+        if let Some(hard) = self.hard {
+            ret.push(format!("\thard {hard}"));
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliXfrmMonitorEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl CanDisplay for CliXfrmMonitorEvent {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, every line including the last one ends with `\`
+    // except the asynchronous and mapping events which are always printed
+    // in multiple lines.
+    fn gen_oneline_string(&self) -> String {
+        match self.object {
+            CliXfrmMonitorObject::Aevent(_)
+            | CliXfrmMonitorObject::Mapping(_) => self.to_string(),
+            _ => format!("{}\\", self.lines().join("\\")),
+        }
+    }
+}
+
+impl CanOutput for CliXfrmMonitorEvent {}
+
+fn parse_nl_msg_to_event(
+    msg: XfrmNetlinkMessage,
+    nokeys: bool,
+    show_stats: bool,
+    ifnames: &HashMap<u32, String>,
+) -> Option<CliXfrmMonitorEvent> {
+    let state = |info, attrs: &[XfrmAttr]| {
+        CliXfrmMonitorObject::State(Box::new(CliXfrmStateInfo::new(
+            info, attrs, nokeys, show_stats, ifnames,
+        )))
+    };
+    let policy = |info, attrs: &[XfrmAttr]| {
+        CliXfrmMonitorObject::Policy(Box::new(CliXfrmPolicyInfo::new(
+            info, attrs, show_stats, ifnames,
+        )))
+    };
+    Some(match msg {
+        XfrmNetlinkMessage::NewSa(msg) => {
+            CliXfrmMonitorEvent::new(state(&msg.info, &msg.attributes))
+        }
+        XfrmNetlinkMessage::UpdSa(msg) => CliXfrmMonitorEvent {
+            event: Some("updated"),
+            ..CliXfrmMonitorEvent::new(state(&msg.info, &msg.attributes))
+        },
+        XfrmNetlinkMessage::DelSa(msg) => {
+            // Like iproute2, the deleted SA is held by attribute
+            let Some(info) =
+                msg.attributes.iter().find_map(|attr| match attr {
+                    XfrmAttr::Sa(v) => Some(v.as_ref()),
+                    _ => None,
+                })
+            else {
+                log::error!("Buggy XFRM_MSG_DELSA: no XFRMA_SA");
+                return None;
+            };
+            CliXfrmMonitorEvent {
+                event: Some("deleted"),
+                ..CliXfrmMonitorEvent::new(state(info, &msg.attributes))
+            }
+        }
+        XfrmNetlinkMessage::Expire(msg) => CliXfrmMonitorEvent {
+            event: Some("expired"),
+            object: state(&msg.state, &msg.attributes),
+            hard: Some(msg.hard),
+        },
+        XfrmNetlinkMessage::NewPolicy(msg) => {
+            CliXfrmMonitorEvent::new(policy(&msg.info, &msg.attributes))
+        }
+        XfrmNetlinkMessage::UpdPolicy(msg) => CliXfrmMonitorEvent {
+            event: Some("updated"),
+            ..CliXfrmMonitorEvent::new(policy(&msg.info, &msg.attributes))
+        },
+        XfrmNetlinkMessage::DelPolicy(msg) => {
+            // Like iproute2, the deleted policy is held by attribute
+            let Some(info) =
+                msg.attributes.iter().find_map(|attr| match attr {
+                    XfrmAttr::Policy(v) => Some(v.as_ref()),
+                    _ => None,
+                })
+            else {
+                log::error!("Buggy XFRM_MSG_DELPOLICY: no XFRMA_POLICY");
+                return None;
+            };
+            CliXfrmMonitorEvent {
+                event: Some("deleted"),
+                ..CliXfrmMonitorEvent::new(policy(info, &msg.attributes))
+            }
+        }
+        XfrmNetlinkMessage::PolExpire(msg) => CliXfrmMonitorEvent {
+            event: Some("expired"),
+            object: policy(&msg.pol, &msg.attributes),
+            hard: Some(msg.hard),
+        },
+        XfrmNetlinkMessage::Acquire(msg) => {
+            CliXfrmMonitorEvent::new(CliXfrmMonitorObject::Acquire(Box::new(
+                CliXfrmAcquire::new(&msg, show_stats, ifnames),
+            )))
+        }
+        XfrmNetlinkMessage::FlushSa(proto) => {
+            CliXfrmMonitorEvent::new(CliXfrmMonitorObject::FlushState {
+                proto: xfrm_proto_to_name(proto),
+            })
+        }
+        XfrmNetlinkMessage::FlushPolicy(attrs) => {
+            CliXfrmMonitorEvent::new(CliXfrmMonitorObject::FlushPolicy {
+                ptype: attrs.iter().find_map(|attr| match attr {
+                    XfrmAttr::PolicyType(v) => Some(ptype_to_name(*v)),
+                    _ => None,
+                }),
+            })
+        }
+        XfrmNetlinkMessage::Report(msg) => {
+            CliXfrmMonitorEvent::new(CliXfrmMonitorObject::Report(Box::new(
+                CliXfrmReport::new(&msg, nokeys, show_stats, ifnames),
+            )))
+        }
+        XfrmNetlinkMessage::NewAe(msg) => CliXfrmMonitorEvent::new(
+            CliXfrmMonitorObject::Aevent(CliXfrmAevent::from(&msg)),
+        ),
+        XfrmNetlinkMessage::Mapping(msg) => CliXfrmMonitorEvent::new(
+            CliXfrmMonitorObject::Mapping(CliXfrmMapping::from(&msg)),
+        ),
+        _ => return None,
+    })
+}
+
+/// Equal to iproute2 `do_xfrm_monitor()`, print the XFRM events until
+/// interrupted.
+pub(crate) async fn handle_monitor(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    fmt: OutputFormat,
+    show_stats: bool,
+) -> Result<(), CliError> {
+    let monitor_opts = XfrmMonitorOptions::parse(opts)?;
+    let ifnames = get_ifnames(ctx.handle()).await?;
+
+    let mut messages = ctx
+        .proto::<XfrmNetlinkMessage>()?
+        .subscribe(&monitor_opts.groups)?;
+
+    while let Some((nl_msg, _)) = messages.next().await {
+        let NetlinkPayload::InnerMessage(msg) = nl_msg.payload else {
+            continue;
+        };
+        if let Some(event) = parse_nl_msg_to_event(
+            msg,
+            monitor_opts.nokeys,
+            show_stats,
+            &ifnames,
+        ) {
+            print_output(&Ok(gen_output_string(&event, fmt)));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,941 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, io::Write};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+use serde::Serialize;
+
+use super::{
+    common::{
+        CliXfrmAttrs, CliXfrmLifetime, CliXfrmSelector, XfrmIdOpts,
+        address_family, duparg, flags_to_names, invarg, parse_flag_list,
+        parse_lifetime, parse_mark, parse_mode, parse_selector, share_to_name,
+        xfrm_request,
+    },
+    message::{
+        AF_INET, XFRM_POLICY_ALLOW, XFRM_POLICY_BLOCK, XFRM_POLICY_FWD,
+        XFRM_POLICY_ICMP, XFRM_POLICY_IN, XFRM_POLICY_LOCALOK, XFRM_POLICY_MAX,
+        XFRM_POLICY_OUT, XFRM_POLICY_TYPE_MAIN, XFRM_POLICY_TYPE_SUB, XfrmAttr,
+        XfrmLifetimeCfg, XfrmMark, XfrmNetlinkMessage, XfrmPolicyIdMessage,
+        XfrmPolicyMessage, XfrmSelector, XfrmSpdInfoAttr, XfrmUserTmpl,
+        XfrmUserpolicyId, XfrmUserpolicyInfo,
+    },
+};
+use crate::{context::NetlinkContext, link::parse_num, route::get_ifnames};
+
+const XFRM_POLICY_FLAG_NAMES: [(&str, u8); 2] =
+    [("localok", XFRM_POLICY_LOCALOK), ("icmp", XFRM_POLICY_ICMP)];
+
+/// Equal to iproute2 `xfrm_policy_dir_parse()`
+fn parse_dir(value: &str) -> Result<u8, CliError> {
+    match value {
+        "in" => Ok(XFRM_POLICY_IN),
+        "out" => Ok(XFRM_POLICY_OUT),
+        "fwd" => Ok(XFRM_POLICY_FWD),
+        _ => Err(invarg(value, "DIR value is invalid")),
+    }
+}
+
+fn dir_to_name(dir: u8) -> String {
+    match dir {
+        XFRM_POLICY_IN => "in".to_string(),
+        XFRM_POLICY_OUT => "out".to_string(),
+        XFRM_POLICY_FWD => "fwd".to_string(),
+        _ => dir.to_string(),
+    }
+}
+
+/// Equal to iproute2 `xfrm_policy_ptype_parse()`
+fn parse_ptype(value: &str) -> Result<u8, CliError> {
+    match value {
+        "main" => Ok(XFRM_POLICY_TYPE_MAIN),
+        "sub" => Ok(XFRM_POLICY_TYPE_SUB),
+        _ => Err(invarg(value, "PTYPE value is invalid")),
+    }
+}
+
+/// Equal to iproute2 `strxf_ptype()`
+pub(crate) fn ptype_to_name(ptype: u8) -> String {
+    match ptype {
+        XFRM_POLICY_TYPE_MAIN => "main".to_string(),
+        XFRM_POLICY_TYPE_SUB => "sub".to_string(),
+        _ => ptype.to_string(),
+    }
+}
+
+fn parse_action(value: &str) -> Result<u8, CliError> {
+    match value {
+        "allow" => Ok(XFRM_POLICY_ALLOW),
+        "block" => Ok(XFRM_POLICY_BLOCK),
+        _ => Err(invarg(value, "ACTION value is invalid")),
+    }
+}
+
+/// Equal to iproute2 `xfrm_tmpl_parse()`, the `tmpl` keyword is consumed
+/// already. Like iproute2, the address family of template becomes the
+/// preferred one for the arguments after it.
+fn parse_tmpl(
+    opts: &mut std::slice::Iter<'_, &str>,
+    family: &mut AddressFamily,
+) -> Result<XfrmUserTmpl, CliError> {
+    // Like iproute2 `NEXT_ARG()`, the template should not be empty
+    next_opt(&mut opts.clone())?;
+    let mut tmpl = XfrmUserTmpl {
+        family: u16::from(u8::from(*family)),
+        aalgos: u32::MAX,
+        ealgos: u32::MAX,
+        calgos: u32::MAX,
+        ..Default::default()
+    };
+    let mut has_id = false;
+
+    while let Some(opt) = opts.as_slice().first().copied() {
+        match opt {
+            "mode" => {
+                opts.next();
+                tmpl.mode = parse_mode(next_opt(opts)?)?;
+            }
+            "reqid" => {
+                opts.next();
+                tmpl.reqid =
+                    parse_num(next_opt(opts)?, "REQID value is invalid")?;
+            }
+            "level" => {
+                opts.next();
+                let value = next_opt(opts)?;
+                tmpl.optional = match value {
+                    "required" => 0,
+                    "use" => 1,
+                    _ => return Err(invarg(value, "LEVEL value is invalid")),
+                };
+            }
+            _ if has_id => break,
+            _ => {
+                opts.next();
+                has_id = true;
+                let id =
+                    XfrmIdOpts::parse(opt, opts, AddressFamily::Unspec, false)?;
+                tmpl.saddr = id.saddr();
+                tmpl.id = id.to_id();
+                if id.family() != 0 {
+                    tmpl.family = id.family();
+                }
+                *family = address_family(tmpl.family);
+            }
+        }
+    }
+    Ok(tmpl)
+}
+
+/// Equal to iproute2 `xfrm_policy_modify()`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    update: bool,
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let mut info = XfrmUserpolicyInfo {
+        lft: XfrmLifetimeCfg::unlimited(),
+        ..Default::default()
+    };
+    info.sel.family = u16::from(u8::from(family));
+    let mut family = family;
+    let mut has_dir = false;
+    let mut has_sel = false;
+    let mut ptype = None;
+    let mut tmpls = Vec::new();
+    let mut mark = XfrmMark::default();
+    let mut if_id = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.as_slice().first().copied() {
+        match opt {
+            "dir" => {
+                opts.next();
+                if has_dir {
+                    return Err(duparg("dir", opt));
+                }
+                has_dir = true;
+                info.dir = parse_dir(next_opt(&mut opts)?)?;
+            }
+            "mark" => {
+                opts.next();
+                mark = parse_mark(&mut opts)?;
+            }
+            "index" => {
+                opts.next();
+                info.index =
+                    parse_num(next_opt(&mut opts)?, "INDEX value is invalid")?;
+            }
+            "ptype" => {
+                opts.next();
+                if ptype.is_some() {
+                    return Err(duparg("ptype", opt));
+                }
+                ptype = Some(parse_ptype(next_opt(&mut opts)?)?);
+            }
+            "action" => {
+                opts.next();
+                info.action = parse_action(next_opt(&mut opts)?)?;
+            }
+            "priority" => {
+                opts.next();
+                info.priority = parse_num(
+                    next_opt(&mut opts)?,
+                    "PRIORITY value is invalid",
+                )?;
+            }
+            "flag" => {
+                opts.next();
+                info.flags = parse_flag_list(
+                    &mut opts,
+                    &XFRM_POLICY_FLAG_NAMES,
+                    "FLAG value is invalid",
+                )?;
+            }
+            "limit" => {
+                opts.next();
+                parse_lifetime(&mut info.lft, &mut opts)?;
+            }
+            "tmpl" => {
+                opts.next();
+                tmpls.push(parse_tmpl(&mut opts, &mut family)?);
+            }
+            "if_id" => {
+                opts.next();
+                if_id = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "IF_ID value is invalid",
+                )?);
+            }
+            _ => {
+                // Like iproute2, the words not consumed by the selector
+                // are reported here
+                if has_sel {
+                    return Err(duparg("unknown", opt));
+                }
+                has_sel = true;
+                let sel = parse_selector(&mut opts, family, &ifnames)?;
+                info.sel = XfrmSelector {
+                    family: if sel.family == 0 {
+                        info.sel.family
+                    } else {
+                        sel.family
+                    },
+                    ..sel
+                };
+                if family == AddressFamily::Unspec {
+                    family = address_family(info.sel.family);
+                }
+            }
+        }
+    }
+
+    if !has_dir {
+        return Err(CliError::from("Not enough information: DIR is required."));
+    }
+
+    let mut attrs = Vec::new();
+    if let Some(ptype) = ptype {
+        attrs.push(XfrmAttr::PolicyType(ptype));
+    }
+    if !tmpls.is_empty() {
+        attrs.push(XfrmAttr::Tmpl(tmpls));
+    }
+    if mark.mask != 0 {
+        attrs.push(XfrmAttr::Mark(mark));
+    }
+    if let Some(if_id) = if_id {
+        attrs.push(XfrmAttr::IfId(if_id));
+    }
+    if info.sel.family == 0 {
+        info.sel.family = AF_INET;
+    }
+
+    let msg = XfrmPolicyMessage {
+        info,
+        attributes: attrs,
+    };
+    xfrm_request(
+        ctx,
+        if update {
+            XfrmNetlinkMessage::UpdPolicy(msg)
+        } else {
+            XfrmNetlinkMessage::NewPolicy(msg)
+        },
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `xfrm_policy_get_or_delete()`
+pub(crate) async fn handle_get_or_delete(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    delete: bool,
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmPolicyInfo>, CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let mut id = XfrmUserpolicyId::default();
+    id.sel.family = u16::from(u8::from(family));
+    let mut has_dir = false;
+    let mut has_sel = false;
+    let mut has_index = false;
+    let mut ptype = None;
+    let mut mark = XfrmMark::default();
+    let mut if_id = None;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.as_slice().first().copied() {
+        match opt {
+            "dir" => {
+                opts.next();
+                if has_dir {
+                    return Err(duparg("dir", opt));
+                }
+                has_dir = true;
+                id.dir = parse_dir(next_opt(&mut opts)?)?;
+            }
+            "mark" => {
+                opts.next();
+                mark = parse_mark(&mut opts)?;
+            }
+            "index" => {
+                opts.next();
+                if has_index {
+                    return Err(duparg("index", opt));
+                }
+                has_index = true;
+                id.index =
+                    parse_num(next_opt(&mut opts)?, "INDEX value is invalid")?;
+            }
+            "ptype" => {
+                opts.next();
+                if ptype.is_some() {
+                    return Err(duparg("ptype", opt));
+                }
+                ptype = Some(parse_ptype(next_opt(&mut opts)?)?);
+            }
+            "if_id" => {
+                opts.next();
+                if_id = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "IF_ID value is invalid",
+                )?);
+            }
+            _ => {
+                if has_sel {
+                    return Err(invarg(opt, "unknown"));
+                }
+                has_sel = true;
+                let sel = parse_selector(
+                    &mut opts,
+                    address_family(id.sel.family),
+                    &ifnames,
+                )?;
+                id.sel = XfrmSelector {
+                    family: if sel.family == 0 {
+                        id.sel.family
+                    } else {
+                        sel.family
+                    },
+                    ..sel
+                };
+            }
+        }
+    }
+
+    if !has_dir {
+        return Err(CliError::from("Not enough information: DIR is required."));
+    }
+    let mut attrs = Vec::new();
+    if let Some(ptype) = ptype {
+        attrs.push(XfrmAttr::PolicyType(ptype));
+    }
+    if !has_sel && !has_index {
+        return Err(CliError::from(
+            "Not enough information: either SELECTOR or INDEX is required.",
+        ));
+    }
+    if has_sel && has_index {
+        return Err(CliError::from(
+            "either \"SELECTOR\" is duplicate, or \"INDEX\" is a garbage.",
+        ));
+    }
+    if id.sel.family == 0 {
+        id.sel.family = AF_INET;
+    }
+    if mark.mask & mark.value != 0 {
+        attrs.push(XfrmAttr::Mark(mark));
+    }
+    if let Some(if_id) = if_id {
+        attrs.push(XfrmAttr::IfId(if_id));
+    }
+
+    let msg = XfrmPolicyIdMessage {
+        id,
+        attributes: attrs,
+    };
+    if delete {
+        xfrm_request(
+            ctx,
+            XfrmNetlinkMessage::DelPolicy(msg),
+            NLM_F_REQUEST | NLM_F_ACK,
+        )
+        .await?;
+        return Ok(Vec::new());
+    }
+    Ok(
+        xfrm_request(ctx, XfrmNetlinkMessage::GetPolicy(msg), NLM_F_REQUEST)
+            .await?
+            .into_iter()
+            .filter_map(|msg| match msg {
+                XfrmNetlinkMessage::NewPolicy(msg) => {
+                    Some(CliXfrmPolicyInfo::new(
+                        &msg.info,
+                        &msg.attributes,
+                        show_stats,
+                        &ifnames,
+                    ))
+                }
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+// Equal to iproute2 `xfrm_policy_filter_match()`
+#[derive(Debug, Default)]
+struct XfrmPolicyFilter {
+    family: u16,
+    sel: Option<XfrmSelector>,
+    dir: Option<u8>,
+    index: Option<u32>,
+    ptype: Option<u8>,
+    action: Option<u8>,
+    priority: Option<u32>,
+    flags: Option<u8>,
+    nosock: bool,
+}
+
+impl XfrmPolicyFilter {
+    fn matches(&self, msg: &XfrmPolicyMessage) -> bool {
+        let info = &msg.info;
+        let ptype = msg
+            .attributes
+            .iter()
+            .find_map(|attr| match attr {
+                XfrmAttr::PolicyType(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(XFRM_POLICY_TYPE_MAIN);
+
+        if self.family != 0 && self.family != info.sel.family {
+            return false;
+        }
+        if self.dir.is_some_and(|v| v != info.dir)
+            || (self.nosock && info.dir >= XFRM_POLICY_MAX)
+            || self.ptype.is_some_and(|v| v != ptype)
+        {
+            return false;
+        }
+        if let Some(sel) = &self.sel
+            && !Self::sel_matches(sel, &info.sel)
+        {
+            return false;
+        }
+        if self.index.is_some_and(|v| v != info.index)
+            || self.action.is_some_and(|v| v != info.action)
+            || self.priority.is_some_and(|v| v != info.priority)
+        {
+            return false;
+        }
+        self.flags.is_none_or(|v| v & info.flags != 0)
+    }
+
+    // Like iproute2, only the selector fields specified are compared and
+    // the addresses are matched by the prefix length of filter.
+    fn sel_matches(filter: &XfrmSelector, sel: &XfrmSelector) -> bool {
+        let addr_matches = |filter_addr: &[u8], addr: &[u8], prefix_len: u8| {
+            let prefix_len = usize::from(prefix_len);
+            let bytes = prefix_len / 8;
+            let bits = prefix_len % 8;
+            filter_addr[..bytes] == addr[..bytes]
+                && (bits == 0 || {
+                    let mask = 0xffu8 << (8 - bits);
+                    filter_addr[bytes] & mask == addr[bytes] & mask
+                })
+        };
+        (filter.prefixlen_s == 0
+            || addr_matches(&filter.saddr.0, &sel.saddr.0, filter.prefixlen_s))
+            && (filter.prefixlen_d == 0
+                || addr_matches(
+                    &filter.daddr.0,
+                    &sel.daddr.0,
+                    filter.prefixlen_d,
+                ))
+            && (filter.ifindex == 0 || filter.ifindex == sel.ifindex)
+            && (filter.proto == 0 || filter.proto == sel.proto)
+            && (filter.sport_mask == 0 || filter.sport == sel.sport)
+            && (filter.dport_mask == 0 || filter.dport == sel.dport)
+    }
+}
+
+/// Equal to iproute2 `xfrm_policy_list_or_deleteall()` for listing
+pub(crate) async fn handle_list(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmPolicyInfo>, CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let mut filter = XfrmPolicyFilter::default();
+    let mut family = family;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.as_slice().first().copied() {
+        match opt {
+            "dir" => {
+                opts.next();
+                filter.dir = Some(parse_dir(next_opt(&mut opts)?)?);
+            }
+            "index" => {
+                opts.next();
+                filter.index = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "INDEX value is invalid",
+                )?);
+            }
+            "ptype" => {
+                opts.next();
+                filter.ptype = Some(parse_ptype(next_opt(&mut opts)?)?);
+            }
+            "action" => {
+                opts.next();
+                filter.action = Some(parse_action(next_opt(&mut opts)?)?);
+            }
+            "priority" => {
+                opts.next();
+                filter.priority = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "PRIORITY value is invalid",
+                )?);
+            }
+            "flag" => {
+                opts.next();
+                filter.flags = Some(parse_flag_list(
+                    &mut opts,
+                    &XFRM_POLICY_FLAG_NAMES,
+                    "FLAG value is invalid",
+                )?);
+            }
+            "nosock" => {
+                opts.next();
+                filter.nosock = true;
+            }
+            _ => {
+                if filter.sel.is_some() {
+                    return Err(invarg(opt, "unknown"));
+                }
+                let sel = parse_selector(&mut opts, family, &ifnames)?;
+                if family == AddressFamily::Unspec {
+                    family = address_family(sel.family);
+                }
+                filter.sel = Some(sel);
+            }
+        }
+    }
+    filter.family = u16::from(u8::from(family));
+
+    Ok(xfrm_request(
+        ctx,
+        XfrmNetlinkMessage::DumpPolicy,
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .filter_map(|msg| match msg {
+        XfrmNetlinkMessage::NewPolicy(msg) if filter.matches(&msg) => {
+            Some(CliXfrmPolicyInfo::new(
+                &msg.info,
+                &msg.attributes,
+                show_stats,
+                &ifnames,
+            ))
+        }
+        _ => None,
+    })
+    .collect())
+}
+
+/// Equal to iproute2 `xfrm_policy_flush()`
+pub(crate) async fn handle_flush(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    show_stats: u8,
+) -> Result<(), CliError> {
+    let mut ptype = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if *opt == "ptype" {
+            if ptype.is_some() {
+                return Err(duparg("ptype", opt));
+            }
+            ptype = Some(parse_ptype(next_opt(&mut opts)?)?);
+        } else {
+            return Err(invarg(opt, "unknown"));
+        }
+    }
+    if show_stats > 1 {
+        writeln!(std::io::stderr(), "Flush policy").ok();
+    }
+    xfrm_request(
+        ctx,
+        XfrmNetlinkMessage::FlushPolicy(
+            ptype.map(XfrmAttr::PolicyType).into_iter().collect(),
+        ),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `xfrm_spd_setinfo()`
+pub(crate) async fn handle_set(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+) -> Result<(), CliError> {
+    let mut attrs = Vec::new();
+    let mut has_thresh4 = false;
+    let mut has_thresh6 = false;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        let (has_thresh, max) = match *opt {
+            "hthresh4" => (&mut has_thresh4, 32),
+            "hthresh6" => (&mut has_thresh6, 128),
+            _ => return Err(invarg(opt, "unknown")),
+        };
+        if *has_thresh {
+            return Err(duparg(opt, opt));
+        }
+        *has_thresh = true;
+        let mut bits = [0u8; 2];
+        for (bits, name) in bits.iter_mut().zip(["LBITS", "RBITS"]) {
+            let value = next_opt(&mut opts)?;
+            let error_msg = format!("{opt} {name} value is invalid");
+            *bits = parse_num::<u8>(value, &error_msg)
+                .ok()
+                .filter(|v| *v <= max)
+                .ok_or_else(|| invarg(value, &error_msg))?;
+        }
+        attrs.push(if *opt == "hthresh4" {
+            XfrmSpdInfoAttr::Ipv4HashThresh(bits[0], bits[1])
+        } else {
+            XfrmSpdInfoAttr::Ipv6HashThresh(bits[0], bits[1])
+        });
+    }
+    xfrm_request(
+        ctx,
+        XfrmNetlinkMessage::NewSpdInfo(attrs),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliXfrmSpdCount {
+    #[serde(rename = "in")]
+    incnt: u32,
+    #[serde(rename = "out")]
+    outcnt: u32,
+    #[serde(rename = "fwd")]
+    fwdcnt: u32,
+}
+
+impl std::fmt::Display for CliXfrmSpdCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "IN {} OUT {} FWD {}",
+            self.incnt, self.outcnt, self.fwdcnt
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct CliXfrmSpdThresh {
+    local: u8,
+    remote: u8,
+}
+
+/// Equal to iproute2 `xfrm_spd_getinfo()`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CliXfrmSpdInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<CliXfrmSpdCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    socket_count: Option<CliXfrmSpdCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets_max: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv4_thresholds: Option<CliXfrmSpdThresh>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv6_thresholds: Option<CliXfrmSpdThresh>,
+}
+
+impl CliXfrmSpdInfo {
+    fn lines(&self) -> Vec<String> {
+        let mut ret = Vec::new();
+        let mut line = "\t SPD".to_string();
+        if let Some(count) = self.count {
+            // Like iproute2, there are two spaces after `IN`
+            line.push_str(&format!(" {}", count).replacen("IN ", "IN  ", 1));
+            if let Some(sock) = self.socket_count {
+                line.push_str(&format!(" (Sock: {sock})"));
+            }
+        }
+        ret.push(line);
+        if let (Some(count), Some(max)) = (self.buckets_count, self.buckets_max)
+        {
+            ret.push(format!("\t SPD buckets: count {count} Max {max}"));
+        }
+        for (name, thresh) in [
+            ("IPv4", self.ipv4_thresholds),
+            ("IPv6", self.ipv6_thresholds),
+        ] {
+            if let Some(thresh) = thresh {
+                ret.push(format!(
+                    "\t SPD {name} thresholds: local {} remote {}",
+                    thresh.local, thresh.remote
+                ));
+            }
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliXfrmSpdInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl CanDisplay for CliXfrmSpdInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, every line including the last one ends with `\`
+    fn gen_oneline_string(&self) -> String {
+        format!("{}\\", self.lines().join("\\"))
+    }
+}
+
+impl CanOutput for CliXfrmSpdInfo {}
+
+/// Equal to iproute2 `xfrm_spd_getinfo()`, the socket policy count is
+/// shown in statistics mode while the hash information in detailed
+/// statistics mode.
+pub(crate) async fn handle_count(
+    ctx: &NetlinkContext,
+    show_stats: u8,
+) -> Result<Vec<CliXfrmSpdInfo>, CliError> {
+    let mut ret = Vec::new();
+    for msg in
+        xfrm_request(ctx, XfrmNetlinkMessage::GetSpdInfo, NLM_F_REQUEST).await?
+    {
+        let XfrmNetlinkMessage::NewSpdInfo(attrs) = msg else {
+            continue;
+        };
+        let mut info = CliXfrmSpdInfo::default();
+        for attr in attrs {
+            match attr {
+                XfrmSpdInfoAttr::Info(v) => {
+                    info.count = Some(CliXfrmSpdCount {
+                        incnt: v.incnt,
+                        outcnt: v.outcnt,
+                        fwdcnt: v.fwdcnt,
+                    });
+                    info.socket_count =
+                        (show_stats > 0).then_some(CliXfrmSpdCount {
+                            incnt: v.inscnt,
+                            outcnt: v.outscnt,
+                            fwdcnt: v.fwdscnt,
+                        });
+                }
+                XfrmSpdInfoAttr::HashInfo(count, max) if show_stats > 1 => {
+                    info.buckets_count = Some(count);
+                    info.buckets_max = Some(max);
+                }
+                XfrmSpdInfoAttr::Ipv4HashThresh(local, remote)
+                    if show_stats > 1 =>
+                {
+                    info.ipv4_thresholds =
+                        Some(CliXfrmSpdThresh { local, remote });
+                }
+                XfrmSpdInfoAttr::Ipv6HashThresh(local, remote)
+                    if show_stats > 1 =>
+                {
+                    info.ipv6_thresholds =
+                        Some(CliXfrmSpdThresh { local, remote });
+                }
+                _ => (),
+            }
+        }
+        ret.push(info);
+    }
+    Ok(ret)
+}
+
+/// Equal to iproute2 `xfrm_policy_info_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmPolicyInfo {
+    sel: CliXfrmSelector,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dir: Option<String>,
+    /// The direction of socket policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    socket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<u32>,
+    priority: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ptype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    share: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flag: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifetime: Option<CliXfrmLifetime>,
+    #[serde(flatten)]
+    attrs: CliXfrmAttrs,
+    /// The raw flags shown in binary in statistics mode
+    #[serde(skip)]
+    flags_raw: Option<u8>,
+}
+
+impl CliXfrmPolicyInfo {
+    pub(crate) fn new(
+        info: &XfrmUserpolicyInfo,
+        attrs: &[XfrmAttr],
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        let is_socket = info.dir >= XFRM_POLICY_MAX;
+        let dir = dir_to_name(if is_socket {
+            info.dir - XFRM_POLICY_MAX
+        } else {
+            info.dir
+        });
+        Self {
+            sel: CliXfrmSelector::new(
+                &info.sel,
+                info.sel.family,
+                show_stats,
+                ifnames,
+            ),
+            dir: (!is_socket).then(|| dir.clone()),
+            socket: is_socket.then_some(dir),
+            action: match info.action {
+                XFRM_POLICY_ALLOW => show_stats.then(|| "allow".to_string()),
+                XFRM_POLICY_BLOCK => Some("block".to_string()),
+                v => Some(v.to_string()),
+            },
+            index: show_stats.then_some(info.index),
+            priority: info.priority,
+            ptype: attrs.iter().find_map(|attr| match attr {
+                XfrmAttr::PolicyType(v) => Some(ptype_to_name(*v)),
+                _ => None,
+            }),
+            share: show_stats.then(|| share_to_name(info.share)),
+            flag: (show_stats || info.flags != 0)
+                .then(|| flags_to_names(info.flags, &XFRM_POLICY_FLAG_NAMES)),
+            lifetime: show_stats
+                .then(|| CliXfrmLifetime::new(&info.lft, Some(&info.curlft))),
+            attrs: CliXfrmAttrs::new(
+                attrs,
+                info.sel.family,
+                false,
+                show_stats,
+                ifnames,
+            ),
+            flags_raw: show_stats.then_some(info.flags),
+        }
+    }
+
+    pub(crate) fn lines(&self) -> Vec<String> {
+        self.lines_with_title("", "")
+    }
+
+    /// Like iproute2 `xfrm_policy_info_print()`, the `title` is printed
+    /// before the selector while the `prefix` before the following lines.
+    pub(crate) fn lines_with_title(
+        &self,
+        prefix: &str,
+        title: &str,
+    ) -> Vec<String> {
+        let prefix = format!("{prefix}\t");
+        let mut ret = vec![format!("{title}{}", self.sel)];
+        let mut line = match (&self.dir, &self.socket) {
+            (_, Some(socket)) => format!("{prefix}socket {socket} "),
+            (Some(dir), None) => format!("{prefix}dir {dir} "),
+            (None, None) => prefix.clone(),
+        };
+        if let Some(action) = &self.action {
+            line.push_str(&format!("action {action} "));
+        }
+        if let Some(index) = self.index {
+            line.push_str(&format!("index {index} "));
+        }
+        line.push_str(&format!("priority {} ", self.priority));
+        if let Some(ptype) = &self.ptype {
+            line.push_str(&format!("ptype {ptype} "));
+        }
+        if let Some(share) = &self.share {
+            line.push_str(&format!("share {share} "));
+        }
+        if let Some(flags) = &self.flag {
+            line.push_str(&format!("flag {}", flags.join(" ")));
+        }
+        if let Some(flags) = self.flags_raw {
+            line.push_str(&format!(" (0x{flags:08b})"));
+        }
+        ret.push(line);
+        if let Some(lifetime) = &self.lifetime {
+            ret.extend(lifetime.lines(&prefix));
+        }
+        ret.extend(self.attrs.lines(&prefix));
+        ret
+    }
+}
+
+impl std::fmt::Display for CliXfrmPolicyInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl CanDisplay for CliXfrmPolicyInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, every line including the last one ends with `\`
+    fn gen_oneline_string(&self) -> String {
+        format!("{}\\", self.lines().join("\\"))
+    }
+}
+
+impl CanOutput for CliXfrmPolicyInfo {}
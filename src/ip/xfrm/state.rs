@@ -0,0 +1,1096 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, io::Write};
+
+use iproute_rs::{CanDisplay, CanOutput, CliError, next_opt};
+use rtnetlink::{
+    packet_core::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST},
+    packet_route::AddressFamily,
+};
+use serde::Serialize;
+
+use super::{
+    common::{
+        CliXfrmAttrs, CliXfrmIdInfo, CliXfrmLifetime, CliXfrmSelector,
+        CliXfrmStats, IPPROTO_AH, IPPROTO_COMP, IPPROTO_ESP, XFRM_MODE_BEET,
+        XFRM_MODE_IN_TRIGGER, XFRM_MODE_ROUTEOPTIMIZATION, XFRM_MODE_TRANSPORT,
+        XFRM_MODE_TUNNEL, XfrmIdOpts, address_family, af_family, duparg,
+        flags_to_names, invarg, missarg, mode_to_name, parse_encap_type,
+        parse_flag_list, parse_lifetime, parse_mark, parse_mode,
+        parse_selector, parse_xfrm_proto, xfrm_proto_is_ipsec,
+        xfrm_proto_is_ro, xfrm_proto_to_name, xfrm_request,
+    },
+    message::{
+        AF_INET, XFRM_OFFLOAD_INBOUND, XFRM_SA_XFLAG_DONT_ENCAP_DSCP,
+        XFRM_SA_XFLAG_OSEQ_MAY_WRAP, XFRM_STATE_AF_UNSPEC, XFRM_STATE_ALIGN4,
+        XFRM_STATE_DECAP_DSCP, XFRM_STATE_ESN, XFRM_STATE_ICMP,
+        XFRM_STATE_NOECN, XFRM_STATE_NOPMTUDISC, XFRM_STATE_WILDRECV,
+        XfrmAddress, XfrmAddressFilter, XfrmAlgo, XfrmAttr, XfrmEncapTmpl,
+        XfrmLifetimeCfg, XfrmMark, XfrmNetlinkMessage, XfrmReplayState,
+        XfrmReplayStateEsn, XfrmSaIdMessage, XfrmSaMessage, XfrmSadInfoAttr,
+        XfrmSpiMessage, XfrmUserOffload, XfrmUsersaId, XfrmUsersaInfo,
+    },
+};
+use crate::{
+    context::NetlinkContext,
+    link::parse_num,
+    prefix::{CliIpPrefix, parse_ip_addr},
+    route::get_ifnames,
+};
+
+// Equal to iproute2 `XFRMA_REPLAY_ESN_MAX`
+const XFRMA_REPLAY_ESN_MAX: u32 = 4096;
+// Equal to iproute2 `XFRM_ALGO_KEY_BUF_SIZE`
+const XFRM_ALGO_KEY_BUF_SIZE: usize = 512;
+
+const XFRM_STATE_FLAG_NAMES: [(&str, u8); 8] = [
+    ("noecn", XFRM_STATE_NOECN),
+    ("decap-dscp", XFRM_STATE_DECAP_DSCP),
+    ("nopmtudisc", XFRM_STATE_NOPMTUDISC),
+    ("wildrecv", XFRM_STATE_WILDRECV),
+    ("icmp", XFRM_STATE_ICMP),
+    ("af-unspec", XFRM_STATE_AF_UNSPEC),
+    ("align4", XFRM_STATE_ALIGN4),
+    ("esn", XFRM_STATE_ESN),
+];
+
+const XFRM_SA_EXTRA_FLAG_NAMES: [(&str, u32); 2] = [
+    ("dont-encap-dscp", XFRM_SA_XFLAG_DONT_ENCAP_DSCP),
+    ("oseq-may-wrap", XFRM_SA_XFLAG_OSEQ_MAY_WRAP),
+];
+
+/// Equal to iproute2 `xfrm_algo_parse()`, the key is either hex prefixed
+/// by `0x` or string.
+fn parse_algo_key(key: &str) -> Result<Vec<u8>, CliError> {
+    let ret = if key.len() > 2
+        && let Some(hex) = key.strip_prefix("0x")
+    {
+        // Like iproute2, zero is added to the top for odd length
+        let hex = if hex.len() % 2 == 1 {
+            format!("0{hex}")
+        } else {
+            hex.to_string()
+        };
+        hex.as_bytes()
+            .chunks(2)
+            .map(|chunk| {
+                std::str::from_utf8(chunk)
+                    .ok()
+                    .and_then(|v| u8::from_str_radix(v, 16).ok())
+                    .ok_or_else(|| invarg(key, "ALGO-KEYMAT value is invalid"))
+            })
+            .collect::<Result<Vec<u8>, CliError>>()?
+    } else {
+        key.as_bytes().to_vec()
+    };
+    if ret.len() > XFRM_ALGO_KEY_BUF_SIZE {
+        return Err(invarg(key, "ALGO-KEYMAT value makes buffer overflow"));
+    }
+    Ok(ret)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum XfrmAlgoType {
+    Crypt,
+    Auth,
+    AuthTrunc,
+    Aead,
+    Comp,
+}
+
+impl XfrmAlgoType {
+    // Equal to iproute2 `xfrm_algotype_getbyname()`
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "enc" => Some(Self::Crypt),
+            "auth" => Some(Self::Auth),
+            "auth-trunc" => Some(Self::AuthTrunc),
+            "aead" => Some(Self::Aead),
+            "comp" => Some(Self::Comp),
+            _ => None,
+        }
+    }
+
+    // Equal to iproute2 `strxf_algotype()`
+    fn name(self) -> &'static str {
+        match self {
+            Self::Crypt => "enc",
+            Self::Auth => "auth",
+            Self::AuthTrunc => "auth-trunc",
+            Self::Aead => "aead",
+            Self::Comp => "comp",
+        }
+    }
+
+    fn to_attr(self, algo: XfrmAlgo) -> XfrmAttr {
+        match self {
+            Self::Crypt => XfrmAttr::AlgCrypt(algo),
+            Self::Auth => XfrmAttr::AlgAuth(algo),
+            Self::AuthTrunc => XfrmAttr::AlgAuthTrunc(algo),
+            Self::Aead => XfrmAttr::AlgAead(algo),
+            Self::Comp => XfrmAttr::AlgComp(algo),
+        }
+    }
+}
+
+// The ALGO-TYPE specified
+#[derive(Debug, Default)]
+struct XfrmAlgoTypes {
+    aead: bool,
+    crypt: bool,
+    auth: bool,
+    comp: bool,
+}
+
+impl XfrmAlgoTypes {
+    fn add(&mut self, kind: XfrmAlgoType) -> Result<(), CliError> {
+        let dup = match kind {
+            XfrmAlgoType::Aead => self.crypt || self.auth || self.aead,
+            XfrmAlgoType::Crypt => self.crypt || self.aead,
+            XfrmAlgoType::Auth | XfrmAlgoType::AuthTrunc => {
+                self.auth || self.aead
+            }
+            XfrmAlgoType::Comp => self.comp,
+        };
+        if dup {
+            return Err(duparg("ALGO-TYPE", kind.name()));
+        }
+        match kind {
+            XfrmAlgoType::Aead => self.aead = true,
+            XfrmAlgoType::Crypt => self.crypt = true,
+            XfrmAlgoType::Auth | XfrmAlgoType::AuthTrunc => self.auth = true,
+            XfrmAlgoType::Comp => self.comp = true,
+        }
+        Ok(())
+    }
+
+    fn any(&self) -> bool {
+        self.aead || self.crypt || self.auth || self.comp
+    }
+}
+
+// Parse `ALGO-NAME [ ALGO-KEYMAT ] [ ALGO-ICV-LEN | ALGO-TRUNC-LEN ]`
+fn parse_algo(
+    kind: XfrmAlgoType,
+    opts: &mut std::slice::Iter<'_, &str>,
+) -> Result<XfrmAlgo, CliError> {
+    let name = opts.next().ok_or_else(|| missarg("ALGO-NAME"))?;
+    let key = if kind == XfrmAlgoType::Comp {
+        Vec::new()
+    } else {
+        parse_algo_key(opts.next().ok_or_else(|| missarg("ALGO-KEYMAT"))?)?
+    };
+    let extra = match kind {
+        XfrmAlgoType::Aead => Some(parse_num(
+            opts.next().ok_or_else(|| missarg("ALGO-ICV-LEN"))?,
+            "ALGO-ICV-LEN value is invalid",
+        )?),
+        XfrmAlgoType::AuthTrunc => Some(parse_num(
+            opts.next().ok_or_else(|| missarg("ALGO-TRUNC-LEN"))?,
+            "ALGO-TRUNC-LEN value is invalid",
+        )?),
+        _ => None,
+    };
+    Ok(XfrmAlgo {
+        name: name.to_string(),
+        key_len: (key.len() * 8) as u32,
+        extra,
+        key,
+    })
+}
+
+fn parse_offload(
+    opts: &mut std::slice::Iter<'_, &str>,
+    ifnames: &HashMap<u32, String>,
+) -> Result<XfrmUserOffload, CliError> {
+    let mut ret = XfrmUserOffload::default();
+    let mut opt = next_opt(opts)?;
+    if opt == "dev" {
+        let name = next_opt(opts)?;
+        ret.ifindex = ifnames
+            .iter()
+            .find(|(_, n)| n.as_str() == name)
+            .and_then(|(i, _)| i32::try_from(*i).ok())
+            .ok_or_else(|| {
+                invarg(name, "value after \"offload dev\" is invalid")
+            })?;
+        opt = next_opt(opts)?;
+    }
+    if opt != "dir" {
+        return Err(invarg(opt, "value after \"offload\" is invalid"));
+    }
+    let dir = next_opt(opts)?;
+    ret.flags = match dir {
+        "in" => XFRM_OFFLOAD_INBOUND,
+        "out" => 0,
+        _ => {
+            return Err(invarg(dir, "value after \"offload dir\" is invalid"));
+        }
+    };
+    Ok(ret)
+}
+
+fn proto_error(msg: &str, proto: u8) -> CliError {
+    CliError::from(
+        format!(
+            "{msg} with XFRM-PROTO value \"{}\"",
+            xfrm_proto_to_name(proto)
+        )
+        .as_str(),
+    )
+}
+
+// Equal to the XFRM protocol checks of iproute2 `xfrm_state_modify()`
+fn check_proto(
+    info: &XfrmUsersaInfo,
+    algos: &XfrmAlgoTypes,
+    has_coa: bool,
+) -> Result<(), CliError> {
+    let proto = info.id.proto;
+    if xfrm_proto_is_ipsec(proto) {
+        match info.mode {
+            XFRM_MODE_TRANSPORT | XFRM_MODE_TUNNEL => (),
+            XFRM_MODE_BEET if proto == IPPROTO_ESP => (),
+            _ => return Err(proto_error("MODE value is invalid", proto)),
+        }
+        match proto {
+            IPPROTO_ESP => {
+                if algos.comp {
+                    return Err(proto_error(
+                        "ALGO-TYPE value \"comp\" is invalid",
+                        proto,
+                    ));
+                }
+                if !algos.crypt && !algos.aead {
+                    return Err(proto_error(
+                        "ALGO-TYPE value \"enc\" or \"aead\" is required",
+                        proto,
+                    ));
+                }
+            }
+            IPPROTO_AH => {
+                if algos.crypt || algos.aead || algos.comp {
+                    return Err(proto_error(
+                        "ALGO-TYPE values \"enc\", \"aead\", and \"comp\" are \
+                         invalid",
+                        proto,
+                    ));
+                }
+                if !algos.auth {
+                    return Err(proto_error(
+                        "ALGO-TYPE value \"auth\" or \"auth-trunc\" is \
+                         required",
+                        proto,
+                    ));
+                }
+            }
+            IPPROTO_COMP => {
+                if algos.crypt || algos.auth || algos.aead {
+                    return Err(proto_error(
+                        "ALGO-TYPE values \"enc\", \"auth\", \"auth-trunc\", \
+                         and \"aead\" are invalid",
+                        proto,
+                    ));
+                }
+                if !algos.comp {
+                    return Err(proto_error(
+                        "ALGO-TYPE value \"comp\" is required",
+                        proto,
+                    ));
+                }
+            }
+            _ => (),
+        }
+    } else if algos.any() {
+        return Err(proto_error("ALGO is invalid", proto));
+    }
+
+    if xfrm_proto_is_ro(proto) {
+        match info.mode {
+            XFRM_MODE_ROUTEOPTIMIZATION | XFRM_MODE_IN_TRIGGER => (),
+            XFRM_MODE_TRANSPORT => {
+                return Err(proto_error("\"mode\" is required", proto));
+            }
+            mode => {
+                return Err(proto_error(
+                    &format!(
+                        "MODE value \"{}\" is invalid",
+                        mode_to_name(mode)
+                    ),
+                    proto,
+                ));
+            }
+        }
+        if !has_coa {
+            return Err(proto_error("\"coa\" is required", proto));
+        }
+    } else if has_coa {
+        return Err(proto_error("\"coa\" is invalid", proto));
+    }
+    Ok(())
+}
+
+/// Equal to iproute2 `xfrm_state_modify()`
+pub(crate) async fn handle_modify(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    update: bool,
+    family: AddressFamily,
+) -> Result<(), CliError> {
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    let mut info = XfrmUsersaInfo {
+        family: u16::from(u8::from(family)),
+        lft: XfrmLifetimeCfg::unlimited(),
+        ..Default::default()
+    };
+    let mut attrs = Vec::new();
+    let mut family = family;
+    let mut has_id = false;
+    let mut has_coa = false;
+    let mut algos = XfrmAlgoTypes::default();
+    let mut mark = XfrmMark::default();
+    let mut replay_window: u32 = 0;
+    let mut seq: u32 = 0;
+    let mut oseq: u32 = 0;
+    let mut seq_hi: u32 = 0;
+    let mut oseq_hi: u32 = 0;
+    let mut extra_flags = 0;
+    let mut offload = None;
+    let mut output_mark = XfrmMark::default();
+    let mut if_id = None;
+    let mut tfcpad = 0;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "mode" => info.mode = parse_mode(next_opt(&mut opts)?)?,
+            "mark" => mark = parse_mark(&mut opts)?,
+            "reqid" => {
+                info.reqid =
+                    parse_num(next_opt(&mut opts)?, "REQID value is invalid")?
+            }
+            "seq" => {
+                // Like iproute2 `xfrm_seq_parse()`, stored in network order
+                info.seq = parse_num::<u32>(
+                    next_opt(&mut opts)?,
+                    "SEQ value is invalid",
+                )?
+                .to_be()
+            }
+            "replay-window" | "replay-seq" | "replay-seq-hi"
+            | "replay-oseq" | "replay-oseq-hi" => {
+                let value = parse_num(
+                    next_opt(&mut opts)?,
+                    &format!("value after \"{opt}\" is invalid"),
+                )?;
+                match *opt {
+                    "replay-window" => replay_window = value,
+                    "replay-seq" => seq = value,
+                    "replay-seq-hi" => seq_hi = value,
+                    "replay-oseq" => oseq = value,
+                    _ => oseq_hi = value,
+                }
+            }
+            "flag" => {
+                info.flags = parse_flag_list(
+                    &mut opts,
+                    &XFRM_STATE_FLAG_NAMES,
+                    "FLAG value is invalid",
+                )?
+            }
+            "extra-flag" => {
+                extra_flags = parse_flag_list(
+                    &mut opts,
+                    &XFRM_SA_EXTRA_FLAG_NAMES,
+                    "EXTRA-FLAG value is invalid",
+                )?
+            }
+            "sel" => {
+                info.sel =
+                    parse_selector(&mut opts, AddressFamily::Unspec, &ifnames)?;
+                family = address_family(info.sel.family);
+            }
+            "limit" => parse_lifetime(&mut info.lft, &mut opts)?,
+            "encap" => {
+                let encap_type = parse_encap_type(next_opt(&mut opts)?)?;
+                let sport = parse_num(
+                    next_opt(&mut opts)?,
+                    "SPORT value after \"encap\" is invalid",
+                )?;
+                let dport = parse_num(
+                    next_opt(&mut opts)?,
+                    "DPORT value after \"encap\" is invalid",
+                )?;
+                let oa =
+                    parse_ip_addr(next_opt(&mut opts)?, AddressFamily::Unspec)?;
+                attrs.push(XfrmAttr::Encap(XfrmEncapTmpl {
+                    encap_type,
+                    sport,
+                    dport,
+                    oa: XfrmAddress::from_ip(oa),
+                }));
+            }
+            "coa" => {
+                if has_coa {
+                    return Err(duparg("coa", opt));
+                }
+                has_coa = true;
+                let coa = CliIpPrefix::parse(next_opt(&mut opts)?, family)?;
+                attrs.push(XfrmAttr::CoAddr(XfrmAddress::from_ip(coa.addr)));
+            }
+            "offload" => offload = Some(parse_offload(&mut opts, &ifnames)?),
+            "output-mark" => {
+                output_mark.value = parse_num(
+                    next_opt(&mut opts)?,
+                    "value after \"output-mark\" is invalid",
+                )?;
+                if opts.as_slice().first() == Some(&"mask") {
+                    opts.next();
+                    output_mark.mask = parse_num(
+                        next_opt(&mut opts)?,
+                        "mask value is invalid",
+                    )?;
+                }
+            }
+            "if_id" => {
+                if_id = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "value after \"if_id\" is invalid",
+                )?)
+            }
+            "tfcpad" => {
+                tfcpad = parse_num(
+                    next_opt(&mut opts)?,
+                    "value after \"tfcpad\" is invalid",
+                )?
+            }
+            _ => {
+                if let Some(kind) = XfrmAlgoType::from_name(opt) {
+                    algos.add(kind)?;
+                    attrs.push(kind.to_attr(parse_algo(kind, &mut opts)?));
+                } else {
+                    if has_id {
+                        return Err(invarg(opt, "unknown"));
+                    }
+                    has_id = true;
+                    let id = XfrmIdOpts::parse(opt, &mut opts, family, false)?;
+                    info.saddr = id.saddr();
+                    info.id = id.to_id();
+                    if id.family() != 0 {
+                        info.family = id.family();
+                    }
+                    if family == AddressFamily::Unspec {
+                        family = address_family(info.family);
+                    }
+                }
+            }
+        }
+    }
+
+    if info.flags & XFRM_STATE_ESN > 0 && replay_window == 0 {
+        return Err(CliError::from("esn flag set without replay-window."));
+    }
+    if replay_window > XFRMA_REPLAY_ESN_MAX {
+        return Err(CliError::from(
+            format!("max replay window size is {XFRMA_REPLAY_ESN_MAX}")
+                .as_str(),
+        ));
+    }
+    if let Some(offload) = offload {
+        attrs.push(XfrmAttr::OffloadDev(offload));
+    }
+    if info.flags & XFRM_STATE_ESN > 0 || replay_window > u32::BITS {
+        attrs.push(XfrmAttr::ReplayEsnVal(XfrmReplayStateEsn {
+            bmp_len: replay_window.div_ceil(u32::BITS),
+            oseq,
+            seq,
+            oseq_hi,
+            seq_hi,
+            replay_window,
+            bmp: Vec::new(),
+        }));
+    } else {
+        if seq != 0 || oseq != 0 {
+            attrs.push(XfrmAttr::ReplayVal(XfrmReplayState {
+                oseq,
+                seq,
+                bitmap: 0,
+            }));
+        }
+        info.replay_window = replay_window as u8;
+    }
+    if extra_flags != 0 {
+        attrs.push(XfrmAttr::SaExtraFlags(extra_flags));
+    }
+
+    if !has_id {
+        return Err(CliError::from("Not enough information: ID is required"));
+    }
+    if mark.mask != 0 {
+        attrs.push(XfrmAttr::Mark(mark));
+    }
+    check_proto(&info, &algos, has_coa)?;
+
+    if output_mark.value != 0 {
+        attrs.push(XfrmAttr::SetMark(output_mark.value));
+    }
+    if output_mark.mask != 0 {
+        attrs.push(XfrmAttr::SetMarkMask(output_mark.mask));
+    }
+    if let Some(if_id) = if_id {
+        attrs.push(XfrmAttr::IfId(if_id));
+    }
+    if tfcpad != 0 {
+        attrs.push(XfrmAttr::TfcPad(tfcpad));
+    }
+    if info.family == 0 {
+        info.family = AF_INET;
+    }
+
+    let msg = XfrmSaMessage {
+        info,
+        attributes: attrs,
+    };
+    xfrm_request(
+        ctx,
+        if update {
+            XfrmNetlinkMessage::UpdSa(msg)
+        } else {
+            XfrmNetlinkMessage::NewSa(msg)
+        },
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `xfrm_state_allocspi()`
+pub(crate) async fn handle_allocspi(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmStateInfo>, CliError> {
+    let mut msg = XfrmSpiMessage::default();
+    msg.info.family = u16::from(u8::from(family));
+    let mut family = family;
+    let mut has_id = false;
+    let mut min = None;
+    let mut max = None;
+    let mut mark = XfrmMark::default();
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "mode" => msg.info.mode = parse_mode(next_opt(&mut opts)?)?,
+            "mark" => mark = parse_mark(&mut opts)?,
+            "reqid" => {
+                msg.info.reqid =
+                    parse_num(next_opt(&mut opts)?, "REQID value is invalid")?
+            }
+            "seq" => {
+                msg.info.seq = parse_num::<u32>(
+                    next_opt(&mut opts)?,
+                    "SEQ value is invalid",
+                )?
+                .to_be()
+            }
+            "min" | "max" => {
+                let value = next_opt(&mut opts)?;
+                let target = if *opt == "min" { &mut min } else { &mut max };
+                if target.is_some() {
+                    return Err(duparg(opt, value));
+                }
+                *target = Some(parse_num::<u32>(
+                    value,
+                    &format!("value after \"{opt}\" is invalid"),
+                )?);
+            }
+            _ => {
+                if has_id {
+                    return Err(invarg(opt, "unknown"));
+                }
+                has_id = true;
+                let id = XfrmIdOpts::parse(opt, &mut opts, family, false)?;
+                if id.spi.unwrap_or_default() != 0 {
+                    return Err(CliError::from("\"spi\" is invalid"));
+                }
+                msg.info.saddr = id.saddr();
+                msg.info.id = id.to_id();
+                if id.family() != 0 {
+                    msg.info.family = id.family();
+                }
+                if family == AddressFamily::Unspec {
+                    family = address_family(msg.info.family);
+                }
+            }
+        }
+    }
+
+    if !has_id {
+        return Err(CliError::from("Not enough information: ID is required"));
+    }
+    (msg.min, msg.max) = match (min, max) {
+        (Some(min), Some(max)) => {
+            if min > max {
+                return Err(CliError::from(
+                    "value after \"min\" is larger than value after \"max\"",
+                ));
+            }
+            (min, max)
+        }
+        (Some(_), None) => return Err(CliError::from("\"max\" is missing")),
+        (None, Some(_)) => return Err(CliError::from("\"min\" is missing")),
+        // Like iproute2, the default range is the one of PF_KEY while
+        // IPCOMP SPI is 16 bits
+        (None, None) => (
+            0x100,
+            if msg.info.id.proto == IPPROTO_COMP {
+                0xffff
+            } else {
+                0x0fffffff
+            },
+        ),
+    };
+    if mark.mask & mark.value != 0 {
+        msg.attributes.push(XfrmAttr::Mark(mark));
+    }
+    if msg.info.family == 0 {
+        msg.info.family = AF_INET;
+    }
+
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(
+        xfrm_request(ctx, XfrmNetlinkMessage::AllocSpi(msg), NLM_F_REQUEST)
+            .await?
+            .into_iter()
+            .filter_map(|msg| {
+                CliXfrmStateInfo::from_nl_msg(msg, false, show_stats, &ifnames)
+            })
+            .collect(),
+    )
+}
+
+/// Equal to iproute2 `xfrm_state_get_or_delete()`
+pub(crate) async fn handle_get_or_delete(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    delete: bool,
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmStateInfo>, CliError> {
+    let mut msg = XfrmSaIdMessage {
+        id: XfrmUsersaId {
+            family: u16::from(u8::from(family)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut has_id = false;
+    let mut mark = XfrmMark::default();
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if *opt == "mark" {
+            mark = parse_mark(&mut opts)?;
+        } else {
+            if has_id {
+                return Err(invarg(opt, "unknown"));
+            }
+            has_id = true;
+            let id = XfrmIdOpts::parse(opt, &mut opts, family, false)?;
+            let xfrm_id = id.to_id();
+            msg.id.daddr = xfrm_id.daddr;
+            msg.id.spi = xfrm_id.spi;
+            msg.id.proto = xfrm_id.proto;
+            if id.family() != 0 {
+                msg.id.family = id.family();
+            }
+            msg.attributes.push(XfrmAttr::SrcAddr(id.saddr()));
+        }
+    }
+    if mark.mask & mark.value != 0 {
+        msg.attributes.push(XfrmAttr::Mark(mark));
+    }
+    if msg.id.family == 0 {
+        msg.id.family = AF_INET;
+    }
+
+    if delete {
+        xfrm_request(
+            ctx,
+            XfrmNetlinkMessage::DelSa(msg),
+            NLM_F_REQUEST | NLM_F_ACK,
+        )
+        .await?;
+        return Ok(Vec::new());
+    }
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(
+        xfrm_request(ctx, XfrmNetlinkMessage::GetSa(msg), NLM_F_REQUEST)
+            .await?
+            .into_iter()
+            .filter_map(|msg| {
+                CliXfrmStateInfo::from_nl_msg(msg, false, show_stats, &ifnames)
+            })
+            .collect(),
+    )
+}
+
+// Equal to iproute2 `xfrm_state_filter_match()`
+#[derive(Debug, Default)]
+struct XfrmStateFilter {
+    id: XfrmIdOpts,
+    mode: Option<u8>,
+    reqid: Option<u32>,
+    flags: Option<u8>,
+}
+
+impl XfrmStateFilter {
+    fn matches(&self, info: &XfrmUsersaInfo) -> bool {
+        if let Some(src) = self.id.src
+            && !src.contains(&info.saddr.to_ip(info.family))
+        {
+            return false;
+        }
+        if let Some(dst) = self.id.dst
+            && !dst.contains(&info.id.daddr.to_ip(info.family))
+        {
+            return false;
+        }
+        if self.id.proto.is_some_and(|v| v != info.id.proto)
+            || self.id.spi.is_some_and(|v| v != info.id.spi)
+            || self.mode.is_some_and(|v| v != info.mode)
+            || self.reqid.is_some_and(|v| v != info.reqid)
+        {
+            return false;
+        }
+        self.flags.is_none_or(|v| v & info.flags != 0)
+    }
+}
+
+/// Equal to iproute2 `xfrm_state_list_or_deleteall()` for listing
+pub(crate) async fn handle_list(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    family: AddressFamily,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmStateInfo>, CliError> {
+    let mut filter = XfrmStateFilter::default();
+    let mut addr_filter = XfrmAddressFilter {
+        family: u16::from(u8::from(family)),
+        ..Default::default()
+    };
+    let mut nokeys = false;
+    let mut has_id = false;
+
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        match *opt {
+            "nokeys" => nokeys = true,
+            "mode" => filter.mode = Some(parse_mode(next_opt(&mut opts)?)?),
+            "reqid" => {
+                filter.reqid = Some(parse_num(
+                    next_opt(&mut opts)?,
+                    "REQID value is invalid",
+                )?)
+            }
+            "flag" => {
+                filter.flags = Some(parse_flag_list(
+                    &mut opts,
+                    &XFRM_STATE_FLAG_NAMES,
+                    "FLAG value is invalid",
+                )?)
+            }
+            _ => {
+                if has_id {
+                    return Err(invarg(opt, "unknown"));
+                }
+                has_id = true;
+                filter.id = XfrmIdOpts::parse(opt, &mut opts, family, true)?;
+                if let Some(src) = filter.id.src {
+                    addr_filter.saddr = XfrmAddress::from_ip(src.addr);
+                    addr_filter.splen = src.prefix_len;
+                    addr_filter.family = af_family(&src.addr);
+                }
+                if let Some(dst) = filter.id.dst {
+                    addr_filter.daddr = XfrmAddress::from_ip(dst.addr);
+                    addr_filter.dplen = dst.prefix_len;
+                    addr_filter.family = af_family(&dst.addr);
+                }
+            }
+        }
+    }
+
+    let mut attrs = Vec::new();
+    if let Some(proto) = filter.id.proto.filter(|p| *p != 0) {
+        attrs.push(XfrmAttr::Proto(proto));
+    }
+    attrs.push(XfrmAttr::AddressFilter(addr_filter));
+
+    let ifnames = get_ifnames(ctx.handle()).await?;
+    Ok(xfrm_request(
+        ctx,
+        XfrmNetlinkMessage::DumpSa(attrs),
+        NLM_F_REQUEST | NLM_F_DUMP,
+    )
+    .await?
+    .into_iter()
+    .filter(|msg| match msg {
+        XfrmNetlinkMessage::NewSa(msg) => filter.matches(&msg.info),
+        _ => false,
+    })
+    .filter_map(|msg| {
+        CliXfrmStateInfo::from_nl_msg(msg, nokeys, show_stats, &ifnames)
+    })
+    .collect())
+}
+
+/// Equal to iproute2 `xfrm_state_flush()`
+pub(crate) async fn handle_flush(
+    ctx: &NetlinkContext,
+    opts: &[&str],
+    show_stats: u8,
+) -> Result<(), CliError> {
+    let mut proto = None;
+    let mut opts = opts.iter();
+    while let Some(opt) = opts.next() {
+        if *opt == "proto" {
+            if proto.is_some() {
+                return Err(duparg("proto", opt));
+            }
+            proto = Some(parse_xfrm_proto(next_opt(&mut opts)?)?);
+        } else {
+            return Err(invarg(opt, "unknown"));
+        }
+    }
+    let proto = proto.unwrap_or_default();
+    if show_stats > 1 {
+        writeln!(
+            std::io::stderr(),
+            "Flush state with XFRM-PROTO value \"{}\"",
+            xfrm_proto_to_name(proto)
+        )
+        .ok();
+    }
+    xfrm_request(
+        ctx,
+        XfrmNetlinkMessage::FlushSa(proto),
+        NLM_F_REQUEST | NLM_F_ACK,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Equal to iproute2 `print_sadinfo()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmSadInfo {
+    count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buckets_max: Option<u32>,
+}
+
+impl std::fmt::Display for CliXfrmSadInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\t SAD count {}", self.count)?;
+        if let (Some(count), Some(max)) = (self.buckets_count, self.buckets_max)
+        {
+            write!(f, " (buckets count {count} Max {max})")?;
+        }
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliXfrmSadInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliXfrmSadInfo {}
+
+/// Equal to iproute2 `xfrm_sad_getinfo()`
+pub(crate) async fn handle_count(
+    ctx: &NetlinkContext,
+    show_stats: bool,
+) -> Result<Vec<CliXfrmSadInfo>, CliError> {
+    let mut ret = Vec::new();
+    for msg in
+        xfrm_request(ctx, XfrmNetlinkMessage::GetSadInfo, NLM_F_REQUEST).await?
+    {
+        let XfrmNetlinkMessage::NewSadInfo(attrs) = msg else {
+            continue;
+        };
+        let mut info = CliXfrmSadInfo {
+            count: 0,
+            buckets_count: None,
+            buckets_max: None,
+        };
+        let mut has_count = false;
+        for attr in attrs {
+            match attr {
+                XfrmSadInfoAttr::Count(v) => {
+                    info.count = v;
+                    has_count = true;
+                }
+                XfrmSadInfoAttr::HashInfo(count, max) if show_stats => {
+                    info.buckets_count = Some(count);
+                    info.buckets_max = Some(max);
+                }
+                _ => (),
+            }
+        }
+        if !has_count {
+            return Err(CliError::from("BAD SAD info returned"));
+        }
+        ret.push(info);
+    }
+    Ok(ret)
+}
+
+/// Equal to iproute2 `xfrm_state_info_print()`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CliXfrmStateInfo {
+    #[serde(flatten)]
+    id: CliXfrmIdInfo,
+    replay_window: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flag: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra_flag: Option<Vec<String>>,
+    #[serde(flatten)]
+    attrs: CliXfrmAttrs,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sel: Option<CliXfrmSelector>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifetime: Option<CliXfrmLifetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<CliXfrmStats>,
+    /// The raw flags shown in binary in statistics mode
+    #[serde(skip)]
+    flags_raw: Option<u8>,
+}
+
+impl CliXfrmStateInfo {
+    pub(crate) fn new(
+        info: &XfrmUsersaInfo,
+        attrs: &[XfrmAttr],
+        nokeys: bool,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Self {
+        Self {
+            id: CliXfrmIdInfo::new(
+                &info.saddr,
+                &info.id,
+                info.mode,
+                info.reqid,
+                info.family,
+                xfrm_proto_is_ipsec(info.id.proto),
+                show_stats,
+            ),
+            replay_window: info.replay_window,
+            seq: show_stats.then_some(info.seq),
+            flag: (show_stats || info.flags != 0)
+                .then(|| flags_to_names(info.flags, &XFRM_STATE_FLAG_NAMES)),
+            extra_flag: attrs.iter().find_map(|attr| match attr {
+                XfrmAttr::SaExtraFlags(v) => {
+                    Some(flags_to_names(*v, &XFRM_SA_EXTRA_FLAG_NAMES))
+                }
+                _ => None,
+            }),
+            attrs: CliXfrmAttrs::new(
+                attrs,
+                info.family,
+                nokeys,
+                show_stats,
+                ifnames,
+            ),
+            sel: (!info.sel.is_zero()).then(|| {
+                CliXfrmSelector::new(
+                    &info.sel,
+                    info.family,
+                    show_stats,
+                    ifnames,
+                )
+            }),
+            lifetime: show_stats
+                .then(|| CliXfrmLifetime::new(&info.lft, Some(&info.curlft))),
+            stats: show_stats.then(|| CliXfrmStats::new(&info.stats)),
+            flags_raw: show_stats.then_some(info.flags),
+        }
+    }
+
+    fn from_nl_msg(
+        msg: XfrmNetlinkMessage,
+        nokeys: bool,
+        show_stats: bool,
+        ifnames: &HashMap<u32, String>,
+    ) -> Option<Self> {
+        match msg {
+            XfrmNetlinkMessage::NewSa(msg) | XfrmNetlinkMessage::UpdSa(msg) => {
+                Some(Self::new(
+                    &msg.info,
+                    &msg.attributes,
+                    nokeys,
+                    show_stats,
+                    ifnames,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut ret = self.id.lines("", "");
+        let mut line = format!("\treplay-window {} ", self.replay_window);
+        if let Some(seq) = self.seq {
+            // Like iproute2, the sequence is shown by `0x%08u`
+            line.push_str(&format!("seq 0x{seq:08} "));
+        }
+        if let Some(flags) = &self.flag {
+            line.push_str(&format!("flag {}", flags.join(" ")));
+        }
+        if let Some(flags) = self.flags_raw {
+            line.push_str(&format!(" (0x{flags:08b})"));
+        }
+        if let Some(flags) = &self.extra_flag {
+            line.push_str(&format!("extra_flag {}", flags.join(" ")));
+        }
+        ret.push(line);
+        ret.extend(self.attrs.lines("\t"));
+        if let Some(sel) = &self.sel {
+            ret.push(format!("\tsel {sel}"));
+        }
+        if let Some(lifetime) = &self.lifetime {
+            ret.extend(lifetime.lines("\t"));
+        }
+        if let Some(stats) = &self.stats {
+            ret.extend(stats.lines("\t"));
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliXfrmStateInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+impl CanDisplay for CliXfrmStateInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Like iproute2, every line including the last one ends with `\`
+    fn gen_oneline_string(&self) -> String {
+        format!("{}\\", self.lines().join("\\"))
+    }
+}
+
+impl CanOutput for CliXfrmStateInfo {}
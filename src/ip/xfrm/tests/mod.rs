@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MIT
+
+#[cfg(test)]
+mod monitor;
+#[cfg(test)]
+mod policy;
+#[cfg(test)]
+mod state;
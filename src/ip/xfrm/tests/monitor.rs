@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+
+use std::{io::Read, process::Child, time::Duration};
+
+use crate::tests::{
+    exec_cmd, ip_rs_exec_cmd_output, ip_rs_spawn, lock_net_test,
+};
+
+// Wait for the monitors subscribing the netlink groups
+const MONITOR_WAIT: Duration = Duration::from_millis(500);
+
+const POLICY: [&str; 8] = [
+    "src",
+    "10.40.0.0/24",
+    "dst",
+    "10.40.1.0/24",
+    "dir",
+    "out",
+    "ptype",
+    "sub",
+];
+
+fn stop_monitor(mut child: Child) -> String {
+    child.kill().ok();
+    child.wait().ok();
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout.read_to_string(&mut output).ok();
+    }
+    output
+}
+
+// Only keep the events of test policy as other tests might change the
+// system in parallel, every event is single line with `-o`.
+fn filter_events(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .filter(|line| {
+            line.contains("10.40.") || line.starts_with("Flushed policy")
+        })
+        .collect()
+}
+
+#[test]
+fn test_xfrm_monitor_policy() {
+    let _lock = lock_net_test();
+    for args in [&["-o"][..], &["-o", "-s"][..]] {
+        let args = [args, &["xfrm", "monitor", "policy"]].concat();
+        let expected = std::process::Command::new("ip")
+            .args(&args)
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn iproute2 xfrm monitor");
+        let ours = ip_rs_spawn(&args);
+        std::thread::sleep(MONITOR_WAIT);
+
+        exec_cmd(
+            &[
+                &["ip", "xfrm", "policy", "add"][..],
+                &POLICY,
+                &[
+                    "priority",
+                    "5",
+                    "tmpl",
+                    "src",
+                    "198.51.100.1",
+                    "dst",
+                    "198.51.100.2",
+                    "proto",
+                    "esp",
+                    "mode",
+                    "tunnel",
+                ],
+            ]
+            .concat(),
+        );
+        exec_cmd(
+            &[
+                &["ip", "xfrm", "policy", "update"][..],
+                &POLICY,
+                &["priority", "6", "action", "block"],
+            ]
+            .concat(),
+        );
+        exec_cmd(&[&["ip", "xfrm", "policy", "delete"][..], &POLICY].concat());
+        // Kernel only notifies flush when any policy was removed
+        exec_cmd(&[&["ip", "xfrm", "policy", "add"][..], &POLICY].concat());
+        exec_cmd(&["ip", "xfrm", "policy", "flush", "ptype", "sub"]);
+
+        std::thread::sleep(MONITOR_WAIT);
+        let expected_output = stop_monitor(expected);
+        let our_output = stop_monitor(ours);
+        let expected_events = filter_events(&expected_output);
+
+        assert_eq!(expected_events.len(), 5);
+        assert!(expected_events[1].starts_with("Updated "));
+        assert!(expected_events[2].starts_with("Deleted "));
+        assert!(expected_events[4].starts_with("Flushed policy ptype sub"));
+        pretty_assertions::assert_eq!(
+            expected_events,
+            filter_events(&our_output)
+        );
+    }
+}
+
+#[test]
+fn test_xfrm_monitor_invalid_args() {
+    let output = ip_rs_exec_cmd_output(&["xfrm", "monitor", "foo"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "Argument \"foo\" is unknown, try \"ip xfrm monitor help\"."
+    ));
+}
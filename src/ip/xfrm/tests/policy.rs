@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    lock_net_test,
+};
+
+const BLOCK_POLICY: [&str; 8] = [
+    "src",
+    "10.20.0.0/24",
+    "dst",
+    "10.20.1.0/24",
+    "dir",
+    "in",
+    "ptype",
+    "sub",
+];
+
+const TMPL_POLICY: [&str; 12] = [
+    "src",
+    "10.20.0.0/24",
+    "dst",
+    "10.20.1.0/24",
+    "proto",
+    "tcp",
+    "dport",
+    "443",
+    "dir",
+    "in",
+    "if_id",
+    "9",
+];
+
+fn add_policies() {
+    exec_cmd(
+        &[
+            &["ip", "xfrm", "policy", "add"][..],
+            &BLOCK_POLICY,
+            &[
+                "action", "block", "priority", "7", "flag", "localok", "icmp",
+                "mark", "0x5", "mask", "0xf",
+            ],
+        ]
+        .concat(),
+    );
+    exec_cmd(
+        &[
+            &["ip", "xfrm", "policy", "add"][..],
+            &TMPL_POLICY,
+            &[
+                "priority",
+                "9",
+                "tmpl",
+                "src",
+                "198.51.100.1",
+                "dst",
+                "198.51.100.2",
+                "proto",
+                "esp",
+                "reqid",
+                "3",
+                "mode",
+                "tunnel",
+                "level",
+                "use",
+            ],
+        ]
+        .concat(),
+    );
+    exec_cmd(&[
+        "ip",
+        "xfrm",
+        "policy",
+        "add",
+        "src",
+        "2001:db8:200::/64",
+        "dst",
+        "2001:db8:201::/64",
+        "dir",
+        "out",
+        "priority",
+        "11",
+        "tmpl",
+        "proto",
+        "ah",
+        "mode",
+        "transport",
+    ]);
+}
+
+fn del_policies() {
+    for args in [
+        [&BLOCK_POLICY[..], &["mark", "0x5", "mask", "0xf"]].concat(),
+        TMPL_POLICY.to_vec(),
+        vec![
+            "src",
+            "2001:db8:200::/64",
+            "dst",
+            "2001:db8:201::/64",
+            "dir",
+            "out",
+        ],
+    ] {
+        let _ = std::process::Command::new("ip")
+            .args([&["xfrm", "policy", "delete"][..], &args[..]].concat())
+            .output();
+    }
+}
+
+fn with_xfrm_policies<T>(test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    del_policies();
+    add_policies();
+    let result = std::panic::catch_unwind(test);
+    del_policies();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_xfrm_policy_list() {
+    with_xfrm_policies(|| {
+        for args in [&[][..], &["-s"][..], &["-o"][..]] {
+            for show_args in [
+                &[][..],
+                &["list", "dir", "in"][..],
+                &["list", "src", "10.20.0.0/16"][..],
+                &["list", "ptype", "sub"][..],
+                &["list", "action", "block"][..],
+                &["list", "priority", "11"][..],
+                &["list", "flag", "icmp"][..],
+                &["list", "proto", "tcp", "nosock"][..],
+                &[&["get"][..], &BLOCK_POLICY, &["mark", "5", "mask", "0xf"]]
+                    .concat(),
+                &[&["get"][..], &TMPL_POLICY].concat(),
+            ] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["xfrm", "policy"], show_args].concat(),
+                );
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["xfrm", "policy"], show_args].concat(),
+                );
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        let expected_output = exec_cmd(&["ip", "-6", "xfrm", "policy"]);
+        let our_output = ip_rs_exec_cmd(&["-6", "xfrm", "policy"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+
+        assert_alias_output(&["xfrm", "policy", "list"], &["xfrm", "policy"]);
+        assert_alias_output(&["xfrm", "policy", "list"], &["x", "p", "lst"]);
+    });
+}
+
+#[test]
+fn test_xfrm_policy_add_update_delete() {
+    with_xfrm_policies(|| {
+        ip_rs_exec_cmd(
+            &[
+                &["xfrm", "policy", "update"][..],
+                &TMPL_POLICY,
+                &["priority", "10", "action", "block"],
+            ]
+            .concat(),
+        );
+        let expected_output =
+            exec_cmd(&["ip", "xfrm", "policy", "list", "proto", "tcp"]);
+        assert!(expected_output.contains("\tdir in action block priority 10 "));
+        let our_output =
+            ip_rs_exec_cmd(&["xfrm", "policy", "list", "proto", "tcp"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+
+        ip_rs_exec_cmd(
+            &[&["xfrm", "policy", "delete"][..], &TMPL_POLICY].concat(),
+        );
+        let output =
+            ip_rs_exec_cmd(&["xfrm", "policy", "list", "proto", "tcp"]);
+        assert!(output.is_empty());
+
+        ip_rs_exec_cmd(&[
+            "xfrm",
+            "policy",
+            "add",
+            "src",
+            "10.20.2.0/24",
+            "dst",
+            "10.20.3.0/24",
+            "dir",
+            "fwd",
+            "ptype",
+            "sub",
+        ]);
+        for args in [&[][..], &["-s"][..], &["-s", "-s"][..], &["-o"][..]] {
+            let expected_output = exec_cmd(
+                &[&["ip"], args, &["xfrm", "policy", "count"]].concat(),
+            );
+            let our_output =
+                ip_rs_exec_cmd(&[args, &["xfrm", "policy", "count"]].concat());
+            pretty_assertions::assert_eq!(expected_output, our_output);
+        }
+
+        ip_rs_exec_cmd(&["xfrm", "policy", "flush", "ptype", "sub"]);
+        let output =
+            ip_rs_exec_cmd(&["xfrm", "policy", "list", "ptype", "sub"]);
+        assert!(output.is_empty());
+    });
+}
+
+#[test]
+fn test_xfrm_policy_set() {
+    let _lock = lock_net_test();
+    ip_rs_exec_cmd(&[
+        "xfrm", "policy", "set", "hthresh4", "24", "16", "hthresh6", "64", "48",
+    ]);
+    let output = exec_cmd(&["ip", "-s", "-s", "xfrm", "policy", "count"]);
+    exec_cmd(&[
+        "ip", "xfrm", "policy", "set", "hthresh4", "32", "32", "hthresh6",
+        "128", "128",
+    ]);
+    assert!(output.contains("\t SPD IPv4 thresholds: local 24 remote 16"));
+    assert!(output.contains("\t SPD IPv6 thresholds: local 64 remote 48"));
+}
+
+#[test]
+fn test_xfrm_policy_invalid_args() {
+    for (args, error) in [
+        (
+            &["xfrm", "policy", "add", "src", "10.0.0.0/24"][..],
+            "Not enough information: DIR is required.",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "foo"][..],
+            "argument \"foo\" is wrong: DIR value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "dir", "out"][..],
+            "duplicate \"dir\": \"dir\" is the second value.",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "action", "drop"][..],
+            "argument \"drop\" is wrong: ACTION value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "ptype", "foo"][..],
+            "argument \"foo\" is wrong: PTYPE value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "flag", "0x1ff"][..],
+            "argument \"0x1ff\" is wrong: FLAG value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "tmpl", "dir", "out"][..],
+            "argument \"XFRM-PROTO\" is required",
+        ),
+        (
+            &[
+                "xfrm", "policy", "add", "dir", "in", "tmpl", "proto", "esp",
+                "level", "foo",
+            ][..],
+            "argument \"foo\" is wrong: LEVEL value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "add", "dir", "in", "foo"][..],
+            "duplicate \"unknown\": \"foo\" is the second value.",
+        ),
+        (
+            &["xfrm", "policy", "get", "dir", "in"][..],
+            "Not enough information: either SELECTOR or INDEX is required.",
+        ),
+        (
+            &[
+                "xfrm",
+                "policy",
+                "get",
+                "dir",
+                "in",
+                "index",
+                "1",
+                "src",
+                "10.0.0.0/24",
+            ][..],
+            "either \"SELECTOR\" is duplicate, or \"INDEX\" is a garbage.",
+        ),
+        (
+            &["xfrm", "policy", "list", "foo"][..],
+            "argument \"foo\" is wrong: unknown",
+        ),
+        (
+            &["xfrm", "policy", "flush", "ptype", "sub", "ptype", "main"][..],
+            "duplicate \"ptype\": \"ptype\" is the second value.",
+        ),
+        (
+            &["xfrm", "policy", "set", "hthresh4", "33", "0"][..],
+            "argument \"33\" is wrong: hthresh4 LBITS value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "set", "hthresh6", "0", "129"][..],
+            "argument \"129\" is wrong: hthresh6 RBITS value is invalid",
+        ),
+        (
+            &["xfrm", "policy", "set", "foo"][..],
+            "argument \"foo\" is wrong: unknown",
+        ),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
@@ -0,0 +1,374 @@
+// SPDX-License-Identifier: MIT
+
+use crate::tests::{
+    assert_alias_output, exec_cmd, ip_rs_exec_cmd, ip_rs_exec_cmd_output,
+    lock_net_test,
+};
+
+const ESP_STATE: [&str; 14] = [
+    "src",
+    "198.51.100.1",
+    "dst",
+    "198.51.100.2",
+    "proto",
+    "esp",
+    "spi",
+    "0x1001",
+    "reqid",
+    "7",
+    "mode",
+    "tunnel",
+    "aead",
+    "rfc4106(gcm(aes))",
+];
+
+fn add_states() {
+    exec_cmd(
+        &[
+            &["ip", "xfrm", "state", "add"][..],
+            &ESP_STATE,
+            &[
+                "0x0102030405060708090a0b0c0d0e0f1011121314",
+                "128",
+                "replay-window",
+                "32",
+                "flag",
+                "noecn",
+                "af-unspec",
+                "sel",
+                "src",
+                "10.0.0.0/24",
+                "dst",
+                "10.0.1.0/24",
+                "proto",
+                "tcp",
+                "dport",
+                "80",
+                "limit",
+                "byte-hard",
+                "1000000",
+            ],
+        ]
+        .concat(),
+    );
+    exec_cmd(&[
+        "ip",
+        "xfrm",
+        "state",
+        "add",
+        "src",
+        "2001:db8:100::1",
+        "dst",
+        "2001:db8:100::2",
+        "proto",
+        "ah",
+        "spi",
+        "0x2002",
+        "auth-trunc",
+        "hmac(sha256)",
+        "0x00112233445566778899aabbccddeeff",
+        "96",
+        "mark",
+        "0x10",
+        "mask",
+        "0xff",
+        "if_id",
+        "5",
+    ]);
+    exec_cmd(&[
+        "ip",
+        "xfrm",
+        "state",
+        "add",
+        "src",
+        "198.51.100.1",
+        "dst",
+        "198.51.100.3",
+        "proto",
+        "esp",
+        "spi",
+        "0x3003",
+        "enc",
+        "cbc(aes)",
+        "0x00112233445566778899aabbccddeeff",
+        "auth",
+        "hmac(sha1)",
+        "0x00112233445566778899aabbccddeeff00112233",
+        "encap",
+        "espinudp",
+        "4500",
+        "4500",
+        "0.0.0.0",
+        "replay-window",
+        "128",
+        "flag",
+        "esn",
+        "output-mark",
+        "0x20",
+    ]);
+}
+
+fn del_states() {
+    for args in [
+        &["src", "198.51.100.1", "dst", "198.51.100.2"][..],
+        &["src", "198.51.100.1", "dst", "198.51.100.3"][..],
+        &["src", "2001:db8:100::1", "dst", "2001:db8:100::2"][..],
+    ] {
+        let _ = std::process::Command::new("ip")
+            .args([&["xfrm", "state", "deleteall"][..], args].concat())
+            .output();
+    }
+}
+
+fn with_xfrm_states<T>(test: T)
+where
+    T: FnOnce() + std::panic::UnwindSafe,
+{
+    let _lock = lock_net_test();
+    del_states();
+    add_states();
+    let result = std::panic::catch_unwind(test);
+    del_states();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_xfrm_state_list() {
+    with_xfrm_states(|| {
+        for args in [&[][..], &["-s"][..], &["-o"][..]] {
+            for show_args in [
+                &[][..],
+                &["list", "src", "198.51.100.1"][..],
+                &["list", "dst", "198.51.100.0/24", "proto", "esp"][..],
+                &["list", "nokeys", "reqid", "7"][..],
+                &["list", "flag", "esn"][..],
+                &["list", "mode", "transport"][..],
+                &[
+                    "get",
+                    "dst",
+                    "198.51.100.2",
+                    "proto",
+                    "esp",
+                    "spi",
+                    "0x1001",
+                ][..],
+                &[
+                    "get",
+                    "dst",
+                    "2001:db8:100::2",
+                    "proto",
+                    "ah",
+                    "spi",
+                    "0x2002",
+                    "mark",
+                    "0x10",
+                    "mask",
+                    "0xff",
+                ][..],
+            ] {
+                let expected_output = exec_cmd(
+                    &[&["ip"], args, &["xfrm", "state"], show_args].concat(),
+                );
+                let our_output = ip_rs_exec_cmd(
+                    &[args, &["xfrm", "state"], show_args].concat(),
+                );
+                pretty_assertions::assert_eq!(expected_output, our_output);
+            }
+        }
+
+        let expected_output = exec_cmd(&["ip", "-6", "xfrm", "state"]);
+        let our_output = ip_rs_exec_cmd(&["-6", "xfrm", "state"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+
+        assert_alias_output(&["xfrm", "state", "list"], &["xfrm", "state"]);
+        assert_alias_output(&["xfrm", "state", "list"], &["x", "s", "lst"]);
+    });
+}
+
+#[test]
+fn test_xfrm_state_add_update_delete() {
+    with_xfrm_states(|| {
+        ip_rs_exec_cmd(
+            &[
+                &["xfrm", "state", "update"][..],
+                &ESP_STATE,
+                &[
+                    "0x1112131415161718191a1b1c1d1e1f2021222324",
+                    "128",
+                    "replay-window",
+                    "64",
+                ],
+            ]
+            .concat(),
+        );
+        let expected_output =
+            exec_cmd(&["ip", "xfrm", "state", "list", "reqid", "7"]);
+        assert!(expected_output.contains("replay-window 0 flag esn"));
+        assert!(expected_output.contains(
+            "aead rfc4106(gcm(aes)) \
+             0x1112131415161718191a1b1c1d1e1f2021222324 128"
+        ));
+        let our_output =
+            ip_rs_exec_cmd(&["xfrm", "state", "list", "reqid", "7"]);
+        pretty_assertions::assert_eq!(expected_output, our_output);
+
+        ip_rs_exec_cmd(&[
+            "xfrm",
+            "state",
+            "delete",
+            "src",
+            "198.51.100.1",
+            "dst",
+            "198.51.100.2",
+            "proto",
+            "esp",
+            "spi",
+            "0x1001",
+        ]);
+        let output = ip_rs_exec_cmd(&["xfrm", "state", "list", "reqid", "7"]);
+        assert!(output.is_empty());
+
+        ip_rs_exec_cmd(&[
+            "xfrm",
+            "state",
+            "add",
+            "src",
+            "198.51.100.1",
+            "dst",
+            "198.51.100.2",
+            "proto",
+            "comp",
+            "spi",
+            "0x4004",
+            "comp",
+            "deflate",
+            "mode",
+            "tunnel",
+        ]);
+        let output = ip_rs_exec_cmd(&["-s", "xfrm", "state", "count"]);
+        let expected_output = exec_cmd(&["ip", "-s", "xfrm", "state", "count"]);
+        pretty_assertions::assert_eq!(expected_output, output);
+        let output = ip_rs_exec_cmd(&["xfrm", "state", "count"]);
+        let expected_output = exec_cmd(&["ip", "xfrm", "state", "count"]);
+        pretty_assertions::assert_eq!(expected_output, output);
+
+        ip_rs_exec_cmd(&["xfrm", "state", "flush", "proto", "comp"]);
+        let output =
+            ip_rs_exec_cmd(&["xfrm", "state", "list", "proto", "comp"]);
+        assert!(output.is_empty());
+    });
+}
+
+#[test]
+fn test_xfrm_state_allocspi() {
+    with_xfrm_states(|| {
+        let output = ip_rs_exec_cmd(&[
+            "xfrm",
+            "state",
+            "allocspi",
+            "src",
+            "198.51.100.1",
+            "dst",
+            "198.51.100.3",
+            "proto",
+            "ah",
+            "min",
+            "0x5000",
+            "max",
+            "0x5000",
+        ]);
+        assert!(output.starts_with(
+            "src 198.51.100.1 dst 198.51.100.3\n\tproto ah spi 0x00005000 \
+             reqid 0 mode transport\n"
+        ));
+        let expected_output = exec_cmd(&[
+            "ip", "xfrm", "state", "list", "proto", "ah", "spi", "0x5000",
+        ]);
+        pretty_assertions::assert_eq!(expected_output, output);
+    });
+}
+
+#[test]
+fn test_xfrm_state_invalid_args() {
+    for (args, error) in [
+        (
+            &["xfrm", "state", "add", "src", "192.0.2.1"][..],
+            "argument \"XFRM-PROTO\" is required",
+        ),
+        (
+            &["xfrm", "state", "add", "proto", "foo"][..],
+            "argument \"foo\" is wrong: XFRM-PROTO value is invalid",
+        ),
+        (
+            &["xfrm", "state", "add", "mode", "tunnel"][..],
+            "Not enough information: ID is required",
+        ),
+        (
+            &["xfrm", "state", "add", "proto", "esp", "spi", "1"][..],
+            "ALGO-TYPE value \"enc\" or \"aead\" is required with XFRM-PROTO \
+             value \"esp\"",
+        ),
+        (
+            &[
+                "xfrm", "state", "add", "proto", "ah", "spi", "1", "enc",
+                "cbc(aes)", "0x00",
+            ][..],
+            "ALGO-TYPE values \"enc\", \"aead\", and \"comp\" are invalid \
+             with XFRM-PROTO value \"ah\"",
+        ),
+        (
+            &[
+                "xfrm", "state", "add", "proto", "esp", "spi", "1", "enc",
+                "cbc(aes)", "0xzz",
+            ][..],
+            "argument \"0xzz\" is wrong: ALGO-KEYMAT value is invalid",
+        ),
+        (
+            &[
+                "xfrm", "state", "add", "proto", "esp", "spi", "1", "aead",
+                "gcm(aes)", "0x00", "aead",
+            ][..],
+            "duplicate \"ALGO-TYPE\": \"aead\" is the second value.",
+        ),
+        (
+            &[
+                "xfrm", "state", "add", "proto", "esp", "spi", "1", "flag",
+                "esn",
+            ][..],
+            "esn flag set without replay-window.",
+        ),
+        (
+            &["xfrm", "state", "add", "proto", "comp", "spi", "0x10000"][..],
+            "SPI value is too large with XFRM-PROTO value \"comp\"",
+        ),
+        (
+            &["xfrm", "state", "allocspi", "proto", "esp", "spi", "1"][..],
+            "\"spi\" is invalid",
+        ),
+        (
+            &["xfrm", "state", "allocspi", "proto", "esp", "min", "5"][..],
+            "\"max\" is missing",
+        ),
+        (
+            &[
+                "xfrm", "state", "allocspi", "proto", "esp", "min", "5", "max",
+                "4",
+            ][..],
+            "value after \"min\" is larger than value after \"max\"",
+        ),
+        (
+            &["xfrm", "state", "list", "foo"][..],
+            "argument \"foo\" is wrong: unknown",
+        ),
+        (
+            &["xfrm", "state", "flush", "proto", "esp", "proto", "ah"][..],
+            "duplicate \"proto\": \"proto\" is the second value.",
+        ),
+        (&["xfrm", "foo"][..], "Usage: ip xfrm XFRM-OBJECT"),
+    ] {
+        let output = ip_rs_exec_cmd_output(args);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(error), "{args:?}: {stderr}");
+    }
+}
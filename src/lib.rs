@@ -1,13 +1,27 @@
 // SPDX-License-Identifier: MIT
 
+mod args;
 mod color;
 mod error;
+pub mod link;
 mod mac;
+mod number;
+mod resolve;
 mod result;
+mod rtnl;
+mod timestamp;
 
 pub use self::{
+    args::{next_opt, normalize_args},
     color::CliColor,
     error::CliError,
     mac::mac_to_string,
-    result::{CanDisplay, CanOutput, OutputFormat, print_result_and_exit},
+    number::CliNumberFormat,
+    resolve::resolve_hostnames,
+    result::{
+        CanDisplay, CanOutput, OutputFormat, gen_output_string, print_output,
+        print_result_and_exit,
+    },
+    rtnl::{nl_error_to_cli, nl_request},
+    timestamp::CliTimestamp,
 };
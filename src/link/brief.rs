@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+use super::iface::CliLinkInfo;
+use crate::{CanDisplay, CanOutput, CliColor, write_with_color};
+
+/// Columnar output of `ip -br link show`
+#[derive(Serialize)]
+pub struct CliLinkInfoBrief {
+    #[serde(skip)]
+    name_with_link: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_index: Option<u32>,
+    ifname: String,
+    operstate: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    address: String,
+    flags: Vec<String>,
+}
+
+impl From<CliLinkInfo> for CliLinkInfoBrief {
+    fn from(info: CliLinkInfo) -> Self {
+        Self {
+            name_with_link: info.name_with_link(),
+            link: info.link,
+            link_index: info.link_index,
+            ifname: info.ifname,
+            operstate: info.operstate,
+            address: info.address,
+            flags: info.flags,
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoBrief {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_with_color!(
+            f,
+            CliColor::IfaceName,
+            "{:<16} ",
+            self.name_with_link
+        )?;
+        if self.operstate == "UP" {
+            write_with_color!(f, CliColor::StateUp, "{:<14} ", self.operstate)?;
+        } else if self.operstate == "DOWN" {
+            write_with_color!(
+                f,
+                CliColor::StateDown,
+                "{:<14} ",
+                self.operstate
+            )?;
+        } else {
+            write!(f, "{:<14} ", self.operstate)?;
+        }
+        if !self.address.is_empty() {
+            write_with_color!(f, CliColor::Mac, "{} ", self.address)?;
+        }
+        write!(f, "<{}> ", self.flags.as_slice().join(","))
+    }
+}
+
+impl CanDisplay for CliLinkInfoBrief {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliLinkInfoBrief {}
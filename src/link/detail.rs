@@ -1,9 +1,13 @@
 // SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
+
 use rtnetlink::packet_route::link::{AfSpecInet6, AfSpecUnspec, LinkAttribute};
 use serde::Serialize;
 
-use crate::link::link_info::CliLinkInfo;
+use super::{
+    ifaces::bridge::CliLinkInfoDataBridgePort, link_info::CliLinkInfoKind,
+};
 
 fn should_skip_netns_immutable(val: &Option<bool>) -> bool {
     matches!(val, None | Some(false))
@@ -31,8 +35,14 @@ fn get_addr_gen_mode(af_spec_unspec: &[AfSpecUnspec]) -> String {
         .unwrap_or_default()
 }
 
+// Like iproute2 `hexstring_n2a()`
+pub(super) fn bytes_to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Interface details shown by `ip -details link show`
 #[derive(Serialize)]
-pub(crate) struct CliLinkInfoDetail {
+pub struct CliLinkInfoDetail {
     promiscuity: u32,
     allmulti: u32,
     min_mtu: u32,
@@ -43,7 +53,7 @@ pub(crate) struct CliLinkInfoDetail {
     )]
     netns_immutable: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    linkinfo: Option<CliLinkInfo>,
+    linkinfo: Option<CliLinkInfoKind>,
     #[serde(skip_serializing_if = "String::is_empty")]
     inet6_addr_gen_mode: String,
     num_tx_queues: u32,
@@ -56,13 +66,19 @@ pub(crate) struct CliLinkInfoDetail {
     gso_ipv4_max_size: u32,
     gro_ipv4_max_size: u32,
     #[serde(skip_serializing_if = "String::is_empty")]
+    phys_port_name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    phys_port_id: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    phys_switch_id: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
     parentbus: String,
     #[serde(skip_serializing_if = "String::is_empty")]
     parentdev: String,
 }
 
 impl CliLinkInfoDetail {
-    pub fn new(nl_attrs: &[LinkAttribute]) -> Self {
+    pub(crate) fn new(nl_attrs: &[LinkAttribute]) -> Self {
         let mut linkinfo = None;
         let mut promiscuity = 0;
         let mut allmulti = 0;
@@ -78,6 +94,9 @@ impl CliLinkInfoDetail {
         let mut gso_ipv4_max_size = 0;
         let mut gro_ipv4_max_size = 0;
         let mut inet6_addr_gen_mode = String::new();
+        let mut phys_port_name = String::new();
+        let mut phys_port_id = String::new();
+        let mut phys_switch_id = String::new();
         let mut parentbus = String::new();
         let mut parentdev = String::new();
         let mut netns_immutable = None;
@@ -101,6 +120,11 @@ impl CliLinkInfoDetail {
                 LinkAttribute::GsoIpv4MaxSize(g) => gso_ipv4_max_size = *g,
                 LinkAttribute::GroIpv4MaxSize(g) => gro_ipv4_max_size = *g,
                 LinkAttribute::NetnsImmutable(v) => netns_immutable = Some(*v),
+                LinkAttribute::PhysPortName(n) => phys_port_name = n.clone(),
+                LinkAttribute::PhysPortId(v) => phys_port_id = bytes_to_hex(v),
+                LinkAttribute::PhysSwitchId(v) => {
+                    phys_switch_id = bytes_to_hex(v)
+                }
                 LinkAttribute::ParentDevName(n) => parentdev = n.clone(),
                 LinkAttribute::ParentDevBusName(n) => parentbus = n.clone(),
                 LinkAttribute::LinkInfo(info) => {
@@ -129,14 +153,46 @@ impl CliLinkInfoDetail {
             gso_ipv4_max_size,
             gro_ipv4_max_size,
             netns_immutable,
+            phys_port_name,
+            phys_port_id,
+            phys_switch_id,
             parentbus,
             parentdev,
         }
     }
 
-    pub fn remove_inet6_addr_gen_mode(&mut self) {
+    /// Kind specific information of `IFLA_LINKINFO`, `None` for
+    /// interfaces without kind like loopback
+    pub fn get_linkinfo(&self) -> Option<&CliLinkInfoKind> {
+        self.linkinfo.as_ref()
+    }
+
+    pub(crate) fn remove_inet6_addr_gen_mode(&mut self) {
         self.inet6_addr_gen_mode = String::new();
     }
+
+    pub(crate) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        self.linkinfo
+            .as_ref()
+            .map(|l| l.get_referred_ifindexes())
+            .unwrap_or_default()
+    }
+
+    /// Resolve interface indexes referred by link info to names
+    pub(crate) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        if let Some(linkinfo) = self.linkinfo.as_mut() {
+            linkinfo.resolve_iface_names(index_2_name);
+        }
+    }
+
+    pub(crate) fn bridge_port_mut(
+        &mut self,
+    ) -> Option<&mut CliLinkInfoDataBridgePort> {
+        self.linkinfo.as_mut()?.bridge_port_mut()
+    }
 }
 
 impl std::fmt::Display for CliLinkInfoDetail {
@@ -175,6 +231,15 @@ impl std::fmt::Display for CliLinkInfoDetail {
             self.gro_ipv4_max_size,
         )?;
 
+        if !self.phys_port_name.is_empty() {
+            write!(f, "portname {} ", self.phys_port_name)?;
+        }
+        if !self.phys_port_id.is_empty() {
+            write!(f, "portid {} ", self.phys_port_id)?;
+        }
+        if !self.phys_switch_id.is_empty() {
+            write!(f, "switchid {} ", self.phys_switch_id)?;
+        }
         if !self.parentbus.is_empty() {
             write!(f, "parentbus {} ", self.parentbus)?;
         }
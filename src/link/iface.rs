@@ -0,0 +1,639 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::{HashMap, HashSet};
+
+use futures_util::{
+    future::join_all,
+    stream::{StreamExt, TryStreamExt},
+};
+use rtnetlink::{
+    packet_core::{
+        NLM_F_DUMP, NLM_F_DUMP_FILTERED, NLM_F_REQUEST, NetlinkHeader,
+        NetlinkMessage, NetlinkPayload,
+    },
+    packet_route::{
+        AddressFamily, RouteNetlinkMessage,
+        link::{
+            InfoKind, LinkAttribute, LinkExtentMask, LinkInfo, LinkMessage,
+            Prop,
+        },
+    },
+};
+use serde::Serialize;
+
+use super::{
+    detail::CliLinkInfoDetail, flags::link_flags_to_string,
+    ifaces::bridge::get_bridge_vlan_tunnels, stats::CliLinkStats,
+    vf::CliLinkVfInfo, xdp::CliLinkXdp,
+};
+use crate::{
+    CanDisplay, CanOutput, CliColor, CliError, CliNumberFormat, mac_to_string,
+    nl_error_to_cli, write_with_color,
+};
+
+/// Network interface information equal to iproute2 `ip link show`.
+///
+/// The JSON/YAML serialization is identical to the output of
+/// `ip -json link show` and the [std::fmt::Display] to the plain text
+/// output.
+#[derive(Serialize, Default)]
+pub struct CliLinkInfo {
+    ifindex: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) link_index: Option<u32>,
+    pub(super) ifname: String,
+    pub(super) flags: Vec<String>,
+    mtu: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xdp: Option<CliLinkXdp>,
+    qdisc: String,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "master")]
+    pub(super) controller: Option<String>,
+    #[serde(skip)]
+    pub(super) controller_ifindex: Option<u32>,
+    pub(super) operstate: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    linkmode: String,
+    group: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    txqlen: Option<u32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    link_type: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub(super) address: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    broadcast: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    permaddr: String,
+    #[serde(skip)]
+    link_netns: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_netnsid: Option<i32>,
+    #[serde(skip)]
+    pub(super) kind: Option<String>,
+    #[serde(skip)]
+    pub(super) port_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten)]
+    details: Option<CliLinkInfoDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats64: Option<CliLinkStats>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    vfinfo_list: Vec<CliLinkVfInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    altnames: Vec<String>,
+}
+
+impl CliLinkInfo {
+    /// Interface name with its link appended, e.g. `veth0@if12`
+    pub fn name_with_link(&self) -> String {
+        if self.link_index.is_some() || self.link.is_some() {
+            let display_name = if let Some(link_name) = &self.link {
+                link_name
+            } else if let Some(link_index) = self.link_index
+                && link_index != 0
+            {
+                &format!("if{link_index}")
+            } else {
+                "NONE"
+            };
+            format!("{}@{display_name}", self.ifname)
+        } else {
+            self.ifname.clone()
+        }
+    }
+
+    fn remove_link_mode(&mut self) {
+        self.linkmode = String::new();
+    }
+
+    fn remove_inet6_addr_gen_mode(&mut self) {
+        if let Some(d) = self.details.as_mut() {
+            d.remove_inet6_addr_gen_mode();
+        }
+    }
+
+    fn remove_link_layer(&mut self) {
+        self.link_type = String::new();
+        self.address = String::new();
+        self.broadcast = String::new();
+        self.permaddr = String::new();
+        self.link_netns = String::new();
+        self.link_netnsid = None;
+    }
+
+    /// For `ip address show`, remove the details that are not present in
+    /// iproute2 `ip address` output.
+    /// Like iproute2, the link layer line is hidden when a network family is
+    /// specified without `-details`.
+    pub fn show_only_addr_details(
+        &mut self,
+        family: AddressFamily,
+        include_details: bool,
+    ) {
+        self.remove_link_mode();
+        self.remove_inet6_addr_gen_mode();
+        // Like iproute2, VF information is only shown by `ip address` with
+        // `-details`
+        if !include_details {
+            self.vfinfo_list.clear();
+        }
+        if let Some(xdp) = self.xdp.as_mut() {
+            xdp.set_address_mode(include_details);
+        }
+        if !include_details
+            && !matches!(family, AddressFamily::Unspec | AddressFamily::Packet)
+        {
+            self.remove_link_layer();
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.ifindex)?;
+        write_with_color!(
+            f,
+            CliColor::IfaceName,
+            "{}: ",
+            self.name_with_link()
+        )?;
+        write!(f, "<{}> mtu {} ", self.flags.as_slice().join(","), self.mtu)?;
+        if let Some(xdp) = self.xdp.as_ref() {
+            write!(f, "{} ", xdp.mode_string())?;
+        }
+        write!(f, "qdisc {}", self.qdisc)?;
+        if let Some(ctrl) = self.controller.as_ref() {
+            write!(f, " master {ctrl}")?;
+        }
+        write!(f, " state ")?;
+        if self.operstate == "UP" {
+            write_with_color!(f, CliColor::StateUp, "{} ", self.operstate)?;
+        } else if self.operstate == "DOWN" {
+            write_with_color!(f, CliColor::StateDown, "{} ", self.operstate)?;
+        } else {
+            write!(f, "{} ", self.operstate)?;
+        }
+
+        if !self.linkmode.is_empty() {
+            write!(f, "mode {} ", self.linkmode)?;
+        }
+        write!(f, "group {} ", self.group)?;
+
+        if let Some(v) = self.txqlen {
+            write!(f, "qlen {v}")?;
+        }
+        if !self.link_type.is_empty() {
+            write!(f, "\n    ")?;
+            write!(f, "link/{} ", self.link_type)?;
+            if !self.address.is_empty() {
+                write_with_color!(f, CliColor::Mac, "{}", self.address)?;
+                write!(f, " brd ")?;
+                write_with_color!(f, CliColor::Mac, "{}", self.broadcast)?;
+            }
+            if !self.permaddr.is_empty() {
+                write!(f, " permaddr ")?;
+                write_with_color!(f, CliColor::Mac, "{}", self.permaddr)?;
+            }
+
+            if !self.link_netns.is_empty() {
+                write!(f, " link-netns {}", self.link_netns)?;
+            } else if let Some(netns_id) = self.link_netnsid {
+                write!(f, " link-netnsid {netns_id}")?;
+            }
+        }
+
+        if let Some(details) = &self.details {
+            write!(f, "{details}",)?;
+        }
+
+        if let Some(xdp) = &self.xdp {
+            write!(f, "{xdp}")?;
+        }
+
+        if let Some(stats) = &self.stats64 {
+            write!(f, "\n{stats}")?;
+        }
+
+        for vf in &self.vfinfo_list {
+            write!(f, "\n    {vf}")?;
+        }
+
+        for altname in &self.altnames {
+            write!(f, "\n    altname {altname}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CanDisplay for CliLinkInfo {
+    fn gen_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl CanOutput for CliLinkInfo {}
+
+/// Kernel side filter of [get_links], equal to iproute2
+/// `iplink_filter_req()`.
+///
+/// Kernels before 4.4 ignore the filters of link dump, [get_links] checks
+/// the interfaces not marked as filtered by kernel.
+#[derive(Debug, Clone, Default)]
+pub struct LinkDumpFilter {
+    /// Only query the interface of this name via `IFLA_IFNAME` instead of
+    /// dumping all
+    pub ifname: Option<String>,
+    /// Only query the interface with this index instead of dumping all
+    pub ifindex: Option<u32>,
+    /// Only dump the ports of this controller via `IFLA_MASTER`
+    pub controller_ifindex: Option<u32>,
+    /// Only dump the interfaces of this kind via `IFLA_INFO_KIND`
+    pub kind: Option<String>,
+}
+
+impl LinkDumpFilter {
+    fn matches(&self, iface: &CliLinkInfo) -> bool {
+        self.controller_ifindex
+            .is_none_or(|i| iface.controller_ifindex == Some(i))
+            && self
+                .kind
+                .as_deref()
+                .is_none_or(|k| iface.kind.as_deref() == Some(k))
+    }
+}
+
+/// Query network interfaces like iproute2 `ip link show`, with names of
+/// controller and link interfaces resolved.
+///
+/// The `include_details` and `stats_level` are equal to the `-details` and
+/// `-statistics` options of iproute2, while `numeric` is equal to
+/// `-numeric`. Querying interface of nonexistent name or index gives no
+/// interface.
+pub async fn get_links(
+    handle: &rtnetlink::Handle,
+    filter: &LinkDumpFilter,
+    include_details: bool,
+    stats_level: u8,
+    number_format: CliNumberFormat,
+    numeric: bool,
+) -> Result<Vec<CliLinkInfo>, CliError> {
+    let mut nl_msg = LinkMessage::default();
+    let mut flags = NLM_F_REQUEST;
+    if let Some(ifname) = filter.ifname.as_deref() {
+        // Kernel rejects `IFLA_IFNAME` in the strict link dump request,
+        // hence query the interface like iproute2 `iplink_get()`
+        nl_msg
+            .attributes
+            .push(LinkAttribute::IfName(ifname.to_string()));
+    } else if let Some(ifindex) = filter.ifindex {
+        nl_msg.header.index = ifindex;
+    } else {
+        flags |= NLM_F_DUMP;
+        if let Some(ifindex) = filter.controller_ifindex {
+            nl_msg.attributes.push(LinkAttribute::Controller(ifindex));
+        }
+        if let Some(kind) = filter.kind.as_deref() {
+            nl_msg.attributes.push(LinkAttribute::LinkInfo(vec![
+                LinkInfo::Kind(InfoKind::Other(kind.to_string())),
+            ]));
+        }
+    }
+    // Like iproute2, request VF information via `RTEXT_FILTER_VF`
+    nl_msg
+        .attributes
+        .push(LinkAttribute::ExtMask(vec![LinkExtentMask::Vf]));
+
+    let mut request = NetlinkMessage::new(
+        NetlinkHeader::default(),
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::GetLink(nl_msg)),
+    );
+    request.header.flags = flags;
+
+    let mut ifaces: Vec<CliLinkInfo> = Vec::new();
+    let mut response = handle.clone().request(request)?;
+    while let Some(msg) = response.next().await {
+        match msg.payload {
+            NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewLink(
+                nl_msg,
+            )) => {
+                let iface = parse_nl_msg_to_iface(
+                    nl_msg,
+                    include_details,
+                    stats_level,
+                    number_format,
+                    numeric,
+                )
+                .await?;
+                // Kernel marks the dump replies it filtered, otherwise
+                // filter here, e.g. the query of single interface
+                if msg.header.flags & NLM_F_DUMP_FILTERED != 0
+                    || filter.matches(&iface)
+                {
+                    ifaces.push(iface);
+                }
+            }
+            NetlinkPayload::Error(e)
+                if flags & NLM_F_DUMP == 0
+                    && e.raw_code() == -(nix::errno::Errno::ENODEV as i32) => {}
+            NetlinkPayload::Error(e) => {
+                if let Some(e) = nl_error_to_cli(&e) {
+                    return Err(e);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    resolve_referred_iface_names(handle, &mut ifaces).await;
+    if include_details {
+        resolve_bridge_vlan_tunnels(handle, &mut ifaces).await?;
+    }
+
+    Ok(ifaces)
+}
+
+impl CliLinkInfo {
+    /// Interface index
+    pub fn get_ifindex(&self) -> u32 {
+        self.ifindex
+    }
+
+    /// Interface name
+    pub fn get_ifname(&self) -> &str {
+        &self.ifname
+    }
+
+    /// Interface kind, e.g. `bridge`, `None` for interfaces without
+    /// `IFLA_INFO_KIND` like loopback
+    pub fn get_kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    /// Kind of the controller this interface is attached to, e.g. `bridge`
+    /// for bridge ports
+    pub fn get_port_kind(&self) -> Option<&str> {
+        self.port_kind.as_deref()
+    }
+
+    /// Name of the controller, only resolved when the controller is dumped
+    /// along with this interface
+    pub fn get_controller(&self) -> Option<&str> {
+        self.controller.as_deref()
+    }
+
+    /// Interface index of the controller
+    pub fn get_controller_ifindex(&self) -> Option<u32> {
+        self.controller_ifindex
+    }
+
+    /// Operational state, e.g. `UP`, `DOWN` and `UNKNOWN`
+    pub fn get_operstate(&self) -> &str {
+        &self.operstate
+    }
+
+    /// Hardware address, empty for interfaces without one
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+
+    /// Interface flags, e.g. `BROADCAST`, `UP` and `LOWER_UP`
+    pub fn get_flags(&self) -> &[String] {
+        self.flags.as_slice()
+    }
+
+    /// Network namespace ID of the link interface when it is in another
+    /// network namespace
+    pub fn get_link_netnsid(&self) -> Option<i32> {
+        self.link_netnsid
+    }
+
+    /// Set the network namespace name of the link interface, shown instead
+    /// of `link-netnsid` like iproute2 does for named network namespaces.
+    pub fn set_link_netns(&mut self, name: &str) {
+        self.link_netns = name.to_string();
+    }
+
+    /// Details included with `-details`
+    pub fn get_details(&self) -> Option<&CliLinkInfoDetail> {
+        self.details.as_ref()
+    }
+
+    /// Statistics included with `-statistics`
+    pub fn get_stats(&self) -> Option<&CliLinkStats> {
+        self.stats64.as_ref()
+    }
+
+    /// Interface indexes referred by this interface, e.g. controller and
+    /// link, which are shown as names.
+    fn get_referred_ifindexes(&self) -> Vec<u32> {
+        let mut ret: Vec<u32> = self
+            .details
+            .as_ref()
+            .map(|d| d.get_referred_ifindexes())
+            .unwrap_or_default();
+        ret.extend(self.controller_ifindex);
+        // The link in other netns cannot be resolved
+        if self.link_netnsid.is_none() {
+            ret.extend(self.link_index);
+        }
+        ret
+    }
+
+    /// Resolve the interface index of controller and link to name
+    pub fn resolve_iface_names(&mut self, index_2_name: &HashMap<u32, String>) {
+        if let Some(details) = self.details.as_mut() {
+            details.resolve_iface_names(index_2_name);
+        }
+        if let Some(ctrl_ifindex) = self.controller_ifindex
+            && let Some(name) = index_2_name.get(&ctrl_ifindex)
+        {
+            self.controller = Some(name.to_string());
+        }
+        // Only set link name if the link is from the current netns
+        if let Some(link_ifindex) = self.link_index
+            && link_ifindex != 0
+            && let Some(name) = index_2_name.get(&link_ifindex)
+            && self.link_netnsid.is_none()
+        {
+            self.link = Some(name.to_string());
+            // Clear link_index if we have a name
+            // We want to serialize one or the other
+            self.link_index = None;
+        }
+    }
+}
+
+/// Parse the `RTM_NEWLINK` message to [CliLinkInfo]. The names of
+/// controller and link interfaces are not resolved, use
+/// [CliLinkInfo::resolve_iface_names] for that.
+pub async fn parse_nl_msg_to_iface(
+    nl_msg: LinkMessage,
+    include_details: bool,
+    stats_level: u8,
+    number_format: CliNumberFormat,
+    numeric: bool,
+) -> Result<CliLinkInfo, CliError> {
+    let mut ret = CliLinkInfo {
+        ifindex: nl_msg.header.index,
+        flags: link_flags_to_string(nl_msg.header.flags),
+        link_type: if numeric {
+            format!("[{}]", u16::from(nl_msg.header.link_layer_type))
+        } else {
+            nl_msg.header.link_layer_type.to_string().to_lowercase()
+        },
+        ..Default::default()
+    };
+
+    ret.details =
+        include_details.then(|| CliLinkInfoDetail::new(&nl_msg.attributes));
+    if stats_level > 0 {
+        ret.stats64 =
+            CliLinkStats::new(&nl_msg.attributes, stats_level, number_format);
+    }
+    ret.xdp = CliLinkXdp::new(&nl_msg.attributes);
+    ret.vfinfo_list =
+        CliLinkVfInfo::new_list(&nl_msg.attributes, &ret.link_type);
+
+    let mut temp_permaddr = String::new();
+
+    for nl_attr in nl_msg.attributes {
+        match nl_attr {
+            LinkAttribute::IfName(name) => ret.ifname = name,
+            LinkAttribute::Mtu(mtu) => ret.mtu = mtu,
+            LinkAttribute::Address(mac) => ret.address = mac_to_string(&mac),
+            LinkAttribute::Broadcast(mac) => {
+                ret.broadcast = mac_to_string(&mac)
+            }
+            LinkAttribute::PermAddress(mac) => {
+                temp_permaddr = mac_to_string(&mac)
+            }
+            LinkAttribute::Qdisc(qdisc) => ret.qdisc = qdisc,
+            LinkAttribute::OperState(state) => {
+                // TODO: impl Display for State in rust-netlink
+                ret.operstate = format!("{state:?}").to_uppercase()
+            }
+            LinkAttribute::TxQueueLen(v) if v > 0 => ret.txqlen = Some(v),
+            LinkAttribute::Group(v) => {
+                ret.group = if numeric {
+                    v.to_string()
+                } else {
+                    resolve_ip_link_group_name(v)
+                }
+            }
+            LinkAttribute::Mode(v) => ret.linkmode = v.to_string(),
+            LinkAttribute::Controller(d) => ret.controller_ifindex = Some(d),
+            LinkAttribute::Link(i) => ret.link_index = Some(i),
+            LinkAttribute::LinkNetNsId(i) => ret.link_netnsid = Some(i),
+            LinkAttribute::LinkInfo(infos) => {
+                for info in infos {
+                    match info {
+                        LinkInfo::Kind(v) => ret.kind = Some(v.to_string()),
+                        LinkInfo::PortKind(v) => {
+                            ret.port_kind = Some(v.to_string())
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            LinkAttribute::PropList(props) => {
+                for prop in props {
+                    if let Prop::AltIfName(altname) = prop {
+                        ret.altnames.push(altname);
+                    }
+                }
+            }
+            _ => {
+                // println!("Remains {:?}", nl_attr);
+            }
+        }
+    }
+
+    // Only set permaddr if it differs from the current address
+    if !temp_permaddr.is_empty() && temp_permaddr != ret.address {
+        ret.permaddr = temp_permaddr;
+    }
+
+    Ok(ret)
+}
+
+/// Resolve interface group ID to name like iproute2 `rtnl_group_n2a()`
+pub fn resolve_ip_link_group_name(id: u32) -> String {
+    // TODO: Read `/usr/share/iproute2/group` and `/etc/iproute2/group`
+    match id {
+        0 => "default".into(),
+        _ => id.to_string(),
+    }
+}
+
+/// Kernel only provides the VLAN tunnel mappings of bridge ports in a
+/// separate `AF_BRIDGE` dump, hence only query it when any port has
+/// `vlan_tunnel` enabled.
+async fn resolve_bridge_vlan_tunnels(
+    handle: &rtnetlink::Handle,
+    links: &mut [CliLinkInfo],
+) -> Result<(), CliError> {
+    if !links.iter_mut().any(|l| {
+        l.details
+            .as_mut()
+            .and_then(|d| d.bridge_port_mut())
+            .is_some_and(|p| p.vlan_tunnel())
+    }) {
+        return Ok(());
+    }
+    let mut tunnels = get_bridge_vlan_tunnels(handle).await?;
+    for link in links.iter_mut() {
+        if let Some(port) =
+            link.details.as_mut().and_then(|d| d.bridge_port_mut())
+            && let Some(map) = tunnels.remove(&link.ifindex)
+        {
+            port.set_vlan_tunnel_map(map);
+        }
+    }
+    Ok(())
+}
+
+/// The interfaces referred by dumped ones might be filtered out by kernel,
+/// hence query their names like iproute2 `ll_index_to_name()`.
+async fn resolve_referred_iface_names(
+    handle: &rtnetlink::Handle,
+    links: &mut [CliLinkInfo],
+) {
+    let mut index_2_name: HashMap<u32, String> = links
+        .iter()
+        .map(|l| (l.ifindex, l.ifname.to_string()))
+        .collect();
+
+    let missing: HashSet<u32> = links
+        .iter()
+        .flat_map(|l| l.get_referred_ifindexes())
+        .filter(|i| *i != 0 && !index_2_name.contains_key(i))
+        .collect();
+    let names = join_all(missing.into_iter().map(|index| async move {
+        let nl_msg = handle
+            .link()
+            .get()
+            .match_index(index)
+            .execute()
+            .try_next()
+            .await
+            .ok()
+            .flatten()?;
+        nl_msg.attributes.into_iter().find_map(|attr| {
+            if let LinkAttribute::IfName(name) = attr {
+                Some((index, name))
+            } else {
+                None
+            }
+        })
+    }))
+    .await;
+    index_2_name.extend(names.into_iter().flatten());
+
+    for link in links.iter_mut() {
+        link.resolve_iface_names(&index_2_name);
+    }
+}
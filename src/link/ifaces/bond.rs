@@ -0,0 +1,467 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use rtnetlink::packet_route::link::{
+    BondAdInfo, BondAdSelect, BondAllPortActive, BondArpValidate, BondLacpRate,
+    BondPortState, InfoBond, InfoBondPort, MiiStatus,
+};
+use serde::Serialize;
+
+use crate::mac_to_string;
+
+/// Bond information equal to iproute2 `bond_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataBond {
+    mode: String,
+    #[serde(skip)]
+    active_slave_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active_slave: Option<String>,
+    miimon: u32,
+    updelay: u32,
+    downdelay: u32,
+    peer_notify_delay: u32,
+    use_carrier: u8,
+    arp_interval: u32,
+    arp_missed_max: u8,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    arp_ip_target: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ns_ip6_target: Vec<String>,
+    arp_validate: Option<String>,
+    arp_all_targets: String,
+    #[serde(skip)]
+    primary_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    primary: Option<String>,
+    primary_reselect: String,
+    fail_over_mac: String,
+    xmit_hash_policy: String,
+    resend_igmp: u32,
+    num_peer_notif: u8,
+    all_slaves_active: u8,
+    min_links: u32,
+    lp_interval: u32,
+    packets_per_slave: u32,
+    ad_lacp_active: String,
+    ad_lacp_rate: String,
+    coupled_control: bool,
+    broadcast_neighbor: bool,
+    ad_select: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_info: Option<CliLinkInfoDataBondAdInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_actor_sys_prio: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_user_port_key: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_actor_system: Option<String>,
+    tlb_dynamic_lb: u8,
+}
+
+#[derive(Serialize, Default)]
+struct CliLinkInfoDataBondAdInfo {
+    aggregator: u16,
+    num_ports: u16,
+    actor_key: u16,
+    partner_key: u16,
+    partner_mac: String,
+}
+
+impl From<&[BondAdInfo]> for CliLinkInfoDataBondAdInfo {
+    fn from(info: &[BondAdInfo]) -> Self {
+        let mut ret = Self::default();
+        for nla in info {
+            match nla {
+                BondAdInfo::Aggregator(v) => ret.aggregator = *v,
+                BondAdInfo::NumPorts(v) => ret.num_ports = *v,
+                BondAdInfo::ActorKey(v) => ret.actor_key = *v,
+                BondAdInfo::PartnerKey(v) => ret.partner_key = *v,
+                BondAdInfo::PartnerMac(v) => ret.partner_mac = mac_to_string(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoDataBondAdInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ad_aggregator {} ", self.aggregator)?;
+        write!(f, "ad_num_ports {} ", self.num_ports)?;
+        write!(f, "ad_actor_key {} ", self.actor_key)?;
+        write!(f, "ad_partner_key {} ", self.partner_key)?;
+        write!(f, "ad_partner_mac {} ", self.partner_mac)
+    }
+}
+
+impl From<&[InfoBond]> for CliLinkInfoDataBond {
+    fn from(info: &[InfoBond]) -> Self {
+        let mut mode = String::new();
+        let mut active_slave_index = 0;
+        let mut miimon = 0;
+        let mut updelay = 0;
+        let mut downdelay = 0;
+        let mut peer_notify_delay = 0;
+        let mut use_carrier = 0;
+        let mut arp_interval = 0;
+        let mut arp_missed_max = 0;
+        let mut arp_ip_target = Vec::new();
+        let mut ns_ip6_target = Vec::new();
+        let mut arp_validate = None;
+        let mut arp_all_targets = String::new();
+        let mut primary_index = 0;
+        let mut primary_reselect = String::new();
+        let mut fail_over_mac = String::new();
+        let mut xmit_hash_policy = String::new();
+        let mut resend_igmp = 0;
+        let mut num_peer_notif = 0;
+        let mut all_slaves_active = 0;
+        let mut min_links = 0;
+        let mut lp_interval = 0;
+        let mut packets_per_slave = 0;
+        let mut ad_lacp_active = String::new();
+        let mut ad_lacp_rate = String::new();
+        let mut coupled_control = false;
+        let mut broadcast_neighbor = false;
+        let mut ad_select = String::new();
+        let mut ad_info = None;
+        let mut ad_actor_sys_prio = None;
+        let mut ad_user_port_key = None;
+        let mut ad_actor_system = None;
+        let mut tlb_dynamic_lb = 0;
+
+        for nla in info {
+            use rtnetlink::packet_route::link::InfoBond;
+            match nla {
+                InfoBond::Mode(v) => mode = v.to_string(),
+                InfoBond::ActivePort(v) => active_slave_index = *v,
+                InfoBond::MiiMon(v) => miimon = *v,
+                InfoBond::UpDelay(v) => updelay = *v,
+                InfoBond::DownDelay(v) => downdelay = *v,
+                InfoBond::PeerNotifDelay(v) => peer_notify_delay = *v,
+                InfoBond::UseCarrier(v) => use_carrier = *v as u8,
+                InfoBond::ArpInterval(v) => arp_interval = *v,
+                InfoBond::MissedMax(v) => arp_missed_max = *v,
+                InfoBond::ArpIpTarget(v) => {
+                    arp_ip_target = v.iter().map(|a| a.to_string()).collect()
+                }
+                InfoBond::NsIp6Target(v) => {
+                    ns_ip6_target = v.iter().map(|a| a.to_string()).collect()
+                }
+                InfoBond::ArpValidate(v) => {
+                    if matches!(v, BondArpValidate::None) {
+                        arp_validate = None
+                    } else {
+                        arp_validate = Some(v.to_string())
+                    }
+                }
+                InfoBond::ArpAllTargets(v) => arp_all_targets = v.to_string(),
+                InfoBond::Primary(v) => primary_index = *v,
+                InfoBond::PrimaryReselect(v) => {
+                    primary_reselect = v.to_string()
+                }
+                InfoBond::FailOverMac(v) => fail_over_mac = v.to_string(),
+                InfoBond::XmitHashPolicy(v) => xmit_hash_policy = v.to_string(),
+                InfoBond::ResendIgmp(v) => resend_igmp = *v,
+                InfoBond::NumPeerNotif(v) => num_peer_notif = *v,
+                InfoBond::AllPortsActive(v) => {
+                    all_slaves_active = if *v == BondAllPortActive::Delivered {
+                        1
+                    } else {
+                        0
+                    };
+                }
+                InfoBond::MinLinks(v) => min_links = *v,
+                InfoBond::LpInterval(v) => lp_interval = *v,
+                InfoBond::PacketsPerPort(v) => packets_per_slave = *v,
+                InfoBond::AdLacpActive(v) => {
+                    ad_lacp_active = if *v { "on" } else { "off" }.to_string()
+                }
+                InfoBond::AdLacpRate(v) => {
+                    ad_lacp_rate = if matches!(*v, BondLacpRate::Fast) {
+                        "fast"
+                    } else {
+                        "slow"
+                    }
+                    .to_string()
+                }
+                InfoBond::AdSelect(v) => {
+                    ad_select = match *v {
+                        BondAdSelect::Stable => "stable",
+                        BondAdSelect::Bandwidth => "bandwidth",
+                        BondAdSelect::Count => "count",
+                        _ => "unknown",
+                    }
+                    .to_string()
+                }
+                InfoBond::AdInfo(v) => ad_info = Some(v.as_slice().into()),
+                InfoBond::AdActorSysPrio(v) => ad_actor_sys_prio = Some(*v),
+                InfoBond::AdUserPortKey(v) => ad_user_port_key = Some(*v),
+                InfoBond::AdActorSystem(v) => {
+                    ad_actor_system = Some(mac_to_string(v))
+                }
+                InfoBond::TlbDynamicLb(v) => tlb_dynamic_lb = *v as u8,
+                InfoBond::CoupledControl(v) => coupled_control = *v,
+                InfoBond::BroadcastNeigh(v) => broadcast_neighbor = *v,
+                _ => (), /* println!("Remains {:?}", nla) */
+            }
+        }
+
+        Self {
+            mode,
+            active_slave_index,
+            // Like iproute2 `ll_index_to_name()`, fallback to `if<index>`
+            // until resolved by `resolve_iface_names()`
+            active_slave: (active_slave_index != 0)
+                .then(|| format!("if{active_slave_index}")),
+            miimon,
+            updelay,
+            downdelay,
+            peer_notify_delay,
+            use_carrier,
+            arp_interval,
+            arp_missed_max,
+            arp_ip_target,
+            ns_ip6_target,
+            arp_validate,
+            arp_all_targets,
+            primary_index,
+            primary: (primary_index != 0).then(|| format!("if{primary_index}")),
+            primary_reselect,
+            fail_over_mac,
+            xmit_hash_policy,
+            resend_igmp,
+            num_peer_notif,
+            all_slaves_active,
+            min_links,
+            lp_interval,
+            packets_per_slave,
+            ad_lacp_active,
+            ad_lacp_rate,
+            ad_select,
+            ad_info,
+            ad_actor_sys_prio,
+            ad_user_port_key,
+            ad_actor_system,
+            tlb_dynamic_lb,
+            coupled_control,
+            broadcast_neighbor,
+        }
+    }
+}
+
+impl CliLinkInfoDataBond {
+    pub(crate) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        vec![self.active_slave_index, self.primary_index]
+    }
+
+    pub(crate) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        if let Some(name) = index_2_name.get(&self.active_slave_index) {
+            self.active_slave = Some(name.to_string());
+        }
+        if let Some(name) = index_2_name.get(&self.primary_index) {
+            self.primary = Some(name.to_string());
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoDataBond {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let on_off = |val: bool| if val { "on" } else { "off" };
+
+        let arp_validate =
+            self.arp_validate.as_ref().map_or("none", |s| s.as_str());
+
+        write!(f, "mode {} ", self.mode)?;
+        if let Some(active_slave) = &self.active_slave {
+            write!(f, "active_slave {active_slave} ")?;
+        }
+        write!(f, "miimon {} ", self.miimon)?;
+        write!(f, "updelay {} ", self.updelay)?;
+        write!(f, "downdelay {} ", self.downdelay)?;
+        write!(f, "peer_notify_delay {} ", self.peer_notify_delay)?;
+        write!(f, "use_carrier {} ", self.use_carrier)?;
+        write!(f, "arp_interval {} ", self.arp_interval)?;
+        write!(f, "arp_missed_max {} ", self.arp_missed_max)?;
+        if !self.arp_ip_target.is_empty() {
+            write!(f, "arp_ip_target {} ", self.arp_ip_target.join(","))?;
+        }
+        if !self.ns_ip6_target.is_empty() {
+            write!(f, "ns_ip6_target {} ", self.ns_ip6_target.join(","))?;
+        }
+        write!(f, "arp_validate {} ", arp_validate)?;
+        write!(f, "arp_all_targets {} ", self.arp_all_targets)?;
+        if let Some(primary) = &self.primary {
+            write!(f, "primary {primary} ")?;
+        }
+        write!(f, "primary_reselect {} ", self.primary_reselect)?;
+        write!(f, "fail_over_mac {} ", self.fail_over_mac)?;
+        write!(f, "xmit_hash_policy {} ", self.xmit_hash_policy)?;
+        write!(f, "resend_igmp {} ", self.resend_igmp)?;
+        write!(f, "num_grat_arp {} ", self.num_peer_notif)?;
+        write!(f, "all_slaves_active {} ", self.all_slaves_active)?;
+        write!(f, "min_links {} ", self.min_links)?;
+        write!(f, "lp_interval {} ", self.lp_interval)?;
+        write!(f, "packets_per_slave {} ", self.packets_per_slave)?;
+        write!(f, "lacp_active {} ", self.ad_lacp_active)?;
+        write!(f, "lacp_rate {} ", self.ad_lacp_rate)?;
+        write!(f, "coupled_control {} ", on_off(self.coupled_control))?;
+        write!(f, "broadcast_neighbor {} ", on_off(self.broadcast_neighbor))?;
+        write!(f, "ad_select {} ", self.ad_select)?;
+        if let Some(ad_info) = &self.ad_info {
+            write!(f, "{ad_info}")?;
+        }
+        if let Some(v) = self.ad_actor_sys_prio {
+            write!(f, "ad_actor_sys_prio {v} ")?;
+        }
+        if let Some(v) = self.ad_user_port_key {
+            write!(f, "ad_user_port_key {v} ")?;
+        }
+        if let Some(v) = &self.ad_actor_system {
+            write!(f, "ad_actor_system {v} ")?;
+        }
+        write!(f, "tlb_dynamic_lb {}", self.tlb_dynamic_lb)?;
+
+        Ok(())
+    }
+}
+
+/// Bond port information equal to iproute2 `bond_slave_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataBondPort {
+    state: String,
+    mii_status: String,
+    link_failure_count: u32,
+    perm_hwaddr: String,
+    queue_id: u16,
+    prio: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_aggregator_id: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_actor_oper_port_state: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_actor_oper_port_state_str: Option<Vec<&'static str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_partner_oper_port_state: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ad_partner_oper_port_state_str: Option<Vec<&'static str>>,
+}
+
+// Equal to the LACP port state bits of iproute2 `print_slave_oper_state()`
+const LACP_PORT_STATES: [&str; 8] = [
+    "active",
+    "short_timeout",
+    "aggregating",
+    "in_sync",
+    "collecting",
+    "distributing",
+    "defaulted",
+    "expired",
+];
+
+fn lacp_port_state_to_strs(state: u16) -> Vec<&'static str> {
+    LACP_PORT_STATES
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| state & (1 << i) != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+impl From<&[InfoBondPort]> for CliLinkInfoDataBondPort {
+    fn from(info: &[InfoBondPort]) -> Self {
+        let mut state = String::new();
+        let mut mii_status = String::new();
+        let mut link_failure_count = 0;
+        let mut perm_hwaddr = String::new();
+        let mut queue_id = 0;
+        let mut prio = 0;
+        let mut ad_aggregator_id = None;
+        let mut ad_actor_oper_port_state = None;
+        let mut ad_partner_oper_port_state = None;
+
+        for nla in info {
+            match nla {
+                InfoBondPort::BondPortState(v) => {
+                    state = match v {
+                        BondPortState::Active => "ACTIVE".to_string(),
+                        BondPortState::Backup => "BACKUP".to_string(),
+                        BondPortState::Other(n) => format!("{}", n),
+                        _ => "unknown".to_string(),
+                    };
+                }
+                InfoBondPort::LinkFailureCount(l) => link_failure_count = *l,
+                InfoBondPort::MiiStatus(s) => {
+                    mii_status = match s {
+                        MiiStatus::Up => "UP".to_string(),
+                        MiiStatus::Down => "DOWN".to_string(),
+                        MiiStatus::Other(n) => format!("{}", n),
+                        _ => "unknown".to_string(),
+                    };
+                }
+                InfoBondPort::PermHwaddr(hwa) => {
+                    perm_hwaddr = mac_to_string(hwa)
+                }
+                InfoBondPort::Prio(p) => prio = *p,
+                InfoBondPort::QueueId(q) => queue_id = *q,
+                InfoBondPort::AdAggregatorId(v) => ad_aggregator_id = Some(*v),
+                InfoBondPort::AdActorOperPortState(v) => {
+                    ad_actor_oper_port_state = Some(*v)
+                }
+                InfoBondPort::AdPartnerOperPortState(v) => {
+                    ad_partner_oper_port_state = Some(*v)
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            state,
+            mii_status,
+            link_failure_count,
+            perm_hwaddr,
+            queue_id,
+            prio,
+            ad_aggregator_id,
+            ad_actor_oper_port_state,
+            ad_actor_oper_port_state_str: ad_actor_oper_port_state
+                .map(|s| lacp_port_state_to_strs(s.into())),
+            ad_partner_oper_port_state,
+            ad_partner_oper_port_state_str: ad_partner_oper_port_state
+                .map(lacp_port_state_to_strs),
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoDataBondPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "state {} ", self.state)?;
+        write!(f, "mii_status {} ", self.mii_status)?;
+        write!(f, "link_failure_count {} ", self.link_failure_count)?;
+        write!(f, "perm_hwaddr {} ", self.perm_hwaddr)?;
+        write!(f, "queue_id {} ", self.queue_id)?;
+        write!(f, "prio {}", self.prio)?;
+        if let Some(v) = self.ad_aggregator_id {
+            write!(f, " ad_aggregator_id {v}")?;
+        }
+        if let Some(v) = self.ad_actor_oper_port_state {
+            write!(f, " ad_actor_oper_port_state {v}")?;
+        }
+        if let Some(v) = &self.ad_actor_oper_port_state_str {
+            write!(f, " ad_actor_oper_port_state_str <{}>", v.join(","))?;
+        }
+        if let Some(v) = self.ad_partner_oper_port_state {
+            write!(f, " ad_partner_oper_port_state {v}")?;
+        }
+        if let Some(v) = &self.ad_partner_oper_port_state_str {
+            write!(f, " ad_partner_oper_port_state_str <{}>", v.join(","))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use rtnetlink::packet_route::link::{
+    GreEncapFlags, GreEncapType, GreIOFlags, InfoGreTap, InfoGreTap6,
+    InfoGreTun, InfoGreTun6,
+};
+use serde::Serialize;
+
+// The `IP6_TNL_F_*` flags of `IFLA_GRE_FLAGS` for ip6gre and ip6gretap
+const IP6_TNL_F_IGN_ENCAP_LIMIT: u32 = 0x1;
+const IP6_TNL_F_USE_ORIG_TCLASS: u32 = 0x2;
+const IP6_TNL_F_USE_ORIG_FLOWLABEL: u32 = 0x4;
+const IP6_TNL_F_RCV_DSCP_COPY: u32 = 0x10;
+const IP6_TNL_F_USE_ORIG_FWMARK: u32 = 0x20;
+const IP6_TNL_F_ALLOW_LOCAL_REMOTE: u32 = 0x40;
+
+/// GRE tunnel information equal to iproute2 `gre_print_opt()`
+///
+/// Shared by gre, gretap, ip6gre and ip6gretap like iproute2
+#[derive(Serialize, Default)]
+pub struct CliLinkInfoDataGre {
+    #[serde(skip)]
+    ipv6: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local: Option<String>,
+    #[serde(skip)]
+    link_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u8>,
+    #[serde(skip)]
+    tos_value: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tos: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pmtudisc: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ignore_df: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_ign_encap_limit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encap_limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_use_orig_tclass: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tclass: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_use_orig_flowlabel: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flowlabel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_rcv_dscp_copy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ikey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    okey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iseq: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oseq: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icsum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ocsum: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_allow_local_remote: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip6_tnl_f_use_orig_fwmark: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fwmark: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encap: Option<CliLinkInfoDataGreEncap>,
+}
+
+#[derive(Serialize, Default)]
+struct CliLinkInfoDataGreEncap {
+    #[serde(rename = "type")]
+    encap_type: Option<String>,
+    sport: u16,
+    dport: u16,
+    csum: bool,
+    csum6: bool,
+    remcsum: bool,
+}
+
+// Equal to iproute2 `tnl_print_encap()`
+impl std::fmt::Display for CliLinkInfoDataGreEncap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let no = |val: bool| if val { "" } else { "no" };
+
+        write!(
+            f,
+            "encap {} ",
+            self.encap_type.as_deref().unwrap_or("unknown")
+        )?;
+        if self.sport == 0 {
+            write!(f, "encap-sport auto ")?;
+        } else {
+            write!(f, "encap-sport {} ", self.sport)?;
+        }
+        write!(f, "encap-dport {} ", self.dport)?;
+        write!(f, "{}encap-csum ", no(self.csum))?;
+        write!(f, "{}encap-csum6 ", no(self.csum6))?;
+        write!(f, "{}encap-remcsum", no(self.remcsum))
+    }
+}
+
+/// Collect the GRE netlink attributes, the `InfoGreTun`, `InfoGreTap`,
+/// `InfoGreTun6` and `InfoGreTap6` are identical but different types.
+macro_rules! impl_from_gre_nlas {
+    ($ipv6:literal; $($nla_type:ident),*) => {$(
+        impl From<&[$nla_type]> for CliLinkInfoDataGre {
+            fn from(info: &[$nla_type]) -> Self {
+                let mut ret = GreNlas {
+                    ipv6: $ipv6,
+                    ..Default::default()
+                };
+                for nla in info {
+                    match nla {
+                        $nla_type::CollectMetadata => ret.external = true,
+                        $nla_type::Remote(v) => ret.remote = Some(*v),
+                        $nla_type::Local(v) => ret.local = Some(*v),
+                        $nla_type::Link(v) => ret.link = *v,
+                        $nla_type::Ttl(v) => ret.ttl = *v,
+                        $nla_type::Tos(v) => ret.tos = *v,
+                        $nla_type::PathMTUDiscovery(v) => {
+                            ret.pmtudisc = Some(*v)
+                        }
+                        $nla_type::IgnoreDontFragment(v) => {
+                            ret.ignore_df = *v
+                        }
+                        $nla_type::IFlags(v) => ret.iflags = *v,
+                        $nla_type::OFlags(v) => ret.oflags = *v,
+                        $nla_type::IKey(v) => ret.ikey = Some(*v),
+                        $nla_type::OKey(v) => ret.okey = Some(*v),
+                        $nla_type::FwMask(v) => ret.fwmark = *v,
+                        $nla_type::Flags(v) => ret.flags = *v,
+                        $nla_type::EncapLimit(v) => {
+                            ret.encap_limit = Some(*v)
+                        }
+                        $nla_type::FlowLabel(v) => ret.flowinfo = Some(*v),
+                        $nla_type::EncapType(v) => {
+                            ret.encap_type = Some(*v)
+                        }
+                        $nla_type::EncapFlags(v) => ret.encap_flags = *v,
+                        $nla_type::SourcePort(v) => ret.encap_sport = *v,
+                        $nla_type::DestinationPort(v) => {
+                            ret.encap_dport = *v
+                        }
+                        _ => (),
+                    }
+                }
+                ret.into()
+            }
+        }
+    )*};
+}
+
+impl_from_gre_nlas!(false; InfoGreTun, InfoGreTap);
+impl_from_gre_nlas!(true; InfoGreTun6, InfoGreTap6);
+
+#[derive(Default)]
+struct GreNlas {
+    ipv6: bool,
+    external: bool,
+    remote: Option<IpAddr>,
+    local: Option<IpAddr>,
+    link: u32,
+    ttl: u8,
+    tos: u8,
+    pmtudisc: Option<bool>,
+    ignore_df: bool,
+    iflags: GreIOFlags,
+    oflags: GreIOFlags,
+    ikey: Option<u32>,
+    okey: Option<u32>,
+    fwmark: u32,
+    flags: u32,
+    encap_limit: Option<u8>,
+    flowinfo: Option<u32>,
+    encap_type: Option<GreEncapType>,
+    encap_flags: GreEncapFlags,
+    encap_sport: u16,
+    encap_dport: u16,
+}
+
+impl From<GreNlas> for CliLinkInfoDataGre {
+    // Equal to iproute2 `gre_print_opt()` of link_gre.c and link_gre6.c
+    fn from(nlas: GreNlas) -> Self {
+        let encap = match nlas.encap_type {
+            None | Some(GreEncapType::None) => None,
+            Some(encap_type) => Some(CliLinkInfoDataGreEncap {
+                encap_type: match encap_type {
+                    GreEncapType::Fou => Some("fou".to_string()),
+                    GreEncapType::Gue => Some("gue".to_string()),
+                    _ => None,
+                },
+                sport: nlas.encap_sport,
+                dport: nlas.encap_dport,
+                csum: nlas.encap_flags.contains(GreEncapFlags::Checksum),
+                csum6: nlas.encap_flags.contains(GreEncapFlags::Checksum6),
+                remcsum: nlas
+                    .encap_flags
+                    .contains(GreEncapFlags::RemoteChecksum),
+            }),
+        };
+
+        if nlas.external {
+            return Self {
+                ipv6: nlas.ipv6,
+                external: Some(true),
+                encap,
+                ..Default::default()
+            };
+        }
+
+        // Like iproute2 `inet_ntop()` on the raw key
+        let key_to_string = |flags: GreIOFlags, key: Option<u32>| {
+            key.filter(|_| flags.contains(GreIOFlags::Key))
+                .map(|k| Ipv4Addr::from(k).to_string())
+        };
+        let flag_set = |flags: GreIOFlags, flag: GreIOFlags| {
+            flags.contains(flag).then_some(true)
+        };
+        let ip6_tnl_flag_set =
+            |flag: u32| (nlas.ipv6 && nlas.flags & flag > 0).then_some(true);
+
+        Self {
+            ipv6: nlas.ipv6,
+            external: None,
+            remote: Some(endpoint_to_string(nlas.remote)),
+            local: Some(endpoint_to_string(nlas.local)),
+            link_index: nlas.link,
+            // Like iproute2 `ll_index_to_name()`, fallback to `if<index>`
+            // until resolved by `resolve_iface_names()`
+            link: (nlas.link != 0).then(|| format!("if{}", nlas.link)),
+            ttl: Some(nlas.ttl),
+            tos_value: nlas.tos,
+            tos: (nlas.tos != 0).then(|| format!("{:#x}", nlas.tos)),
+            pmtudisc: nlas.pmtudisc,
+            ignore_df: nlas.ignore_df.then_some(true),
+            ip6_tnl_f_ign_encap_limit: ip6_tnl_flag_set(
+                IP6_TNL_F_IGN_ENCAP_LIMIT,
+            ),
+            encap_limit: nlas
+                .encap_limit
+                .filter(|_| nlas.flags & IP6_TNL_F_IGN_ENCAP_LIMIT == 0),
+            ip6_tnl_f_use_orig_tclass: ip6_tnl_flag_set(
+                IP6_TNL_F_USE_ORIG_TCLASS,
+            ),
+            tclass: nlas
+                .flowinfo
+                .filter(|_| nlas.flags & IP6_TNL_F_USE_ORIG_TCLASS == 0)
+                .map(|v| format!("0x{:02x}", (v >> 20) as u8)),
+            ip6_tnl_f_use_orig_flowlabel: ip6_tnl_flag_set(
+                IP6_TNL_F_USE_ORIG_FLOWLABEL,
+            ),
+            flowlabel: nlas
+                .flowinfo
+                .filter(|_| nlas.flags & IP6_TNL_F_USE_ORIG_FLOWLABEL == 0)
+                .map(|v| format!("0x{:05x}", v & 0xfffff)),
+            ip6_tnl_f_rcv_dscp_copy: ip6_tnl_flag_set(IP6_TNL_F_RCV_DSCP_COPY),
+            ikey: key_to_string(nlas.iflags, nlas.ikey),
+            okey: key_to_string(nlas.oflags, nlas.okey),
+            iseq: flag_set(nlas.iflags, GreIOFlags::Sequence),
+            oseq: flag_set(nlas.oflags, GreIOFlags::Sequence),
+            icsum: flag_set(nlas.iflags, GreIOFlags::Checksum),
+            ocsum: flag_set(nlas.oflags, GreIOFlags::Checksum),
+            ip6_tnl_f_allow_local_remote: ip6_tnl_flag_set(
+                IP6_TNL_F_ALLOW_LOCAL_REMOTE,
+            ),
+            ip6_tnl_f_use_orig_fwmark: ip6_tnl_flag_set(
+                IP6_TNL_F_USE_ORIG_FWMARK,
+            ),
+            fwmark: (nlas.fwmark != 0
+                && nlas.flags & IP6_TNL_F_USE_ORIG_FWMARK == 0)
+                .then(|| format!("{:#x}", nlas.fwmark)),
+            encap,
+        }
+    }
+}
+
+// Equal to iproute2 `tnl_print_endpoint()`
+pub(crate) fn endpoint_to_string(addr: Option<IpAddr>) -> String {
+    match addr {
+        Some(addr) if !addr.is_unspecified() => addr.to_string(),
+        _ => "any".to_string(),
+    }
+}
+
+impl CliLinkInfoDataGre {
+    pub(crate) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        vec![self.link_index]
+    }
+
+    pub(crate) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        if let Some(name) = index_2_name.get(&self.link_index) {
+            self.link = Some(name.to_string());
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoDataGre {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut items = Vec::new();
+        if self.external == Some(true) {
+            items.push("external".to_string());
+        }
+        if let Some(v) = &self.remote {
+            items.push(format!("remote {v}"));
+        }
+        if let Some(v) = &self.local {
+            items.push(format!("local {v}"));
+        }
+        if let Some(v) = &self.link {
+            items.push(format!("dev {v}"));
+        }
+        if let Some(ttl) = self.ttl {
+            let name = if self.ipv6 { "hoplimit" } else { "ttl" };
+            if ttl == 0 {
+                items.push(format!("{name} inherit"));
+            } else {
+                items.push(format!("{name} {ttl}"));
+            }
+        }
+        if let Some(tos) = &self.tos {
+            if self.tos_value == 1 {
+                items.push("tos inherit".to_string());
+            } else {
+                items.push(format!("tos {tos}"));
+            }
+        }
+        if self.pmtudisc == Some(false) {
+            items.push("nopmtudisc".to_string());
+        }
+        if self.ignore_df == Some(true) {
+            items.push("ignore-df".to_string());
+        }
+        if self.ip6_tnl_f_ign_encap_limit == Some(true) {
+            items.push("encaplimit none".to_string());
+        } else if let Some(v) = self.encap_limit {
+            items.push(format!("encaplimit {v}"));
+        }
+        if self.ip6_tnl_f_use_orig_tclass == Some(true) {
+            items.push("tclass inherit".to_string());
+        } else if let Some(v) = &self.tclass {
+            items.push(format!("tclass {v}"));
+        }
+        if self.ip6_tnl_f_use_orig_flowlabel == Some(true) {
+            items.push("flowlabel inherit".to_string());
+        } else if let Some(v) = &self.flowlabel {
+            items.push(format!("flowlabel {v}"));
+        }
+        if self.ip6_tnl_f_rcv_dscp_copy == Some(true) {
+            items.push("dscp inherit".to_string());
+        }
+        if let Some(v) = &self.ikey {
+            items.push(format!("ikey {v}"));
+        }
+        if let Some(v) = &self.okey {
+            items.push(format!("okey {v}"));
+        }
+        if self.iseq.is_some() {
+            items.push("iseq".to_string());
+        }
+        if self.oseq.is_some() {
+            items.push("oseq".to_string());
+        }
+        if self.icsum.is_some() {
+            items.push("icsum".to_string());
+        }
+        if self.ocsum.is_some() {
+            items.push("ocsum".to_string());
+        }
+        if self.ip6_tnl_f_allow_local_remote == Some(true) {
+            items.push("allow-localremote".to_string());
+        }
+        if self.ip6_tnl_f_use_orig_fwmark == Some(true) {
+            items.push("fwmark inherit".to_string());
+        } else if let Some(v) = &self.fwmark {
+            items.push(format!("fwmark {v}"));
+        }
+        if let Some(v) = &self.encap {
+            items.push(v.to_string());
+        }
+        write!(f, "{}", items.join(" "))
+    }
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use rtnetlink::packet_route::link::InfoHsr;
+use serde::Serialize;
+
+use crate::mac_to_string;
+
+/// HSR information equal to iproute2 `hsr_print_opt()`
+///
+/// Shared by HSR and PRP like iproute2
+#[derive(Serialize)]
+pub struct CliLinkInfoDataHsr {
+    #[serde(skip)]
+    slave1_index: u32,
+    slave1: Option<String>,
+    #[serde(skip)]
+    slave2_index: u32,
+    slave2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq_nr: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    supervision_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proto: Option<u8>,
+}
+
+impl From<&[InfoHsr]> for CliLinkInfoDataHsr {
+    fn from(info: &[InfoHsr]) -> Self {
+        let mut slave1_index = 0;
+        let mut slave2_index = 0;
+        let mut seq_nr = None;
+        let mut supervision_addr = None;
+        let mut proto = None;
+
+        for nla in info {
+            match nla {
+                InfoHsr::Port1(v) => slave1_index = *v,
+                InfoHsr::Port2(v) => slave2_index = *v,
+                InfoHsr::SeqNr(v) => seq_nr = Some(*v),
+                InfoHsr::SupervisionAddr(v) => {
+                    supervision_addr = Some(mac_to_string(v))
+                }
+                InfoHsr::Protocol(v) => proto = Some(u8::from(*v)),
+                _ => (),
+            }
+        }
+
+        // Like iproute2 `ll_index_to_name()`, fallback to `if<index>`
+        // until resolved by `resolve_iface_names()`
+        let index_to_name =
+            |index: u32| (index != 0).then(|| format!("if{index}"));
+
+        Self {
+            slave1_index,
+            slave1: index_to_name(slave1_index),
+            slave2_index,
+            slave2: index_to_name(slave2_index),
+            seq_nr,
+            supervision_addr,
+            proto,
+        }
+    }
+}
+
+impl CliLinkInfoDataHsr {
+    pub(crate) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        vec![self.slave1_index, self.slave2_index]
+    }
+
+    pub(crate) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        if let Some(name) = index_2_name.get(&self.slave1_index) {
+            self.slave1 = Some(name.to_string());
+        }
+        if let Some(name) = index_2_name.get(&self.slave2_index) {
+            self.slave2 = Some(name.to_string());
+        }
+    }
+}
+
+// Equal to iproute2 `hsr_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataHsr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "slave1 {} ", self.slave1.as_deref().unwrap_or("<none>"))?;
+        write!(f, "slave2 {}", self.slave2.as_deref().unwrap_or("<none>"))?;
+        if let Some(v) = self.seq_nr {
+            write!(f, " sequence {v}")?;
+        }
+        if let Some(v) = &self.supervision_addr {
+            write!(f, " supervision {v}")?;
+        }
+        if let Some(v) = self.proto {
+            write!(f, " proto {v}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{
+    InfoIpVlan, InfoIpVtap, IpVlanFlags, IpVlanMode,
+};
+use serde::Serialize;
+
+/// IPVLAN information equal to iproute2 `ipvlan_print_opt()`
+///
+/// Shared by ipvlan and ipvtap like iproute2
+#[derive(Serialize, Default)]
+pub struct CliLinkInfoDataIpVlan {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vepa: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bridge: Option<bool>,
+}
+
+impl CliLinkInfoDataIpVlan {
+    fn set_mode(&mut self, mode: &IpVlanMode) {
+        self.mode = Some(
+            match mode {
+                IpVlanMode::L2 => "l2",
+                IpVlanMode::L3 => "l3",
+                IpVlanMode::L3S => "l3s",
+                _ => "unknown",
+            }
+            .to_string(),
+        );
+    }
+
+    // Only one of the flags is shown, like iproute2
+    fn set_flags(&mut self, flags: &IpVlanFlags) {
+        if flags.contains(IpVlanFlags::Private) {
+            self.private = Some(true);
+        } else if flags.contains(IpVlanFlags::Vepa) {
+            self.vepa = Some(true);
+        } else {
+            self.bridge = Some(true);
+        }
+    }
+}
+
+impl From<&[InfoIpVlan]> for CliLinkInfoDataIpVlan {
+    fn from(info: &[InfoIpVlan]) -> Self {
+        let mut ret = Self::default();
+        for nla in info {
+            match nla {
+                InfoIpVlan::Mode(v) => ret.set_mode(v),
+                InfoIpVlan::Flags(v) => ret.set_flags(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+impl From<&[InfoIpVtap]> for CliLinkInfoDataIpVlan {
+    fn from(info: &[InfoIpVtap]) -> Self {
+        let mut ret = Self::default();
+        for nla in info {
+            match nla {
+                InfoIpVtap::Mode(v) => ret.set_mode(v),
+                InfoIpVtap::Flags(v) => ret.set_flags(v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// Equal to iproute2 `ipvlan_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataIpVlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut items = Vec::new();
+        if let Some(mode) = &self.mode {
+            // iproute2 prints a leading space before mode
+            items.push(format!(" mode {mode}"));
+        }
+        if self.private == Some(true) {
+            items.push("private".to_string());
+        } else if self.vepa == Some(true) {
+            items.push("vepa".to_string());
+        } else if self.bridge == Some(true) {
+            items.push("bridge".to_string());
+        }
+        write!(f, "{}", items.join(" "))
+    }
+}
@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{
+    InfoMacSec, MacSecCipherId, MacSecOffload, MacSecValidate,
+};
+use serde::Serialize;
+
+/// MACsec information equal to iproute2 `macsec_print_opt()`
+#[derive(Serialize, Default)]
+pub struct CliLinkInfoDataMacSec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sci: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protect: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cipher_suite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icv_len: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding_sa: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offload: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encrypt: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inc_sci: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    es: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scb: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replay_protect: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    window: Option<u32>,
+}
+
+impl From<&[InfoMacSec]> for CliLinkInfoDataMacSec {
+    fn from(info: &[InfoMacSec]) -> Self {
+        let mut ret = Self::default();
+
+        for nla in info {
+            match nla {
+                // iproute2 prints the SCI in network byte order
+                InfoMacSec::Sci(v) => {
+                    ret.sci = Some(format!("{:016x}", u64::from_be(*v)))
+                }
+                InfoMacSec::Protect(v) => ret.protect = Some(*v != 0),
+                InfoMacSec::CipherSuite(v) => {
+                    ret.cipher_suite = Some(
+                        match v {
+                            MacSecCipherId::GcmAes128 => "GCM-AES-128",
+                            MacSecCipherId::GcmAes256 => "GCM-AES-256",
+                            MacSecCipherId::GcmAesXpn128 => "GCM-AES-XPN-128",
+                            MacSecCipherId::GcmAesXpn256 => "GCM-AES-XPN-256",
+                            _ => "(unknown)",
+                        }
+                        .to_string(),
+                    )
+                }
+                InfoMacSec::IcvLen(v) => ret.icv_len = Some(*v),
+                InfoMacSec::EncodingSa(v) => ret.encoding_sa = Some(*v),
+                InfoMacSec::Validation(v) => {
+                    ret.validation = Some(
+                        match v {
+                            MacSecValidate::Disabled => "disabled",
+                            MacSecValidate::Check => "check",
+                            MacSecValidate::Strict => "strict",
+                            _ => "(unknown)",
+                        }
+                        .to_string(),
+                    )
+                }
+                InfoMacSec::Offload(v) => {
+                    ret.offload = Some(
+                        match v {
+                            MacSecOffload::Off => "off",
+                            MacSecOffload::Phy => "phy",
+                            MacSecOffload::Mac => "mac",
+                            _ => "(unknown)",
+                        }
+                        .to_string(),
+                    )
+                }
+                InfoMacSec::Encrypt(v) => ret.encrypt = Some(*v != 0),
+                InfoMacSec::IncSci(v) => ret.inc_sci = Some(*v != 0),
+                InfoMacSec::Es(v) => ret.es = Some(*v != 0),
+                InfoMacSec::Scb(v) => ret.scb = Some(*v != 0),
+                InfoMacSec::ReplayProtect(v) => {
+                    ret.replay_protect = Some(*v != 0)
+                }
+                InfoMacSec::Window(v) => ret.window = Some(*v),
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// Equal to iproute2 `macsec_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataMacSec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let on_off = |val: bool| if val { "on" } else { "off" };
+
+        let mut items = Vec::new();
+        if let Some(v) = &self.sci {
+            items.push(format!("sci {v}"));
+        }
+        if let Some(v) = self.protect {
+            items.push(format!("protect {}", on_off(v)));
+        }
+        if let Some(v) = &self.cipher_suite {
+            items.push(format!("cipher {v}"));
+        }
+        if let Some(v) = self.icv_len {
+            items.push(format!("icvlen {v}"));
+        }
+        if let Some(v) = self.encoding_sa {
+            items.push(format!("encodingsa {v}"));
+        }
+        if let Some(v) = &self.validation {
+            items.push(format!("validate {v}"));
+        }
+        if let Some(v) = &self.offload {
+            items.push(format!("offload {v}"));
+        }
+        // The plain text names of some flags differ from the JSON keys
+        for (name, flag) in [
+            ("encrypt", self.encrypt),
+            ("send_sci", self.inc_sci),
+            ("end_station", self.es),
+            ("scb", self.scb),
+            ("replay", self.replay_protect),
+        ] {
+            if let Some(v) = flag {
+                items.push(format!("{name} {}", on_off(v)));
+            }
+        }
+        if let Some(v) = self.window {
+            items.push(format!("window {v}"));
+        }
+        write!(f, "{}", items.join(" "))
+    }
+}
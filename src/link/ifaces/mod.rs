@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT
+
+pub(super) mod bond;
+pub(super) mod bridge;
+pub(super) mod gre;
+pub(super) mod hsr;
+pub(super) mod ipvlan;
+pub(super) mod macsec;
+pub(super) mod tun;
+pub(super) mod vlan;
+pub(super) mod vrf;
+pub(super) mod vti;
+pub(super) mod xfrm;
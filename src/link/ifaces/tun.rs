@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+
+use nix::unistd::{Gid, Group, Uid, User};
+use rtnetlink::packet_route::link::{InfoTun, TunType};
+use serde::Serialize;
+
+/// TUN/TAP information equal to iproute2 `tun_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataTun {
+    #[serde(rename = "type")]
+    tun_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pi: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vnet_hdr: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    numqueues: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    numdisabled: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    multi_queue: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<CliTunOwner>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<CliTunOwner>,
+}
+
+/// Like iproute2, the owner is shown by name if found, otherwise by ID.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CliTunOwner {
+    Name(String),
+    Id(u32),
+}
+
+impl std::fmt::Display for CliTunOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(v) => write!(f, "{v}"),
+            Self::Id(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl CliTunOwner {
+    fn from_uid(uid: u32) -> Self {
+        match User::from_uid(Uid::from_raw(uid)) {
+            Ok(Some(user)) => Self::Name(user.name),
+            _ => Self::Id(uid),
+        }
+    }
+
+    fn from_gid(gid: u32) -> Self {
+        match Group::from_gid(Gid::from_raw(gid)) {
+            Ok(Some(group)) => Self::Name(group.name),
+            _ => Self::Id(gid),
+        }
+    }
+}
+
+impl From<&[InfoTun]> for CliLinkInfoDataTun {
+    fn from(info: &[InfoTun]) -> Self {
+        let mut ret = Self {
+            tun_type: String::new(),
+            pi: None,
+            vnet_hdr: None,
+            numqueues: None,
+            numdisabled: None,
+            multi_queue: None,
+            persist: None,
+            user: None,
+            group: None,
+        };
+
+        for nla in info {
+            match nla {
+                InfoTun::Type(v) => {
+                    ret.tun_type = match v {
+                        TunType::Tun => "tun".to_string(),
+                        TunType::Tap => "tap".to_string(),
+                        _ => format!("UNKNOWN:{}", u8::from(*v)),
+                    }
+                }
+                InfoTun::Pi(v) => ret.pi = Some(*v),
+                InfoTun::VnetHdr(v) => ret.vnet_hdr = Some(*v),
+                InfoTun::NumQueues(v) => ret.numqueues = Some(*v),
+                InfoTun::NumDisabledQueues(v) => ret.numdisabled = Some(*v),
+                InfoTun::MultiQueue(v) => ret.multi_queue = Some(*v),
+                InfoTun::Persist(v) => ret.persist = Some(*v),
+                InfoTun::Owner(v) => ret.user = Some(CliTunOwner::from_uid(*v)),
+                InfoTun::Group(v) => {
+                    ret.group = Some(CliTunOwner::from_gid(*v))
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+}
+
+// Equal to iproute2 `tun_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataTun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let on_off = |val: bool| if val { "on" } else { "off" };
+
+        let mut items = Vec::new();
+        if !self.tun_type.is_empty() {
+            items.push(format!("type {}", self.tun_type));
+        }
+        if let Some(v) = self.pi {
+            items.push(format!("pi {}", on_off(v)));
+        }
+        if let Some(v) = self.vnet_hdr {
+            items.push(format!("vnet_hdr {}", on_off(v)));
+        }
+        if let Some(v) = self.numqueues {
+            items.push(format!("numqueues {v}"));
+        }
+        if let Some(v) = self.numdisabled {
+            items.push(format!("numdisabled {v}"));
+        }
+        if let Some(v) = self.multi_queue {
+            items.push(format!("multi_queue {}", on_off(v)));
+        }
+        if let Some(v) = self.persist {
+            items.push(format!("persist {}", on_off(v)));
+        }
+        if let Some(v) = &self.user {
+            items.push(format!("user {v}"));
+        }
+        if let Some(v) = &self.group {
+            items.push(format!("group {v}"));
+        }
+        write!(f, "{}", items.join(" "))
+    }
+}
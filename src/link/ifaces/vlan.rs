@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{InfoVlan, VlanFlags, VlanQosMapping};
+use serde::Serialize;
+
+/// VLAN information equal to iproute2 `vlan_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataVlan {
+    protocol: String,
+    id: u16,
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingress_qos: Option<Vec<CliVlanQosMapping>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    egress_qos: Option<Vec<CliVlanQosMapping>>,
+}
+
+#[derive(Serialize)]
+struct CliVlanQosMapping {
+    from: u32,
+    to: u32,
+}
+
+impl std::fmt::Display for CliVlanQosMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.from, self.to)
+    }
+}
+
+fn qos_mappings_from(maps: &[VlanQosMapping]) -> Vec<CliVlanQosMapping> {
+    maps.iter()
+        .filter_map(|m| {
+            if let VlanQosMapping::Mapping { from, to } = m {
+                Some(CliVlanQosMapping {
+                    from: *from,
+                    to: *to,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+impl From<&[InfoVlan]> for CliLinkInfoDataVlan {
+    fn from(info: &[InfoVlan]) -> Self {
+        let mut id = 0;
+        let mut flags = Vec::new();
+        let mut protocol = String::new();
+        let mut ingress_qos = None;
+        let mut egress_qos = None;
+
+        for nla in info {
+            match nla {
+                InfoVlan::Id(v) => id = *v,
+                InfoVlan::Flags((flags_val, _)) => {
+                    if flags_val.contains(VlanFlags::ReorderHdr) {
+                        flags.push("REORDER_HDR".to_string());
+                    }
+                    if flags_val.contains(VlanFlags::Gvrp) {
+                        flags.push("GVRP".to_string());
+                    }
+                    if flags_val.contains(VlanFlags::LooseBinding) {
+                        flags.push("LOOSE_BINDING".to_string());
+                    }
+                    if flags_val.contains(VlanFlags::Mvrp) {
+                        flags.push("MVRP".to_string());
+                    }
+                    if flags_val.contains(VlanFlags::BridgeBinding) {
+                        flags.push("BRIDGE_BINDING".to_string());
+                    }
+                }
+                InfoVlan::Protocol(v) => {
+                    protocol = v.to_string().to_uppercase();
+                }
+                InfoVlan::IngressQos(v) => {
+                    ingress_qos = Some(qos_mappings_from(v))
+                }
+                InfoVlan::EgressQos(v) => {
+                    egress_qos = Some(qos_mappings_from(v))
+                }
+                _ => (),
+            }
+        }
+
+        Self {
+            id,
+            flags,
+            protocol,
+            ingress_qos,
+            egress_qos,
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoDataVlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "protocol {} ", self.protocol)?;
+        write!(f, "id {} ", self.id)?;
+        if !self.flags.is_empty() {
+            write!(f, "<{}>", self.flags.as_slice().join(","))?;
+        }
+        for (name, maps) in [
+            ("ingress-qos-map", &self.ingress_qos),
+            ("egress-qos-map", &self.egress_qos),
+        ] {
+            if let Some(maps) = maps {
+                write!(f, " \n      {name} {{ ")?;
+                for map in maps {
+                    write!(f, "{map} ")?;
+                }
+                write!(f, "}}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{InfoVrf, InfoVrfPort};
+use serde::Serialize;
+
+/// VRF information equal to iproute2 `vrf_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataVrf {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<u32>,
+}
+
+impl From<&[InfoVrf]> for CliLinkInfoDataVrf {
+    fn from(info: &[InfoVrf]) -> Self {
+        let mut table = None;
+        for nla in info {
+            if let InfoVrf::TableId(v) = nla {
+                table = Some(*v);
+            }
+        }
+        Self { table }
+    }
+}
+
+// Equal to iproute2 `vrf_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataVrf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(v) = self.table {
+            write!(f, "table {v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// VRF port information equal to iproute2 `vrf_slave_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataVrfPort {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<u32>,
+}
+
+impl From<&[InfoVrfPort]> for CliLinkInfoDataVrfPort {
+    fn from(info: &[InfoVrfPort]) -> Self {
+        let mut table = None;
+        for nla in info {
+            if let InfoVrfPort::TableId(v) = nla {
+                table = Some(*v);
+            }
+        }
+        Self { table }
+    }
+}
+
+// Equal to iproute2 `vrf_slave_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataVrfPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(v) = self.table {
+            write!(f, "table {v}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, net::Ipv4Addr};
+
+use rtnetlink::packet_route::link::InfoVti;
+use serde::Serialize;
+
+use super::gre::endpoint_to_string;
+
+/// VTI tunnel information equal to iproute2 `vti_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataVti {
+    remote: String,
+    local: String,
+    #[serde(skip)]
+    link_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ikey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    okey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fwmark: Option<String>,
+}
+
+impl From<&[InfoVti]> for CliLinkInfoDataVti {
+    fn from(info: &[InfoVti]) -> Self {
+        let mut remote = None;
+        let mut local = None;
+        let mut link_index = 0;
+        let mut ikey = None;
+        let mut okey = None;
+        let mut fwmark = 0;
+
+        for nla in info {
+            match nla {
+                InfoVti::Remote(v) => remote = Some(*v),
+                InfoVti::Local(v) => local = Some(*v),
+                InfoVti::Link(v) => link_index = *v,
+                // Like iproute2 `inet_ntop()` on the raw key
+                InfoVti::IKey(v) => ikey = Some(Ipv4Addr::from(*v).to_string()),
+                InfoVti::OKey(v) => okey = Some(Ipv4Addr::from(*v).to_string()),
+                InfoVti::FwMark(v) => fwmark = *v,
+                _ => (),
+            }
+        }
+
+        Self {
+            remote: endpoint_to_string(remote),
+            local: endpoint_to_string(local),
+            link_index,
+            // Like iproute2 `ll_index_to_name()`, fallback to `if<index>`
+            // until resolved by `resolve_iface_names()`
+            link: (link_index != 0).then(|| format!("if{link_index}")),
+            ikey,
+            okey,
+            fwmark: (fwmark != 0).then(|| format!("{fwmark:#x}")),
+        }
+    }
+}
+
+impl CliLinkInfoDataVti {
+    pub(crate) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        vec![self.link_index]
+    }
+
+    pub(crate) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        if let Some(name) = index_2_name.get(&self.link_index) {
+            self.link = Some(name.to_string());
+        }
+    }
+}
+
+// Equal to iproute2 `vti_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataVti {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "remote {} local {}", self.remote, self.local)?;
+        if let Some(v) = &self.link {
+            write!(f, " dev {v}")?;
+        }
+        if let Some(v) = &self.ikey {
+            write!(f, " ikey {v}")?;
+        }
+        if let Some(v) = &self.okey {
+            write!(f, " okey {v}")?;
+        }
+        if let Some(v) = &self.fwmark {
+            write!(f, " fwmark {v}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::{packet_core::Nla, packet_route::link::InfoXfrm};
+use serde::Serialize;
+
+const IFLA_XFRM_COLLECT_METADATA: u16 = 3;
+
+/// XFRM interface information equal to iproute2 `xfrm_print_opt()`
+#[derive(Serialize)]
+pub struct CliLinkInfoDataXfrm {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    if_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external: Option<bool>,
+}
+
+impl From<&[InfoXfrm]> for CliLinkInfoDataXfrm {
+    fn from(info: &[InfoXfrm]) -> Self {
+        let mut if_id = None;
+        let mut external = None;
+
+        for nla in info {
+            match nla {
+                InfoXfrm::IfId(v) => if_id = Some(format!("{v:#x}")),
+                // The lower device is shown as link of the interface,
+                // e.g. `xfrm0@eth0`
+                InfoXfrm::Other(nla)
+                    if nla.kind() == IFLA_XFRM_COLLECT_METADATA =>
+                {
+                    external = Some(true)
+                }
+                _ => (),
+            }
+        }
+
+        Self { if_id, external }
+    }
+}
+
+// Equal to iproute2 `xfrm_print_opt()`
+impl std::fmt::Display for CliLinkInfoDataXfrm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut items = Vec::new();
+        if let Some(v) = &self.if_id {
+            items.push(format!("if_id {v}"));
+        }
+        if self.external == Some(true) {
+            items.push("external".to_string());
+        }
+        write!(f, "{}", items.join(" "))
+    }
+}
@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, convert::TryFrom};
+
+use rtnetlink::packet_route::link::{InfoData, InfoPortData, LinkInfo};
+use serde::Serialize;
+
+use super::{
+    detail::bytes_to_hex,
+    ifaces::{
+        bond::{CliLinkInfoDataBond, CliLinkInfoDataBondPort},
+        bridge::{CliLinkInfoDataBridge, CliLinkInfoDataBridgePort},
+        gre::CliLinkInfoDataGre,
+        hsr::CliLinkInfoDataHsr,
+        ipvlan::CliLinkInfoDataIpVlan,
+        macsec::CliLinkInfoDataMacSec,
+        tun::CliLinkInfoDataTun,
+        vlan::CliLinkInfoDataVlan,
+        vrf::{CliLinkInfoDataVrf, CliLinkInfoDataVrfPort},
+        vti::CliLinkInfoDataVti,
+        xfrm::CliLinkInfoDataXfrm,
+    },
+};
+
+/// Kind specific information of `IFLA_LINKINFO`
+#[derive(Serialize)]
+pub struct CliLinkInfoKind {
+    info_kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info_data: Option<CliLinkInfoData>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "info_slave_kind"
+    )]
+    info_port_kind: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "info_slave_data"
+    )]
+    info_port_data: Option<CliLinkInfoPortData>,
+}
+
+impl TryFrom<&[LinkInfo]> for CliLinkInfoKind {
+    type Error = ();
+
+    fn try_from(infos: &[LinkInfo]) -> Result<Self, ()> {
+        let mut info_kind = String::new();
+        let mut info_data = None;
+        let mut info_port_kind = None;
+        let mut info_port_data = None;
+        for info in infos {
+            match info {
+                LinkInfo::Kind(v) => {
+                    info_kind = v.to_string();
+                }
+                LinkInfo::Data(v) => {
+                    info_data = v.try_into().ok();
+                }
+                LinkInfo::PortKind(v) => info_port_kind = Some(v.to_string()),
+                LinkInfo::PortData(v) => info_port_data = v.try_into().ok(),
+                _ => (),
+            }
+        }
+        if info_kind.is_empty() {
+            Err(())
+        } else {
+            Ok(Self {
+                info_kind,
+                info_data,
+                info_port_kind,
+                info_port_data,
+            })
+        }
+    }
+}
+
+impl CliLinkInfoKind {
+    /// Interface kind, e.g. `bridge`
+    pub fn get_kind(&self) -> &str {
+        &self.info_kind
+    }
+
+    /// Kind specific data, `None` for kinds not supported yet
+    pub fn get_data(&self) -> Option<&CliLinkInfoData> {
+        self.info_data.as_ref()
+    }
+
+    /// Kind of the controller this interface is attached to
+    pub fn get_port_kind(&self) -> Option<&str> {
+        self.info_port_kind.as_deref()
+    }
+
+    /// Controller specific data of the port, e.g. bridge port state
+    pub fn get_port_data(&self) -> Option<&CliLinkInfoPortData> {
+        self.info_port_data.as_ref()
+    }
+
+    /// Interface indexes referred by kind specific data, e.g. the active
+    /// port of bond
+    pub(super) fn get_referred_ifindexes(&self) -> Vec<u32> {
+        match self.info_data.as_ref() {
+            Some(CliLinkInfoData::Bond(bond)) => bond.get_referred_ifindexes(),
+            Some(CliLinkInfoData::Gre(gre)) => gre.get_referred_ifindexes(),
+            Some(CliLinkInfoData::Vti(vti)) => vti.get_referred_ifindexes(),
+            Some(CliLinkInfoData::Hsr(hsr)) => hsr.get_referred_ifindexes(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub(super) fn resolve_iface_names(
+        &mut self,
+        index_2_name: &HashMap<u32, String>,
+    ) {
+        match self.info_data.as_mut() {
+            Some(CliLinkInfoData::Bond(bond)) => {
+                bond.resolve_iface_names(index_2_name);
+            }
+            Some(CliLinkInfoData::Gre(gre)) => {
+                gre.resolve_iface_names(index_2_name);
+            }
+            Some(CliLinkInfoData::Vti(vti)) => {
+                vti.resolve_iface_names(index_2_name);
+            }
+            Some(CliLinkInfoData::Hsr(hsr)) => {
+                hsr.resolve_iface_names(index_2_name);
+            }
+            _ => (),
+        }
+    }
+
+    pub(super) fn bridge_port_mut(
+        &mut self,
+    ) -> Option<&mut CliLinkInfoDataBridgePort> {
+        if let Some(CliLinkInfoPortData::Bridge(port)) =
+            self.info_port_data.as_mut()
+        {
+            Some(port)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\n    ")?;
+        write!(f, "{} ", self.info_kind)?;
+        if let Some(data) = &self.info_data {
+            write!(f, "{data} ")?;
+        }
+
+        if let Some(port_kind) = &self.info_port_kind {
+            write!(f, "\n    {}_slave ", port_kind)?;
+            if let Some(port_data) = &self.info_port_data {
+                write!(f, "{port_data} ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Kind specific data of `IFLA_INFO_DATA`
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum CliLinkInfoData {
+    Vlan(Box<CliLinkInfoDataVlan>),
+    Bridge(Box<CliLinkInfoDataBridge>),
+    Bond(Box<CliLinkInfoDataBond>),
+    Tun(Box<CliLinkInfoDataTun>),
+    IpVlan(Box<CliLinkInfoDataIpVlan>),
+    Gre(Box<CliLinkInfoDataGre>),
+    Vti(Box<CliLinkInfoDataVti>),
+    Vrf(Box<CliLinkInfoDataVrf>),
+    Xfrm(Box<CliLinkInfoDataXfrm>),
+    Hsr(Box<CliLinkInfoDataHsr>),
+    MacSec(Box<CliLinkInfoDataMacSec>),
+    /// Hex dump of data for kinds unknown to netlink-packet-route
+    Other(String),
+}
+
+impl TryFrom<&InfoData> for CliLinkInfoData {
+    type Error = ();
+
+    fn try_from(info_data: &InfoData) -> Result<CliLinkInfoData, ()> {
+        match info_data {
+            InfoData::Bridge(v) => {
+                Ok(Self::Bridge(Box::new(v.as_slice().into())))
+            }
+            InfoData::Vlan(v) => Ok(Self::Vlan(Box::new(v.as_slice().into()))),
+            InfoData::Bond(v) => Ok(Self::Bond(Box::new(v.as_slice().into()))),
+            InfoData::Tun(v) => Ok(Self::Tun(Box::new(v.as_slice().into()))),
+            InfoData::IpVlan(v) => {
+                Ok(Self::IpVlan(Box::new(v.as_slice().into())))
+            }
+            InfoData::IpVtap(v) => {
+                Ok(Self::IpVlan(Box::new(v.as_slice().into())))
+            }
+            InfoData::GreTun(v) => Ok(Self::Gre(Box::new(v.as_slice().into()))),
+            InfoData::GreTap(v) => Ok(Self::Gre(Box::new(v.as_slice().into()))),
+            InfoData::GreTun6(v) => {
+                Ok(Self::Gre(Box::new(v.as_slice().into())))
+            }
+            InfoData::GreTap6(v) => {
+                Ok(Self::Gre(Box::new(v.as_slice().into())))
+            }
+            InfoData::Vti(v) => Ok(Self::Vti(Box::new(v.as_slice().into()))),
+            InfoData::Vrf(v) => Ok(Self::Vrf(Box::new(v.as_slice().into()))),
+            InfoData::Xfrm(v) => Ok(Self::Xfrm(Box::new(v.as_slice().into()))),
+            InfoData::Hsr(v) => Ok(Self::Hsr(Box::new(v.as_slice().into()))),
+            InfoData::MacSec(v) => {
+                Ok(Self::MacSec(Box::new(v.as_slice().into())))
+            }
+            // Like iproute2, no data shown for veth, the peer is shown as
+            // link of the interface, e.g. `veth0@veth1`
+            InfoData::Veth(_) => Err(()),
+            InfoData::Other(v) if !v.is_empty() => {
+                Ok(Self::Other(bytes_to_hex(v)))
+            }
+            // Like iproute2 for kinds without `print_opt()`, only the kind is
+            // shown for the not yet supported ones and the ones without data,
+            // e.g. wireguard
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkInfoData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliLinkInfoData::Vlan(v) => write!(f, "{v}"),
+            CliLinkInfoData::Bridge(v) => write!(f, "{v}"),
+            CliLinkInfoData::Bond(v) => write!(f, "{v}"),
+            CliLinkInfoData::Tun(v) => write!(f, "{v}"),
+            CliLinkInfoData::IpVlan(v) => write!(f, "{v}"),
+            CliLinkInfoData::Gre(v) => write!(f, "{v}"),
+            CliLinkInfoData::Vti(v) => write!(f, "{v}"),
+            CliLinkInfoData::Vrf(v) => write!(f, "{v}"),
+            CliLinkInfoData::Xfrm(v) => write!(f, "{v}"),
+            CliLinkInfoData::Hsr(v) => write!(f, "{v}"),
+            CliLinkInfoData::MacSec(v) => write!(f, "{v}"),
+            CliLinkInfoData::Other(v) => write!(f, "data {v}"),
+        }
+    }
+}
+
+/// Port specific data of `IFLA_INFO_SLAVE_DATA`
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum CliLinkInfoPortData {
+    Bridge(CliLinkInfoDataBridgePort),
+    Bond(CliLinkInfoDataBondPort),
+    Vrf(CliLinkInfoDataVrfPort),
+    /// Hex dump of data for port kinds unknown to netlink-packet-route
+    Other(String),
+}
+
+impl std::fmt::Display for CliLinkInfoPortData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliLinkInfoPortData::Bridge(v) => write!(f, "{v}"),
+            CliLinkInfoPortData::Bond(v) => write!(f, "{v}"),
+            CliLinkInfoPortData::Vrf(v) => write!(f, "{v}"),
+            CliLinkInfoPortData::Other(v) => write!(f, "data {v}"),
+        }
+    }
+}
+
+impl TryFrom<&InfoPortData> for CliLinkInfoPortData {
+    type Error = ();
+
+    fn try_from(info_data: &InfoPortData) -> Result<CliLinkInfoPortData, ()> {
+        match info_data {
+            InfoPortData::BridgePort(v) => {
+                Ok(Self::Bridge(v.as_slice().into()))
+            }
+            InfoPortData::BondPort(v) => Ok(Self::Bond(v.as_slice().into())),
+            InfoPortData::VrfPort(v) => Ok(Self::Vrf(v.as_slice().into())),
+            InfoPortData::Other(v) if !v.is_empty() => {
+                Ok(Self::Other(bytes_to_hex(v)))
+            }
+            // Only the port kind is shown for the not yet supported ones
+            _ => Err(()),
+        }
+    }
+}
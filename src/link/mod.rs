@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MIT
+
+//! Structured network interface information equal to iproute2
+//! `ip link show`.
+//!
+//! The structures serialize to the same JSON as `ip -json link show` and
+//! display as the plain text output of `ip link show`, hence could be used
+//! by other programs to query interfaces without spawning `ip`:
+//!
+//! ```no_run
+//! # async fn show() -> Result<(), iproute_rs::CliError> {
+//! let (conn, handle, _) = rtnetlink::new_connection()?;
+//! tokio::spawn(conn);
+//! let links = iproute_rs::link::get_links(
+//!     &handle,
+//!     &iproute_rs::link::LinkDumpFilter::default(),
+//!     true,
+//!     0,
+//!     iproute_rs::CliNumberFormat::default(),
+//!     false,
+//! )
+//! .await?;
+//! for link in links {
+//!     println!("{} {}", link.get_ifname(), link.get_operstate());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod brief;
+mod detail;
+mod flags;
+mod iface;
+mod ifaces;
+mod link_info;
+mod stats;
+mod vf;
+mod xdp;
+
+pub use self::{
+    brief::CliLinkInfoBrief,
+    detail::CliLinkInfoDetail,
+    flags::link_flags_to_string,
+    iface::{
+        CliLinkInfo, LinkDumpFilter, get_links, parse_nl_msg_to_iface,
+        resolve_ip_link_group_name,
+    },
+    ifaces::{
+        bond::{CliLinkInfoDataBond, CliLinkInfoDataBondPort},
+        bridge::{
+            CliBridgeVlanTunnel, CliLinkInfoDataBridge,
+            CliLinkInfoDataBridgePort,
+        },
+        gre::CliLinkInfoDataGre,
+        hsr::CliLinkInfoDataHsr,
+        ipvlan::CliLinkInfoDataIpVlan,
+        macsec::CliLinkInfoDataMacSec,
+        tun::CliLinkInfoDataTun,
+        vlan::CliLinkInfoDataVlan,
+        vrf::{CliLinkInfoDataVrf, CliLinkInfoDataVrfPort},
+        vti::CliLinkInfoDataVti,
+        xfrm::CliLinkInfoDataXfrm,
+    },
+    link_info::{CliLinkInfoData, CliLinkInfoKind, CliLinkInfoPortData},
+    stats::CliLinkStats,
+};
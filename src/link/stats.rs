@@ -0,0 +1,426 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{LinkAttribute, Stats, Stats64};
+use serde::Serialize;
+
+use crate::CliNumberFormat;
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
+
+// Equal to the `cols` of iproute2 `print_link_stats64()`
+const STATS_COLUMN_MIN_WIDTH: [usize; 8] = [
+    "*X errors:".len(),
+    "packets".len(),
+    "errors".len(),
+    "dropped".len(),
+    "heartbt".len(),
+    "overrun".len(),
+    "compressed".len(),
+    "otherhost".len(),
+];
+
+/// Interface statistics shown by `ip -statistics link show`
+#[derive(Serialize)]
+pub struct CliLinkStats {
+    rx: CliLinkStatsRx,
+    tx: CliLinkStatsTx,
+    #[serde(skip)]
+    number_format: CliNumberFormat,
+}
+
+#[derive(Serialize)]
+struct CliLinkStatsRx {
+    bytes: u64,
+    packets: u64,
+    errors: u64,
+    dropped: u64,
+    over_errors: u64,
+    multicast: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    compressed: u64,
+    #[serde(skip)]
+    missed: u64,
+    #[serde(skip_serializing_if = "Option::is_none", flatten)]
+    error_details: Option<CliLinkStatsRxErrors>,
+}
+
+#[derive(Serialize)]
+struct CliLinkStatsRxErrors {
+    length_errors: u64,
+    crc_errors: u64,
+    frame_errors: u64,
+    fifo_errors: u64,
+    missed_errors: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    nohandler: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    otherhost: u64,
+}
+
+#[derive(Serialize)]
+struct CliLinkStatsTx {
+    bytes: u64,
+    packets: u64,
+    errors: u64,
+    dropped: u64,
+    carrier_errors: u64,
+    collisions: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    compressed: u64,
+    #[serde(skip_serializing_if = "Option::is_none", flatten)]
+    error_details: Option<CliLinkStatsTxErrors>,
+}
+
+#[derive(Serialize)]
+struct CliLinkStatsTxErrors {
+    aborted_errors: u64,
+    fifo_errors: u64,
+    window_errors: u64,
+    heartbeat_errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    carrier_changes: Option<u32>,
+}
+
+impl CliLinkStats {
+    /// Return None if no statistics found in netlink attributes.
+    /// The `stats_level` is the count of `-s` argument, the error details are
+    /// only included when it is bigger than 1.
+    pub(crate) fn new(
+        nl_attrs: &[LinkAttribute],
+        stats_level: u8,
+        number_format: CliNumberFormat,
+    ) -> Option<Self> {
+        let mut stats64 = None;
+        let mut stats32 = None;
+        let mut carrier_changes = None;
+
+        for nl_attr in nl_attrs {
+            match nl_attr {
+                LinkAttribute::Stats64(s) => stats64 = Some(*s),
+                LinkAttribute::Stats(s) => stats32 = Some(*s),
+                LinkAttribute::CarrierChanges(c) => carrier_changes = Some(*c),
+                _ => (),
+            }
+        }
+        // iproute2 prefers IFLA_STATS64 over IFLA_STATS
+        let s = stats64.or_else(|| stats32.as_ref().map(stats32_to_64))?;
+
+        Some(Self::from_stats64(
+            &s,
+            carrier_changes,
+            stats_level,
+            number_format,
+        ))
+    }
+
+    /// Like iproute2 `print_stats64()`, the carrier changes are only shown
+    /// along with error details when provided.
+    pub fn from_stats64(
+        s: &Stats64,
+        carrier_changes: Option<u32>,
+        stats_level: u8,
+        number_format: CliNumberFormat,
+    ) -> Self {
+        let with_errors = stats_level > 1;
+
+        Self {
+            rx: CliLinkStatsRx {
+                bytes: s.rx_bytes,
+                packets: s.rx_packets,
+                errors: s.rx_errors,
+                dropped: s.rx_dropped,
+                over_errors: s.rx_over_errors,
+                multicast: s.multicast,
+                compressed: s.rx_compressed,
+                missed: s.rx_missed_errors,
+                error_details: with_errors.then_some(CliLinkStatsRxErrors {
+                    length_errors: s.rx_length_errors,
+                    crc_errors: s.rx_crc_errors,
+                    frame_errors: s.rx_frame_errors,
+                    fifo_errors: s.rx_fifo_errors,
+                    missed_errors: s.rx_missed_errors,
+                    nohandler: s.rx_nohandler,
+                    otherhost: s.rx_otherhost_dropped,
+                }),
+            },
+            tx: CliLinkStatsTx {
+                bytes: s.tx_bytes,
+                packets: s.tx_packets,
+                errors: s.tx_errors,
+                dropped: s.tx_dropped,
+                carrier_errors: s.tx_carrier_errors,
+                collisions: s.collisions,
+                compressed: s.tx_compressed,
+                error_details: with_errors.then_some(CliLinkStatsTxErrors {
+                    aborted_errors: s.tx_aborted_errors,
+                    fifo_errors: s.tx_fifo_errors,
+                    window_errors: s.tx_window_errors,
+                    heartbeat_errors: s.tx_heartbeat_errors,
+                    carrier_changes,
+                }),
+            },
+            number_format,
+        }
+    }
+
+    // Equal to iproute2 `size_columns()`
+    fn column_widths(&self) -> [usize; 8] {
+        let mut cols = STATS_COLUMN_MIN_WIDTH;
+        // iproute2 does not resize columns for human readable numbers
+        if self.number_format.is_human() {
+            return cols;
+        }
+        let rx = &self.rx;
+        let tx = &self.tx;
+
+        let mut rows: Vec<Vec<u64>> = vec![vec![
+            rx.bytes,
+            rx.packets,
+            rx.errors,
+            rx.dropped,
+            rx.missed,
+            rx.multicast,
+            rx.compressed,
+        ]];
+        if let Some(e) = rx.error_details.as_ref() {
+            rows.push(vec![
+                0,
+                e.length_errors,
+                e.crc_errors,
+                e.frame_errors,
+                e.fifo_errors,
+                rx.over_errors,
+                e.nohandler,
+                e.otherhost,
+            ]);
+        }
+        rows.push(vec![
+            tx.bytes,
+            tx.packets,
+            tx.errors,
+            tx.dropped,
+            tx.carrier_errors,
+            tx.collisions,
+            tx.compressed,
+        ]);
+        if let Some(e) = tx.error_details.as_ref() {
+            rows.push(vec![
+                0,
+                e.aborted_errors,
+                e.fifo_errors,
+                e.window_errors,
+                e.heartbeat_errors,
+                e.carrier_changes.unwrap_or_default().into(),
+            ]);
+        }
+
+        for row in rows {
+            for (col, val) in cols.iter_mut().zip(row) {
+                *col = (*col).max(val.to_string().len());
+            }
+        }
+        cols
+    }
+}
+
+impl std::fmt::Display for CliLinkStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cols = self.column_widths();
+        let rx = &self.rx;
+        let tx = &self.tx;
+
+        write!(f, "    RX: {:>w$}", "bytes", w = cols[0] - 4)?;
+        write_headers(
+            f,
+            &cols[1..],
+            &[
+                "packets",
+                "errors",
+                "dropped",
+                "missed",
+                "mcast",
+                if rx.compressed != 0 { "compressed" } else { "" },
+            ],
+        )?;
+        write!(f, "\n    ")?;
+        write_nums(
+            f,
+            self.number_format,
+            &cols,
+            &[
+                rx.bytes,
+                rx.packets,
+                rx.errors,
+                rx.dropped,
+                rx.missed,
+                rx.multicast,
+            ],
+        )?;
+        if rx.compressed != 0 {
+            write_num(f, self.number_format, cols[6], rx.compressed)?;
+        }
+
+        if let Some(e) = rx.error_details.as_ref() {
+            write!(f, "\n    RX errors:{:>w$}", "", w = cols[0] - 10)?;
+            write_headers(
+                f,
+                &cols[1..],
+                &["length", "crc", "frame", "fifo", "overrun"],
+            )?;
+            if e.nohandler != 0 {
+                write_headers(f, &cols[6..], &["nohandler"])?;
+            }
+            if e.otherhost != 0 {
+                write_headers(f, &cols[7..], &["otherhost"])?;
+            }
+            write!(f, "\n{:>w$}", "", w = cols[0] + 5)?;
+            write_nums(
+                f,
+                self.number_format,
+                &cols[1..],
+                &[
+                    e.length_errors,
+                    e.crc_errors,
+                    e.frame_errors,
+                    e.fifo_errors,
+                    rx.over_errors,
+                ],
+            )?;
+            if e.nohandler != 0 {
+                write_num(f, self.number_format, cols[6], e.nohandler)?;
+            }
+            if e.otherhost != 0 {
+                write_num(f, self.number_format, cols[7], e.otherhost)?;
+            }
+        }
+
+        write!(f, "\n    TX: {:>w$}", "bytes", w = cols[0] - 4)?;
+        write_headers(
+            f,
+            &cols[1..],
+            &[
+                "packets",
+                "errors",
+                "dropped",
+                "carrier",
+                "collsns",
+                if tx.compressed != 0 { "compressed" } else { "" },
+            ],
+        )?;
+        write!(f, "\n    ")?;
+        write_nums(
+            f,
+            self.number_format,
+            &cols,
+            &[
+                tx.bytes,
+                tx.packets,
+                tx.errors,
+                tx.dropped,
+                tx.carrier_errors,
+                tx.collisions,
+            ],
+        )?;
+        if tx.compressed != 0 {
+            write_num(f, self.number_format, cols[6], tx.compressed)?;
+        }
+
+        if let Some(e) = tx.error_details.as_ref() {
+            write!(f, "\n    TX errors:{:>w$}", "", w = cols[0] - 10)?;
+            write_headers(
+                f,
+                &cols[1..],
+                &["aborted", "fifo", "window", "heartbt"],
+            )?;
+            write_headers(
+                f,
+                &cols[5..],
+                &[if e.carrier_changes.is_some() {
+                    "transns"
+                } else {
+                    ""
+                }],
+            )?;
+            write!(f, "\n{:>w$}", "", w = cols[0] + 5)?;
+            write_nums(
+                f,
+                self.number_format,
+                &cols[1..],
+                &[
+                    e.aborted_errors,
+                    e.fifo_errors,
+                    e.window_errors,
+                    e.heartbeat_errors,
+                ],
+            )?;
+            if let Some(c) = e.carrier_changes {
+                write_num(f, self.number_format, cols[5], c.into())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_headers(
+    f: &mut std::fmt::Formatter<'_>,
+    widths: &[usize],
+    names: &[&str],
+) -> std::fmt::Result {
+    for (width, name) in widths.iter().zip(names) {
+        write!(f, " {name:>width$}")?;
+    }
+    Ok(())
+}
+
+// Equal to iproute2 `print_num()`
+fn write_num(
+    f: &mut std::fmt::Formatter<'_>,
+    number_format: CliNumberFormat,
+    width: usize,
+    count: u64,
+) -> std::fmt::Result {
+    write!(f, "{:>width$} ", number_format.format(count))
+}
+
+fn write_nums(
+    f: &mut std::fmt::Formatter<'_>,
+    number_format: CliNumberFormat,
+    widths: &[usize],
+    counts: &[u64],
+) -> std::fmt::Result {
+    for (width, count) in widths.iter().zip(counts) {
+        write_num(f, number_format, *width, *count)?;
+    }
+    Ok(())
+}
+
+fn stats32_to_64(s: &Stats) -> Stats64 {
+    let mut ret = Stats64::default();
+    ret.rx_packets = s.rx_packets.into();
+    ret.tx_packets = s.tx_packets.into();
+    ret.rx_bytes = s.rx_bytes.into();
+    ret.tx_bytes = s.tx_bytes.into();
+    ret.rx_errors = s.rx_errors.into();
+    ret.tx_errors = s.tx_errors.into();
+    ret.rx_dropped = s.rx_dropped.into();
+    ret.tx_dropped = s.tx_dropped.into();
+    ret.multicast = s.multicast.into();
+    ret.collisions = s.collisions.into();
+    ret.rx_length_errors = s.rx_length_errors.into();
+    ret.rx_over_errors = s.rx_over_errors.into();
+    ret.rx_crc_errors = s.rx_crc_errors.into();
+    ret.rx_frame_errors = s.rx_frame_errors.into();
+    ret.rx_fifo_errors = s.rx_fifo_errors.into();
+    ret.rx_missed_errors = s.rx_missed_errors.into();
+    ret.tx_aborted_errors = s.tx_aborted_errors.into();
+    ret.tx_carrier_errors = s.tx_carrier_errors.into();
+    ret.tx_fifo_errors = s.tx_fifo_errors.into();
+    ret.tx_heartbeat_errors = s.tx_heartbeat_errors.into();
+    ret.tx_window_errors = s.tx_window_errors.into();
+    ret.rx_compressed = s.rx_compressed.into();
+    ret.tx_compressed = s.tx_compressed.into();
+    ret.rx_nohandler = s.rx_nohandler.into();
+    ret
+}
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{
+    LinkAttribute, LinkVfInfo, VfInfo, VfVlan, VlanProtocol,
+};
+use serde::Serialize;
+
+use crate::{CliColor, mac_to_string, write_with_color};
+
+#[derive(Serialize, Default)]
+struct CliLinkVfVlan {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vlan: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qos: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<String>,
+}
+
+impl CliLinkVfVlan {
+    fn new(vlan_id: u32, qos: u32, protocol: Option<VlanProtocol>) -> Self {
+        Self {
+            vlan: (vlan_id != 0).then_some(vlan_id),
+            qos: (qos != 0).then_some(qos),
+            // Like iproute2, only non 802.1Q protocol is shown
+            protocol: match protocol {
+                None | Some(VlanProtocol::Ieee8021Q) => None,
+                Some(VlanProtocol::Ieee8021Ad) => Some("802.1ad".to_string()),
+                Some(p) => Some(p.to_string()),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for CliLinkVfVlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(v) = self.vlan {
+            write!(f, ", vlan {v}")?;
+        }
+        if let Some(v) = self.qos {
+            write!(f, ", qos {v}")?;
+        }
+        if let Some(v) = self.protocol.as_ref() {
+            write!(f, ", vlan protocol {v}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Default)]
+struct CliLinkVfRate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tx: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_tx: Option<u32>,
+}
+
+#[derive(Serialize, Default)]
+pub(crate) struct CliLinkVfInfo {
+    vf: u32,
+    link_type: String,
+    address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broadcast: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vlan_list: Option<Vec<CliLinkVfVlan>>,
+    #[serde(flatten)]
+    vlan: CliLinkVfVlan,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate: Option<CliLinkVfRate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spoofchk: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trust: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_rss_en: Option<bool>,
+}
+
+impl CliLinkVfInfo {
+    // The VF hardware address is always 32 bytes in kernel, iproute2 trims
+    // it to the length of the PF address.
+    fn new(vf_info: LinkVfInfo, link_type: &str, addr_len: usize) -> Self {
+        let mut ret = Self {
+            link_type: link_type.to_string(),
+            ..Default::default()
+        };
+        for nla in vf_info.0 {
+            match nla {
+                VfInfo::Mac(v) => {
+                    ret.vf = v.vf_id;
+                    ret.address =
+                        mac_to_string(&v.mac[..addr_len.min(v.mac.len())]);
+                }
+                VfInfo::Broadcast(v) => {
+                    ret.broadcast = Some(mac_to_string(
+                        &v.addr[..addr_len.min(v.addr.len())],
+                    ));
+                }
+                VfInfo::Vlan(v) => {
+                    ret.vlan = CliLinkVfVlan::new(v.vlan_id, v.qos, None);
+                }
+                VfInfo::VlanList(vlans) => {
+                    ret.vlan_list = Some(
+                        vlans
+                            .into_iter()
+                            .filter_map(|v| {
+                                if let VfVlan::Info(v) = v {
+                                    Some(CliLinkVfVlan::new(
+                                        v.vlan_id,
+                                        v.qos,
+                                        Some(v.protocol),
+                                    ))
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect(),
+                    );
+                }
+                VfInfo::TxRate(v) if v.rate != 0 => ret.tx_rate = Some(v.rate),
+                VfInfo::Rate(v) => {
+                    ret.rate = Some(CliLinkVfRate {
+                        max_tx: (v.max_tx_rate != 0).then_some(v.max_tx_rate),
+                        min_tx: (v.min_tx_rate != 0).then_some(v.min_tx_rate),
+                    });
+                }
+                VfInfo::SpoofCheck(v) => ret.spoofchk = Some(v.enabled),
+                VfInfo::LinkState(v) => {
+                    ret.link_state = Some(v.state.to_string())
+                }
+                VfInfo::Trust(v) => ret.trust = Some(v.enabled),
+                VfInfo::RssQueryEn(v) => ret.query_rss_en = Some(v.enabled),
+                _ => (),
+            }
+        }
+        // iproute2 only prints the VLAN list when kernel provides it
+        if ret.vlan_list.is_some() {
+            ret.vlan = CliLinkVfVlan::default();
+        }
+        ret
+    }
+
+    /// Like iproute2, VF information is only shown when `IFLA_NUM_VF` is
+    /// also provided by kernel.
+    pub(crate) fn new_list(
+        attributes: &[LinkAttribute],
+        link_type: &str,
+    ) -> Vec<Self> {
+        if !attributes
+            .iter()
+            .any(|a| matches!(a, LinkAttribute::NumVf(_)))
+        {
+            return Vec::new();
+        }
+        let addr_len = attributes
+            .iter()
+            .find_map(|a| {
+                if let LinkAttribute::Address(mac) = a {
+                    Some(mac.len())
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        attributes
+            .iter()
+            .filter_map(|a| {
+                if let LinkAttribute::VfInfoList(v) = a {
+                    Some(v)
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .map(|v| Self::new(v.clone(), link_type, addr_len))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for CliLinkVfInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vf {}     link/{} ", self.vf, self.link_type)?;
+        write_with_color!(f, CliColor::Mac, "{}", self.address)?;
+        if let Some(brd) = self.broadcast.as_ref() {
+            write!(f, " brd ")?;
+            write_with_color!(f, CliColor::Mac, "{brd}")?;
+        }
+        if let Some(vlans) = self.vlan_list.as_ref() {
+            for vlan in vlans {
+                write!(f, "{vlan}")?;
+            }
+        } else {
+            write!(f, "{}", self.vlan)?;
+        }
+        if let Some(v) = self.tx_rate {
+            write!(f, ", tx rate {v} (Mbps)")?;
+        }
+        if let Some(rate) = self.rate.as_ref() {
+            if let Some(v) = rate.max_tx {
+                write!(f, ", max_tx_rate {v}Mbps")?;
+            }
+            if let Some(v) = rate.min_tx {
+                write!(f, ", min_tx_rate {v}Mbps")?;
+            }
+        }
+        if let Some(v) = self.spoofchk {
+            write!(f, ", spoof checking {}", if v { "on" } else { "off" })?;
+        }
+        if let Some(v) = self.link_state.as_ref() {
+            write!(f, ", link-state {v}")?;
+        }
+        if let Some(v) = self.trust {
+            write!(f, ", trust {}", if v { "on" } else { "off" })?;
+        }
+        if let Some(v) = self.query_rss_en {
+            write!(f, ", query_rss {}", if v { "on" } else { "off" })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: MIT
+
+use rtnetlink::packet_route::link::{LinkAttribute, LinkXdp, XdpAttached};
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy)]
+struct CliLinkXdpProg {
+    id: u32,
+}
+
+#[derive(Serialize)]
+struct CliLinkXdpAttached {
+    mode: u8,
+    prog: CliLinkXdpProg,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CliLinkXdp {
+    mode: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prog: Option<CliLinkXdpProg>,
+    attached: Vec<CliLinkXdpAttached>,
+    #[serde(skip)]
+    attached_mode: XdpAttached,
+    #[serde(skip)]
+    prog_id: Option<u32>,
+    #[serde(skip)]
+    skb_prog_id: Option<u32>,
+    #[serde(skip)]
+    drv_prog_id: Option<u32>,
+    #[serde(skip)]
+    hw_prog_id: Option<u32>,
+    // `ip address` shows the program ID after the mode instead of the
+    // `prog/xdp` lines.
+    #[serde(skip)]
+    show_id_in_mode: bool,
+    #[serde(skip)]
+    show_progs: bool,
+}
+
+impl CliLinkXdp {
+    pub(crate) fn new(attributes: &[LinkAttribute]) -> Option<Self> {
+        let xdp_nlas = attributes.iter().find_map(|a| {
+            if let LinkAttribute::Xdp(v) = a {
+                Some(v)
+            } else {
+                None
+            }
+        })?;
+
+        let mut attached_mode = None;
+        let mut prog_id = None;
+        let mut skb_prog_id = None;
+        let mut drv_prog_id = None;
+        let mut hw_prog_id = None;
+        for nla in xdp_nlas {
+            match nla {
+                LinkXdp::Attached(v) => attached_mode = Some(*v),
+                LinkXdp::ProgId(v) => prog_id = Some(*v),
+                LinkXdp::SkbProgId(v) => skb_prog_id = Some(*v),
+                LinkXdp::DrvProgId(v) => drv_prog_id = Some(*v),
+                LinkXdp::HwProgId(v) => hw_prog_id = Some(*v),
+                _ => (),
+            }
+        }
+        let attached_mode = attached_mode?;
+        if attached_mode == XdpAttached::None {
+            return None;
+        }
+        let mode = u8::from(attached_mode);
+
+        let mut attached = Vec::new();
+        if skb_prog_id.is_some()
+            || drv_prog_id.is_some()
+            || hw_prog_id.is_some()
+        {
+            for (id, mode) in [
+                (skb_prog_id, XdpAttached::SocketBuffer),
+                (drv_prog_id, XdpAttached::Driver),
+                (hw_prog_id, XdpAttached::Hardware),
+            ] {
+                if let Some(id) = id {
+                    attached.push(CliLinkXdpAttached {
+                        mode: u8::from(mode),
+                        prog: CliLinkXdpProg { id },
+                    });
+                }
+            }
+        } else if let Some(id) = prog_id {
+            // Older kernel only provides IFLA_XDP_PROG_ID
+            attached.push(CliLinkXdpAttached {
+                mode,
+                prog: CliLinkXdpProg { id },
+            });
+        }
+
+        Some(Self {
+            mode,
+            prog: prog_id
+                .filter(|id| *id != 0)
+                .map(|id| CliLinkXdpProg { id }),
+            attached,
+            attached_mode,
+            prog_id,
+            skb_prog_id,
+            drv_prog_id,
+            hw_prog_id,
+            show_id_in_mode: false,
+            show_progs: true,
+        })
+    }
+
+    pub(crate) fn set_address_mode(&mut self, include_details: bool) {
+        self.show_id_in_mode = true;
+        self.show_progs = include_details;
+    }
+
+    /// The attach mode shown after MTU, e.g. `xdpgeneric`
+    pub(crate) fn mode_string(&self) -> String {
+        let mut ret = match self.attached_mode {
+            XdpAttached::Driver => "xdp".to_string(),
+            XdpAttached::SocketBuffer => "xdpgeneric".to_string(),
+            XdpAttached::Hardware => "xdpoffload".to_string(),
+            XdpAttached::Multiple => "xdpmulti".to_string(),
+            _ => format!("xdp[{}]", self.mode),
+        };
+        if self.show_id_in_mode
+            && let Some(id) = self.prog_id.filter(|id| *id != 0)
+        {
+            ret.push_str(&format!("/id:{id}"));
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for CliLinkXdp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.show_progs {
+            return Ok(());
+        }
+        for (prefix, id) in [
+            ("", self.prog_id),
+            ("generic", self.skb_prog_id),
+            ("drv", self.drv_prog_id),
+            ("offload", self.hw_prog_id),
+        ] {
+            if let Some(id) = id.filter(|id| *id != 0) {
+                write!(f, "\n    prog/xdp{prefix} id {id} ")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT
+
+const HUMAN_PREFIXES: [char; 6] = ['k', 'M', 'G', 'T', 'P', 'E'];
+
+/// How counters are rendered, controlled by the `-human` and `-iec` options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CliNumberFormat {
+    #[default]
+    Raw,
+    /// Suffix with k, M, G, etc. in base of 1000
+    Human,
+    /// Suffix with Ki, Mi, Gi, etc. in base of 1024
+    HumanIec,
+}
+
+impl CliNumberFormat {
+    /// Like iproute2, the `-iec` option only take effect when `-human` is
+    /// also specified.
+    pub fn new(human: bool, iec: bool) -> Self {
+        match (human, iec) {
+            (false, _) => Self::Raw,
+            (true, false) => Self::Human,
+            (true, true) => Self::HumanIec,
+        }
+    }
+
+    pub fn is_human(&self) -> bool {
+        *self != Self::Raw
+    }
+
+    // Equal to iproute2 `print_num()`
+    pub fn format(&self, count: u64) -> String {
+        let (base, suffix) = match self {
+            Self::Raw => return count.to_string(),
+            Self::Human => (1000u64, ""),
+            Self::HumanIec => (1024u64, "i"),
+        };
+        if count < base {
+            return count.to_string();
+        }
+
+        let mut pow = 1u64;
+        let mut prefix_index = 0;
+        loop {
+            pow *= base;
+            if count / base < pow || prefix_index == HUMAN_PREFIXES.len() - 1 {
+                break;
+            }
+            prefix_index += 1;
+        }
+
+        // Show less digits after dot when the number is big
+        let mut precision = 2;
+        let mut limit = 1u64;
+        while precision > 0 {
+            limit *= 10;
+            if count / pow < limit {
+                break;
+            }
+            precision -= 1;
+        }
+
+        format!(
+            "{:.precision$}{}{suffix}",
+            count as f64 / pow as f64,
+            HUMAN_PREFIXES[prefix_index],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CliNumberFormat;
+
+    #[test]
+    fn test_number_format_raw() {
+        assert_eq!(CliNumberFormat::Raw.format(123456789), "123456789");
+        assert_eq!(CliNumberFormat::new(false, true).format(2048), "2048");
+    }
+
+    #[test]
+    fn test_number_format_human() {
+        assert_eq!(CliNumberFormat::Human.format(999), "999");
+        assert_eq!(CliNumberFormat::Human.format(1000), "1.00k");
+        assert_eq!(CliNumberFormat::Human.format(13113473), "13.1M");
+        assert_eq!(CliNumberFormat::Human.format(987654321), "988M");
+    }
+
+    #[test]
+    fn test_number_format_human_iec() {
+        assert_eq!(CliNumberFormat::HumanIec.format(1023), "1023");
+        assert_eq!(CliNumberFormat::HumanIec.format(2048), "2.00ki");
+        assert_eq!(CliNumberFormat::HumanIec.format(5 << 30), "5.00Gi");
+    }
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    time::Duration,
+};
+
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reverse resolve IP addresses to hostnames for the `-resolve` option.
+/// The lookups are done in parallel using tokio blocking thread pool, the
+/// addresses failed to resolve within timeout are not included in the
+/// returned HashMap, hence caller should fallback to numeric address.
+pub async fn resolve_hostnames(addrs: &[IpAddr]) -> HashMap<IpAddr, String> {
+    let deadline = tokio::time::Instant::now() + RESOLVE_TIMEOUT;
+
+    let tasks: Vec<_> = addrs
+        .iter()
+        .copied()
+        .collect::<HashSet<IpAddr>>()
+        .into_iter()
+        .map(|addr| {
+            (
+                addr,
+                tokio::task::spawn_blocking(move || {
+                    dns_lookup::lookup_addr(&addr)
+                }),
+            )
+        })
+        .collect();
+
+    let mut ret = HashMap::new();
+    for (addr, task) in tasks {
+        match tokio::time::timeout_at(deadline, task).await {
+            Ok(Ok(Ok(name))) => {
+                ret.insert(addr, name);
+            }
+            Ok(Ok(Err(e))) => {
+                log::debug!("Failed to resolve {addr}: {e}");
+            }
+            Ok(Err(e)) => {
+                log::debug!("Failed to resolve {addr}: {e}");
+            }
+            Err(_) => {
+                log::debug!("Timeout on resolving {addr}");
+            }
+        }
+    }
+    ret
+}
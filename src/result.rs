@@ -2,15 +2,33 @@
 
 use std::io::Write;
 
-use crate::CliError;
+use crate::{CliError, CliTimestamp};
 
 pub trait CanDisplay: serde::Serialize + Sized {
     fn gen_string(&self) -> String;
 
+    /// Single line string with `\` replacing embedded newlines, used by
+    /// `-oneline`.
+    fn gen_oneline_string(&self) -> String {
+        self.gen_string().replace('\n', "\\")
+    }
+
     fn to_json_string(&self) -> String {
         serde_json::to_string(self).expect("Failed to generate JSON string")
     }
 
+    /// Indented JSON used by `-pretty`
+    fn to_pretty_json_string(&self) -> String {
+        let mut buf = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(
+            &mut buf,
+            serde_json::ser::PrettyFormatter::with_indent(b"    "),
+        );
+        self.serialize(&mut serializer)
+            .expect("Failed to generate JSON string");
+        String::from_utf8(buf).expect("Failed to generate JSON string")
+    }
+
     fn to_yaml_string(&self) -> String {
         serde_yaml::to_string(self).expect("Failed to generate JSON string")
     }
@@ -24,6 +42,15 @@ where
         let strings: Vec<String> = self.iter().map(T::gen_string).collect();
         strings.join("\n").to_string()
     }
+
+    fn gen_oneline_string(&self) -> String {
+        let strings: Vec<String> = self
+            .iter()
+            .map(T::gen_oneline_string)
+            .filter(|s| !s.is_empty())
+            .collect();
+        strings.join("\n")
+    }
 }
 
 impl<T> CanDisplay for Vec<T>
@@ -33,6 +60,10 @@ where
     fn gen_string(&self) -> String {
         self.as_slice().gen_string()
     }
+
+    fn gen_oneline_string(&self) -> String {
+        self.as_slice().gen_oneline_string()
+    }
 }
 
 impl CanDisplay for String {
@@ -41,44 +72,106 @@ impl CanDisplay for String {
     }
 }
 
+/// `None` prints nothing in any format, like iproute2 does for commands
+/// changing the configuration, e.g. `ip link add`.
+impl<T> CanDisplay for Option<T>
+where
+    T: CanDisplay,
+{
+    fn gen_string(&self) -> String {
+        self.as_ref().map(T::gen_string).unwrap_or_default()
+    }
+
+    fn gen_oneline_string(&self) -> String {
+        self.as_ref().map(T::gen_oneline_string).unwrap_or_default()
+    }
+
+    fn to_json_string(&self) -> String {
+        self.as_ref().map(T::to_json_string).unwrap_or_default()
+    }
+
+    fn to_pretty_json_string(&self) -> String {
+        self.as_ref()
+            .map(T::to_pretty_json_string)
+            .unwrap_or_default()
+    }
+
+    fn to_yaml_string(&self) -> String {
+        self.as_ref().map(T::to_yaml_string).unwrap_or_default()
+    }
+}
+
 pub trait CanOutput: serde::Serialize + CanDisplay + Sized {
     fn to_cli_string(&self) -> String {
         self.gen_string()
     }
+
+    fn to_oneline_string(&self) -> String {
+        self.gen_oneline_string()
+    }
 }
 
 impl CanOutput for String {}
 
 impl<T> CanOutput for &[T] where T: CanOutput + std::fmt::Display {}
 impl<T> CanOutput for Vec<T> where T: CanOutput + std::fmt::Display {}
+impl<T> CanOutput for Option<T> where T: CanDisplay {}
 
-pub fn print_result_and_exit<T>(result: Result<T, CliError>, fmt: OutputFormat)
+/// Render the result in specified format, the timestamp header is included
+/// for text output when enabled.
+pub fn gen_output_string<T>(result: &T, fmt: OutputFormat) -> String
 where
     T: CanOutput,
 {
+    let output = match fmt {
+        OutputFormat::Cli => result.to_cli_string(),
+        OutputFormat::Oneline => result.to_oneline_string(),
+        OutputFormat::Json => result.to_json_string(),
+        OutputFormat::PrettyJson => result.to_pretty_json_string(),
+        OutputFormat::Yaml => result.to_yaml_string(),
+    };
+    if !output.is_empty()
+        && matches!(fmt, OutputFormat::Cli | OutputFormat::Oneline)
+    {
+        format!("{}{output}", CliTimestamp::header())
+    } else {
+        output
+    }
+}
+
+/// Print rendered output to stdout or error to stderr.
+pub fn print_output(result: &Result<String, CliError>) {
     match result {
-        Ok(s) => {
-            let mut stdout = std::io::stdout();
-            let output = match fmt {
-                OutputFormat::Cli => s.to_cli_string(),
-                OutputFormat::Json => s.to_json_string(),
-                OutputFormat::Yaml => s.to_yaml_string(),
-            };
-            writeln!(stdout, "{output}").ok();
-            std::process::exit(0);
+        Ok(output) => {
+            // iproute2 prints nothing when filters match no entry
+            if !output.is_empty() {
+                writeln!(std::io::stdout(), "{output}").ok();
+            }
         }
         Err(e) => {
-            let mut stderr = std::io::stderr();
-            writeln!(stderr, "{e}").ok();
-            std::process::exit(e.code);
+            writeln!(std::io::stderr(), "{e}").ok();
         }
     }
 }
 
+pub fn print_result_and_exit<T>(result: Result<T, CliError>, fmt: OutputFormat)
+where
+    T: CanOutput,
+{
+    let result = result.map(|s| gen_output_string(&s, fmt));
+    print_output(&result);
+    match result {
+        Ok(_) => std::process::exit(0),
+        Err(e) => std::process::exit(e.code),
+    }
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum OutputFormat {
     #[default]
     Cli,
+    Oneline,
     Yaml,
     Json,
+    PrettyJson,
 }
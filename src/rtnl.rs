@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt::Debug;
+
+use futures_util::stream::StreamExt;
+use rtnetlink::{
+    packet_core::{
+        ErrorMessage, NetlinkDeserializable, NetlinkHeader, NetlinkMessage,
+        NetlinkPayload, NetlinkSerializable,
+    },
+    proto::ConnectionHandle,
+    sys::SocketAddr,
+};
+
+use crate::CliError;
+
+/// Like iproute2, show the kernel errno as `strerror()`
+pub fn nl_error_to_cli(e: &ErrorMessage) -> Option<CliError> {
+    e.code.map(|code| {
+        CliError::from(
+            format!(
+                "RTNETLINK answers: {}",
+                nix::errno::Errno::from_raw(code.get().abs()).desc()
+            )
+            .as_str(),
+        )
+    })
+}
+
+/// Send the netlink request with specified netlink flags over the
+/// connection from `rtnetlink::proto::new_connection()` and collect the
+/// payload of replies. This is for the message types not covered by
+/// `rtnetlink::Handle`, e.g. `RTM_NEWNEXTHOP`, `RTM_GETVLAN` or
+/// `NETLINK_XFRM` messages.
+pub async fn nl_request<M>(
+    handle: &ConnectionHandle<M>,
+    msg: M,
+    flags: u16,
+) -> Result<Vec<M>, CliError>
+where
+    M: NetlinkSerializable + NetlinkDeserializable + Debug,
+{
+    let mut header = NetlinkHeader::default();
+    header.flags = flags;
+    let nl_msg = NetlinkMessage::new(header, NetlinkPayload::InnerMessage(msg));
+
+    let mut ret = Vec::new();
+    let mut replies = handle
+        .request(nl_msg, SocketAddr::new(0, 0))
+        .map_err(|e| CliError::from(format!("{e}").as_str()))?;
+    while let Some(reply) = replies.next().await {
+        match reply.payload {
+            NetlinkPayload::InnerMessage(msg) => ret.push(msg),
+            NetlinkPayload::Error(e) => {
+                if let Some(e) = nl_error_to_cli(&e) {
+                    return Err(e);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(ret)
+}
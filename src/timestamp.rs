@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT
+
+use std::sync::OnceLock;
+
+static TIMESTAMP_MODE: OnceLock<CliTimestamp> = OnceLock::new();
+
+/// Timestamp printed before output requested by `-timestamp` and `-tshort`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CliTimestamp {
+    #[default]
+    Disabled,
+    /// `Timestamp: Sat Oct 17 12:00:00 2026 123456 usec` in dedicate line
+    Full,
+    /// `[2026-10-17T12:00:00.123456] ` as prefix
+    Short,
+}
+
+impl CliTimestamp {
+    pub fn enable(short: bool) {
+        TIMESTAMP_MODE
+            .get_or_init(|| if short { Self::Short } else { Self::Full });
+    }
+
+    fn mode() -> Self {
+        *TIMESTAMP_MODE.get_or_init(|| Self::Disabled)
+    }
+
+    /// Equal to iproute2 `print_timestamp()`, return empty string if not
+    /// enabled.
+    pub fn header() -> String {
+        let now = chrono::Local::now();
+        match Self::mode() {
+            Self::Disabled => String::new(),
+            Self::Full => format!(
+                "Timestamp: {} {} usec\n",
+                now.format("%a %b %e %H:%M:%S %Y"),
+                now.timestamp_subsec_micros()
+            ),
+            Self::Short => format!(
+                "[{}.{:06}] ",
+                now.format("%Y-%m-%dT%H:%M:%S"),
+                now.timestamp_subsec_micros()
+            ),
+        }
+    }
+}